@@ -1,4 +1,13 @@
 //! AC7 Encryption
+//!
+//! Ace Combat 7 encrypts its assets with a xor key stream derived from the asset name.
+//! The stream starts after the magic of the .uasset and continues into the .uexp,
+//! so the key state only depends on the asset name and the offset in the combined data.
+//!
+//! The whole files can be processed with [`decrypt`] and [`encrypt`], [`AC7Stream`]
+//! applies the encryption while reading or writing any `Read`/`Write` implementation.
+
+use std::io::{self, Read, Seek, SeekFrom, Write};
 
 use crate::UE4_ASSET_MAGIC;
 
@@ -40,8 +49,18 @@ impl AC7XorKey {
     /// let (decrypted_data, decrypted_bulk) = ac7::decrypt(&data, &bulk_data, key);
     /// ```
     pub fn new(name: &str) -> Self {
-        let name_key = Self::calc_name_key(name);
-        let offset = 4;
+        Self::with_offset(name, 4)
+    }
+
+    /// Creates a new AC7XorKey for an asset with the specified name, starting at an offset in the key stream
+    ///
+    /// The offset is the position in the .uasset data, .uexp data continues at the length of the .uasset.
+    pub fn with_offset(name: &str, offset: u32) -> Self {
+        Self::from_name_key(Self::calc_name_key(name), offset)
+    }
+
+    /// Creates a new AC7XorKey from a name key, starting at an offset in the key stream
+    pub fn from_name_key(name_key: i32, offset: u32) -> Self {
         let (pk1, pk2) = Self::calc_pkey_from_nkey(name_key as u32, offset);
 
         Self {
@@ -52,6 +71,25 @@ impl AC7XorKey {
         }
     }
 
+    /// Get the name key this key was derived from
+    pub fn get_name_key(&self) -> i32 {
+        self.name_key
+    }
+
+    /// Get the offset in the key stream this key started at
+    pub fn get_offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Encrypt or decrypt data in place, advancing the key
+    ///
+    /// Encryption and decryption are the same operation.
+    pub fn apply(&mut self, data: &mut [u8]) {
+        for byte in data {
+            *byte = self.xor_byte(*byte);
+        }
+    }
+
     /// Process a single byte with this key
     fn xor_byte(&mut self, byte: u8) -> u8 {
         let byte = byte ^ AC7_KEY[(self.pk1 * 1024 + self.pk2) as usize];
@@ -70,8 +108,8 @@ impl AC7XorKey {
         byte as u8
     }
 
-    /// Calculate a name key for a given name
-    fn calc_name_key(name: &str) -> i32 {
+    /// Calculate a name key for a given name, the name is case-insensitive
+    pub fn calc_name_key(name: &str) -> i32 {
         let name = name.to_uppercase();
 
         let mut num = 0i32;
@@ -90,8 +128,8 @@ impl AC7XorKey {
         num
     }
 
-    /// Calculate private key from name key
-    fn calc_pkey_from_nkey(nkey: u32, data_offset: u32) -> (u32, u32) {
+    /// Calculate the private key for an offset in the key stream from a name key
+    pub fn calc_pkey_from_nkey(nkey: u32, data_offset: u32) -> (u32, u32) {
         let mut num = nkey as u128 * 7;
         let big_int = 5440514381186227205u128;
         num += data_offset as u128;
@@ -129,24 +167,18 @@ pub fn decrypt(uasset: &[u8], uexp: &[u8], mut key: AC7XorKey) -> (Vec<u8>, Vec<
 
 /// Decrypt a uasset file using a given key
 pub fn decrypt_uasset(uasset: &[u8], key: &mut AC7XorKey) -> Vec<u8> {
-    let mut decrypted = vec![0u8; uasset.len()];
-    decrypted[..4].copy_from_slice(&u32::to_be_bytes(UE4_ASSET_MAGIC)); // todo: replace with constant
-
-    for i in 4..uasset.len() {
-        decrypted[i] = key.xor_byte(uasset[i]);
-    }
-
+    let mut decrypted = uasset.to_vec();
+    decrypted[..4].copy_from_slice(&u32::to_be_bytes(UE4_ASSET_MAGIC));
+    key.apply(&mut decrypted[4..]);
     decrypted
 }
 
 /// Decrypt a uexp file using a given key
+///
+/// The key needs to be at the end of the uasset, like after [`decrypt_uasset`].
 pub fn decrypt_uexp(uexp: &[u8], key: &mut AC7XorKey) -> Vec<u8> {
-    let mut decrypted = vec![0u8; uexp.len()];
-
-    for i in 0..uexp.len() {
-        decrypted[i] = key.xor_byte(uexp[i]);
-    }
-
+    let mut decrypted = uexp.to_vec();
+    key.apply(&mut decrypted);
     decrypted
 }
 
@@ -158,27 +190,153 @@ pub fn encrypt(uasset: &[u8], uexp: &[u8], mut key: AC7XorKey) -> (Vec<u8>, Vec<
     )
 }
 
-const AC7_ASSET_MAGIC: u32 = 0x37454341;
+/// Magic of encrypted AC7 assets
+pub const AC7_ASSET_MAGIC: u32 = 0x37454341;
 
 /// Encrypt a uasset file using a given key
 pub fn encrypt_uasset(uasset: &[u8], key: &mut AC7XorKey) -> Vec<u8> {
-    let mut encrypted = vec![0u8; uasset.len()];
+    let mut encrypted = uasset.to_vec();
     encrypted[..4].copy_from_slice(&u32::to_le_bytes(AC7_ASSET_MAGIC));
-
-    for i in 4..uasset.len() {
-        encrypted[i] = key.xor_byte(uasset[i]);
-    }
-
+    key.apply(&mut encrypted[4..]);
     encrypted
 }
 
 /// Encrypt a uexp file using a given key
+///
+/// The key needs to be at the end of the uasset, like after [`encrypt_uasset`].
 pub fn encrypt_uexp(uexp: &[u8], key: &mut AC7XorKey) -> Vec<u8> {
-    let mut encrypted = vec![0u8; uexp.len()];
+    let mut encrypted = uexp.to_vec();
+    key.apply(&mut encrypted);
+    encrypted
+}
+
+/// Encrypting/decrypting stream wrapper
+///
+/// Data read from the wrapped stream is decrypted, data written to it is encrypted.
+/// Seeking is supported, the key is derived from the position in the stream.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use unreal_asset::{ac7::AC7Stream, engine_version::EngineVersion, Asset};
+///
+/// let name = "ex02_IGC_03_Subtitle";
+/// let uasset = File::open("ex02_IGC_03_Subtitle.uasset").unwrap();
+/// let uasset_len = uasset.metadata().unwrap().len();
+/// let uexp = File::open("ex02_IGC_03_Subtitle.uexp").unwrap();
+///
+/// let asset = Asset::new(
+///     AC7Stream::uasset(uasset, name),
+///     Some(AC7Stream::uexp(uexp, name, uasset_len)),
+///     EngineVersion::VER_UE4_18,
+///     None,
+/// )
+/// .unwrap();
+/// ```
+#[derive(Debug)]
+pub struct AC7Stream<S> {
+    inner: S,
+    /// Name key of the asset
+    name_key: i32,
+    /// Offset of the start of the stream in the key stream
+    base_offset: u64,
+    /// Does the stream start with the asset magic
+    has_magic: bool,
+    /// Position in the stream
+    position: u64,
+}
 
-    for i in 0..uexp.len() {
-        encrypted[i] = key.xor_byte(uexp[i]);
+impl<S> AC7Stream<S> {
+    /// Wrap a .uasset stream of the asset with the specified name
+    pub fn uasset(inner: S, name: &str) -> Self {
+        Self::new(inner, AC7XorKey::calc_name_key(name), 0, true)
     }
 
-    encrypted
+    /// Wrap a .uexp stream of the asset with the specified name
+    ///
+    /// The key stream continues from the end of the .uasset, so its length is needed.
+    pub fn uexp(inner: S, name: &str, uasset_len: u64) -> Self {
+        Self::new(inner, AC7XorKey::calc_name_key(name), uasset_len, false)
+    }
+
+    /// Wrap a stream starting at `base_offset` in the key stream
+    ///
+    /// If `has_magic` is set, the first 4 bytes are treated as the asset magic,
+    /// which is replaced instead of being encrypted.
+    pub fn new(inner: S, name_key: i32, base_offset: u64, has_magic: bool) -> Self {
+        AC7Stream {
+            inner,
+            name_key,
+            base_offset,
+            has_magic,
+            position: 0,
+        }
+    }
+
+    /// Get a reference to the wrapped stream
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Consumes the `AC7Stream`, returning the wrapped stream
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    /// Encrypt or decrypt data at a position in the stream
+    fn transform(&self, position: u64, data: &mut [u8], magic: [u8; 4]) {
+        let mut start = 0;
+        if self.has_magic {
+            while position + (start as u64) < 4 && start < data.len() {
+                data[start] = magic[position as usize + start];
+                start += 1;
+            }
+        }
+
+        let mut key = AC7XorKey::from_name_key(
+            self.name_key,
+            (self.base_offset + position + start as u64) as u32,
+        );
+        key.apply(&mut data[start..]);
+    }
+}
+
+impl<S: Read> Read for AC7Stream<S> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.transform(
+            self.position,
+            &mut buf[..read],
+            u32::to_be_bytes(UE4_ASSET_MAGIC),
+        );
+        self.position += read as u64;
+        Ok(read)
+    }
+}
+
+impl<S: Write> Write for AC7Stream<S> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut encrypted = buf.to_vec();
+        self.transform(
+            self.position,
+            &mut encrypted,
+            u32::to_le_bytes(AC7_ASSET_MAGIC),
+        );
+        let written = self.inner.write(&encrypted)?;
+        self.position += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+impl<S: Seek> Seek for AC7Stream<S> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.position = self.inner.seek(pos)?;
+        Ok(self.position)
+    }
 }
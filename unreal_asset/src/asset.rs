@@ -1,12 +1,15 @@
 //! Main [`Asset`] type
 
+use std::any::Any;
 use std::fmt::{Debug, Formatter};
-use std::io::{Read, Seek, SeekFrom, Write};
+use std::io::{self, Cursor, Read, Seek, SeekFrom, Write};
 use std::mem::size_of;
+use std::path::Path;
 
-use byteorder::{ReadBytesExt, WriteBytesExt, BE, LE};
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 
-use unreal_asset_base::flags::EObjectFlags;
+use unreal_asset_base::flags::{EObjectFlags, EStripDataFlags};
+use unreal_asset_base::import::{join_object_path, ImportMapTrait};
 use unreal_asset_base::passthrough_archive_reader;
 use unreal_asset_base::types::PackageIndexTrait;
 use unreal_asset_base::{
@@ -19,18 +22,30 @@ use unreal_asset_base::{
     error::Error,
     flags::EPackageFlags,
     object_version::{ObjectVersion, ObjectVersionUE5},
-    reader::{ArchiveReader, ArchiveTrait, ArchiveType, ArchiveWriter, RawReader, RawWriter},
-    types::{fname::FNameContainer, FName, GenerationInfo, PackageIndex},
+    reader::{
+        ArchiveReader, ArchiveTrait, ArchiveType, ArchiveWriter, GameQuirks, ParseOptions,
+        RawReader, RawWriter, SizeCalculatingWriter,
+    },
+    types::{
+        fname::FNameContainer, FName, GenerationInfo, PackageIndex, PackedObjectRef, StripDataFlags,
+    },
     unversioned::Usmap,
+    warning::AssetWarning,
     FNameContainer, Guid, Import,
 };
-use unreal_asset_exports::{BaseExport, Export, ExportBaseTrait, ExportNormalTrait, ExportTrait};
+use unreal_asset_exports::{
+    class_export::SerializedInterfaceReference, BaseExport, Export, ExportBaseTrait,
+    ExportNormalTrait, ExportTrait,
+};
 use unreal_asset_properties::world_tile_property::FWorldTileInfo;
+use unreal_helpers::AtomicFile;
 
 use crate::asset_archive_writer::AssetArchiveWriter;
 use crate::asset_data::{AssetData, AssetTrait, ExportReaderTrait};
+use crate::extras::{self, ExtrasContext};
 use crate::fengineversion::FEngineVersion;
-use crate::UE4_ASSET_MAGIC;
+use crate::package_file_summary::{self, FPackageFileSummary};
+use crate::project::AssetSource;
 
 /// Parent Class Info
 #[derive(FNameContainer, Debug, Clone, Eq, PartialEq)]
@@ -108,8 +123,8 @@ impl UAssetExportMapEntry {
 
         entry.outer_index = PackageIndex::new(archive.read_i32::<LE>()?);
         entry.object_name = archive.read_fname()?;
-        entry.object_flags = EObjectFlags::from_bits(archive.read_u32::<LE>()?)
-            .ok_or_else(|| Error::invalid_file("Invalid property flags".to_string()))?;
+        // keep unknown flags, newer engine versions and custom engines add flags
+        entry.object_flags = EObjectFlags::from_bits_retain(archive.read_u32::<LE>()?);
 
         if archive.get_object_version() < ObjectVersion::VER_UE4_64BIT_EXPORTMAP_SERIALSIZES {
             entry.serial_size = archive.read_i32::<LE>()? as i64;
@@ -149,14 +164,21 @@ impl UAssetExportMapEntry {
             >= ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS
         {
             entry.first_export_dependency_offset = archive.read_i32::<LE>()?;
+            let length = archive.read_i32::<LE>()?;
             entry.serialization_before_serialization_dependencies =
-                Vec::with_capacity(archive.read_i32::<LE>()? as usize);
+                Vec::with_capacity(archive.check_array_len(length)?);
+
+            let length = archive.read_i32::<LE>()?;
             entry.create_before_serialization_dependencies =
-                Vec::with_capacity(archive.read_i32::<LE>()? as usize);
+                Vec::with_capacity(archive.check_array_len(length)?);
+
+            let length = archive.read_i32::<LE>()?;
             entry.serialization_before_create_dependencies =
-                Vec::with_capacity(archive.read_i32::<LE>()? as usize);
+                Vec::with_capacity(archive.check_array_len(length)?);
+
+            let length = archive.read_i32::<LE>()?;
             entry.create_before_create_dependencies =
-                Vec::with_capacity(archive.read_i32::<LE>()? as usize);
+                Vec::with_capacity(archive.check_array_len(length)?);
         }
 
         Ok(entry)
@@ -342,8 +364,210 @@ struct AssetHeader {
     bulk_data_start_offset: i64,
 }
 
+/// Writes the export data of an asset, see [`Asset::write_exports`]
+trait ExportDataWriter {
+    /// Write all exports followed by the asset magic
+    ///
+    /// `base_offset` is the offset of the writer in the combined .uasset and .uexp data.
+    /// Returns the start offsets of all exports and the bulk data start offset.
+    fn write_exports<Writer: ArchiveWriter<PackageIndex>>(
+        &mut self,
+        writer: &mut Writer,
+        base_offset: u64,
+    ) -> Result<(Vec<u64>, i64), Error>;
+}
+
+/// Writes the parsed exports of an asset
+struct ParsedExports<'a, C: Read + Seek>(&'a Asset<C>);
+
+impl<C: Read + Seek> ExportDataWriter for ParsedExports<'_, C> {
+    fn write_exports<Writer: ArchiveWriter<PackageIndex>>(
+        &mut self,
+        writer: &mut Writer,
+        base_offset: u64,
+    ) -> Result<(Vec<u64>, i64), Error> {
+        self.0.write_exports(writer, base_offset)
+    }
+}
+
+/// Writes no export data and returns a known export layout, used to write only the package header,
+/// see [`Asset::write_uexp_in_place`]
+struct ExportLayout {
+    /// Start offsets of all exports
+    category_starts: Vec<u64>,
+    /// Bulk data start offset
+    bulk_data_start_offset: i64,
+}
+
+impl ExportLayout {
+    /// Get the export layout an asset was read with
+    fn read<C: Read + Seek>(asset: &Asset<C>) -> Self {
+        ExportLayout {
+            category_starts: asset
+                .asset_data
+                .exports
+                .iter()
+                .map(|e| e.get_base_export().serial_offset as u64)
+                .collect(),
+            bulk_data_start_offset: asset.bulk_data_start_offset,
+        }
+    }
+}
+
+impl ExportDataWriter for ExportLayout {
+    fn write_exports<Writer: ArchiveWriter<PackageIndex>>(
+        &mut self,
+        _writer: &mut Writer,
+        _base_offset: u64,
+    ) -> Result<(Vec<u64>, i64), Error> {
+        Ok((self.category_starts.clone(), self.bulk_data_start_offset))
+    }
+}
+
+/// Export of an asset being rewritten with [`Asset::rewrite_streaming`]
+pub struct StreamedExport<'a, R: Read + Seek> {
+    /// Index of the export
+    pub index: PackageIndex,
+    /// Export map entry of the export, the export data itself is not parsed
+    pub base_export: &'a BaseExport<PackageIndex>,
+    /// Reader of the source asset
+    reader: &'a mut Chain<R>,
+    /// Offset of the export data in the source asset
+    offset: u64,
+    /// Length of the export data in the source asset
+    len: u64,
+}
+
+impl<R: Read + Seek> StreamedExport<'_, R> {
+    /// Get the length of the serialized export in the source asset, including extras and padding
+    pub fn data_len(&self) -> u64 {
+        self.len
+    }
+
+    /// Get a reader over the serialized export in the source asset
+    pub fn reader(&mut self) -> Result<impl Read + '_, Error> {
+        self.reader.seek(SeekFrom::Start(self.offset))?;
+        Ok((&mut *self.reader).take(self.len))
+    }
+
+    /// Read the serialized export from the source asset
+    pub fn read_data(&mut self) -> Result<Vec<u8>, Error> {
+        // the length comes from the export map, don't trust it for preallocation
+        let mut data = Vec::new();
+        self.reader()?.read_to_end(&mut data)?;
+        Ok(data)
+    }
+}
+
+/// Writes exports copied from a source asset, letting a closure replace them
+struct StreamedExports<'a, R: Read + Seek, F> {
+    /// Reader of the source asset
+    reader: Chain<R>,
+    /// Offsets and lengths of the export data in the source asset
+    data_ranges: Vec<(u64, u64)>,
+    /// Exports without their data
+    exports: &'a [Export<PackageIndex>],
+    /// Closure returning the replaced data of an export
+    replace: F,
+}
+
+impl<R, F> ExportDataWriter for StreamedExports<'_, R, F>
+where
+    R: Read + Seek,
+    F: FnMut(&mut StreamedExport<'_, R>) -> Result<Option<Vec<u8>>, Error>,
+{
+    fn write_exports<Writer: ArchiveWriter<PackageIndex>>(
+        &mut self,
+        writer: &mut Writer,
+        base_offset: u64,
+    ) -> Result<(Vec<u64>, i64), Error> {
+        let mut category_starts = Vec::with_capacity(self.exports.len());
+
+        for (i, (export, &(offset, len))) in self.exports.iter().zip(&self.data_ranges).enumerate()
+        {
+            category_starts.push(writer.position() + base_offset);
+
+            let mut streamed = StreamedExport {
+                index: PackageIndex::from_export(i as i32)?,
+                base_export: export.get_base_export(),
+                reader: &mut self.reader,
+                offset,
+                len,
+            };
+
+            match (self.replace)(&mut streamed)? {
+                Some(data) => writer.write_all(&data)?,
+                None => {
+                    let copied = io::copy(&mut streamed.reader()?, writer)?;
+                    if copied != len {
+                        return Err(Error::invalid_file(format!(
+                            "Export {} ends past the end of the asset",
+                            i + 1
+                        )));
+                    }
+                }
+            }
+        }
+        writer.write_all(&[0xc1, 0x83, 0x2a, 0x9e])?;
+
+        let bulk_data_start_offset = (base_offset + writer.position()) as i64 - 4;
+
+        writer.rewind()?;
+
+        Ok((category_starts, bulk_data_start_offset))
+    }
+}
+
+bitflags::bitflags! {
+    /// Sections removed by [`Asset::strip`]
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct StripSections : u32 {
+        /// Thumbnail table of assets saved in the editor
+        const THUMBNAILS = 0x01;
+        /// Gatherable text data used for localization in the editor
+        const GATHERABLE_TEXT = 0x02;
+        /// Searchable names used by the editor's asset search
+        const SEARCHABLE_NAMES = 0x04;
+        /// Name map entries which aren't referenced anywhere in the asset
+        const UNUSED_NAMES = 0x08;
+    }
+}
+
+/// Inconsistent offset found by [`Asset::audit_bulk_offsets`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BulkOffsetIssue {
+    /// Export doesn't start where the previous export or the package header ends
+    ExportOffset {
+        /// Export
+        export: PackageIndex,
+        /// Serial offset of the export
+        offset: i64,
+        /// End of the previous export or the package header
+        expected: i64,
+    },
+    /// Export doesn't end where the next export or the export data ends
+    ExportSize {
+        /// Export
+        export: PackageIndex,
+        /// Serial size of the export
+        size: i64,
+        /// Distance to the next export or the end of the export data
+        expected: i64,
+    },
+    /// Bulk data start offset doesn't point to the end of the export data
+    BulkDataStartOffset {
+        /// Bulk data start offset
+        offset: i64,
+        /// End of the export data
+        expected: i64,
+    },
+}
+
 //#[derive(Debug)]
 /// Unreal Engine uasset
+///
+/// With the `threading` feature enabled a parsed asset is `Send + Sync` as long as its reader is,
+/// so `&Asset` can be shared across threads for analysis. Mutation requires `&mut Asset`.
 #[derive(FNameContainer)]
 pub struct Asset<C: Read + Seek> {
     /// Raw reader
@@ -381,6 +605,12 @@ pub struct Asset<C: Read + Seek> {
     pub package_source: u32,
     /// Folder name
     pub folder_name: String,
+    /// Localization id, only serialized in assets with editor-only data
+    pub localization_id: Option<String>,
+    /// Persistent guid, only serialized in assets with editor-only data
+    pub persistent_guid: Option<Guid>,
+    /// Owner persistent guid, only serialized in assets with editor-only data from a small range of versions
+    pub owner_persistent_guid: Option<Guid>,
 
     // map struct type override
     // override name map hashes
@@ -449,6 +679,10 @@ pub struct Asset<C: Read + Seek> {
 
     /// Parent class
     parent_class: Option<ParentClassInfo>,
+
+    /// Warnings about problems found while parsing
+    #[container_ignore]
+    pub warnings: Vec<AssetWarning>,
 }
 
 impl<'a, C: Read + Seek> Asset<C> {
@@ -459,6 +693,56 @@ impl<'a, C: Read + Seek> Asset<C> {
         engine_version: EngineVersion,
         mappings: Option<Usmap>,
     ) -> Result<Self, Error> {
+        let mut asset = Self::create(asset_data, bulk_data, engine_version, mappings);
+        asset.parse_data()?;
+        Ok(asset)
+    }
+
+    /// Create an asset from a binary file, enforcing the given parsing limits
+    ///
+    /// This should be used when parsing untrusted files, see [`ParseOptions::hardened`]
+    pub fn new_with_options(
+        asset_data: C,
+        bulk_data: Option<C>,
+        engine_version: EngineVersion,
+        mappings: Option<Usmap>,
+        parse_options: ParseOptions,
+    ) -> Result<Self, Error> {
+        let mut asset = Self::create(asset_data, bulk_data, engine_version, mappings);
+        asset
+            .raw_reader
+            .set_read_ahead_size(parse_options.read_ahead_size);
+        asset.raw_reader.parse_options = parse_options;
+        asset.asset_data.quirks = parse_options.quirks;
+        asset.parse_data()?;
+        Ok(asset)
+    }
+
+    /// Create an asset from a binary file, parsing only the package header
+    ///
+    /// Exports are read as [`BaseExport`]s without their data, so the .uexp of split assets is not needed,
+    /// `bulk_data` only has to be given to tell that the asset uses separate bulk data files.
+    pub(crate) fn new_header_only(
+        asset_data: C,
+        bulk_data: Option<C>,
+        engine_version: EngineVersion,
+    ) -> Result<Self, Error> {
+        let mut asset = Self::create(asset_data, bulk_data, engine_version, None);
+        let export_map = asset.parse_package_header()?;
+        asset.asset_data.exports = export_map
+            .into_iter()
+            .map(|e| Export::BaseExport(e.to_base_export()))
+            .collect();
+        Ok(asset)
+    }
+
+    /// Create an asset without parsing anything from the binary file
+    pub(crate) fn create(
+        asset_data: C,
+        bulk_data: Option<C>,
+        engine_version: EngineVersion,
+        mappings: Option<Usmap>,
+    ) -> Self {
         let use_event_driven_loader = bulk_data.is_some();
 
         let chain = Chain::new(asset_data, bulk_data);
@@ -486,6 +770,9 @@ impl<'a, C: Read + Seek> Asset<C> {
             chunk_ids: Vec::new(),
             package_source: 0,
             folder_name: String::from(""),
+            localization_id: None,
+            persistent_guid: None,
+            owner_persistent_guid: None,
             header_offset: 0,
             name_count: 0,
             name_offset: 0,
@@ -516,11 +803,11 @@ impl<'a, C: Read + Seek> Asset<C> {
             depends_map: None,
             soft_package_reference_list: None,
             parent_class: None,
+            warnings: Vec::new(),
         };
         asset.set_engine_version(engine_version);
         asset.asset_data.mappings = mappings;
-        asset.parse_data()?;
-        Ok(asset)
+        asset
     }
 
     /// Set asset engine version
@@ -531,212 +818,78 @@ impl<'a, C: Read + Seek> Asset<C> {
     }
 
     /// Parse asset header
-    fn parse_header(&mut self) -> Result<(), Error> {
-        // reuseable buffers for reading
-
+    pub(crate) fn parse_header(&mut self) -> Result<(), Error> {
         // seek to start
         self.rewind()?;
 
-        // read and check magic
-        if self.read_u32::<BE>()? != UE4_ASSET_MAGIC {
-            return Err(Error::invalid_file(
-                "File is not a valid uasset file".to_string(),
-            ));
-        }
-
-        // read legacy version
-        self.legacy_file_version = self.read_i32::<LE>()?;
-        if self.legacy_file_version != -4 {
-            // LegacyUE3Version for backwards-compatibility with UE3 games: always 864 in versioned assets, always 0 in unversioned assets
-            self.read_exact(&mut [0u8; 4])?;
-        }
-
-        // read unreal version
-        let file_version = self.read_i32::<LE>()?.try_into()?;
-
-        self.asset_data.summary.unversioned = file_version == ObjectVersion::UNKNOWN;
-
-        if self.asset_data.summary.unversioned {
-            if self.asset_data.object_version == ObjectVersion::UNKNOWN {
-                return Err(Error::invalid_file("Cannot begin serialization of an unversioned asset before an engine version is manually specified".to_string()));
-            }
-        } else {
-            self.asset_data.object_version = file_version;
-        }
-
-        if self.legacy_file_version <= -8 {
-            let object_version_ue5: ObjectVersionUE5 = self.read_i32::<LE>()?.try_into()?;
-            if object_version_ue5 > ObjectVersionUE5::UNKNOWN {
-                self.asset_data.object_version_ue5 = object_version_ue5;
-            }
-        }
+        let old_container = self.asset_data.summary.custom_versions.clone();
+        let summary = FPackageFileSummary::read(self, Some(&old_container))?;
 
-        if self.asset_data.object_version_ue5 == ObjectVersionUE5::UNKNOWN {
-            let mappings_version = self
-                .get_mappings()
-                .map(|e| e.object_version_ue5)
-                .unwrap_or(ObjectVersionUE5::UNKNOWN);
-            if mappings_version > ObjectVersionUE5::UNKNOWN {
-                self.asset_data.object_version_ue5 = mappings_version;
-            }
+        if !summary.compressed_chunks.is_empty() {
+            let data = summary.decompress(self)?;
+            self.raw_reader.replace_asset_data(data);
         }
 
-        // read file license version
-        self.asset_data.summary.file_licensee_version = self.read_i32::<LE>()?;
+        self.legacy_file_version = summary.legacy_file_version;
+        self.asset_data.summary.unversioned = summary.unversioned;
+        self.asset_data.object_version = summary.object_version;
+        self.asset_data.object_version_ue5 = summary.object_version_ue5;
+        self.asset_data.summary.file_licensee_version = summary.file_licensee_version;
+        self.asset_data.summary.licensee_field = summary.licensee_field;
 
-        // read custom versions container
         if self.legacy_file_version <= -2 {
-            // TODO: support for enum-based custom versions
-            let old_container = self.asset_data.summary.custom_versions.clone();
-            self.asset_data.summary.custom_versions = self.read_custom_version_container(
-                self.get_custom_version_serialization_format(),
-                Some(&old_container),
-            )?;
-        }
-
-        // read header offset
-        self.header_offset = self.read_i32::<LE>()?;
-
-        // read folder name
-        self.folder_name = self
-            .read_fstring()?
-            .ok_or_else(|| Error::no_data("folder_name is None".to_string()))?;
-
-        // read package flags
-        self.asset_data.summary.package_flags = EPackageFlags::from_bits(self.read_u32::<LE>()?)
-            .ok_or_else(|| Error::invalid_file("Invalid package flags".to_string()))?;
-
-        // read name count and offset
-        self.name_count = self.read_i32::<LE>()?;
-        self.name_offset = self.read_i32::<LE>()?;
-
-        if self.get_object_version_ue5() >= ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST {
-            self.soft_object_paths_count = self.read_i32::<LE>()?;
-            self.soft_object_paths_offset = self.read_i32::<LE>()?;
-        }
-
-        // read text gatherable data
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_SERIALIZE_TEXT_IN_PACKAGES {
-            self.gatherable_text_data_count = self.read_i32::<LE>()?;
-            self.gatherable_text_data_offset = self.read_i32::<LE>()?;
-        }
-
-        // read count and offset for exports, imports, depends, soft package references, searchable names, thumbnail table
-        self.asset_data.summary.export_count = self.read_i32::<LE>()?;
-        self.export_offset = self.read_i32::<LE>()?;
-        self.asset_data.summary.import_count = self.read_i32::<LE>()?;
-        self.import_offset = self.read_i32::<LE>()?;
-        self.depends_offset = self.read_i32::<LE>()?;
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_ADD_STRING_ASSET_REFERENCES_MAP
-        {
-            self.soft_package_reference_count = self.read_i32::<LE>()?;
-            self.soft_package_reference_offset = self.read_i32::<LE>()?;
-        }
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_ADDED_SEARCHABLE_NAMES {
-            self.searchable_names_offset = self.read_i32::<LE>()?;
-        }
-        self.thumbnail_table_offset = self.read_i32::<LE>()?;
-
-        // read guid
-        self.package_guid = self.raw_reader.read_guid()?;
-
-        // raed generations
-        let generations_count = self.read_i32::<LE>()?;
-        for _ in 0..generations_count {
-            let export_count = self.read_i32::<LE>()?;
-            let name_count = self.read_i32::<LE>()?;
-            self.generations.push(GenerationInfo {
-                export_count,
-                name_count,
-            });
-        }
-
-        // read advanced engine version
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_ENGINE_VERSION_OBJECT {
-            self.engine_version_recorded = FEngineVersion::read(self)?;
-        } else {
-            self.engine_version_recorded =
-                FEngineVersion::new(4, 0, 0, self.read_u32::<LE>()?, None);
-        }
-        if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_PACKAGE_SUMMARY_HAS_COMPATIBLE_ENGINE_VERSION
-        {
-            self.engine_version_compatible = FEngineVersion::read(self)?;
-        } else {
-            self.engine_version_compatible = self.engine_version_recorded.clone();
-        }
-
-        // read compression data
-        self.compression_flags = self.read_u32::<LE>()?;
-        let compression_block_count = self.read_u32::<LE>()?;
-        if compression_block_count > 0 {
-            return Err(Error::invalid_file(
-                "Compression block count is not zero".to_string(),
-            ));
-        }
-
-        self.package_source = self.read_u32::<LE>()?;
-
-        // some other old unsupported stuff
-        let additional_to_cook = self.read_i32::<LE>()?;
-        if additional_to_cook != 0 {
-            return Err(Error::invalid_file(
-                "Additional to cook is not zero".to_string(),
-            ));
-        }
-        if self.legacy_file_version > -7 {
-            let texture_allocations_count = self.read_i32::<LE>()?;
-            if texture_allocations_count != 0 {
-                return Err(Error::invalid_file(
-                    "Texture allocations count is not zero".to_string(),
-                ));
-            }
-        }
-
-        self.asset_registry_data_offset = self.read_i32::<LE>()?;
-        self.bulk_data_start_offset = self.read_i64::<LE>()?;
-
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_WORLD_LEVEL_INFO {
-            self.world_tile_info_offset = self.read_i32::<LE>()?;
-        }
-
-        if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_CHANGED_CHUNKID_TO_BE_AN_ARRAY_OF_CHUNKIDS
-        {
-            let chunk_id_count = self.read_i32::<LE>()?;
-
-            for _ in 0..chunk_id_count {
-                let chunk_id = self.read_i32::<LE>()?;
-                self.chunk_ids.push(chunk_id);
-            }
-        } else if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_ADDED_CHUNKID_TO_ASSETDATA_AND_UPACKAGE
-        {
-            self.chunk_ids = vec![];
-            self.chunk_ids[0] = self.read_i32::<LE>()?;
-        }
-
-        if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS
-        {
-            self.preload_dependency_count = self.read_i32::<LE>()?;
-            self.preload_dependency_offset = self.read_i32::<LE>()?;
-        }
-
-        self.names_referenced_from_export_data_count = match self.get_object_version_ue5()
-            >= ObjectVersionUE5::NAMES_REFERENCED_FROM_EXPORT_DATA
-        {
-            true => self.read_i32::<LE>()?,
-            false => self.name_count,
+            self.asset_data.summary.custom_versions = summary.custom_versions;
+            self.warnings.extend(
+                self.asset_data
+                    .summary
+                    .custom_versions
+                    .iter()
+                    .filter(|e| e.friendly_name.is_none())
+                    .map(|e| AssetWarning::UnknownCustomVersion(e.guid, e.version)),
+            );
+        }
+
+        self.header_offset = summary.header_offset;
+        self.folder_name = summary.folder_name;
+        self.asset_data.summary.package_flags = summary.package_flags;
+        self.name_count = summary.name_count;
+        self.name_offset = summary.name_offset;
+        self.soft_object_paths_count = summary.soft_object_paths_count;
+        self.soft_object_paths_offset = summary.soft_object_paths_offset;
+        self.localization_id = summary.localization_id;
+        self.gatherable_text_data_count = summary.gatherable_text_data_count;
+        self.gatherable_text_data_offset = summary.gatherable_text_data_offset;
+        self.asset_data.summary.export_count = summary.export_count;
+        self.export_offset = summary.export_offset;
+        self.asset_data.summary.import_count = summary.import_count;
+        self.import_offset = summary.import_offset;
+        self.depends_offset = summary.depends_offset;
+        self.soft_package_reference_count = summary.soft_package_reference_count;
+        self.soft_package_reference_offset = summary.soft_package_reference_offset;
+        self.searchable_names_offset = summary.searchable_names_offset;
+        self.thumbnail_table_offset = summary.thumbnail_table_offset;
+        self.package_guid = summary.package_guid;
+        self.persistent_guid = summary.persistent_guid;
+        self.owner_persistent_guid = summary.owner_persistent_guid;
+        self.generations = summary.generations;
+        self.engine_version_recorded = summary.engine_version_recorded;
+        self.engine_version_compatible = summary.engine_version_compatible;
+        // the asset is written uncompressed
+        self.compression_flags = match summary.compressed_chunks.is_empty() {
+            true => summary.compression_flags,
+            false => 0,
         };
-
-        if self.get_object_version_ue5() >= ObjectVersionUE5::PAYLOAD_TOC {
-            self.payload_toc_offset = self.read_i64::<LE>()?;
-        }
-
-        if self.get_object_version_ue5() >= ObjectVersionUE5::DATA_RESOURCES {
-            self.data_resource_offset = self.read_i32::<LE>()?;
-        }
+        self.package_source = summary.package_source;
+        self.asset_registry_data_offset = summary.asset_registry_data_offset;
+        self.bulk_data_start_offset = summary.bulk_data_start_offset;
+        self.world_tile_info_offset = summary.world_tile_info_offset;
+        self.chunk_ids = summary.chunk_ids;
+        self.preload_dependency_count = summary.preload_dependency_count;
+        self.preload_dependency_offset = summary.preload_dependency_offset;
+        self.names_referenced_from_export_data_count =
+            summary.names_referenced_from_export_data_count;
+        self.payload_toc_offset = summary.payload_toc_offset;
+        self.data_resource_offset = summary.data_resource_offset;
 
         Ok(())
     }
@@ -769,7 +922,26 @@ impl<'a, C: Read + Seek> Asset<C> {
     }
 
     /// Add an `FName`
+    ///
+    /// Like the engine, a numeric suffix is split off into the instance number,
+    /// `Foo_3` is added as `Foo` with instance number 4, see [`FName::split_number`].
+    /// Use [`Asset::add_fname_verbatim`] to add a name as is.
     pub fn add_fname(&mut self, slice: &str) -> FName {
+        self.name_map.get_mut().add_fname_split(slice)
+    }
+
+    /// Add an `FName` with an instance number
+    ///
+    /// Like the engine, a numeric suffix is only split off when `number` is 0
+    pub fn add_fname_with_number(&mut self, slice: &str, number: i32) -> FName {
+        match number {
+            0 => self.add_fname(slice),
+            _ => self.name_map.get_mut().add_fname_with_number(slice, number),
+        }
+    }
+
+    /// Add an `FName` without splitting a numeric suffix off, its instance number will be 0
+    pub fn add_fname_verbatim(&mut self, slice: &str) -> FName {
         self.name_map.get_mut().add_fname(slice)
     }
 
@@ -834,106 +1006,686 @@ impl<'a, C: Read + Seek> Asset<C> {
         Some(self.imports[index as usize].clone())
     }
 
-    /// Get an export
-    pub fn get_export(&'a self, index: PackageIndex) -> Option<&'a Export<PackageIndex>> {
-        self.asset_data.get_export(index)
-    }
+    /// Find an import by content or add it if it doesn't exist
+    pub(crate) fn find_or_add_import(
+        &mut self,
+        class_package: &str,
+        class_name: &str,
+        outer_index: PackageIndex,
+        object_name: &str,
+    ) -> PackageIndex {
+        // names are added with their numeric suffix split off, but may have been added verbatim before
+        let matches = |fname: &FName, name: &str| {
+            let (content, number) = FName::split_number(name);
+            fname == name || (fname == content && fname.get_number() == number)
+        };
+        let existing = self.imports.iter().position(|import| {
+            matches(&import.class_package, class_package)
+                && matches(&import.class_name, class_name)
+                && import.outer_index == outer_index
+                && matches(&import.object_name, object_name)
+        });
 
-    /// Get a mutable export reference
-    pub fn get_export_mut(
-        &'a mut self,
-        index: PackageIndex,
-    ) -> Option<&'a mut Export<PackageIndex>> {
-        self.asset_data.get_export_mut(index)
+        match existing {
+            Some(index) => PackageIndex::new(-(index as i32) - 1),
+            None => {
+                let import = Import::new(
+                    self.add_fname(class_package),
+                    self.add_fname(class_name),
+                    outer_index,
+                    self.add_fname(object_name),
+                    false,
+                );
+                self.add_import(import)
+            }
+        }
     }
 
-    /// Get custom version serialization format
-    pub fn get_custom_version_serialization_format(&self) -> ECustomVersionSerializationFormat {
-        if self.legacy_file_version > 3 {
-            return ECustomVersionSerializationFormat::Enums;
-        }
-        if self.legacy_file_version > -6 {
-            return ECustomVersionSerializationFormat::Guids;
+    /// Get the import table index of the package an import is located in
+    fn import_package(&self, import_index: usize) -> Option<usize> {
+        let mut index = import_index;
+        // guard against circular outers in malformed assets
+        for _ in 0..self.imports.len() {
+            let outer_index = self.imports.get(index)?.outer_index;
+            match outer_index.index {
+                0 => return Some(index),
+                outer if outer < 0 => index = (-outer - 1) as usize,
+                _ => return None,
+            }
         }
-        ECustomVersionSerializationFormat::Optimized
+        None
     }
 
-    /// Parse asset data
-    fn parse_data(&mut self) -> Result<(), Error> {
-        self.parse_header()?;
+    /// Get the import table indices of the imports located in a package, excluding the package itself
+    fn package_object_imports(&self, package: usize) -> impl Iterator<Item = usize> + '_ {
+        (0..self.imports.len())
+            .filter(move |&i| i != package && self.import_package(i) == Some(package))
+    }
 
-        self.seek(SeekFrom::Start(self.name_offset as u64))?;
+    /// Get the import table indices of all package imports
+    fn package_imports(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.imports.len()).filter(move |&i| self.imports[i].outer_index.index == 0)
+    }
 
-        for _ in 0..self.name_count {
-            let (name, hash) = self.read_name_map_string(None)?;
-            if hash == 0 {
-                // todo: good FString type
-                self.override_name_map_hashes.insert(name.clone(), 0);
+    /// Pack a reference to an import or export into a [`PackedObjectRef`]
+    ///
+    /// References to exports use package id 0 and the export's [`PackageIndex`] as object path id.
+    /// References to imports use the 1-based position of the import's package among the package imports as package id,
+    /// and the 1-based position of the import among the imports located in that package as object path id.
+    /// The package import itself has object path id 0.
+    pub fn pack_object_ref(&self, index: PackageIndex) -> Result<PackedObjectRef, Error> {
+        if index.index == 0 {
+            return Ok(PackedObjectRef::NULL);
+        }
+
+        if index.is_export() {
+            if self.asset_data.get_export(index).is_none() {
+                return Err(Error::invalid_package_index(format!(
+                    "Export {index} doesn't exist"
+                )));
             }
-            self.add_name_reference(name, true);
+            return PackedObjectRef::pack(0, index.index as u32, 0);
         }
 
-        if self.import_offset > 0 {
-            self.seek(SeekFrom::Start(self.import_offset as u64))?;
-            for _i in 0..self.asset_data.summary.import_count {
-                let class_package = self.read_fname()?;
-                let class_name = self.read_fname()?;
-                let outer_index = PackageIndex::new(self.read_i32::<LE>()?);
-                let object_name = self.read_fname()?;
-                let optional =
-                    match self.get_object_version_ue5() >= ObjectVersionUE5::OPTIONAL_RESOURCES {
-                        true => self.read_i32::<LE>()? == 1,
-                        false => false,
-                    };
+        let import_index = (-index.index - 1) as usize;
+        let package = self.import_package(import_index).ok_or_else(|| {
+            Error::invalid_package_index(format!("Import {index} isn't located in a package"))
+        })?;
 
-                let import = Import::new(
-                    class_package,
-                    class_name,
-                    outer_index,
-                    object_name,
-                    optional,
-                );
-                self.imports.push(import);
+        let package_id = self
+            .package_imports()
+            .position(|e| e == package)
+            .expect("Packages are package imports")
+            + 1;
+        let object_path_id = match import_index == package {
+            true => 0,
+            false => {
+                self.package_object_imports(package)
+                    .position(|e| e == import_index)
+                    .expect("Imports are located in their package")
+                    + 1
             }
-        }
+        };
 
-        let mut export_map = Vec::with_capacity(self.asset_data.summary.export_count as usize);
-        if self.export_offset > 0 {
-            self.seek(SeekFrom::Start(self.export_offset as u64))?;
-            for _i in 0..self.asset_data.summary.export_count {
-                export_map.push(UAssetExportMapEntry::read(self)?);
-            }
+        PackedObjectRef::pack(package_id as u32, object_path_id as u32, 0)
+    }
+
+    /// Unpack a [`PackedObjectRef`] created by [`Asset::pack_object_ref`] into a [`PackageIndex`]
+    pub fn unpack_object_ref(&self, packed: PackedObjectRef) -> Result<PackageIndex, Error> {
+        if packed.is_null() {
+            return Ok(PackageIndex::new(0));
+        }
+        if !packed.is_packed() {
+            return Err(Error::invalid_package_index(format!(
+                "Object reference {:#x} is not packed",
+                packed.encoded
+            )));
         }
 
-        let depends_offset_zero_version_range =
-            ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS
-                ..ObjectVersion::VER_UE4_64BIT_EXPORTMAP_SERIALSIZES;
-        if self.depends_offset > 0
-            || depends_offset_zero_version_range.contains(&self.get_object_version())
-        {
-            let mut depends_map = Vec::with_capacity(self.asset_data.summary.export_count as usize);
+        let object_path_id = packed.object_path_id() as usize;
+        let not_found = || {
+            Error::invalid_package_index(format!(
+                "Packed object reference {:#x} doesn't reference an object of this asset",
+                packed.encoded
+            ))
+        };
 
-            // 4.14-4.15 the depends offset wasnt updated so always serialized as 0
-            if self.depends_offset > 0 {
-                self.seek(SeekFrom::Start(self.depends_offset as u64))?;
+        let import_index = match packed.package_id() {
+            0 => {
+                let index = PackageIndex::new(object_path_id as i32);
+                return match self.asset_data.get_export(index) {
+                    Some(_) => Ok(index),
+                    None => Err(not_found()),
+                };
             }
-
-            for _i in 0..self.asset_data.summary.export_count as usize {
-                let size = self.read_i32::<LE>()?;
-                let mut data: Vec<i32> = Vec::new();
-                for _j in 0..size {
-                    data.push(self.read_i32::<LE>()?);
+            package_id => {
+                let package = self
+                    .package_imports()
+                    .nth(package_id as usize - 1)
+                    .ok_or_else(not_found)?;
+                match object_path_id {
+                    0 => package,
+                    _ => self
+                        .package_object_imports(package)
+                        .nth(object_path_id - 1)
+                        .ok_or_else(not_found)?,
                 }
-                depends_map.push(data);
             }
-            self.depends_map = Some(depends_map);
-        }
-
-        if self.soft_package_reference_offset > 0 {
-            let mut soft_package_reference_list =
-                Vec::with_capacity(self.soft_package_reference_count as usize);
+        };
 
-            self.seek(SeekFrom::Start(self.soft_package_reference_offset as u64))?;
+        Ok(PackageIndex::new(-(import_index as i32) - 1))
+    }
+
+    /// Make a class export implement an interface
+    ///
+    /// `interface_path` is the object path of the interface class, e.g. `/Script/Engine.Interface_AssetUserData`
+    /// for a native interface or `/Game/Interfaces/BPI_Interact.BPI_Interact_C` for a blueprint interface.
+    /// Imports of the interface and its package are added if they don't exist yet.
+    ///
+    /// Only the interface reference is added, functions of the interface have to be implemented separately.
+    /// Returns the import index of the interface class.
+    pub fn add_class_interface(
+        &mut self,
+        class_export: PackageIndex,
+        interface_path: &str,
+    ) -> Result<PackageIndex, Error> {
+        let (package_path, interface_name) = interface_path.split_once('.').ok_or_else(|| {
+            Error::no_data(format!("{interface_path} is not a valid object path"))
+        })?;
+
+        if !matches!(
+            self.asset_data.get_export(class_export),
+            Some(Export::ClassExport(_))
+        ) {
+            return Err(Error::invalid_package_index(format!(
+                "Export {class_export} is not a class export"
+            )));
+        }
+
+        let (class_package, class_name) = match package_path.starts_with("/Script/") {
+            true => ("/Script/CoreUObject", "Class"),
+            false => ("/Script/Engine", "BlueprintGeneratedClass"),
+        };
+
+        let package = self.find_or_add_import(
+            "/Script/CoreUObject",
+            "Package",
+            PackageIndex::new(0),
+            package_path,
+        );
+        let interface = self.find_or_add_import(class_package, class_name, package, interface_name);
+
+        let Some(Export::ClassExport(class)) = self.asset_data.get_export_mut(class_export) else {
+            unreachable!("Export was checked to be a class export");
+        };
+
+        // interfaces added by blueprints are always implemented by k2
+        class.add_interface(SerializedInterfaceReference::new(interface, 0, true));
+
+        let dependencies = &mut class
+            .get_base_export_mut()
+            .create_before_serialization_dependencies;
+        if !dependencies.contains(&interface) {
+            dependencies.push(interface);
+        }
+
+        Ok(interface)
+    }
+
+    /// Get an export
+    pub fn get_export(&'a self, index: PackageIndex) -> Option<&'a Export<PackageIndex>> {
+        self.asset_data.get_export(index)
+    }
+
+    /// Get a mutable export reference
+    pub fn get_export_mut(
+        &'a mut self,
+        index: PackageIndex,
+    ) -> Option<&'a mut Export<PackageIndex>> {
+        self.asset_data.get_export_mut(index)
+    }
+
+    /// Get mutable references to multiple exports at once
+    ///
+    /// Returns `None` if any index is not a valid export index or if an export is requested more than once.
+    pub fn get_many_exports_mut<const N: usize>(
+        &'a mut self,
+        indices: [PackageIndex; N],
+    ) -> Option<[&'a mut Export<PackageIndex>; N]> {
+        self.asset_data.get_many_exports_mut(indices)
+    }
+
+    /// Get the object path of an export relative to its package
+    ///
+    /// For example `Asset_C` for a top level export or `Default__Asset_C:Component` for a subobject.
+    pub fn get_export_object_path(&self, index: PackageIndex) -> Option<String> {
+        let mut names = Vec::new();
+
+        let mut current = index;
+        while let Some(export) = self.asset_data.get_export(current) {
+            // guard against circular outers in malformed assets
+            if names.len() > self.asset_data.exports.len() {
+                return None;
+            }
+
+            let base_export = export.get_base_export();
+            names.push(base_export.object_name.get_owned_content());
+            current = base_export.outer_index;
+        }
+
+        if names.is_empty() {
+            return None;
+        }
+
+        // join as if the objects were inside of an unnamed package and strip the leading separator
+        let path = join_object_path(std::iter::once(String::new()).chain(names.into_iter().rev()));
+        Some(path[1..].to_string())
+    }
+
+    /// Find an export by its full object path, e.g. `/Game/Items/Conveyor.Conveyor_C`
+    ///
+    /// Cooked assets usually don't store their own package path,
+    /// in that case the package path only needs to be present in the name map.
+    /// `.` and `:` separators are treated the same.
+    pub fn find_export_by_object_path(&self, object_path: &str) -> Option<PackageIndex> {
+        let (package_path, object_path) = object_path.split_once('.')?;
+
+        let package_matches = match self.folder_name.starts_with('/') {
+            true => self.folder_name == package_path,
+            false => self.search_name_reference(package_path).is_some(),
+        };
+        if !package_matches {
+            return None;
+        }
+
+        let object_path = object_path.replace(':', ".");
+        (0..self.asset_data.exports.len())
+            .filter_map(|index| PackageIndex::from_export(index as i32).ok())
+            .find(|index| {
+                self.get_export_object_path(*index)
+                    .is_some_and(|path| path.replace(':', ".") == object_path)
+            })
+    }
+
+    /// Get the class inheritance path of an export
+    ///
+    /// The first entry is the export's class, followed by its super classes up to the root class,
+    /// e.g. `["BP_Conveyor_C", "BP_Machine_C", "Actor", "Object"]`.
+    ///
+    /// Classes exported from this asset are followed through their super struct,
+    /// imported classes can only be followed through the asset's mappings.
+    /// Use [`Asset::get_class_hierarchy_with_source`] to also follow classes from other packages.
+    pub fn get_class_hierarchy(&self, export_index: PackageIndex) -> Vec<String> {
+        let mut hierarchy = Vec::new();
+        let Some(export) = self.asset_data.get_export(export_index) else {
+            return hierarchy;
+        };
+
+        let class_index = export.get_base_export().class_index;
+        if let ClassLink::Package { class_name, .. } | ClassLink::Script { class_name } =
+            get_local_class_hierarchy(self, class_index, &mut hierarchy)
+        {
+            self.append_mappings_hierarchy(&class_name, &mut hierarchy);
+        }
+
+        hierarchy
+    }
+
+    /// Get the class inheritance path of an export, loading packages of imported classes from a source
+    ///
+    /// Works like [`Asset::get_class_hierarchy`], but classes imported from packages which exist in `source`
+    /// are followed into those packages. Packages which are not in the source, e.g. `/Script/` packages,
+    /// are followed through the asset's mappings.
+    pub fn get_class_hierarchy_with_source(
+        &self,
+        export_index: PackageIndex,
+        source: &mut impl AssetSource,
+    ) -> Result<Vec<String>, Error> {
+        let mut hierarchy = Vec::new();
+        let Some(export) = self.asset_data.get_export(export_index) else {
+            return Ok(hierarchy);
+        };
+
+        let mut link =
+            get_local_class_hierarchy(self, export.get_base_export().class_index, &mut hierarchy);
+
+        while let ClassLink::Package {
+            package_path,
+            object_path,
+            class_name,
+        } = link
+        {
+            let Some(asset) = self.load_package(&package_path, source)? else {
+                self.append_mappings_hierarchy(&class_name, &mut hierarchy);
+                return Ok(hierarchy);
+            };
+
+            let Some(class_export) = asset.find_export_by_object_path(&object_path) else {
+                self.append_mappings_hierarchy(&class_name, &mut hierarchy);
+                return Ok(hierarchy);
+            };
+
+            // the class itself was already added when its import was visited
+            let super_index = asset
+                .asset_data
+                .get_export(class_export)
+                .map(get_super_class)
+                .unwrap_or_default();
+
+            link = get_local_class_hierarchy(&asset, super_index, &mut hierarchy);
+            if hierarchy.len() > MAX_CLASS_HIERARCHY_DEPTH {
+                return Ok(hierarchy);
+            }
+        }
+
+        if let ClassLink::Script { class_name } = link {
+            self.append_mappings_hierarchy(&class_name, &mut hierarchy);
+        }
+
+        Ok(hierarchy)
+    }
+
+    /// Load a package from an asset source with this asset's engine version and mappings
+    fn load_package(
+        &self,
+        package_path: &str,
+        source: &mut impl AssetSource,
+    ) -> Result<Option<Asset<Cursor<Vec<u8>>>>, Error> {
+        for extension in ["uasset", "umap"] {
+            let Some(data) = source.read_file(&format!("{package_path}.{extension}"))? else {
+                continue;
+            };
+            let bulk = source.read_file(&format!("{package_path}.uexp"))?;
+
+            return Ok(Some(Asset::new(
+                Cursor::new(data),
+                bulk.map(Cursor::new),
+                self.get_engine_version(),
+                self.asset_data.mappings.clone(),
+            )?));
+        }
+
+        Ok(None)
+    }
+
+    /// Parse the extras of an export with the [`ExtrasHandler`] registered for its class
+    ///
+    /// Handlers registered for a super class are used if the export's class has none.
+    /// Returns `None` if the export isn't a normal export or no handler is registered.
+    ///
+    /// [`ExtrasHandler`]: crate::extras::ExtrasHandler
+    pub fn read_extras<T: Any>(&self, export_index: PackageIndex) -> Result<Option<T>, Error> {
+        let Some(normal_export) = self
+            .asset_data
+            .get_export(export_index)
+            .and_then(|e| e.get_normal_export())
+        else {
+            return Ok(None);
+        };
+
+        extras::read_extras(
+            &self.get_class_hierarchy(export_index),
+            &normal_export.extras,
+            |class_name| self.get_extras_context(class_name),
+        )
+    }
+
+    /// Serialize structured extras with the [`ExtrasHandler`] registered for the class of an export
+    /// and store them in the export
+    ///
+    /// Returns `false` and leaves the export unchanged if it isn't a normal export or no handler is registered.
+    ///
+    /// [`ExtrasHandler`]: crate::extras::ExtrasHandler
+    pub fn write_extras<T: Any>(
+        &mut self,
+        export_index: PackageIndex,
+        extras: &T,
+    ) -> Result<bool, Error> {
+        if self
+            .asset_data
+            .get_export(export_index)
+            .and_then(|e| e.get_normal_export())
+            .is_none()
+        {
+            return Ok(false);
+        }
+
+        let Some(data) = extras::write_extras(
+            &self.get_class_hierarchy(export_index),
+            extras,
+            |class_name| self.get_extras_context(class_name),
+        )?
+        else {
+            return Ok(false);
+        };
+
+        if let Some(normal_export) = self
+            .asset_data
+            .get_export_mut(export_index)
+            .and_then(|e| e.get_normal_export_mut())
+        {
+            normal_export.extras = data;
+        }
+        Ok(true)
+    }
+
+    /// Get the context extras handlers are called with
+    fn get_extras_context(&self, class_name: String) -> ExtrasContext {
+        ExtrasContext {
+            class_name,
+            engine_version: self.get_engine_version(),
+            object_version: self.get_object_version(),
+            object_version_ue5: self.get_object_version_ue5(),
+            name_map: self.get_name_map(),
+        }
+    }
+
+    /// Append super classes of a class to a hierarchy using the asset's mappings
+    fn append_mappings_hierarchy(&self, class_name: &str, hierarchy: &mut Vec<String>) {
+        let Some(mappings) = self.asset_data.mappings.as_ref() else {
+            return;
+        };
+
+        let mut current = class_name;
+        while let Some(schema) = mappings.schemas.get_by_key(current) {
+            if schema.super_type.is_empty() || hierarchy.len() > MAX_CLASS_HIERARCHY_DEPTH {
+                break;
+            }
+
+            hierarchy.push(schema.super_type.clone());
+            current = &schema.super_type;
+        }
+    }
+
+    /// Rename the package this asset belongs to
+    ///
+    /// Both full package paths (`/Game/Old/Asset` -> `/Game/New/Renamed`) and
+    /// folders (`/Game/Old` -> `/Game/New`) are supported.
+    ///
+    /// This updates the folder name, every name map entry and soft package reference
+    /// pointing into the old path and, if the asset name itself changed,
+    /// the names of the exports named after the asset (`Asset`, `Asset_C`, `Default__Asset_C`).
+    ///
+    /// Other assets referencing this package need to be updated with [`Asset::fix_package_references`].
+    pub fn rename_package(&mut self, old_path: &str, new_path: &str) {
+        if let Some(folder_name) = rename_package_path(&self.folder_name, old_path, new_path) {
+            self.folder_name = folder_name;
+        }
+
+        self.fix_package_references(old_path, new_path);
+
+        let old_asset_name = get_asset_name(old_path);
+        let new_asset_name = get_asset_name(new_path);
+        if old_asset_name == new_asset_name {
+            return;
+        }
+
+        let mut name_map = self.name_map.get_mut();
+        for index in 0..name_map.get_name_map_index_list().len() as i32 {
+            let renamed = rename_asset_object_name(
+                name_map.get_name_reference(index),
+                old_asset_name,
+                new_asset_name,
+            );
+
+            if let Some(renamed) = renamed {
+                self.override_name_map_hashes
+                    .remove_by_key(name_map.get_name_reference(index));
+                name_map.set_name_reference(index, renamed);
+            }
+        }
+    }
+
+    /// Fix references to a renamed package
+    ///
+    /// Name map entries, soft package references and imports pointing into `old_path`
+    /// are changed to point into `new_path`.
+    ///
+    /// Returns `true` if any reference was changed.
+    pub fn fix_package_references(&mut self, old_path: &str, new_path: &str) -> bool {
+        let mut changed = false;
+
+        {
+            let mut name_map = self.name_map.get_mut();
+            for index in 0..name_map.get_name_map_index_list().len() as i32 {
+                let renamed =
+                    rename_package_path(name_map.get_name_reference(index), old_path, new_path);
+
+                if let Some(renamed) = renamed {
+                    self.override_name_map_hashes
+                        .remove_by_key(name_map.get_name_reference(index));
+                    name_map.set_name_reference(index, renamed);
+                    changed = true;
+                }
+            }
+        }
+
+        if let Some(ref mut soft_package_reference_list) = self.soft_package_reference_list {
+            for reference in soft_package_reference_list.iter_mut() {
+                if let Some(renamed) = rename_package_path(reference, old_path, new_path) {
+                    *reference = renamed;
+                    changed = true;
+                }
+            }
+        }
+
+        let old_asset_name = get_asset_name(old_path);
+        let new_asset_name = get_asset_name(new_path);
+        if old_asset_name == new_asset_name {
+            return changed;
+        }
+
+        // objects imported from the renamed package, e.g. Asset_C and Default__Asset_C
+        for i in 0..self.imports.len() {
+            let outer_index = self.imports[i].outer_index;
+            if !outer_index.is_import() {
+                continue;
+            }
+
+            let outer = &self.imports[(-outer_index.index - 1) as usize];
+            if outer.outer_index.index != 0
+                || outer.object_name.get_content(|name| name != new_path)
+            {
+                continue;
+            }
+
+            let renamed = self.imports[i]
+                .object_name
+                .get_content(|name| rename_asset_object_name(name, old_asset_name, new_asset_name));
+
+            if let Some(renamed) = renamed {
+                let number = self.imports[i].object_name.get_number();
+                self.imports[i].object_name = self
+                    .name_map
+                    .get_mut()
+                    .add_fname_with_number(&renamed, number);
+                changed = true;
+            }
+        }
+
+        changed
+    }
+
+    /// Get custom version serialization format
+    pub fn get_custom_version_serialization_format(&self) -> ECustomVersionSerializationFormat {
+        package_file_summary::get_custom_version_serialization_format(self.legacy_file_version)
+    }
+
+    /// Parse the name map, the header must already be parsed
+    pub(crate) fn parse_name_map(&mut self) -> Result<(), Error> {
+        self.seek(SeekFrom::Start(self.name_offset as u64))?;
+
+        for _ in 0..self.name_count {
+            let (name, hash) = self.read_name_map_string(None)?;
+            // names don't have a hash to preserve when the game doesn't serialize them
+            if hash == 0 && !self.asset_data.quirks.contains(GameQuirks::NO_NAME_HASHES) {
+                // todo: good FString type
+                self.override_name_map_hashes.insert(name.clone(), 0);
+            }
+            self.add_name_reference(name, true);
+        }
+
+        Ok(())
+    }
+
+    /// Parse imports, the name map must already be parsed
+    pub(crate) fn parse_imports(&mut self) -> Result<(), Error> {
+        if self.import_offset > 0 {
+            self.seek(SeekFrom::Start(self.import_offset as u64))?;
+            for _i in 0..self.asset_data.summary.import_count {
+                let class_package = self.read_fname()?;
+                let class_name = self.read_fname()?;
+                let outer_index = PackageIndex::new(self.read_i32::<LE>()?);
+                let object_name = self.read_fname()?;
+                let optional =
+                    match self.get_object_version_ue5() >= ObjectVersionUE5::OPTIONAL_RESOURCES {
+                        true => self.read_i32::<LE>()? == 1,
+                        false => false,
+                    };
+
+                let import = Import::new(
+                    class_package,
+                    class_name,
+                    outer_index,
+                    object_name,
+                    optional,
+                );
+                self.imports.push(import);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parse the export map, the name map must already be parsed
+    pub(crate) fn parse_export_map(&mut self) -> Result<Vec<UAssetExportMapEntry>, Error> {
+        let mut export_map = Vec::with_capacity(self.asset_data.summary.export_count as usize);
+        if self.export_offset > 0 {
+            self.seek(SeekFrom::Start(self.export_offset as u64))?;
+            for _i in 0..self.asset_data.summary.export_count {
+                export_map.push(UAssetExportMapEntry::read(self)?);
+            }
+        }
+
+        Ok(export_map)
+    }
+
+    /// Parse everything but the export data, returns the export map
+    fn parse_package_header(&mut self) -> Result<Vec<UAssetExportMapEntry>, Error> {
+        self.parse_header()?;
+        self.parse_name_map()?;
+        self.parse_imports()?;
+        let mut export_map = self.parse_export_map()?;
+
+        let depends_offset_zero_version_range =
+            ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS
+                ..ObjectVersion::VER_UE4_64BIT_EXPORTMAP_SERIALSIZES;
+        if self.depends_offset > 0
+            || depends_offset_zero_version_range.contains(&self.get_object_version())
+        {
+            let mut depends_map = Vec::with_capacity(self.asset_data.summary.export_count as usize);
+
+            // 4.14-4.15 the depends offset wasnt updated so always serialized as 0
+            if self.depends_offset > 0 {
+                self.seek(SeekFrom::Start(self.depends_offset as u64))?;
+            }
+
+            for _i in 0..self.asset_data.summary.export_count as usize {
+                let size = self.read_i32::<LE>()?;
+                let mut data: Vec<i32> = Vec::new();
+                for _j in 0..size {
+                    data.push(self.read_i32::<LE>()?);
+                }
+                depends_map.push(data);
+            }
+            self.depends_map = Some(depends_map);
+        }
+
+        if self.soft_package_reference_offset > 0 {
+            let mut soft_package_reference_list =
+                Vec::with_capacity(self.check_array_len(self.soft_package_reference_count)?);
+
+            self.seek(SeekFrom::Start(self.soft_package_reference_offset as u64))?;
 
             for _i in 0..self.soft_package_reference_count as usize {
                 if let Some(reference) = self.read_fstring()? {
@@ -972,209 +1724,752 @@ impl<'a, C: Read + Seek> Asset<C> {
             self.seek(SeekFrom::Start(self.preload_dependency_offset as u64))?;
         }
 
-        if self.header_offset > 0 && !export_map.is_empty() {
-            let map_len = export_map.len();
-            self.asset_data.exports.reserve(map_len);
+        Ok(export_map)
+    }
+
+    /// Parse asset data
+    fn parse_data(&mut self) -> Result<(), Error> {
+        let export_map = self.parse_package_header()?;
+
+        if self.header_offset > 0 && !export_map.is_empty() {
+            let map_len = export_map.len();
+            self.asset_data.exports.reserve(map_len);
+
+            let serial_offsets = export_map
+                .iter()
+                .map(|e| e.serial_offset as u64)
+                .collect::<Vec<_>>();
+
+            for (i, entry) in export_map.into_iter().enumerate() {
+                let base_export = entry.to_base_export();
+
+                let next_starting = match i < (map_len - 1) {
+                    true => serial_offsets[i + 1],
+                    false => self.data_length()? - 4,
+                };
+
+                let export = self.read_export(base_export, next_starting)?;
+                self.asset_data.exports.push(export);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Get the package file summary this asset is written with
+    fn get_package_file_summary(&self, asset_header: &AssetHeader) -> FPackageFileSummary {
+        let name_count = self.name_map.get_ref().get_name_map_index_list().len() as i32;
+        let export_count = self.asset_data.exports.len() as i32;
+
+        FPackageFileSummary {
+            legacy_file_version: self.legacy_file_version,
+            legacy_ue3_version: match self.asset_data.summary.unversioned {
+                true => 0,
+                false => 864,
+            },
+            unversioned: self.asset_data.summary.unversioned,
+            object_version: self.asset_data.object_version,
+            object_version_ue5: self.get_object_version_ue5(),
+            file_licensee_version: self.asset_data.summary.file_licensee_version,
+            licensee_field: match self
+                .asset_data
+                .quirks
+                .contains(GameQuirks::SUMMARY_LICENSEE_FIELD)
+            {
+                true => Some(self.asset_data.summary.licensee_field.unwrap_or_default()),
+                false => None,
+            },
+            custom_versions: self.asset_data.summary.custom_versions.clone(),
+            header_offset: asset_header.header_offset,
+            folder_name: self.folder_name.clone(),
+            package_flags: self.asset_data.summary.package_flags,
+            name_count,
+            name_offset: asset_header.name_offset,
+            soft_object_paths_count: self.soft_object_paths_count,
+            soft_object_paths_offset: self.soft_object_paths_offset,
+            localization_id: self.localization_id.clone(),
+            gatherable_text_data_count: self.gatherable_text_data_count,
+            gatherable_text_data_offset: self.gatherable_text_data_offset,
+            export_count,
+            export_offset: asset_header.export_offset,
+            import_count: self.imports.len() as i32,
+            import_offset: asset_header.import_offset,
+            depends_offset: asset_header.depends_offset,
+            soft_package_reference_count: self.soft_package_reference_count,
+            soft_package_reference_offset: asset_header.soft_package_reference_offset,
+            searchable_names_offset: self.searchable_names_offset,
+            thumbnail_table_offset: self.thumbnail_table_offset,
+            package_guid: self.package_guid,
+            persistent_guid: self.persistent_guid,
+            owner_persistent_guid: self.owner_persistent_guid,
+            generations: self
+                .generations
+                .iter()
+                .map(|_| GenerationInfo::new(export_count, name_count))
+                .collect(),
+            engine_version_recorded: self.engine_version_recorded.clone(),
+            engine_version_compatible: self.engine_version_compatible.clone(),
+            compression_flags: self.compression_flags,
+            compressed_chunks: Vec::new(),
+            package_source: self.package_source,
+            asset_registry_data_offset: asset_header.asset_registry_data_offset,
+            bulk_data_start_offset: asset_header.bulk_data_start_offset,
+            world_tile_info_offset: asset_header.world_tile_info_offset,
+            chunk_ids: self.chunk_ids.clone(),
+            preload_dependency_count: asset_header.preload_dependency_count,
+            preload_dependency_offset: asset_header.preload_dependency_offset,
+            names_referenced_from_export_data_count: self.names_referenced_from_export_data_count,
+            payload_toc_offset: self.payload_toc_offset,
+            data_resource_offset: self.data_resource_offset,
+        }
+    }
+
+    /// Write asset header
+    fn write_header<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        cursor: &mut Writer,
+        asset_header: &AssetHeader,
+    ) -> Result<(), Error> {
+        self.get_package_file_summary(asset_header).write(cursor)
+    }
+
+    /// Rebuild the FName map
+    /// This can be used if it's too complicated to keep track of all FNames that were added into the asset
+    /// This is useful when copying export from one asset into another
+    /// This will automatically figure out every new FName and add them to the name map
+    pub fn rebuild_name_map(&mut self) {
+        let mut current_name_map = self.name_map.clone();
+        self.traverse_fnames(&mut |mut name| {
+            let content = name.get_owned_content();
+            let FName::Backed {
+                index, name_map, ..
+            } = &mut name
+            else {
+                return;
+            };
+
+            if *name_map != current_name_map {
+                let new_index = current_name_map
+                    .get_mut()
+                    .add_name_reference(content, false);
+
+                *index = new_index;
+                *name_map = current_name_map.clone();
+            }
+        });
+    }
+
+    /// Check if export data is written to a separate `.uexp` file
+    pub fn use_separate_bulk_data_files(&self) -> bool {
+        self.asset_data.use_event_driven_loader
+    }
+
+    /// Set if export data should be written to a separate `.uexp` file
+    ///
+    /// Export data is moved between the `.uasset` and the `.uexp` file on the next [`Asset::write_data`] call,
+    /// which must then be called with a `uexp_cursor` if and only if `value` is true.
+    /// Export serial offsets and `bulk_data_start_offset` are recalculated for the new layout.
+    ///
+    /// Preload dependencies are only written to split assets, they are kept in the exports
+    /// but not written while the asset uses a combined layout.
+    pub fn set_use_separate_bulk_data_files(&mut self, value: bool) {
+        self.asset_data.use_event_driven_loader = value;
+        self.raw_reader.use_event_driven_loader = value;
+    }
+
+    /// Set if properties should be written unversioned
+    ///
+    /// Properties are written in the new format on the next [`Asset::write_data`] call.
+    /// Unversioned properties are written using the asset's mappings, which have to contain
+    /// all classes and structs of its properties.
+    pub fn set_unversioned_properties(&mut self, value: bool) -> Result<(), Error> {
+        if value && self.asset_data.mappings.is_none() {
+            return Err(Error::no_data(
+                "Unversioned properties can't be written without mappings".to_string(),
+            ));
+        }
+
+        let package_flags = self.asset_data.summary.package_flags;
+        self.asset_data
+            .summary
+            .package_flags
+            .set(EPackageFlags::PKG_UNVERSIONED_PROPERTIES, value);
+
+        // tagged properties add their names to the name map while they are written
+        if !value {
+            self.back_dummy_names();
+            if let Err(e) = self.add_write_names() {
+                self.asset_data.summary.package_flags = package_flags;
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Add the names of all dummy `FName`s to the name map and replace them with backed names
+    ///
+    /// Names of unversioned properties are taken from the mappings and aren't in the name map,
+    /// but tagged properties serialize them. Array entry names are never serialized and are kept as is.
+    fn back_dummy_names(&mut self) {
+        let mut name_map = self.name_map.clone();
+        self.traverse_fnames(&mut |name| {
+            if let FName::Dummy { value, number } = name {
+                if *number != i32::MIN {
+                    *name = name_map.get_mut().add_fname_with_number(value, *number);
+                }
+            }
+        });
+    }
+
+    /// Write the asset once to add names which are only added to the name map while writing,
+    /// like property types and the `None` terminator of tagged properties
+    ///
+    /// Names added while writing are added after the name map was written, so they have to be added beforehand.
+    fn add_write_names(&self) -> Result<(), Error> {
+        let mut cursor = Cursor::new(Vec::new());
+        let mut bulk_cursor = match self.use_separate_bulk_data_files() {
+            true => Some(Cursor::new(Vec::new())),
+            false => None,
+        };
+        self.write_data(&mut cursor, bulk_cursor.as_mut())
+    }
+
+    /// Strip editor-only sections and unused names to make the asset smaller
+    ///
+    /// Thumbnails, gatherable text data and searchable names aren't parsed, stripping them
+    /// removes their offsets from the header so it doesn't point to data which isn't written.
+    ///
+    /// Unused names are found by looking at all parsed `FName`s, so this fails for assets with raw exports.
+    /// Extras of exports are expected not to contain names.
+    pub fn strip(&mut self, sections: StripSections) -> Result<(), Error> {
+        if sections.contains(StripSections::UNUSED_NAMES) {
+            self.remove_unused_names()?;
+        }
+
+        if sections.contains(StripSections::THUMBNAILS) {
+            self.thumbnail_table_offset = 0;
+        }
+        if sections.contains(StripSections::GATHERABLE_TEXT) {
+            self.gatherable_text_data_count = 0;
+            self.gatherable_text_data_offset = 0;
+        }
+        if sections.contains(StripSections::SEARCHABLE_NAMES) {
+            self.searchable_names_offset = 0;
+        }
+
+        Ok(())
+    }
+
+    /// Remove all names which aren't referenced by the asset from the name map
+    ///
+    /// The remaining names keep their order, names added while writing are added again at the end.
+    fn remove_unused_names(&mut self) -> Result<(), Error> {
+        if self
+            .asset_data
+            .exports
+            .iter()
+            .any(|e| matches!(e, Export::RawExport(_)))
+        {
+            return Err(Error::no_data(
+                "Unused names can't be found in assets with raw exports".to_string(),
+            ));
+        }
+
+        let old_name_map = self.name_map.clone();
+        let mut used = vec![false; old_name_map.get_ref().get_name_map_index_list().len()];
+        self.traverse_fnames(&mut |name| {
+            if let FName::Backed {
+                index, name_map, ..
+            } = name
+            {
+                if *name_map == old_name_map {
+                    if let Some(used) = used.get_mut(*index as usize) {
+                        *used = true;
+                    }
+                }
+            }
+        });
+
+        let mut name_map = NameMap::new();
+        let mut new_indices = Vec::with_capacity(used.len());
+        let mut names_referenced_from_export_data_count = 0;
+        for (i, name) in old_name_map
+            .get_ref()
+            .get_name_map_index_list()
+            .iter()
+            .enumerate()
+        {
+            new_indices.push(match used[i] {
+                true => {
+                    if (i as i32) < self.names_referenced_from_export_data_count {
+                        names_referenced_from_export_data_count += 1;
+                    }
+                    Some(name_map.get_mut().add_name_reference(name.clone(), true))
+                }
+                false => None,
+            });
+        }
+
+        self.traverse_fnames(&mut |name| {
+            if let FName::Backed {
+                index,
+                name_map: fname_map,
+                ..
+            } = name
+            {
+                if *fname_map == old_name_map {
+                    if let Some(Some(new_index)) = new_indices.get(*index as usize) {
+                        *index = *new_index;
+                        *fname_map = name_map.clone();
+                    }
+                }
+            }
+        });
+
+        self.name_map = name_map.clone();
+        self.raw_reader.name_map = name_map;
+        self.names_referenced_from_export_data_count = names_referenced_from_export_data_count;
+
+        self.add_write_names()
+    }
+
+    /// Get the packages this package references softly, `None` if the asset has no soft package reference list
+    pub fn get_soft_package_references(&self) -> Option<&[String]> {
+        self.soft_package_reference_list.as_deref()
+    }
+
+    /// Get the ids of the streaming install chunks this package is assigned to
+    pub fn get_chunk_ids(&self) -> &[i32] {
+        &self.chunk_ids
+    }
+
+    /// Set the ids of the streaming install chunks this package is assigned to
+    ///
+    /// Paks of a chunk are usually named `pakchunk<id>-<platform>.pak`.
+    /// Engine versions before [`ObjectVersion::VER_UE4_CHANGED_CHUNKID_TO_BE_AN_ARRAY_OF_CHUNKIDS`]
+    /// only store the first chunk id.
+    pub fn set_chunk_ids(&mut self, chunk_ids: Vec<i32>) {
+        self.chunk_ids = chunk_ids;
+    }
+
+    /// Assign this package to a streaming install chunk, does nothing if it's already assigned to it
+    pub fn add_chunk_id(&mut self, chunk_id: i32) {
+        if !self.chunk_ids.contains(&chunk_id) {
+            self.chunk_ids.push(chunk_id);
+        }
+    }
+
+    /// Derive the package guid from the package name
+    ///
+    /// The same package name always results in the same guid.
+    pub fn generate_package_guid(&mut self, package_name: &str) {
+        self.package_guid = Guid::new_v5(&PACKAGE_GUID_NAMESPACE, package_name);
+    }
+
+    /// Check if this asset contains editor-only data
+    ///
+    /// Cooked assets have their editor-only data stripped, which is marked by [`EPackageFlags::PKG_FILTER_EDITOR_ONLY`].
+    /// The summary of assets with editor-only data contains additional fields,
+    /// e.g. [`Asset::localization_id`], which are only written if this returns `true`.
+    pub fn has_editor_only_data(&self) -> bool {
+        self.asset_data.summary.package_flags.has_editor_only_data()
+    }
+
+    /// Get the strip flags objects in this asset are expected to be serialized with
+    ///
+    /// Editor data is stripped from assets without editor-only data.
+    pub fn get_strip_data_flags(&self) -> StripDataFlags {
+        match self.has_editor_only_data() {
+            true => StripDataFlags::default(),
+            false => StripDataFlags::new(EStripDataFlags::EDITOR, 0),
+        }
+    }
+
+    /// Write all exports followed by the asset magic
+    ///
+    /// `base_offset` is the offset of the writer in the combined .uasset and .uexp data.
+    /// Returns the start offsets of all exports and the bulk data start offset.
+    fn write_exports<Writer: ArchiveWriter<PackageIndex>>(
+        &self,
+        writer: &mut Writer,
+        base_offset: u64,
+    ) -> Result<(Vec<u64>, i64), Error> {
+        let mut category_starts = Vec::with_capacity(self.asset_data.exports.len());
 
-            let serial_offsets = export_map
-                .iter()
-                .map(|e| e.serial_offset as u64)
-                .collect::<Vec<_>>();
+        for export in &self.asset_data.exports {
+            category_starts.push(writer.position() + base_offset);
 
-            for (i, entry) in export_map.into_iter().enumerate() {
-                let base_export = entry.to_base_export();
+            export.write(writer)?;
 
-                let next_starting = match i < (map_len - 1) {
-                    true => serial_offsets[i + 1],
-                    false => self.data_length()? - 4,
-                };
+            if let Some(normal_export) = export.get_normal_export() {
+                writer.write_all(&normal_export.extras)?;
 
-                let export = self.read_export(base_export, next_starting)?;
-                self.asset_data.exports.push(export);
+                let padding_len = normal_export.get_padding_len(writer.position() + base_offset);
+                writer.write_all(&vec![0u8; padding_len as usize])?;
             }
         }
+        writer.write_all(&[0xc1, 0x83, 0x2a, 0x9e])?;
 
-        Ok(())
+        let bulk_data_start_offset = (base_offset + writer.position()) as i64 - 4;
+
+        writer.rewind()?;
+
+        Ok((category_starts, bulk_data_start_offset))
     }
 
-    /// Write asset header
-    fn write_header<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+    /// Write asset data
+    pub fn write_data<W: Read + Seek + Write>(
         &self,
-        cursor: &mut Writer,
-        asset_header: &AssetHeader,
+        cursor: &mut W,
+        uexp_cursor: Option<&mut W>,
     ) -> Result<(), Error> {
-        cursor.write_u32::<BE>(UE4_ASSET_MAGIC)?;
-        cursor.write_i32::<LE>(self.legacy_file_version)?;
-
-        if self.legacy_file_version != 4 {
-            match self.asset_data.summary.unversioned {
-                true => cursor.write_i32::<LE>(0)?,
-                false => cursor.write_i32::<LE>(864)?,
-            };
-        }
-
-        match self.asset_data.summary.unversioned {
-            true => cursor.write_i32::<LE>(0)?,
-            false => cursor.write_i32::<LE>(self.asset_data.object_version as i32)?,
-        };
+        self.write_data_split(cursor, uexp_cursor)
+    }
 
-        if self.legacy_file_version <= -8 {
-            match self.asset_data.summary.unversioned {
-                true => cursor.write_i32::<LE>(0)?,
-                false => cursor.write_i32::<LE>(self.get_object_version_ue5() as i32)?,
-            };
+    /// Patch the header of a file previously written from this asset in place
+    ///
+    /// Only writes header fields which don't change the layout of the file: package flags, guids,
+    /// versions, engine versions, chunk ids, the folder name and the localization id.
+    /// Offsets, counts and custom versions are kept as they are in the file,
+    /// the name map, imports and exports of the file are not touched.
+    ///
+    /// This is much cheaper than writing the whole asset, but returns an error and leaves the file untouched
+    /// if the header would change size, if the name, import or export counts of this asset don't match the file,
+    /// or if flags changing how exports are serialized differ.
+    pub fn patch_header_in_place<F: Read + Seek + Write>(&self, file: &mut F) -> Result<(), Error> {
+        file.rewind()?;
+        let mut reader = RawReader::<PackageIndex, _>::new(
+            Chain::new(&mut *file, None),
+            self.asset_data.object_version,
+            self.asset_data.object_version_ue5,
+            self.asset_data.use_event_driven_loader,
+            NameMap::new(),
+        );
+        reader.parse_options.quirks = self.asset_data.quirks;
+        let file_summary = FPackageFileSummary::read(&mut reader, None)?;
+        let header_size = reader.position();
+        drop(reader);
+
+        let counts = [
+            (
+                "Name count",
+                file_summary.name_count,
+                self.name_map.get_ref().get_name_map_index_list().len(),
+            ),
+            (
+                "Import count",
+                file_summary.import_count,
+                self.imports.len(),
+            ),
+            (
+                "Export count",
+                file_summary.export_count,
+                self.asset_data.exports.len(),
+            ),
+        ];
+        for (what, file_count, count) in counts {
+            if file_count as usize != count {
+                return Err(Error::header_layout_changed(format!(
+                    "{what} is {count}, but {file_count} in the file"
+                )));
+            }
         }
 
-        cursor.write_i32::<LE>(self.asset_data.summary.file_licensee_version)?;
-        if self.legacy_file_version <= -2 {
-            match self.asset_data.summary.unversioned {
-                true => cursor.write_i32::<LE>(0)?,
-                false => {
-                    cursor.write_i32::<LE>(self.asset_data.summary.custom_versions.len() as i32)?;
-                    for custom_version in &self.asset_data.summary.custom_versions {
-                        cursor.write_guid(&custom_version.guid)?;
-                        cursor.write_i32::<LE>(custom_version.version)?;
-                    }
-                }
-            };
+        let layout_flags =
+            EPackageFlags::PKG_UNVERSIONED_PROPERTIES | EPackageFlags::PKG_FILTER_EDITOR_ONLY;
+        let package_flags = self.asset_data.summary.package_flags;
+        if package_flags & layout_flags != file_summary.package_flags & layout_flags {
+            return Err(Error::header_layout_changed(format!(
+                "{:?} changed",
+                (package_flags ^ file_summary.package_flags) & layout_flags
+            )));
         }
 
-        cursor.write_i32::<LE>(asset_header.header_offset)?;
-        cursor.write_fstring(Some(&self.folder_name))?;
-        cursor.write_u32::<LE>(self.asset_data.summary.package_flags.bits())?;
-        cursor.write_i32::<LE>(self.name_map.get_ref().get_name_map_index_list().len() as i32)?;
-        cursor.write_i32::<LE>(asset_header.name_offset)?;
+        let licensee_field = match self
+            .asset_data
+            .quirks
+            .contains(GameQuirks::SUMMARY_LICENSEE_FIELD)
+        {
+            true => Some(self.asset_data.summary.licensee_field.unwrap_or_default()),
+            false => None,
+        };
 
-        if self.get_object_version_ue5() >= ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST {
-            cursor.write_i32::<LE>(self.soft_object_paths_count)?;
-            cursor.write_i32::<LE>(self.soft_object_paths_offset)?;
-        }
+        let summary = FPackageFileSummary {
+            object_version: self.asset_data.object_version,
+            object_version_ue5: self.get_object_version_ue5(),
+            file_licensee_version: self.asset_data.summary.file_licensee_version,
+            licensee_field,
+            folder_name: self.folder_name.clone(),
+            package_flags,
+            localization_id: self.localization_id.clone(),
+            package_guid: self.package_guid,
+            persistent_guid: self.persistent_guid,
+            owner_persistent_guid: self.owner_persistent_guid,
+            engine_version_recorded: self.engine_version_recorded.clone(),
+            engine_version_compatible: self.engine_version_compatible.clone(),
+            // compressed packages are decompressed when parsed, keep the flags of the compressed chunks
+            compression_flags: match file_summary.compressed_chunks.is_empty() {
+                true => self.compression_flags,
+                false => file_summary.compression_flags,
+            },
+            package_source: self.package_source,
+            chunk_ids: self.chunk_ids.clone(),
+            ..file_summary
+        };
 
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_SERIALIZE_TEXT_IN_PACKAGES {
-            cursor.write_i32::<LE>(self.gatherable_text_data_count)?;
-            cursor.write_i32::<LE>(self.gatherable_text_data_offset)?;
+        let data = summary.to_bytes()?;
+        if data.len() as u64 != header_size {
+            return Err(Error::header_layout_changed(format!(
+                "header size changed from {header_size} to {} bytes",
+                data.len()
+            )));
         }
 
-        cursor.write_i32::<LE>(self.asset_data.exports.len() as i32)?;
-        cursor.write_i32::<LE>(asset_header.export_offset)?;
-        cursor.write_i32::<LE>(self.imports.len() as i32)?;
-        cursor.write_i32::<LE>(asset_header.import_offset)?;
-        cursor.write_i32::<LE>(asset_header.depends_offset)?;
+        file.rewind()?;
+        file.write_all(&data)?;
+        Ok(())
+    }
 
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_ADD_STRING_ASSET_REFERENCES_MAP
-        {
-            cursor.write_i32::<LE>(self.soft_package_reference_count)?;
-            cursor.write_i32::<LE>(asset_header.soft_package_reference_offset)?;
+    /// Write the export data to a new .uexp and patch the export offsets and sizes into the existing .uasset
+    ///
+    /// This only works if nothing but export data changed, `uasset_file` has to be the .uasset this asset was read from.
+    /// The export layout is calculated without writing anything first, then the .uasset is checked by writing
+    /// the header with the export layout the asset was read with. If that doesn't give the same file,
+    /// e.g. because names or imports were added, or if writing the exports would add names,
+    /// an error is returned, neither file is written and the asset is left untouched.
+    ///
+    /// Otherwise the exports are written to `uexp_cursor` and only the bytes of the .uasset which changed,
+    /// the export offsets and sizes and the bulk data start offset, are written to `uasset_file`.
+    pub fn write_uexp_in_place<F: Read + Seek + Write, B: Seek + Write>(
+        &self,
+        uasset_file: &mut F,
+        uexp_cursor: &mut B,
+    ) -> Result<(), Error> {
+        if !self.use_separate_bulk_data_files() {
+            return Err(Error::no_data(
+                "Only assets with separate bulk data files have a .uexp".to_string(),
+            ));
         }
 
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_ADDED_SEARCHABLE_NAMES {
-            cursor.write_i32::<LE>(self.searchable_names_offset)?;
+        // names added while writing exports would change the name map,
+        // write to a copy of it so they are found without adding them to this asset
+        let name_map = self.name_map.clone_resource();
+        let (category_starts, bulk_data_start_offset) =
+            self.write_uexp(&mut SizeCalculatingWriter::new(), name_map.clone())?;
+        let name_count = self.name_map.get_ref().get_name_map_index_list().len();
+        let write_name_count = name_map.get_ref().get_name_map_index_list().len();
+        if write_name_count != name_count {
+            return Err(Error::header_layout_changed(format!(
+                "writing the exports adds {} names, the whole asset has to be written",
+                write_name_count - name_count
+            )));
         }
 
-        cursor.write_i32::<LE>(self.thumbnail_table_offset)?;
-        cursor.write_guid(&self.package_guid)?;
-        cursor.write_i32::<LE>(self.generations.len() as i32)?;
+        let mut original = Vec::new();
+        uasset_file.rewind()?;
+        uasset_file.read_to_end(&mut original)?;
 
-        for _ in 0..self.generations.len() {
-            cursor.write_i32::<LE>(self.asset_data.exports.len() as i32)?;
-            cursor
-                .write_i32::<LE>(self.name_map.get_ref().get_name_map_index_list().len() as i32)?;
+        let mut unchanged = Cursor::new(Vec::with_capacity(original.len()));
+        self.write_data_with(
+            &mut unchanged,
+            Some(&mut SizeCalculatingWriter::new()),
+            &mut ExportLayout::read(self),
+        )?;
+        if unchanged.into_inner() != original {
+            return Err(Error::header_layout_changed(
+                "package header changed, the whole asset has to be written".to_string(),
+            ));
         }
 
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_ENGINE_VERSION_OBJECT {
-            self.engine_version_recorded.write(cursor)?;
-        } else {
-            cursor.write_u32::<LE>(self.engine_version_recorded.build)?;
-        }
+        // offsets and sizes are written with a fixed width, so the patched header has the same size
+        let mut patched = Cursor::new(Vec::with_capacity(original.len()));
+        self.write_data_with(
+            &mut patched,
+            Some(&mut SizeCalculatingWriter::new()),
+            &mut ExportLayout {
+                category_starts,
+                bulk_data_start_offset,
+            },
+        )?;
+        let patched = patched.into_inner();
 
-        if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_PACKAGE_SUMMARY_HAS_COMPATIBLE_ENGINE_VERSION
-        {
-            self.engine_version_recorded.write(cursor)?;
-        }
+        self.write_uexp(uexp_cursor, self.name_map.clone())?;
 
-        cursor.write_u32::<LE>(self.compression_flags)?;
-        cursor.write_i32::<LE>(0)?; // numCompressedChunks
-        cursor.write_u32::<LE>(self.package_source)?;
-        cursor.write_i32::<LE>(0)?; // numAdditionalPackagesToCook
+        let mut i = 0;
+        while i < patched.len() {
+            if patched[i] == original[i] {
+                i += 1;
+                continue;
+            }
 
-        if self.legacy_file_version > -7 {
-            cursor.write_i32::<LE>(0)?; // numTextureallocations
+            let start = i;
+            while i < patched.len() && patched[i] != original[i] {
+                i += 1;
+            }
+            uasset_file.seek(SeekFrom::Start(start as u64))?;
+            uasset_file.write_all(&patched[start..i])?;
         }
+        Ok(())
+    }
 
-        cursor.write_i32::<LE>(asset_header.asset_registry_data_offset)?;
-        cursor.write_i64::<LE>(asset_header.bulk_data_start_offset)?;
+    /// Write the exports of an asset with separate bulk data files to a .uexp
+    ///
+    /// Names added while writing are added to `name_map`.
+    /// Returns the start offsets of all exports and the bulk data start offset.
+    fn write_uexp<W: Seek + Write>(
+        &self,
+        cursor: &mut W,
+        name_map: SharedResource<NameMap>,
+    ) -> Result<(Vec<u64>, i64), Error> {
+        let mut raw_writer = RawWriter::<PackageIndex, _>::new(
+            cursor,
+            self.asset_data.object_version,
+            self.asset_data.object_version_ue5,
+            self.asset_data.use_event_driven_loader,
+            name_map.clone(),
+        );
+        let mut writer =
+            AssetArchiveWriter::new(&mut raw_writer, &self.asset_data, &self.imports, name_map);
+        self.write_exports(&mut writer, self.header_offset as u64)
+    }
 
-        if self.asset_data.object_version >= ObjectVersion::VER_UE4_WORLD_LEVEL_INFO {
-            cursor.write_i32::<LE>(asset_header.world_tile_info_offset)?;
-        }
+    /// Check the export offsets and sizes and the bulk data start offset read from the asset
+    ///
+    /// Exports are expected to be stored back to back in export map order, starting at the end of the package header,
+    /// and the bulk data start offset is expected to point to the end of the export data,
+    /// before the trailing package tag. This is how the engine and [`Asset::write_data`] lay out assets.
+    /// An empty list means the offsets are consistent with the asset data.
+    pub fn audit_bulk_offsets(&mut self) -> Result<Vec<BulkOffsetIssue>, Error> {
+        let data_end = self.data_length()? as i64 - 4;
+        let layout = self.get_export_layout();
+
+        let mut issues = Vec::new();
+        let mut previous_end = self.header_offset as i64;
+        for (i, &(offset, size)) in layout.iter().enumerate() {
+            let export = PackageIndex::from_export(i as i32)?;
+            if offset != previous_end {
+                issues.push(BulkOffsetIssue::ExportOffset {
+                    export,
+                    offset,
+                    expected: previous_end,
+                });
+            }
 
-        if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_CHANGED_CHUNKID_TO_BE_AN_ARRAY_OF_CHUNKIDS
-        {
-            cursor.write_i32::<LE>(self.chunk_ids.len() as i32)?;
-            for chunk_id in &self.chunk_ids {
-                cursor.write_i32::<LE>(*chunk_id)?;
+            let next_offset = layout.get(i + 1).map_or(data_end, |&(offset, _)| offset);
+            if size != next_offset - offset {
+                issues.push(BulkOffsetIssue::ExportSize {
+                    export,
+                    size,
+                    expected: next_offset - offset,
+                });
             }
-        } else if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_ADDED_CHUNKID_TO_ASSETDATA_AND_UPACKAGE
-        {
-            cursor.write_i32::<LE>(self.chunk_ids[0])?;
+            previous_end = offset + size;
         }
 
-        if self.asset_data.object_version
-            >= ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS
-        {
-            cursor.write_i32::<LE>(asset_header.preload_dependency_count)?;
-            cursor.write_i32::<LE>(asset_header.preload_dependency_offset)?;
+        if self.bulk_data_start_offset != data_end {
+            issues.push(BulkOffsetIssue::BulkDataStartOffset {
+                offset: self.bulk_data_start_offset,
+                expected: data_end,
+            });
         }
 
-        if self.get_object_version_ue5() >= ObjectVersionUE5::NAMES_REFERENCED_FROM_EXPORT_DATA {
-            cursor.write_i32::<LE>(self.names_referenced_from_export_data_count)?;
+        Ok(issues)
+    }
+
+    /// Repair the offsets reported by [`Asset::audit_bulk_offsets`] and read the exports again
+    ///
+    /// Offsets are recomputed from the export sizes if those add up to the size of the export data,
+    /// otherwise sizes are recomputed from the offsets if those are ordered and start at the end of the package header.
+    /// Returns an error if neither can be trusted.
+    ///
+    /// Exports are read again from the asset data with the repaired offsets, changes made to them are lost.
+    /// [`Asset::write_data`] always writes recomputed offsets, so writing the repaired asset gives a consistent file.
+    pub fn repair_bulk_offsets(&mut self) -> Result<(), Error> {
+        let data_end = self.data_length()? as i64 - 4;
+        let layout = self.get_export_layout();
+
+        let header_offset = self.header_offset as i64;
+        let starts_at_header = match layout.first() {
+            Some(&(offset, _)) => offset == header_offset,
+            None => true,
+        };
+        let offsets_valid = starts_at_header
+            && layout.windows(2).all(|e| e[0].0 <= e[1].0)
+            && layout.iter().all(|&(offset, _)| offset <= data_end);
+        let sizes_valid = layout.iter().all(|&(_, size)| size >= 0)
+            && header_offset + layout.iter().map(|&(_, size)| size).sum::<i64>() == data_end;
+
+        let mut repaired = Vec::with_capacity(layout.len());
+        if sizes_valid {
+            let mut offset = header_offset;
+            for &(_, size) in &layout {
+                repaired.push((offset, size));
+                offset += size;
+            }
+        } else if offsets_valid {
+            for (i, &(offset, _)) in layout.iter().enumerate() {
+                let next_offset = layout.get(i + 1).map_or(data_end, |&(offset, _)| offset);
+                repaired.push((offset, next_offset - offset));
+            }
+        } else {
+            return Err(Error::invalid_file(
+                "Export offsets and sizes are both inconsistent with the asset data".to_string(),
+            ));
         }
 
-        if self.get_object_version_ue5() >= ObjectVersionUE5::PAYLOAD_TOC {
-            cursor.write_i64::<LE>(self.payload_toc_offset)?;
+        self.bulk_data_start_offset = data_end;
+        if repaired == layout {
+            return Ok(());
         }
 
-        if self.get_object_version_ue5() >= ObjectVersionUE5::DATA_RESOURCES {
-            cursor.write_i32::<LE>(self.data_resource_offset)?;
+        let old_exports = std::mem::take(&mut self.asset_data.exports);
+        for (i, &(offset, size)) in repaired.iter().enumerate() {
+            let mut base_export = old_exports[i].get_base_export().clone();
+            base_export.serial_offset = offset;
+            base_export.serial_size = size;
+
+            match self.read_export(base_export, (offset + size) as u64) {
+                Ok(export) => self.asset_data.exports.push(export),
+                Err(e) => {
+                    self.asset_data.exports = old_exports;
+                    return Err(e);
+                }
+            }
         }
 
         Ok(())
     }
 
-    /// Rebuild the FName map
-    /// This can be used if it's too complicated to keep track of all FNames that were added into the asset
-    /// This is useful when copying export from one asset into another
-    /// This will automatically figure out every new FName and add them to the name map
-    pub fn rebuild_name_map(&mut self) {
-        let mut current_name_map = self.name_map.clone();
-        self.traverse_fnames(&mut |mut name| {
-            let content = name.get_owned_content();
-            let FName::Backed {
-                index, name_map, ..
-            } = &mut name
-            else {
-                return;
-            };
-
-            if *name_map != current_name_map {
-                let new_index = current_name_map
-                    .get_mut()
-                    .add_name_reference(content, false);
+    /// Get the serial offsets and sizes of all exports
+    fn get_export_layout(&self) -> Vec<(i64, i64)> {
+        self.asset_data
+            .exports
+            .iter()
+            .map(|e| {
+                let base_export = e.get_base_export();
+                (base_export.serial_offset, base_export.serial_size)
+            })
+            .collect()
+    }
 
-                *index = new_index;
-                *name_map = current_name_map.clone();
-            }
-        });
+    /// Write asset data, the .uasset and .uexp can be written to different writer types
+    ///
+    /// E.g. the .uasset can be written to a `File` while the .uexp is written to a `Vec`.
+    pub fn write_data_split<W: Read + Seek + Write, B: Read + Seek + Write>(
+        &self,
+        cursor: &mut W,
+        uexp_cursor: Option<&mut B>,
+    ) -> Result<(), Error> {
+        self.write_data_with(cursor, uexp_cursor, &mut ParsedExports(self))
     }
 
-    /// Write asset data
-    pub fn write_data<W: Read + Seek + Write>(
+    /// Write asset data, with the export data written by `exports`
+    fn write_data_with<W: Read + Seek + Write, B: Seek + Write>(
         &self,
         cursor: &mut W,
-        uexp_cursor: Option<&mut W>,
+        uexp_cursor: Option<&mut B>,
+        exports: &mut impl ExportDataWriter,
     ) -> Result<(), Error> {
         if self.asset_data.use_event_driven_loader != uexp_cursor.is_some() {
             return Err(Error::no_data(format!(
@@ -1226,7 +2521,9 @@ impl<'a, C: Read + Seek> Asset<C> {
             // todo: case preserving FString
             serializer.write_fstring(Some(name))?;
 
-            if self.asset_data.object_version >= ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED {
+            if self.asset_data.object_version >= ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED
+                && !self.asset_data.quirks.contains(GameQuirks::NO_NAME_HASHES)
+            {
                 match self.override_name_map_hashes.get_by_key(name) {
                     Some(e) => serializer.write_u32::<LE>(*e)?,
                     None => serializer.write_u32::<LE>(crc::generate_hash(name))?,
@@ -1355,56 +2652,27 @@ impl<'a, C: Read + Seek> Asset<C> {
             false => 0,
         };
 
-        let mut category_starts = Vec::with_capacity(self.asset_data.exports.len());
-
         let final_cursor_pos = serializer.position();
 
-        let mut raw_bulk_serializer = match self.asset_data.use_event_driven_loader {
-            true => Some(RawWriter::new(
-                uexp_cursor.unwrap(),
-                self.asset_data.object_version,
-                self.asset_data.object_version_ue5,
-                self.asset_data.use_event_driven_loader,
-                self.name_map.clone(),
-            )),
-            false => None,
-        };
-
-        let mut bulk_serializer = match self.asset_data.use_event_driven_loader {
-            true => Some(AssetArchiveWriter::new(
-                raw_bulk_serializer.as_mut().unwrap(),
-                &self.asset_data,
-                &self.imports,
-                self.name_map.clone(),
-            )),
-            false => None,
-        };
-
-        let bulk_serializer = match self.asset_data.use_event_driven_loader {
-            true => bulk_serializer.as_mut().unwrap(),
-            false => &mut serializer,
-        };
-
-        for export in &self.asset_data.exports {
-            category_starts.push(match self.asset_data.use_event_driven_loader {
-                true => bulk_serializer.position() + final_cursor_pos,
-                false => bulk_serializer.position(),
-            });
-
-            export.write(bulk_serializer)?;
-
-            if let Some(normal_export) = export.get_normal_export() {
-                bulk_serializer.write_all(&normal_export.extras)?;
+        let (category_starts, bulk_data_start_offset) = match uexp_cursor {
+            Some(uexp_cursor) => {
+                let mut raw_bulk_serializer = RawWriter::new(
+                    uexp_cursor,
+                    self.asset_data.object_version,
+                    self.asset_data.object_version_ue5,
+                    self.asset_data.use_event_driven_loader,
+                    self.name_map.clone(),
+                );
+                let mut bulk_serializer = AssetArchiveWriter::new(
+                    &mut raw_bulk_serializer,
+                    &self.asset_data,
+                    &self.imports,
+                    self.name_map.clone(),
+                );
+                exports.write_exports(&mut bulk_serializer, final_cursor_pos)?
             }
-        }
-        bulk_serializer.write_all(&[0xc1, 0x83, 0x2a, 0x9e])?;
-
-        let bulk_data_start_offset = match self.asset_data.use_event_driven_loader {
-            true => final_cursor_pos as i64 + bulk_serializer.position() as i64,
-            false => bulk_serializer.position() as i64,
-        } - 4;
-
-        bulk_serializer.rewind()?;
+            None => exports.write_exports(&mut serializer, 0)?,
+        };
 
         if !self.asset_data.exports.is_empty() {
             serializer.seek(SeekFrom::Start(export_offset as u64))?;
@@ -1455,6 +2723,166 @@ impl<'a, C: Read + Seek> Asset<C> {
 
         Ok(())
     }
+
+    /// Save the asset to a .uasset or .umap file
+    ///
+    /// If the asset uses separate bulk data files, the .uexp is written next to it.
+    /// Both files are serialized before anything is written and are then written to temporary files,
+    /// which are renamed over the destination, so a failed save doesn't leave truncated files behind.
+    /// .ubulk files are not part of the asset and are left untouched.
+    pub fn save_to_files<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        let path = path.as_ref();
+
+        let mut data = Cursor::new(Vec::new());
+        let mut bulk = match self.use_separate_bulk_data_files() {
+            true => Some(Cursor::new(Vec::new())),
+            false => None,
+        };
+        self.write_data(&mut data, bulk.as_mut())?;
+
+        let mut files = vec![(path.to_path_buf(), data.into_inner())];
+        if let Some(bulk) = bulk {
+            files.push((path.with_extension("uexp"), bulk.into_inner()));
+        }
+
+        // temporary files are removed when dropped, so a failed write leaves none of them behind
+        let mut temp_files = Vec::with_capacity(files.len());
+        for (path, data) in &files {
+            let mut file = AtomicFile::create(path)?;
+            file.write_all(data)?;
+            temp_files.push(file);
+        }
+
+        for file in temp_files {
+            file.commit()?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Asset<Cursor<Vec<u8>>> {
+    /// Create a new empty asset
+    ///
+    /// The asset has no names, imports or exports and is set up to be written
+    /// as a versioned cooked package for the given engine version.
+    /// It gets a random package guid, use [`Asset::generate_package_guid`] for a reproducible one.
+    /// If `use_event_driven_loader` is true, export data is written to a separate `.uexp` file.
+    pub fn new_empty(
+        engine_version: EngineVersion,
+        use_event_driven_loader: bool,
+        mappings: Option<Usmap>,
+    ) -> Self {
+        let bulk_data = match use_event_driven_loader {
+            true => Some(Cursor::new(Vec::new())),
+            false => None,
+        };
+
+        let mut asset = Self::create(Cursor::new(Vec::new()), bulk_data, engine_version, mappings);
+
+        asset.legacy_file_version =
+            match asset.asset_data.object_version_ue5 > ObjectVersionUE5::UNKNOWN {
+                true => -8,
+                false => -7,
+            };
+        asset.folder_name = String::from("None");
+        asset.package_guid = Guid::new_v4();
+        asset.asset_data.summary.package_flags = EPackageFlags::PKG_FILTER_EDITOR_ONLY;
+        asset.generations = vec![GenerationInfo::new(0, 0)];
+        asset.depends_map = Some(Vec::new());
+
+        asset
+    }
+
+    /// Copy an asset while replacing the data of selected exports, without holding the whole asset in memory
+    ///
+    /// Only the package header is parsed, export data is read from `input` and `input_bulk` one export at a time.
+    /// `replace` is called for every export in order and returns the new serialized data of the export,
+    /// including its extras, or `None` to copy the export as it is.
+    /// The export map and header are written with the new export sizes.
+    ///
+    /// `output_bulk` must be given if the asset has a .uexp file.
+    pub fn rewrite_streaming<R, W, B, F>(
+        mut input: R,
+        input_bulk: Option<R>,
+        engine_version: EngineVersion,
+        output: &mut W,
+        output_bulk: Option<&mut B>,
+        replace: F,
+    ) -> Result<(), Error>
+    where
+        R: Read + Seek,
+        W: Read + Seek + Write,
+        B: Read + Seek + Write,
+        F: FnMut(&mut StreamedExport<'_, R>) -> Result<Option<Vec<u8>>, Error>,
+    {
+        let header_offset = {
+            let mut summary_asset = Asset::create(&mut input, None, engine_version, None);
+            summary_asset.parse_header()?;
+            summary_asset.header_offset
+        };
+
+        // the package header is everything before the first export
+        input.rewind()?;
+        let mut header = Vec::new();
+        match header_offset > 0 {
+            true => (&mut input)
+                .take(header_offset as u64)
+                .read_to_end(&mut header)?,
+            false => input.read_to_end(&mut header)?,
+        };
+
+        let mut asset = Self::create(
+            Cursor::new(header),
+            input_bulk.as_ref().map(|_| Cursor::new(Vec::new())),
+            engine_version,
+            None,
+        );
+        let export_map = asset.parse_package_header()?;
+
+        let mut reader = Chain::new(input, input_bulk);
+        let data_end = reader.seek(SeekFrom::End(0))?.saturating_sub(4);
+
+        let serial_offsets = export_map
+            .iter()
+            .map(|e| e.serial_offset as u64)
+            .collect::<Vec<_>>();
+        let mut data_ranges = Vec::with_capacity(export_map.len());
+        for (i, entry) in export_map.into_iter().enumerate() {
+            let offset = serial_offsets[i];
+            let next_starting = serial_offsets.get(i + 1).copied().unwrap_or(data_end);
+            let len = next_starting.checked_sub(offset).ok_or_else(|| {
+                Error::invalid_file(format!("Export {} overlaps the next export", i + 1))
+            })?;
+
+            data_ranges.push((offset, len));
+            asset
+                .asset_data
+                .exports
+                .push(Export::BaseExport(entry.to_base_export()));
+        }
+
+        asset.write_data_with(
+            output,
+            output_bulk,
+            &mut StreamedExports {
+                reader,
+                data_ranges,
+                exports: &asset.asset_data.exports,
+                replace,
+            },
+        )
+    }
+}
+
+impl<C: Read + Seek> ImportMapTrait for Asset<C> {
+    fn get_import_ref(&self, index: PackageIndex) -> Option<&Import> {
+        self.imports.get_import_ref(index)
+    }
+
+    fn import_count(&self) -> usize {
+        self.imports.len()
+    }
 }
 
 impl<C: Read + Seek> AssetTrait<PackageIndex> for Asset<C> {
@@ -1509,6 +2937,18 @@ impl<C: Read + Seek> ArchiveTrait<PackageIndex> for Asset<C> {
         self.asset_data.use_event_driven_loader
     }
 
+    fn get_quirks(&self) -> GameQuirks {
+        self.asset_data.quirks
+    }
+
+    fn get_parse_options(&self) -> ParseOptions {
+        self.raw_reader.get_parse_options()
+    }
+
+    fn zero_mask_defaults(&self) -> bool {
+        self.asset_data.zero_mask_defaults
+    }
+
     fn position(&mut self) -> u64 {
         self.raw_reader.position()
     }
@@ -1603,6 +3043,9 @@ impl<C: Read + Seek> Debug for Asset<C> {
             .field("asset_data", &self.asset_data)
             .field("package_source", &self.package_source)
             .field("folder_name", &self.folder_name)
+            .field("localization_id", &self.localization_id)
+            .field("persistent_guid", &self.persistent_guid)
+            .field("owner_persistent_guid", &self.owner_persistent_guid)
             // map struct type override
             // override name map hashes
             .field("header_offset", &self.header_offset)
@@ -1638,6 +3081,134 @@ impl<C: Read + Seek> Debug for Asset<C> {
             .field("world_tile_info_data_offset", &self.world_tile_info_offset)
             .field("preload_dependency_count", &self.preload_dependency_count)
             .field("preload_dependency_offset", &self.preload_dependency_offset)
+            .field("warnings", &self.warnings)
             .finish()
     }
 }
+
+/// Namespace of package guids generated from package names
+const PACKAGE_GUID_NAMESPACE: Guid =
+    Guid::from_ints(0x8a3c21f4, 0x5d0e4b7a, 0x9c61e2d8, 0x1f47b053);
+
+/// Maximum depth of a class hierarchy, guards against circular super classes in malformed assets
+const MAX_CLASS_HIERARCHY_DEPTH: usize = 256;
+
+/// Where a class hierarchy continues after leaving an asset
+enum ClassLink {
+    /// Hierarchy reached the root class
+    Done,
+    /// Class imported from a `/Script/` package
+    Script {
+        /// Class name
+        class_name: String,
+    },
+    /// Class imported from another package
+    Package {
+        /// Package path
+        package_path: String,
+        /// Full object path of the class
+        object_path: String,
+        /// Class name
+        class_name: String,
+    },
+}
+
+/// Get the super class of a class or struct export
+fn get_super_class(export: &Export<PackageIndex>) -> PackageIndex {
+    match export {
+        Export::ClassExport(class_export) => class_export.struct_export.super_struct,
+        Export::FunctionExport(function_export) => function_export.struct_export.super_struct,
+        Export::StructExport(struct_export) => struct_export.super_struct,
+        Export::UserDefinedStructExport(struct_export) => struct_export.struct_export.super_struct,
+        export => export.get_base_export().super_index,
+    }
+}
+
+/// Add classes of a hierarchy exported from an asset, returns where the hierarchy continues
+fn get_local_class_hierarchy<C: Read + Seek>(
+    asset: &Asset<C>,
+    mut class_index: PackageIndex,
+    hierarchy: &mut Vec<String>,
+) -> ClassLink {
+    while hierarchy.len() <= MAX_CLASS_HIERARCHY_DEPTH {
+        if let Some(export) = asset.asset_data.get_export(class_index) {
+            hierarchy.push(export.get_base_export().object_name.get_owned_content());
+            class_index = get_super_class(export);
+            continue;
+        }
+
+        let Some(import) = asset.get_import_ref(class_index) else {
+            return ClassLink::Done;
+        };
+
+        let class_name = import.object_name.get_owned_content();
+        hierarchy.push(class_name.clone());
+
+        let object_path = import.resolved_path(asset);
+        return match object_path.split_once('.') {
+            Some((package_path, _)) if !package_path.starts_with("/Script/") => {
+                ClassLink::Package {
+                    package_path: package_path.to_string(),
+                    object_path,
+                    class_name,
+                }
+            }
+            _ => ClassLink::Script { class_name },
+        };
+    }
+
+    ClassLink::Done
+}
+
+/// Get the asset name of a package path, e.g. `Asset` for `/Game/Folder/Asset`
+fn get_asset_name(path: &str) -> &str {
+    path.rsplit('/').next().unwrap_or(path)
+}
+
+/// Rename an object named after an asset, e.g. `Asset`, `Asset_C` or `Default__Asset_C`
+fn rename_asset_object_name(
+    name: &str,
+    old_asset_name: &str,
+    new_asset_name: &str,
+) -> Option<String> {
+    let (prefix, name) = match name.strip_prefix("Default__") {
+        Some(name) => ("Default__", name),
+        None => ("", name),
+    };
+
+    match name.strip_prefix(old_asset_name)? {
+        suffix @ ("" | "_C") => Some(format!("{prefix}{new_asset_name}{suffix}")),
+        _ => None,
+    }
+}
+
+/// Rename a path pointing into `old_path`
+///
+/// This handles the package path itself, paths inside of it if it's a folder
+/// and object paths like `/Game/Folder/Asset.Asset_C`
+fn rename_package_path(path: &str, old_path: &str, new_path: &str) -> Option<String> {
+    let rest = path.strip_prefix(old_path)?;
+
+    if rest.is_empty() {
+        return Some(new_path.to_string());
+    }
+
+    if rest.starts_with('/') {
+        return Some(format!("{new_path}{rest}"));
+    }
+
+    let object_path = rest.strip_prefix('.')?;
+    let (object_name, sub_object) = match object_path.find(':') {
+        Some(index) => object_path.split_at(index),
+        None => (object_path, ""),
+    };
+
+    let object_name = rename_asset_object_name(
+        object_name,
+        get_asset_name(old_path),
+        get_asset_name(new_path),
+    )
+    .unwrap_or_else(|| object_name.to_string());
+
+    Some(format!("{new_path}.{object_name}{sub_object}"))
+}
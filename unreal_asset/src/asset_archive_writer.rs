@@ -7,10 +7,9 @@ use unreal_asset_base::{
     containers::{IndexedMap, NameMap, SharedResource},
     custom_version::{CustomVersion, CustomVersionTrait},
     engine_version::EngineVersion,
-    flags::EPackageFlags,
     object_version::{ObjectVersion, ObjectVersionUE5},
     passthrough_archive_writer,
-    reader::{ArchiveTrait, ArchiveType, ArchiveWriter},
+    reader::{ArchiveTrait, ArchiveType, ArchiveWriter, GameQuirks},
     types::{FName, PackageIndex, PackageIndexTrait},
     unversioned::Usmap,
     Error, Import,
@@ -83,13 +82,21 @@ impl<'parent_writer, 'asset, ParentWriter: ArchiveWriter<PackageIndex>> ArchiveT
         self.asset_data
             .summary
             .package_flags
-            .contains(EPackageFlags::PKG_UNVERSIONED_PROPERTIES)
+            .uses_unversioned_properties()
     }
 
     fn use_event_driven_loader(&self) -> bool {
         self.asset_data.use_event_driven_loader
     }
 
+    fn get_quirks(&self) -> GameQuirks {
+        self.asset_data.quirks
+    }
+
+    fn zero_mask_defaults(&self) -> bool {
+        self.asset_data.zero_mask_defaults
+    }
+
     fn position(&mut self) -> u64 {
         self.writer.position()
     }
@@ -8,19 +8,18 @@ use unreal_asset_base::{
     containers::{indexed_map::IndexedMap, name_map::NameMap, shared_resource::SharedResource},
     custom_version::{CustomVersion, CustomVersionTrait},
     engine_version::{get_object_versions, EngineVersion},
-    error::Error,
-    flags::EPackageFlags,
+    error::{Error, PropertyError},
     object_version::{ObjectVersion, ObjectVersionUE5},
-    reader::ArchiveReader,
+    reader::{ArchiveReader, GameQuirks, StructFallbackBehavior},
     types::{FName, PackageIndex, PackageIndexTrait},
     unversioned::Usmap,
-    FNameContainer,
+    FNameContainer, Guid,
 };
 use unreal_asset_exports::{
-    base_export::BaseExport, class_export::ClassExport, data_table_export::DataTableExport,
-    enum_export::EnumExport, function_export::FunctionExport, level_export::LevelExport,
-    normal_export::NormalExport, properties::fproperty::FProperty, property_export::PropertyExport,
-    raw_export::RawExport, string_table_export::StringTableExport,
+    base_export::BaseExport, class_export::ClassExport, curve_table_export::CurveTableExport,
+    data_table_export::DataTableExport, enum_export::EnumExport, function_export::FunctionExport,
+    level_export::LevelExport, normal_export::NormalExport, properties::fproperty::FProperty,
+    property_export::PropertyExport, raw_export::RawExport, string_table_export::StringTableExport,
     user_defined_struct_export::UserDefinedStructExport, world_export::WorldExport, Export,
     ExportNormalTrait,
 };
@@ -71,6 +70,18 @@ pub struct AssetData<Index: PackageIndexTrait> {
     /// This is used for specifying those types
     #[container_ignore]
     pub array_struct_type_override: IndexedMap<String, String>,
+
+    /// Deviations of the game from stock engine serialization, used when reading and writing
+    #[container_ignore]
+    pub quirks: GameQuirks,
+
+    /// Write unversioned properties equal to the zero value of their type zero-masked, like the cooker does
+    ///
+    /// Shrinks written assets, but parsing the written asset returns an [`EmptyProperty`] for each of these properties.
+    ///
+    /// [`EmptyProperty`]: unreal_asset_properties::empty_property::EmptyProperty
+    #[container_ignore]
+    pub zero_mask_defaults: bool,
 }
 
 /// Export read from [`AssetData`]
@@ -171,6 +182,125 @@ impl<Index: PackageIndexTrait> AssetData<Index> {
             .unwrap_or_else(|| CustomVersion::new(T::GUID, 0))
     }
 
+    /// Get a custom version by its friendly name
+    pub fn get_custom_version_by_name(&self, friendly_name: &str) -> Option<&CustomVersion> {
+        self.summary
+            .custom_versions
+            .iter()
+            .find(|e| e.friendly_name.as_deref() == Some(friendly_name))
+    }
+
+    /// Set a custom version, adding it if the asset doesn't have it yet
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use unreal_asset::{
+    ///     custom_version::FFrameworkObjectVersion, engine_version::EngineVersion, Asset,
+    /// };
+    ///
+    /// let file = File::open("NewAsset.uasset")?;
+    /// let mut asset = Asset::new(file, None, EngineVersion::VER_UE4_25, None)?;
+    /// asset
+    ///     .asset_data
+    ///     .set_custom_version(FFrameworkObjectVersion::FixNonTransactionalPins);
+    /// # Ok::<(), unreal_asset::Error>(())
+    /// ```
+    pub fn set_custom_version<T>(&mut self, version: T)
+    where
+        T: CustomVersionTrait + Into<i32>,
+    {
+        self.insert_custom_version(CustomVersion::from_version(version));
+    }
+
+    /// Set a known custom version by its friendly name, adding it if the asset doesn't have it yet
+    ///
+    /// This allows setting custom versions which don't have a [`CustomVersionTrait`] implementation.
+    pub fn set_custom_version_by_name(
+        &mut self,
+        friendly_name: &str,
+        version: i32,
+    ) -> Result<(), Error> {
+        let custom_version = CustomVersion::from_friendly_name(friendly_name, version)
+            .ok_or_else(|| Error::no_data(format!("Unknown custom version {friendly_name}")))?;
+        self.insert_custom_version(custom_version);
+        Ok(())
+    }
+
+    /// Remove a custom version
+    pub fn remove_custom_version(&mut self, guid: Guid) -> Option<CustomVersion> {
+        let index = self
+            .summary
+            .custom_versions
+            .iter()
+            .position(|e| e.guid == guid)?;
+        Some(self.summary.custom_versions.remove(index))
+    }
+
+    /// Get custom versions required by the engine version which this asset doesn't have
+    ///
+    /// The returned custom versions have the version number of the engine version.
+    pub fn get_missing_custom_versions(&self) -> Vec<CustomVersion> {
+        if self.engine_version == EngineVersion::UNKNOWN {
+            return Vec::new();
+        }
+
+        CustomVersion::get_default_custom_version_container(self.engine_version)
+            .into_iter()
+            .filter(|e| {
+                !self
+                    .summary
+                    .custom_versions
+                    .iter()
+                    .any(|existing| existing.guid == e.guid)
+            })
+            .collect()
+    }
+
+    /// Check that this asset has all custom versions required by the engine version
+    ///
+    /// Versioned assets with missing custom versions are read back with those versions set to 0,
+    /// call this before writing an asset after editing its custom versions.
+    pub fn validate_custom_versions(&self) -> Result<(), Error> {
+        if self.summary.unversioned {
+            return Ok(());
+        }
+
+        let missing = self.get_missing_custom_versions();
+        if missing.is_empty() {
+            return Ok(());
+        }
+
+        let names = missing
+            .iter()
+            .map(|e| {
+                e.friendly_name
+                    .clone()
+                    .unwrap_or_else(|| e.guid.to_string())
+            })
+            .collect::<Vec<_>>();
+        Err(Error::no_data(format!(
+            "Missing custom versions required by {:?}: {}",
+            self.engine_version,
+            names.join(", ")
+        )))
+    }
+
+    /// Insert a custom version, replacing an existing one with the same guid
+    fn insert_custom_version(&mut self, custom_version: CustomVersion) {
+        match self
+            .summary
+            .custom_versions
+            .iter_mut()
+            .find(|e| e.guid == custom_version.guid)
+        {
+            Some(existing) => *existing = custom_version,
+            None => self.summary.custom_versions.push(custom_version),
+        }
+    }
+
     /// Get engine version
     pub fn get_engine_version(&self) -> EngineVersion {
         self.engine_version
@@ -206,6 +336,36 @@ impl<Index: PackageIndexTrait> AssetData<Index> {
         Some(&mut self.exports[index as usize])
     }
 
+    /// Get mutable references to multiple exports at once
+    ///
+    /// Returns `None` if any index is not a valid export index or if an export is requested more than once.
+    pub fn get_many_exports_mut<const N: usize>(
+        &mut self,
+        indices: [PackageIndex; N],
+    ) -> Option<[&mut Export<Index>; N]> {
+        let mut positions = [0usize; N];
+        for (i, index) in indices.iter().enumerate() {
+            if !index.is_export() || index.index > self.exports.len() as i32 {
+                return None;
+            }
+
+            let position = index.index as usize - 1;
+            if positions[..i].contains(&position) {
+                return None;
+            }
+            positions[i] = position;
+        }
+
+        let mut exports: [Option<&mut Export<Index>>; N] = std::array::from_fn(|_| None);
+        for (position, export) in self.exports.iter_mut().enumerate() {
+            if let Some(i) = positions.iter().position(|e| *e == position) {
+                exports[i] = Some(export);
+            }
+        }
+
+        Some(exports.map(|e| e.expect("All positions are valid export indices")))
+    }
+
     /// Searches for an returns this asset's ClassExport, if one exists
     pub fn get_class_export(&self) -> Option<&ClassExport<Index>> {
         self.exports
@@ -215,9 +375,7 @@ impl<Index: PackageIndexTrait> AssetData<Index> {
 
     /// Get if the asset has unversioned properties
     pub fn has_unversioned_properties(&self) -> bool {
-        self.summary
-            .package_flags
-            .contains(EPackageFlags::PKG_UNVERSIONED_PROPERTIES)
+        self.summary.package_flags.uses_unversioned_properties()
     }
 }
 
@@ -281,6 +439,8 @@ impl<Index: PackageIndexTrait> Default for AssetData<Index> {
                 "Keys".to_string(),
                 "RichCurveKey".to_string(),
             )]),
+            quirks: GameQuirks::NONE,
+            zero_mask_defaults: false,
         }
     }
 }
@@ -349,6 +509,9 @@ pub trait ExportReaderTrait<Index: PackageIndexTrait>:
                 "StringTable" => StringTableExport::from_base(&base_export, self)?.into(),
                 "Enum" | "UserDefinedEnum" => EnumExport::from_base(&base_export, self)?.into(),
                 "Function" => FunctionExport::from_base(&base_export, self)?.into(),
+                "CurveTable" | "CompositeCurveTable" => {
+                    CurveTableExport::from_base(&base_export, self)?.into()
+                }
                 _ => {
                     if export_class_type.ends_with("DataTable") {
                         DataTableExport::from_base(&base_export, self)?.into()
@@ -423,8 +586,30 @@ pub trait ExportReaderTrait<Index: PackageIndexTrait>:
                 new_array_overrides,
             ));
         } else if let Some(normal_export) = export.get_normal_export_mut() {
+            let max_export_size = self.get_parse_options().max_export_size;
+            if extras_len as u64 > max_export_size {
+                return Err(Error::limit_exceeded(
+                    "Export extras size",
+                    extras_len as u64,
+                    max_export_size,
+                ));
+            }
+
             let mut extras = vec![0u8; extras_len as usize];
             self.read_exact(&mut extras)?;
+
+            // the data before the padding can't be told apart from zero bytes at the end of the extras,
+            // so all trailing zero bytes which fit in the padding are assumed to be padding
+            let export_alignment = self.get_parse_options().export_alignment;
+            if let Some(alignment) = export_alignment.filter(|e| *e > 1) {
+                if next_starting.is_multiple_of(alignment) {
+                    let zeros = extras.iter().rev().take_while(|e| **e == 0).count() as u64;
+                    let padding_len = zeros.min(alignment - 1) as usize;
+                    extras.truncate(extras.len() - padding_len);
+                    normal_export.padding_alignment = Some(alignment);
+                }
+            }
+
             normal_export.extras = extras;
         }
 
@@ -452,6 +637,22 @@ pub trait ExportReaderTrait<Index: PackageIndexTrait>:
     ) -> Result<Export<Index>, Error> {
         let serial_offset = base_export.serial_offset as u64;
 
+        if base_export.serial_size < 0 {
+            return Err(Error::invalid_file(format!(
+                "Negative export size {}",
+                base_export.serial_size
+            )));
+        }
+
+        let max_export_size = self.get_parse_options().max_export_size;
+        if base_export.serial_size as u64 > max_export_size {
+            return Err(Error::limit_exceeded(
+                "Export size",
+                base_export.serial_size as u64,
+                max_export_size,
+            ));
+        }
+
         match self.read_export_no_raw(base_export.clone(), next_starting) {
             Ok(e) => {
                 let asset_data_mut = self.get_asset_data_mut();
@@ -459,6 +660,15 @@ pub trait ExportReaderTrait<Index: PackageIndexTrait>:
 
                 Ok(reduced)
             }
+            // limits set in `ParseOptions` shouldn't be silently ignored
+            Err(e @ Error::LimitExceeded(..))
+            | Err(e @ Error::Property(PropertyError::DepthLimitExceeded(..))) => Err(e),
+            // neither should structs of unknown type if they were asked to fail
+            Err(e @ Error::Property(PropertyError::UnknownStructType(..)))
+                if self.get_parse_options().struct_fallback == StructFallbackBehavior::Error =>
+            {
+                Err(e)
+            }
             Err(_e) => {
                 // todo: warning?
                 self.seek(SeekFrom::Start(serial_offset))?;
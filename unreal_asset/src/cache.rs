@@ -0,0 +1,322 @@
+//! On-disk cache for parsed assets
+//!
+//! Analyzing a whole game install means parsing thousands of assets, most of which don't change between runs.
+//! [`AssetCache`] stores an [`AssetSummary`] of every parsed asset in a user-provided directory,
+//! keyed by a hash of the asset's contents, so unchanged assets are not parsed again.
+
+use std::fs;
+use std::io::{self, Cursor, Read, Seek, Write};
+use std::path::PathBuf;
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use naive_cityhash::cityhash64;
+
+use unreal_helpers::{AtomicFile, UnrealReadExt, UnrealWriteExt};
+
+use crate::engine_version::EngineVersion;
+use crate::error::Error;
+use crate::exports::ExportBaseTrait;
+use crate::flags::EPackageFlags;
+use crate::unversioned::Usmap;
+use crate::Asset;
+
+/// Cache file magic
+const CACHE_MAGIC: u32 = u32::from_be_bytes(*b"UACS");
+/// Cache file format version, bumped whenever the format of [`AssetSummary`] changes
+const CACHE_VERSION: u32 = 1;
+/// Cache file extension
+const CACHE_EXTENSION: &str = "summary";
+
+/// Summary of an import
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ImportSummary {
+    /// Class package
+    pub class_package: String,
+    /// Class name
+    pub class_name: String,
+    /// Outer index
+    pub outer_index: i32,
+    /// Object name
+    pub object_name: String,
+}
+
+/// Summary of an export
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ExportSummary {
+    /// Object name
+    pub object_name: String,
+    /// Class index
+    pub class_index: i32,
+    /// Outer index
+    pub outer_index: i32,
+    /// Serialized size
+    pub serial_size: i64,
+}
+
+/// Parsed information about an asset which can be stored in an [`AssetCache`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AssetSummary {
+    /// Package flags
+    pub package_flags: EPackageFlags,
+    /// Name map
+    pub names: Vec<String>,
+    /// Imports
+    pub imports: Vec<ImportSummary>,
+    /// Exports
+    pub exports: Vec<ExportSummary>,
+}
+
+impl AssetSummary {
+    /// Create an `AssetSummary` from a parsed asset
+    pub fn from_asset<C: Read + Seek>(asset: &Asset<C>) -> Self {
+        let names = asset
+            .get_name_map()
+            .get_ref()
+            .get_name_map_index_list()
+            .to_vec();
+
+        let imports = asset
+            .imports
+            .iter()
+            .map(|import| ImportSummary {
+                class_package: import.class_package.get_owned_content(),
+                class_name: import.class_name.get_owned_content(),
+                outer_index: import.outer_index.index,
+                object_name: import.object_name.get_owned_content(),
+            })
+            .collect();
+
+        let exports = asset
+            .asset_data
+            .exports
+            .iter()
+            .map(|export| {
+                let base = export.get_base_export();
+                ExportSummary {
+                    object_name: base.object_name.get_owned_content(),
+                    class_index: base.class_index.index,
+                    outer_index: base.outer_index.index,
+                    serial_size: base.serial_size,
+                }
+            })
+            .collect();
+
+        AssetSummary {
+            package_flags: asset.asset_data.summary.package_flags,
+            names,
+            imports,
+            exports,
+        }
+    }
+
+    /// Read an `AssetSummary` from cache data
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, Error> {
+        if reader.read_u32::<LE>()? != CACHE_MAGIC {
+            return Err(Error::invalid_file("Invalid cache file magic".to_string()));
+        }
+
+        let version = reader.read_u32::<LE>()?;
+        if version != CACHE_VERSION {
+            return Err(Error::invalid_file(format!(
+                "Unsupported cache file version {version}"
+            )));
+        }
+
+        let package_flags = EPackageFlags::from_bits_retain(reader.read_u32::<LE>()?);
+
+        let mut names = Vec::new();
+        for _ in 0..reader.read_u32::<LE>()? {
+            names.push(read_string(reader)?);
+        }
+
+        let mut imports = Vec::new();
+        for _ in 0..reader.read_u32::<LE>()? {
+            imports.push(ImportSummary {
+                class_package: read_string(reader)?,
+                class_name: read_string(reader)?,
+                outer_index: reader.read_i32::<LE>()?,
+                object_name: read_string(reader)?,
+            });
+        }
+
+        let mut exports = Vec::new();
+        for _ in 0..reader.read_u32::<LE>()? {
+            exports.push(ExportSummary {
+                object_name: read_string(reader)?,
+                class_index: reader.read_i32::<LE>()?,
+                outer_index: reader.read_i32::<LE>()?,
+                serial_size: reader.read_i64::<LE>()?,
+            });
+        }
+
+        Ok(AssetSummary {
+            package_flags,
+            names,
+            imports,
+            exports,
+        })
+    }
+
+    /// Write an `AssetSummary` as cache data
+    pub fn write<W: io::Write + Seek>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_u32::<LE>(CACHE_MAGIC)?;
+        writer.write_u32::<LE>(CACHE_VERSION)?;
+
+        writer.write_u32::<LE>(self.package_flags.bits())?;
+
+        writer.write_u32::<LE>(self.names.len() as u32)?;
+        for name in &self.names {
+            writer.write_fstring(Some(name))?;
+        }
+
+        writer.write_u32::<LE>(self.imports.len() as u32)?;
+        for import in &self.imports {
+            writer.write_fstring(Some(&import.class_package))?;
+            writer.write_fstring(Some(&import.class_name))?;
+            writer.write_i32::<LE>(import.outer_index)?;
+            writer.write_fstring(Some(&import.object_name))?;
+        }
+
+        writer.write_u32::<LE>(self.exports.len() as u32)?;
+        for export in &self.exports {
+            writer.write_fstring(Some(&export.object_name))?;
+            writer.write_i32::<LE>(export.class_index)?;
+            writer.write_i32::<LE>(export.outer_index)?;
+            writer.write_i64::<LE>(export.serial_size)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Read a non-optional cache string
+pub(crate) fn read_string<R: Read + Seek>(reader: &mut R) -> Result<String, Error> {
+    Ok(reader.read_fstring()?.unwrap_or_default())
+}
+
+/// Cache of [`AssetSummary`]s stored in a directory
+///
+/// Entries are keyed by a hash of the asset's `.uasset` and `.uexp` contents and the engine version
+/// the asset was parsed with. Mappings are not part of the key, the cache should be cleared
+/// when assets are parsed with different mappings.
+///
+/// # Examples
+///
+/// ```no_run
+/// use unreal_asset::{cache::AssetCache, engine_version::EngineVersion};
+///
+/// let cache = AssetCache::new("analysis_cache");
+///
+/// let data = std::fs::read("Conveyor.uasset").unwrap();
+/// let bulk = std::fs::read("Conveyor.uexp").unwrap();
+///
+/// // parses the asset on the first run, subsequent runs read the cached summary
+/// let summary = cache
+///     .get_or_parse(&data, Some(&bulk), EngineVersion::VER_UE4_23, None)
+///     .unwrap();
+/// println!("{} exports", summary.exports.len());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetCache {
+    /// Cache directory
+    pub directory: PathBuf,
+}
+
+impl AssetCache {
+    /// Create a new `AssetCache` instance, the directory is created when the first entry is inserted
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        AssetCache {
+            directory: directory.into(),
+        }
+    }
+
+    /// Get the cache key for asset contents
+    pub fn get_key(data: &[u8], bulk_data: Option<&[u8]>, engine_version: EngineVersion) -> String {
+        let bulk_hash = bulk_data.map(cityhash64).unwrap_or_default();
+        format!(
+            "{:016x}{:016x}_{}",
+            cityhash64(data),
+            bulk_hash,
+            i32::from(engine_version)
+        )
+    }
+
+    fn get_entry_path(&self, key: &str) -> PathBuf {
+        self.directory.join(key).with_extension(CACHE_EXTENSION)
+    }
+
+    /// Get a cached summary
+    ///
+    /// Returns `None` if there is no entry for the key, or if the entry was written
+    /// by an incompatible version of this crate.
+    pub fn get(&self, key: &str) -> Result<Option<AssetSummary>, Error> {
+        let data = match fs::read(self.get_entry_path(key)) {
+            Ok(data) => data,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(AssetSummary::read(&mut Cursor::new(data)).ok())
+    }
+
+    /// Insert a summary into the cache, replacing an existing entry
+    pub fn insert(&self, key: &str, summary: &AssetSummary) -> Result<(), Error> {
+        let mut data = Cursor::new(Vec::new());
+        summary.write(&mut data)?;
+
+        fs::create_dir_all(&self.directory)?;
+
+        // write to a temporary file first so an interrupted write never leaves a partial entry
+        let mut file = AtomicFile::create(self.get_entry_path(key))?;
+        file.write_all(data.get_ref())?;
+        file.commit()?;
+
+        Ok(())
+    }
+
+    /// Get a cached summary of an asset, parsing the asset and caching its summary if there is no entry
+    pub fn get_or_parse(
+        &self,
+        data: &[u8],
+        bulk_data: Option<&[u8]>,
+        engine_version: EngineVersion,
+        mappings: Option<Usmap>,
+    ) -> Result<AssetSummary, Error> {
+        let key = Self::get_key(data, bulk_data, engine_version);
+        if let Some(summary) = self.get(&key)? {
+            return Ok(summary);
+        }
+
+        let asset = Asset::new(
+            Cursor::new(data),
+            bulk_data.map(Cursor::new),
+            engine_version,
+            mappings,
+        )?;
+
+        let summary = AssetSummary::from_asset(&asset);
+        self.insert(&key, &summary)?;
+        Ok(summary)
+    }
+
+    /// Remove all entries from the cache
+    pub fn clear(&self) -> Result<(), Error> {
+        let entries = match fs::read_dir(&self.directory) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+
+        for entry in entries {
+            let path = entry?.path();
+            if path
+                .extension()
+                .is_some_and(|extension| extension == CACHE_EXTENSION)
+            {
+                fs::remove_file(path)?;
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,267 @@
+//! Bulk conversion between unversioned and versioned properties
+//!
+//! Tools which only understand tagged properties can't read cooked assets with unversioned properties.
+//! [`convert_directory`] converts every asset of a directory with [`Asset::set_unversioned_properties`]
+//! and writes the converted assets to another directory.
+//! With the `pak` feature enabled, [`convert_pak`] does the same for the entries of a pak.
+//!
+//! Mappings are needed in both directions, for reading unversioned properties and for writing them.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::Cursor;
+#[cfg(feature = "pak")]
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+
+use crate::engine_version::EngineVersion;
+use crate::error::Error;
+use crate::unversioned::Usmap;
+use crate::Asset;
+
+/// Format properties are converted to
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PropertyFormat {
+    /// Tagged properties, which can be read without mappings
+    Versioned,
+    /// Unversioned properties, as written by cooking with unversioned properties enabled
+    Unversioned,
+}
+
+/// Asset which couldn't be converted
+#[derive(Debug)]
+pub struct ConversionFailure {
+    /// Path of the asset file, relative to the converted directory or pak
+    pub path: String,
+    /// Error
+    pub error: Error,
+}
+
+/// Convert the properties of an asset
+///
+/// Returns the converted asset data and bulk data, bulk data is only returned if `bulk_data` is set.
+pub fn convert_asset(
+    asset_data: &[u8],
+    bulk_data: Option<&[u8]>,
+    engine_version: EngineVersion,
+    mappings: &Usmap,
+    format: PropertyFormat,
+) -> Result<(Vec<u8>, Option<Vec<u8>>), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(asset_data),
+        bulk_data.map(Cursor::new),
+        engine_version,
+        Some(mappings.clone()),
+    )?;
+    asset.set_unversioned_properties(format == PropertyFormat::Unversioned)?;
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut bulk_cursor = bulk_data.map(|_| Cursor::new(Vec::new()));
+    asset.write_data(&mut cursor, bulk_cursor.as_mut())?;
+
+    Ok((cursor.into_inner(), bulk_cursor.map(|e| e.into_inner())))
+}
+
+/// Convert all assets of a directory and its subdirectories into another directory
+///
+/// The directory structure is kept, files which aren't assets (e.g. `.ubulk` files) are copied unchanged.
+/// Assets which can't be converted are copied unchanged and returned as failures.
+pub fn convert_directory(
+    input: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    engine_version: EngineVersion,
+    mappings: &Usmap,
+    format: PropertyFormat,
+) -> Result<Vec<ConversionFailure>, Error> {
+    let input = input.as_ref();
+    let output = output.as_ref();
+
+    let mut paths = Vec::new();
+    find_files(input, &mut paths)?;
+    paths.sort();
+
+    let asset_stems = paths
+        .iter()
+        .filter_map(|e| asset_stem(e))
+        .collect::<HashSet<_>>();
+
+    let mut failures = Vec::new();
+    for path in &paths {
+        let Some(stem) = asset_stem(path) else {
+            // bulk data is written together with its asset
+            if !bulk_stem(path).is_some_and(|e| asset_stems.contains(e)) {
+                write_file(output, path, &fs::read(input.join(path))?)?;
+            }
+            continue;
+        };
+
+        let bulk_path = format!("{stem}.uexp");
+        let asset_data = fs::read(input.join(path))?;
+        let bulk_data = match paths.contains(&bulk_path) {
+            true => Some(fs::read(input.join(&bulk_path))?),
+            false => None,
+        };
+
+        let (asset_data, bulk_data) = match convert_asset(
+            &asset_data,
+            bulk_data.as_deref(),
+            engine_version,
+            mappings,
+            format,
+        ) {
+            Ok(converted) => converted,
+            Err(error) => {
+                failures.push(ConversionFailure {
+                    path: path.clone(),
+                    error,
+                });
+                (asset_data, bulk_data)
+            }
+        };
+
+        write_file(output, path, &asset_data)?;
+        if let Some(bulk_data) = bulk_data {
+            write_file(output, &bulk_path, &bulk_data)?;
+        }
+    }
+
+    Ok(failures)
+}
+
+/// Convert all assets of a pak into another pak
+///
+/// Entries which aren't assets are copied unchanged, like with [`unreal_pak::repack`]
+/// the mount point and entry metadata are kept.
+/// Assets which can't be converted are copied unchanged and returned as failures.
+///
+/// The reader needs to have its index loaded already, an index loaded with
+/// [`unreal_pak::PakReader::load_index_lazy`] is fully decoded. The writer is not finished,
+/// call [`unreal_pak::PakWriter::finish_write`] afterwards.
+#[cfg(feature = "pak")]
+pub fn convert_pak<R: Read + Seek, W: Write + Seek>(
+    reader: &mut unreal_pak::PakReader<R>,
+    writer: &mut unreal_pak::PakWriter<W>,
+    engine_version: EngineVersion,
+    mappings: &Usmap,
+    format: PropertyFormat,
+) -> Result<Vec<ConversionFailure>, Error> {
+    writer.mount_point = reader.mount_point.clone();
+    writer.encryption_key_guid = reader.get_encryption_key_guid().unwrap_or_default();
+    reader.preload_full_index().map_err(pak_error)?;
+
+    let names = reader
+        .get_entry_names()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    let asset_stems = names
+        .iter()
+        .filter_map(|e| asset_stem(e))
+        .collect::<HashSet<_>>();
+
+    let mut failures = Vec::new();
+    for name in &names {
+        let Some(stem) = asset_stem(name) else {
+            // bulk data is written together with its asset
+            if !bulk_stem(name).is_some_and(|e| asset_stems.contains(e)) {
+                let data = reader.read_entry(name).map_err(pak_error)?;
+                write_pak_entry(reader, writer, name, &data)?;
+            }
+            continue;
+        };
+
+        let bulk_name = format!("{stem}.uexp");
+        let asset_data = reader.read_entry(name).map_err(pak_error)?;
+        let bulk_data = match names.contains(&bulk_name) {
+            true => Some(reader.read_entry(&bulk_name).map_err(pak_error)?),
+            false => None,
+        };
+
+        let (asset_data, bulk_data) = match convert_asset(
+            &asset_data,
+            bulk_data.as_deref(),
+            engine_version,
+            mappings,
+            format,
+        ) {
+            Ok(converted) => converted,
+            Err(error) => {
+                failures.push(ConversionFailure {
+                    path: name.clone(),
+                    error,
+                });
+                (asset_data, bulk_data)
+            }
+        };
+
+        write_pak_entry(reader, writer, name, &asset_data)?;
+        if let Some(bulk_data) = bulk_data {
+            write_pak_entry(reader, writer, &bulk_name, &bulk_data)?;
+        }
+    }
+
+    Ok(failures)
+}
+
+/// Get the path of an asset file without its extension, `None` if the file isn't an asset
+fn asset_stem(path: &str) -> Option<&str> {
+    path.strip_suffix(".uasset")
+        .or_else(|| path.strip_suffix(".umap"))
+}
+
+/// Get the path of a bulk data file without its extension
+fn bulk_stem(path: &str) -> Option<&str> {
+    path.strip_suffix(".uexp")
+}
+
+/// Recursively find all files in a directory, as `/` separated paths relative to it
+fn find_files(directory: &Path, paths: &mut Vec<String>) -> Result<(), Error> {
+    let mut directories = vec![directory.to_path_buf()];
+    while let Some(current) = directories.pop() {
+        for entry in fs::read_dir(&current)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                directories.push(path);
+            } else if let Ok(relative) = path.strip_prefix(directory) {
+                let relative = relative
+                    .components()
+                    .map(|e| e.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>();
+                paths.push(relative.join("/"));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Write a file into the output directory, creating its parent directories
+fn write_file(output: &Path, path: &str, data: &[u8]) -> Result<(), Error> {
+    let file_path = output.join(path);
+    if let Some(parent) = file_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(file_path, data)?;
+    Ok(())
+}
+
+/// Write a pak entry with the metadata of the entry it's converted from
+#[cfg(feature = "pak")]
+fn write_pak_entry<R: Read + Seek, W: Write + Seek>(
+    reader: &unreal_pak::PakReader<R>,
+    writer: &mut unreal_pak::PakWriter<W>,
+    name: &String,
+    data: &[u8],
+) -> Result<(), Error> {
+    let metadata = reader.get_entry_metadata(name).unwrap_or_default();
+    writer
+        .write_entry_with_metadata(name, data, &metadata)
+        .map_err(pak_error)
+}
+
+/// Convert a pak error
+#[cfg(feature = "pak")]
+fn pak_error(error: unreal_pak::PakError) -> Error {
+    Error::invalid_file(error.to_string())
+}
@@ -0,0 +1,91 @@
+//! Human readable display of names, imports and package indices
+//!
+//! `FName`s and imports implement [`Display`] on their own, package indices only display their raw index.
+//! [`AssetDisplay::display`] wraps a value together with the asset it belongs to,
+//! so that package indices are shown with the object path they point to, e.g. for logging:
+//!
+//! ```no_run
+//! # use std::io::Cursor;
+//! # use unreal_asset::{display::AssetDisplay, Asset, types::PackageIndex};
+//! # fn log(asset: &Asset<Cursor<Vec<u8>>>, index: PackageIndex) {
+//! println!("outer: {}", index.display(asset));
+//! # }
+//! ```
+
+use std::fmt::{self, Display};
+use std::io::{Read, Seek};
+
+use crate::import::Import;
+use crate::types::{FName, PackageIndex};
+use crate::Asset;
+
+/// Values which can be displayed with the context of the asset they belong to
+pub trait AssetDisplay {
+    /// Format this value with the context of `asset`
+    fn fmt_with_asset<C: Read + Seek>(
+        &self,
+        asset: &Asset<C>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result;
+
+    /// Wrap this value with the asset it belongs to, the returned value implements [`Display`]
+    fn display<'a, C: Read + Seek>(&'a self, asset: &'a Asset<C>) -> WithAsset<'a, Self, C> {
+        WithAsset { value: self, asset }
+    }
+}
+
+/// Value displayed with the context of its asset, see [`AssetDisplay::display`]
+pub struct WithAsset<'a, T: ?Sized, C: Read + Seek> {
+    value: &'a T,
+    asset: &'a Asset<C>,
+}
+
+impl<T: AssetDisplay + ?Sized, C: Read + Seek> Display for WithAsset<'_, T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.value.fmt_with_asset(self.asset, f)
+    }
+}
+
+/// Displays the name with its instance number, names don't need the asset to be displayed
+impl AssetDisplay for FName {
+    fn fmt_with_asset<C: Read + Seek>(
+        &self,
+        _asset: &Asset<C>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// Displays the import with its full object path,
+/// e.g. `/Script/Engine.BlueprintGeneratedClass'/Game/Items/Conveyor.Conveyor_C'`
+impl AssetDisplay for Import {
+    fn fmt_with_asset<C: Read + Seek>(
+        &self,
+        asset: &Asset<C>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}'{}'",
+            self.class_package,
+            self.class_name,
+            self.resolved_path(asset.imports.as_slice())
+        )
+    }
+}
+
+/// Displays the index with the object path of the import or export it points to,
+/// e.g. `-3 (/Script/Engine.Actor)`, `2 (Default__Conveyor_C:Root)` or `0 (null)`
+impl AssetDisplay for PackageIndex {
+    fn fmt_with_asset<C: Read + Seek>(
+        &self,
+        asset: &Asset<C>,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self.index {
+            0 => write!(f, "0 (null)"),
+            index => write!(f, "{} ({})", index, asset.format_object_path(*self)),
+        }
+    }
+}
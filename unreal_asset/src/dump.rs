@@ -0,0 +1,370 @@
+//! Annotated hex dumps of exports and import/export tables
+//!
+//! [`Asset::debug_dump_export`] serializes an export and labels every known field,
+//! which makes it easier to find where the parsed data ends and unknown data starts
+//! when reverse-engineering new export types.
+//!
+//! [`Asset::format_tables`] lists the imports and exports of an asset with their classes and outers.
+
+use std::fmt::Write as _;
+use std::io::{Cursor, Read, Seek, Write};
+
+use unreal_asset_base::reader::{ArchiveTrait, ArchiveWriter, RawWriter};
+use unreal_asset_base::types::fname::ToSerializedName;
+use unreal_asset_exports::{ExportBaseTrait, ExportNormalTrait, ExportTrait};
+use unreal_asset_properties::{generate_unversioned_header, Property, PropertyDataTrait};
+
+use crate::asset_archive_writer::AssetArchiveWriter;
+use crate::error::Error;
+use crate::import::ImportMapTrait;
+use crate::types::PackageIndex;
+use crate::{Asset, Export};
+
+/// Options for [`Asset::debug_dump_export`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DumpOptions {
+    /// Amount of bytes shown per line
+    pub bytes_per_line: usize,
+    /// Show offsets relative to the start of the file instead of the start of the export,
+    /// these are based on the serial offset the export was read from
+    pub absolute_offsets: bool,
+    /// Show printable bytes as ASCII next to the hex view
+    pub ascii: bool,
+    /// Maximum amount of bytes shown, the rest of the export is omitted
+    pub max_len: Option<usize>,
+}
+
+impl Default for DumpOptions {
+    fn default() -> Self {
+        DumpOptions {
+            bytes_per_line: 16,
+            absolute_offsets: false,
+            ascii: true,
+            max_len: None,
+        }
+    }
+}
+
+/// Labelled range of serialized export data
+struct DumpRegion {
+    /// Start offset in the serialized export
+    start: u64,
+    /// End offset in the serialized export
+    end: u64,
+    /// Label
+    label: String,
+}
+
+impl<C: Read + Seek> Asset<C> {
+    /// Create an annotated hex dump of an export
+    ///
+    /// The export is serialized with the asset's current data, so edits are reflected in the dump.
+    /// Properties, the unversioned header, export specific data, extras and padding are labelled.
+    pub fn debug_dump_export(
+        &self,
+        index: PackageIndex,
+        options: DumpOptions,
+    ) -> Result<String, Error> {
+        let export = self.asset_data.get_export(index).ok_or_else(|| {
+            Error::invalid_package_index(format!("Export {} doesn't exist", index.index))
+        })?;
+
+        let mut cursor = Cursor::new(Vec::new());
+        let mut raw_writer = RawWriter::new(
+            &mut cursor,
+            self.asset_data.object_version,
+            self.asset_data.object_version_ue5,
+            self.asset_data.use_event_driven_loader,
+            self.get_name_map(),
+        );
+        let mut writer = AssetArchiveWriter::new(
+            &mut raw_writer,
+            &self.asset_data,
+            &self.imports,
+            self.get_name_map(),
+        );
+
+        let mut regions = Vec::new();
+
+        // properties are written first by every export with a normal export,
+        // write them separately to find their boundaries and then overwrite them with the whole export
+        if let Some(normal_export) = export.get_normal_export() {
+            let class_type = normal_export
+                .base_export
+                .get_class_type_for_ancestry(&writer);
+            let (unversioned_header, sorted_properties) =
+                match generate_unversioned_header(&writer, &normal_export.properties, &class_type)?
+                {
+                    Some((header, properties)) => (Some(header), Some(properties)),
+                    None => (None, None),
+                };
+
+            if let Some(unversioned_header) = unversioned_header {
+                let start = writer.position();
+                unversioned_header.write(&mut writer)?;
+                regions.push(DumpRegion {
+                    start,
+                    end: writer.position(),
+                    label: String::from("Unversioned header"),
+                });
+            }
+
+            let properties = sorted_properties
+                .as_ref()
+                .unwrap_or(&normal_export.properties);
+            for property in properties {
+                let start = writer.position();
+                Property::write(property, &mut writer, true)?;
+                regions.push(DumpRegion {
+                    start,
+                    end: writer.position(),
+                    label: format!(
+                        "{} ({})",
+                        property.get_name().get_owned_content(),
+                        property.to_serialized_name()
+                    ),
+                });
+            }
+
+            if !writer.has_unversioned_properties() {
+                let start = writer.position();
+                let none = writer.add_fname("None");
+                writer.write_fname(&none)?;
+                regions.push(DumpRegion {
+                    start,
+                    end: writer.position(),
+                    label: String::from("None"),
+                });
+            }
+
+            writer.set_position(0)?;
+        }
+
+        let data_start = regions.last().map(|e| e.end).unwrap_or_default();
+        export.write(&mut writer)?;
+        let data_end = writer.position();
+        if data_end > data_start {
+            regions.push(DumpRegion {
+                start: data_start,
+                end: data_end,
+                label: match export {
+                    Export::RawExport(_) => String::from("Raw data"),
+                    _ => String::from("Export data"),
+                },
+            });
+        }
+
+        if let Some(normal_export) = export.get_normal_export() {
+            writer.write_all(&normal_export.extras)?;
+            regions.push(DumpRegion {
+                start: data_end,
+                end: writer.position(),
+                label: String::from("Extras"),
+            });
+
+            // padding depends on the position in the file, assume the export stays where it was read from
+            let padding_start = writer.position();
+            let padding_len = normal_export
+                .get_padding_len(export.get_base_export().serial_offset as u64 + padding_start);
+            writer.write_all(&vec![0u8; padding_len as usize])?;
+            regions.push(DumpRegion {
+                start: padding_start,
+                end: writer.position(),
+                label: String::from("Padding"),
+            });
+        }
+
+        let data = cursor.into_inner();
+
+        let base_export = export.get_base_export();
+        let base_offset = match options.absolute_offsets {
+            true => base_export.serial_offset as u64,
+            false => 0,
+        };
+
+        let mut dump = format!(
+            "Export {} ({}), {} bytes\n",
+            index.index,
+            base_export.object_name.get_owned_content(),
+            data.len()
+        );
+        format_regions(&mut dump, &data, &regions, base_offset, &options);
+        Ok(dump)
+    }
+
+    /// Format the imports and exports of this asset as aligned tables
+    ///
+    /// Classes and outers are shown as object paths, export classes and outers which are
+    /// exports themselves are shown relative to this package.
+    pub fn format_tables(&self) -> String {
+        let import_rows = self
+            .imports
+            .iter()
+            .enumerate()
+            .map(|(i, import)| {
+                vec![
+                    (-(i as i32) - 1).to_string(),
+                    format!("{}.{}", import.class_package, import.class_name),
+                    self.format_object_path(import.outer_index),
+                    import.object_name.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let export_rows = self
+            .asset_data
+            .exports
+            .iter()
+            .enumerate()
+            .map(|(i, export)| {
+                let base_export = export.get_base_export();
+                vec![
+                    (i + 1).to_string(),
+                    match base_export.class_index.index {
+                        0 => String::from("Class"),
+                        _ => self.format_object_path(base_export.class_index),
+                    },
+                    self.format_object_path(base_export.outer_index),
+                    base_export.object_name.to_string(),
+                    base_export.serial_offset.to_string(),
+                    base_export.serial_size.to_string(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut tables = String::new();
+        format_table(
+            &mut tables,
+            &format!("Imports ({})", import_rows.len()),
+            &[
+                ("Index", true),
+                ("Class", false),
+                ("Outer", false),
+                ("Object name", false),
+            ],
+            &import_rows,
+        );
+        tables.push('\n');
+        format_table(
+            &mut tables,
+            &format!("Exports ({})", export_rows.len()),
+            &[
+                ("Index", true),
+                ("Class", false),
+                ("Outer", false),
+                ("Object name", false),
+                ("Offset", true),
+                ("Size", true),
+            ],
+            &export_rows,
+        );
+        tables
+    }
+
+    /// Format the object path of an import or export for [`Asset::format_tables`], empty for a null index
+    pub(crate) fn format_object_path(&self, index: PackageIndex) -> String {
+        if let Some(import) = self.imports.get_import_ref(index) {
+            return import.resolved_path(self.imports.as_slice());
+        }
+
+        match index.index {
+            0 => String::new(),
+            _ => self
+                .get_export_object_path(index)
+                .unwrap_or_else(|| format!("<invalid {}>", index.index)),
+        }
+    }
+}
+
+/// Format rows of cells as a table with aligned columns
+///
+/// Every column has a header and is aligned to the right if its flag is set.
+fn format_table(out: &mut String, title: &str, columns: &[(&str, bool)], rows: &[Vec<String>]) {
+    let _ = writeln!(out, "{title}");
+
+    let widths = columns
+        .iter()
+        .enumerate()
+        .map(|(i, (header, _))| {
+            rows.iter()
+                .map(|row| row[i].chars().count())
+                .chain(std::iter::once(header.chars().count()))
+                .max()
+                .unwrap_or_default()
+        })
+        .collect::<Vec<_>>();
+
+    let mut format_row = |cells: &mut dyn Iterator<Item = &str>| {
+        let mut line = String::new();
+        for (i, cell) in cells.enumerate() {
+            if i > 0 {
+                line.push_str("  ");
+            }
+            let width = widths[i];
+            let _ = match columns[i].1 {
+                true => write!(line, "{cell:>width$}"),
+                false => write!(line, "{cell:<width$}"),
+            };
+        }
+        let _ = writeln!(out, "{}", line.trim_end());
+    };
+
+    format_row(&mut columns.iter().map(|(header, _)| *header));
+    let separators = widths.iter().map(|e| "-".repeat(*e)).collect::<Vec<_>>();
+    format_row(&mut separators.iter().map(String::as_str));
+    for row in rows {
+        format_row(&mut row.iter().map(String::as_str));
+    }
+}
+
+/// Format labelled regions of data as hex
+fn format_regions(
+    dump: &mut String,
+    data: &[u8],
+    regions: &[DumpRegion],
+    base_offset: u64,
+    options: &DumpOptions,
+) {
+    let bytes_per_line = options.bytes_per_line.max(1);
+    let max_len = options.max_len.unwrap_or(data.len()).min(data.len()) as u64;
+
+    for region in regions.iter().filter(|e| e.end > e.start) {
+        if region.start >= max_len {
+            break;
+        }
+
+        let _ = writeln!(
+            dump,
+            "[{:#010x}..{:#010x}] {}",
+            base_offset + region.start,
+            base_offset + region.end,
+            region.label
+        );
+
+        let region_data = &data[region.start as usize..region.end.min(max_len) as usize];
+        for (i, line) in region_data.chunks(bytes_per_line).enumerate() {
+            let offset = base_offset + region.start + (i * bytes_per_line) as u64;
+            let _ = write!(dump, "  {offset:08x}  ");
+            for byte in line {
+                let _ = write!(dump, "{byte:02x} ");
+            }
+
+            if options.ascii {
+                let padding = (bytes_per_line - line.len()) * 3;
+                let ascii = line
+                    .iter()
+                    .map(|e| match e.is_ascii_graphic() || *e == b' ' {
+                        true => *e as char,
+                        false => '.',
+                    })
+                    .collect::<String>();
+                let _ = write!(dump, "{:padding$} |{ascii}|", "");
+            }
+            dump.push('\n');
+        }
+    }
+
+    if max_len < data.len() as u64 {
+        let _ = writeln!(dump, "... {} more bytes", data.len() as u64 - max_len);
+    }
+}
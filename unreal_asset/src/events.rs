@@ -0,0 +1,297 @@
+//! Event-driven asset parsing
+//!
+//! [`Asset::parse_events`] reads an asset and reports what it finds to a handler
+//! without building exports or properties, which is a lot faster when only a few fields are needed,
+//! e.g. when indexing all assets of a game.
+
+use std::io::{Read, Seek, SeekFrom};
+
+use byteorder::{ReadBytesExt, LE};
+
+use unreal_asset_base::reader::{ArchiveReader, ArchiveTrait};
+use unreal_asset_exports::BaseExport;
+
+use crate::engine_version::EngineVersion;
+use crate::error::Error;
+use crate::object_version::ObjectVersion;
+use crate::types::{FName, PackageIndex};
+use crate::unversioned::Usmap;
+use crate::{Asset, Guid, Import};
+
+/// Tag of a serialized property
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PropertyTag {
+    /// Property name
+    pub name: FName,
+    /// Property type, e.g. `IntProperty`
+    pub property_type: FName,
+    /// Size of the serialized value in bytes
+    pub size: i32,
+    /// Array index of the property, used by static arrays
+    pub array_index: i32,
+    /// Struct type of a `StructProperty`
+    pub struct_type: Option<FName>,
+    /// Struct guid of a `StructProperty`
+    pub struct_guid: Option<Guid>,
+    /// Enum name of a `ByteProperty` or an `EnumProperty`
+    pub enum_name: Option<FName>,
+    /// Inner type of an `ArrayProperty`, a `SetProperty` or a `MapProperty`
+    pub inner_type: Option<FName>,
+    /// Value type of a `MapProperty`
+    pub value_type: Option<FName>,
+    /// Value of a `BoolProperty`, which is stored in the tag
+    pub bool_value: Option<bool>,
+    /// Property guid
+    pub property_guid: Option<Guid>,
+    /// Offset of the serialized value
+    pub value_offset: u64,
+}
+
+impl PropertyTag {
+    /// Read a `PropertyTag` from an asset, returns `None` when the `None` property terminating a property list is read
+    pub fn read<Reader: ArchiveReader<PackageIndex>>(
+        reader: &mut Reader,
+    ) -> Result<Option<Self>, Error> {
+        let name = reader.read_fname()?;
+        if name == "None" {
+            return Ok(None);
+        }
+
+        let property_type = reader.read_fname()?;
+        let size = reader.read_i32::<LE>()?;
+        let array_index = reader.read_i32::<LE>()?;
+
+        let mut tag = PropertyTag {
+            name,
+            property_type,
+            size,
+            array_index,
+            struct_type: None,
+            struct_guid: None,
+            enum_name: None,
+            inner_type: None,
+            value_type: None,
+            bool_value: None,
+            property_guid: None,
+            value_offset: 0,
+        };
+
+        let property_type = tag.property_type.get_owned_content();
+        match property_type.as_str() {
+            "StructProperty" => {
+                tag.struct_type = Some(reader.read_fname()?);
+                if reader.get_object_version() >= ObjectVersion::VER_UE4_STRUCT_GUID_IN_PROPERTY_TAG
+                {
+                    tag.struct_guid = Some(reader.read_guid()?);
+                }
+            }
+            "BoolProperty" => tag.bool_value = Some(reader.read_bool()?),
+            "ByteProperty" | "EnumProperty" => tag.enum_name = Some(reader.read_fname()?),
+            "ArrayProperty" | "SetProperty" => tag.inner_type = Some(reader.read_fname()?),
+            "MapProperty" => {
+                tag.inner_type = Some(reader.read_fname()?);
+                tag.value_type = Some(reader.read_fname()?);
+            }
+            _ => {}
+        }
+
+        tag.property_guid = reader.read_property_guid()?;
+        tag.value_offset = reader.position();
+
+        Ok(Some(tag))
+    }
+}
+
+/// Event emitted by [`Asset::parse_events`]
+#[derive(Debug)]
+pub enum AssetEvent<'a> {
+    /// Name map entry
+    NameEntry {
+        /// Name map index
+        index: i32,
+        /// Name
+        name: &'a str,
+    },
+    /// Import
+    Import {
+        /// Import index
+        index: PackageIndex,
+        /// Import
+        import: &'a Import,
+    },
+    /// Export map entry, all export headers are emitted before any properties
+    ExportHeader {
+        /// Export index
+        index: PackageIndex,
+        /// Export header
+        export: &'a BaseExport<PackageIndex>,
+    },
+    /// Start of a top-level export property, the property value is not parsed
+    PropertyStart {
+        /// Export the property belongs to
+        export: PackageIndex,
+        /// Property tag
+        tag: &'a PropertyTag,
+    },
+    /// End of a top-level export property
+    PropertyEnd {
+        /// Export the property belongs to
+        export: PackageIndex,
+        /// Property tag
+        tag: &'a PropertyTag,
+    },
+}
+
+/// Returned by event handlers to control parsing
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ParseControl {
+    /// Continue parsing
+    #[default]
+    Continue,
+    /// Skip the properties of the current export
+    ///
+    /// Returning this from an [`AssetEvent::ExportHeader`] event skips all properties of the export,
+    /// returning this from a property event skips the rest of the export's properties.
+    SkipExport,
+    /// Stop parsing
+    Stop,
+}
+
+impl<C: Read + Seek> Asset<C> {
+    /// Parse an asset, emitting events instead of building exports
+    ///
+    /// Events are emitted in file order: name map entries, imports, export headers
+    /// and then the top-level properties of each export.
+    ///
+    /// Property values are skipped using the size in their tag, nested properties are not reported.
+    /// Properties are only reported for assets with versioned properties.
+    /// A property tag which can't be read is returned as an error, exports which don't start
+    /// with a property list should be skipped by returning [`ParseControl::SkipExport`] for their header.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::fs::File;
+    ///
+    /// use unreal_asset::{
+    ///     engine_version::EngineVersion,
+    ///     events::{AssetEvent, ParseControl},
+    ///     Asset,
+    /// };
+    ///
+    /// let file = File::open("asset.uasset").unwrap();
+    /// let mut class_names = Vec::new();
+    ///
+    /// Asset::parse_events(file, None, EngineVersion::VER_UE4_23, None, |event| {
+    ///     match event {
+    ///         AssetEvent::Import { import, .. } => {
+    ///             class_names.push(import.class_name.get_owned_content())
+    ///         }
+    ///         AssetEvent::ExportHeader { .. } => return ParseControl::Stop,
+    ///         _ => {}
+    ///     }
+    ///     ParseControl::Continue
+    /// })
+    /// .unwrap();
+    /// ```
+    pub fn parse_events(
+        asset_data: C,
+        bulk_data: Option<C>,
+        engine_version: EngineVersion,
+        mappings: Option<Usmap>,
+        mut handler: impl FnMut(AssetEvent<'_>) -> ParseControl,
+    ) -> Result<(), Error> {
+        let mut asset = Self::create(asset_data, bulk_data, engine_version, mappings);
+        asset.parse_header()?;
+        asset.parse_name_map()?;
+
+        let name_map = asset.get_name_map();
+        for (index, name) in name_map
+            .get_ref()
+            .get_name_map_index_list()
+            .iter()
+            .enumerate()
+        {
+            let event = AssetEvent::NameEntry {
+                index: index as i32,
+                name,
+            };
+            if handler(event) == ParseControl::Stop {
+                return Ok(());
+            }
+        }
+
+        asset.parse_imports()?;
+        for (index, import) in asset.imports.iter().enumerate() {
+            let event = AssetEvent::Import {
+                index: PackageIndex::from_import(index as i32)?,
+                import,
+            };
+            if handler(event) == ParseControl::Stop {
+                return Ok(());
+            }
+        }
+
+        let exports = asset
+            .parse_export_map()?
+            .into_iter()
+            .map(|entry| entry.to_base_export())
+            .collect::<Vec<_>>();
+
+        let mut skipped = vec![false; exports.len()];
+        for (i, export) in exports.iter().enumerate() {
+            let event = AssetEvent::ExportHeader {
+                index: PackageIndex::from_export(i as i32)?,
+                export,
+            };
+            match handler(event) {
+                ParseControl::Continue => {}
+                ParseControl::SkipExport => skipped[i] = true,
+                ParseControl::Stop => return Ok(()),
+            }
+        }
+
+        if asset.has_unversioned_properties() {
+            return Ok(());
+        }
+
+        for (i, export) in exports.iter().enumerate() {
+            if skipped[i] || export.serial_size <= 0 {
+                continue;
+            }
+
+            let index = PackageIndex::from_export(i as i32)?;
+            let export_end = export.serial_offset as u64 + export.serial_size as u64;
+            asset.seek(SeekFrom::Start(export.serial_offset as u64))?;
+
+            while let Some(tag) = PropertyTag::read(&mut asset)? {
+                let value_end = tag.value_offset + tag.size.max(0) as u64;
+                if value_end > export_end {
+                    break;
+                }
+
+                match handler(AssetEvent::PropertyStart {
+                    export: index,
+                    tag: &tag,
+                }) {
+                    ParseControl::Continue => {}
+                    ParseControl::SkipExport => break,
+                    ParseControl::Stop => return Ok(()),
+                }
+
+                asset.seek(SeekFrom::Start(value_end))?;
+
+                match handler(AssetEvent::PropertyEnd {
+                    export: index,
+                    tag: &tag,
+                }) {
+                    ParseControl::Continue => {}
+                    ParseControl::SkipExport => break,
+                    ParseControl::Stop => return Ok(()),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+//! Export extras hooks
+//!
+//! Data which is serialized natively after the properties of an export, e.g. the cooked physics data
+//! of a `BodySetup`, is kept as raw bytes in [`NormalExport::extras`].
+//! An [`ExtrasHandler`] registered for a class turns these bytes into a structured value and back,
+//! so game specific formats can be worked with without adding them to this crate.
+//!
+//! The raw bytes stay the serialized form of the extras, assets are written exactly as before
+//! until structured extras are stored with [`Asset::write_extras`].
+//!
+//! [`NormalExport::extras`]: unreal_asset_exports::NormalExport::extras
+//! [`Asset::write_extras`]: crate::Asset::write_extras
+
+use std::any::{self, Any};
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock, PoisonError, RwLock};
+
+use unreal_asset_base::containers::{NameMap, SharedResource};
+use unreal_asset_base::engine_version::EngineVersion;
+use unreal_asset_base::object_version::{ObjectVersion, ObjectVersionUE5};
+use unreal_asset_base::Error;
+
+/// Information about the export and asset extras are read from or written to
+#[derive(Debug, Clone)]
+pub struct ExtrasContext {
+    /// Class the handler was registered for, the export's class or one of its super classes
+    pub class_name: String,
+    /// Engine version of the asset
+    pub engine_version: EngineVersion,
+    /// Object version of the asset
+    pub object_version: ObjectVersion,
+    /// UE5 object version of the asset
+    pub object_version_ue5: ObjectVersionUE5,
+    /// Name map of the asset, to resolve and add names referenced by the extras
+    pub name_map: SharedResource<NameMap>,
+}
+
+/// Parser and writer of the extras of exports of a class
+pub trait ExtrasHandler: Send + Sync + 'static {
+    /// Structured form of the extras
+    type Extras: Any;
+
+    /// Parse the extras of an export
+    fn read(&self, extras: &[u8], context: &ExtrasContext) -> Result<Self::Extras, Error>;
+
+    /// Serialize structured extras
+    fn write(&self, extras: &Self::Extras, context: &ExtrasContext) -> Result<Vec<u8>, Error>;
+}
+
+/// Type erased [`ExtrasHandler`] stored in the registry
+trait DynExtrasHandler: Send + Sync {
+    fn read(&self, extras: &[u8], context: &ExtrasContext) -> Result<Box<dyn Any>, Error>;
+
+    fn write(&self, extras: &dyn Any, context: &ExtrasContext) -> Result<Vec<u8>, Error>;
+}
+
+impl<T: ExtrasHandler> DynExtrasHandler for T {
+    fn read(&self, extras: &[u8], context: &ExtrasContext) -> Result<Box<dyn Any>, Error> {
+        Ok(Box::new(ExtrasHandler::read(self, extras, context)?))
+    }
+
+    fn write(&self, extras: &dyn Any, context: &ExtrasContext) -> Result<Vec<u8>, Error> {
+        let extras = extras.downcast_ref::<T::Extras>().ok_or_else(|| {
+            Error::no_data(format!(
+                "Extras handler of {} doesn't write {}",
+                context.class_name,
+                any::type_name::<T::Extras>()
+            ))
+        })?;
+        ExtrasHandler::write(self, extras, context)
+    }
+}
+
+/// Registered handlers by class name
+type Registry = RwLock<HashMap<String, Arc<dyn DynExtrasHandler>>>;
+
+fn registry() -> &'static Registry {
+    static REGISTRY: OnceLock<Registry> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register a handler for the extras of exports of a class, e.g. `BodySetup`
+///
+/// The handler is also used for subclasses which don't have a handler of their own.
+/// Registering a handler for a class which already has one replaces it.
+pub fn register_extras_handler(class_name: &str, handler: impl ExtrasHandler) {
+    registry()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(class_name.to_string(), Arc::new(handler));
+}
+
+/// Remove the handler registered for a class, returns `false` if there was none
+pub fn unregister_extras_handler(class_name: &str) -> bool {
+    registry()
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .remove(class_name)
+        .is_some()
+}
+
+/// Check if a handler is registered for a class
+pub fn has_extras_handler(class_name: &str) -> bool {
+    registry()
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .contains_key(class_name)
+}
+
+/// Read extras with the handler of the first class in `class_hierarchy` that has one
+///
+/// Returns `None` if no class has a handler.
+pub(crate) fn read_extras<T: Any>(
+    class_hierarchy: &[String],
+    extras: &[u8],
+    context: impl FnOnce(String) -> ExtrasContext,
+) -> Result<Option<T>, Error> {
+    let Some((class_name, handler)) = find_handler(class_hierarchy) else {
+        return Ok(None);
+    };
+    let context = context(class_name);
+
+    let extras = handler.read(extras, &context)?;
+    extras.downcast::<T>().map(|e| Some(*e)).map_err(|_| {
+        Error::no_data(format!(
+            "Extras handler of {} doesn't read {}",
+            context.class_name,
+            any::type_name::<T>()
+        ))
+    })
+}
+
+/// Write extras with the handler of the first class in `class_hierarchy` that has one
+///
+/// Returns `None` if no class has a handler.
+pub(crate) fn write_extras<T: Any>(
+    class_hierarchy: &[String],
+    extras: &T,
+    context: impl FnOnce(String) -> ExtrasContext,
+) -> Result<Option<Vec<u8>>, Error> {
+    let Some((class_name, handler)) = find_handler(class_hierarchy) else {
+        return Ok(None);
+    };
+    handler.write(extras, &context(class_name)).map(Some)
+}
+
+fn find_handler(class_hierarchy: &[String]) -> Option<(String, Arc<dyn DynExtrasHandler>)> {
+    let registry = registry().read().unwrap_or_else(PoisonError::into_inner);
+    class_hierarchy.iter().find_map(|class_name| {
+        registry
+            .get(class_name)
+            .map(|handler| (class_name.clone(), Arc::clone(handler)))
+    })
+}
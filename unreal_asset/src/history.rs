@@ -0,0 +1,258 @@
+//! Undo and redo support for asset exports
+//!
+//! [`AssetHistory`] records the exports changed by each edit instead of snapshotting whole assets,
+//! which keeps undo cheap even for large assets.
+
+use unreal_asset_base::types::{PackageIndex, PackageIndexTrait};
+use unreal_asset_exports::Export;
+
+use crate::asset_data::AssetData;
+
+/// A single change to an asset's exports
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Change<Index: PackageIndexTrait> {
+    /// Export at `index` was modified, contains the export's state before or after the change
+    Modified {
+        index: usize,
+        export: Box<Export<Index>>,
+    },
+    /// Export was inserted at `index`
+    Inserted { index: usize },
+    /// Export was removed from `index`
+    Removed {
+        index: usize,
+        export: Box<Export<Index>>,
+    },
+}
+
+impl<Index: PackageIndexTrait> Change<Index> {
+    /// Apply this change to exports, returning the change which reverts it
+    fn apply(self, exports: &mut Vec<Export<Index>>) -> Self {
+        match self {
+            Change::Modified { index, mut export } => {
+                std::mem::swap(&mut exports[index], &mut export);
+                Change::Modified { index, export }
+            }
+            Change::Inserted { index } => Change::Removed {
+                index,
+                export: Box::new(exports.remove(index)),
+            },
+            Change::Removed { index, export } => {
+                exports.insert(index, *export);
+                Change::Inserted { index }
+            }
+        }
+    }
+}
+
+/// Opt-in change tracking for an asset's exports
+///
+/// Edits made through an `AssetHistory` can be undone and redone,
+/// edits made directly to the asset are not tracked and should not be mixed with tracked edits.
+///
+/// # Examples
+///
+/// ```no_run
+/// use unreal_asset::{history::AssetHistory, types::PackageIndex, Asset};
+/// # use unreal_asset::engine_version::EngineVersion;
+/// # let data = std::io::Cursor::new(Vec::new());
+/// # let mut asset = Asset::new(data, None, EngineVersion::VER_UE4_23, None).unwrap();
+///
+/// let mut history = AssetHistory::new();
+/// history.edit_export(&mut asset.asset_data, PackageIndex::new(1), |export| {
+///     // modify the export
+/// });
+///
+/// history.undo(&mut asset.asset_data);
+/// history.redo(&mut asset.asset_data);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssetHistory<Index: PackageIndexTrait> {
+    /// Maximum amount of undo steps that are kept, `None` for unlimited
+    pub max_steps: Option<usize>,
+    undo_stack: Vec<Vec<Change<Index>>>,
+    redo_stack: Vec<Vec<Change<Index>>>,
+    group: Option<Vec<Change<Index>>>,
+}
+
+impl<Index: PackageIndexTrait> AssetHistory<Index> {
+    /// Create a new `AssetHistory` with an unlimited amount of undo steps
+    pub fn new() -> Self {
+        AssetHistory {
+            max_steps: None,
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            group: None,
+        }
+    }
+
+    /// Create a new `AssetHistory` which keeps at most `max_steps` undo steps
+    pub fn with_max_steps(max_steps: usize) -> Self {
+        AssetHistory {
+            max_steps: Some(max_steps),
+            ..Self::new()
+        }
+    }
+
+    fn record(&mut self, change: Change<Index>) {
+        self.redo_stack.clear();
+
+        match self.group {
+            Some(ref mut group) => group.push(change),
+            None => self.push_step(vec![change]),
+        }
+    }
+
+    fn push_step(&mut self, step: Vec<Change<Index>>) {
+        if step.is_empty() {
+            return;
+        }
+
+        self.undo_stack.push(step);
+        if let Some(max_steps) = self.max_steps {
+            if self.undo_stack.len() > max_steps {
+                let excess = self.undo_stack.len() - max_steps;
+                self.undo_stack.drain(..excess);
+            }
+        }
+    }
+
+    /// Start grouping changes, all changes until [`AssetHistory::end_group`] is called
+    /// are undone and redone as a single step
+    pub fn begin_group(&mut self) {
+        if self.group.is_none() {
+            self.group = Some(Vec::new());
+        }
+    }
+
+    /// Stop grouping changes and record the group as a single step
+    pub fn end_group(&mut self) {
+        if let Some(group) = self.group.take() {
+            self.push_step(group);
+        }
+    }
+
+    /// Edit an export
+    ///
+    /// Only the edited export is snapshotted, if the edit doesn't change the export nothing is recorded.
+    /// Returns `None` if the export doesn't exist.
+    pub fn edit_export<T>(
+        &mut self,
+        asset: &mut AssetData<Index>,
+        index: PackageIndex,
+        edit: impl FnOnce(&mut Export<Index>) -> T,
+    ) -> Option<T> {
+        let export = asset.get_export_mut(index)?;
+
+        let old_export = export.clone();
+        let result = edit(export);
+
+        if *export != old_export {
+            self.record(Change::Modified {
+                index: (index.index - 1) as usize,
+                export: Box::new(old_export),
+            });
+        }
+
+        Some(result)
+    }
+
+    /// Add an export to the end of the export list
+    ///
+    /// Returns the new export's index.
+    pub fn add_export(
+        &mut self,
+        asset: &mut AssetData<Index>,
+        export: Export<Index>,
+    ) -> PackageIndex {
+        asset.exports.push(export);
+        let index = asset.exports.len() - 1;
+
+        self.record(Change::Inserted { index });
+        PackageIndex::from_export(index as i32).expect("Invalid export index")
+    }
+
+    /// Remove an export
+    ///
+    /// References to exports after the removed one are not updated.
+    /// Returns `None` if the export doesn't exist.
+    pub fn remove_export(
+        &mut self,
+        asset: &mut AssetData<Index>,
+        index: PackageIndex,
+    ) -> Option<Export<Index>> {
+        asset.get_export(index)?;
+
+        let index = (index.index - 1) as usize;
+        let export = asset.exports.remove(index);
+
+        self.record(Change::Removed {
+            index,
+            export: Box::new(export.clone()),
+        });
+        Some(export)
+    }
+
+    /// Undo the last step
+    ///
+    /// Returns `false` if there was nothing to undo.
+    pub fn undo(&mut self, asset: &mut AssetData<Index>) -> bool {
+        self.end_group();
+
+        let Some(step) = self.undo_stack.pop() else {
+            return false;
+        };
+
+        let inverse = step
+            .into_iter()
+            .rev()
+            .map(|change| change.apply(&mut asset.exports))
+            .collect();
+        self.redo_stack.push(inverse);
+
+        true
+    }
+
+    /// Redo the last undone step
+    ///
+    /// Returns `false` if there was nothing to redo.
+    pub fn redo(&mut self, asset: &mut AssetData<Index>) -> bool {
+        self.end_group();
+
+        let Some(step) = self.redo_stack.pop() else {
+            return false;
+        };
+
+        let inverse = step
+            .into_iter()
+            .rev()
+            .map(|change| change.apply(&mut asset.exports))
+            .collect();
+        self.undo_stack.push(inverse);
+
+        true
+    }
+
+    /// Check if there is anything to undo
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty() || self.group.as_ref().is_some_and(|e| !e.is_empty())
+    }
+
+    /// Check if there is anything to redo
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+
+    /// Forget all recorded changes
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+        self.group = None;
+    }
+}
+
+impl<Index: PackageIndexTrait> Default for AssetHistory<Index> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,186 @@
+//! Well-known imports
+//!
+//! Imports reference objects in other packages and have to form a chain, an import of a class
+//! is outered to an import of its package. [`Imports`] creates these chains and adds the names
+//! they use to the name map, reusing imports which already exist.
+
+use std::io::{Read, Seek};
+
+use unreal_asset_base::types::PackageIndex;
+
+use crate::Asset;
+
+/// Package of the core object classes
+pub const CORE_UOBJECT: &str = "/Script/CoreUObject";
+/// Package of the engine classes
+pub const ENGINE: &str = "/Script/Engine";
+
+/// Class of packages
+pub const PACKAGE_CLASS: &str = "Package";
+/// Class of native classes
+pub const CLASS_CLASS: &str = "Class";
+/// Class of native structs
+pub const SCRIPT_STRUCT_CLASS: &str = "ScriptStruct";
+/// Class of native enums
+pub const ENUM_CLASS: &str = "Enum";
+/// Class of functions
+pub const FUNCTION_CLASS: &str = "Function";
+/// Class of blueprint classes
+pub const BLUEPRINT_GENERATED_CLASS: &str = "BlueprintGeneratedClass";
+
+/// Prefix of class default object names
+pub const DEFAULT_OBJECT_PREFIX: &str = "Default__";
+
+/// Factory for well-known imports
+///
+/// Every method finds an existing import or adds it, together with all imports it's outered to.
+/// Package names without a leading `/` are treated as native modules, `Engine` is the same as `/Script/Engine`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use std::fs::File;
+///
+/// use unreal_asset::{engine_version::EngineVersion, Asset};
+///
+/// let file = File::open("NewDataTable.uasset").unwrap();
+/// let mut asset = Asset::new(file, None, EngineVersion::VER_UE4_25, None).unwrap();
+///
+/// let mut imports = asset.import_factory();
+/// let data_table = imports.class("Engine", "DataTable");
+/// let template = imports.default_object("Engine", "DataTable");
+/// ```
+pub struct Imports<'asset, C: Read + Seek> {
+    asset: &'asset mut Asset<C>,
+}
+
+impl<'asset, C: Read + Seek> Imports<'asset, C> {
+    /// Create a new `Imports` instance adding imports to an asset
+    pub fn new(asset: &'asset mut Asset<C>) -> Self {
+        Imports { asset }
+    }
+
+    /// Get the path of a package, native module names are prefixed with `/Script/`
+    pub fn package_path(package: &str) -> String {
+        match package.starts_with('/') {
+            true => package.to_string(),
+            false => format!("/Script/{package}"),
+        }
+    }
+
+    /// Find or add an import of a package
+    pub fn package(&mut self, package: &str) -> PackageIndex {
+        let package = Self::package_path(package);
+        self.asset
+            .find_or_add_import(CORE_UOBJECT, PACKAGE_CLASS, PackageIndex::new(0), &package)
+    }
+
+    /// Find or add an import of an object, `class_package` and `class_name` are the class of the object
+    pub fn object(
+        &mut self,
+        class_package: &str,
+        class_name: &str,
+        outer_index: PackageIndex,
+        object_name: &str,
+    ) -> PackageIndex {
+        let class_package = Self::package_path(class_package);
+        self.asset
+            .find_or_add_import(&class_package, class_name, outer_index, object_name)
+    }
+
+    /// Find or add an import of an object in a package, `class_package` and `class_name` are the class of the object
+    pub fn object_in_package(
+        &mut self,
+        package: &str,
+        class_package: &str,
+        class_name: &str,
+        object_name: &str,
+    ) -> PackageIndex {
+        let package = self.package(package);
+        self.object(class_package, class_name, package, object_name)
+    }
+
+    /// Find or add a chain of imports of nested objects in a package, returns the innermost object
+    ///
+    /// `objects` are the `(class_package, class_name, object_name)` of the objects from the outermost to the innermost,
+    /// every object is outered to the one before it and the first one to the package.
+    /// Returns the package if `objects` is empty.
+    pub fn chain(&mut self, package: &str, objects: &[(&str, &str, &str)]) -> PackageIndex {
+        let mut outer_index = self.package(package);
+        for &(class_package, class_name, object_name) in objects {
+            outer_index = self.object(class_package, class_name, outer_index, object_name);
+        }
+        outer_index
+    }
+
+    /// Find or add an import of a native class, e.g. `class("Engine", "DataTable")`
+    pub fn class(&mut self, package: &str, class_name: &str) -> PackageIndex {
+        self.object_in_package(package, CORE_UOBJECT, CLASS_CLASS, class_name)
+    }
+
+    /// Find or add an import of a native struct, e.g. `script_struct("CoreUObject", "Vector")`
+    pub fn script_struct(&mut self, package: &str, struct_name: &str) -> PackageIndex {
+        self.object_in_package(package, CORE_UOBJECT, SCRIPT_STRUCT_CLASS, struct_name)
+    }
+
+    /// Find or add an import of a native enum
+    pub fn enumeration(&mut self, package: &str, enum_name: &str) -> PackageIndex {
+        self.object_in_package(package, CORE_UOBJECT, ENUM_CLASS, enum_name)
+    }
+
+    /// Find or add an import of a blueprint class, e.g. `blueprint_class("/Game/BP_Door", "BP_Door_C")`
+    pub fn blueprint_class(&mut self, package: &str, class_name: &str) -> PackageIndex {
+        self.object_in_package(package, ENGINE, BLUEPRINT_GENERATED_CLASS, class_name)
+    }
+
+    /// Find or add an import of a function of a class
+    ///
+    /// `class` is the import of the class, e.g. returned by [`Imports::class`].
+    pub fn function(&mut self, class: PackageIndex, function_name: &str) -> PackageIndex {
+        self.object(CORE_UOBJECT, FUNCTION_CLASS, class, function_name)
+    }
+
+    /// Find or add an import of the class default object of a native class
+    ///
+    /// This is used as the template of exports of the class.
+    pub fn default_object(&mut self, package: &str, class_name: &str) -> PackageIndex {
+        let package_index = self.package(package);
+        self.object(
+            package,
+            class_name,
+            package_index,
+            &format!("{DEFAULT_OBJECT_PREFIX}{class_name}"),
+        )
+    }
+}
+
+impl<C: Read + Seek> Asset<C> {
+    /// Get a factory for adding well-known imports to this asset
+    pub fn import_factory(&mut self) -> Imports<'_, C> {
+        Imports::new(self)
+    }
+
+    /// Find or add a chain of imports of nested objects in a package, see [`Imports::chain`]
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use unreal_asset::{engine_version::EngineVersion, Asset};
+    ///
+    /// let mut asset = Asset::new_empty(EngineVersion::VER_UE4_25, true, None);
+    /// let component = asset.add_import_chain(
+    ///     "/Game/BP_Door",
+    ///     &[
+    ///         ("/Game/BP_Door", "BP_Door_C", "Default__BP_Door_C"),
+    ///         ("Engine", "StaticMeshComponent", "Mesh"),
+    ///     ],
+    /// );
+    /// ```
+    pub fn add_import_chain(
+        &mut self,
+        package: &str,
+        objects: &[(&str, &str, &str)],
+    ) -> PackageIndex {
+        self.import_factory().chain(package, objects)
+    }
+}
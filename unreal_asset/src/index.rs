@@ -0,0 +1,418 @@
+//! Index of the packages of a game install
+//!
+//! Finding every asset which references a package means looking at the header of every asset of a game.
+//! A [`SummaryIndex`] stores the [`AssetSummary`], export class names and referenced packages of every
+//! indexed package, so queries like [`SummaryIndex::find_referencing`] don't read any asset again.
+//! Indexes can be saved to a file and loaded again with [`SummaryIndex::save`] and [`SummaryIndex::load`].
+//!
+//! With the `pak` feature enabled, all paks of an install are indexed with [`SummaryIndex::scan_paks`].
+//! IoStore containers (`.utoc`/`.ucas`) are not supported.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{self, Cursor, Read, Seek, Write};
+use std::path::Path;
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+
+use unreal_helpers::{AtomicFile, UnrealWriteExt};
+
+use crate::cache::{read_string, AssetSummary};
+use crate::engine_version::EngineVersion;
+use crate::error::Error;
+use crate::Asset;
+
+/// Index file magic
+const INDEX_MAGIC: u32 = u32::from_be_bytes(*b"UAIX");
+/// Index file format version, bumped whenever the format of [`IndexedPackage`] changes
+const INDEX_VERSION: u32 = 1;
+
+/// Package in a [`SummaryIndex`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedPackage {
+    /// Package path, e.g. `/Game/Items/Conveyor`
+    pub package_path: String,
+    /// Where the package was found, e.g. the path of its pak
+    pub source: String,
+    /// Summary of the package header
+    pub summary: AssetSummary,
+    /// Packages referenced softly, e.g. by soft object paths
+    pub soft_references: Vec<String>,
+    /// Class names of all exports, in export order
+    pub class_names: Vec<String>,
+    /// Packages imported by this package
+    pub imported_packages: Vec<String>,
+}
+
+impl IndexedPackage {
+    /// Create a new `IndexedPackage` instance, class names and imported packages are taken from the summary
+    pub fn new(
+        package_path: impl Into<String>,
+        source: impl Into<String>,
+        summary: AssetSummary,
+        soft_references: Vec<String>,
+    ) -> Self {
+        let class_names = summary
+            .exports
+            .iter()
+            .map(|export| match export.class_index {
+                // exports without a class are classes themselves
+                0 => String::from("Class"),
+                index if index < 0 => summary
+                    .imports
+                    .get((-index - 1) as usize)
+                    .map(|e| e.object_name.clone())
+                    .unwrap_or_default(),
+                index => summary
+                    .exports
+                    .get((index - 1) as usize)
+                    .map(|e| e.object_name.clone())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        let mut imported_packages = Vec::new();
+        for import in &summary.imports {
+            if import.class_name == "Package" && !imported_packages.contains(&import.object_name) {
+                imported_packages.push(import.object_name.clone());
+            }
+        }
+
+        IndexedPackage {
+            package_path: package_path.into(),
+            source: source.into(),
+            summary,
+            soft_references,
+            class_names,
+            imported_packages,
+        }
+    }
+
+    /// Create an `IndexedPackage` from a parsed asset
+    pub fn from_asset<C: Read + Seek>(
+        package_path: impl Into<String>,
+        source: impl Into<String>,
+        asset: &Asset<C>,
+    ) -> Self {
+        let soft_references = asset
+            .get_soft_package_references()
+            .map(|e| e.to_vec())
+            .unwrap_or_default();
+
+        Self::new(
+            package_path,
+            source,
+            AssetSummary::from_asset(asset),
+            soft_references,
+        )
+    }
+
+    /// Check if this package imports or softly references a package
+    ///
+    /// Package paths are compared case-insensitively, like the engine does.
+    pub fn references(&self, package_path: &str) -> bool {
+        self.imported_packages
+            .iter()
+            .chain(&self.soft_references)
+            .any(|e| e.eq_ignore_ascii_case(package_path))
+    }
+}
+
+/// Index of packages by their package paths
+///
+/// # Examples
+///
+/// ```no_run
+/// use unreal_asset::{engine_version::EngineVersion, index::SummaryIndex};
+///
+/// let mut index = SummaryIndex::new();
+///
+/// let data = std::fs::read("Content/Items/Conveyor.uasset").unwrap();
+/// index
+///     .index_asset(
+///         "/Game/Items/Conveyor",
+///         "Content/Items/Conveyor.uasset",
+///         &data,
+///         true,
+///         EngineVersion::VER_UE4_23,
+///     )
+///     .unwrap();
+///
+/// for package in index.find_referencing("/Game/Items/Belt") {
+///     println!("{}", package.package_path);
+/// }
+/// index.save("index.bin").unwrap();
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SummaryIndex {
+    /// Indexed packages by package path
+    packages: BTreeMap<String, IndexedPackage>,
+}
+
+impl SummaryIndex {
+    /// Create a new empty `SummaryIndex` instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the amount of indexed packages
+    pub fn len(&self) -> usize {
+        self.packages.len()
+    }
+
+    /// Check if the index is empty
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty()
+    }
+
+    /// Insert a package, returns the package it replaced
+    pub fn insert(&mut self, package: IndexedPackage) -> Option<IndexedPackage> {
+        self.packages.insert(package.package_path.clone(), package)
+    }
+
+    /// Index an asset, replacing the package if it's already indexed
+    ///
+    /// Only the package header is parsed, so the .uexp of split assets is not needed.
+    /// `separate_bulk_data` tells if the asset has a .uexp file.
+    pub fn index_asset(
+        &mut self,
+        package_path: impl Into<String>,
+        source: impl Into<String>,
+        data: &[u8],
+        separate_bulk_data: bool,
+        engine_version: EngineVersion,
+    ) -> Result<(), Error> {
+        let bulk_data = match separate_bulk_data {
+            true => Some(Cursor::new(&[][..])),
+            false => None,
+        };
+        let asset = Asset::new_header_only(Cursor::new(data), bulk_data, engine_version)?;
+
+        self.insert(IndexedPackage::from_asset(package_path, source, &asset));
+        Ok(())
+    }
+
+    /// Get an indexed package
+    pub fn get(&self, package_path: &str) -> Option<&IndexedPackage> {
+        self.packages.get(package_path)
+    }
+
+    /// Iterate over all indexed packages, sorted by package path
+    pub fn packages(&self) -> impl Iterator<Item = &IndexedPackage> {
+        self.packages.values()
+    }
+
+    /// Find all packages which import or softly reference a package
+    pub fn find_referencing(&self, package_path: &str) -> Vec<&IndexedPackage> {
+        self.packages()
+            .filter(|e| e.references(package_path))
+            .collect()
+    }
+
+    /// Find all packages with an export of a class
+    pub fn find_by_class(&self, class_name: &str) -> Vec<&IndexedPackage> {
+        self.packages()
+            .filter(|e| e.class_names.iter().any(|name| name == class_name))
+            .collect()
+    }
+
+    /// Read a `SummaryIndex` from index data
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, Error> {
+        if reader.read_u32::<LE>()? != INDEX_MAGIC {
+            return Err(Error::invalid_file("Invalid index file magic".to_string()));
+        }
+
+        let version = reader.read_u32::<LE>()?;
+        if version != INDEX_VERSION {
+            return Err(Error::invalid_file(format!(
+                "Unsupported index file version {version}"
+            )));
+        }
+
+        let mut index = SummaryIndex::new();
+        for _ in 0..reader.read_u32::<LE>()? {
+            let package_path = read_string(reader)?;
+            let source = read_string(reader)?;
+
+            let mut soft_references = Vec::new();
+            for _ in 0..reader.read_u32::<LE>()? {
+                soft_references.push(read_string(reader)?);
+            }
+
+            let summary = AssetSummary::read(reader)?;
+            index.insert(IndexedPackage::new(
+                package_path,
+                source,
+                summary,
+                soft_references,
+            ));
+        }
+
+        Ok(index)
+    }
+
+    /// Write a `SummaryIndex` as index data
+    pub fn write<W: io::Write + Seek>(&self, writer: &mut W) -> Result<(), Error> {
+        writer.write_u32::<LE>(INDEX_MAGIC)?;
+        writer.write_u32::<LE>(INDEX_VERSION)?;
+
+        writer.write_u32::<LE>(self.packages.len() as u32)?;
+        for package in self.packages() {
+            writer.write_fstring(Some(&package.package_path))?;
+            writer.write_fstring(Some(&package.source))?;
+
+            writer.write_u32::<LE>(package.soft_references.len() as u32)?;
+            for reference in &package.soft_references {
+                writer.write_fstring(Some(reference))?;
+            }
+
+            package.summary.write(writer)?;
+        }
+
+        Ok(())
+    }
+
+    /// Load an index from a file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let data = fs::read(path)?;
+        Self::read(&mut Cursor::new(data))
+    }
+
+    /// Save the index to a file
+    ///
+    /// The index is written to a temporary file first, so an interrupted save never leaves a partial index.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Error> {
+        let mut data = Cursor::new(Vec::new());
+        self.write(&mut data)?;
+
+        let mut file = AtomicFile::create(path)?;
+        file.write_all(data.get_ref())?;
+        file.commit()?;
+
+        Ok(())
+    }
+}
+
+/// Pak or pak entry which couldn't be indexed by [`SummaryIndex::scan_paks`]
+#[cfg(feature = "pak")]
+#[derive(Debug)]
+pub struct IndexFailure {
+    /// Path of the pak
+    pub source: String,
+    /// Entry which couldn't be indexed, `None` if the pak itself couldn't be read
+    pub entry: Option<String>,
+    /// Error
+    pub error: Error,
+}
+
+#[cfg(feature = "pak")]
+impl SummaryIndex {
+    /// Index all packages in the paks of a directory and its subdirectories, e.g. `GameName/Content/Paks`
+    ///
+    /// `game_name` is the name of the game directory paks are mounted in, see [`unreal_pak::mount_point`].
+    /// Paks are indexed in order of their paths, so packages of patch paks (`_P.pak`), which sort after
+    /// the paks they patch, replace the packages they override.
+    /// Paks and entries which can't be read are skipped and returned as failures.
+    pub fn scan_paks(
+        &mut self,
+        directory: impl AsRef<Path>,
+        game_name: &str,
+        engine_version: EngineVersion,
+    ) -> Result<Vec<IndexFailure>, Error> {
+        let mut pak_paths = Vec::new();
+        find_paks(directory.as_ref(), &mut pak_paths)?;
+        pak_paths.sort();
+
+        let mut failures = Vec::new();
+        for pak_path in pak_paths {
+            let source = pak_path.to_string_lossy().into_owned();
+            if let Err(error) =
+                self.index_pak(&pak_path, &source, game_name, engine_version, &mut failures)
+            {
+                failures.push(IndexFailure {
+                    source,
+                    entry: None,
+                    error,
+                });
+            }
+        }
+
+        Ok(failures)
+    }
+
+    /// Index all packages in a pak
+    fn index_pak(
+        &mut self,
+        path: &Path,
+        source: &str,
+        game_name: &str,
+        engine_version: EngineVersion,
+        failures: &mut Vec<IndexFailure>,
+    ) -> Result<(), Error> {
+        let file = io::BufReader::new(fs::File::open(path)?);
+        let mut pak = unreal_pak::PakReader::new(file);
+        pak.load_index().map_err(pak_error)?;
+
+        let entry_names = pak
+            .get_entry_names()
+            .into_iter()
+            .filter(|e| e.ends_with(".uasset") || e.ends_with(".umap"))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for entry in entry_names {
+            let Some(package_path) = pak.get_object_path(game_name, &entry) else {
+                continue;
+            };
+
+            let bulk_entry = match entry.rsplit_once('.') {
+                Some((stem, _)) => format!("{stem}.uexp"),
+                None => continue,
+            };
+            let separate_bulk_data = pak.contains_entry(&bulk_entry);
+
+            let result = pak.read_entry(&entry).map_err(pak_error).and_then(|data| {
+                self.index_asset(
+                    package_path,
+                    source,
+                    &data,
+                    separate_bulk_data,
+                    engine_version,
+                )
+            });
+            if let Err(error) = result {
+                failures.push(IndexFailure {
+                    source: source.to_string(),
+                    entry: Some(entry),
+                    error,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Recursively find all paks in a directory
+#[cfg(feature = "pak")]
+fn find_paks(directory: &Path, pak_paths: &mut Vec<std::path::PathBuf>) -> Result<(), Error> {
+    for entry in fs::read_dir(directory)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            find_paks(&path, pak_paths)?;
+        } else if path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("pak"))
+        {
+            pak_paths.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Convert a pak error
+#[cfg(feature = "pak")]
+fn pak_error(error: unreal_pak::PakError) -> Error {
+    Error::invalid_file(error.to_string())
+}
@@ -0,0 +1,367 @@
+//! Kismet string literal scanning and patching
+//!
+//! Text mods often need to change strings embedded in blueprint bytecode.
+//! [`find_kismet_strings`] lists the string constants in the bytecode of all function exports,
+//! including the strings of text constants, and [`replace_kismet_string`] replaces one of them.
+//!
+//! Kismet code offsets are offsets into the in-memory bytecode, so changing the length of a string
+//! moves all code after it. When a string is replaced, jump targets, execution flow addresses,
+//! skip offsets, switch offsets and context skip sizes of its function are fixed up.
+//! If the string is in an ubergraph function, the entry points other functions pass to it are fixed up as well.
+
+use std::io::{Read, Seek};
+
+use unreal_asset_base::cast;
+use unreal_asset_base::reader::{RawWriter, SizeCalculatingWriter};
+use unreal_asset_base::types::PackageIndexTrait;
+use unreal_asset_exports::{Export, ExportBaseTrait};
+use unreal_asset_kismet::{EExprToken, ExStringConst, ExUnicodeStringConst, KismetExpression};
+
+use crate::asset_archive_writer::AssetArchiveWriter;
+use crate::error::Error;
+use crate::types::PackageIndex;
+use crate::Asset;
+
+/// Name prefix of ubergraph functions
+const UBERGRAPH_PREFIX: &str = "ExecuteUbergraph";
+
+/// String constant in kismet bytecode
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KismetString {
+    /// Function export the string is in
+    pub export: PackageIndex,
+    /// Path of the string expression, the index of its statement in the bytecode
+    /// followed by the child indices leading to it, see [`KismetExpression::children`]
+    pub path: Vec<usize>,
+    /// Is the string part of a text constant
+    pub in_text: bool,
+    /// Is the string a UTF-16 string constant
+    pub unicode: bool,
+    /// Value
+    pub value: String,
+}
+
+/// Find all string constants in the bytecode of function exports
+pub fn find_kismet_strings<C: Read + Seek>(asset: &Asset<C>) -> Vec<KismetString> {
+    let mut strings = Vec::new();
+
+    for (i, export) in asset.asset_data.exports.iter().enumerate() {
+        let Some(bytecode) = function_bytecode(export) else {
+            continue;
+        };
+        let export = PackageIndex::new(i as i32 + 1);
+
+        for (j, statement) in bytecode.iter().enumerate() {
+            find_strings(statement, export, &mut vec![j], false, &mut strings);
+        }
+    }
+
+    strings
+}
+
+/// Find all string constants in an expression
+fn find_strings(
+    expression: &KismetExpression,
+    export: PackageIndex,
+    path: &mut Vec<usize>,
+    in_text: bool,
+    strings: &mut Vec<KismetString>,
+) {
+    let (unicode, value) = match expression {
+        KismetExpression::ExStringConst(e) => (false, &e.value),
+        KismetExpression::ExUnicodeStringConst(e) => (true, &e.value),
+        _ => {
+            let in_text = in_text || matches!(expression, KismetExpression::ExTextConst(_));
+            for (i, child) in expression.children().into_iter().enumerate() {
+                path.push(i);
+                find_strings(child, export, path, in_text, strings);
+                path.pop();
+            }
+            return;
+        }
+    };
+
+    strings.push(KismetString {
+        export,
+        path: path.clone(),
+        in_text,
+        unicode,
+        value: value.clone(),
+    });
+}
+
+/// Replace a string constant found by [`find_kismet_strings`]
+///
+/// ASCII strings are written as ANSI string constants, other strings as UTF-16 string constants.
+/// Code offsets are fixed up for the new string size.
+pub fn replace_kismet_string<C: Read + Seek>(
+    asset: &mut Asset<C>,
+    string: &KismetString,
+    value: &str,
+) -> Result<(), Error> {
+    let not_found = || {
+        Error::no_data(format!(
+            "No kismet string at {:?} in export {}",
+            string.path, string.export.index
+        ))
+    };
+
+    let export_index = match string.export.is_export() {
+        true => string.export.index as usize - 1,
+        false => return Err(not_found()),
+    };
+    let export = asset
+        .asset_data
+        .exports
+        .get(export_index)
+        .ok_or_else(not_found)?;
+    let bytecode = function_bytecode(export).ok_or_else(not_found)?;
+    let statement = *string.path.first().ok_or_else(not_found)?;
+    let statement_offset = get_code_size(asset, bytecode.get(..statement).ok_or_else(not_found)?)?;
+    let is_ubergraph = export
+        .get_base_export()
+        .object_name
+        .get_content(|e| e.starts_with(UBERGRAPH_PREFIX));
+
+    let bytecode =
+        function_bytecode_mut(&mut asset.asset_data.exports[export_index]).ok_or_else(not_found)?;
+    let expression = get_expression_mut(bytecode, &string.path).ok_or_else(not_found)?;
+
+    let (unicode, old_value) = match expression {
+        KismetExpression::ExStringConst(e) => (false, &e.value),
+        KismetExpression::ExUnicodeStringConst(e) => (true, &e.value),
+        _ => return Err(not_found()),
+    };
+    let old_size = get_string_size(old_value, unicode);
+
+    let unicode = unicode || !value.is_ascii();
+    let new_size = get_string_size(value, unicode);
+    *expression = match unicode {
+        true => ExUnicodeStringConst {
+            token: EExprToken::ExUnicodeStringConst,
+            value: value.to_string(),
+        }
+        .into(),
+        false => ExStringConst {
+            token: EExprToken::ExStringConst,
+            value: value.to_string(),
+        }
+        .into(),
+    };
+
+    let delta = new_size - old_size;
+    if delta == 0 {
+        return Ok(());
+    }
+
+    for (i, statement) in bytecode.iter_mut().enumerate() {
+        fix_up_offsets(
+            statement,
+            &mut vec![i],
+            &string.path,
+            statement_offset,
+            delta,
+        );
+    }
+
+    if is_ubergraph {
+        for export in &mut asset.asset_data.exports {
+            let Some(bytecode) = function_bytecode_mut(export) else {
+                continue;
+            };
+            for statement in bytecode {
+                fix_up_entry_points(statement, string.export, statement_offset, delta);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Replace string constants in the bytecode of all function exports
+///
+/// `replace` returns the new value of a string, or `None` to keep it.
+/// Returns the amount of replaced strings.
+pub fn replace_kismet_strings<C: Read + Seek>(
+    asset: &mut Asset<C>,
+    mut replace: impl FnMut(&KismetString) -> Option<String>,
+) -> Result<usize, Error> {
+    let mut replaced = 0;
+    for string in find_kismet_strings(asset) {
+        if let Some(value) = replace(&string) {
+            replace_kismet_string(asset, &string, &value)?;
+            replaced += 1;
+        }
+    }
+    Ok(replaced)
+}
+
+/// Get the bytecode of a function export
+fn function_bytecode(export: &Export<PackageIndex>) -> Option<&Vec<KismetExpression>> {
+    cast!(Export, FunctionExport, export)?
+        .struct_export
+        .script_bytecode
+        .as_ref()
+}
+
+/// Get the bytecode of a function export mutably
+fn function_bytecode_mut(export: &mut Export<PackageIndex>) -> Option<&mut Vec<KismetExpression>> {
+    cast!(Export, FunctionExport, export)?
+        .struct_export
+        .script_bytecode
+        .as_mut()
+}
+
+/// Get an expression by its path
+fn get_expression_mut<'a>(
+    bytecode: &'a mut [KismetExpression],
+    path: &[usize],
+) -> Option<&'a mut KismetExpression> {
+    let (statement, path) = path.split_first()?;
+    let mut expression = bytecode.get_mut(*statement)?;
+    for i in path {
+        expression = expression.children_mut().into_iter().nth(*i)?;
+    }
+    Some(expression)
+}
+
+/// Get the in-memory size of string constant
+fn get_string_size(value: &str, unicode: bool) -> i64 {
+    match unicode {
+        true => (value.encode_utf16().count() as i64 + 1) * 2,
+        false => value.len() as i64 + 1,
+    }
+}
+
+/// Get the in-memory size of bytecode
+fn get_code_size<C: Read + Seek>(
+    asset: &Asset<C>,
+    bytecode: &[KismetExpression],
+) -> Result<u32, Error> {
+    let mut cursor = SizeCalculatingWriter::new();
+    let mut raw_writer = RawWriter::<PackageIndex, _>::new(
+        &mut cursor,
+        asset.asset_data.object_version,
+        asset.asset_data.object_version_ue5,
+        asset.asset_data.use_event_driven_loader,
+        asset.get_name_map(),
+    );
+    let mut writer = AssetArchiveWriter::new(
+        &mut raw_writer,
+        &asset.asset_data,
+        &asset.imports,
+        asset.get_name_map(),
+    );
+
+    let mut size = 0;
+    for expression in bytecode {
+        size += KismetExpression::write(expression, &mut writer)?;
+    }
+    Ok(size as u32)
+}
+
+/// Move an offset by `delta`
+fn shift(offset: &mut u32, delta: i64) {
+    *offset = (*offset as i64 + delta) as u32;
+}
+
+/// Move an offset by `delta` if it points to code after the start of the changed statement
+fn shift_after(offset: &mut u32, statement_offset: u32, delta: i64) {
+    if *offset > statement_offset {
+        shift(offset, delta);
+    }
+}
+
+/// Fix up the code offsets of an expression and its children after a string changed size
+///
+/// Absolute offsets all point to the start of a statement, except for switch offsets,
+/// which are moved if they point past the string.
+/// Skip sizes are changed if they skip over the string.
+fn fix_up_offsets(
+    expression: &mut KismetExpression,
+    path: &mut Vec<usize>,
+    string_path: &[usize],
+    statement_offset: u32,
+    delta: i64,
+) {
+    // child index leading to the string, if this expression contains it
+    let string_child = match string_path.starts_with(path) {
+        true => string_path.get(path.len()).copied(),
+        false => None,
+    };
+
+    match expression {
+        KismetExpression::ExJump(e) => shift_after(&mut e.code_offset, statement_offset, delta),
+        KismetExpression::ExJumpIfNot(e) => {
+            shift_after(&mut e.code_offset, statement_offset, delta)
+        }
+        KismetExpression::ExPushExecutionFlow(e) => {
+            shift_after(&mut e.pushing_address, statement_offset, delta)
+        }
+        KismetExpression::ExSkipOffsetConst(e) => {
+            shift_after(&mut e.value, statement_offset, delta)
+        }
+        // the skip size covers the context expression, the second child
+        KismetExpression::ExContext(e) if string_child == Some(1) => shift(&mut e.offset, delta),
+        KismetExpression::ExContextFailSilent(e) if string_child == Some(1) => {
+            shift(&mut e.offset, delta)
+        }
+        KismetExpression::ExClassContext(e) if string_child == Some(1) => {
+            shift(&mut e.offset, delta)
+        }
+        KismetExpression::ExSkip(e) if string_child == Some(0) => shift(&mut e.code_offset, delta),
+        KismetExpression::ExSwitchValue(e) => match string_child {
+            Some(child) => {
+                // the index term is followed by the value and result terms of every case
+                for (i, case) in e.cases.iter_mut().enumerate() {
+                    if child <= 2 + i * 2 {
+                        shift(&mut case.next_offset, delta);
+                    }
+                }
+                shift(&mut e.end_goto_offset, delta);
+            }
+            None if path.as_slice() > string_path => {
+                for case in &mut e.cases {
+                    shift(&mut case.next_offset, delta);
+                }
+                shift(&mut e.end_goto_offset, delta);
+            }
+            None => {}
+        },
+        _ => {}
+    }
+
+    for (i, child) in expression.children_mut().into_iter().enumerate() {
+        path.push(i);
+        fix_up_offsets(child, path, string_path, statement_offset, delta);
+        path.pop();
+    }
+}
+
+/// Fix up the entry points passed to an ubergraph function after a string in it changed size
+fn fix_up_entry_points(
+    expression: &mut KismetExpression,
+    ubergraph: PackageIndex,
+    statement_offset: u32,
+    delta: i64,
+) {
+    let parameters = match expression {
+        KismetExpression::ExFinalFunction(e) if e.stack_node == ubergraph => {
+            Some(&mut e.parameters)
+        }
+        KismetExpression::ExLocalFinalFunction(e) if e.stack_node == ubergraph => {
+            Some(&mut e.parameters)
+        }
+        _ => None,
+    };
+    if let Some(KismetExpression::ExIntConst(entry_point)) = parameters.and_then(|e| e.first_mut())
+    {
+        if entry_point.value as u32 > statement_offset {
+            entry_point.value = (entry_point.value as i64 + delta) as i32;
+        }
+    }
+
+    for child in expression.children_mut() {
+        fix_up_entry_points(child, ubergraph, statement_offset, delta);
+    }
+}
@@ -55,6 +55,7 @@ pub use base::object_version;
 pub use base::reader;
 pub use base::types;
 pub use base::unversioned;
+pub use base::warning;
 
 pub use base::cast;
 pub use base::Guid;
@@ -65,8 +66,10 @@ pub use import::Import;
 pub use unreal_asset_properties as properties;
 
 // kismet
+#[cfg(feature = "kismet")]
 pub use unreal_asset_kismet as kismet;
 
+#[cfg(feature = "kismet")]
 pub use kismet::KismetExpression;
 
 // exports
@@ -78,6 +81,7 @@ pub use exports::properties::uproperty;
 pub use exports::Export;
 
 // registry
+#[cfg(feature = "registry")]
 pub use unreal_asset_registry as registry;
 
 // modules
@@ -85,8 +89,23 @@ pub mod ac7;
 pub mod asset;
 pub mod asset_archive_writer;
 pub mod asset_data;
+pub mod cache;
+pub mod convert;
+pub mod display;
+pub mod dump;
+pub mod events;
+pub mod extras;
 pub mod fengineversion;
+pub mod history;
+pub mod imports;
+pub mod index;
+#[cfg(feature = "kismet")]
+pub mod kismet_strings;
+pub mod object_redirector;
 pub mod package_file_summary;
+pub mod project;
+#[cfg(feature = "test-support")]
+pub mod test_support;
 
 pub use asset::Asset;
 
@@ -0,0 +1,153 @@
+//! ObjectRedirector package creation
+//!
+//! Object redirectors are left behind by the editor when content is moved,
+//! they keep references to the old object path working by pointing the loader at the new one.
+
+use std::io::Cursor;
+
+use byteorder::{WriteBytesExt, LE};
+
+use unreal_asset_base::{
+    engine_version::EngineVersion, flags::EObjectFlags, types::PackageIndex, Error, Import,
+};
+use unreal_asset_exports::{BaseExport, Export, NormalExport};
+
+use crate::Asset;
+
+/// Split an object path like `/Game/Folder/Asset.Asset` into a package path and an object name
+///
+/// If the path doesn't contain an object name, the asset name is used.
+fn split_object_path(path: &str) -> (&str, &str) {
+    match path.split_once('.') {
+        Some((package, object)) => (package, object),
+        None => (path, path.rsplit('/').next().unwrap_or(path)),
+    }
+}
+
+/// Create a package containing a single ObjectRedirector export
+///
+/// * `redirector_path` - package path of the redirector, e.g. `/Game/Old/Asset`
+/// * `target_path` - object path the redirector points at, e.g. `/Game/New/Asset.Asset`
+/// * `target_class_path` - class path of the target object, e.g. `/Script/Engine.Blueprint`
+///
+/// The created asset can be written with [`Asset::write_data`]
+/// and should be saved at the location of `redirector_path`.
+///
+/// # Example
+///
+/// ```no_run
+/// use std::io::Cursor;
+///
+/// use unreal_asset::{engine_version::EngineVersion, object_redirector::create_object_redirector};
+///
+/// let asset = create_object_redirector(
+///     EngineVersion::VER_UE4_23,
+///     true,
+///     "/Game/Old/Asset",
+///     "/Game/New/Asset.Asset",
+///     "/Script/Engine.Blueprint",
+/// )
+/// .unwrap();
+///
+/// let mut uasset = Cursor::new(Vec::new());
+/// let mut uexp = Cursor::new(Vec::new());
+/// asset.write_data(&mut uasset, Some(&mut uexp)).unwrap();
+/// ```
+pub fn create_object_redirector(
+    engine_version: EngineVersion,
+    use_event_driven_loader: bool,
+    redirector_path: &str,
+    target_path: &str,
+    target_class_path: &str,
+) -> Result<Asset<Cursor<Vec<u8>>>, Error> {
+    let (target_package, target_object) = split_object_path(target_path);
+    let (target_class_package, target_class_name) = target_class_path
+        .split_once('.')
+        .ok_or_else(|| Error::invalid_file(format!("Invalid class path {target_class_path}")))?;
+    let redirector_name = redirector_path
+        .rsplit('/')
+        .next()
+        .unwrap_or(redirector_path);
+
+    let mut asset = Asset::new_empty(engine_version, use_event_driven_loader, None);
+    asset.generate_package_guid(redirector_path);
+
+    // written after the properties of every export, needs to exist before the name map is written
+    asset.add_fname("None");
+
+    let core_uobject = asset.add_fname("/Script/CoreUObject");
+    let package = asset.add_fname("Package");
+    let class = asset.add_fname("Class");
+    let object_redirector = asset.add_fname("ObjectRedirector");
+    let default_object_redirector = asset.add_fname("Default__ObjectRedirector");
+    let target_package = asset.add_fname(target_package);
+    let target_class_package = asset.add_fname(target_class_package);
+    let target_class_name = asset.add_fname(target_class_name);
+    let target_object = asset.add_fname(target_object);
+    let redirector_name = asset.add_fname(redirector_name);
+
+    let core_uobject_import = asset.add_import(Import::new(
+        core_uobject.clone(),
+        package.clone(),
+        PackageIndex::new(0),
+        core_uobject.clone(),
+        false,
+    ));
+    let class_import = asset.add_import(Import::new(
+        core_uobject.clone(),
+        class,
+        core_uobject_import,
+        object_redirector.clone(),
+        false,
+    ));
+    let template_import = asset.add_import(Import::new(
+        core_uobject.clone(),
+        object_redirector,
+        core_uobject_import,
+        default_object_redirector,
+        false,
+    ));
+
+    let target_package_import = asset.add_import(Import::new(
+        core_uobject,
+        package,
+        PackageIndex::new(0),
+        target_package,
+        false,
+    ));
+    let target_import = asset.add_import(Import::new(
+        target_class_package,
+        target_class_name,
+        target_package_import,
+        target_object,
+        false,
+    ));
+
+    // UObject guid flag followed by the destination object
+    let mut extras = Vec::with_capacity(8);
+    extras.write_i32::<LE>(0)?;
+    extras.write_i32::<LE>(target_import.index)?;
+
+    let base_export = BaseExport {
+        class_index: class_import,
+        template_index: template_import,
+        object_name: redirector_name,
+        object_flags: EObjectFlags::RF_PUBLIC | EObjectFlags::RF_STANDALONE,
+        is_asset: true,
+        serialization_before_create_dependencies: vec![class_import, template_import],
+        create_before_serialization_dependencies: vec![target_import],
+        ..Default::default()
+    };
+
+    asset
+        .asset_data
+        .exports
+        .push(Export::NormalExport(NormalExport {
+            base_export,
+            extras,
+            padding_alignment: None,
+            properties: Vec::new(),
+        }));
+
+    Ok(asset)
+}
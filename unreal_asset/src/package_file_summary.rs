@@ -1,10 +1,25 @@
 //! Package(asset) file summary
 
+use std::io::{Cursor, Read, Seek, SeekFrom};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, BE, LE};
+
 use unreal_asset_base::{
+    compression::{self, CompressionMethod},
+    containers::{Chain, NameMap},
     custom_version::{CustomVersion, CustomVersionTrait},
+    engine_version::{get_object_versions, EngineVersion},
+    enums::ECustomVersionSerializationFormat,
     flags::EPackageFlags,
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    reader::{ArchiveReader, ArchiveWriter, GameQuirks, RawReader, RawWriter},
+    types::{GenerationInfo, PackageIndex, PackageIndexTrait},
+    Error, Guid,
 };
 
+use crate::fengineversion::FEngineVersion;
+use crate::UE4_ASSET_MAGIC;
+
 /// Package file summary
 #[derive(Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct PackageFileSummary {
@@ -16,6 +31,8 @@ pub struct PackageFileSummary {
     pub import_count: i32,
     /// File licensee version
     pub file_licensee_version: i32,
+    /// Extra field after the licensee version, see [`GameQuirks::SUMMARY_LICENSEE_FIELD`]
+    pub licensee_field: Option<i32>,
     /// Custom versions
     pub custom_versions: Vec<CustomVersion>,
     /// Is unversioned
@@ -40,3 +57,791 @@ impl PackageFileSummary {
             .unwrap_or_else(|| CustomVersion::new(T::GUID, 0))
     }
 }
+
+/// Tag at the start of every compressed chunk
+const PACKAGE_FILE_TAG: i64 = 0x9E2A83C1;
+/// Block size of compressed chunks which store the tag instead of their block size
+const LOADING_COMPRESSION_CHUNK_SIZE: i64 = 0x20000;
+
+/// Zlib package compression flag
+const COMPRESS_ZLIB: u32 = 0x01;
+/// Gzip package compression flag
+const COMPRESS_GZIP: u32 = 0x02;
+/// Package compression flags selecting the compression method, the other flags are only hints for the compressor
+const COMPRESSION_METHOD_MASK: u32 = 0x0f;
+
+/// Compressed chunk of a package saved with package compression
+///
+/// The summary is stored uncompressed, everything after it is split into chunks which are compressed separately.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct FCompressedChunk {
+    /// Offset of the chunk in the uncompressed package
+    pub uncompressed_offset: i32,
+    /// Uncompressed size of the chunk
+    pub uncompressed_size: i32,
+    /// Offset of the chunk in the compressed package
+    pub compressed_offset: i32,
+    /// Compressed size of the chunk, including its block table
+    pub compressed_size: i32,
+}
+
+/// Complete serialized package file summary, the header at the start of every asset
+///
+/// Can be read and written on its own without parsing the rest of the asset,
+/// e.g. to inspect or patch header fields like the package flags or the package guid.
+/// [`Asset`](crate::Asset) reads and writes its header through this type.
+///
+/// Offsets and counts are stored as they are serialized, changing them without moving
+/// the data they point to corrupts the asset.
+#[derive(Debug, Clone)]
+pub struct FPackageFileSummary {
+    /// Legacy file version, negative for all UE4 and UE5 assets
+    pub legacy_file_version: i32,
+    /// Legacy UE3 version, always 864 in versioned assets and 0 in unversioned assets
+    pub legacy_ue3_version: i32,
+    /// Is unversioned, the object versions are then taken from the engine version the summary is read with
+    pub unversioned: bool,
+    /// Object version
+    pub object_version: ObjectVersion,
+    /// UE5 object version
+    pub object_version_ue5: ObjectVersionUE5,
+    /// File licensee version
+    pub file_licensee_version: i32,
+    /// Extra field after the licensee version, see [`GameQuirks::SUMMARY_LICENSEE_FIELD`]
+    ///
+    /// Only read with the quirk set, written if it's `Some`.
+    pub licensee_field: Option<i32>,
+    /// Custom versions
+    pub custom_versions: Vec<CustomVersion>,
+    /// Total size of the header
+    pub header_offset: i32,
+    /// Folder name
+    pub folder_name: String,
+    /// Package flags
+    pub package_flags: EPackageFlags,
+    /// Name count
+    pub name_count: i32,
+    /// Name map offset
+    pub name_offset: i32,
+    /// Soft object paths count
+    pub soft_object_paths_count: i32,
+    /// Soft object paths offset
+    pub soft_object_paths_offset: i32,
+    /// Localization id
+    pub localization_id: Option<String>,
+    /// Gatherable text data count
+    pub gatherable_text_data_count: i32,
+    /// Gatherable text data offset
+    pub gatherable_text_data_offset: i32,
+    /// Export count
+    pub export_count: i32,
+    /// Exports offset
+    pub export_offset: i32,
+    /// Import count
+    pub import_count: i32,
+    /// Imports offset
+    pub import_offset: i32,
+    /// Dependencies offset
+    pub depends_offset: i32,
+    /// Soft package reference count
+    pub soft_package_reference_count: i32,
+    /// Soft package reference offset
+    pub soft_package_reference_offset: i32,
+    /// Searchable names offset
+    pub searchable_names_offset: i32,
+    /// Thumbnail table offset
+    pub thumbnail_table_offset: i32,
+    /// Package guid
+    pub package_guid: Guid,
+    /// Persistent guid, only serialized in assets with editor only data
+    pub persistent_guid: Option<Guid>,
+    /// Owner persistent guid, only serialized in assets with editor only data
+    pub owner_persistent_guid: Option<Guid>,
+    /// Generations
+    pub generations: Vec<GenerationInfo>,
+    /// Engine version this asset was saved with
+    pub engine_version_recorded: FEngineVersion,
+    /// Oldest engine version this asset is compatible with
+    pub engine_version_compatible: FEngineVersion,
+    /// Compression flags
+    pub compression_flags: u32,
+    /// Compressed chunks of packages saved with package compression
+    pub compressed_chunks: Vec<FCompressedChunk>,
+    /// Package source
+    pub package_source: u32,
+    /// Asset registry data offset
+    pub asset_registry_data_offset: i32,
+    /// Bulk data start offset
+    pub bulk_data_start_offset: i64,
+    /// World tile info offset
+    pub world_tile_info_offset: i32,
+    /// Chunk ids
+    pub chunk_ids: Vec<i32>,
+    /// Preload dependency count
+    pub preload_dependency_count: i32,
+    /// Preload dependency offset
+    pub preload_dependency_offset: i32,
+    /// Names referenced from export data count
+    pub names_referenced_from_export_data_count: i32,
+    /// Payload table of contents offset
+    pub payload_toc_offset: i64,
+    /// Data resource offset
+    pub data_resource_offset: i32,
+}
+
+/// Check a count read from the asset header against its limit
+fn check_count(what: &str, count: i32, limit: usize) -> Result<(), Error> {
+    if count < 0 {
+        return Err(Error::invalid_file(format!("Negative {what} {count}")));
+    }
+
+    if count as u64 > limit as u64 {
+        return Err(Error::limit_exceeded(what, count as u64, limit as u64));
+    }
+
+    Ok(())
+}
+
+/// Get the custom version serialization format used by a legacy file version
+pub(crate) fn get_custom_version_serialization_format(
+    legacy_file_version: i32,
+) -> ECustomVersionSerializationFormat {
+    if legacy_file_version > 3 {
+        return ECustomVersionSerializationFormat::Enums;
+    }
+    if legacy_file_version > -6 {
+        return ECustomVersionSerializationFormat::Guids;
+    }
+    ECustomVersionSerializationFormat::Optimized
+}
+
+impl FPackageFileSummary {
+    /// Read a summary from a binary file
+    ///
+    /// `engine_version` is needed for unversioned assets,
+    /// `quirks` are the same quirks the asset would be parsed with.
+    pub fn from_reader<C: Read + Seek>(
+        reader: C,
+        engine_version: EngineVersion,
+        quirks: GameQuirks,
+    ) -> Result<Self, Error> {
+        let (object_version, object_version_ue5) = get_object_versions(engine_version);
+        let mut reader = RawReader::<PackageIndex, C>::new(
+            Chain::new(reader, None),
+            object_version,
+            object_version_ue5,
+            false,
+            NameMap::new(),
+        );
+        reader.parse_options.quirks = quirks;
+
+        let custom_versions = CustomVersion::get_default_custom_version_container(engine_version);
+        Self::read(&mut reader, Some(&custom_versions))
+    }
+
+    /// Read a summary from an archive positioned at the start of the asset
+    ///
+    /// The object versions of the archive are used if the asset is unversioned.
+    /// Custom versions of the mappings and of `old_custom_versions` which are not serialized
+    /// in the asset are added to the custom versions.
+    pub fn read<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        archive: &mut Reader,
+        old_custom_versions: Option<&[CustomVersion]>,
+    ) -> Result<Self, Error> {
+        // read and check magic
+        if archive.read_u32::<BE>()? != UE4_ASSET_MAGIC {
+            return Err(Error::invalid_file(
+                "File is not a valid uasset file".to_string(),
+            ));
+        }
+
+        // read legacy version
+        let legacy_file_version = archive.read_i32::<LE>()?;
+        let legacy_ue3_version = match legacy_file_version != -4 {
+            // LegacyUE3Version for backwards-compatibility with UE3 games: always 864 in versioned assets, always 0 in unversioned assets
+            true => archive.read_i32::<LE>()?,
+            false => 0,
+        };
+
+        // read unreal version
+        let file_version: ObjectVersion = archive.read_i32::<LE>()?.try_into()?;
+
+        let unversioned = file_version == ObjectVersion::UNKNOWN;
+        let object_version = match unversioned {
+            true => {
+                if archive.get_object_version() == ObjectVersion::UNKNOWN {
+                    return Err(Error::invalid_file("Cannot begin serialization of an unversioned asset before an engine version is manually specified".to_string()));
+                }
+                archive.get_object_version()
+            }
+            false => file_version,
+        };
+
+        let mut object_version_ue5 = archive.get_object_version_ue5();
+        if legacy_file_version <= -8 {
+            let file_version_ue5: ObjectVersionUE5 = archive.read_i32::<LE>()?.try_into()?;
+            if file_version_ue5 > ObjectVersionUE5::UNKNOWN {
+                object_version_ue5 = file_version_ue5;
+            }
+        }
+
+        if object_version_ue5 == ObjectVersionUE5::UNKNOWN {
+            let mappings_version = archive
+                .get_mappings()
+                .map(|e| e.object_version_ue5)
+                .unwrap_or(ObjectVersionUE5::UNKNOWN);
+            if mappings_version > ObjectVersionUE5::UNKNOWN {
+                object_version_ue5 = mappings_version;
+            }
+        }
+
+        // read file license version
+        let file_licensee_version = archive.read_i32::<LE>()?;
+        let licensee_field = match archive
+            .get_quirks()
+            .contains(GameQuirks::SUMMARY_LICENSEE_FIELD)
+        {
+            true => Some(archive.read_i32::<LE>()?),
+            false => None,
+        };
+
+        // read custom versions container
+        let custom_versions = match legacy_file_version <= -2 {
+            // TODO: support for enum-based custom versions
+            true => archive.read_custom_version_container(
+                get_custom_version_serialization_format(legacy_file_version),
+                old_custom_versions,
+            )?,
+            false => old_custom_versions.map(|e| e.to_vec()).unwrap_or_default(),
+        };
+
+        // read header offset
+        let header_offset = archive.read_i32::<LE>()?;
+
+        // read folder name
+        let folder_name = archive
+            .read_fstring()?
+            .ok_or_else(|| Error::no_data("folder_name is None".to_string()))?;
+
+        // read package flags
+        let package_flags = EPackageFlags::from_bits(archive.read_u32::<LE>()?)
+            .ok_or_else(|| Error::invalid_file("Invalid package flags".to_string()))?;
+        let has_editor_only_data = package_flags.has_editor_only_data();
+
+        // read name count and offset
+        let name_count = archive.read_i32::<LE>()?;
+        let name_offset = archive.read_i32::<LE>()?;
+
+        let (soft_object_paths_count, soft_object_paths_offset) =
+            match object_version_ue5 >= ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST {
+                true => (archive.read_i32::<LE>()?, archive.read_i32::<LE>()?),
+                false => (0, 0),
+            };
+
+        // read localization id
+        let mut localization_id = None;
+        if has_editor_only_data
+            && object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_SUMMARY_LOCALIZATION_ID
+        {
+            localization_id = archive.read_fstring()?;
+        }
+
+        // read text gatherable data
+        let (gatherable_text_data_count, gatherable_text_data_offset) =
+            match object_version >= ObjectVersion::VER_UE4_SERIALIZE_TEXT_IN_PACKAGES {
+                true => (archive.read_i32::<LE>()?, archive.read_i32::<LE>()?),
+                false => (0, 0),
+            };
+
+        // read count and offset for exports, imports, depends, soft package references, searchable names, thumbnail table
+        let export_count = archive.read_i32::<LE>()?;
+        let export_offset = archive.read_i32::<LE>()?;
+        let import_count = archive.read_i32::<LE>()?;
+        let import_offset = archive.read_i32::<LE>()?;
+
+        let parse_options = archive.get_parse_options();
+        check_count("Name count", name_count, parse_options.max_name_count)?;
+        check_count("Export count", export_count, parse_options.max_export_count)?;
+        check_count("Import count", import_count, parse_options.max_import_count)?;
+
+        let depends_offset = archive.read_i32::<LE>()?;
+        let (soft_package_reference_count, soft_package_reference_offset) =
+            match object_version >= ObjectVersion::VER_UE4_ADD_STRING_ASSET_REFERENCES_MAP {
+                true => (archive.read_i32::<LE>()?, archive.read_i32::<LE>()?),
+                false => (0, 0),
+            };
+        let searchable_names_offset =
+            match object_version >= ObjectVersion::VER_UE4_ADDED_SEARCHABLE_NAMES {
+                true => archive.read_i32::<LE>()?,
+                false => 0,
+            };
+        let thumbnail_table_offset = archive.read_i32::<LE>()?;
+
+        // read guid
+        let package_guid = archive.read_guid()?;
+
+        // read persistent guids
+        let mut persistent_guid = None;
+        let mut owner_persistent_guid = None;
+        if has_editor_only_data {
+            if object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_OWNER {
+                persistent_guid = Some(archive.read_guid()?);
+            }
+            if object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_OWNER
+                && object_version < ObjectVersion::VER_UE4_NON_OUTER_PACKAGE_IMPORT
+            {
+                owner_persistent_guid = Some(archive.read_guid()?);
+            }
+        }
+
+        // read generations
+        let generations_count = archive.read_i32::<LE>()?;
+        let generations_count = archive.check_array_len(generations_count)?;
+        let mut generations = Vec::with_capacity(generations_count);
+        for _ in 0..generations_count {
+            let export_count = archive.read_i32::<LE>()?;
+            let name_count = archive.read_i32::<LE>()?;
+            generations.push(GenerationInfo::new(export_count, name_count));
+        }
+
+        // read advanced engine version
+        let engine_version_recorded =
+            match object_version >= ObjectVersion::VER_UE4_ENGINE_VERSION_OBJECT {
+                true => FEngineVersion::read(archive)?,
+                false => FEngineVersion::new(4, 0, 0, archive.read_u32::<LE>()?, None),
+            };
+        let engine_version_compatible = match object_version
+            >= ObjectVersion::VER_UE4_PACKAGE_SUMMARY_HAS_COMPATIBLE_ENGINE_VERSION
+        {
+            true => FEngineVersion::read(archive)?,
+            false => engine_version_recorded.clone(),
+        };
+
+        // read compression data
+        let compression_flags = archive.read_u32::<LE>()?;
+        let compressed_chunk_count = archive.read_i32::<LE>()?;
+        check_count(
+            "Compressed chunk count",
+            compressed_chunk_count,
+            parse_options.max_array_len,
+        )?;
+        let mut compressed_chunks = Vec::with_capacity(compressed_chunk_count as usize);
+        for _ in 0..compressed_chunk_count {
+            compressed_chunks.push(FCompressedChunk {
+                uncompressed_offset: archive.read_i32::<LE>()?,
+                uncompressed_size: archive.read_i32::<LE>()?,
+                compressed_offset: archive.read_i32::<LE>()?,
+                compressed_size: archive.read_i32::<LE>()?,
+            });
+        }
+
+        let package_source = archive.read_u32::<LE>()?;
+
+        // some other old unsupported stuff
+        let additional_to_cook = archive.read_i32::<LE>()?;
+        if additional_to_cook != 0 {
+            return Err(Error::invalid_file(
+                "Additional to cook is not zero".to_string(),
+            ));
+        }
+        if legacy_file_version > -7 {
+            let texture_allocations_count = archive.read_i32::<LE>()?;
+            if texture_allocations_count != 0 {
+                return Err(Error::invalid_file(
+                    "Texture allocations count is not zero".to_string(),
+                ));
+            }
+        }
+
+        let asset_registry_data_offset = archive.read_i32::<LE>()?;
+        let bulk_data_start_offset = archive.read_i64::<LE>()?;
+
+        let world_tile_info_offset = match object_version >= ObjectVersion::VER_UE4_WORLD_LEVEL_INFO
+        {
+            true => archive.read_i32::<LE>()?,
+            false => 0,
+        };
+
+        let mut chunk_ids = Vec::new();
+        if object_version >= ObjectVersion::VER_UE4_CHANGED_CHUNKID_TO_BE_AN_ARRAY_OF_CHUNKIDS {
+            let chunk_id_count = archive.read_i32::<LE>()?;
+            let chunk_id_count = archive.check_array_len(chunk_id_count)?;
+            for _ in 0..chunk_id_count {
+                chunk_ids.push(archive.read_i32::<LE>()?);
+            }
+        } else if object_version >= ObjectVersion::VER_UE4_ADDED_CHUNKID_TO_ASSETDATA_AND_UPACKAGE {
+            // single chunk id, INDEX_NONE if the package isn't assigned to a chunk
+            let chunk_id = archive.read_i32::<LE>()?;
+            if chunk_id >= 0 {
+                chunk_ids.push(chunk_id);
+            }
+        }
+
+        let (preload_dependency_count, preload_dependency_offset) =
+            match object_version >= ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS {
+                true => (archive.read_i32::<LE>()?, archive.read_i32::<LE>()?),
+                false => (0, 0),
+            };
+
+        let names_referenced_from_export_data_count =
+            match object_version_ue5 >= ObjectVersionUE5::NAMES_REFERENCED_FROM_EXPORT_DATA {
+                true => archive.read_i32::<LE>()?,
+                false => name_count,
+            };
+
+        let payload_toc_offset = match object_version_ue5 >= ObjectVersionUE5::PAYLOAD_TOC {
+            true => archive.read_i64::<LE>()?,
+            false => 0,
+        };
+
+        let data_resource_offset = match object_version_ue5 >= ObjectVersionUE5::DATA_RESOURCES {
+            true => archive.read_i32::<LE>()?,
+            false => 0,
+        };
+
+        Ok(FPackageFileSummary {
+            legacy_file_version,
+            legacy_ue3_version,
+            unversioned,
+            object_version,
+            object_version_ue5,
+            file_licensee_version,
+            licensee_field,
+            custom_versions,
+            header_offset,
+            folder_name,
+            package_flags,
+            name_count,
+            name_offset,
+            soft_object_paths_count,
+            soft_object_paths_offset,
+            localization_id,
+            gatherable_text_data_count,
+            gatherable_text_data_offset,
+            export_count,
+            export_offset,
+            import_count,
+            import_offset,
+            depends_offset,
+            soft_package_reference_count,
+            soft_package_reference_offset,
+            searchable_names_offset,
+            thumbnail_table_offset,
+            package_guid,
+            persistent_guid,
+            owner_persistent_guid,
+            generations,
+            engine_version_recorded,
+            engine_version_compatible,
+            compression_flags,
+            compressed_chunks,
+            package_source,
+            asset_registry_data_offset,
+            bulk_data_start_offset,
+            world_tile_info_offset,
+            chunk_ids,
+            preload_dependency_count,
+            preload_dependency_offset,
+            names_referenced_from_export_data_count,
+            payload_toc_offset,
+            data_resource_offset,
+        })
+    }
+
+    /// Decompress a package saved with package compression
+    ///
+    /// `archive` reads the compressed package this summary was read from.
+    /// Returns the uncompressed package, which has the same layout as an uncompressed asset,
+    /// except for this summary still declaring the compressed chunks.
+    pub fn decompress<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        &self,
+        archive: &mut Reader,
+    ) -> Result<Vec<u8>, Error> {
+        let method = match self.compression_flags & COMPRESSION_METHOD_MASK {
+            COMPRESS_ZLIB => CompressionMethod::Zlib,
+            COMPRESS_GZIP => CompressionMethod::Gzip,
+            flags => {
+                return Err(Error::invalid_file(format!(
+                    "Unsupported package compression flags {flags:#x}"
+                )))
+            }
+        };
+
+        let mut size = 0;
+        for chunk in &self.compressed_chunks {
+            if chunk.uncompressed_offset < 0
+                || chunk.uncompressed_size < 0
+                || chunk.compressed_offset < 0
+                || chunk.compressed_size < 0
+            {
+                return Err(Error::invalid_file(format!(
+                    "Invalid compressed chunk {chunk:?}"
+                )));
+            }
+            size = u64::max(
+                size,
+                chunk.uncompressed_offset as u64 + chunk.uncompressed_size as u64,
+            );
+        }
+
+        let max_size = archive.get_parse_options().max_export_size;
+        if size > max_size {
+            return Err(Error::limit_exceeded(
+                "Uncompressed package size",
+                size,
+                max_size,
+            ));
+        }
+
+        // the summary in front of the first chunk is not compressed
+        let header_size = self
+            .compressed_chunks
+            .iter()
+            .map(|e| e.uncompressed_offset as u64)
+            .min()
+            .unwrap_or_default();
+        let mut data = vec![0u8; size as usize];
+        archive.seek(SeekFrom::Start(0))?;
+        archive.read_exact(&mut data[..header_size as usize])?;
+
+        for chunk in &self.compressed_chunks {
+            let invalid_chunk = || {
+                Error::invalid_file(format!(
+                    "Invalid compressed chunk at offset {}",
+                    chunk.compressed_offset
+                ))
+            };
+
+            archive.seek(SeekFrom::Start(chunk.compressed_offset as u64))?;
+            if archive.read_i64::<LE>()? != PACKAGE_FILE_TAG {
+                return Err(invalid_chunk());
+            }
+            let block_size = match archive.read_i64::<LE>()? {
+                PACKAGE_FILE_TAG => LOADING_COMPRESSION_CHUNK_SIZE,
+                block_size => block_size,
+            };
+            let _compressed_size = archive.read_i64::<LE>()?;
+            let uncompressed_size = archive.read_i64::<LE>()?;
+            if block_size <= 0 || uncompressed_size != chunk.uncompressed_size as i64 {
+                return Err(invalid_chunk());
+            }
+
+            let block_count = (uncompressed_size + block_size - 1) / block_size;
+            let mut blocks = Vec::new();
+            for _ in 0..block_count {
+                let compressed_size = archive.read_i64::<LE>()?;
+                let uncompressed_size = archive.read_i64::<LE>()?;
+                blocks.push((compressed_size, uncompressed_size));
+            }
+
+            let mut offset = chunk.uncompressed_offset as usize;
+            let end = offset + chunk.uncompressed_size as usize;
+            for (compressed_size, uncompressed_size) in blocks {
+                if !(0..=chunk.compressed_size as i64).contains(&compressed_size)
+                    || !(0..=(end - offset) as i64).contains(&uncompressed_size)
+                {
+                    return Err(invalid_chunk());
+                }
+
+                let mut compressed = vec![0u8; compressed_size as usize];
+                archive.read_exact(&mut compressed)?;
+
+                let block_end = offset + uncompressed_size as usize;
+                compression::decompress(method.clone(), &compressed, &mut data[offset..block_end])?;
+                offset = block_end;
+            }
+
+            if offset != end {
+                return Err(invalid_chunk());
+            }
+        }
+
+        Ok(data)
+    }
+
+    /// Check if the asset has editor only data
+    pub fn has_editor_only_data(&self) -> bool {
+        self.package_flags.has_editor_only_data()
+    }
+
+    /// Write this summary
+    ///
+    /// Fields are written for the object versions of this summary, not the ones of the archive.
+    pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        archive: &mut Writer,
+    ) -> Result<(), Error> {
+        archive.write_u32::<BE>(UE4_ASSET_MAGIC)?;
+        archive.write_i32::<LE>(self.legacy_file_version)?;
+
+        if self.legacy_file_version != -4 {
+            archive.write_i32::<LE>(self.legacy_ue3_version)?;
+        }
+
+        match self.unversioned {
+            true => archive.write_i32::<LE>(0)?,
+            false => archive.write_i32::<LE>(self.object_version as i32)?,
+        };
+
+        if self.legacy_file_version <= -8 {
+            match self.unversioned {
+                true => archive.write_i32::<LE>(0)?,
+                false => archive.write_i32::<LE>(self.object_version_ue5 as i32)?,
+            };
+        }
+
+        archive.write_i32::<LE>(self.file_licensee_version)?;
+        if let Some(licensee_field) = self.licensee_field {
+            archive.write_i32::<LE>(licensee_field)?;
+        }
+        if self.legacy_file_version <= -2 {
+            match self.unversioned {
+                true => archive.write_i32::<LE>(0)?,
+                false => {
+                    archive.write_i32::<LE>(self.custom_versions.len() as i32)?;
+                    for custom_version in &self.custom_versions {
+                        archive.write_guid(&custom_version.guid)?;
+                        archive.write_i32::<LE>(custom_version.version)?;
+                    }
+                }
+            };
+        }
+
+        archive.write_i32::<LE>(self.header_offset)?;
+        archive.write_fstring(Some(&self.folder_name))?;
+        archive.write_u32::<LE>(self.package_flags.bits())?;
+        archive.write_i32::<LE>(self.name_count)?;
+        archive.write_i32::<LE>(self.name_offset)?;
+
+        if self.object_version_ue5 >= ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST {
+            archive.write_i32::<LE>(self.soft_object_paths_count)?;
+            archive.write_i32::<LE>(self.soft_object_paths_offset)?;
+        }
+
+        if self.has_editor_only_data()
+            && self.object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_SUMMARY_LOCALIZATION_ID
+        {
+            archive.write_fstring(self.localization_id.as_deref())?;
+        }
+
+        if self.object_version >= ObjectVersion::VER_UE4_SERIALIZE_TEXT_IN_PACKAGES {
+            archive.write_i32::<LE>(self.gatherable_text_data_count)?;
+            archive.write_i32::<LE>(self.gatherable_text_data_offset)?;
+        }
+
+        archive.write_i32::<LE>(self.export_count)?;
+        archive.write_i32::<LE>(self.export_offset)?;
+        archive.write_i32::<LE>(self.import_count)?;
+        archive.write_i32::<LE>(self.import_offset)?;
+        archive.write_i32::<LE>(self.depends_offset)?;
+
+        if self.object_version >= ObjectVersion::VER_UE4_ADD_STRING_ASSET_REFERENCES_MAP {
+            archive.write_i32::<LE>(self.soft_package_reference_count)?;
+            archive.write_i32::<LE>(self.soft_package_reference_offset)?;
+        }
+
+        if self.object_version >= ObjectVersion::VER_UE4_ADDED_SEARCHABLE_NAMES {
+            archive.write_i32::<LE>(self.searchable_names_offset)?;
+        }
+
+        archive.write_i32::<LE>(self.thumbnail_table_offset)?;
+        archive.write_guid(&self.package_guid)?;
+
+        if self.has_editor_only_data() {
+            if self.object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_OWNER {
+                // assets saved before persistent guids were added use the package guid
+                archive.write_guid(self.persistent_guid.as_ref().unwrap_or(&self.package_guid))?;
+            }
+            if self.object_version >= ObjectVersion::VER_UE4_ADDED_PACKAGE_OWNER
+                && self.object_version < ObjectVersion::VER_UE4_NON_OUTER_PACKAGE_IMPORT
+            {
+                archive.write_guid(&self.owner_persistent_guid.unwrap_or_default())?;
+            }
+        }
+
+        archive.write_i32::<LE>(self.generations.len() as i32)?;
+        for generation in &self.generations {
+            archive.write_i32::<LE>(generation.export_count)?;
+            archive.write_i32::<LE>(generation.name_count)?;
+        }
+
+        if self.object_version >= ObjectVersion::VER_UE4_ENGINE_VERSION_OBJECT {
+            self.engine_version_recorded.write(archive)?;
+        } else {
+            archive.write_u32::<LE>(self.engine_version_recorded.build)?;
+        }
+
+        if self.object_version
+            >= ObjectVersion::VER_UE4_PACKAGE_SUMMARY_HAS_COMPATIBLE_ENGINE_VERSION
+        {
+            self.engine_version_compatible.write(archive)?;
+        }
+
+        archive.write_u32::<LE>(self.compression_flags)?;
+        archive.write_i32::<LE>(self.compressed_chunks.len() as i32)?;
+        for chunk in &self.compressed_chunks {
+            archive.write_i32::<LE>(chunk.uncompressed_offset)?;
+            archive.write_i32::<LE>(chunk.uncompressed_size)?;
+            archive.write_i32::<LE>(chunk.compressed_offset)?;
+            archive.write_i32::<LE>(chunk.compressed_size)?;
+        }
+        archive.write_u32::<LE>(self.package_source)?;
+        archive.write_i32::<LE>(0)?; // numAdditionalPackagesToCook
+
+        if self.legacy_file_version > -7 {
+            archive.write_i32::<LE>(0)?; // numTextureallocations
+        }
+
+        archive.write_i32::<LE>(self.asset_registry_data_offset)?;
+        archive.write_i64::<LE>(self.bulk_data_start_offset)?;
+
+        if self.object_version >= ObjectVersion::VER_UE4_WORLD_LEVEL_INFO {
+            archive.write_i32::<LE>(self.world_tile_info_offset)?;
+        }
+
+        if self.object_version >= ObjectVersion::VER_UE4_CHANGED_CHUNKID_TO_BE_AN_ARRAY_OF_CHUNKIDS
+        {
+            archive.write_i32::<LE>(self.chunk_ids.len() as i32)?;
+            for chunk_id in &self.chunk_ids {
+                archive.write_i32::<LE>(*chunk_id)?;
+            }
+        } else if self.object_version
+            >= ObjectVersion::VER_UE4_ADDED_CHUNKID_TO_ASSETDATA_AND_UPACKAGE
+        {
+            archive.write_i32::<LE>(self.chunk_ids.first().copied().unwrap_or(-1))?;
+        }
+
+        if self.object_version >= ObjectVersion::VER_UE4_PRELOAD_DEPENDENCIES_IN_COOKED_EXPORTS {
+            archive.write_i32::<LE>(self.preload_dependency_count)?;
+            archive.write_i32::<LE>(self.preload_dependency_offset)?;
+        }
+
+        if self.object_version_ue5 >= ObjectVersionUE5::NAMES_REFERENCED_FROM_EXPORT_DATA {
+            archive.write_i32::<LE>(self.names_referenced_from_export_data_count)?;
+        }
+
+        if self.object_version_ue5 >= ObjectVersionUE5::PAYLOAD_TOC {
+            archive.write_i64::<LE>(self.payload_toc_offset)?;
+        }
+
+        if self.object_version_ue5 >= ObjectVersionUE5::DATA_RESOURCES {
+            archive.write_i32::<LE>(self.data_resource_offset)?;
+        }
+
+        Ok(())
+    }
+
+    /// Serialize this summary
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let mut cursor = Cursor::new(Vec::new());
+        let mut writer = RawWriter::<PackageIndex, _>::new(
+            &mut cursor,
+            self.object_version,
+            self.object_version_ue5,
+            false,
+            NameMap::new(),
+        );
+        self.write(&mut writer)?;
+        Ok(cursor.into_inner())
+    }
+}
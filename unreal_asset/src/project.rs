@@ -0,0 +1,505 @@
+//! Multi-asset projects
+//!
+//! A [`Project`] manages a set of related assets, e.g. a blueprint and the packages it depends on.
+//! Assets are loaded from an [`AssetSource`], changes are tracked per asset
+//! and all modified assets are saved together into an [`AssetSink`].
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs;
+use std::io::{self, Cursor};
+#[cfg(feature = "zip")]
+use std::io::{Read, Seek};
+use std::path::PathBuf;
+
+use crate::engine_version::EngineVersion;
+use crate::error::Error;
+use crate::import::ImportMapTrait;
+use crate::types::PackageIndex;
+use crate::unversioned::Usmap;
+use crate::Asset;
+
+/// Source files for assets are read from
+///
+/// Paths are game paths with an extension, e.g. `/Game/Items/Conveyor.uasset`.
+pub trait AssetSource {
+    /// Read a file, returns `None` if the file doesn't exist
+    fn read_file(&mut self, path: &str) -> Result<Option<Vec<u8>>, Error>;
+}
+
+/// Destination assets are saved to
+///
+/// Paths are game paths with an extension, e.g. `/Game/Items/Conveyor.uasset`.
+pub trait AssetSink {
+    /// Write a file, replacing it if it already exists
+    fn write_file(&mut self, path: &str, data: Vec<u8>) -> Result<(), Error>;
+}
+
+impl AssetSource for HashMap<String, Vec<u8>> {
+    fn read_file(&mut self, path: &str) -> Result<Option<Vec<u8>>, Error> {
+        Ok(self.get(path).cloned())
+    }
+}
+
+impl AssetSink for HashMap<String, Vec<u8>> {
+    fn write_file(&mut self, path: &str, data: Vec<u8>) -> Result<(), Error> {
+        self.insert(path.to_string(), data);
+        Ok(())
+    }
+}
+
+/// Asset source and sink backed by a game directory on disk
+///
+/// `/Game/` paths are mapped to the `Content` directory, other mount points
+/// are mapped to a directory of the same name, e.g. `/MyPlugin/Asset.uasset` to `MyPlugin/Asset.uasset`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectorySource {
+    /// Game directory
+    pub root: PathBuf,
+}
+
+impl DirectorySource {
+    /// Create a new `DirectorySource` instance
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        DirectorySource { root: root.into() }
+    }
+
+    /// Get the path on disk for a game path
+    pub fn get_file_path(&self, path: &str) -> PathBuf {
+        let path = path.trim_start_matches('/');
+        match path.strip_prefix("Game/") {
+            Some(path) => self.root.join("Content").join(path),
+            None => self.root.join(path),
+        }
+    }
+}
+
+impl AssetSource for DirectorySource {
+    fn read_file(&mut self, path: &str) -> Result<Option<Vec<u8>>, Error> {
+        match fs::read(self.get_file_path(path)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+impl AssetSink for DirectorySource {
+    fn write_file(&mut self, path: &str, data: Vec<u8>) -> Result<(), Error> {
+        let file_path = self.get_file_path(path);
+        if let Some(parent) = file_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(file_path, data)?;
+        Ok(())
+    }
+}
+
+/// Asset source backed by a zip archive
+///
+/// Mods are often distributed as zips of loose `.uasset`/`.uexp` files,
+/// this reads them without extracting the archive first.
+/// Game paths are mapped to entries like in [`DirectorySource`], relative to `root` inside the archive.
+#[cfg(feature = "zip")]
+#[derive(Debug)]
+pub struct ZipSource<R: Read + Seek> {
+    /// Zip archive
+    archive: zip::ZipArchive<R>,
+    /// Entry names with `/` separators mapped to their names in the archive
+    entries: HashMap<String, String>,
+    /// Directory in the archive game paths are relative to, empty for the archive root
+    pub root: String,
+}
+
+#[cfg(feature = "zip")]
+impl<R: Read + Seek> ZipSource<R> {
+    /// Create a new `ZipSource` instance reading from the archive root
+    pub fn new(reader: R) -> Result<Self, Error> {
+        Self::with_root(reader, String::new())
+    }
+
+    /// Create a new `ZipSource` instance reading from a directory in the archive
+    pub fn with_root(reader: R, root: impl Into<String>) -> Result<Self, Error> {
+        let archive = zip::ZipArchive::new(reader).map_err(zip_error)?;
+        // zips created on windows can use backslashes as separators
+        let entries = archive
+            .file_names()
+            .map(|e| (e.replace('\\', "/"), e.to_string()))
+            .collect();
+
+        let root: String = root.into();
+        Ok(ZipSource {
+            archive,
+            entries,
+            root: root.trim_matches(['/', '\\']).replace('\\', "/"),
+        })
+    }
+
+    /// Get the entry name in the archive for a game path
+    pub fn get_entry_name(&self, path: &str) -> String {
+        let path = path.trim_start_matches('/');
+        let path = match path.strip_prefix("Game/") {
+            Some(path) => format!("Content/{path}"),
+            None => path.to_string(),
+        };
+
+        match self.root.is_empty() {
+            true => path,
+            false => format!("{}/{path}", self.root),
+        }
+    }
+
+    /// Get package paths of all assets in the archive, e.g. `/Game/Items/Conveyor`
+    pub fn get_package_paths(&self) -> Vec<String> {
+        let mut package_paths = self
+            .entries
+            .keys()
+            .filter_map(|name| {
+                let name = match self.root.is_empty() {
+                    true => name.as_str(),
+                    false => name.strip_prefix(&self.root)?.strip_prefix('/')?,
+                };
+                let name = name
+                    .strip_suffix(".uasset")
+                    .or_else(|| name.strip_suffix(".umap"))?;
+
+                Some(match name.strip_prefix("Content/") {
+                    Some(name) => format!("/Game/{name}"),
+                    None => format!("/{name}"),
+                })
+            })
+            .collect::<Vec<_>>();
+        package_paths.sort();
+        package_paths
+    }
+}
+
+#[cfg(feature = "zip")]
+impl<R: Read + Seek> AssetSource for ZipSource<R> {
+    fn read_file(&mut self, path: &str) -> Result<Option<Vec<u8>>, Error> {
+        let Some(name) = self.entries.get(&self.get_entry_name(path)) else {
+            return Ok(None);
+        };
+
+        let mut file = self.archive.by_name(name).map_err(zip_error)?;
+        let mut data = Vec::with_capacity(file.size() as usize);
+        file.read_to_end(&mut data)?;
+        Ok(Some(data))
+    }
+}
+
+/// Convert a zip error
+#[cfg(feature = "zip")]
+fn zip_error(error: zip::result::ZipError) -> Error {
+    match error {
+        zip::result::ZipError::Io(e) => e.into(),
+        e => Error::invalid_file(e.to_string()),
+    }
+}
+
+/// Asset loaded into a project
+struct ProjectAsset {
+    /// Asset
+    asset: Asset<Cursor<Vec<u8>>>,
+    /// Extension of the asset file, `uasset` or `umap`
+    extension: &'static str,
+    /// Was the asset modified since it was loaded or saved
+    dirty: bool,
+}
+
+/// A set of related assets which are edited and saved together
+///
+/// Assets are identified by their package path, e.g. `/Game/Items/Conveyor`.
+pub struct Project<S: AssetSource> {
+    /// Source assets are loaded from
+    pub source: S,
+    /// Engine version of all assets in the project
+    pub engine_version: EngineVersion,
+    /// Mappings used for assets with unversioned properties
+    pub mappings: Option<Usmap>,
+    assets: BTreeMap<String, ProjectAsset>,
+}
+
+impl<S: AssetSource> Project<S> {
+    /// Create a new empty `Project`
+    pub fn new(source: S, engine_version: EngineVersion, mappings: Option<Usmap>) -> Self {
+        Project {
+            source,
+            engine_version,
+            mappings,
+            assets: BTreeMap::new(),
+        }
+    }
+
+    /// Load an asset into the project
+    ///
+    /// If the asset is already loaded, the loaded asset is returned.
+    /// The asset is marked as dirty, since it can be modified through the returned reference.
+    pub fn load(&mut self, package_path: &str) -> Result<&mut Asset<Cursor<Vec<u8>>>, Error> {
+        let project_asset = self.load_project_asset(package_path)?;
+        project_asset.dirty = true;
+        Ok(&mut project_asset.asset)
+    }
+
+    /// Load an asset into the project without marking it as dirty
+    fn load_project_asset(&mut self, package_path: &str) -> Result<&mut ProjectAsset, Error> {
+        if !self.assets.contains_key(package_path) {
+            let project_asset = self.read_asset(package_path)?.ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("Asset {package_path} not found"),
+                )
+            })?;
+            self.assets.insert(package_path.to_string(), project_asset);
+        }
+
+        Ok(self
+            .assets
+            .get_mut(package_path)
+            .expect("Asset was just loaded"))
+    }
+
+    /// Load an asset and all packages it depends on which exist in the source
+    ///
+    /// Dependencies are found recursively through package imports,
+    /// packages which are not in the source (e.g. `/Script/` packages) are skipped.
+    ///
+    /// Returns package paths of all assets that were loaded by this call.
+    pub fn load_with_dependencies(&mut self, package_path: &str) -> Result<Vec<String>, Error> {
+        let mut loaded = Vec::new();
+
+        if !self.assets.contains_key(package_path) {
+            self.load_project_asset(package_path)?;
+            loaded.push(package_path.to_string());
+        }
+
+        let mut visited = BTreeSet::new();
+        let mut queue = vec![package_path.to_string()];
+
+        while let Some(path) = queue.pop() {
+            if !visited.insert(path.clone()) {
+                continue;
+            }
+
+            for dependency in self.get_package_dependencies(&path) {
+                if visited.contains(&dependency) {
+                    continue;
+                }
+
+                if !self.assets.contains_key(&dependency) {
+                    let Some(project_asset) = self.read_asset(&dependency)? else {
+                        continue;
+                    };
+                    self.assets.insert(dependency.clone(), project_asset);
+                    loaded.push(dependency.clone());
+                }
+
+                queue.push(dependency);
+            }
+        }
+
+        Ok(loaded)
+    }
+
+    fn read_asset(&mut self, package_path: &str) -> Result<Option<ProjectAsset>, Error> {
+        for extension in ["uasset", "umap"] {
+            let Some(data) = self
+                .source
+                .read_file(&format!("{package_path}.{extension}"))?
+            else {
+                continue;
+            };
+            let bulk = self.source.read_file(&format!("{package_path}.uexp"))?;
+
+            let asset = Asset::new(
+                Cursor::new(data),
+                bulk.map(Cursor::new),
+                self.engine_version,
+                self.mappings.clone(),
+            )?;
+
+            return Ok(Some(ProjectAsset {
+                asset,
+                extension,
+                dirty: false,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    fn get_package_dependencies(&self, package_path: &str) -> Vec<String> {
+        let Some(project_asset) = self.assets.get(package_path) else {
+            return Vec::new();
+        };
+
+        project_asset
+            .asset
+            .imports
+            .iter()
+            .filter(|import| import.outer_index.index == 0 && import.class_name == "Package")
+            .map(|import| import.object_name.get_owned_content())
+            .filter(|dependency| dependency != package_path)
+            .collect()
+    }
+
+    /// Resolve an import of a loaded asset to the export it refers to
+    ///
+    /// The package containing the export is loaded if it is in the source.
+    /// Returns the package path and the export index,
+    /// or `None` if the import refers to a package itself or its package is not in the source.
+    pub fn resolve_import(
+        &mut self,
+        package_path: &str,
+        import_index: PackageIndex,
+    ) -> Result<Option<(String, PackageIndex)>, Error> {
+        let project_asset = self
+            .assets
+            .get(package_path)
+            .ok_or_else(|| Error::no_data(format!("Asset {package_path} is not loaded")))?;
+
+        let import = project_asset
+            .asset
+            .get_import_ref(import_index)
+            .ok_or_else(|| {
+                Error::invalid_package_index(format!("Unknown import {import_index}"))
+            })?;
+
+        let object_path = import.resolved_path(&project_asset.asset);
+        let Some((import_package_path, _)) = object_path.split_once('.') else {
+            return Ok(None);
+        };
+
+        if !self.assets.contains_key(import_package_path) {
+            let Some(project_asset) = self.read_asset(import_package_path)? else {
+                return Ok(None);
+            };
+            self.assets
+                .insert(import_package_path.to_string(), project_asset);
+        }
+
+        Ok(self.assets[import_package_path]
+            .asset
+            .find_export_by_object_path(&object_path)
+            .map(|index| (import_package_path.to_string(), index)))
+    }
+
+    /// Check if an asset is loaded
+    pub fn contains(&self, package_path: &str) -> bool {
+        self.assets.contains_key(package_path)
+    }
+
+    /// Get package paths of all loaded assets
+    pub fn get_package_paths(&self) -> Vec<&str> {
+        self.assets.keys().map(|e| e.as_str()).collect()
+    }
+
+    /// Get a loaded asset
+    pub fn get(&self, package_path: &str) -> Option<&Asset<Cursor<Vec<u8>>>> {
+        self.assets.get(package_path).map(|e| &e.asset)
+    }
+
+    /// Get a mutable reference to a loaded asset, this marks the asset as dirty
+    pub fn get_mut(&mut self, package_path: &str) -> Option<&mut Asset<Cursor<Vec<u8>>>> {
+        self.assets.get_mut(package_path).map(|e| {
+            e.dirty = true;
+            &mut e.asset
+        })
+    }
+
+    /// Mark an asset as dirty so it gets saved
+    ///
+    /// Returns `false` if the asset is not loaded.
+    pub fn mark_dirty(&mut self, package_path: &str) -> bool {
+        self.assets
+            .get_mut(package_path)
+            .map(|e| e.dirty = true)
+            .is_some()
+    }
+
+    /// Check if an asset was modified since it was loaded or saved
+    pub fn is_dirty(&self, package_path: &str) -> bool {
+        self.assets.get(package_path).is_some_and(|e| e.dirty)
+    }
+
+    /// Get package paths of all modified assets
+    pub fn get_dirty_package_paths(&self) -> Vec<&str> {
+        self.assets
+            .iter()
+            .filter(|(_, e)| e.dirty)
+            .map(|(path, _)| path.as_str())
+            .collect()
+    }
+
+    /// Remove an asset from the project, discarding any unsaved changes
+    pub fn unload(&mut self, package_path: &str) -> Option<Asset<Cursor<Vec<u8>>>> {
+        self.assets.remove(package_path).map(|e| e.asset)
+    }
+
+    /// Rename a loaded package
+    ///
+    /// References to the package are fixed in all loaded assets, assets which were changed
+    /// are marked as dirty. Assets which are not loaded are not updated.
+    ///
+    /// The renamed asset is saved under its new path, the files at the old path are left untouched.
+    pub fn rename(&mut self, old_path: &str, new_path: &str) -> Result<(), Error> {
+        if self.assets.contains_key(new_path) {
+            return Err(Error::no_data(format!(
+                "Asset {new_path} is already loaded"
+            )));
+        }
+
+        let mut project_asset = self
+            .assets
+            .remove(old_path)
+            .ok_or_else(|| Error::no_data(format!("Asset {old_path} is not loaded")))?;
+
+        project_asset.asset.rename_package(old_path, new_path);
+        project_asset.dirty = true;
+
+        for other in self.assets.values_mut() {
+            if other.asset.fix_package_references(old_path, new_path) {
+                other.dirty = true;
+            }
+        }
+
+        self.assets.insert(new_path.to_string(), project_asset);
+        Ok(())
+    }
+
+    /// Save all modified assets
+    ///
+    /// All assets are serialized before anything is written,
+    /// so a serialization error doesn't leave the sink with only some of the assets saved.
+    pub fn save(&mut self, sink: &mut impl AssetSink) -> Result<(), Error> {
+        let mut files = Vec::new();
+
+        for (package_path, project_asset) in self.assets.iter().filter(|(_, e)| e.dirty) {
+            let asset = &project_asset.asset;
+
+            let mut data = Cursor::new(Vec::new());
+            let mut bulk = match asset.use_separate_bulk_data_files() {
+                true => Some(Cursor::new(Vec::new())),
+                false => None,
+            };
+            asset.write_data(&mut data, bulk.as_mut())?;
+
+            files.push((
+                format!("{package_path}.{}", project_asset.extension),
+                data.into_inner(),
+            ));
+            if let Some(bulk) = bulk {
+                files.push((format!("{package_path}.uexp"), bulk.into_inner()));
+            }
+        }
+
+        for (path, data) in files {
+            sink.write_file(&path, data)?;
+        }
+
+        for project_asset in self.assets.values_mut() {
+            project_asset.dirty = false;
+        }
+
+        Ok(())
+    }
+}
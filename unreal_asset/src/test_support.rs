@@ -0,0 +1,202 @@
+//! Golden-file regression test helpers
+//!
+//! Enabled with the `test-support` feature, so crates adding support for game specific
+//! assets can test their fixtures the same way this crate does.
+//!
+//! A [`Fixture`] is a `.uasset` or `.umap` file with its optional `.uexp` file.
+//! Fixtures are checked to be written byte-identical to how they were read,
+//! and [`snapshot_properties`] renders their property trees so they can be compared
+//! against a golden file with [`assert_snapshot`].
+//!
+//! ```no_run
+//! use unreal_asset::{engine_version::EngineVersion, test_support::{self, Fixture}};
+//!
+//! let fixture = Fixture::load("tests/assets/Augment_BroadBrush.uasset").unwrap();
+//! let mut asset = fixture.parse(EngineVersion::VER_UE4_23, None).unwrap();
+//!
+//! fixture.assert_round_trip(&mut asset).unwrap();
+//! test_support::assert_all_exports_parsed(&asset);
+//! test_support::assert_snapshot(
+//!     &test_support::snapshot_properties(&asset),
+//!     "tests/snapshots/Augment_BroadBrush.txt",
+//! )
+//! .unwrap();
+//! ```
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{Cursor, Read, Seek};
+use std::path::{Path, PathBuf};
+
+use unreal_asset_base::{cast, unversioned::Usmap};
+use unreal_asset_exports::{ExportBaseTrait, ExportNormalTrait};
+
+use crate::engine_version::EngineVersion;
+use crate::error::Error;
+use crate::{Asset, Export};
+
+/// Environment variable which makes [`assert_snapshot`] overwrite golden files instead of comparing them
+pub const UPDATE_SNAPSHOTS_VAR: &str = "UNREAL_ASSET_UPDATE_SNAPSHOTS";
+
+/// Fixture asset
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fixture {
+    /// Path of the `.uasset` or `.umap` file
+    pub path: PathBuf,
+    /// Asset data
+    pub uasset: Vec<u8>,
+    /// Bulk data, if the asset has a `.uexp` file
+    pub uexp: Option<Vec<u8>>,
+}
+
+impl Fixture {
+    /// Load a fixture, the `.uexp` file next to it is loaded if it exists
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let path = path.as_ref().to_path_buf();
+        let uasset = fs::read(&path)?;
+
+        let uexp_path = path.with_extension("uexp");
+        let uexp = match uexp_path.is_file() {
+            true => Some(fs::read(uexp_path)?),
+            false => None,
+        };
+
+        Ok(Fixture { path, uasset, uexp })
+    }
+
+    /// Parse the fixture
+    pub fn parse(
+        &self,
+        engine_version: EngineVersion,
+        mappings: Option<Usmap>,
+    ) -> Result<Asset<Cursor<Vec<u8>>>, Error> {
+        Asset::new(
+            Cursor::new(self.uasset.clone()),
+            self.uexp.clone().map(Cursor::new),
+            engine_version,
+            mappings,
+        )
+    }
+
+    /// Assert that an asset is written byte-identical to this fixture
+    pub fn assert_round_trip<C: Read + Seek>(&self, asset: &mut Asset<C>) -> Result<(), Error> {
+        assert_round_trip(asset, &self.uasset, self.uexp.as_deref())
+    }
+}
+
+/// Assert that an asset is written byte-identical to `uasset` and `uexp`
+///
+/// Panics at the first differing byte.
+pub fn assert_round_trip<C: Read + Seek>(
+    asset: &mut Asset<C>,
+    uasset: &[u8],
+    uexp: Option<&[u8]>,
+) -> Result<(), Error> {
+    let mut written = Cursor::new(Vec::new());
+    let mut written_bulk = uexp.map(|_| Cursor::new(Vec::new()));
+    asset.write_data(&mut written, written_bulk.as_mut())?;
+
+    assert_bytes_eq("uasset", &written.into_inner(), uasset);
+    if let (Some(written_bulk), Some(uexp)) = (written_bulk, uexp) {
+        assert_bytes_eq("uexp", &written_bulk.into_inner(), uexp);
+    }
+
+    Ok(())
+}
+
+/// Assert that two buffers are equal, reporting the first differing offset instead of both buffers
+fn assert_bytes_eq(name: &str, written: &[u8], expected: &[u8]) {
+    if let Some(offset) = written.iter().zip(expected).position(|(a, b)| a != b) {
+        panic!(
+            "Written {name} differs at offset {offset:#x}: {:#04x} instead of {:#04x}",
+            written[offset], expected[offset]
+        );
+    }
+    assert_eq!(
+        written.len(),
+        expected.len(),
+        "Written {name} has a different length"
+    );
+}
+
+/// Assert that no export of an asset failed to parse and was read as a raw export
+pub fn assert_all_exports_parsed<C: Read + Seek>(asset: &Asset<C>) {
+    for (i, export) in asset.asset_data.exports.iter().enumerate() {
+        if cast!(Export, RawExport, export).is_some() {
+            let object_name = &export.get_base_export().object_name;
+            panic!(
+                "Export {} ({}) failed to parse",
+                i + 1,
+                object_name.get_owned_content()
+            );
+        }
+    }
+}
+
+/// Render the property trees of all exports of an asset
+///
+/// Properties are rendered with their pretty-printed `Debug` representation,
+/// rows of data tables are rendered after the properties of the export.
+pub fn snapshot_properties<C: Read + Seek>(asset: &Asset<C>) -> String {
+    let mut snapshot = String::new();
+
+    for (i, export) in asset.asset_data.exports.iter().enumerate() {
+        let object_name = export.get_base_export().object_name.get_owned_content();
+        let _ = writeln!(snapshot, "Export {} {}", i + 1, object_name);
+
+        if let Some(raw) = cast!(Export, RawExport, export) {
+            let _ = writeln!(snapshot, "Raw data, {} bytes", raw.data.len());
+            continue;
+        }
+        if let Some(normal_export) = export.get_normal_export() {
+            for property in &normal_export.properties {
+                let _ = writeln!(snapshot, "{property:#?}");
+            }
+        }
+        if let Some(data_table) = cast!(Export, DataTableExport, export) {
+            for row in &data_table.table.data {
+                let _ = writeln!(snapshot, "{row:#?}");
+            }
+        }
+    }
+
+    snapshot
+}
+
+/// Compare a snapshot against a golden file
+///
+/// The golden file is written if it doesn't exist yet or [`UPDATE_SNAPSHOTS_VAR`] is set.
+/// Panics at the first differing line.
+pub fn assert_snapshot(snapshot: &str, path: impl AsRef<Path>) -> Result<(), Error> {
+    let path = path.as_ref();
+
+    if std::env::var_os(UPDATE_SNAPSHOTS_VAR).is_some() || !path.exists() {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, snapshot)?;
+        return Ok(());
+    }
+
+    let golden = fs::read_to_string(path)?;
+    let mismatch = snapshot
+        .lines()
+        .zip(golden.lines())
+        .enumerate()
+        .find(|(_, (line, golden_line))| line != golden_line);
+    if let Some((i, (line, golden_line))) = mismatch {
+        panic!(
+            "Snapshot differs from {} at line {}:\n  got:      {line}\n  expected: {golden_line}\nSet {UPDATE_SNAPSHOTS_VAR} to update it",
+            path.display(),
+            i + 1
+        );
+    }
+    assert_eq!(
+        snapshot.lines().count(),
+        golden.lines().count(),
+        "Snapshot has a different amount of lines than {}",
+        path.display()
+    );
+
+    Ok(())
+}
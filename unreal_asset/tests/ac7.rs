@@ -1,7 +1,7 @@
 use std::io::Cursor;
 
 use unreal_asset::{
-    ac7::{self, AC7XorKey},
+    ac7::{self, AC7Stream, AC7XorKey},
     engine_version::EngineVersion,
     Asset, Error,
 };
@@ -52,3 +52,27 @@ fn ac7() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn ac7_stream() -> Result<(), Error> {
+    for (name, asset_data, bulk_data) in TEST_ASSETS {
+        let uasset_len = asset_data.len() as u64;
+
+        let parsed = Asset::new(
+            AC7Stream::uasset(Cursor::new(asset_data), name),
+            Some(AC7Stream::uexp(Cursor::new(bulk_data), name, uasset_len)),
+            EngineVersion::VER_UE4_18,
+            None,
+        )?;
+        assert!(shared::verify_all_exports_parsed(&parsed));
+
+        let mut data = AC7Stream::uasset(Cursor::new(Vec::new()), name);
+        let mut bulk = AC7Stream::uexp(Cursor::new(Vec::new()), name, uasset_len);
+        parsed.write_data(&mut data, Some(&mut bulk))?;
+
+        assert_eq!(asset_data, data.into_inner().into_inner());
+        assert_eq!(bulk_data, bulk.into_inner().into_inner());
+    }
+
+    Ok(())
+}
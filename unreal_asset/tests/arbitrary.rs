@@ -0,0 +1,34 @@
+#![cfg(feature = "arbitrary")]
+
+use arbitrary::{Arbitrary, Unstructured};
+
+use unreal_asset::{
+    properties::{Property, PropertyDataTrait},
+    types::{FName, PackageIndex},
+};
+
+/// Deterministic pseudo random input
+fn input(seed: u32) -> Vec<u8> {
+    (0..1024u32)
+        .map(|i| (i.wrapping_mul(2654435761).wrapping_add(seed) >> 13) as u8)
+        .collect()
+}
+
+#[test]
+fn arbitrary_properties() -> arbitrary::Result<()> {
+    for seed in 0..64 {
+        let data = input(seed);
+        let mut u = Unstructured::new(&data);
+
+        let name = FName::arbitrary(&mut u)?;
+        assert!(matches!(name, FName::Dummy { .. }));
+        PackageIndex::arbitrary(&mut u)?;
+
+        let properties = Vec::<Property>::arbitrary(&mut u)?;
+        for property in &properties {
+            assert!(property.get_ancestry().ancestry.is_empty());
+        }
+    }
+
+    Ok(())
+}
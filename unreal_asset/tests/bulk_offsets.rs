@@ -0,0 +1,83 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    asset::BulkOffsetIssue, engine_version::EngineVersion, exports::ExportBaseTrait,
+    types::PackageIndex, Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+fn write_asset(asset: &Asset<Cursor<&[u8]>>) -> Result<Vec<u8>, Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    asset.write_data(&mut cursor, None::<&mut Cursor<Vec<u8>>>)?;
+    Ok(cursor.into_inner())
+}
+
+#[test]
+fn audit_and_repair_bulk_offsets() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    assert!(asset.audit_bulk_offsets()?.is_empty());
+    assert!(asset.asset_data.exports.len() > 1);
+
+    let expected = write_asset(&asset)?;
+    let data_end = TEST_ASSET.len() as i64 - 4;
+
+    // a wrong size is recomputed from the offsets
+    let base_export = asset.asset_data.exports[0].get_base_export_mut();
+    let size = base_export.serial_size;
+    base_export.serial_size += 10;
+    asset.bulk_data_start_offset = 0;
+
+    let issues = asset.audit_bulk_offsets()?;
+    assert!(issues.contains(&BulkOffsetIssue::ExportSize {
+        export: PackageIndex::new(1),
+        size: size + 10,
+        expected: size,
+    }));
+    assert!(issues.contains(&BulkOffsetIssue::BulkDataStartOffset {
+        offset: 0,
+        expected: data_end,
+    }));
+
+    asset.repair_bulk_offsets()?;
+    assert!(asset.audit_bulk_offsets()?.is_empty());
+    assert_eq!(write_asset(&asset)?, expected);
+
+    // a wrong offset is recomputed from the sizes, the export is read again from the right offset
+    let base_export = asset.asset_data.exports[1].get_base_export_mut();
+    let offset = base_export.serial_offset;
+    base_export.serial_offset -= 7;
+
+    let issues = asset.audit_bulk_offsets()?;
+    assert!(issues.contains(&BulkOffsetIssue::ExportOffset {
+        export: PackageIndex::new(2),
+        offset: offset - 7,
+        expected: offset,
+    }));
+
+    asset.repair_bulk_offsets()?;
+    assert!(asset.audit_bulk_offsets()?.is_empty());
+    assert_eq!(write_asset(&asset)?, expected);
+
+    // neither can be trusted
+    let base_export = asset.asset_data.exports[0].get_base_export_mut();
+    base_export.serial_offset += 7;
+    base_export.serial_size += 3;
+    assert!(asset.repair_bulk_offsets().is_err());
+
+    Ok(())
+}
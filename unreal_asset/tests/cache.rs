@@ -0,0 +1,46 @@
+use unreal_asset::{
+    cache::{AssetCache, AssetSummary},
+    engine_version::EngineVersion,
+    Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn cache() -> Result<(), Error> {
+    let directory =
+        std::env::temp_dir().join(format!("unreal_asset_cache_test_{}", std::process::id()));
+    let cache = AssetCache::new(&directory);
+
+    let key = AssetCache::get_key(TEST_ASSET, None, EngineVersion::VER_UE4_23);
+    assert!(cache.get(&key)?.is_none());
+
+    let summary = cache.get_or_parse(TEST_ASSET, None, EngineVersion::VER_UE4_23, None)?;
+    assert!(!summary.exports.is_empty());
+    assert_eq!(cache.get(&key)?.as_ref(), Some(&summary));
+
+    let mut data = std::io::Cursor::new(Vec::new());
+    summary.write(&mut data)?;
+    data.set_position(0);
+    assert_eq!(AssetSummary::read(&mut data)?, summary);
+
+    assert_ne!(
+        key,
+        AssetCache::get_key(TEST_ASSET, None, EngineVersion::VER_UE4_24)
+    );
+
+    cache.clear()?;
+    assert!(cache.get(&key)?.is_none());
+
+    std::fs::remove_dir_all(directory)?;
+    Ok(())
+}
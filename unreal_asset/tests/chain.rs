@@ -34,3 +34,36 @@ fn seek() {
     assert_eq!(read_at(SeekFrom::End(-4)).unwrap(), 4);
     assert!(read_at(SeekFrom::End(-12)).is_err());
 }
+
+#[test]
+fn read_ahead() {
+    use byteorder::ReadBytesExt;
+    use std::io::Cursor;
+    use unreal_helpers::InstrumentedReader;
+
+    let first = InstrumentedReader::new(Cursor::new((0..=255).collect::<Vec<u8>>()));
+    let first_stats = first.stats_handle();
+    let second = InstrumentedReader::new(Cursor::new((0..=255).rev().collect::<Vec<u8>>()));
+    let second_stats = second.stats_handle();
+
+    let mut chain = Chain::with_read_ahead_size(first, Some(second), 64);
+    for i in 0..=255 {
+        assert_eq!(chain.read_u8().unwrap(), i);
+    }
+    for i in (0..=255).rev() {
+        assert_eq!(chain.read_u8().unwrap(), i);
+    }
+    assert!(chain.read_u8().is_err());
+
+    // 4 buffer fills per reader and the read at the end of the second reader
+    assert_eq!(first_stats.get().reads, 4);
+    assert_eq!(second_stats.get().reads, 5);
+
+    chain.seek(SeekFrom::Start(250)).unwrap();
+    let mut buf = [0u8; 12];
+    chain.read_exact(&mut buf).unwrap();
+    assert_eq!(
+        buf,
+        [250, 251, 252, 253, 254, 255, 255, 254, 253, 252, 251, 250]
+    );
+}
@@ -0,0 +1,47 @@
+use std::io::Cursor;
+
+use unreal_asset::{engine_version::EngineVersion, Asset, Error};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/BloodStained/PB_DT_RandomizerRoomCheck"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn chunk_ids() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    assert!(asset.get_chunk_ids().is_empty());
+
+    asset.set_chunk_ids(vec![3]);
+    asset.add_chunk_id(7);
+    asset.add_chunk_id(3);
+    assert_eq!(asset.get_chunk_ids(), [3, 7]);
+
+    let mut modified = Cursor::new(Vec::new());
+    asset.write_data(&mut modified, None)?;
+    let modified = modified.into_inner();
+
+    let mut parsed_back = Asset::new(
+        Cursor::new(modified.as_slice()),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    assert_eq!(parsed_back.get_chunk_ids(), [3, 7]);
+    shared::verify_binary_equality(&modified, None, &mut parsed_back)?;
+
+    Ok(())
+}
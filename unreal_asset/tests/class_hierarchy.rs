@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion, exports::ExportBaseTrait, import::ImportMapTrait,
+    types::PackageIndex, Asset, Error, Export,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn class_hierarchy() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let class_index = asset
+        .find_export_by_object_path(
+            "/Game/Components_Small/Augment_BroadBrush.Augment_BroadBrush_C",
+        )
+        .expect("Failed to find class export");
+    let Some(Export::ClassExport(class_export)) = asset.get_export(class_index) else {
+        panic!("Augment_BroadBrush_C is not a class export");
+    };
+    let super_class = asset
+        .get_import_ref(class_export.struct_export.super_struct)
+        .expect("Failed to find super class import");
+
+    let default_object = asset
+        .asset_data
+        .exports
+        .iter()
+        .position(|e| e.get_base_export().object_name == "Default__Augment_BroadBrush_C")
+        .expect("Failed to find class default object");
+    let default_object = PackageIndex::from_export(default_object as i32)?;
+
+    let hierarchy = asset.get_class_hierarchy(default_object);
+    assert_eq!(
+        hierarchy,
+        vec![
+            "Augment_BroadBrush_C".to_string(),
+            super_class.object_name.get_owned_content()
+        ]
+    );
+
+    // packages which are not in the source are resolved like without a source
+    let mut source: HashMap<String, Vec<u8>> = HashMap::new();
+    assert_eq!(
+        asset.get_class_hierarchy_with_source(default_object, &mut source)?,
+        hierarchy
+    );
+
+    Ok(())
+}
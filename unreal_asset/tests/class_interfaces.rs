@@ -0,0 +1,71 @@
+use std::io::Cursor;
+
+use unreal_asset::{engine_version::EngineVersion, Asset, Error, Export};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn add_class_interface() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let class_index = asset
+        .find_export_by_object_path(
+            "/Game/Components_Small/Augment_BroadBrush.Augment_BroadBrush_C",
+        )
+        .expect("Failed to find class export");
+    let import_count = asset.imports.len();
+
+    let interface =
+        asset.add_class_interface(class_index, "/Game/Interfaces/BPI_Test.BPI_Test_C")?;
+    assert_eq!(asset.imports.len(), import_count + 2);
+
+    let import = asset
+        .get_import(interface)
+        .expect("Interface was not imported");
+    assert_eq!(import.class_name, "BlueprintGeneratedClass");
+    assert_eq!(import.object_name, "BPI_Test_C");
+
+    // adding the same interface again reuses the imports
+    assert_eq!(
+        asset.add_class_interface(class_index, "/Game/Interfaces/BPI_Test.BPI_Test_C")?,
+        interface
+    );
+    assert_eq!(asset.imports.len(), import_count + 2);
+
+    let Some(Export::ClassExport(class)) = asset.get_export(class_index) else {
+        panic!("Augment_BroadBrush_C is not a class export");
+    };
+    assert!(class.has_interface(interface));
+    assert_eq!(
+        class
+            .interfaces
+            .iter()
+            .filter(|e| e.class == interface)
+            .count(),
+        1
+    );
+
+    assert!(asset
+        .add_class_interface(class_index, "NotAnObjectPath")
+        .is_err());
+
+    shared::verify_reparse(&mut asset, EngineVersion::VER_UE4_23)?;
+
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    cast,
+    containers::NameMap,
+    engine_version::EngineVersion,
+    exports::{data_table_export::DataTableExport, Export},
+    properties::Property,
+    types::{fname::FNameContainer, FName},
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/BloodStained/PB_DT_RandomizerRoomCheck"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn content_hash() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+
+    let export = &asset.asset_data.exports[0];
+    let hash = export.content_hash(&asset)?;
+    assert_eq!(export.content_hash(&asset)?, hash);
+
+    // move every name of the export to a name map with a different order
+    let mut names = Vec::new();
+    let mut moved = export.clone();
+    moved.traverse_fnames(&mut |fname: &mut FName| names.push(fname.get_owned_content()));
+
+    let mut name_map = NameMap::new();
+    for name in names.iter().rev() {
+        name_map.get_mut().add_fname(name);
+    }
+    moved.traverse_fnames(&mut |fname: &mut FName| {
+        *fname = name_map
+            .get_mut()
+            .add_fname_with_number(&fname.get_owned_content(), fname.get_number())
+    });
+    assert_eq!(moved.content_hash(&asset)?, hash);
+
+    // changing a value changes the hash
+    let mut changed = export.clone();
+    let data_table_export: &mut DataTableExport<_> =
+        cast!(Export, DataTableExport, &mut changed).expect("Export is not a DataTableExport");
+    let bool_prop = data_table_export.table.data[0]
+        .value
+        .iter_mut()
+        .find_map(|e| cast!(Property, BoolProperty, e))
+        .expect("Row has no BoolProperty");
+    bool_prop.value = !bool_prop.value;
+    assert_ne!(changed.content_hash(&asset)?, hash);
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    cast,
+    engine_version::EngineVersion,
+    exports::{
+        curve_table_export::{CurveTableExport, CurveTableMode},
+        data_table_export::DataTableExport,
+        Export, ExportBaseTrait,
+    },
+    properties::{int_property::FloatProperty, struct_property::StructProperty, Property},
+    Asset, Error,
+};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/BloodStained/PB_DT_RandomizerRoomCheck"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+/// Turn the data table export of the test asset into a curve table export of class `class_name`
+fn curve_table_asset(class_name: &str) -> Result<Vec<u8>, Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+
+    let class_index = asset.asset_data.exports[0].get_base_export().class_index;
+    let class_name = asset.add_fname(class_name);
+    asset.imports[(-class_index.index - 1) as usize].object_name = class_name;
+
+    let mut curve = StructProperty {
+        name: asset.add_fname("Curve"),
+        struct_type: Some(asset.add_fname("RichCurve")),
+        serialize_none: true,
+        ..Default::default()
+    };
+    curve.value.push(
+        FloatProperty {
+            name: asset.add_fname("DefaultValue"),
+            value: 2.5.into(),
+            ..Default::default()
+        }
+        .into(),
+    );
+    asset.add_fname("FloatProperty");
+
+    let data_table_export: &DataTableExport<_> =
+        cast!(Export, DataTableExport, &asset.asset_data.exports[0])
+            .expect("First export is not a DataTableExport");
+    asset.asset_data.exports[0] = CurveTableExport {
+        normal_export: data_table_export.normal_export.clone(),
+        curve_table_mode: CurveTableMode::RichCurves,
+        table: vec![curve],
+    }
+    .into();
+
+    let mut data = Cursor::new(Vec::new());
+    asset.write_data(&mut data, None)?;
+    Ok(data.into_inner())
+}
+
+#[test]
+fn curve_table_round_trip() -> Result<(), Error> {
+    let data = curve_table_asset("CurveTable")?;
+    let mut asset = Asset::new(
+        Cursor::new(data.as_slice()),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+
+    shared::verify_binary_equality(&data, None, &mut asset)?;
+
+    let curve_table_export: &CurveTableExport<_> =
+        cast!(Export, CurveTableExport, &asset.asset_data.exports[0])
+            .expect("First export is not a CurveTableExport");
+    assert_eq!(
+        curve_table_export.curve_table_mode,
+        CurveTableMode::RichCurves
+    );
+    assert_eq!(curve_table_export.table.len(), 1);
+    assert!(matches!(
+        curve_table_export.table[0].value[..],
+        [Property::FloatProperty(_)]
+    ));
+    assert_eq!(curve_table_export.evaluate("Curve", 0.0), Some(2.5));
+
+    Ok(())
+}
+
+#[test]
+fn curve_table_class_names() -> Result<(), Error> {
+    let is_curve_table = |class_name| -> Result<bool, Error> {
+        let data = curve_table_asset(class_name)?;
+        let asset = Asset::new(
+            Cursor::new(data.as_slice()),
+            None,
+            EngineVersion::VER_UE4_18,
+            None,
+        )?;
+        Ok(matches!(
+            asset.asset_data.exports[0],
+            Export::CurveTableExport(_)
+        ))
+    };
+
+    assert!(is_curve_table("CompositeCurveTable")?);
+    // only engine curve table classes are read as curve tables
+    assert!(!is_curve_table("BP_StatsCurveTable")?);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    custom_version::CustomVersion, engine_version::EngineVersion, warning::AssetWarning, Asset,
+    Error, Guid,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Versioned/Assault_M1A1Thompson_WW2_DrumSuppressor"
+        )
+    };
+}
+
+// custom versions are only serialized for versioned assets
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+const TEST_GUID: Guid = Guid::from_ints(0x5e1d3c2b, 0x0a4f4e6d, 0x8b7c9d0e, 0x1f2a3b4c);
+
+#[test]
+fn unknown_custom_versions() -> Result<(), Error> {
+    assert!(!CustomVersion::is_known(&TEST_GUID));
+
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::UNKNOWN,
+        None,
+    )?;
+    assert!(!asset
+        .warnings
+        .contains(&AssetWarning::UnknownCustomVersion(TEST_GUID, 2)));
+
+    asset
+        .asset_data
+        .summary
+        .custom_versions
+        .push(CustomVersion::new(TEST_GUID, 2));
+
+    let mut data = Cursor::new(Vec::new());
+    asset.write_data(&mut data, None)?;
+
+    let asset = Asset::new(
+        Cursor::new(data.into_inner()),
+        None,
+        EngineVersion::UNKNOWN,
+        None,
+    )?;
+    assert!(asset
+        .warnings
+        .contains(&AssetWarning::UnknownCustomVersion(TEST_GUID, 2)));
+
+    CustomVersion::register(
+        TEST_GUID,
+        "FTestGameObjectVersion",
+        &[
+            (EngineVersion::VER_UE4_23, 2),
+            (EngineVersion::VER_UE4_OLDEST_LOADABLE_PACKAGE, 0),
+        ],
+    );
+    assert!(CustomVersion::is_known(&TEST_GUID));
+    assert_eq!(
+        CustomVersion::new(TEST_GUID, 2).friendly_name.as_deref(),
+        Some("FTestGameObjectVersion")
+    );
+    assert_eq!(
+        CustomVersion::get_guid_from_friendly_name("FTestGameObjectVersion"),
+        Some(TEST_GUID)
+    );
+    assert!(
+        CustomVersion::get_default_custom_version_container(EngineVersion::VER_UE4_23)
+            .iter()
+            .any(|e| e.guid == TEST_GUID && e.version == 2)
+    );
+
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use unreal_asset::{
+    asset_data::AssetData,
+    custom_version::{
+        CustomVersion, CustomVersionTrait, FCoreObjectVersion, FFrameworkObjectVersion,
+    },
+    engine_version::EngineVersion,
+    types::PackageIndex,
+    Error,
+};
+
+fn versioned_asset_data() -> AssetData<PackageIndex> {
+    let mut data = AssetData::new();
+    data.set_engine_version(EngineVersion::VER_UE4_25);
+    data.summary.unversioned = false;
+    data
+}
+
+#[test]
+fn custom_version_lookup() {
+    let data = versioned_asset_data();
+
+    let core = data
+        .get_custom_version_by_name(FCoreObjectVersion::FRIENDLY_NAME)
+        .expect("Missing FCoreObjectVersion");
+    assert_eq!(core.guid, FCoreObjectVersion::GUID);
+    assert!(data.get_custom_version_by_name("FMissingVersion").is_none());
+
+    assert_eq!(
+        CustomVersion::get_guid_from_friendly_name(FFrameworkObjectVersion::FRIENDLY_NAME),
+        Some(FFrameworkObjectVersion::GUID)
+    );
+}
+
+#[test]
+fn custom_version_editing() -> Result<(), Error> {
+    let mut data = versioned_asset_data();
+    let count = data.summary.custom_versions.len();
+
+    data.set_custom_version(FFrameworkObjectVersion::FixNonTransactionalPins);
+    assert_eq!(data.summary.custom_versions.len(), count);
+    assert_eq!(
+        data.get_custom_version::<FFrameworkObjectVersion>().version,
+        FFrameworkObjectVersion::FixNonTransactionalPins as i32
+    );
+
+    data.set_custom_version_by_name("FBlueprintsObjectVersion", 3)?;
+    assert_eq!(data.summary.custom_versions.len(), count + 1);
+    assert_eq!(
+        data.get_custom_version_by_name("FBlueprintsObjectVersion")
+            .map(|e| e.version),
+        Some(3)
+    );
+
+    assert!(data
+        .set_custom_version_by_name("FMissingVersion", 1)
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn custom_version_validation() {
+    let mut data = versioned_asset_data();
+    assert!(data.validate_custom_versions().is_ok());
+
+    let removed = data.remove_custom_version(FCoreObjectVersion::GUID);
+    assert!(removed.is_some());
+
+    let missing = data.get_missing_custom_versions();
+    assert_eq!(missing.len(), 1);
+    assert_eq!(missing[0].guid, FCoreObjectVersion::GUID);
+    assert!(data.validate_custom_versions().is_err());
+
+    data.summary.unversioned = true;
+    assert!(data.validate_custom_versions().is_ok());
+    data.summary.unversioned = false;
+
+    data.set_custom_version(FCoreObjectVersion::EnumProperties);
+    assert!(data.validate_custom_versions().is_ok());
+}
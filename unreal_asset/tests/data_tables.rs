@@ -90,3 +90,52 @@ fn data_tables() -> Result<(), Error> {
 
     Ok(())
 }
+
+#[test]
+fn visit_data_table_properties() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+
+    let data_table_export: &DataTableExport<_> =
+        cast!(Export, DataTableExport, &asset.asset_data.exports[0])
+            .expect("First export is not a DataTableExport");
+    let row_properties = data_table_export
+        .table
+        .data
+        .iter()
+        .map(|e| e.value.len())
+        .sum::<usize>();
+
+    let mut visited = 0;
+    let mut bool_count = 0;
+    asset.asset_data.exports[0].visit_properties(&mut |property| {
+        visited += 1;
+        if matches!(property, Property::BoolProperty(_)) {
+            bool_count += 1;
+        }
+    });
+    assert!(visited >= row_properties);
+    assert!(bool_count > 0);
+
+    // set all flags through the mutable visitor
+    asset.asset_data.exports[0].visit_properties_mut(&mut |property| {
+        if let Some(bool_prop) = cast!(Property, BoolProperty, property) {
+            bool_prop.value = true;
+        }
+    });
+
+    let mut set_count = 0;
+    asset.asset_data.exports[0].visit_properties(&mut |property| {
+        if let Some(bool_prop) = cast!(Property, BoolProperty, property) {
+            assert!(bool_prop.value);
+            set_count += 1;
+        }
+    });
+    assert_eq!(set_count, bool_count);
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    containers::{Chain, NameMap},
+    fproperty::{FGenericProperty, FMulticastSparseDelegateProperty, FProperty},
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    reader::{RawReader, RawWriter},
+    types::PackageIndex,
+    Error,
+};
+
+#[test]
+fn sparse_delegate_fproperty() -> Result<(), Error> {
+    let mut name_map = NameMap::new();
+    let property: FProperty = {
+        let mut map = name_map.get_mut();
+        FMulticastSparseDelegateProperty {
+            generic_property: FGenericProperty {
+                name: map.add_fname("OnInteracted"),
+                rep_notify_func: map.add_fname("None"),
+                ..Default::default()
+            },
+            signature_function: PackageIndex::new(-3),
+        }
+        .into()
+    };
+
+    let mut data = Cursor::new(Vec::new());
+    let mut writer = RawWriter::<PackageIndex, _>::new(
+        &mut data,
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        name_map.clone(),
+    );
+    FProperty::write(&property, &mut writer)?;
+
+    let mut reader = RawReader::<PackageIndex, _>::new(
+        Chain::new(Cursor::new(data.into_inner()), None),
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        name_map,
+    );
+    let read = match FProperty::new(&mut reader)? {
+        FProperty::FMulticastSparseDelegateProperty(read) => read,
+        other => panic!("Sparse delegate was read as {other:?}"),
+    };
+    assert_eq!(read.signature_function, PackageIndex::new(-3));
+    assert_eq!(read.generic_property.name, "OnInteracted");
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    display::AssetDisplay,
+    dump::DumpOptions,
+    engine_version::EngineVersion,
+    exports::ExportBaseTrait,
+    types::{FName, PackageIndex},
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn dump_export() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let dump = asset.debug_dump_export(PackageIndex::new(1), DumpOptions::default())?;
+    assert!(dump.starts_with("Export 1 ("));
+    assert!(dump.contains("[0x00000000.."));
+    assert!(dump.contains("] None\n"));
+
+    let dump = asset.debug_dump_export(
+        PackageIndex::new(1),
+        DumpOptions {
+            max_len: Some(4),
+            ..Default::default()
+        },
+    )?;
+    assert!(dump.contains("more bytes"));
+
+    assert!(asset
+        .debug_dump_export(PackageIndex::new(-1), DumpOptions::default())
+        .is_err());
+
+    Ok(())
+}
+
+#[test]
+fn format_tables() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let tables = asset.format_tables();
+    let lines = tables.lines().collect::<Vec<_>>();
+
+    assert_eq!(lines[0], format!("Imports ({})", asset.imports.len()));
+    assert!(lines[1].starts_with("Index  Class"));
+    assert!(lines[3].trim_start().starts_with("-1  /Script/"));
+
+    let exports_start = 3 + asset.imports.len() + 1;
+    assert_eq!(
+        lines[exports_start],
+        format!("Exports ({})", asset.asset_data.exports.len())
+    );
+    assert!(lines[exports_start + 1].ends_with("Offset  Size"));
+    assert_eq!(
+        lines.len(),
+        exports_start + 3 + asset.asset_data.exports.len()
+    );
+
+    // columns are aligned
+    let class_column = lines[1].find("Class").expect("Class header");
+    for line in &lines[3..exports_start - 1] {
+        assert_eq!(line.find('/'), Some(class_column));
+    }
+
+    Ok(())
+}
+
+#[test]
+fn display() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    assert_eq!(FName::new_dummy("Foo".to_string(), 0).to_string(), "Foo");
+    assert_eq!(FName::new_dummy("Foo".to_string(), 4).to_string(), "Foo_3");
+
+    let import = &asset.imports[0];
+    assert_eq!(
+        import.to_string(),
+        format!(
+            "{}.{}'{}'",
+            import.class_package, import.class_name, import.object_name
+        )
+    );
+    assert!(import
+        .display(&asset)
+        .to_string()
+        .ends_with(&format!("'{}'", import.resolved_path(&asset.imports))));
+
+    assert_eq!(PackageIndex::new(0).display(&asset).to_string(), "0 (null)");
+    assert_eq!(
+        PackageIndex::new(-1).display(&asset).to_string(),
+        format!("-1 ({})", import.resolved_path(&asset.imports))
+    );
+
+    let export_path = asset
+        .get_export_object_path(PackageIndex::new(1))
+        .expect("Export path");
+    assert_eq!(
+        PackageIndex::new(1).display(&asset).to_string(),
+        format!("1 ({export_path})")
+    );
+
+    let export = asset.asset_data.exports[0].get_base_export();
+    assert_eq!(
+        export.object_name.display(&asset).to_string(),
+        export.object_name.to_string()
+    );
+
+    Ok(())
+}
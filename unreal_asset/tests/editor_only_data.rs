@@ -0,0 +1,70 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    containers::{Chain, NameMap},
+    engine_version::EngineVersion,
+    flags::EStripDataFlags,
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    reader::{RawReader, RawWriter},
+    types::{PackageIndex, StripDataFlags},
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn cooked_asset_strips_editor_data() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    assert!(!asset.has_editor_only_data());
+    assert_eq!(asset.localization_id, None);
+    assert_eq!(asset.persistent_guid, None);
+    assert!(asset.get_strip_data_flags().is_editor_data_stripped());
+
+    Ok(())
+}
+
+#[test]
+fn strip_data_flags() -> Result<(), Error> {
+    let flags = StripDataFlags::new(EStripDataFlags::EDITOR | EStripDataFlags::SERVER, 0x4);
+
+    let mut data = Cursor::new(Vec::new());
+    let mut writer = RawWriter::<PackageIndex, _>::new(
+        &mut data,
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        NameMap::new(),
+    );
+    flags.write(&mut writer)?;
+
+    let mut reader = RawReader::<PackageIndex, _>::new(
+        Chain::new(Cursor::new(data.into_inner()), None),
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        NameMap::new(),
+    );
+    let read = StripDataFlags::read(&mut reader)?;
+    assert_eq!(read, flags);
+    assert!(read.is_editor_data_stripped());
+    assert!(read.is_server_data_stripped());
+    assert!(read.is_class_data_stripped(0x4));
+    assert!(!read.is_class_data_stripped(0x1));
+
+    Ok(())
+}
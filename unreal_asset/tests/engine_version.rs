@@ -0,0 +1,148 @@
+use unreal_asset::{
+    custom_version::{
+        CustomVersion, FCoreObjectVersion, FFortniteMainBranchObjectVersion,
+        FFrameworkObjectVersion,
+    },
+    engine_version::{
+        get_all_object_versions, get_minimum_engine_version, get_minimum_engine_version_ue5,
+        guess_engine_version, guess_engine_versions, EngineVersion, EngineVersionEvidence,
+    },
+    object_version::{ObjectVersion, ObjectVersionUE5},
+};
+
+#[test]
+fn guess_from_custom_versions() {
+    let custom_versions = [
+        CustomVersion::from_version(FCoreObjectVersion::SkeletalMaterialEditorDataStripping),
+        CustomVersion::from_version(
+            FFrameworkObjectVersion::BlueprintGeneratedClassIsAlwaysAuthoritative,
+        ),
+        CustomVersion::from_version(FFortniteMainBranchObjectVersion::FortHUDElementNowRequiresTag),
+    ];
+
+    let candidates = guess_engine_versions(
+        ObjectVersion::VER_UE4_FIX_WIDE_STRING_CRC,
+        ObjectVersionUE5::UNKNOWN,
+        &custom_versions,
+    );
+    assert_eq!(candidates.len(), 3);
+    assert_eq!(candidates[0].engine_version, EngineVersion::VER_UE4_22);
+    assert_eq!(candidates[0].confidence, 1.0);
+    assert!(candidates[0].evidence.iter().all(|e| e.is_supporting()));
+    assert!(candidates[1..].iter().all(|e| e.confidence < 1.0));
+
+    assert_eq!(
+        guess_engine_version(
+            ObjectVersion::VER_UE4_FIX_WIDE_STRING_CRC,
+            ObjectVersionUE5::UNKNOWN,
+            &custom_versions,
+        ),
+        EngineVersion::VER_UE4_22
+    );
+}
+
+#[test]
+fn guess_between_releases() {
+    let custom_versions = [CustomVersion::from_version(
+        FFrameworkObjectVersion::EnforceConstInAnimBlueprintFunctionGraphs,
+    )];
+
+    let candidates = guess_engine_versions(
+        ObjectVersion::VER_UE4_ADDED_SWEEP_WHILE_WALKING_FLAG,
+        ObjectVersionUE5::UNKNOWN,
+        &custom_versions,
+    );
+    assert_eq!(candidates[0].engine_version, EngineVersion::VER_UE4_16);
+    assert!(candidates[0].confidence < 1.0);
+    assert!(matches!(
+        candidates[0].evidence[1],
+        EngineVersionEvidence::CustomVersionBetweenReleases(_)
+    ));
+    assert!(matches!(
+        candidates[1].evidence[1],
+        EngineVersionEvidence::CustomVersionMismatch(_, _)
+    ));
+}
+
+#[test]
+fn guess_ue5() {
+    assert_eq!(
+        guess_engine_version(
+            ObjectVersion::VER_UE4_CORRECT_LICENSEE_FLAG,
+            ObjectVersionUE5::LARGE_WORLD_COORDINATES,
+            &[],
+        ),
+        EngineVersion::VER_UE5_0
+    );
+    assert!(
+        guess_engine_versions(ObjectVersion::UNKNOWN, ObjectVersionUE5::UNKNOWN, &[]).is_empty()
+    );
+}
+
+#[test]
+fn object_version_mapping() {
+    assert_eq!(
+        EngineVersion::VER_UE4_18.to_object_versions(),
+        (
+            ObjectVersion::VER_UE4_ADDED_SOFT_OBJECT_PATH,
+            ObjectVersionUE5::UNKNOWN
+        )
+    );
+    assert_eq!(
+        get_all_object_versions().count(),
+        EngineVersion::RELEASES.len()
+    );
+
+    // object versions between releases need the next release
+    assert_eq!(
+        get_minimum_engine_version(ObjectVersion::VER_UE4_ADDED_SOFT_OBJECT_PATH),
+        Some(EngineVersion::VER_UE4_18)
+    );
+    assert_eq!(
+        get_minimum_engine_version(ObjectVersion::VER_UE4_APEX_CLOTH_TESSELLATION),
+        Some(EngineVersion::VER_UE4_9)
+    );
+    assert_eq!(
+        get_minimum_engine_version(ObjectVersion::VER_UE4_AUTOMATIC_VERSION),
+        None
+    );
+    assert_eq!(
+        get_minimum_engine_version_ue5(ObjectVersionUE5::ADD_SOFTOBJECTPATH_LIST),
+        Some(EngineVersion::VER_UE5_1)
+    );
+    assert_eq!(
+        get_minimum_engine_version_ue5(ObjectVersionUE5::UNKNOWN),
+        None
+    );
+}
+
+#[test]
+fn version_strings() {
+    for engine_version in EngineVersion::RELEASES {
+        assert_eq!(
+            EngineVersion::from_version_string(&engine_version.to_string()),
+            Some(engine_version)
+        );
+    }
+
+    assert_eq!(EngineVersion::VER_UE4_27.to_string(), "4.27");
+    assert_eq!(EngineVersion::UNKNOWN.to_string(), "UNKNOWN");
+    assert_eq!(EngineVersion::VER_UE5_1.get_major_minor(), Some((5, 1)));
+    assert!(!EngineVersion::VER_UE4_AUTOMATIC_VERSION.is_release());
+
+    assert_eq!(
+        EngineVersion::from_version_string("UE4.25"),
+        Some(EngineVersion::VER_UE4_25)
+    );
+    assert_eq!(
+        EngineVersion::from_version_string("5.0.3"),
+        Some(EngineVersion::VER_UE5_0)
+    );
+    assert_eq!(
+        EngineVersion::from_version_string("ver_ue4_26"),
+        Some(EngineVersion::VER_UE4_26)
+    );
+    assert_eq!(EngineVersion::from_version_string("4.28"), None);
+    assert_eq!(EngineVersion::from_version_string("4"), None);
+    assert_eq!(EngineVersion::from_version_string("UNKNOWN"), None);
+}
@@ -0,0 +1,131 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    events::{AssetEvent, ParseControl},
+    exports::{ExportBaseTrait, ExportNormalTrait},
+    properties::PropertyDataTrait,
+    types::PackageIndex,
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn events_match_full_parse() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let mut names = Vec::new();
+    let mut imports = Vec::new();
+    let mut exports = Vec::new();
+    let mut properties = Vec::new();
+
+    Asset::parse_events(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        |event| {
+            match event {
+                AssetEvent::NameEntry { name, .. } => names.push(name.to_string()),
+                AssetEvent::Import { import, .. } => imports.push(import.clone()),
+                AssetEvent::ExportHeader { export, .. } => {
+                    exports.push(export.object_name.get_owned_content())
+                }
+                AssetEvent::PropertyStart { export, tag } => {
+                    properties.push((export, tag.name.get_owned_content()))
+                }
+                AssetEvent::PropertyEnd { .. } => {}
+            }
+            ParseControl::Continue
+        },
+    )?;
+
+    assert_eq!(
+        names,
+        asset.get_name_map().get_ref().get_name_map_index_list()
+    );
+    assert_eq!(imports, asset.imports);
+    assert_eq!(
+        exports,
+        asset
+            .asset_data
+            .exports
+            .iter()
+            .map(|e| e.get_base_export().object_name.get_owned_content())
+            .collect::<Vec<_>>()
+    );
+
+    let mut expected_properties = Vec::new();
+    for (i, export) in asset.asset_data.exports.iter().enumerate() {
+        if let Some(normal_export) = export.get_normal_export() {
+            for property in &normal_export.properties {
+                expected_properties.push((
+                    PackageIndex::new(i as i32 + 1),
+                    property.get_name().get_owned_content(),
+                ));
+            }
+        }
+    }
+    assert_eq!(properties, expected_properties);
+
+    Ok(())
+}
+
+#[test]
+fn events_stop() -> Result<(), Error> {
+    let mut count = 0;
+    Asset::parse_events(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        |_| {
+            count += 1;
+            ParseControl::Stop
+        },
+    )?;
+    assert_eq!(count, 1);
+
+    Ok(())
+}
+
+#[test]
+fn events_invalid_property_tag() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    // the name index of the first property tag of the first export
+    let offset = asset.asset_data.exports[0].get_base_export().serial_offset as usize;
+    let mut data = TEST_ASSET.to_vec();
+    data[offset..offset + 4].copy_from_slice(&i32::MAX.to_le_bytes());
+
+    let result = Asset::parse_events(
+        Cursor::new(data),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        |_| ParseControl::Continue,
+    );
+    assert!(result.is_err());
+
+    Ok(())
+}
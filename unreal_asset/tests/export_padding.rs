@@ -0,0 +1,67 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    exports::{ExportBaseTrait, ExportNormalTrait},
+    reader::ParseOptions,
+    Asset, Error,
+};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+const ALIGNMENT: u64 = 16;
+
+#[test]
+fn export_padding() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    for export in &mut asset.asset_data.exports {
+        if let Some(normal_export) = export.get_normal_export_mut() {
+            normal_export.padding_alignment = Some(ALIGNMENT);
+        }
+    }
+
+    let mut cursor = Cursor::new(Vec::new());
+    asset.write_data(&mut cursor, None)?;
+    let padded = cursor.into_inner();
+
+    let parse_options = ParseOptions {
+        export_alignment: Some(ALIGNMENT),
+        ..Default::default()
+    };
+    let mut padded_asset = Asset::new_with_options(
+        Cursor::new(padded.as_slice()),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        parse_options,
+    )?;
+
+    let exports = &padded_asset.asset_data.exports;
+    for (previous, export) in exports.iter().zip(exports.iter().skip(1)) {
+        if previous.get_normal_export().is_some() {
+            assert_eq!(export.get_base_export().serial_offset as u64 % ALIGNMENT, 0);
+        }
+    }
+
+    // unmodified padded exports are written back byte for byte
+    shared::verify_binary_equality(&padded, None, &mut padded_asset)?;
+
+    Ok(())
+}
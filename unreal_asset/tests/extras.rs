@@ -0,0 +1,96 @@
+use std::io::{Cursor, Read};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    extras::{self, ExtrasContext, ExtrasHandler},
+    object_redirector::create_object_redirector,
+    types::PackageIndex,
+    Asset, Error,
+};
+
+/// Extras of an ObjectRedirector, the index of the redirector's target
+struct RedirectorExtras;
+
+impl ExtrasHandler for RedirectorExtras {
+    type Extras = PackageIndex;
+
+    fn read(&self, extras: &[u8], context: &ExtrasContext) -> Result<Self::Extras, Error> {
+        assert_eq!(context.class_name, "ObjectRedirector");
+
+        let mut reader = Cursor::new(extras);
+        reader.read_i32::<LE>()?;
+        let target = PackageIndex::new(reader.read_i32::<LE>()?);
+        if reader.read(&mut [0u8])? != 0 {
+            return Err(Error::invalid_file(
+                "Trailing redirector extras".to_string(),
+            ));
+        }
+        Ok(target)
+    }
+
+    fn write(&self, extras: &Self::Extras, _context: &ExtrasContext) -> Result<Vec<u8>, Error> {
+        let mut data = Vec::with_capacity(8);
+        data.write_i32::<LE>(0)?;
+        data.write_i32::<LE>(extras.index)?;
+        Ok(data)
+    }
+}
+
+#[test]
+fn extras_handler() -> Result<(), Error> {
+    let asset = create_object_redirector(
+        EngineVersion::VER_UE4_23,
+        true,
+        "/Game/Old/Asset",
+        "/Game/New/Renamed.Renamed",
+        "/Script/Engine.Blueprint",
+    )?;
+
+    let mut uasset = Cursor::new(Vec::new());
+    let mut uexp = Cursor::new(Vec::new());
+    asset.write_data(&mut uasset, Some(&mut uexp))?;
+
+    let mut asset = Asset::new(
+        Cursor::new(uasset.into_inner()),
+        Some(Cursor::new(uexp.into_inner())),
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    let export_index = PackageIndex::new(1);
+
+    // extras stay unparsed without a handler
+    assert_eq!(asset.read_extras::<PackageIndex>(export_index)?, None);
+
+    extras::register_extras_handler("ObjectRedirector", RedirectorExtras);
+    assert!(extras::has_extras_handler("ObjectRedirector"));
+
+    assert_eq!(
+        asset.read_extras::<PackageIndex>(export_index)?,
+        Some(PackageIndex::new(-5))
+    );
+    assert!(asset.read_extras::<String>(export_index).is_err());
+
+    assert!(asset.write_extras(export_index, &PackageIndex::new(-4))?);
+    assert!(asset.write_extras(export_index, &0u32).is_err());
+
+    let mut uasset = Cursor::new(Vec::new());
+    let mut uexp = Cursor::new(Vec::new());
+    asset.write_data(&mut uasset, Some(&mut uexp))?;
+    let parsed = Asset::new(
+        Cursor::new(uasset.into_inner()),
+        Some(Cursor::new(uexp.into_inner())),
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    assert_eq!(
+        parsed.read_extras::<PackageIndex>(export_index)?,
+        Some(PackageIndex::new(-4))
+    );
+
+    assert!(extras::unregister_extras_handler("ObjectRedirector"));
+    assert_eq!(parsed.read_extras::<PackageIndex>(export_index)?, None);
+
+    Ok(())
+}
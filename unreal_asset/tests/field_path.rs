@@ -0,0 +1,65 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    containers::{Chain, NameMap},
+    fproperty::{FFieldPathProperty, FGenericProperty, FProperty},
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    reader::{RawReader, RawWriter},
+    types::{FieldPath, PackageIndex},
+    Error,
+};
+
+#[test]
+fn field_path() -> Result<(), Error> {
+    let mut name_map = NameMap::new();
+    let (field_path, property) = {
+        let mut map = name_map.get_mut();
+        let field_path = FieldPath::new(
+            vec![map.add_fname("Inner"), map.add_fname("Outer")],
+            PackageIndex::new(-2),
+        );
+        let property: FProperty = FFieldPathProperty {
+            generic_property: FGenericProperty {
+                name: map.add_fname("TargetProperty"),
+                rep_notify_func: map.add_fname("None"),
+                ..Default::default()
+            },
+            property_class: map.add_fname("FloatProperty"),
+        }
+        .into();
+        (field_path, property)
+    };
+
+    let mut data = Cursor::new(Vec::new());
+    let mut writer = RawWriter::<PackageIndex, _>::new(
+        &mut data,
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        name_map.clone(),
+    );
+    assert_eq!(field_path.write(&mut writer, true)?, 24);
+    field_path.write(&mut writer, false)?;
+    FProperty::write(&property, &mut writer)?;
+
+    let mut reader = RawReader::<PackageIndex, _>::new(
+        Chain::new(Cursor::new(data.into_inner()), None),
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        name_map,
+    );
+    assert_eq!(FieldPath::read(&mut reader, true)?, field_path);
+
+    let without_owner = FieldPath::read(&mut reader, false)?;
+    assert_eq!(without_owner.path, field_path.path);
+    assert_eq!(without_owner.resolved_owner, PackageIndex::new(0));
+
+    let read = match FProperty::new(&mut reader)? {
+        FProperty::FFieldPathProperty(read) => read,
+        other => panic!("Field path property was read as {other:?}"),
+    };
+    assert_eq!(read.property_class, "FloatProperty");
+
+    Ok(())
+}
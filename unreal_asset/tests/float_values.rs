@@ -0,0 +1,49 @@
+use ordered_float::OrderedFloat;
+
+use unreal_asset::{
+    properties::{
+        color_property::LinearColorProperty, int_property::FloatProperty,
+        per_platform_property::PerPlatformFloatProperty,
+        rich_curve_key_property::RichCurveKeyProperty, vector_property::VectorProperty,
+    },
+    types::{
+        vector::{Color, Vector},
+        FName,
+    },
+};
+
+#[test]
+fn scalar_values() {
+    let mut property = FloatProperty::default();
+    property.set_value(1.5);
+    assert_eq!(property.value, OrderedFloat(1.5));
+    assert_eq!(property.value(), 1.5);
+
+    let mut key = RichCurveKeyProperty::default();
+    key.set_time(2.0);
+    key.set_leave_tangent_weight(0.25);
+    assert_eq!(key.time(), 2.0);
+    assert_eq!(key.leave_tangent_weight, OrderedFloat(0.25));
+}
+
+#[test]
+fn component_values() {
+    let mut property = VectorProperty::default();
+    property.set_value(Vector::new(1.0, 2.0, 3.0));
+    assert_eq!(
+        property.value,
+        Vector::new(OrderedFloat(1.0), OrderedFloat(2.0), OrderedFloat(3.0))
+    );
+    assert_eq!(property.value(), Vector::new(1.0, 2.0, 3.0));
+
+    let mut property = LinearColorProperty::default();
+    property.set_color(Color::new(0.5, 0.25, 0.0, 1.0));
+    assert_eq!(property.color.a, OrderedFloat(1.0));
+    assert_eq!(property.color(), Color::new(0.5, 0.25, 0.0, 1.0));
+
+    let mut property = PerPlatformFloatProperty::default();
+    let per_platform = vec![(FName::new_dummy("Mobile".to_string(), 0), 0.5)];
+    property.set_per_platform(per_platform.clone());
+    assert_eq!(property.per_platform[0].1, OrderedFloat(0.5));
+    assert_eq!(property.per_platform(), per_platform);
+}
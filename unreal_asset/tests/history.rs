@@ -0,0 +1,88 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    exports::{Export, ExportNormalTrait},
+    history::AssetHistory,
+    types::PackageIndex,
+    Asset, Error,
+};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn undo_redo() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    let original_exports = asset.asset_data.exports.clone();
+
+    let edited = asset
+        .asset_data
+        .exports
+        .iter()
+        .position(|e| matches!(e, Export::NormalExport(e) if !e.properties.is_empty()))
+        .expect("No NormalExport with properties");
+    let edited = PackageIndex::new(edited as i32 + 1);
+
+    let mut history = AssetHistory::new();
+
+    history
+        .edit_export(&mut asset.asset_data, edited, |export| {
+            export
+                .get_normal_export_mut()
+                .expect("Export is not a NormalExport")
+                .properties
+                .clear()
+        })
+        .expect("Export doesn't exist");
+    let edited_exports = asset.asset_data.exports.clone();
+    assert_ne!(edited_exports, original_exports);
+
+    let removed = history
+        .remove_export(&mut asset.asset_data, PackageIndex::new(1))
+        .expect("First export doesn't exist");
+    let index = history.add_export(&mut asset.asset_data, removed);
+    assert_eq!(index.index, asset.asset_data.exports.len() as i32);
+
+    assert!(history.undo(&mut asset.asset_data));
+    assert!(history.undo(&mut asset.asset_data));
+    assert_eq!(asset.asset_data.exports, edited_exports);
+
+    assert!(history.undo(&mut asset.asset_data));
+    assert_eq!(asset.asset_data.exports, original_exports);
+    assert!(!history.undo(&mut asset.asset_data));
+
+    assert!(history.redo(&mut asset.asset_data));
+    assert_eq!(asset.asset_data.exports, edited_exports);
+
+    history.begin_group();
+    let removed = history
+        .remove_export(&mut asset.asset_data, PackageIndex::new(1))
+        .expect("First export doesn't exist");
+    history.add_export(&mut asset.asset_data, removed);
+    history.end_group();
+    assert!(!history.can_redo());
+
+    assert!(history.undo(&mut asset.asset_data));
+    assert_eq!(asset.asset_data.exports, edited_exports);
+
+    assert!(history.undo(&mut asset.asset_data));
+    shared::verify_binary_equality(TEST_ASSET, None, &mut asset)?;
+
+    Ok(())
+}
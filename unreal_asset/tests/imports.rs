@@ -0,0 +1,123 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion, imports::Imports, types::PackageIndex, Asset, Import,
+};
+
+fn get_import(asset: &Asset<Cursor<Vec<u8>>>, index: PackageIndex) -> Import {
+    asset.get_import(index).expect("Import doesn't exist")
+}
+
+#[test]
+fn import_chains() {
+    let mut asset = Asset::new_empty(EngineVersion::VER_UE4_25, true, None);
+
+    let mut imports = asset.import_factory();
+    let data_table = imports.class("Engine", "DataTable");
+    let template = imports.default_object("/Script/Engine", "DataTable");
+    let engine = imports.package("Engine");
+    let door = imports.blueprint_class("/Game/BP_Door", "BP_Door_C");
+
+    // the engine package is shared by all imports in it
+    assert_eq!(asset.imports.len(), 5);
+
+    let engine_import = get_import(&asset, engine);
+    assert_eq!(
+        engine_import.object_name.get_owned_content(),
+        "/Script/Engine"
+    );
+    assert_eq!(engine_import.class_name.get_owned_content(), "Package");
+    assert_eq!(engine_import.outer_index, PackageIndex::new(0));
+
+    let data_table_import = get_import(&asset, data_table);
+    assert_eq!(
+        data_table_import.class_package.get_owned_content(),
+        "/Script/CoreUObject"
+    );
+    assert_eq!(data_table_import.class_name.get_owned_content(), "Class");
+    assert_eq!(data_table_import.outer_index, engine);
+
+    let template_import = get_import(&asset, template);
+    assert_eq!(
+        template_import.class_package.get_owned_content(),
+        "/Script/Engine"
+    );
+    assert_eq!(template_import.class_name.get_owned_content(), "DataTable");
+    assert_eq!(
+        template_import.object_name.get_owned_content(),
+        "Default__DataTable"
+    );
+    assert_eq!(template_import.outer_index, engine);
+
+    let door_import = get_import(&asset, door);
+    assert_eq!(
+        door_import.class_name.get_owned_content(),
+        "BlueprintGeneratedClass"
+    );
+    let door_package = get_import(&asset, door_import.outer_index);
+    assert_eq!(
+        door_package.object_name.get_owned_content(),
+        "/Game/BP_Door"
+    );
+
+    // existing imports are reused
+    let mut imports = Imports::new(&mut asset);
+    assert_eq!(imports.class("Engine", "DataTable"), data_table);
+    assert_eq!(asset.imports.len(), 5);
+
+    assert!(asset.search_name_reference("Default__DataTable").is_some());
+}
+
+#[test]
+fn add_import_chain() {
+    let mut asset = Asset::new_empty(EngineVersion::VER_UE4_25, true, None);
+
+    let mesh = asset.add_import_chain(
+        "/Game/BP_Door",
+        &[
+            ("/Game/BP_Door", "BP_Door_C", "Default__BP_Door_C"),
+            ("Engine", "StaticMeshComponent", "Mesh"),
+        ],
+    );
+    assert_eq!(asset.imports.len(), 3);
+
+    let mesh_import = get_import(&asset, mesh);
+    assert_eq!(mesh_import.object_name.get_owned_content(), "Mesh");
+    assert_eq!(
+        mesh_import.class_package.get_owned_content(),
+        "/Script/Engine"
+    );
+    assert_eq!(
+        mesh_import.class_name.get_owned_content(),
+        "StaticMeshComponent"
+    );
+
+    let default_object = get_import(&asset, mesh_import.outer_index);
+    assert_eq!(
+        default_object.object_name.get_owned_content(),
+        "Default__BP_Door_C"
+    );
+    assert_eq!(default_object.class_name.get_owned_content(), "BP_Door_C");
+
+    let package = get_import(&asset, default_object.outer_index);
+    assert_eq!(package.object_name.get_owned_content(), "/Game/BP_Door");
+    assert_eq!(package.outer_index, PackageIndex::new(0));
+
+    // shared outers are reused
+    let other = asset.add_import_chain(
+        "/Game/BP_Door",
+        &[
+            ("/Game/BP_Door", "BP_Door_C", "Default__BP_Door_C"),
+            ("Engine", "BoxComponent", "Collision"),
+        ],
+    );
+    assert_eq!(asset.imports.len(), 4);
+    assert_eq!(
+        get_import(&asset, other).outer_index,
+        mesh_import.outer_index
+    );
+    assert_eq!(
+        asset.add_import_chain("/Game/BP_Door", &[]),
+        default_object.outer_index
+    );
+}
@@ -0,0 +1,112 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    index::{IndexedPackage, SummaryIndex},
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+const PACKAGE_PATH: &str = "/Game/Items/Augment_BroadBrush";
+
+#[test]
+fn summary_index() -> Result<(), Error> {
+    let mut index = SummaryIndex::new();
+    index.index_asset(
+        PACKAGE_PATH,
+        "Augment_BroadBrush.uasset",
+        TEST_ASSET,
+        false,
+        EngineVersion::VER_UE4_23,
+    )?;
+    assert_eq!(index.len(), 1);
+
+    let package = index.get(PACKAGE_PATH).expect("Package was indexed");
+
+    // indexing only parses the header, which has to give the same result as parsing the whole asset
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    assert_eq!(
+        package,
+        &IndexedPackage::from_asset(PACKAGE_PATH, "Augment_BroadBrush.uasset", &asset)
+    );
+
+    assert_eq!(package.class_names.len(), asset.asset_data.exports.len());
+    assert!(!package.imported_packages.is_empty());
+
+    let referenced = package.imported_packages[0].clone();
+    assert_eq!(index.find_referencing(&referenced), vec![package]);
+    assert_eq!(
+        index.find_referencing(&referenced.to_uppercase()),
+        vec![package]
+    );
+    assert!(index.find_referencing("/Game/NotReferenced").is_empty());
+
+    assert_eq!(index.find_by_class(&package.class_names[0]), vec![package]);
+    assert!(index.find_by_class("NotAClass").is_empty());
+
+    let mut data = Cursor::new(Vec::new());
+    index.write(&mut data)?;
+    data.set_position(0);
+    assert_eq!(SummaryIndex::read(&mut data)?, index);
+
+    Ok(())
+}
+
+#[cfg(feature = "pak")]
+#[test]
+fn summary_index_scan_paks() -> Result<(), Error> {
+    use unreal_pak::{pakversion::PakVersion, PakWriter};
+
+    let directory =
+        std::env::temp_dir().join(format!("unreal_asset_index_test_{}", std::process::id()));
+    let paks = directory.join("Astro/Content/Paks");
+    std::fs::create_dir_all(&paks)?;
+
+    let pak_path = paks.join("Astro-WindowsNoEditor.pak");
+    let mut pak = PakWriter::new(
+        std::fs::File::create(&pak_path)?,
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    let write_entry = |pak: &mut PakWriter<_>, name: &str, data: &[u8]| {
+        pak.write_entry(&name.to_string(), data, false)
+            .map_err(|e| Error::invalid_file(e.to_string()))
+    };
+    write_entry(
+        &mut pak,
+        "Astro/Content/Items/Augment_BroadBrush.uasset",
+        TEST_ASSET,
+    )?;
+    write_entry(&mut pak, "Astro/Content/Items/Broken.uasset", &[0; 16])?;
+    pak.finish_write()
+        .map_err(|e| Error::invalid_file(e.to_string()))?;
+
+    let mut index = SummaryIndex::new();
+    let failures = index.scan_paks(&directory, "Astro", EngineVersion::VER_UE4_23)?;
+
+    assert_eq!(failures.len(), 1);
+    assert_eq!(
+        failures[0].entry.as_deref(),
+        Some("Astro/Content/Items/Broken.uasset")
+    );
+
+    assert_eq!(index.len(), 1);
+    let package = index.get(PACKAGE_PATH).expect("Package was indexed");
+    assert_eq!(package.source, pak_path.to_string_lossy());
+
+    std::fs::remove_dir_all(directory)?;
+    Ok(())
+}
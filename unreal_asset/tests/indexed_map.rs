@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use unreal_asset::containers::indexed_map::IndexedMap;
+use unreal_asset::containers::indexed_map::{entry::Entry, IndexedMap};
 
 #[test]
 fn insertion() {
@@ -99,3 +99,126 @@ fn mutable_iterator() {
         index += 1;
     }
 }
+
+#[test]
+fn removal_keeps_order() {
+    let mut indexed_map =
+        IndexedMap::from([("Test1", 1), ("Test2", 2), ("Test3", 3), ("Test4", 4)]);
+
+    indexed_map.remove_by_key("Test1");
+    indexed_map.remove_by_key("Test4");
+    indexed_map.insert("Test5", 5);
+
+    let values = indexed_map.values().copied().collect::<Vec<_>>();
+    assert_eq!(values, vec![2, 3, 5]);
+
+    assert_eq!(indexed_map.get_by_index(1), Some(&2));
+    assert_eq!(indexed_map.get_by_index(2), Some(&3));
+    assert_eq!(indexed_map.get_by_index(3), Some(&5));
+}
+
+#[test]
+fn retain() {
+    let mut indexed_map =
+        IndexedMap::from([("Test1", 1), ("Test2", 2), ("Test3", 3), ("Test4", 4)]);
+
+    indexed_map.retain(|_, value| {
+        *value *= 10;
+        *value != 20
+    });
+
+    let keys = indexed_map.keys().copied().collect::<Vec<_>>();
+    assert_eq!(keys, vec!["Test1", "Test3", "Test4"]);
+    assert_eq!(indexed_map.get_by_key("Test3"), Some(&30));
+
+    indexed_map.remove_by_key("Test4");
+    assert_eq!(indexed_map.len(), 2);
+}
+
+#[test]
+fn drain() {
+    let mut indexed_map = IndexedMap::from([("Test1", 1), ("Test2", 2), ("Test3", 3)]);
+
+    let drained = indexed_map
+        .drain()
+        .map(|(_, key, value)| (key, value))
+        .collect::<Vec<_>>();
+
+    assert_eq!(drained, vec![("Test1", 1), ("Test2", 2), ("Test3", 3)]);
+    assert!(indexed_map.is_empty());
+}
+
+#[test]
+fn entry() {
+    let mut indexed_map = IndexedMap::from([("Test1", 1), ("Test2", 2)]);
+
+    *indexed_map.entry("Test1").or_insert(10) += 1;
+    indexed_map.entry("Test3").or_insert_with(|| 3);
+    indexed_map
+        .entry("Test2")
+        .and_modify(|value| *value *= 10)
+        .or_default();
+    *indexed_map.entry("Test4").or_default() += 4;
+
+    let entries = indexed_map
+        .iter()
+        .map(|(index, key, value)| (index, *key, *value))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        entries,
+        vec![
+            (0, "Test1", 2),
+            (1, "Test2", 20),
+            (2, "Test3", 3),
+            (3, "Test4", 4)
+        ]
+    );
+
+    match indexed_map.entry("Test2") {
+        Entry::Occupied(mut entry) => {
+            assert_eq!(entry.index(), 1);
+            assert_eq!(entry.insert(5), 20);
+            assert_eq!(entry.remove(), 5);
+        }
+        Entry::Vacant(_) => panic!("Test2 is vacant"),
+    }
+    assert!(matches!(indexed_map.entry("Test2"), Entry::Vacant(_)));
+    assert_eq!(
+        indexed_map.keys().copied().collect::<Vec<_>>(),
+        vec!["Test1", "Test3", "Test4"]
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn serde() {
+    use serde::de::{value::MapDeserializer, Deserialize};
+
+    let indexed_map = IndexedMap::from([("Test2".to_string(), 2), ("Test1".to_string(), 1)]);
+    let json = serde_json::to_string(&indexed_map).unwrap();
+    assert_eq!(json, r#"{"Test2":2,"Test1":1}"#);
+    assert_eq!(
+        serde_json::from_str::<IndexedMap<String, i32>>(&json).unwrap(),
+        indexed_map
+    );
+
+    /// Iterator which claims to contain far more entries than it does
+    struct LyingIterator;
+
+    impl Iterator for LyingIterator {
+        type Item = (i32, i32);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            None
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (usize::MAX, Some(usize::MAX))
+        }
+    }
+
+    // the size hint is not used to allocate more than a bounded amount of memory
+    let deserializer = MapDeserializer::<_, serde::de::value::Error>::new(LyingIterator);
+    let indexed_map = IndexedMap::<i32, i32>::deserialize(deserializer).unwrap();
+    assert!(indexed_map.is_empty());
+}
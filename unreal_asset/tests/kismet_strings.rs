@@ -0,0 +1,78 @@
+#![cfg(feature = "kismet")]
+
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    kismet_strings::{find_kismet_strings, replace_kismet_string, replace_kismet_strings},
+    Asset, Error,
+};
+
+mod shared;
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/assets/general/Astroneer_prebulk/DebugMenu.uasset"
+));
+
+fn write_asset(asset: &Asset<Cursor<&[u8]>>) -> Result<Vec<u8>, Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    asset.write_data(&mut cursor, None::<&mut Cursor<Vec<u8>>>)?;
+    Ok(cursor.into_inner())
+}
+
+#[test]
+fn kismet_strings() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let strings = find_kismet_strings(&asset);
+    let string = strings
+        .iter()
+        .find(|e| e.value == "AstroToggleHUD 0")
+        .expect("String constant was found")
+        .clone();
+    assert!(!string.in_text);
+    assert!(!string.unicode);
+    assert!(strings.iter().any(|e| e.in_text));
+
+    // a longer string moves all code after it
+    replace_kismet_string(&mut asset, &string, "AstroToggleHUD 0 | stat fps")?;
+    let written = write_asset(&asset)?;
+    let reparsed = Asset::new(
+        Cursor::new(written.as_slice()),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    let reparsed_strings = find_kismet_strings(&reparsed);
+    assert_eq!(reparsed_strings, find_kismet_strings(&asset));
+    assert_eq!(reparsed_strings.len(), strings.len());
+    let replaced = reparsed_strings
+        .iter()
+        .find(|e| e.export == string.export && e.path == string.path)
+        .expect("Replaced string was found");
+    assert_eq!(replaced.value, "AstroToggleHUD 0 | stat fps");
+
+    // restoring the original string restores all offsets
+    let replaced_count = replace_kismet_strings(&mut asset, |e| {
+        (e.export == string.export && e.path == string.path).then(|| string.value.clone())
+    })?;
+    assert_eq!(replaced_count, 1);
+    shared::verify_binary_equality(TEST_ASSET, None, &mut asset)?;
+
+    // non-ascii strings are written as unicode strings
+    replace_kismet_string(&mut asset, &string, "AstroToggleHUD Ü")?;
+    let replaced = find_kismet_strings(&asset)
+        .into_iter()
+        .find(|e| e.export == string.export && e.path == string.path)
+        .expect("Replaced string was found");
+    assert!(replaced.unicode);
+    assert_eq!(replaced.value, "AstroToggleHUD Ü");
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion, exports::ExportBaseTrait, types::PackageIndex, Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn many_exports_mut() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let first = PackageIndex::new(1);
+    let second = PackageIndex::new(2);
+    let first_name = asset
+        .get_export(first)
+        .unwrap()
+        .get_base_export()
+        .object_name
+        .clone();
+    let second_name = asset
+        .get_export(second)
+        .unwrap()
+        .get_base_export()
+        .object_name
+        .clone();
+
+    let [first_export, second_export] = asset
+        .get_many_exports_mut([first, second])
+        .expect("Failed to get exports");
+    std::mem::swap(
+        &mut first_export.get_base_export_mut().object_name,
+        &mut second_export.get_base_export_mut().object_name,
+    );
+
+    assert_eq!(
+        asset
+            .get_export(first)
+            .unwrap()
+            .get_base_export()
+            .object_name,
+        second_name
+    );
+    assert_eq!(
+        asset
+            .get_export(second)
+            .unwrap()
+            .get_base_export()
+            .object_name,
+        first_name
+    );
+
+    let export_count = asset.asset_data.exports.len() as i32;
+    assert!(asset.get_many_exports_mut([first, first]).is_none());
+    assert!(asset
+        .get_many_exports_mut([first, PackageIndex::new(-1)])
+        .is_none());
+    assert!(asset.get_many_exports_mut([PackageIndex::new(0)]).is_none());
+    assert!(asset
+        .get_many_exports_mut([PackageIndex::new(export_count + 1)])
+        .is_none());
+    assert!(asset.get_many_exports_mut::<0>([]).is_some());
+
+    Ok(())
+}
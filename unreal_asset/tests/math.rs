@@ -0,0 +1,72 @@
+use ordered_float::OrderedFloat;
+
+use unreal_asset::types::{
+    math::Matrix,
+    vector::{Transform, Vector},
+};
+
+const TOLERANCE: f64 = 1e-6;
+
+fn vector(x: f64, y: f64, z: f64) -> Vector<OrderedFloat<f64>> {
+    Vector::new(OrderedFloat(x), OrderedFloat(y), OrderedFloat(z))
+}
+
+fn assert_vector_eq(a: &Vector<OrderedFloat<f64>>, b: &Vector<OrderedFloat<f64>>) {
+    assert!(a.sub(b).length() < TOLERANCE, "{a:?} != {b:?}");
+}
+
+#[test]
+fn rotator_quat_roundtrip() {
+    let rotator = vector(30.0, -45.0, 60.0);
+    let rotator_roundtrip = rotator.rotator_to_quat().quat_to_rotator();
+
+    assert_vector_eq(&rotator, &rotator_roundtrip);
+}
+
+#[test]
+fn transform_compose() {
+    let transform = Transform::new(
+        vector(10.0, 20.0, 30.0).rotator_to_quat(),
+        vector(100.0, -50.0, 25.0),
+        vector(2.0, 2.0, 2.0),
+    );
+    let parent = Transform::new(
+        vector(0.0, 90.0, 0.0).rotator_to_quat(),
+        vector(0.0, 0.0, 10.0),
+        vector(1.0, 1.0, 1.0),
+    );
+
+    let position = vector(1.0, 2.0, 3.0);
+    let composed = transform.compose(&parent);
+    assert_vector_eq(
+        &composed.transform_position(&position),
+        &parent.transform_position(&transform.transform_position(&position)),
+    );
+
+    let identity = transform.compose(&transform.inverse());
+    assert_vector_eq(&identity.transform_position(&position), &position);
+}
+
+#[test]
+fn matrix_inverse() {
+    let transform = Transform::new(
+        vector(10.0, 20.0, 30.0).rotator_to_quat(),
+        vector(100.0, -50.0, 25.0),
+        vector(1.0, 2.0, 3.0),
+    );
+    let matrix = transform.to_matrix();
+
+    let position = vector(1.0, 2.0, 3.0);
+    assert_vector_eq(
+        &matrix.transform_position(&position),
+        &transform.transform_position(&position),
+    );
+
+    let inverse = matrix.inverse().expect("Matrix is singular");
+    assert_vector_eq(
+        &inverse.transform_position(&matrix.transform_position(&position)),
+        &position,
+    );
+
+    assert!(Matrix::new([[OrderedFloat(0.0); 4]; 4]).inverse().is_none());
+}
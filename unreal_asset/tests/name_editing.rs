@@ -1,6 +1,8 @@
+use unreal_asset::{containers::NameMap, types::fname::FName};
+
 #[test]
 fn name_editing() {
-    let mut map = unreal_asset::containers::name_map::NameMap::from_name_batch(&[
+    let mut map = NameMap::from_name_batch(&[
         "never".to_string(),
         "gonna".to_string(),
         "give".to_string(),
@@ -14,3 +16,75 @@ fn name_editing() {
     let new = map.get_ref().create_fname(i, 0);
     assert_eq!(content, new.get_owned_content());
 }
+
+#[test]
+fn name_lookup() {
+    let names = (0..10000).map(|e| format!("Name_{e}")).collect::<Vec<_>>();
+    let mut map = NameMap::from_name_batch(&names);
+
+    // every name has to resolve to its own entry, no matter what it hashes to
+    for (index, name) in names.iter().enumerate() {
+        assert_eq!(
+            map.get_ref().search_name_reference(name),
+            Some(index as i32)
+        );
+    }
+    assert_eq!(map.get_ref().search_name_reference("Name_10000"), None);
+
+    // replacing an entry keeps the lookup of its old content if it's duplicated
+    let duplicate = map.get_mut().add_name_reference("Name_0".to_string(), true);
+    assert_eq!(
+        map.get_ref().search_name_reference("Name_0"),
+        Some(duplicate)
+    );
+    map.get_mut()
+        .set_name_reference(duplicate, "Renamed".to_string());
+    assert_eq!(map.get_ref().search_name_reference("Name_0"), Some(0));
+    assert_eq!(
+        map.get_ref().search_name_reference("Renamed"),
+        Some(duplicate)
+    );
+}
+
+#[test]
+fn rebuild_lookup() {
+    let mut map = NameMap::from_name_batch(&["never".to_string(), "gonna".to_string()]);
+
+    *map.get_mut().get_name_reference_mut(1) = "let".to_string();
+    assert_eq!(map.get_ref().search_name_reference("let"), None);
+
+    map.get_mut().rebuild_lookup();
+    assert_eq!(map.get_ref().search_name_reference("let"), Some(1));
+    assert_eq!(map.get_ref().search_name_reference("gonna"), None);
+    assert_eq!(map.get_ref().search_name_reference("never"), Some(0));
+}
+
+#[test]
+fn split_number() {
+    assert_eq!(FName::split_number("Foo_3"), ("Foo", 4));
+    assert_eq!(FName::split_number("Foo_0"), ("Foo", 1));
+    assert_eq!(FName::split_number("Foo_Bar_12"), ("Foo_Bar", 13));
+    assert_eq!(FName::split_number("Foo_01"), ("Foo_01", 0));
+    assert_eq!(FName::split_number("Foo3"), ("Foo3", 0));
+    assert_eq!(FName::split_number("Foo_"), ("Foo_", 0));
+    assert_eq!(FName::split_number("123"), ("123", 0));
+    assert_eq!(FName::split_number("Foo_2147483647"), ("Foo_2147483647", 0));
+    assert_eq!(
+        FName::split_number("Foo_99999999999"),
+        ("Foo_99999999999", 0)
+    );
+
+    let mut map = NameMap::new();
+    let split = map.get_mut().add_fname_split("Foo_3");
+    assert_eq!(split, "Foo");
+    assert_eq!(split.get_number(), 4);
+
+    // the same base name is reused instead of adding a new entry for every suffix
+    let other = map.get_mut().add_fname_split("Foo_4");
+    assert_eq!(other.get_number(), 5);
+    assert_eq!(map.get_ref().get_name_map_index_list(), ["Foo".to_string()]);
+
+    let verbatim = map.get_mut().add_fname("Foo_3");
+    assert_eq!(verbatim, "Foo_3");
+    assert_eq!(verbatim.get_number(), 0);
+}
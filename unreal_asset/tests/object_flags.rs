@@ -0,0 +1,23 @@
+use unreal_asset::{exports::BaseExport, flags::EObjectFlags, types::PackageIndex};
+
+#[test]
+fn object_flag_helpers() {
+    let mut export = BaseExport::<PackageIndex>::default();
+    assert!(!export.is_public());
+
+    export.set_asset_flags();
+    assert!(export.is_public());
+    assert!(export.is_standalone());
+    assert!(export.is_transactional());
+    assert_eq!(export.object_flags, EObjectFlags::ASSET_FLAGS);
+
+    export.set_transactional(false);
+    assert!(!export.is_transactional());
+    assert_eq!(
+        export.object_flags,
+        EObjectFlags::RF_PUBLIC | EObjectFlags::RF_STANDALONE
+    );
+
+    assert!(EObjectFlags::LOAD_FLAGS.contains(EObjectFlags::ASSET_FLAGS));
+    assert!(!EObjectFlags::LOAD_FLAGS.contains(EObjectFlags::RF_WAS_LOADED));
+}
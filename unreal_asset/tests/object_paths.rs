@@ -0,0 +1,55 @@
+use std::io::Cursor;
+
+use unreal_asset::{engine_version::EngineVersion, Asset, Error};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn object_paths() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let import = asset
+        .imports
+        .iter()
+        .find(|import| import.object_name == "BlueprintGeneratedClass")
+        .expect("Failed to find BlueprintGeneratedClass import");
+    assert_eq!(
+        import.resolved_path(&asset),
+        "/Script/Engine.BlueprintGeneratedClass"
+    );
+
+    let class_index = asset
+        .find_export_by_object_path(
+            "/Game/Components_Small/Augment_BroadBrush.Augment_BroadBrush_C",
+        )
+        .expect("Failed to find class export");
+    assert_eq!(
+        asset.get_export_object_path(class_index).as_deref(),
+        Some("Augment_BroadBrush_C")
+    );
+
+    assert!(asset
+        .find_export_by_object_path(
+            "/Game/Components_Small/Augment_BroadBrush.Default__Augment_BroadBrush_C"
+        )
+        .is_some());
+    assert!(asset
+        .find_export_by_object_path("/Game/Components_Small/Other.Augment_BroadBrush_C")
+        .is_none());
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    cast,
+    engine_version::EngineVersion,
+    exports::{Export, ExportBaseTrait, NormalExport},
+    object_redirector::create_object_redirector,
+    Asset, Error,
+};
+
+#[test]
+fn object_redirector() -> Result<(), Error> {
+    let asset = create_object_redirector(
+        EngineVersion::VER_UE4_23,
+        true,
+        "/Game/Old/Asset",
+        "/Game/New/Renamed.Renamed",
+        "/Script/Engine.Blueprint",
+    )?;
+
+    let mut uasset = Cursor::new(Vec::new());
+    let mut uexp = Cursor::new(Vec::new());
+    asset.write_data(&mut uasset, Some(&mut uexp))?;
+
+    let parsed = Asset::new(
+        Cursor::new(uasset.into_inner()),
+        Some(Cursor::new(uexp.into_inner())),
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    assert_eq!(parsed.imports.len(), 5);
+    assert_eq!(parsed.asset_data.exports.len(), 1);
+
+    let export = &parsed.asset_data.exports[0];
+    assert_eq!(
+        export.get_base_export().object_name.get_owned_content(),
+        "Asset"
+    );
+
+    let target = parsed
+        .get_import(
+            export
+                .get_base_export()
+                .create_before_serialization_dependencies[0],
+        )
+        .expect("Failed to find redirector target");
+    assert_eq!(target.object_name.get_owned_content(), "Renamed");
+
+    let normal_export: &NormalExport<_> =
+        cast!(Export, NormalExport, export).expect("Redirector is not a NormalExport");
+    assert!(normal_export.properties.is_empty());
+    assert_eq!(normal_export.extras[4..], (-5i32).to_le_bytes());
+
+    // the package guid is derived from the redirector path
+    assert!(!parsed.package_guid.is_zero());
+    assert_eq!(parsed.package_guid, asset.package_guid);
+    let other = create_object_redirector(
+        EngineVersion::VER_UE4_23,
+        true,
+        "/Game/Old/Other",
+        "/Game/New/Renamed.Renamed",
+        "/Script/Engine.Blueprint",
+    )?;
+    assert_ne!(other.package_guid, asset.package_guid);
+
+    Ok(())
+}
+
+#[test]
+fn new_empty_package_guid() {
+    let mut asset = Asset::new_empty(EngineVersion::VER_UE4_23, true, None);
+    assert!(!asset.package_guid.is_zero());
+    assert_ne!(
+        asset.package_guid,
+        Asset::new_empty(EngineVersion::VER_UE4_23, true, None).package_guid
+    );
+
+    asset.generate_package_guid("/Game/Maps/Level");
+    let package_guid = asset.package_guid;
+    asset.generate_package_guid("/Game/Maps/Level");
+    assert_eq!(asset.package_guid, package_guid);
+}
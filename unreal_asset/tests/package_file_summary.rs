@@ -0,0 +1,234 @@
+use std::io::{Cursor, Write};
+
+use byteorder::{WriteBytesExt, LE};
+use flate2::{write::ZlibEncoder, Compression};
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    flags::EPackageFlags,
+    package_file_summary::{FCompressedChunk, FPackageFileSummary},
+    reader::GameQuirks,
+    types::GenerationInfo,
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/BloodStained/PB_DT_RandomizerRoomCheck"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn package_file_summary() -> Result<(), Error> {
+    let summary = FPackageFileSummary::from_reader(
+        Cursor::new(TEST_ASSET),
+        EngineVersion::VER_UE4_18,
+        GameQuirks::NONE,
+    )?;
+
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    assert_eq!(summary.package_guid, asset.package_guid);
+    assert_eq!(summary.folder_name, asset.folder_name);
+    assert_eq!(
+        summary.package_flags,
+        asset.asset_data.summary.package_flags
+    );
+    assert_eq!(
+        summary.export_count as usize,
+        asset.asset_data.exports.len()
+    );
+
+    // the summary is written exactly as it was read
+    let data = summary.to_bytes()?;
+    assert_eq!(data, TEST_ASSET[..data.len()]);
+
+    Ok(())
+}
+
+#[test]
+fn patch_package_file_summary() -> Result<(), Error> {
+    let mut summary = FPackageFileSummary::from_reader(
+        Cursor::new(TEST_ASSET),
+        EngineVersion::VER_UE4_18,
+        GameQuirks::NONE,
+    )?;
+    let header_size = summary.to_bytes()?.len();
+
+    summary.package_flags.insert(EPackageFlags::PKG_DEVELOPER);
+    let data = summary.to_bytes()?;
+    assert_eq!(data.len(), header_size);
+
+    let mut patched = TEST_ASSET.to_vec();
+    patched[..header_size].copy_from_slice(&data);
+
+    let asset = Asset::new(
+        Cursor::new(patched.as_slice()),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    assert!(asset
+        .asset_data
+        .summary
+        .package_flags
+        .contains(EPackageFlags::PKG_DEVELOPER));
+
+    Ok(())
+}
+
+#[test]
+fn patch_header_in_place() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+
+    let mut file = Cursor::new(Vec::new());
+    asset.write_data(&mut file, None)?;
+
+    asset
+        .asset_data
+        .summary
+        .package_flags
+        .insert(EPackageFlags::PKG_DEVELOPER);
+    asset.generate_package_guid("/Game/Patched");
+    asset.patch_header_in_place(&mut file)?;
+
+    // patching the header must give the same result as writing the whole asset
+    let mut written = Cursor::new(Vec::new());
+    asset.write_data(&mut written, None)?;
+    assert_eq!(file.into_inner(), written.into_inner());
+
+    Ok(())
+}
+
+#[test]
+fn patch_header_in_place_layout_changed() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+
+    let mut file = Cursor::new(Vec::new());
+    asset.write_data(&mut file, None)?;
+    let original = file.get_ref().clone();
+
+    // a new name needs the name map to be rewritten
+    asset.add_fname("PatchedName");
+    assert!(asset.patch_header_in_place(&mut file).is_err());
+
+    // the folder name changes the size of the header
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    asset.folder_name.push_str("Longer");
+    assert!(asset.patch_header_in_place(&mut file).is_err());
+
+    assert_eq!(file.into_inner(), original);
+
+    Ok(())
+}
+
+#[test]
+fn compressed_package() -> Result<(), Error> {
+    const BLOCK_SIZE: usize = 1024;
+
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    let mut expected = Cursor::new(Vec::new());
+    asset.write_data(&mut expected, None)?;
+
+    // two more generations make room for the compressed chunk in the summary
+    let generation_count = asset.generations.len();
+    asset.generations.push(GenerationInfo::new(0, 0));
+    asset.generations.push(GenerationInfo::new(0, 0));
+    let mut uncompressed = Cursor::new(Vec::new());
+    asset.write_data(&mut uncompressed, None)?;
+    let uncompressed = uncompressed.into_inner();
+
+    let mut summary = FPackageFileSummary::from_reader(
+        Cursor::new(uncompressed.as_slice()),
+        EngineVersion::VER_UE4_18,
+        GameQuirks::NONE,
+    )?;
+    let header_size = summary.to_bytes()?.len();
+    let body = &uncompressed[header_size..];
+
+    let mut blocks = Vec::new();
+    for block in body.chunks(BLOCK_SIZE) {
+        let mut encoder = ZlibEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(block)?;
+        blocks.push((encoder.finish()?, block.len()));
+    }
+
+    let mut chunk = Vec::new();
+    chunk.write_i64::<LE>(0x9E2A83C1)?;
+    chunk.write_i64::<LE>(BLOCK_SIZE as i64)?;
+    chunk.write_i64::<LE>(blocks.iter().map(|(e, _)| e.len() as i64).sum())?;
+    chunk.write_i64::<LE>(body.len() as i64)?;
+    for (compressed, uncompressed_size) in &blocks {
+        chunk.write_i64::<LE>(compressed.len() as i64)?;
+        chunk.write_i64::<LE>(*uncompressed_size as i64)?;
+    }
+    for (compressed, _) in &blocks {
+        chunk.extend_from_slice(compressed);
+    }
+
+    summary.generations.truncate(generation_count);
+    summary.compression_flags = 0x01;
+    summary.compressed_chunks = vec![FCompressedChunk {
+        uncompressed_offset: header_size as i32,
+        uncompressed_size: body.len() as i32,
+        compressed_offset: header_size as i32,
+        compressed_size: chunk.len() as i32,
+    }];
+    let mut compressed = summary.to_bytes()?;
+    assert_eq!(compressed.len(), header_size);
+    compressed.extend_from_slice(&chunk);
+
+    // the compressed package is read like the uncompressed one and written uncompressed
+    let asset = Asset::new(
+        Cursor::new(compressed.as_slice()),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )?;
+    let mut written = Cursor::new(Vec::new());
+    asset.write_data(&mut written, None)?;
+    assert_eq!(written.into_inner(), expected.into_inner());
+
+    // unknown compression methods are rejected
+    summary.compression_flags = 0x04;
+    let mut compressed = summary.to_bytes()?;
+    compressed.extend_from_slice(&chunk);
+    assert!(Asset::new(
+        Cursor::new(compressed.as_slice()),
+        None,
+        EngineVersion::VER_UE4_18,
+        None,
+    )
+    .is_err());
+
+    Ok(())
+}
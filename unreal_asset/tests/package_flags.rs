@@ -0,0 +1,27 @@
+use unreal_asset::flags::EPackageFlags;
+
+#[test]
+fn package_flag_helpers() {
+    let mut flags = EPackageFlags::PKG_NONE;
+    assert!(!flags.is_cooked());
+    assert!(flags.has_editor_only_data());
+
+    // unversioned properties are only used by cooked packages
+    flags.set_unversioned_properties(true);
+    assert!(flags.uses_unversioned_properties());
+    assert!(flags.is_cooked());
+    assert!(flags.is_filter_editor_only());
+
+    flags.set_cooked(false);
+    assert!(!flags.is_cooked());
+    assert!(!flags.uses_unversioned_properties());
+    assert!(flags.is_filter_editor_only());
+
+    flags.set_cooked(true);
+    assert_eq!(flags, EPackageFlags::COOKED_FLAGS);
+
+    // packages with editor-only data are never cooked
+    flags.set_filter_editor_only(false);
+    assert!(flags.has_editor_only_data());
+    assert_eq!(flags, EPackageFlags::PKG_NONE);
+}
@@ -0,0 +1,66 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    types::{PackageIndex, PackedObjectRef},
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn packed_object_ref_layout() -> Result<(), Error> {
+    let packed = PackedObjectRef::pack(0x7FFF_FFFF, 0x00FF_FFFF, 0xAB)?;
+    assert!(packed.is_packed());
+    assert_eq!(packed.package_id(), 0x7FFF_FFFF);
+    assert_eq!(packed.object_path_id(), 0x00FF_FFFF);
+    assert_eq!(packed.data_class_descriptor_id(), 0xAB);
+
+    assert_eq!(
+        PackedObjectRef::pack(3, 2, 0)?.encoded,
+        (3 << 33) | (2 << 1) | 1
+    );
+    assert!(PackedObjectRef::pack(0x8000_0000, 0, 0).is_err());
+    assert!(PackedObjectRef::pack(0, 0x0100_0000, 0).is_err());
+    assert!(PackedObjectRef::NULL.is_null());
+
+    Ok(())
+}
+
+#[test]
+fn packed_object_ref_translation() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let imports = (1..=asset.imports.len() as i32).map(|e| PackageIndex::new(-e));
+    let exports = (1..=asset.asset_data.exports.len() as i32).map(PackageIndex::new);
+    for index in imports.chain(exports) {
+        let packed = asset.pack_object_ref(index)?;
+        assert!(packed.is_packed());
+        assert_eq!(asset.unpack_object_ref(packed)?, index);
+    }
+
+    assert!(asset.pack_object_ref(PackageIndex::new(0))?.is_null());
+    assert_eq!(
+        asset.unpack_object_ref(PackedObjectRef::NULL)?,
+        PackageIndex::new(0)
+    );
+    assert!(asset
+        .unpack_object_ref(PackedObjectRef::pack(0x7FFF_FFFF, 1, 0)?)
+        .is_err());
+
+    Ok(())
+}
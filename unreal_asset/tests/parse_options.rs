@@ -0,0 +1,233 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    error::PropertyError,
+    exports::ExportBaseTrait,
+    reader::{GameQuirks, ParseOptions, StructFallbackBehavior},
+    Asset, Error,
+};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn hardened_parse_options() -> Result<(), Error> {
+    let mut asset = Asset::new_with_options(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        ParseOptions::hardened(),
+    )?;
+
+    shared::verify_binary_equality(TEST_ASSET, None, &mut asset)?;
+
+    Ok(())
+}
+
+#[test]
+fn exceeded_parse_options() {
+    let parse_options = ParseOptions {
+        max_name_count: 1,
+        ..ParseOptions::hardened()
+    };
+
+    let asset = Asset::new_with_options(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        parse_options,
+    );
+
+    assert!(matches!(asset, Err(Error::LimitExceeded(..))));
+}
+
+#[test]
+fn negative_export_size() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    let base_export = asset.asset_data.exports[0].get_base_export();
+
+    // serial size and offset are stored next to each other in the export map
+    let mut entry = base_export.serial_size.to_le_bytes().to_vec();
+    entry.extend(base_export.serial_offset.to_le_bytes());
+    let position = TEST_ASSET
+        .windows(entry.len())
+        .position(|e| e == entry)
+        .expect("export map entry not found");
+
+    let mut data = TEST_ASSET.to_vec();
+    data[position..position + 8].copy_from_slice(&(-1i64).to_le_bytes());
+
+    let asset = Asset::new(Cursor::new(data), None, EngineVersion::VER_UE4_23, None);
+    assert!(matches!(asset, Err(Error::InvalidFile(..))));
+
+    Ok(())
+}
+
+#[test]
+fn exceeded_property_depth() {
+    let parse_options = ParseOptions {
+        max_property_depth: 0,
+        ..ParseOptions::hardened()
+    };
+
+    let asset = Asset::new_with_options(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        parse_options,
+    );
+
+    assert!(matches!(
+        asset,
+        Err(Error::Property(PropertyError::DepthLimitExceeded(..)))
+    ));
+}
+
+#[test]
+fn exceeded_nested_property_depth() -> Result<(), Error> {
+    // Recipe.Ingredients is an array of ItemRecipeIngredient structs, their members are nested 3 levels deep
+    let read_with_depth = |max_property_depth| {
+        Asset::new_with_options(
+            Cursor::new(TEST_ASSET),
+            None,
+            EngineVersion::VER_UE4_23,
+            None,
+            ParseOptions {
+                max_property_depth,
+                ..ParseOptions::hardened()
+            },
+        )
+    };
+
+    match read_with_depth(3) {
+        Err(Error::Property(PropertyError::DepthLimitExceeded(name, ancestry, 3))) => {
+            assert_eq!(&*name, "ItemType");
+            assert!(ancestry.ends_with("/Ingredients/ItemRecipeIngredient"));
+        }
+        result => panic!(
+            "expected the depth limit to be exceeded, got {:?}",
+            result.err()
+        ),
+    }
+
+    read_with_depth(4)?;
+
+    Ok(())
+}
+
+#[test]
+fn game_quirks() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let quirks = GameQuirks::NO_NAME_HASHES
+        | GameQuirks::NO_PROPERTY_GUIDS
+        | GameQuirks::SUMMARY_LICENSEE_FIELD;
+    asset.asset_data.quirks = quirks;
+    asset.asset_data.summary.licensee_field = Some(7);
+
+    let mut cursor = Cursor::new(Vec::new());
+    asset.write_data(&mut cursor, None)?;
+    let quirky = cursor.into_inner();
+    assert_ne!(quirky.as_slice(), TEST_ASSET);
+
+    let parse_options = ParseOptions {
+        quirks,
+        ..Default::default()
+    };
+    let mut quirky_asset = Asset::new_with_options(
+        Cursor::new(quirky.as_slice()),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+        parse_options,
+    )?;
+    assert_eq!(quirky_asset.asset_data.summary.licensee_field, Some(7));
+    assert!(shared::verify_all_exports_parsed(&quirky_asset));
+    shared::verify_binary_equality(&quirky, None, &mut quirky_asset)?;
+
+    // without quirks the asset is written like the stock engine does
+    quirky_asset.asset_data.quirks = GameQuirks::NONE;
+    quirky_asset.asset_data.summary.licensee_field = None;
+    let mut cursor = Cursor::new(Vec::new());
+    quirky_asset.write_data(&mut cursor, None)?;
+    assert_eq!(cursor.into_inner(), TEST_ASSET);
+
+    Ok(())
+}
+
+#[test]
+fn struct_fallback() -> Result<(), Error> {
+    const ASSET_FILE: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/assets/general/Misc_426/RaceSimDataAsset.uasset"
+    ));
+    const ASSET_BULK_FILE: &[u8] = include_bytes!(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/assets/general/Misc_426/RaceSimDataAsset.uexp"
+    ));
+
+    // guessing reads the structs of unknown type as tagged properties
+    let asset = Asset::new(
+        Cursor::new(ASSET_FILE),
+        Some(Cursor::new(ASSET_BULK_FILE)),
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+    assert!(shared::verify_all_exports_parsed(&asset));
+
+    // the struct types of the RaceScalingOptions entries are neither serialized nor known
+    let asset = Asset::new_with_options(
+        Cursor::new(ASSET_FILE),
+        Some(Cursor::new(ASSET_BULK_FILE)),
+        EngineVersion::VER_UE4_26,
+        None,
+        ParseOptions {
+            struct_fallback: StructFallbackBehavior::Error,
+            ..Default::default()
+        },
+    );
+    assert!(matches!(
+        asset,
+        Err(Error::Property(PropertyError::UnknownStructType(..)))
+    ));
+
+    // without a known size the export is kept raw
+    let mut asset = Asset::new_with_options(
+        Cursor::new(ASSET_FILE),
+        Some(Cursor::new(ASSET_BULK_FILE)),
+        EngineVersion::VER_UE4_26,
+        None,
+        ParseOptions {
+            struct_fallback: StructFallbackBehavior::RawPreserve,
+            ..Default::default()
+        },
+    )?;
+    assert!(!shared::verify_all_exports_parsed(&asset));
+    shared::verify_binary_equality(ASSET_FILE, Some(ASSET_BULK_FILE), &mut asset)?;
+
+    Ok(())
+}
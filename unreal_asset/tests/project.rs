@@ -0,0 +1,57 @@
+use std::collections::HashMap;
+use std::io;
+
+use unreal_asset::{engine_version::EngineVersion, project::Project, Error};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+const PACKAGE_PATH: &str = "/Game/Components_Small/Augment_BroadBrush";
+const RENAMED_PACKAGE_PATH: &str = "/Game/Components_Renamed/Augment_Renamed";
+
+#[test]
+fn project() -> Result<(), Error> {
+    let source = HashMap::from([(format!("{PACKAGE_PATH}.uasset"), TEST_ASSET.to_vec())]);
+    let mut project = Project::new(source, EngineVersion::VER_UE4_23, None);
+
+    let loaded = project.load_with_dependencies(PACKAGE_PATH)?;
+    assert_eq!(loaded, vec![PACKAGE_PATH.to_string()]);
+    assert!(!project.is_dirty(PACKAGE_PATH));
+
+    let mut sink: HashMap<String, Vec<u8>> = HashMap::new();
+    project.save(&mut sink)?;
+    assert!(sink.is_empty());
+
+    project.rename(PACKAGE_PATH, RENAMED_PACKAGE_PATH)?;
+    assert!(!project.contains(PACKAGE_PATH));
+    assert_eq!(
+        project.get_dirty_package_paths(),
+        vec![RENAMED_PACKAGE_PATH]
+    );
+
+    project.save(&mut sink)?;
+    assert!(project.get_dirty_package_paths().is_empty());
+
+    let mut project = Project::new(sink, EngineVersion::VER_UE4_23, None);
+    let asset = project.load(RENAMED_PACKAGE_PATH)?;
+    assert!(asset
+        .search_name_reference("/Game/Components_Renamed/Augment_Renamed")
+        .is_some());
+    // the asset can be modified through the returned reference
+    assert!(project.is_dirty(RENAMED_PACKAGE_PATH));
+
+    assert!(matches!(
+        project.load(PACKAGE_PATH),
+        Err(Error::Io(e)) if e.kind() == io::ErrorKind::NotFound
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,286 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    containers::{Chain, NameMap},
+    custom_version::FAssetRegistryVersionType,
+    engine_version::{self, EngineVersion},
+    reader::{RawReader, RawWriter},
+    registry::{dependencies::DependencyType, AssetRegistryState},
+    types::PackageIndex,
+    Error,
+};
+
+/// Depends node of a test registry
+struct Node {
+    primary_asset_type: Option<&'static str>,
+    package_name: &'static str,
+    hard: &'static [i32],
+    soft: &'static [i32],
+    hard_manage: &'static [i32],
+}
+
+impl Node {
+    const fn package(
+        package_name: &'static str,
+        hard: &'static [i32],
+        soft: &'static [i32],
+    ) -> Self {
+        Node {
+            primary_asset_type: None,
+            package_name,
+            hard,
+            soft,
+            hard_manage: &[],
+        }
+    }
+}
+
+/// Nodes are referenced by their index
+const NODES: [Node; 5] = [
+    Node {
+        primary_asset_type: Some("Map"),
+        package_name: "/Game/Maps/Planet",
+        hard: &[],
+        soft: &[],
+        hard_manage: &[1],
+    },
+    Node::package("/Game/Maps/Planet", &[2], &[3]),
+    Node::package("/Game/Items/BP_Item", &[4], &[]),
+    Node::package("/Game/Textures/T_Sky", &[], &[]),
+    Node::package("/Script/Engine", &[], &[]),
+];
+
+/// Write an asset registry without assets with a name table, in the last format before dependency flags
+fn registry_data(nodes: &[Node]) -> Result<Vec<u8>, Error> {
+    let (object_version, object_version_ue5) =
+        engine_version::get_object_versions(EngineVersion::VER_UE4_25);
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = RawWriter::<PackageIndex, _>::new(
+        &mut cursor,
+        object_version,
+        object_version_ue5,
+        false,
+        NameMap::new(),
+    );
+    FAssetRegistryVersionType::AddedCookedMD5Hash.write(&mut writer)?;
+    let mut data = cursor.into_inner();
+
+    let mut names = Vec::new();
+    let mut name_index = |name: &'static str| match names.iter().position(|e| *e == name) {
+        Some(index) => index as i32,
+        None => {
+            names.push(name);
+            names.len() as i32 - 1
+        }
+    };
+    let write_i32 = |body: &mut Vec<u8>, value: i32| body.extend(value.to_le_bytes());
+
+    let mut body = Vec::new();
+    // assets
+    write_i32(&mut body, 0);
+
+    write_i32(&mut body, nodes.len() as i32);
+    for (index, node) in nodes.iter().enumerate() {
+        let referencers = nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| {
+                [e.hard, e.soft, e.hard_manage]
+                    .concat()
+                    .contains(&(index as i32))
+            })
+            .map(|(i, _)| i as i32)
+            .collect::<Vec<_>>();
+
+        body.push(match node.primary_asset_type {
+            Some(_) => 0b11,
+            None => 0b01,
+        });
+        write_i32(&mut body, name_index(node.package_name));
+        write_i32(&mut body, 0);
+        if let Some(primary_asset_type) = node.primary_asset_type {
+            write_i32(&mut body, name_index(primary_asset_type));
+            write_i32(&mut body, 0);
+        }
+
+        // hard, soft, name, soft manage, hard manage and referencer counts
+        for count in [
+            node.hard.len(),
+            node.soft.len(),
+            0,
+            0,
+            node.hard_manage.len(),
+        ] {
+            write_i32(&mut body, count as i32);
+        }
+        write_i32(&mut body, referencers.len() as i32);
+        for index in [node.hard, node.soft, node.hard_manage, &referencers].concat() {
+            write_i32(&mut body, index);
+        }
+    }
+
+    // package data
+    write_i32(&mut body, 0);
+
+    let name_offset = data.len() + 8 + body.len();
+    data.extend((name_offset as i64).to_le_bytes());
+    data.extend(body);
+
+    data.extend((names.len() as i32).to_le_bytes());
+    for name in names {
+        data.extend((name.len() as i32 + 1).to_le_bytes());
+        data.extend(name.as_bytes());
+        data.push(0);
+        // hashes
+        data.extend([0u8; 4]);
+    }
+
+    Ok(data)
+}
+
+fn read_registry(data: Vec<u8>) -> Result<AssetRegistryState, Error> {
+    let (object_version, object_version_ue5) =
+        engine_version::get_object_versions(EngineVersion::VER_UE4_25);
+    let mut reader = RawReader::new(
+        Chain::new(Cursor::new(data), None),
+        object_version,
+        object_version_ue5,
+        false,
+        NameMap::new(),
+    );
+    AssetRegistryState::new(&mut reader)
+}
+
+#[test]
+fn dependency_queries() -> Result<(), Error> {
+    let registry = read_registry(registry_data(&NODES)?)?;
+
+    let describe = |dependencies: Vec<unreal_asset::registry::dependencies::Dependency>| {
+        dependencies
+            .iter()
+            .map(|e| (e.identifier.to_string(), e.dependency_type))
+            .collect::<Vec<_>>()
+    };
+
+    // the primary asset id of the map is not the package node
+    let node = registry.find_package_node("/game/maps/PLANET").unwrap();
+    assert_eq!(node.get_index(), 1);
+    assert!(registry.find_package_node("/Game/Missing").is_none());
+    assert!(registry.get_depends_node(-1).is_none());
+    assert_eq!(
+        registry.get_depends_node(4).unwrap().identifier.to_string(),
+        "/Script/Engine"
+    );
+
+    assert_eq!(
+        describe(registry.dependencies_of("/Game/Maps/Planet", &DependencyType::PACKAGE)),
+        [
+            ("/Game/Items/BP_Item".to_string(), DependencyType::Hard),
+            ("/Game/Textures/T_Sky".to_string(), DependencyType::Soft),
+        ]
+    );
+    assert_eq!(
+        describe(registry.dependencies_of("/Game/Maps/Planet", &[DependencyType::Soft])),
+        [("/Game/Textures/T_Sky".to_string(), DependencyType::Soft)]
+    );
+    assert!(registry
+        .dependencies_of("/Game/Missing", &DependencyType::ALL)
+        .is_empty());
+
+    assert_eq!(
+        describe(registry.referencers_of("/Game/Maps/Planet", &DependencyType::ALL)),
+        [(
+            "Map:/Game/Maps/Planet".to_string(),
+            DependencyType::HardManage
+        )]
+    );
+    assert!(registry
+        .referencers_of("/Game/Maps/Planet", &DependencyType::PACKAGE)
+        .is_empty());
+    assert_eq!(
+        describe(registry.referencers_of("/Game/Items/BP_Item", &DependencyType::PACKAGE)),
+        [("/Game/Maps/Planet".to_string(), DependencyType::Hard)]
+    );
+    assert_eq!(
+        describe(registry.referencers_of("/script/engine", &DependencyType::ALL)),
+        [("/Game/Items/BP_Item".to_string(), DependencyType::Hard)]
+    );
+
+    Ok(())
+}
+
+fn write_registry(registry: &AssetRegistryState) -> Result<Vec<u8>, Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    registry.write(&mut cursor)?;
+    Ok(cursor.into_inner())
+}
+
+#[test]
+fn asset_round_trip() -> Result<(), Error> {
+    let mut registry = read_registry(registry_data(&NODES)?)?;
+
+    let mut item = registry.create_asset_data(
+        "/Game/Mods/MyMod/BP_Item.BP_Item_C",
+        "/Script/Engine.BlueprintGeneratedClass",
+    );
+    let tag = registry.create_fname("ItemType");
+    item.set_tag(tag, Some("Weapon".to_string()));
+    // tag names are compared case-insensitively
+    let tag = registry.create_fname("itemtype");
+    item.set_tag(tag, Some("Tool".to_string()));
+    assert_eq!(item.tags_and_values.len(), 1);
+    registry.add_asset(item);
+
+    let table = registry.create_asset_data("/Game/Mods/MyMod/DT_Items", "/Script/Engine.DataTable");
+    registry.add_asset(table);
+    // adding an asset with the same object path replaces it
+    let mut table =
+        registry.create_asset_data("/Game/Mods/MyMod/DT_Items", "/Script/Engine.DataTable");
+    let tag = registry.create_fname("RowStructure");
+    table.set_tag(tag, Some("ItemRow".to_string()));
+    registry.add_asset(table);
+    assert_eq!(registry.assets_data.len(), 2);
+
+    let mut registry = read_registry(write_registry(&registry)?)?;
+    assert_eq!(registry.assets_data.len(), 2);
+    assert_eq!(registry.depends_nodes.len(), NODES.len());
+
+    let item = registry
+        .find_asset("/game/mods/mymod/bp_item.bp_item_c")
+        .unwrap();
+    assert_eq!(
+        item.package_name.get_owned_content(),
+        "/Game/Mods/MyMod/BP_Item"
+    );
+    assert_eq!(item.package_path.get_owned_content(), "/Game/Mods/MyMod");
+    assert_eq!(item.asset_name.get_owned_content(), "BP_Item_C");
+    assert_eq!(
+        item.asset_class.as_ref().map(|e| e.get_owned_content()),
+        Some("BlueprintGeneratedClass".to_string())
+    );
+    assert_eq!(item.get_tag("ITEMTYPE"), Some("Tool"));
+
+    let table = registry.find_asset("/Game/Mods/MyMod/DT_Items").unwrap();
+    assert_eq!(table.asset_name.get_owned_content(), "DT_Items");
+    assert_eq!(table.get_tag("RowStructure"), Some("ItemRow"));
+
+    assert!(registry.remove_asset("/Game/Mods/MyMod/DT_Items").is_some());
+    assert!(registry.remove_asset("/Game/Mods/MyMod/DT_Items").is_none());
+    registry
+        .find_asset_mut("/Game/Mods/MyMod/BP_Item.BP_Item_C")
+        .unwrap()
+        .remove_tag("ItemType");
+
+    let registry = read_registry(write_registry(&registry)?)?;
+    assert_eq!(registry.assets_data.len(), 1);
+    assert!(registry.assets_data[0].tags_and_values.is_empty());
+    assert_eq!(
+        registry
+            .dependencies_of("/Game/Maps/Planet", &DependencyType::PACKAGE)
+            .len(),
+        2
+    );
+
+    Ok(())
+}
@@ -0,0 +1,76 @@
+use std::io::Cursor;
+
+use unreal_asset::{engine_version::EngineVersion, exports::ExportBaseTrait, Asset, Error};
+
+mod shared;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+#[test]
+fn rename_package() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    asset.rename_package(
+        "/Game/Components_Small/Augment_BroadBrush",
+        "/Game/Components_Renamed/Augment_Renamed",
+    );
+
+    assert!(asset
+        .search_name_reference("/Game/Components_Renamed/Augment_Renamed")
+        .is_some());
+    assert!(asset
+        .search_name_reference("/Game/Components_Small/Augment_BroadBrush")
+        .is_none());
+    assert!(asset
+        .search_name_reference("Augment_BroadBrush_C")
+        .is_none());
+
+    let export_names = asset
+        .asset_data
+        .exports
+        .iter()
+        .map(|e| e.get_base_export().object_name.get_owned_content())
+        .collect::<Vec<_>>();
+    assert!(export_names.contains(&"Augment_Renamed_C".to_string()));
+    assert!(export_names.contains(&"Default__Augment_Renamed_C".to_string()));
+
+    shared::verify_reparse(&mut asset, EngineVersion::VER_UE4_23)?;
+
+    Ok(())
+}
+
+#[test]
+fn fix_package_references() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    assert!(asset.fix_package_references("/Game/Globals/StringTables", "/Game/Strings"));
+    assert!(asset.search_name_reference("/Game/Strings/Items").is_some());
+    assert!(asset
+        .search_name_reference("/Game/Strings/Items.Items")
+        .is_some());
+
+    assert!(!asset.fix_package_references("/Game/DoesNotExist", "/Game/Other"));
+
+    shared::verify_reparse(&mut asset, EngineVersion::VER_UE4_23)?;
+
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use std::io::{Cursor, Read, Seek};
+
+use unreal_asset::{engine_version::EngineVersion, exports::ExportNormalTrait, Asset, Error};
+
+macro_rules! assets_folder {
+    () => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/assets/general/")
+    };
+}
+
+const BROAD_BRUSH: &[u8] = include_bytes!(concat!(
+    assets_folder!(),
+    "Astroneer_prebulk/Augment_BroadBrush.uasset"
+));
+
+const RACE_SIM: &[u8] = include_bytes!(concat!(
+    assets_folder!(),
+    "Misc_426/RaceSimDataAsset.uasset"
+));
+const RACE_SIM_BULK: &[u8] =
+    include_bytes!(concat!(assets_folder!(), "Misc_426/RaceSimDataAsset.uexp"));
+
+fn write_asset<C: Read + Seek>(asset: &Asset<C>) -> Result<(Vec<u8>, Option<Vec<u8>>), Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut bulk_cursor = match asset.use_separate_bulk_data_files() {
+        true => Some(Cursor::new(Vec::new())),
+        false => None,
+    };
+    asset.write_data(&mut cursor, bulk_cursor.as_mut())?;
+
+    Ok((cursor.into_inner(), bulk_cursor.map(|e| e.into_inner())))
+}
+
+#[test]
+fn rewrite_streaming_copy() -> Result<(), Error> {
+    for (data, bulk, engine_version) in [
+        (BROAD_BRUSH, None, EngineVersion::VER_UE4_23),
+        (RACE_SIM, Some(RACE_SIM_BULK), EngineVersion::VER_UE4_26),
+    ] {
+        let asset = Asset::new(
+            Cursor::new(data),
+            bulk.map(Cursor::new),
+            engine_version,
+            None,
+        )?;
+        let (expected, expected_bulk) = write_asset(&asset)?;
+
+        let mut output = Cursor::new(Vec::new());
+        let mut output_bulk = bulk.map(|_| Cursor::new(Vec::new()));
+        let mut visited = Vec::new();
+        Asset::rewrite_streaming(
+            Cursor::new(data),
+            bulk.map(Cursor::new),
+            engine_version,
+            &mut output,
+            output_bulk.as_mut(),
+            |export| {
+                visited.push(export.index);
+                match export.index.index % 2 == 0 {
+                    true => Ok(Some(export.read_data()?)),
+                    false => Ok(None),
+                }
+            },
+        )?;
+
+        assert_eq!(visited.len(), asset.asset_data.exports.len());
+        assert_eq!(output.into_inner(), expected);
+        assert_eq!(output_bulk.map(|e| e.into_inner()), expected_bulk);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn rewrite_streaming_replace() -> Result<(), Error> {
+    const APPENDED: [u8; 16] = [0xab; 16];
+
+    let mut output = Cursor::new(Vec::new());
+    Asset::rewrite_streaming(
+        Cursor::new(BROAD_BRUSH),
+        None,
+        EngineVersion::VER_UE4_23,
+        &mut output,
+        None::<&mut Cursor<Vec<u8>>>,
+        |export| match export.index.index == 1 {
+            true => {
+                let mut data = export.read_data()?;
+                assert_eq!(data.len() as u64, export.data_len());
+                data.extend_from_slice(&APPENDED);
+                Ok(Some(data))
+            }
+            false => Ok(None),
+        },
+    )?;
+
+    let original = Asset::new(
+        Cursor::new(BROAD_BRUSH),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    let rewritten = Asset::new(
+        Cursor::new(output.into_inner()),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    assert_eq!(
+        rewritten.asset_data.exports.len(),
+        original.asset_data.exports.len()
+    );
+
+    for (i, (rewritten, original)) in rewritten
+        .asset_data
+        .exports
+        .iter()
+        .zip(&original.asset_data.exports)
+        .enumerate()
+    {
+        let (Some(rewritten), Some(original)) =
+            (rewritten.get_normal_export(), original.get_normal_export())
+        else {
+            continue;
+        };
+        assert_eq!(rewritten.properties, original.properties);
+
+        let mut extras = original.extras.clone();
+        if i == 0 {
+            extras.extend_from_slice(&APPENDED);
+        }
+        assert_eq!(rewritten.extras, extras);
+        assert_eq!(
+            rewritten.base_export.serial_size,
+            original.base_export.serial_size + if i == 0 { 16 } else { 0 },
+            "Export {} has the wrong size",
+            i + 1
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,198 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    exports::ExportNormalTrait,
+    properties::{int_property::Int8Property, Property},
+    Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Misc_426/RaceSimDataAsset"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+const TEST_BULK: &[u8] = include_bytes!(concat!(test_asset!(), ".uexp"));
+
+fn write_asset<C: std::io::Read + std::io::Seek>(
+    asset: &Asset<C>,
+) -> Result<(Vec<u8>, Option<Vec<u8>>), Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    let mut bulk_cursor = match asset.use_separate_bulk_data_files() {
+        true => Some(Cursor::new(Vec::new())),
+        false => None,
+    };
+    asset.write_data(&mut cursor, bulk_cursor.as_mut())?;
+
+    Ok((cursor.into_inner(), bulk_cursor.map(|e| e.into_inner())))
+}
+
+#[test]
+fn separate_bulk_data_conversion() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        Some(Cursor::new(TEST_BULK)),
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+    assert!(asset.use_separate_bulk_data_files());
+    let (_, split_bulk) = write_asset(&asset)?;
+
+    asset.set_use_separate_bulk_data_files(false);
+    let (combined_data, combined_bulk) = write_asset(&asset)?;
+    assert!(combined_bulk.is_none());
+
+    let mut combined = Asset::new(
+        Cursor::new(combined_data),
+        None,
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+    assert!(!combined.use_separate_bulk_data_files());
+    assert_eq!(
+        combined.asset_data.exports.len(),
+        asset.asset_data.exports.len()
+    );
+
+    combined.set_use_separate_bulk_data_files(true);
+    let (resplit_data, resplit_bulk) = write_asset(&combined)?;
+    assert_eq!(
+        resplit_bulk.as_ref().map(|e| e.len()),
+        split_bulk.as_ref().map(|e| e.len())
+    );
+
+    let resplit = Asset::new(
+        Cursor::new(resplit_data),
+        resplit_bulk.map(Cursor::new),
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+    assert_eq!(
+        resplit.asset_data.exports.len(),
+        asset.asset_data.exports.len()
+    );
+
+    Ok(())
+}
+
+#[test]
+fn split_writer_types() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        Some(Cursor::new(TEST_BULK)),
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut bulk = Vec::new();
+    asset.write_data_split(&mut cursor, Some(&mut Cursor::new(&mut bulk)))?;
+
+    assert_eq!(cursor.into_inner(), TEST_ASSET);
+    assert_eq!(bulk, TEST_BULK);
+
+    Ok(())
+}
+
+#[test]
+fn save_to_files() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        Some(Cursor::new(TEST_BULK)),
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+
+    let directory =
+        std::env::temp_dir().join(format!("unreal_asset_save_test_{}", std::process::id()));
+    std::fs::create_dir_all(&directory)?;
+
+    asset.save_to_files(directory.join("RaceSimDataAsset.uasset"))?;
+    assert_eq!(
+        std::fs::read(directory.join("RaceSimDataAsset.uasset"))?,
+        TEST_ASSET
+    );
+    assert_eq!(
+        std::fs::read(directory.join("RaceSimDataAsset.uexp"))?,
+        TEST_BULK
+    );
+    assert_eq!(std::fs::read_dir(&directory)?.count(), 2);
+
+    std::fs::remove_dir_all(&directory)?;
+
+    Ok(())
+}
+
+#[test]
+fn write_uexp_in_place() -> Result<(), Error> {
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        Some(Cursor::new(TEST_BULK)),
+        EngineVersion::VER_UE4_26,
+        None,
+    )?;
+
+    // changing export data only changes the serial sizes and offsets in the header
+    asset
+        .asset_data
+        .exports
+        .iter_mut()
+        .find_map(|e| e.get_normal_export_mut())
+        .expect("Normal export was found")
+        .extras
+        .extend_from_slice(&[0; 8]);
+    let (expected_data, expected_bulk) = write_asset(&asset)?;
+
+    let mut uasset_file = Cursor::new(TEST_ASSET.to_vec());
+    let mut uexp_file = Cursor::new(Vec::new());
+    asset.write_uexp_in_place(&mut uasset_file, &mut uexp_file)?;
+    assert_eq!(uasset_file.into_inner(), expected_data);
+    assert_eq!(Some(uexp_file.into_inner()), expected_bulk);
+
+    // the type of a new property is only added to the name map while writing
+    let name_count = asset
+        .get_name_map()
+        .get_ref()
+        .get_name_map_index_list()
+        .len();
+    let normal_export = asset
+        .asset_data
+        .exports
+        .iter_mut()
+        .find_map(|e| e.get_normal_export_mut())
+        .expect("Normal export was found");
+    normal_export
+        .properties
+        .push(Property::Int8Property(Int8Property {
+            name: normal_export.base_export.object_name.clone(),
+            ..Default::default()
+        }));
+    let mut uasset_file = Cursor::new(TEST_ASSET.to_vec());
+    assert!(asset
+        .write_uexp_in_place(&mut uasset_file, &mut Cursor::new(Vec::new()))
+        .is_err());
+    assert_eq!(uasset_file.into_inner(), TEST_ASSET);
+    assert_eq!(
+        asset
+            .get_name_map()
+            .get_ref()
+            .get_name_map_index_list()
+            .len(),
+        name_count
+    );
+
+    // a new name changes the header layout
+    asset.add_fname("NewName");
+    let mut uasset_file = Cursor::new(TEST_ASSET.to_vec());
+    assert!(asset
+        .write_uexp_in_place(&mut uasset_file, &mut Cursor::new(Vec::new()))
+        .is_err());
+    assert_eq!(uasset_file.into_inner(), TEST_ASSET);
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+use std::io::{Cursor, Seek, SeekFrom, Write};
+
+use unreal_asset::{
+    containers::{NameMap, SharedResource},
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    properties::{str_property::StrProperty, Property},
+    reader::{RawWriter, SizeCalculatingWriter},
+    types::PackageIndex,
+    unversioned::Ancestry,
+    Error,
+};
+
+fn write_property<W: Write + Seek>(
+    cursor: &mut W,
+    name_map: SharedResource<NameMap>,
+    property: &Property,
+) -> Result<usize, Error> {
+    let mut writer = RawWriter::<PackageIndex, _>::new(
+        cursor,
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+        false,
+        name_map,
+    );
+    Property::write(property, &mut writer, true)
+}
+
+#[test]
+fn size_calculating_writer() -> Result<(), Error> {
+    let mut name_map = NameMap::new();
+    let property = Property::StrProperty(StrProperty {
+        name: name_map.get_mut().add_fname("Value"),
+        ancestry: Ancestry::default(),
+        property_guid: None,
+        duplication_index: 0,
+        value: Some("Hello, world!".to_string()),
+    });
+
+    let mut cursor = Cursor::new(Vec::new());
+    write_property(&mut cursor, name_map.clone(), &property)?;
+
+    let mut size_writer = SizeCalculatingWriter::new();
+    write_property(&mut size_writer, name_map, &property)?;
+
+    // the property length is written after the property by seeking back
+    assert_eq!(size_writer.len(), cursor.get_ref().len() as u64);
+    assert_eq!(size_writer.position(), size_writer.len());
+
+    size_writer.seek(SeekFrom::Start(4))?;
+    size_writer.write_all(&[0; 2])?;
+    assert_eq!(size_writer.position(), 6);
+    assert_eq!(size_writer.len(), cursor.get_ref().len() as u64);
+    assert!(size_writer.seek(SeekFrom::Current(-7)).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    containers::NameMap,
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    properties::{
+        object_property::{SoftObjectPath, TopLevelAssetPath},
+        soft_path_property::SoftObjectPathPropertyValue,
+    },
+    reader::RawWriter,
+    types::{FName, PackageIndex},
+    Error,
+};
+
+#[test]
+fn soft_object_path_forms() {
+    // UE 5.1 form
+    let new = SoftObjectPath::from_parts(
+        FName::from_slice("/Game/Maps/Level"),
+        FName::from_slice("Level"),
+        Some("PersistentLevel.Actor".to_string()),
+    );
+
+    // pre UE 5.1 form
+    let old = SoftObjectPath {
+        asset_path: TopLevelAssetPath::new(None, FName::from_slice("/Game/Maps/Level.Level")),
+        sub_path_string: Some("PersistentLevel.Actor".to_string()),
+    };
+
+    for path in [&new, &old] {
+        assert_eq!(path.get_package_name(), "/Game/Maps/Level");
+        assert_eq!(path.get_asset_name(), "Level");
+        assert_eq!(path.get_sub_path(), Some("PersistentLevel.Actor"));
+        assert_eq!(
+            path.get_path(),
+            "/Game/Maps/Level.Level:PersistentLevel.Actor"
+        );
+        assert!(!path.is_null());
+    }
+
+    let null =
+        SoftObjectPath::from_parts(FName::from_slice("None"), FName::from_slice("None"), None);
+    assert!(null.is_null());
+    assert_eq!(null.get_sub_path(), None);
+    assert_eq!(SoftObjectPathPropertyValue::New(null).get_path(), None);
+}
+
+#[test]
+fn soft_object_path_version_form() -> Result<(), Error> {
+    let mut name_map = NameMap::new();
+    let old = SoftObjectPathPropertyValue::Old(Some(
+        "/Game/Maps/Level.Level:PersistentLevel.Actor".to_string(),
+    ));
+
+    // converting adds the names, writing leaves the name map untouched
+    for object_version_ue5 in [
+        ObjectVersionUE5::INITIAL_VERSION,
+        ObjectVersionUE5::FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES,
+    ] {
+        let mut cursor = Cursor::new(Vec::new());
+        let mut writer = RawWriter::<PackageIndex, _>::new(
+            &mut cursor,
+            ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+            object_version_ue5,
+            false,
+            name_map.clone(),
+        );
+        assert!(old.write(&mut writer).is_err());
+
+        let converted = old.to_version_form(
+            &mut name_map,
+            ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+            object_version_ue5,
+        );
+        assert_eq!(converted.get_path(), old.get_path());
+
+        let name_count = name_map.get_ref().get_name_map_index_list().len();
+        converted.write(&mut writer)?;
+        assert_eq!(
+            name_map.get_ref().get_name_map_index_list().len(),
+            name_count
+        );
+    }
+    assert_eq!(
+        name_map.get_ref().get_name_map_index_list(),
+        ["/Game/Maps/Level.Level", "/Game/Maps/Level", "Level"]
+    );
+
+    // paths are stored as strings before soft object paths were added
+    let new = old.to_version_form(
+        &mut name_map,
+        ObjectVersion::VER_UE4_AUTOMATIC_VERSION,
+        ObjectVersionUE5::UNKNOWN,
+    );
+    assert!(matches!(new, SoftObjectPathPropertyValue::New(_)));
+    assert_eq!(
+        new.to_version_form(
+            &mut name_map,
+            ObjectVersion::VER_UE4_ADDED_SWEEP_WHILE_WALKING_FLAG,
+            ObjectVersionUE5::UNKNOWN,
+        ),
+        old
+    );
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    asset::StripSections,
+    engine_version::EngineVersion,
+    exports::ExportNormalTrait,
+    types::fname::{FName, FNameContainer},
+    Asset, Error,
+};
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush.uasset"
+));
+
+/// Replace all names with their content, so they can be compared across name maps
+fn to_dummy_names(container: &mut impl FNameContainer) {
+    container.traverse_fnames(&mut |name| {
+        *name = FName::new_dummy(name.get_owned_content(), name.get_number());
+    });
+}
+
+fn write_asset(asset: &Asset<Cursor<&[u8]>>) -> Result<Vec<u8>, Error> {
+    let mut cursor = Cursor::new(Vec::new());
+    asset.write_data(&mut cursor, None::<&mut Cursor<Vec<u8>>>)?;
+    Ok(cursor.into_inner())
+}
+
+#[test]
+fn strip_unused_names() -> Result<(), Error> {
+    let original = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let mut asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    asset.add_name_reference("UnusedName".to_string(), false);
+    asset.strip(StripSections::all())?;
+    assert_eq!(asset.search_name_reference("UnusedName"), None);
+
+    let stripped = write_asset(&asset)?;
+    assert!(stripped.len() <= TEST_ASSET.len());
+
+    let parsed = Asset::new(
+        Cursor::new(stripped.as_slice()),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+    assert_eq!(parsed.search_name_reference("UnusedName"), None);
+    assert_eq!(
+        parsed.get_name_map().get_ref().get_name_map_index_list(),
+        asset.get_name_map().get_ref().get_name_map_index_list()
+    );
+
+    let mut parsed_names = (parsed.imports.clone(), parsed.asset_data.exports.clone());
+    let mut original_names = (original.imports, original.asset_data.exports);
+    to_dummy_names(&mut parsed_names);
+    to_dummy_names(&mut original_names);
+    assert_eq!(parsed_names.0, original_names.0);
+
+    for (parsed, original) in parsed_names.1.iter().zip(&original_names.1) {
+        assert_eq!(
+            parsed.get_normal_export().map(|e| &e.properties),
+            original.get_normal_export().map(|e| &e.properties)
+        );
+    }
+
+    // stripping a stripped asset doesn't change it
+    let mut parsed = parsed;
+    parsed.strip(StripSections::all())?;
+    let mut cursor = Cursor::new(Vec::new());
+    parsed.write_data(&mut cursor, None)?;
+    assert_eq!(cursor.into_inner(), stripped);
+
+    Ok(())
+}
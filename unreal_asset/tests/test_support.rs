@@ -0,0 +1,47 @@
+#![cfg(feature = "test-support")]
+
+use std::fs;
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    test_support::{self, Fixture},
+    Error,
+};
+
+const TEST_ASSET: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush.uasset"
+);
+
+#[test]
+fn fixture_round_trip() -> Result<(), Error> {
+    let fixture = Fixture::load(TEST_ASSET)?;
+    assert!(fixture.uexp.is_none());
+
+    let mut asset = fixture.parse(EngineVersion::VER_UE4_23, None)?;
+    fixture.assert_round_trip(&mut asset)?;
+    test_support::assert_all_exports_parsed(&asset);
+
+    Ok(())
+}
+
+#[test]
+fn property_snapshot() -> Result<(), Error> {
+    let fixture = Fixture::load(TEST_ASSET)?;
+    let asset = fixture.parse(EngineVersion::VER_UE4_23, None)?;
+
+    let snapshot = test_support::snapshot_properties(&asset);
+    assert!(snapshot.starts_with("Export 1 "));
+
+    let golden =
+        std::env::temp_dir().join(format!("unreal_asset_snapshot_{}.txt", std::process::id()));
+    let _ = fs::remove_file(&golden);
+
+    // the first run writes the golden file, the second compares against it
+    test_support::assert_snapshot(&snapshot, &golden)?;
+    test_support::assert_snapshot(&snapshot, &golden)?;
+    assert_eq!(fs::read_to_string(&golden)?, snapshot);
+
+    fs::remove_file(&golden)?;
+    Ok(())
+}
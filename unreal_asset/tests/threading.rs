@@ -0,0 +1,67 @@
+#![cfg(feature = "threading")]
+
+use std::io::Cursor;
+use std::thread;
+
+use unreal_asset::{
+    engine_version::EngineVersion, exports::ExportBaseTrait, types::PackageIndex, Asset, Error,
+};
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Astroneer_prebulk/Augment_BroadBrush"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn asset_is_send_sync() {
+    assert_send_sync::<Asset<Cursor<&[u8]>>>();
+    assert_send_sync::<Asset<Cursor<Vec<u8>>>>();
+}
+
+#[test]
+fn shared_asset_access() -> Result<(), Error> {
+    let asset = Asset::new(
+        Cursor::new(TEST_ASSET),
+        None,
+        EngineVersion::VER_UE4_23,
+        None,
+    )?;
+
+    let expected = asset
+        .asset_data
+        .exports
+        .iter()
+        .map(|e| e.get_base_export().object_name.get_owned_content())
+        .collect::<Vec<_>>();
+
+    let names = thread::scope(|scope| {
+        let handles = (0..asset.asset_data.exports.len())
+            .map(|i| {
+                let asset = &asset;
+                scope.spawn(move || {
+                    let export = asset
+                        .asset_data
+                        .get_export(PackageIndex::new(i as i32 + 1))
+                        .expect("Missing export");
+                    export.get_base_export().object_name.get_owned_content()
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .map(|e| e.join().expect("Thread panicked"))
+            .collect::<Vec<_>>()
+    });
+
+    assert_eq!(names, expected);
+    Ok(())
+}
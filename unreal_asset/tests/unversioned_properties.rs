@@ -0,0 +1,439 @@
+use std::io::Cursor;
+
+use unreal_asset::{
+    cast,
+    containers::IndexedMap,
+    convert::{convert_asset, convert_directory, PropertyFormat},
+    engine_version::EngineVersion,
+    exports::{BaseExport, Export, NormalExport},
+    flags::{EObjectFlags, EPackageFlags},
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    properties::{
+        int_property::IntProperty, validate_unversioned_header, Property, PropertyDataTrait,
+    },
+    types::{FName, PackageIndex},
+    unversioned::{
+        header::{UnversionedHeader, UnversionedHeaderFragment},
+        properties::{shallow_property::UsmapShallowPropertyData, EPropertyType, UsmapProperty},
+        Ancestry, EUsmapCompressionMethod, EUsmapVersion, Usmap, UsmapExtensionVersion,
+        UsmapSchema,
+    },
+    Asset, Error, Import,
+};
+
+const CLASS_NAME: &str = "TestClass";
+
+/// Create mappings for a class with `A: i32`, `B: i32` and `C: [i32; 2]`
+fn create_mappings() -> Usmap {
+    let mut properties = IndexedMap::new();
+    for (name, schema_index, array_index) in [("A", 0, 0), ("B", 1, 0), ("C", 2, 0), ("C", 3, 1)] {
+        properties.insert(
+            (name.to_string(), array_index as u32),
+            UsmapProperty {
+                name: name.to_string(),
+                schema_index,
+                array_size: if name == "C" { 2 } else { 1 },
+                array_index,
+                property_data: UsmapShallowPropertyData {
+                    property_type: EPropertyType::IntProperty,
+                }
+                .into(),
+            },
+        );
+    }
+
+    let mut schemas = IndexedMap::new();
+    schemas.insert(
+        CLASS_NAME.to_string(),
+        UsmapSchema {
+            name: CLASS_NAME.to_string(),
+            super_type: String::new(),
+            prop_count: 4,
+            module_path: None,
+            properties,
+        },
+    );
+
+    Usmap {
+        version: EUsmapVersion::Initial,
+        name_map: Vec::new(),
+        enum_map: IndexedMap::new(),
+        schemas,
+        extension_version: UsmapExtensionVersion::NONE,
+        object_version: ObjectVersion::UNKNOWN,
+        object_version_ue5: ObjectVersionUE5::UNKNOWN,
+        custom_versions: Vec::new(),
+        compression_method: EUsmapCompressionMethod::None,
+        net_cl: 0,
+    }
+}
+
+fn int_property(name: &str, duplication_index: i32, value: i32) -> Property {
+    Property::IntProperty(IntProperty {
+        name: FName::from_slice(name),
+        ancestry: Ancestry::new(FName::from_slice(CLASS_NAME)),
+        property_guid: None,
+        duplication_index,
+        value,
+    })
+}
+
+/// Create an asset with unversioned properties and a single export
+fn create_asset(properties: Vec<Property>) -> Asset<Cursor<Vec<u8>>> {
+    let mut asset = Asset::new_empty(EngineVersion::VER_UE4_27, true, Some(create_mappings()));
+    asset
+        .asset_data
+        .summary
+        .package_flags
+        .insert(EPackageFlags::PKG_UNVERSIONED_PROPERTIES);
+
+    let core_uobject = asset.add_fname("/Script/CoreUObject");
+    let package = asset.add_fname("Package");
+    let class = asset.add_fname("Class");
+    let class_name = asset.add_fname(CLASS_NAME);
+    let default_object = asset.add_fname("Default__TestClass");
+    let object_name = asset.add_fname("TestObject");
+
+    let core_uobject_import = asset.add_import(Import::new(
+        core_uobject.clone(),
+        package,
+        PackageIndex::new(0),
+        core_uobject.clone(),
+        false,
+    ));
+    let class_import = asset.add_import(Import::new(
+        core_uobject.clone(),
+        class,
+        core_uobject_import,
+        class_name.clone(),
+        false,
+    ));
+    let template_import = asset.add_import(Import::new(
+        core_uobject,
+        class_name,
+        core_uobject_import,
+        default_object,
+        false,
+    ));
+
+    asset
+        .asset_data
+        .exports
+        .push(Export::NormalExport(NormalExport {
+            base_export: BaseExport {
+                class_index: class_import,
+                template_index: template_import,
+                object_name,
+                object_flags: EObjectFlags::RF_PUBLIC,
+                ..Default::default()
+            },
+            extras: vec![0; 4],
+            padding_alignment: None,
+            properties,
+        }));
+
+    asset
+}
+
+fn round_trip(asset: &Asset<Cursor<Vec<u8>>>) -> Result<Asset<Cursor<Vec<u8>>>, Error> {
+    let mut uasset = Cursor::new(Vec::new());
+    let mut uexp = Cursor::new(Vec::new());
+    asset.write_data(&mut uasset, Some(&mut uexp))?;
+
+    Asset::new(
+        Cursor::new(uasset.into_inner()),
+        Some(Cursor::new(uexp.into_inner())),
+        EngineVersion::VER_UE4_27,
+        Some(create_mappings()),
+    )
+}
+
+fn get_properties(asset: &Asset<Cursor<Vec<u8>>>) -> &[Property] {
+    let normal_export: &NormalExport<_> = cast!(Export, NormalExport, &asset.asset_data.exports[0])
+        .expect("Export is not a NormalExport");
+    &normal_export.properties
+}
+
+#[test]
+fn zeroed_properties() -> Result<(), Error> {
+    let mut zeroed = int_property("B", 0, 0);
+    zeroed.set_zeroed();
+    assert!(zeroed.is_zeroed());
+
+    // out of mapping order on purpose, C[0] is skipped
+    let asset = create_asset(vec![
+        int_property("C", 1, 5),
+        int_property("A", 0, 1),
+        zeroed,
+    ]);
+    let parsed = round_trip(&asset)?;
+
+    let properties = get_properties(&parsed);
+    assert_eq!(properties.len(), 3);
+
+    let a = cast!(Property, IntProperty, &properties[0]).expect("A is not an IntProperty");
+    assert_eq!(a.name.get_owned_content(), "A");
+    assert_eq!(a.value, 1);
+
+    let b = cast!(Property, EmptyProperty, &properties[1]).expect("B is not zeroed");
+    assert_eq!(b.name.get_owned_content(), "B");
+    assert_eq!(b.type_name.get_owned_content(), "IntProperty");
+
+    let c = cast!(Property, IntProperty, &properties[2]).expect("C is not an IntProperty");
+    assert_eq!(c.name.get_owned_content(), "C");
+    assert_eq!(c.get_duplication_index(), 1);
+    assert_eq!(c.value, 5);
+
+    // writing the parsed asset again must produce the same properties
+    let reparsed = round_trip(&parsed)?;
+    assert_eq!(get_properties(&reparsed), properties);
+
+    Ok(())
+}
+
+#[test]
+fn all_properties_zeroed() -> Result<(), Error> {
+    let mut properties = vec![int_property("A", 0, 0), int_property("C", 0, 0)];
+    for property in properties.iter_mut() {
+        property.set_zeroed();
+    }
+
+    let parsed = round_trip(&create_asset(properties))?;
+    let properties = get_properties(&parsed);
+    assert_eq!(properties.len(), 2);
+    assert!(properties.iter().all(|e| e.is_zeroed()));
+    assert_eq!(properties[1].get_duplication_index(), 0);
+
+    Ok(())
+}
+
+#[test]
+fn zero_mask_defaults() -> Result<(), Error> {
+    let properties = vec![
+        int_property("A", 0, 0),
+        int_property("B", 0, 3),
+        int_property("C", 0, 0),
+    ];
+
+    // zero values are kept unless the asset zero-masks defaults
+    let parsed = round_trip(&create_asset(properties.clone()))?;
+    assert!(get_properties(&parsed).iter().all(|e| !e.is_zeroed()));
+
+    let mut asset = create_asset(properties);
+    asset.asset_data.zero_mask_defaults = true;
+    let parsed = round_trip(&asset)?;
+
+    let properties = get_properties(&parsed);
+    assert_eq!(properties.len(), 3);
+    assert!(properties[0].is_zeroed());
+    assert!(properties[2].is_zeroed());
+
+    let b = cast!(Property, IntProperty, &properties[1]).expect("B is not an IntProperty");
+    assert_eq!(b.value, 3);
+
+    Ok(())
+}
+
+#[test]
+fn no_properties() -> Result<(), Error> {
+    let parsed = round_trip(&create_asset(Vec::new()))?;
+    assert!(get_properties(&parsed).is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn duplicate_properties() {
+    let asset = create_asset(vec![int_property("A", 0, 1), int_property("A", 0, 2)]);
+
+    let mut uasset = Cursor::new(Vec::new());
+    let mut uexp = Cursor::new(Vec::new());
+    assert!(asset.write_data(&mut uasset, Some(&mut uexp)).is_err());
+}
+
+/// Check the properties written by `convert_property_format`
+fn check_converted_properties(asset: &Asset<Cursor<Vec<u8>>>) {
+    let properties = get_properties(asset);
+    assert_eq!(properties.len(), 2);
+
+    let a = cast!(Property, IntProperty, &properties[0]).expect("A is not an IntProperty");
+    assert_eq!(a.name.get_owned_content(), "A");
+    assert_eq!(a.value, 1);
+
+    let c = cast!(Property, IntProperty, &properties[1]).expect("C is not an IntProperty");
+    assert_eq!(c.name.get_owned_content(), "C");
+    assert_eq!(c.get_duplication_index(), 1);
+    assert_eq!(c.value, 5);
+}
+
+#[test]
+fn convert_property_format() -> Result<(), Error> {
+    let asset = create_asset(vec![int_property("A", 0, 1), int_property("C", 1, 5)]);
+    let mut uasset = Cursor::new(Vec::new());
+    let mut uexp = Cursor::new(Vec::new());
+    asset.write_data(&mut uasset, Some(&mut uexp))?;
+    let (uasset, uexp) = (uasset.into_inner(), uexp.into_inner());
+
+    let mappings = create_mappings();
+    let (versioned, versioned_bulk) = convert_asset(
+        &uasset,
+        Some(&uexp),
+        EngineVersion::VER_UE4_27,
+        &mappings,
+        PropertyFormat::Versioned,
+    )?;
+
+    // tagged properties are read without mappings
+    let parsed = Asset::new(
+        Cursor::new(versioned.clone()),
+        versioned_bulk.clone().map(Cursor::new),
+        EngineVersion::VER_UE4_27,
+        None,
+    )?;
+    assert!(!parsed.asset_data.has_unversioned_properties());
+    check_converted_properties(&parsed);
+
+    let (unversioned, unversioned_bulk) = convert_asset(
+        &versioned,
+        versioned_bulk.as_deref(),
+        EngineVersion::VER_UE4_27,
+        &mappings,
+        PropertyFormat::Unversioned,
+    )?;
+    let parsed = Asset::new(
+        Cursor::new(unversioned),
+        unversioned_bulk.map(Cursor::new),
+        EngineVersion::VER_UE4_27,
+        Some(create_mappings()),
+    )?;
+    assert!(parsed.asset_data.has_unversioned_properties());
+    check_converted_properties(&parsed);
+
+    // directories are converted with their structure, other files are copied
+    let directory =
+        std::env::temp_dir().join(format!("unreal_asset_convert_test_{}", std::process::id()));
+    let input = directory.join("input");
+    let output = directory.join("output");
+    std::fs::create_dir_all(input.join("Items"))?;
+    std::fs::write(input.join("Items/TestObject.uasset"), &uasset)?;
+    std::fs::write(input.join("Items/TestObject.uexp"), &uexp)?;
+    std::fs::write(input.join("Items/Broken.uasset"), [0; 16])?;
+    std::fs::write(input.join("Readme.txt"), "readme")?;
+
+    let failures = convert_directory(
+        &input,
+        &output,
+        EngineVersion::VER_UE4_27,
+        &mappings,
+        PropertyFormat::Versioned,
+    )?;
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].path, "Items/Broken.uasset");
+
+    assert_eq!(
+        std::fs::read(output.join("Items/TestObject.uasset"))?,
+        versioned
+    );
+    assert_eq!(
+        Some(std::fs::read(output.join("Items/TestObject.uexp"))?),
+        versioned_bulk
+    );
+    assert_eq!(std::fs::read(output.join("Items/Broken.uasset"))?, [0; 16]);
+    assert_eq!(std::fs::read(output.join("Readme.txt"))?, b"readme");
+
+    std::fs::remove_dir_all(directory)?;
+    Ok(())
+}
+
+/// Create a header serializing `A`, a zeroed `B` and `C[1]`
+fn create_header() -> UnversionedHeader {
+    UnversionedHeader {
+        fragments: vec![
+            UnversionedHeaderFragment {
+                skip_num: 0,
+                value_num: 2,
+                first_num: 0,
+                is_last: false,
+                has_zeros: true,
+            },
+            UnversionedHeaderFragment {
+                skip_num: 1,
+                value_num: 1,
+                first_num: 3,
+                is_last: true,
+                has_zeros: false,
+            },
+        ],
+        zero_mask: [false, true].into_iter().collect(),
+        has_non_zero_values: true,
+        unversioned_property_index: 0,
+        current_fragment_index: 0,
+        zero_mask_index: 0,
+    }
+}
+
+#[test]
+fn validate_header() -> Result<(), Error> {
+    let mut zeroed = int_property("B", 0, 0);
+    zeroed.set_zeroed();
+    let properties = vec![int_property("A", 0, 1), zeroed, int_property("C", 1, 5)];
+    let asset = create_asset(Vec::new());
+
+    let mut header = create_header();
+    validate_unversioned_header(&asset, &header, &properties)?;
+
+    // B is zeroed, but the mask says otherwise
+    header.zero_mask.set(1, false);
+    assert!(validate_unversioned_header(&asset, &header, &properties).is_err());
+    header.zero_mask.set(1, true);
+
+    // C[1] is serialized at the index of C[0]
+    header.fragments[1].skip_num = 0;
+    header.fragments[1].first_num = 2;
+    assert!(validate_unversioned_header(&asset, &header, &properties).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn validate_header_fragments() -> Result<(), Error> {
+    let mappings = create_mappings();
+
+    let mut header = create_header();
+    header.validate(&mappings, CLASS_NAME)?;
+
+    // the mappings only have 4 properties
+    header.fragments[1].skip_num = 2;
+    header.fragments[1].first_num = 4;
+    assert!(header.validate_fragments().is_ok());
+    assert!(header.validate(&mappings, CLASS_NAME).is_err());
+
+    let mut header = create_header();
+    header.fragments[0].is_last = true;
+    assert!(header.validate_fragments().is_err());
+
+    let mut header = create_header();
+    header.zero_mask.push(false);
+    assert!(header.validate_fragments().is_err());
+
+    Ok(())
+}
+
+#[test]
+fn describe_header() {
+    let header = create_header();
+
+    assert_eq!(
+        header.describe(Some(&create_mappings()), CLASS_NAME),
+        "Fragment 0: skip 0, 2 values (0..=1), has zeros\n\
+         \x20    0 A\n\
+         \x20    1 B (zero)\n\
+         Fragment 1: skip 1, 1 values (3..=3), last\n\
+         \x20    3 C[1]\n\
+         Zero mask: 2 bits, has non-zero values: true\n"
+    );
+
+    assert!(header
+        .describe(None, CLASS_NAME)
+        .contains("     3 <unknown>\n"));
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "zip")]
+
+use std::io::{Cursor, Write};
+
+use unreal_asset::{
+    engine_version::EngineVersion,
+    project::{AssetSource, Project, ZipSource},
+    Error,
+};
+use zip::write::FileOptions;
+
+macro_rules! test_asset {
+    () => {
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/assets/general/Misc_426/RaceSimDataAsset"
+        )
+    };
+}
+
+const TEST_ASSET: &[u8] = include_bytes!(concat!(test_asset!(), ".uasset"));
+const TEST_BULK: &[u8] = include_bytes!(concat!(test_asset!(), ".uexp"));
+
+const PACKAGE_PATH: &str = "/Game/Data/RaceSimDataAsset";
+
+fn create_zip(entries: &[(&str, &[u8])]) -> Cursor<Vec<u8>> {
+    let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, data) in entries {
+        writer
+            .start_file(*name, FileOptions::default())
+            .expect("Failed to start zip entry");
+        writer.write_all(data).expect("Failed to write zip entry");
+    }
+    let mut cursor = writer.finish().expect("Failed to finish zip");
+    cursor.set_position(0);
+    cursor
+}
+
+#[test]
+fn zip_source() -> Result<(), Error> {
+    let zip = create_zip(&[
+        ("MyMod/Content/Data/RaceSimDataAsset.uasset", TEST_ASSET),
+        ("MyMod\\Content\\Data\\RaceSimDataAsset.uexp", TEST_BULK),
+        ("MyMod/readme.txt", b"readme"),
+    ]);
+
+    let mut source = ZipSource::with_root(zip, "MyMod/")?;
+    assert_eq!(source.get_package_paths(), vec![PACKAGE_PATH.to_string()]);
+    assert_eq!(
+        source
+            .read_file(&format!("{PACKAGE_PATH}.uexp"))?
+            .as_deref(),
+        Some(TEST_BULK)
+    );
+    assert!(source.read_file("/Game/Data/Missing.uasset")?.is_none());
+
+    let mut project = Project::new(source, EngineVersion::VER_UE4_26, None);
+    let asset = project.load(PACKAGE_PATH)?;
+    assert!(asset.use_separate_bulk_data_files());
+
+    Ok(())
+}
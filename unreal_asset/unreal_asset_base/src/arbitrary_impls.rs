@@ -0,0 +1,24 @@
+//! `Arbitrary` implementations for fuzzing and property based testing, enabled with the `arbitrary` feature
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::types::{FName, PackageIndex, PackedObjectRef};
+
+impl<'a> Arbitrary<'a> for FName {
+    /// Generates dummy names, which have to be added to the name map of an asset before they can be written
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(FName::new_dummy(u.arbitrary()?, u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PackageIndex {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(PackageIndex::new(u.arbitrary()?))
+    }
+}
+
+impl<'a> Arbitrary<'a> for PackedObjectRef {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(PackedObjectRef::new(u.arbitrary()?))
+    }
+}
@@ -1,19 +1,49 @@
 //! Chain for chaining two `Read` + `Seek` implementations
 
-use std::io::{Read, Result, Seek, SeekFrom};
+use std::io::{Error, ErrorKind, Read, Result, Seek, SeekFrom};
+
+/// Default size of the read-ahead buffer of a [`Chain`]
+pub const DEFAULT_READ_AHEAD_SIZE: usize = 64 * 1024;
 
 /// Chain for chaining two `Read` + `Seek` implementations
+///
+/// Reads go through a read-ahead buffer, so parsing, which issues many tiny reads,
+/// doesn't turn into a syscall per read when reading from a `File`.
+/// Seeks only move the position of the chain, the underlying readers are seeked when data needs to be read.
 pub struct Chain<C: Read + Seek> {
     first: C,
+    /// Data read instead of the first reader, set when the first reader is replaced
+    first_data: Option<Vec<u8>>,
     second: Option<C>,
     first_len: u64,
     second_len: u64,
     pos: u64,
+    /// Position of the first reader, `None` if it's unknown
+    first_pos: Option<u64>,
+    /// Position of the second reader, `None` if it's unknown
+    second_pos: Option<u64>,
+    /// Read-ahead buffer
+    buffer: Vec<u8>,
+    /// Position of the chain the read-ahead buffer starts at
+    buffer_start: u64,
+    /// Amount of bytes read into the read-ahead buffer at once
+    read_ahead_size: usize,
 }
 
 impl<C: Read + Seek> Chain<C> {
-    /// Create a new chain
-    pub fn new(mut first: C, mut second: Option<C>) -> Self {
+    /// Create a new chain with a read-ahead buffer of [`DEFAULT_READ_AHEAD_SIZE`] bytes
+    pub fn new(first: C, second: Option<C>) -> Self {
+        Self::with_read_ahead_size(first, second, DEFAULT_READ_AHEAD_SIZE)
+    }
+
+    /// Create a new chain with a read-ahead buffer of `read_ahead_size` bytes
+    ///
+    /// A size of 0 disables the read-ahead buffer.
+    pub fn with_read_ahead_size(
+        mut first: C,
+        mut second: Option<C>,
+        read_ahead_size: usize,
+    ) -> Self {
         // ignore errors for now
         let first_len = first.seek(SeekFrom::End(0)).unwrap_or_default();
         first.rewind().unwrap_or_default();
@@ -27,74 +57,134 @@ impl<C: Read + Seek> Chain<C> {
         };
         Self {
             first,
+            first_data: None,
             second,
             first_len,
             second_len,
             pos: 0,
+            first_pos: None,
+            second_pos: None,
+            buffer: Vec::new(),
+            buffer_start: 0,
+            read_ahead_size,
+        }
+    }
+
+    /// Set the size of the read-ahead buffer, a size of 0 disables it
+    pub fn set_read_ahead_size(&mut self, read_ahead_size: usize) {
+        self.read_ahead_size = read_ahead_size;
+        self.buffer = Vec::new();
+    }
+
+    /// Replace the data of the first reader, e.g. with the decompressed data of a compressed package
+    ///
+    /// The first reader is no longer read from after this, positions of the second reader
+    /// move with the length of the new data.
+    pub fn replace_first(&mut self, data: Vec<u8>) {
+        self.first_len = data.len() as u64;
+        self.first_data = Some(data);
+        self.first_pos = None;
+        self.buffer = Vec::new();
+        self.buffer_start = 0;
+    }
+
+    /// Check if a position is contained in the read-ahead buffer
+    fn is_buffered(&self, pos: u64) -> bool {
+        pos >= self.buffer_start && pos < self.buffer_start + self.buffer.len() as u64
+    }
+
+    /// Read from the underlying readers at a position, reads don't cross from the first into the second reader
+    fn read_at(&mut self, pos: u64, buf: &mut [u8]) -> Result<usize> {
+        match self.second.as_mut() {
+            Some(sec) if pos >= self.first_len => {
+                let offset = pos - self.first_len;
+                if self.second_pos != Some(offset) {
+                    self.second_pos = None;
+                    sec.seek(SeekFrom::Start(offset))?;
+                }
+                let len_read = sec.read(buf)?;
+                self.second_pos = Some(offset + len_read as u64);
+                Ok(len_read)
+            }
+            second => {
+                let len = match second.is_some() {
+                    true => buf.len().min((self.first_len - pos) as usize),
+                    false => buf.len(),
+                };
+                if let Some(data) = self.first_data.as_deref() {
+                    let start = pos.min(data.len() as u64) as usize;
+                    let len_read = len.min(data.len() - start);
+                    buf[..len_read].copy_from_slice(&data[start..start + len_read]);
+                    return Ok(len_read);
+                }
+                if self.first_pos != Some(pos) {
+                    self.first_pos = None;
+                    self.first.seek(SeekFrom::Start(pos))?;
+                }
+                let len_read = self.first.read(&mut buf[..len])?;
+                self.first_pos = Some(pos + len_read as u64);
+                Ok(len_read)
+            }
+        }
+    }
+
+    /// Fill the read-ahead buffer starting at the current position
+    fn fill_buffer(&mut self) -> Result<()> {
+        let mut buffer = std::mem::take(&mut self.buffer);
+        buffer.resize(self.read_ahead_size, 0);
+
+        let mut filled = 0;
+        while filled < buffer.len() {
+            match self.read_at(self.pos + filled as u64, &mut buffer[filled..]) {
+                Ok(0) => break,
+                Ok(len_read) => filled += len_read,
+                Err(e) if e.kind() == ErrorKind::Interrupted => {}
+                // return what was read so far, the error will occur again on the next read
+                Err(_) if filled > 0 => break,
+                Err(e) => return Err(e),
+            }
         }
+
+        buffer.truncate(filled);
+        self.buffer = buffer;
+        self.buffer_start = self.pos;
+        Ok(())
     }
 }
 
 impl<C: Read + Seek> Read for Chain<C> {
-    // this is an implementation of read so clippy complaining about use of read is stupid
-    #[allow(clippy::unused_io_amount)]
     fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
-        match self.second.as_mut() {
-            Some(sec) => {
-                let len_read = match self.pos >= self.first_len {
-                    true => sec.read(buf)?,
-                    false => {
-                        let len = buf.len() as u64;
-                        let to_end = self.first_len - self.pos;
-                        match to_end >= len {
-                            true => self.first.read(buf)?,
-                            false => {
-                                let mut first = vec![0; to_end as usize];
-                                let excess = len - to_end;
-                                let mut second = vec![
-                                    0;
-                                    match excess > self.second_len {
-                                        true => self.second_len,
-                                        false => excess,
-                                    } as usize
-                                ];
-                                self.first.read_exact(&mut first)?;
-                                sec.read_exact(&mut second)?;
-                                first.append(&mut second);
-                                first.as_slice().read(buf)?
-                            }
-                        }
-                    }
-                };
+        if !self.is_buffered(self.pos) {
+            // large reads are not worth buffering
+            if buf.len() >= self.read_ahead_size {
+                let len_read = self.read_at(self.pos, buf)?;
                 self.pos += len_read as u64;
-                Ok(len_read)
+                return Ok(len_read);
             }
-            None => self.first.read(buf),
+            self.fill_buffer()?;
         }
+
+        let start = (self.pos - self.buffer_start) as usize;
+        let len_read = (&self.buffer[start..]).read(buf)?;
+        self.pos += len_read as u64;
+        Ok(len_read)
     }
 }
 
 impl<C: Read + Seek> Seek for Chain<C> {
     fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
-        match self.second.as_mut() {
-            Some(sec) => match pos {
-                SeekFrom::Start(offset) => {
-                    self.pos = match offset < self.first_len {
-                        true => self.first.seek(pos)?,
-                        false => {
-                            self.first_len + sec.seek(SeekFrom::Start(offset - self.first_len))?
-                        }
-                    };
-                    Ok(offset)
-                }
-                SeekFrom::End(offset) => self.seek(SeekFrom::Start(
-                    ((self.first_len + self.second_len) as i64 + offset) as u64,
-                )),
-                SeekFrom::Current(offset) => {
-                    self.seek(SeekFrom::Start((self.pos as i64 + offset) as u64))
-                }
-            },
-            None => self.first.seek(pos),
-        }
+        let new_pos = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => (self.first_len + self.second_len).checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.pos.checked_add_signed(offset),
+        };
+
+        self.pos = new_pos.ok_or_else(|| {
+            Error::new(
+                ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.pos)
     }
 }
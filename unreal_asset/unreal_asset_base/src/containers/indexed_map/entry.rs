@@ -3,6 +3,7 @@ use std::hash::Hash;
 
 use super::IndexedMap;
 
+/// Entry of an [`IndexedMap`], either vacant or occupied
 pub enum Entry<'map, K: 'map, V: 'map>
 where
     K: Eq + Hash,
@@ -45,6 +46,24 @@ where
             Entry::Occupied(entry) => entry.key(),
         }
     }
+
+    /// Modifies the value of an occupied entry before any potential insert
+    pub fn and_modify<F: FnOnce(&mut V)>(mut self, f: F) -> Self {
+        if let Entry::Occupied(ref mut entry) = self {
+            f(entry.get_mut());
+        }
+        self
+    }
+}
+
+impl<'map, K, V> Entry<'map, K, V>
+where
+    K: Eq + Hash,
+    V: Default,
+{
+    pub fn or_default(self) -> &'map mut V {
+        self.or_insert_with(V::default)
+    }
 }
 
 impl<K, V> Debug for Entry<'_, K, V>
@@ -93,7 +112,7 @@ where
     /// NOTE: Insertion index is calculated upon calling this function
     /// Not upon creating the entry
     pub fn insert(self, value: V) -> &'map mut V {
-        &mut self.map.internal_insert(self.key, value).value
+        &mut self.map.insert_new(self.key, value).value
     }
 }
 
@@ -125,6 +144,12 @@ where
         self.map.store[self.store_place].key_map_index.0.as_ref()
     }
 
+    /// Gets the insertion index of the entry
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.map.store[self.store_place].index_map_index
+    }
+
     pub fn remove_entry(self) -> (usize, K, V) {
         self.map.remove_by_store_place(self.store_place).unwrap()
     }
@@ -147,6 +172,11 @@ where
     pub fn remove(self) -> V {
         self.remove_entry().2
     }
+
+    /// Replaces the value of the entry, keeping its position, and returns the old value
+    pub fn insert(&mut self, value: V) -> V {
+        std::mem::replace(self.get_mut(), value)
+    }
 }
 
 impl<K, V> Debug for OccupiedEntry<'_, K, V>
@@ -5,7 +5,11 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::iter::FusedIterator;
+#[cfg(not(feature = "threading"))]
 use std::rc::Rc;
+// keys are shared by the internal maps, so they must be atomically counted for the map to be `Send + Sync`
+#[cfg(feature = "threading")]
+use std::sync::Arc as Rc;
 
 pub mod entry;
 
@@ -129,6 +133,26 @@ where
 ///
 /// Insertion time is O(1)
 /// Deletion time is O(n) worst-case
+///
+/// # Ordering
+///
+/// [`IndexedMap::iter`], [`IndexedMap::keys`], [`IndexedMap::values`] and the owning iterator
+/// always yield entries in insertion order. Replacing the value of an existing key keeps its position,
+/// removing an entry keeps the relative order of the remaining entries.
+///
+/// Insertion indices are never reused while the map contains entries inserted after them,
+/// so removing an entry doesn't change the insertion index of any other entry.
+///
+/// [`IndexedMap::iter_key`] iterates in hash order, which is unspecified.
+///
+/// # Hashing
+///
+/// Keys are hashed with FxHash from `rustc-hash`, which is much faster than the default SipHash
+/// for the short string keys used in assets but not resistant against HashDoS.
+/// Looking up an entry with [`IndexedMap::entry`] and inserting through the returned [`Entry`]
+/// only hashes the key once.
+///
+/// With the `serde` feature enabled the map is serialized as a map in insertion order.
 pub struct IndexedMap<K, V>
 where
     K: Eq + Hash,
@@ -558,7 +582,7 @@ where
             store: slab::Slab::with_capacity(capacity),
             key_map: rustc_hash::FxHashMap::with_capacity_and_hasher(capacity, Default::default()),
             index_map: BTreeMap::new(),
-            index_iter_map: Vec::new(),
+            index_iter_map: Vec::with_capacity(capacity),
         }
     }
 
@@ -568,17 +592,28 @@ where
             return &mut self.store[*storage_place];
         }
 
+        self.insert_new(key, value)
+    }
+
+    /// Inserts a key which is known to not be in the map yet
+    fn insert_new(&'map mut self, key: K, value: V) -> &'map mut IndexedValue<K, V> {
+        let index = self
+            .index_map
+            .last_key_value()
+            .map(|(index, _)| index + 1)
+            .unwrap_or_default();
+
         let key_rc = KeyItem(Rc::new(key));
         let indexed_value = IndexedValue {
             value,
             key_map_index: key_rc.rc_clone(),
-            index_map_index: self.key_map.len(),
+            index_map_index: index,
             index_iter_map_index: self.index_iter_map.len(),
         };
         let store_place = self.store.insert(indexed_value);
 
         self.key_map.insert(key_rc, store_place);
-        self.index_map.insert(self.key_map.len() - 1, store_place);
+        self.index_map.insert(index, store_place);
         self.index_iter_map.push(store_place);
         &mut self.store[store_place]
     }
@@ -589,6 +624,9 @@ where
         self.internal_insert(key, value);
     }
 
+    /// Gets the entry of a key for in-place manipulation
+    ///
+    /// A vacant entry gets its insertion index when a value is inserted through it.
     pub fn entry(&'map mut self, key: K) -> Entry<'map, K, V> {
         if let Some(store_place) = self.key_map.get(&key) {
             let place = *store_place;
@@ -680,6 +718,9 @@ where
         let value = self.store.remove(store_place);
 
         self.index_iter_map.remove(value.index_iter_map_index);
+        for store_place in &self.index_iter_map[value.index_iter_map_index..] {
+            self.store[*store_place].index_iter_map_index -= 1;
+        }
         self.index_map.remove(&value.index_map_index);
         self.key_map.remove(value.key_map_index.0.as_ref());
 
@@ -710,7 +751,54 @@ where
         self.remove_by_store_place(*self.key_map.get(key)?)
     }
 
-    pub fn contains_key(&self, key: &K) -> bool {
+    /// Retains only the entries for which the predicate returns `true`
+    ///
+    /// Entries are visited in insertion order, the order of the retained entries is preserved.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &mut V) -> bool,
+    {
+        let store = &mut self.store;
+        let key_map = &mut self.key_map;
+        let index_map = &mut self.index_map;
+
+        self.index_iter_map.retain(|store_place| {
+            let value = &mut store[*store_place];
+            if f(value.key_map_index.0.as_ref(), &mut value.value) {
+                return true;
+            }
+
+            let value = store.remove(*store_place);
+            index_map.remove(&value.index_map_index);
+            key_map.remove(value.key_map_index.0.as_ref());
+            false
+        });
+
+        for (index_iter_map_index, store_place) in self.index_iter_map.iter().enumerate() {
+            self.store[*store_place].index_iter_map_index = index_iter_map_index;
+        }
+    }
+
+    /// Removes all entries from the map, returning them as an iterator in insertion order
+    ///
+    /// The map is emptied even if the iterator is not fully consumed.
+    pub fn drain(&mut self) -> IndexedMapIndexIteratorOwned<K, V> {
+        std::mem::take(self).into_iter()
+    }
+
+    /// Removes all entries from the map
+    pub fn clear(&mut self) {
+        self.store.clear();
+        self.key_map.clear();
+        self.index_map.clear();
+        self.index_iter_map.clear();
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        KeyItem<K>: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
         self.key_map.contains_key(key)
     }
 
@@ -855,3 +943,68 @@ where
         Self::from_iter(value)
     }
 }
+
+#[cfg(feature = "serde")]
+impl<K, V> serde::Serialize for IndexedMap<K, V>
+where
+    K: Eq + Hash + serde::Serialize,
+    V: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.len()))?;
+        for (_, key, value) in self.iter() {
+            map.serialize_entry(key, value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, K, V> serde::Deserialize<'de> for IndexedMap<K, V>
+where
+    K: Eq + Hash + serde::Deserialize<'de>,
+    V: serde::Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        struct IndexedMapVisitor<K, V>(std::marker::PhantomData<(K, V)>);
+
+        impl<'de, K, V> serde::de::Visitor<'de> for IndexedMapVisitor<K, V>
+        where
+            K: Eq + Hash + serde::Deserialize<'de>,
+            V: serde::Deserialize<'de>,
+        {
+            type Value = IndexedMap<K, V>;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A>(self, mut access: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                // the size hint comes from the serialized data, so it can't be trusted for preallocation
+                const MAX_PREALLOCATION: usize = 1024 * 1024;
+                let max_capacity =
+                    MAX_PREALLOCATION / std::mem::size_of::<IndexedValue<K, V>>().max(1);
+                let mut map = IndexedMap::with_capacity(
+                    access.size_hint().unwrap_or_default().min(max_capacity),
+                );
+                while let Some((key, value)) = access.next_entry()? {
+                    map.insert(key, value);
+                }
+                Ok(map)
+            }
+        }
+
+        deserializer.deserialize_map(IndexedMapVisitor(std::marker::PhantomData))
+    }
+}
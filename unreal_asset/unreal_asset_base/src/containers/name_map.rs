@@ -1,8 +1,5 @@
 //! Asset name map
 
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-
 use crate::containers::{
     indexed_map::IndexedMap,
     shared_resource::{CyclicSharedResource, SharedResource, SharedResourceWeakRef},
@@ -13,7 +10,9 @@ use crate::types::fname::{EMappedNameType, FName};
 #[derive(Debug, Clone)]
 pub struct NameMap {
     /// Name map lookup
-    name_map_lookup: IndexedMap<u64, i32>,
+    ///
+    /// Keyed by the names themselves, so different names can never resolve to the same index
+    name_map_lookup: IndexedMap<String, i32>,
     /// Name map index list
     name_map_index_list: Vec<String>,
     /// A reference to self
@@ -44,10 +43,7 @@ impl NameMap {
 
     /// Search an FName reference
     pub fn search_name_reference(&self, name: &str) -> Option<i32> {
-        let mut s = DefaultHasher::new();
-        name.hash(&mut s);
-
-        self.name_map_lookup.get_by_key(&s.finish()).copied()
+        self.name_map_lookup.get_by_key(name).copied()
     }
 
     /// Add an FName reference
@@ -59,14 +55,10 @@ impl NameMap {
             }
         }
 
-        let mut s = DefaultHasher::new();
-        name.hash(&mut s);
-
-        let hash = s.finish();
+        let index = self.name_map_index_list.len() as i32;
         self.name_map_index_list.push(name.clone());
-        self.name_map_lookup
-            .insert(hash, (self.name_map_index_list.len() - 1) as i32);
-        (self.name_map_index_list.len() - 1) as i32
+        self.name_map_lookup.insert(name, index);
+        index
     }
 
     /// Get all FNames
@@ -93,10 +85,48 @@ impl NameMap {
     }
 
     /// Get a mutable name reference by an FName map index
+    ///
+    /// The lookup is not updated, call [`NameMap::rebuild_lookup`] after changing names
+    /// or use [`NameMap::set_name_reference`] instead
     pub fn get_name_reference_mut(&mut self, index: i32) -> &mut String {
         &mut self.name_map_index_list[index as usize]
     }
 
+    /// Replace the content of a name map entry while keeping the lookup in sync
+    ///
+    /// Unlike [`NameMap::get_name_reference_mut`] this makes sure the entry can still be found
+    /// with [`NameMap::search_name_reference`] after being changed
+    pub fn set_name_reference(&mut self, index: i32, name: String) {
+        let old_name =
+            std::mem::replace(&mut self.name_map_index_list[index as usize], name.clone());
+
+        if self.name_map_lookup.get_by_key(old_name.as_str()) == Some(&index) {
+            self.name_map_lookup.remove_by_key(old_name.as_str());
+
+            // another entry with the old content may still exist
+            if let Some(duplicate) = self
+                .name_map_index_list
+                .iter()
+                .rposition(|e| e == &old_name)
+            {
+                self.name_map_lookup.insert(old_name, duplicate as i32);
+            }
+        }
+
+        self.name_map_lookup.entry(name).or_insert(index);
+    }
+
+    /// Rebuild the lookup from the name map entries
+    ///
+    /// Needed after names were changed with [`NameMap::get_name_reference_mut`].
+    /// Like when adding duplicates, the last entry with a given content is found by [`NameMap::search_name_reference`]
+    pub fn rebuild_lookup(&mut self) {
+        self.name_map_lookup = IndexedMap::with_capacity(self.name_map_index_list.len());
+        for (index, name) in self.name_map_index_list.iter().enumerate() {
+            self.name_map_lookup.insert(name.clone(), index as i32);
+        }
+    }
+
     /// Create an `FName` for an index in this name map
     pub fn create_fname(&self, index: i32, number: i32) -> FName {
         FName::Backed {
@@ -118,6 +148,14 @@ impl NameMap {
         self.create_fname(index, number)
     }
 
+    /// Add an `FName`, splitting a numeric suffix off into the instance number like the engine
+    ///
+    /// `Foo_3` is added as `Foo` with instance number 4, see [`FName::split_number`]
+    pub fn add_fname_split(&mut self, slice: &str) -> FName {
+        let (name, number) = FName::split_number(slice);
+        self.add_fname_with_number(name, number)
+    }
+
     /// Returns if the name map is empty
     pub fn is_empty(&self) -> bool {
         self.name_map_index_list.is_empty()
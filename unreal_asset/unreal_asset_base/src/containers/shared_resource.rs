@@ -2,7 +2,8 @@
 //!
 //! This is used when a resource must be shared between multiple other resources
 //!
-//! The implementation depends on the `threading` feature being enabled
+//! The implementation depends on the `threading` feature being enabled,
+//! with it shared resources are `Send + Sync` and can be shared across threads
 
 use std::fmt;
 use std::ops::Deref;
@@ -101,7 +102,7 @@ impl<T> SharedResource<T> {
     ///
     /// In a multithreaded scenario panics if the lock was poisoned
     #[cfg(feature = "threading")]
-    pub fn get_mut(&mut self) -> std::sync::RwLockWriteGuard<T> {
+    pub fn get_mut(&mut self) -> std::sync::RwLockWriteGuard<'_, T> {
         self.resource.write().unwrap()
     }
 }
@@ -153,6 +154,18 @@ impl<T: ?Sized + PartialEq> PartialEq for SharedResource<T> {
 #[cfg(not(feature = "threading"))]
 impl<T: ?Sized + Eq> Eq for SharedResource<T> {}
 
+#[cfg(feature = "threading")]
+impl<T: ?Sized + PartialEq> PartialEq for SharedResource<T> {
+    fn eq(&self, other: &Self) -> bool {
+        // locking the same resource twice could deadlock
+        std::sync::Arc::ptr_eq(&self.resource, &other.resource)
+            || *self.resource.read().unwrap() == *other.resource.read().unwrap()
+    }
+}
+
+#[cfg(feature = "threading")]
+impl<T: ?Sized + Eq> Eq for SharedResource<T> {}
+
 #[cfg(not(feature = "threading"))]
 impl<T: ?Sized + PartialOrd> PartialOrd for SharedResource<T> {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
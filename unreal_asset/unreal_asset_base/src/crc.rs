@@ -181,11 +181,39 @@ pub fn generate_hash(string: &str) -> u32 {
 
 /// Generates a cityhash64 hash for a lowercase variant of a string
 pub fn cityhash64_to_lower(string: &str) -> u64 {
-    let encoded = string.encode_utf16().map(to_lower).collect::<Vec<_>>();
-    // this is safe because we know that this is a u16 array, therefore it can safely be aligned to u8
-    // this is also faster than alternatives without unsafe block
-    let (_, aligned, _) = unsafe { encoded.align_to::<u8>() };
-    cityhash64(aligned)
+    cityhash64_to_lower_with_buffer(string, &mut Vec::new())
+}
+
+/// Generates cityhash64 hashes for lowercase variants of multiple strings
+///
+/// This reuses a single encoding buffer for all strings, which is considerably faster
+/// than calling [`cityhash64_to_lower`] for every entry of a large name batch.
+pub fn cityhash64_to_lower_batch<S: AsRef<str>>(strings: &[S]) -> Vec<u64> {
+    let mut buffer = Vec::new();
+    strings
+        .iter()
+        .map(|string| cityhash64_to_lower_with_buffer(string.as_ref(), &mut buffer))
+        .collect()
+}
+
+fn cityhash64_to_lower_with_buffer(string: &str, buffer: &mut Vec<u8>) -> u64 {
+    buffer.clear();
+    buffer.reserve(string.len() * 2);
+
+    match string.is_ascii() {
+        true => {
+            for byte in string.bytes() {
+                buffer.extend_from_slice(&[byte.to_ascii_lowercase(), 0]);
+            }
+        }
+        false => {
+            for character in string.encode_utf16().map(to_lower) {
+                buffer.extend_from_slice(&character.to_le_bytes());
+            }
+        }
+    }
+
+    cityhash64(buffer.as_slice())
 }
 
 fn to_upper(character: u16) -> u16 {
@@ -197,7 +225,7 @@ fn to_upper(character: u16) -> u16 {
 }
 
 fn to_lower(character: u16) -> u16 {
-    if character.saturating_sub('a' as u16) < 26u16 {
+    if character.saturating_sub('A' as u16) < 26u16 {
         (character as u8 as char).to_lowercase().next().unwrap() as u16
     } else {
         character
@@ -2,6 +2,7 @@
 
 use std::collections::HashMap;
 use std::fmt::Display;
+use std::sync::{PoisonError, RwLock};
 
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 use lazy_static::lazy_static;
@@ -80,12 +81,42 @@ lazy_static! {
         ( FReleaseObjectVersion::GUID,                              (String::from(FReleaseObjectVersion::FRIENDLY_NAME), Some(FReleaseObjectVersion::VERSION_MAPPINGS)) ),
         ( FSequencerObjectVersion::GUID,                            (String::from(FSequencerObjectVersion::FRIENDLY_NAME), Some(FSequencerObjectVersion::VERSION_MAPPINGS)) ),
     ]);
+
+    /// Custom versions registered at runtime, these take priority over the built-in ones
+    static ref REGISTERED_VERSION_INFO: RwLock<HashMap<Guid, VersionInfo>> = RwLock::new(HashMap::new());
+}
+
+/// Get the info of a registered or built-in custom version
+fn get_version_info(guid: &Guid) -> Option<VersionInfo> {
+    REGISTERED_VERSION_INFO
+        .read()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(guid)
+        .or_else(|| GUID_TO_VERSION_INFO.get(guid))
+        .cloned()
+}
+
+/// Get the info of all registered and built-in custom versions
+fn get_all_version_info() -> Vec<(Guid, VersionInfo)> {
+    let registered = REGISTERED_VERSION_INFO
+        .read()
+        .unwrap_or_else(PoisonError::into_inner);
+
+    registered
+        .iter()
+        .chain(
+            GUID_TO_VERSION_INFO
+                .iter()
+                .filter(|(guid, _)| !registered.contains_key(*guid)),
+        )
+        .map(|(guid, info)| (*guid, info.clone()))
+        .collect()
 }
 
 impl CustomVersion {
     /// Create a new custom version
     pub fn new(guid: Guid, version: i32) -> Self {
-        let version_info = GUID_TO_VERSION_INFO.get(&guid).map(|e| e.to_owned());
+        let version_info = get_version_info(&guid);
         CustomVersion {
             guid,
             friendly_name: version_info.as_ref().map(|e| e.0.clone()),
@@ -94,6 +125,50 @@ impl CustomVersion {
         }
     }
 
+    /// Create a known custom version by its friendly name
+    pub fn from_friendly_name(friendly_name: &str, version: i32) -> Option<Self> {
+        Some(CustomVersion::new(
+            CustomVersion::get_guid_from_friendly_name(friendly_name)?,
+            version,
+        ))
+    }
+
+    /// Get the guid of a known custom version by its friendly name
+    pub fn get_guid_from_friendly_name(friendly_name: &str) -> Option<Guid> {
+        get_all_version_info()
+            .into_iter()
+            .find(|(_, (name, _))| name == friendly_name)
+            .map(|(guid, _)| guid)
+    }
+
+    /// Register a custom version which isn't known by this crate, e.g. from a game's engine branch
+    ///
+    /// Registered custom versions get their friendly name when read, and their version mappings
+    /// are used for default custom version containers and engine version guessing.
+    /// Mappings are ordered from newest to oldest engine version like [`CustomVersionTrait::VERSION_MAPPINGS`],
+    /// mappings created at runtime can be made `'static` with [`Vec::leak`].
+    /// Registering a built-in custom version's guid overrides it.
+    pub fn register(
+        guid: Guid,
+        friendly_name: &str,
+        version_mappings: &'static [(EngineVersion, i32)],
+    ) {
+        REGISTERED_VERSION_INFO
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(guid, (friendly_name.to_string(), Some(version_mappings)));
+    }
+
+    /// Register a custom version enum, see [`CustomVersion::register`]
+    pub fn register_trait<T: CustomVersionTrait>() {
+        CustomVersion::register(T::GUID, T::FRIENDLY_NAME, T::VERSION_MAPPINGS);
+    }
+
+    /// Check if a custom version guid is registered or built-in
+    pub fn is_known(guid: &Guid) -> bool {
+        get_version_info(guid).is_some()
+    }
+
     /// Read a custom version from an asset
     pub fn read<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -101,7 +176,7 @@ impl CustomVersion {
         let guid = asset.read_guid()?;
         let version = asset.read_i32::<LE>()?;
 
-        let version_info = GUID_TO_VERSION_INFO.get(&guid).map(|e| e.to_owned());
+        let version_info = get_version_info(&guid);
         Ok(Self {
             guid,
             friendly_name: version_info.as_ref().map(|e| e.0.clone()),
@@ -161,8 +236,8 @@ impl CustomVersion {
     ) -> Vec<CustomVersion> {
         let mut container = Vec::new();
 
-        for (guid, _) in GUID_TO_VERSION_INFO.iter() {
-            let mut version = CustomVersion::new(*guid, 0);
+        for (guid, _) in get_all_version_info() {
+            let mut version = CustomVersion::new(guid, 0);
             if let Some(version_number) =
                 version.get_version_number_from_engine_version(engine_version)
             {
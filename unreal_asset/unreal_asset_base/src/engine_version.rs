@@ -1,5 +1,7 @@
 //! Unreal Engine version
 
+use std::fmt;
+
 use lazy_static::lazy_static;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
@@ -91,6 +93,113 @@ pub enum EngineVersion {
     VER_UE4_AUTOMATIC_VERSION_PLUS_ONE,
 }
 
+impl EngineVersion {
+    /// All retail versions, oldest first
+    pub const RELEASES: [EngineVersion; 31] = [
+        EngineVersion::VER_UE4_0,
+        EngineVersion::VER_UE4_1,
+        EngineVersion::VER_UE4_2,
+        EngineVersion::VER_UE4_3,
+        EngineVersion::VER_UE4_4,
+        EngineVersion::VER_UE4_5,
+        EngineVersion::VER_UE4_6,
+        EngineVersion::VER_UE4_7,
+        EngineVersion::VER_UE4_8,
+        EngineVersion::VER_UE4_9,
+        EngineVersion::VER_UE4_10,
+        EngineVersion::VER_UE4_11,
+        EngineVersion::VER_UE4_12,
+        EngineVersion::VER_UE4_13,
+        EngineVersion::VER_UE4_14,
+        EngineVersion::VER_UE4_15,
+        EngineVersion::VER_UE4_16,
+        EngineVersion::VER_UE4_17,
+        EngineVersion::VER_UE4_18,
+        EngineVersion::VER_UE4_19,
+        EngineVersion::VER_UE4_20,
+        EngineVersion::VER_UE4_21,
+        EngineVersion::VER_UE4_22,
+        EngineVersion::VER_UE4_23,
+        EngineVersion::VER_UE4_24,
+        EngineVersion::VER_UE4_25,
+        EngineVersion::VER_UE4_26,
+        EngineVersion::VER_UE4_27,
+        EngineVersion::VER_UE5_0,
+        EngineVersion::VER_UE5_1,
+        EngineVersion::VER_UE5_2,
+    ];
+
+    /// Get object versions of this engine version, same as [`get_object_versions`]
+    pub fn to_object_versions(self) -> (ObjectVersion, ObjectVersionUE5) {
+        get_object_versions(self)
+    }
+
+    /// Check if this is a retail version
+    pub fn is_release(self) -> bool {
+        self.get_major_minor().is_some()
+    }
+
+    /// Get the major and minor version number, e.g. `(4, 27)`
+    ///
+    /// Returns `None` for versions which aren't a release.
+    pub fn get_major_minor(self) -> Option<(u32, u32)> {
+        let version = i32::from(self);
+        let ue4_0 = i32::from(EngineVersion::VER_UE4_0);
+        let ue5_0 = i32::from(EngineVersion::VER_UE5_0);
+        let ue5_2 = i32::from(EngineVersion::VER_UE5_2);
+
+        match version {
+            _ if version >= ue4_0 && version < ue5_0 => Some((4, (version - ue4_0) as u32)),
+            _ if version >= ue5_0 && version <= ue5_2 => Some((5, (version - ue5_0) as u32)),
+            _ => None,
+        }
+    }
+
+    /// Parse an engine version entered by a user
+    ///
+    /// Accepts version numbers like `4.27`, `UE4.27` or `5.1.1`, the patch version is ignored,
+    /// and variant names like `VER_UE4_27`. Returns `None` for unknown versions.
+    pub fn from_version_string(version: &str) -> Option<EngineVersion> {
+        let version = version.trim();
+
+        if let Some(release) = Self::RELEASES
+            .iter()
+            .find(|e| format!("{e:?}").eq_ignore_ascii_case(version))
+        {
+            return Some(*release);
+        }
+
+        let version = match version.get(..2) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("ue") => version[2..].trim_start(),
+            _ => version,
+        };
+
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse::<u32>().ok()?;
+        let minor = parts.next()?.parse::<u32>().ok()?;
+        if let Some(patch) = parts.next() {
+            patch.parse::<u32>().ok()?;
+        }
+        if parts.next().is_some() {
+            return None;
+        }
+
+        Self::RELEASES
+            .iter()
+            .find(|e| e.get_major_minor() == Some((major, minor)))
+            .copied()
+    }
+}
+
+impl fmt::Display for EngineVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.get_major_minor() {
+            Some((major, minor)) => write!(f, "{major}.{minor}"),
+            None => fmt::Debug::fmt(self, f),
+        }
+    }
+}
+
 lazy_static! {
     static ref OBJECT_VERSION_TO_ENGINE_VERSION: Vec<(ObjectVersion, EngineVersion)> = Vec::from([
         (
@@ -280,62 +389,185 @@ pub fn get_object_versions(engine_version: EngineVersion) -> (ObjectVersion, Obj
     (object_version, object_version_ue5)
 }
 
-/// Guess the engine version from the object versions and an array of custom versions
-pub fn guess_engine_version(
-    object_version: ObjectVersion,
-    object_version_ue5: ObjectVersionUE5,
-    custom_versions: &[CustomVersion],
-) -> EngineVersion {
-    // todo: this doesn't always reflect the version that was set from the set_engine_version
-    let possible_versions = get_possible_versions(object_version, object_version_ue5);
+/// Get the object versions of all retail versions, oldest first
+pub fn get_all_object_versions(
+) -> impl Iterator<Item = (EngineVersion, ObjectVersion, ObjectVersionUE5)> {
+    EngineVersion::RELEASES.into_iter().map(|engine_version| {
+        let (object_version, object_version_ue5) = get_object_versions(engine_version);
+        (engine_version, object_version, object_version_ue5)
+    })
+}
 
-    if possible_versions.is_empty() {
-        return EngineVersion::UNKNOWN;
+/// Get the oldest engine version which can load assets with an object version
+///
+/// Returns `None` if the object version is unknown or newer than any known engine version.
+pub fn get_minimum_engine_version(object_version: ObjectVersion) -> Option<EngineVersion> {
+    if object_version == ObjectVersion::UNKNOWN {
+        return None;
     }
 
-    if possible_versions.len() == 1 {
-        return possible_versions[0];
-    }
+    OBJECT_VERSION_TO_ENGINE_VERSION
+        .iter()
+        .find(|(version, _)| *version >= object_version)
+        .map(|(_, engine_version)| *engine_version)
+}
 
-    let mut min_introduced = EngineVersion::VER_UE4_OLDEST_LOADABLE_PACKAGE;
-    let mut max_introduced = EngineVersion::VER_UE4_AUTOMATIC_VERSION_PLUS_ONE;
+/// Get the oldest engine version which can load assets with a UE5 object version
+///
+/// Returns `None` if the object version is unknown or newer than any known engine version.
+pub fn get_minimum_engine_version_ue5(
+    object_version_ue5: ObjectVersionUE5,
+) -> Option<EngineVersion> {
+    if object_version_ue5 == ObjectVersionUE5::UNKNOWN {
+        return None;
+    }
 
-    for custom_version in custom_versions {
-        let current_min_introduced = custom_version
-            .get_engine_version_from_version_number(custom_version.version)
-            .unwrap_or(EngineVersion::UNKNOWN);
-        let current_max_introduced = custom_version
-            .get_engine_version_from_version_number(custom_version.version + 1)
-            .unwrap_or(EngineVersion::UNKNOWN);
+    OBJECT_VERSION_TO_ENGINE_VERSION_UE5
+        .iter()
+        .find(|(version, _)| *version >= object_version_ue5)
+        .map(|(_, engine_version)| *engine_version)
+}
 
-        if current_min_introduced != EngineVersion::UNKNOWN
-            && current_min_introduced > min_introduced
-        {
-            min_introduced = current_min_introduced;
-        }
+/// Evidence used when guessing an engine version
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EngineVersionEvidence {
+    /// Object version is used by the engine version
+    ObjectVersion(ObjectVersion),
+    /// UE5 object version is used by the engine version
+    ObjectVersionUE5(ObjectVersionUE5),
+    /// Custom version has the value the engine version was released with
+    CustomVersionExact(CustomVersion),
+    /// Custom version is newer than the engine version release but older than the next release,
+    /// this happens with hotfix releases and custom engine builds
+    CustomVersionBetweenReleases(CustomVersion),
+    /// Custom version doesn't match the engine version, contains the expected version number
+    CustomVersionMismatch(CustomVersion, i32),
+}
 
-        if current_max_introduced != EngineVersion::UNKNOWN
-            && current_max_introduced < max_introduced
-        {
-            max_introduced = current_max_introduced;
-        }
+impl EngineVersionEvidence {
+    /// Check if this evidence supports the engine version
+    pub fn is_supporting(&self) -> bool {
+        !matches!(self, EngineVersionEvidence::CustomVersionMismatch(..))
     }
+}
+
+/// Engine version candidate returned by [`guess_engine_versions`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineVersionCandidate {
+    /// Engine version
+    pub engine_version: EngineVersion,
+    /// Confidence from 0 to 1
+    pub confidence: f32,
+    /// Evidence used for this candidate
+    pub evidence: Vec<EngineVersionEvidence>,
+}
+
+/// Guess possible engine versions from the object versions and an array of custom versions
+///
+/// Every engine version using the object versions is a candidate, candidates are scored by how
+/// many of the known custom versions match the values they were released with.
+/// The returned candidates are sorted by confidence, most likely first.
+pub fn guess_engine_versions(
+    object_version: ObjectVersion,
+    object_version_ue5: ObjectVersionUE5,
+    custom_versions: &[CustomVersion],
+) -> Vec<EngineVersionCandidate> {
+    let ue5_version = OBJECT_VERSION_TO_ENGINE_VERSION_UE5
+        .iter()
+        .find(|(version, _)| *version == object_version_ue5)
+        .map(|(_, engine_version)| *engine_version);
 
-    let mut final_possible_versions = possible_versions
+    let mut candidates = OBJECT_VERSION_TO_ENGINE_VERSION
         .iter()
-        .filter(|e| **e >= min_introduced && **e < max_introduced)
-        .copied()
+        .filter(|(version, _)| *version == object_version)
+        .map(|(_, engine_version)| *engine_version)
+        .filter(|engine_version| match ue5_version {
+            Some(ue5_version) => *engine_version == ue5_version,
+            // ue5 object versions which aren't a release still rule out ue4
+            None if object_version_ue5 != ObjectVersionUE5::UNKNOWN => {
+                *engine_version >= EngineVersion::VER_UE5_0
+            }
+            None => true,
+        })
+        .map(|engine_version| {
+            let mut evidence = vec![EngineVersionEvidence::ObjectVersion(object_version)];
+            if ue5_version.is_some() {
+                evidence.push(EngineVersionEvidence::ObjectVersionUE5(object_version_ue5));
+            }
+
+            let mut support = 1.0;
+            let mut total = 1.0;
+            for custom_version in custom_versions {
+                let Some(custom_evidence) =
+                    get_custom_version_evidence(custom_version, engine_version)
+                else {
+                    continue;
+                };
+
+                total += 1.0;
+                support += match custom_evidence {
+                    EngineVersionEvidence::CustomVersionExact(_) => 1.0,
+                    EngineVersionEvidence::CustomVersionBetweenReleases(_) => 0.5,
+                    _ => 0.0,
+                };
+                evidence.push(custom_evidence);
+            }
+
+            EngineVersionCandidate {
+                engine_version,
+                confidence: support / total,
+                evidence,
+            }
+        })
         .collect::<Vec<_>>();
 
-    final_possible_versions.sort();
+    candidates.sort_by(|a, b| {
+        b.confidence
+            .total_cmp(&a.confidence)
+            .then(a.engine_version.cmp(&b.engine_version))
+    });
+    candidates
+}
+
+/// Compare a custom version against the value an engine version was released with
+fn get_custom_version_evidence(
+    custom_version: &CustomVersion,
+    engine_version: EngineVersion,
+) -> Option<EngineVersionEvidence> {
+    let expected = custom_version.get_version_number_from_engine_version(engine_version)?;
 
-    if final_possible_versions.is_empty() {
-        // there must be a special set of custom versions; we'll just ignore our intuitions and go with the object version alone
-        return possible_versions[0];
-    }
+    // value of the next release, versions after the latest release can be newer than any known value
+    let next_release = custom_version
+        .version_mappings
+        .iter()
+        .filter(|(version, _)| {
+            *version > engine_version && *version < EngineVersion::VER_UE4_AUTOMATIC_VERSION
+        })
+        .min_by_key(|(version, _)| *version)
+        .map(|(_, version_number)| *version_number)
+        .unwrap_or(i32::MAX);
 
-    final_possible_versions
+    Some(match custom_version.version {
+        version if version == expected => {
+            EngineVersionEvidence::CustomVersionExact(custom_version.clone())
+        }
+        version if version > expected && version < next_release => {
+            EngineVersionEvidence::CustomVersionBetweenReleases(custom_version.clone())
+        }
+        _ => EngineVersionEvidence::CustomVersionMismatch(custom_version.clone(), expected),
+    })
+}
+
+/// Guess the engine version from the object versions and an array of custom versions
+///
+/// Returns the most likely candidate of [`guess_engine_versions`].
+pub fn guess_engine_version(
+    object_version: ObjectVersion,
+    object_version_ue5: ObjectVersionUE5,
+    custom_versions: &[CustomVersion],
+) -> EngineVersion {
+    guess_engine_versions(object_version, object_version_ue5, custom_versions)
         .first()
-        .copied()
+        .map(|e| e.engine_version)
         .unwrap_or(EngineVersion::UNKNOWN)
 }
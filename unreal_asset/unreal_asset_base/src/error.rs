@@ -126,9 +126,21 @@ pub enum PropertyError {
     /// An unversioned property was found, but no unversioned header was provided
     #[error("Tried to read an unversioned property without an unversioned header")]
     NoUnversionedHeader,
+    /// The struct type of a `StructProperty` could not be determined
+    #[error("Cannot determine struct type for property {0} ancestry {1}")]
+    UnknownStructType(Box<str>, Box<str>),
     /// An unversioned property schema was not found
     #[error("Unversioned property schema for {0} at index {1} was not found")]
     NoSchema(Box<str>, usize),
+    /// Properties were nested deeper than allowed by `ParseOptions`
+    #[error("Property {0} ancestry {1} exceeds the maximum nesting depth of {2}")]
+    DepthLimitExceeded(Box<str>, Box<str>, usize),
+    /// An unversioned property was serialized more than once
+    #[error("Unversioned property {0} at index {1} is serialized more than once")]
+    DuplicateUnversionedProperty(Box<str>, u32),
+    /// An unversioned header doesn't match the properties it describes
+    #[error("Unversioned header mismatch: {0}")]
+    UnversionedHeaderMismatch(Box<str>),
     /// Other
     #[error("{0}")]
     Other(Box<str>),
@@ -154,6 +166,20 @@ impl PropertyError {
         )
     }
 
+    /// Create a `PropertyError` for a `StructProperty` for which the struct type could not be determined
+    pub fn unknown_struct_type(name: &str, ancestry: &Ancestry) -> Self {
+        PropertyError::UnknownStructType(
+            name.to_string().into_boxed_str(),
+            ancestry
+                .ancestry
+                .iter()
+                .map(|e| e.get_owned_content())
+                .collect::<Vec<_>>()
+                .join("/")
+                .into_boxed_str(),
+        )
+    }
+
     /// Create a `PropertyError` for an unversioned property that failed to deserialize because no mappings were loaded
     pub fn no_mappings() -> Self {
         PropertyError::NoMappings
@@ -188,6 +214,31 @@ impl PropertyError {
         PropertyError::NoSchema(name.into_boxed_str(), index)
     }
 
+    /// Create a `PropertyError` for a property that was nested deeper than allowed
+    pub fn depth_limit_exceeded(name: &str, ancestry: &Ancestry, limit: usize) -> Self {
+        PropertyError::DepthLimitExceeded(
+            name.to_string().into_boxed_str(),
+            ancestry
+                .ancestry
+                .iter()
+                .map(|e| e.get_owned_content())
+                .collect::<Vec<_>>()
+                .join("/")
+                .into_boxed_str(),
+            limit,
+        )
+    }
+
+    /// Create a `PropertyError` for an unversioned property that was serialized more than once
+    pub fn duplicate_unversioned_property(name: &str, index: u32) -> Self {
+        PropertyError::DuplicateUnversionedProperty(name.to_string().into_boxed_str(), index)
+    }
+
+    /// Create a `PropertyError` for an unversioned header that doesn't match its properties
+    pub fn unversioned_header_mismatch(msg: String) -> Self {
+        PropertyError::UnversionedHeaderMismatch(msg.into_boxed_str())
+    }
+
     /// Create a `PropertyError` for a field that was expected to have a value, but was None
     pub fn property_field_none(field_name: &str, expected: &str) -> Self {
         PropertyError::PropertyFieldNone(
@@ -329,6 +380,12 @@ pub enum Error {
     /// A package index is invalid
     #[error("{0}")]
     InvalidPackageIndex(Box<str>),
+    /// A limit set in `ParseOptions` was exceeded
+    #[error("{0} {1} exceeds the limit of {2}")]
+    LimitExceeded(Box<str>, u64, u64),
+    /// A header can't be patched in place because its layout changed
+    #[error("Header can't be patched in place: {0}")]
+    HeaderLayoutChanged(Box<str>),
     /// An enum value is invalid
     #[error("{0}")]
     InvalidEnumValue(Box<str>),
@@ -385,6 +442,16 @@ impl Error {
         Error::InvalidPackageIndex(msg.into_boxed_str())
     }
 
+    /// Create an `Error` when a limit set in `ParseOptions` was exceeded
+    pub fn limit_exceeded(what: &str, value: u64, limit: u64) -> Self {
+        Error::LimitExceeded(what.to_string().into_boxed_str(), value, limit)
+    }
+
+    /// Create an `Error` when a header can't be patched in place because its layout changed
+    pub fn header_layout_changed(msg: String) -> Self {
+        Error::HeaderLayoutChanged(msg.into_boxed_str())
+    }
+
     /// Create an `Error` when a part of the library is not implemented
     pub fn unimplemented(msg: String) -> Self {
         Error::Unimplemented(msg.into_boxed_str())
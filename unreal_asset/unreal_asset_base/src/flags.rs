@@ -66,7 +66,7 @@ bitflags! {
         /// Object will be loaded
         const RF_WILL_BE_LOADED = 0x08000000;
         /// Object has an external package
-        const RF_HAS_EXTERNAL_PACKAGE = 0x1000000;
+        const RF_HAS_EXTERNAL_PACKAGE = 0x10000000;
     }
 
     /// Package flags
@@ -472,6 +472,110 @@ bitflags! {
         /// Flags that are always computed; never loaded or done with code generation
         const COMPUTED_FLAGS = Self::NET_DELTA_SERIALIZE_NATIVE.bits() | Self::NET_SERIALIZE_NATIVE.bits() | Self::SERIALIZE_NATIVE.bits() | Self::POST_SERIALIZE_NATIVE.bits() | Self::COPY_NATIVE.bits() | Self::IS_PLAIN_OLD_DATA.bits() | Self::NO_DESTRUCTOR.bits() | Self::ZERO_CONSTRUCTOR.bits() | Self::IDENTICAL_NATIVE.bits() | Self::ADD_STRUCT_REFERENCED_OBJECTS.bits() | Self::EXPORT_TEXT_ITEM_NATIVE.bits() | Self::IMPORT_TEXT_ITEM_NATIVE.bits() | Self::SERIALIZE_FROM_MISMATCHED_TAG.bits() | Self::POST_SCRIPT_CONSTRUCT.bits() | Self::NET_SHARED_SERIALIZATION.bits();
     }
+
+    /// Data stripping flags
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct EStripDataFlags: u8 {
+        /// No data was stripped
+        const NONE = 0;
+
+        /// Editor-only data was stripped
+        const EDITOR = 1;
+
+        /// Server-only data was stripped
+        const SERVER = 2;
+
+        /// All data was stripped
+        const ALL = 0xff;
+    }
+}
+
+impl EObjectFlags {
+    /// Flags of the main object of an asset, used by the editor when creating new assets
+    pub const ASSET_FLAGS: EObjectFlags = EObjectFlags::RF_PUBLIC
+        .union(EObjectFlags::RF_STANDALONE)
+        .union(EObjectFlags::RF_TRANSACTIONAL);
+
+    /// Flags which are saved to a package, all other flags only exist at runtime
+    pub const LOAD_FLAGS: EObjectFlags = EObjectFlags::RF_PUBLIC
+        .union(EObjectFlags::RF_STANDALONE)
+        .union(EObjectFlags::RF_TRANSACTIONAL)
+        .union(EObjectFlags::RF_CLASS_DEFAULT_OBJECT)
+        .union(EObjectFlags::RF_ARCHETYPE_OBJECT)
+        .union(EObjectFlags::RF_DEFAULT_SUB_OBJECT)
+        .union(EObjectFlags::RF_TEXT_EXPORT_TRANSIENT)
+        .union(EObjectFlags::RF_INHERITABLE_COMPONENT_TEMPLATE)
+        .union(EObjectFlags::RF_DUPLICATE_TRANSIENT)
+        .union(EObjectFlags::RF_NON_P_I_E_DUPLICATE_TRANSIENT);
+}
+
+impl EPackageFlags {
+    /// Flags of cooked packages, cooking always filters out editor-only data
+    pub const COOKED_FLAGS: EPackageFlags =
+        EPackageFlags::PKG_COOKED.union(EPackageFlags::PKG_FILTER_EDITOR_ONLY);
+
+    /// Check if the package is cooked
+    pub fn is_cooked(&self) -> bool {
+        self.contains(EPackageFlags::PKG_COOKED)
+    }
+
+    /// Check if the package has its editor-only data filtered out
+    pub fn is_filter_editor_only(&self) -> bool {
+        self.contains(EPackageFlags::PKG_FILTER_EDITOR_ONLY)
+    }
+
+    /// Check if the package contains editor-only data
+    pub fn has_editor_only_data(&self) -> bool {
+        !self.is_filter_editor_only()
+    }
+
+    /// Check if the package uses unversioned property serialization
+    pub fn uses_unversioned_properties(&self) -> bool {
+        self.contains(EPackageFlags::PKG_UNVERSIONED_PROPERTIES)
+    }
+
+    /// Check if the package contains a level or world
+    pub fn contains_map(&self) -> bool {
+        self.contains(EPackageFlags::PKG_CONTAINS_MAP)
+    }
+
+    /// Check if the package is an editor-only package
+    pub fn is_editor_only(&self) -> bool {
+        self.contains(EPackageFlags::PKG_EDITOR_ONLY)
+    }
+
+    /// Mark the package as cooked or uncooked
+    ///
+    /// Cooked packages have their editor-only data filtered out,
+    /// uncooked packages don't use unversioned properties.
+    pub fn set_cooked(&mut self, value: bool) {
+        match value {
+            true => self.insert(EPackageFlags::COOKED_FLAGS),
+            false => {
+                self.remove(EPackageFlags::PKG_COOKED | EPackageFlags::PKG_UNVERSIONED_PROPERTIES)
+            }
+        }
+    }
+
+    /// Set if the package has its editor-only data filtered out
+    ///
+    /// Packages with editor-only data are uncooked, see [`EPackageFlags::set_cooked`].
+    pub fn set_filter_editor_only(&mut self, value: bool) {
+        if !value {
+            self.set_cooked(false);
+        }
+        self.set(EPackageFlags::PKG_FILTER_EDITOR_ONLY, value);
+    }
+
+    /// Set if the package uses unversioned property serialization
+    ///
+    /// Only cooked packages use unversioned properties, see [`EPackageFlags::set_cooked`].
+    pub fn set_unversioned_properties(&mut self, value: bool) {
+        if value {
+            self.set_cooked(true);
+        }
+        self.set(EPackageFlags::PKG_UNVERSIONED_PROPERTIES, value);
+    }
 }
 
 impl Default for EObjectFlags {
@@ -509,3 +613,9 @@ impl Default for EStructFlags {
         Self::NO_FLAGS
     }
 }
+
+impl Default for EStripDataFlags {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::FNameContainer;
 
-use crate::types::{FName, PackageIndex};
+use crate::types::{FName, PackageIndex, PackageIndexTrait};
 
 /// Import struct for an Asset
 ///
@@ -44,4 +44,84 @@ impl Import {
             optional,
         }
     }
+
+    /// Resolve the full object path of this import, e.g. `/Game/Items/Conveyor.Conveyor_C`
+    ///
+    /// Outers which can't be found in `imports` end the path.
+    pub fn resolved_path<T: ImportMapTrait + ?Sized>(&self, imports: &T) -> String {
+        let mut names = vec![self.object_name.get_owned_content()];
+
+        let mut outer_index = self.outer_index;
+        while let Some(outer) = imports.get_import_ref(outer_index) {
+            // guard against circular outers in malformed assets
+            if names.len() > imports.import_count() {
+                break;
+            }
+            names.push(outer.object_name.get_owned_content());
+            outer_index = outer.outer_index;
+        }
+
+        join_object_path(names.into_iter().rev())
+    }
+}
+
+/// Displays the import like an object reference, e.g. `/Script/Engine.BlueprintGeneratedClass'Conveyor_C'`
+///
+/// Only the object name is shown, the outers are needed to get the full object path, see [`Import::resolved_path`].
+impl std::fmt::Display for Import {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}'{}'",
+            self.class_package, self.class_name, self.object_name
+        )
+    }
+}
+
+/// Join object names into an object path, the first name should be the package name
+///
+/// Objects directly inside a package are separated with `.`, their subobjects with `:`,
+/// and everything nested deeper with `.` again, matching how the engine builds path names.
+pub fn join_object_path<I: IntoIterator<Item = S>, S: AsRef<str>>(names: I) -> String {
+    let mut path = String::new();
+    for (depth, name) in names.into_iter().enumerate() {
+        match depth {
+            0 => {}
+            2 => path.push(':'),
+            _ => path.push('.'),
+        }
+        path.push_str(name.as_ref());
+    }
+    path
+}
+
+/// Implemented by types which contain an import map
+pub trait ImportMapTrait {
+    /// Get a reference to an import by its `PackageIndex`
+    fn get_import_ref(&self, index: PackageIndex) -> Option<&Import>;
+    /// Get the amount of imports
+    fn import_count(&self) -> usize;
+}
+
+impl ImportMapTrait for [Import] {
+    fn get_import_ref(&self, index: PackageIndex) -> Option<&Import> {
+        match index.is_import() {
+            true => self.get((-index.index - 1) as usize),
+            false => None,
+        }
+    }
+
+    fn import_count(&self) -> usize {
+        self.len()
+    }
+}
+
+impl ImportMapTrait for Vec<Import> {
+    fn get_import_ref(&self, index: PackageIndex) -> Option<&Import> {
+        self.as_slice().get_import_ref(index)
+    }
+
+    fn import_count(&self) -> usize {
+        self.len()
+    }
 }
@@ -3,6 +3,8 @@
 
 //! unreal_asset crate base members
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
 pub mod compression;
 pub mod containers;
 pub mod crc;
@@ -18,6 +20,7 @@ pub mod object_version;
 pub mod reader;
 pub mod types;
 pub mod unversioned;
+pub mod warning;
 
 pub use unreal_asset_proc_macro::FNameContainer;
 pub use unreal_helpers::Guid;
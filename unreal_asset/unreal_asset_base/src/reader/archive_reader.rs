@@ -4,6 +4,7 @@ use std::collections::HashSet;
 use std::io::{self, Read};
 
 use byteorder::{ReadBytesExt, LE};
+use unreal_helpers::read_ext::decode_fstring;
 
 use crate::crc;
 use crate::custom_version::CustomVersion;
@@ -11,15 +12,20 @@ use crate::enums;
 use crate::enums::ECustomVersionSerializationFormat;
 use crate::error::{Error, FNameError};
 use crate::object_version::ObjectVersion;
-use crate::reader::ArchiveTrait;
+use crate::reader::{ArchiveTrait, GameQuirks};
 use crate::types::{FName, PackageIndexTrait, SerializedNameHeader};
 use crate::Guid;
 
 /// A trait that allows reading from an archive in an asset-specific way
 pub trait ArchiveReader<Index: PackageIndexTrait>: ArchiveTrait<Index> + Read {
     /// Read a `Guid` property
+    ///
+    /// Unversioned properties don't have a property tag, so nothing is read for them.
     fn read_property_guid(&mut self) -> Result<Option<Guid>, Error> {
-        if self.get_object_version() >= ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG {
+        if self.get_object_version() >= ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG
+            && !self.has_unversioned_properties()
+            && !self.get_quirks().contains(GameQuirks::NO_PROPERTY_GUIDS)
+        {
             let has_property_guid = self.read_bool()?;
             if has_property_guid {
                 return Ok(Some(self.read_guid()?));
@@ -27,6 +33,32 @@ pub trait ArchiveReader<Index: PackageIndexTrait>: ArchiveTrait<Index> + Read {
         }
         Ok(None)
     }
+    /// Check an array length read from this archive against [`crate::reader::ParseOptions::max_array_len`]
+    ///
+    /// Negative lengths are rejected as well, this should be used before allocating anything
+    /// based on a length read from the archive.
+    fn check_array_len<T: TryInto<i64>>(&self, length: T) -> Result<usize, Error> {
+        let length: i64 = length
+            .try_into()
+            .map_err(|_| Error::invalid_file("Array length out of range".to_string()))?;
+        if length < 0 {
+            return Err(Error::invalid_file(format!(
+                "Negative array length {length}"
+            )));
+        }
+
+        let limit = self.get_parse_options().max_array_len;
+        if length as u64 > limit as u64 {
+            return Err(Error::limit_exceeded(
+                "Array length",
+                length as u64,
+                limit as u64,
+            ));
+        }
+
+        Ok(length as usize)
+    }
+
     /// Read an `FName`
     fn read_fname(&mut self) -> Result<FName, Error> {
         let index = self.read_i32::<LE>()?;
@@ -110,6 +142,7 @@ pub trait ArchiveReader<Index: PackageIndexTrait>: ArchiveTrait<Index> + Read {
         .ok_or_else(|| Error::no_data("name_map_string is None".to_string()))?;
 
         let hash = match self.get_object_version() >= ObjectVersion::VER_UE4_NAME_HASHES_SERIALIZED
+            && !self.get_quirks().contains(GameQuirks::NO_NAME_HASHES)
             && !string.is_empty()
         {
             true => self.read_u32::<LE>()?,
@@ -126,49 +159,57 @@ pub trait ArchiveReader<Index: PackageIndexTrait>: ArchiveTrait<Index> + Read {
             return Ok((Vec::new(), 0));
         }
 
-        let _strings_length = self.read_u64::<LE>()?;
+        let strings_length = self.read_u64::<LE>()?;
         let hash_version = self.read_u64::<LE>()?;
 
-        let hashes = match hash_version {
-            hash if hash == enums::HASH_VERSION_CITYHASH64 => {
-                let mut hashes = Vec::with_capacity(num_strings as usize);
-                for _ in 0..num_strings {
-                    hashes.push(self.read_u64::<LE>()?); // cityhash64 of crc::to_lower_string
-                }
-                Ok(hashes)
-            }
-            _ => Err(Error::unimplemented(format!(
+        if hash_version != enums::HASH_VERSION_CITYHASH64 {
+            return Err(Error::unimplemented(format!(
                 "Unimplemented name batch algorithm: {}",
                 hash_version
-            ))),
-        }?;
+            )));
+        }
+
+        let num_strings = self.check_array_len(num_strings)?;
+
+        // cityhash64 of crc::to_lower_string
+        let mut hashes = Vec::with_capacity(num_strings);
+        for _ in 0..num_strings {
+            hashes.push(self.read_u64::<LE>()?);
+        }
 
-        let mut name_headers = Vec::with_capacity(num_strings as usize);
+        let mut name_headers = Vec::with_capacity(num_strings);
         for _ in 0..num_strings {
             name_headers.push(SerializedNameHeader::read(self)?);
         }
 
-        let mut name_batch = Vec::with_capacity(num_strings as usize);
+        // all strings are read with a single read and then decoded from the buffer
+        let mut string_data = vec![0u8; self.check_array_len(strings_length)?];
+        self.read_exact(&mut string_data)?;
 
+        let mut name_batch = Vec::with_capacity(num_strings);
+        let mut offset = 0;
         for name_header in name_headers {
-            name_batch.push(self.read_name_map_string(Some(name_header)).map(|e| e.0)?);
+            let len = match name_header.is_wide {
+                true => name_header.len as usize * 2,
+                false => name_header.len as usize,
+            };
+
+            let data = string_data.get(offset..offset + len).ok_or_else(|| {
+                Error::invalid_file(format!(
+                    "Name batch string at offset {offset} exceeds strings length {strings_length}"
+                ))
+            })?;
+            offset += len;
+
+            name_batch.push(decode_fstring(data, name_header.is_wide)?);
         }
 
         if verify_hashes {
-            for (i, entry) in name_batch.iter().enumerate() {
-                let hash = match hash_version {
-                    hash if hash == enums::HASH_VERSION_CITYHASH64 => {
-                        Ok(crc::cityhash64_to_lower(entry))
-                    }
-                    _ => Err(Error::unimplemented(format!(
-                        "Unimplemented name batch algorithm: {}",
-                        hash_version
-                    ))),
-                }?;
-
-                if hash != hashes[i] {
+            let computed_hashes = crc::cityhash64_to_lower_batch(&name_batch);
+            for ((entry, hash), expected) in name_batch.iter().zip(computed_hashes).zip(hashes) {
+                if hash != expected {
                     return Err(Error::name_batch_hash_mismatch(
-                        hashes[i],
+                        expected,
                         hash,
                         entry.clone(),
                     ));
@@ -196,7 +237,7 @@ pub trait ArchiveReader<Index: PackageIndexTrait>: ArchiveTrait<Index> + Read {
         length: i32,
         getter: impl Fn(&mut Self) -> Result<T, Error>,
     ) -> Result<Vec<T>, Error> {
-        let mut array = Vec::with_capacity(length as usize);
+        let mut array = Vec::with_capacity(self.check_array_len(length)?);
         for _ in 0..length {
             array.push(getter(self)?);
         }
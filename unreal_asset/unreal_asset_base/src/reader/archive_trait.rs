@@ -7,6 +7,7 @@ use crate::containers::{IndexedMap, NameMap, SharedResource};
 use crate::custom_version::{CustomVersion, CustomVersionTrait};
 use crate::engine_version::EngineVersion;
 use crate::object_version::{ObjectVersion, ObjectVersionUE5};
+use crate::reader::{GameQuirks, ParseOptions};
 use crate::types::{FName, PackageIndex, PackageIndexTrait};
 use crate::unversioned::Usmap;
 
@@ -62,6 +63,21 @@ pub trait ArchiveTrait<Index: PackageIndexTrait>: Seek {
     /// Get if the archive uses the event driven loader
     fn use_event_driven_loader(&self) -> bool;
 
+    /// Get the game specific quirks used by this archive
+    fn get_quirks(&self) -> GameQuirks {
+        GameQuirks::NONE
+    }
+
+    /// Get the limits and settings used while reading this archive
+    fn get_parse_options(&self) -> ParseOptions {
+        ParseOptions::default()
+    }
+
+    /// Whether unversioned properties equal to the zero value of their type should be written zero-masked
+    fn zero_mask_defaults(&self) -> bool {
+        false
+    }
+
     /// Archive data length
     fn data_length(&mut self) -> io::Result<u64> {
         let current_position = self.position();
@@ -6,15 +6,20 @@ use byteorder::{WriteBytesExt, LE};
 
 use crate::error::{Error, FNameError};
 use crate::object_version::ObjectVersion;
-use crate::reader::ArchiveTrait;
+use crate::reader::{ArchiveTrait, GameQuirks};
 use crate::types::{FName, PackageIndexTrait};
 use crate::Guid;
 
 /// A trait that allows for writing to an archive in an asset-specific way
 pub trait ArchiveWriter<Index: PackageIndexTrait>: ArchiveTrait<Index> + Write {
     /// Write a `Guid` property
+    ///
+    /// Unversioned properties don't have a property tag, so nothing is written for them.
     fn write_property_guid(&mut self, guid: Option<&Guid>) -> Result<(), Error> {
-        if self.get_object_version() >= ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG {
+        if self.get_object_version() >= ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG
+            && !self.has_unversioned_properties()
+            && !self.get_quirks().contains(GameQuirks::NO_PROPERTY_GUIDS)
+        {
             self.write_bool(guid.is_some())?;
             if let Some(data) = guid {
                 self.write_guid(data)?;
@@ -10,8 +10,17 @@ pub use archive_trait::ArchiveType;
 pub mod archive_writer;
 pub use archive_writer::ArchiveWriter;
 
+pub mod parse_options;
+pub use parse_options::{ParseOptions, StructFallbackBehavior};
+
+pub mod quirks;
+pub use quirks::GameQuirks;
+
 pub mod raw_reader;
 pub use raw_reader::RawReader;
 
 pub mod raw_writer;
 pub use raw_writer::RawWriter;
+
+pub mod size_calculating_writer;
+pub use size_calculating_writer::{NullWriter, SizeCalculatingWriter};
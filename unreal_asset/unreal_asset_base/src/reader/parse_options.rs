@@ -0,0 +1,88 @@
+//! Limits enforced while parsing
+
+use crate::containers::chain::DEFAULT_READ_AHEAD_SIZE;
+use crate::reader::GameQuirks;
+
+/// Limits enforced by archive readers while parsing
+///
+/// Lengths read from a file are checked against these limits before anything gets allocated,
+/// so that corrupt or malicious files return an error instead of trying to allocate gigabytes of memory.
+///
+/// Also contains game specific layout information which can't be detected from the file itself
+/// and settings for how the file is read.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct ParseOptions {
+    /// Maximum amount of name map entries
+    pub max_name_count: usize,
+    /// Maximum amount of imports
+    pub max_import_count: usize,
+    /// Maximum amount of exports
+    pub max_export_count: usize,
+    /// Maximum serialized size of a single export in bytes
+    pub max_export_size: u64,
+    /// Maximum length of an array read from an archive
+    pub max_array_len: usize,
+    /// Maximum nesting depth of properties, e.g. structs inside of arrays inside of structs
+    pub max_property_depth: usize,
+    /// Alignment the game pads export data to, trailing zero bytes shorter than the alignment
+    /// are then read as padding instead of extras, so that it's moved when the export changes size
+    pub export_alignment: Option<u64>,
+    /// Size of the read-ahead buffer of the reader in bytes, 0 disables it
+    pub read_ahead_size: usize,
+    /// Deviations of the game from stock engine serialization
+    pub quirks: GameQuirks,
+    /// How structs are read whose struct type is neither serialized, overridden nor found in the mappings
+    pub struct_fallback: StructFallbackBehavior,
+}
+
+/// How to read a `StructProperty` with an unknown struct type
+///
+/// Struct types of structs in arrays, sets and maps are not always serialized.
+/// They are taken from the type overrides of the asset or the mappings if possible,
+/// this decides what happens when neither knows the type.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum StructFallbackBehavior {
+    /// Read the struct as tagged properties, which fails or misreads data for natively serialized structs
+    #[default]
+    Guess,
+    /// Keep the data of each struct as raw bytes if the size of the structs is known,
+    /// which is the case for structs in arrays and sets of fixed size structs, fail otherwise
+    RawPreserve,
+    /// Fail to read the property
+    Error,
+}
+
+impl ParseOptions {
+    /// Limits suitable for parsing untrusted files, e.g. when fuzzing
+    pub fn hardened() -> Self {
+        ParseOptions {
+            max_name_count: 1 << 16,
+            max_import_count: 1 << 16,
+            max_export_count: 1 << 16,
+            max_export_size: 1 << 28,
+            max_array_len: 1 << 16,
+            max_property_depth: 32,
+            export_alignment: None,
+            read_ahead_size: DEFAULT_READ_AHEAD_SIZE,
+            quirks: GameQuirks::NONE,
+            struct_fallback: StructFallbackBehavior::Guess,
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        ParseOptions {
+            max_name_count: 1 << 24,
+            max_import_count: 1 << 24,
+            max_export_count: 1 << 24,
+            max_export_size: 1 << 32,
+            max_array_len: 1 << 24,
+            max_property_depth: 128,
+            export_alignment: None,
+            read_ahead_size: DEFAULT_READ_AHEAD_SIZE,
+            quirks: GameQuirks::NONE,
+            struct_fallback: StructFallbackBehavior::Guess,
+        }
+    }
+}
@@ -0,0 +1,32 @@
+//! Game specific serialization quirks
+
+use bitflags::bitflags;
+
+bitflags! {
+    /// Deviations of a game from stock engine serialization
+    ///
+    /// Some games are built with modified engines which serialize assets differently.
+    /// Instead of forking the parser per game, these deviations are described here
+    /// and consulted by readers and writers wherever such a game differs from the stock engine.
+    ///
+    /// Quirks are set with [`ParseOptions::quirks`](crate::reader::ParseOptions::quirks) when reading
+    /// and are kept by the asset, so it's written the same way.
+    #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+    pub struct GameQuirks : u32
+    {
+        /// No quirks
+        const NONE = 0x00000000;
+        /// Name map entries don't have hashes, even though the object version says they do
+        const NO_NAME_HASHES = 0x00000001;
+        /// Property tags don't have a property guid, even though the object version says they do
+        const NO_PROPERTY_GUIDS = 0x00000002;
+        /// The package file summary has an extra `i32` after the licensee version
+        const SUMMARY_LICENSEE_FIELD = 0x00000004;
+    }
+}
+
+impl Default for GameQuirks {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
@@ -11,7 +11,7 @@ use crate::engine_version::{guess_engine_version, EngineVersion};
 use crate::object_version::{ObjectVersion, ObjectVersionUE5};
 use crate::reader::{
     archive_trait::{ArchiveTrait, ArchiveType},
-    ArchiveReader,
+    ArchiveReader, GameQuirks, ParseOptions,
 };
 use crate::types::{FName, PackageIndex, PackageIndexTrait, SerializedNameHeader};
 use crate::unversioned::Usmap;
@@ -30,6 +30,8 @@ pub struct RawReader<Index: PackageIndexTrait, C: Read + Seek> {
     pub use_event_driven_loader: bool,
     /// Name map
     pub name_map: SharedResource<NameMap>,
+    /// Parsing limits
+    pub parse_options: ParseOptions,
     /// Empty map
     empty_map: IndexedMap<String, String>,
 
@@ -52,10 +54,21 @@ impl<Index: PackageIndexTrait, C: Read + Seek> RawReader<Index, C> {
             object_version_ue5,
             use_event_driven_loader,
             name_map,
+            parse_options: ParseOptions::default(),
             empty_map: IndexedMap::new(),
             _marker: PhantomData,
         }
     }
+
+    /// Set the size of the read-ahead buffer of the reader cursor, a size of 0 disables it
+    pub fn set_read_ahead_size(&mut self, read_ahead_size: usize) {
+        self.cursor.set_read_ahead_size(read_ahead_size);
+    }
+
+    /// Replace the asset data read by the reader, see [`Chain::replace_first`]
+    pub fn replace_asset_data(&mut self, data: Vec<u8>) {
+        self.cursor.replace_first(data);
+    }
 }
 
 impl<Index: PackageIndexTrait, C: Read + Seek> ArchiveTrait<Index> for RawReader<Index, C> {
@@ -79,6 +92,14 @@ impl<Index: PackageIndexTrait, C: Read + Seek> ArchiveTrait<Index> for RawReader
         self.use_event_driven_loader
     }
 
+    fn get_quirks(&self) -> GameQuirks {
+        self.parse_options.quirks
+    }
+
+    fn get_parse_options(&self) -> ParseOptions {
+        self.parse_options
+    }
+
     fn position(&mut self) -> u64 {
         self.cursor.stream_position().unwrap_or_default()
     }
@@ -0,0 +1,72 @@
+//! Writers which don't store the written data
+
+use std::io::{self, Seek, SeekFrom, Write};
+
+/// Writer which discards all data and only keeps track of the position and length
+///
+/// Used for calculating the serialized size of something without allocating a buffer,
+/// e.g. as the cursor of a [`RawWriter`](crate::reader::RawWriter) when testing serializers.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SizeCalculatingWriter {
+    /// Current position
+    position: u64,
+    /// Length of the written data
+    len: u64,
+}
+
+/// Writer which discards all data
+///
+/// This is a [`SizeCalculatingWriter`], for when only checking that something can be serialized.
+pub type NullWriter = SizeCalculatingWriter;
+
+impl SizeCalculatingWriter {
+    /// Create a new `SizeCalculatingWriter` instance
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the length of the written data
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Check if no data was written
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get the current position
+    pub fn position(&self) -> u64 {
+        self.position
+    }
+}
+
+impl Write for SizeCalculatingWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.position += buf.len() as u64;
+        self.len = self.len.max(self.position);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Seek for SizeCalculatingWriter {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let new_position = match pos {
+            SeekFrom::Start(offset) => Some(offset),
+            SeekFrom::End(offset) => self.len.checked_add_signed(offset),
+            SeekFrom::Current(offset) => self.position.checked_add_signed(offset),
+        };
+
+        self.position = new_position.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative or overflowing position",
+            )
+        })?;
+        Ok(self.position)
+    }
+}
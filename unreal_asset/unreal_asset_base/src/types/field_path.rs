@@ -0,0 +1,98 @@
+//! Field paths
+
+use std::mem::size_of;
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+
+use crate::custom_version::{FFortniteMainBranchObjectVersion, FReleaseObjectVersion};
+use crate::error::Error;
+use crate::reader::{ArchiveReader, ArchiveTrait, ArchiveWriter};
+use crate::types::{FName, PackageIndex, PackageIndexTrait};
+use crate::FNameContainer;
+
+// silly `FNameContainer` fix
+mod unreal_asset_base {
+    pub use crate::types;
+}
+
+/// Path to an `FField`, e.g. a property of a blueprint class
+///
+/// The path is stored from the innermost field to the outermost one, the owner is the struct the outermost field belongs to.
+#[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPath {
+    /// Path
+    pub path: Vec<FName>,
+    /// Path owner
+    #[container_ignore]
+    pub resolved_owner: PackageIndex,
+}
+
+impl FieldPath {
+    /// Create a new `FieldPath` instance
+    pub fn new(path: Vec<FName>, resolved_owner: PackageIndex) -> Self {
+        FieldPath {
+            path,
+            resolved_owner,
+        }
+    }
+
+    /// Check if an archive serializes the owner of field paths
+    pub fn has_serialized_owner<Index: PackageIndexTrait>(
+        archive: &impl ArchiveTrait<Index>,
+    ) -> bool {
+        archive
+            .get_custom_version::<FReleaseObjectVersion>()
+            .version
+            >= FReleaseObjectVersion::FFieldPathOwnerSerialization as i32
+            || archive
+                .get_custom_version::<FFortniteMainBranchObjectVersion>()
+                .version
+                >= FFortniteMainBranchObjectVersion::FFieldPathOwnerSerialization as i32
+    }
+
+    /// Read a `FieldPath` from an asset
+    ///
+    /// The owner is only read if `with_owner` is set, it's left null otherwise.
+    pub fn read<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        asset: &mut Reader,
+        with_owner: bool,
+    ) -> Result<Self, Error> {
+        let length = asset.read_i32::<LE>()?;
+        let mut path = Vec::with_capacity(asset.check_array_len(length)?);
+        for _ in 0..length {
+            path.push(asset.read_fname()?);
+        }
+
+        let resolved_owner = match with_owner {
+            true => PackageIndex::new(asset.read_i32::<LE>()?),
+            false => PackageIndex::new(0),
+        };
+
+        Ok(FieldPath {
+            path,
+            resolved_owner,
+        })
+    }
+
+    /// Write a `FieldPath` to an asset
+    ///
+    /// Returns the amount of bytes written.
+    pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        asset: &mut Writer,
+        with_owner: bool,
+    ) -> Result<usize, Error> {
+        asset.write_i32::<LE>(self.path.len() as i32)?;
+        for name in &self.path {
+            asset.write_fname(name)?;
+        }
+
+        let mut size = size_of::<i32>() + size_of::<i32>() * 2 * self.path.len();
+        if with_owner {
+            asset.write_i32::<LE>(self.resolved_owner.index)?;
+            size += size_of::<i32>();
+        }
+
+        Ok(size)
+    }
+}
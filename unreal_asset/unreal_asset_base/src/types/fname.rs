@@ -70,6 +70,16 @@ impl std::fmt::Debug for FName {
     }
 }
 
+/// Displays the name like the engine, with the instance number appended as a suffix, e.g. `Foo_3`
+impl std::fmt::Display for FName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get_content(|name| match self.get_number() {
+            0 => f.write_str(name),
+            number => write!(f, "{}_{}", name, number - 1),
+        })
+    }
+}
+
 /// Get implementer serialized name
 pub trait ToSerializedName {
     /// Convert to serialized name
@@ -126,6 +136,33 @@ impl FName {
         FName::new_dummy(value.to_string(), 0)
     }
 
+    /// Split a numeric suffix off a name like the engine does when creating an `FName`
+    ///
+    /// Returns the name without the `_<number>` suffix and the instance number, which is the suffix + 1.
+    /// Names without a suffix are returned unchanged with an instance number of 0,
+    /// as are suffixes with leading zeros like `Foo_01`, since they can't be restored from a number.
+    pub fn split_number(name: &str) -> (&str, i32) {
+        let bytes = name.as_bytes();
+        let digits = bytes
+            .iter()
+            .rev()
+            .take_while(|e| e.is_ascii_digit())
+            .count();
+        if digits == 0 || digits >= bytes.len() || digits > 10 {
+            return (name, 0);
+        }
+
+        let base_len = bytes.len() - digits - 1;
+        if bytes[base_len] != b'_' || (digits > 1 && bytes[base_len + 1] == b'0') {
+            return (name, 0);
+        }
+
+        match name[base_len + 1..].parse::<i64>() {
+            Ok(number) if number < i32::MAX as i64 => (&name[..base_len], number as i32 + 1),
+            _ => (name, 0),
+        }
+    }
+
     /// Get access to this `FName`'s content
     pub fn get_content<T>(&self, func: impl FnOnce(&str) -> T) -> T {
         match self {
@@ -0,0 +1,368 @@
+//! Math helpers for vector, rotator, quat and transform types
+//!
+//! All helpers follow Unreal Engine conventions, rotators are stored as a [`Vector`]
+//! of pitch, yaw and roll in degrees, quaternions as a [`Vector4`] and matrices are row-major
+//! with translation in the last row.
+
+use std::f64::consts::PI;
+
+use ordered_float::OrderedFloat;
+
+use super::vector::{Transform, Vector, Vector4};
+
+/// Threshold used when converting a quaternion near gimbal lock to a rotator
+const SINGULARITY_THRESHOLD: f64 = 0.4999995;
+/// Tolerance used when checking for zero values
+const SMALL_NUMBER: f64 = 1e-8;
+
+type F64 = OrderedFloat<f64>;
+
+/// Clamp an angle in degrees to (-180, 180]
+fn normalize_axis(angle: f64) -> f64 {
+    let angle = angle % 360.0;
+    let angle = (angle + 360.0) % 360.0;
+    if angle > 180.0 {
+        angle - 360.0
+    } else {
+        angle
+    }
+}
+
+fn vector(x: f64, y: f64, z: f64) -> Vector<F64> {
+    Vector::new(OrderedFloat(x), OrderedFloat(y), OrderedFloat(z))
+}
+
+fn quat(x: f64, y: f64, z: f64, w: f64) -> Vector4<F64> {
+    Vector4::new(
+        OrderedFloat(x),
+        OrderedFloat(y),
+        OrderedFloat(z),
+        OrderedFloat(w),
+    )
+}
+
+impl Vector<F64> {
+    /// Add two vectors
+    pub fn add(&self, other: &Self) -> Self {
+        vector(
+            self.x.0 + other.x.0,
+            self.y.0 + other.y.0,
+            self.z.0 + other.z.0,
+        )
+    }
+
+    /// Subtract a vector from this one
+    pub fn sub(&self, other: &Self) -> Self {
+        vector(
+            self.x.0 - other.x.0,
+            self.y.0 - other.y.0,
+            self.z.0 - other.z.0,
+        )
+    }
+
+    /// Multiply two vectors component-wise
+    pub fn mul(&self, other: &Self) -> Self {
+        vector(
+            self.x.0 * other.x.0,
+            self.y.0 * other.y.0,
+            self.z.0 * other.z.0,
+        )
+    }
+
+    /// Multiply all components by a scalar
+    pub fn scale(&self, scale: f64) -> Self {
+        vector(self.x.0 * scale, self.y.0 * scale, self.z.0 * scale)
+    }
+
+    /// Dot product
+    pub fn dot(&self, other: &Self) -> f64 {
+        self.x.0 * other.x.0 + self.y.0 * other.y.0 + self.z.0 * other.z.0
+    }
+
+    /// Cross product
+    pub fn cross(&self, other: &Self) -> Self {
+        vector(
+            self.y.0 * other.z.0 - self.z.0 * other.y.0,
+            self.z.0 * other.x.0 - self.x.0 * other.z.0,
+            self.x.0 * other.y.0 - self.y.0 * other.x.0,
+        )
+    }
+
+    /// Length of this vector
+    pub fn length(&self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    /// Convert a rotator of pitch, yaw and roll in degrees to a quaternion
+    pub fn rotator_to_quat(&self) -> Vector4<F64> {
+        let half_to_rad = PI / 360.0;
+        let (sp, cp) = (self.x.0 * half_to_rad).sin_cos();
+        let (sy, cy) = (self.y.0 * half_to_rad).sin_cos();
+        let (sr, cr) = (self.z.0 * half_to_rad).sin_cos();
+
+        quat(
+            cr * sp * sy - sr * cp * cy,
+            -cr * sp * cy - sr * cp * sy,
+            cr * cp * sy - sr * sp * cy,
+            cr * cp * cy + sr * sp * sy,
+        )
+    }
+}
+
+impl Vector4<F64> {
+    /// Identity quaternion
+    pub fn identity_quat() -> Self {
+        quat(0.0, 0.0, 0.0, 1.0)
+    }
+
+    /// Multiply two quaternions, the result first applies `other` and then `self`
+    pub fn quat_mul(&self, other: &Self) -> Self {
+        let (ax, ay, az, aw) = (self.x.0, self.y.0, self.z.0, self.w.0);
+        let (bx, by, bz, bw) = (other.x.0, other.y.0, other.z.0, other.w.0);
+
+        quat(
+            aw * bx + ax * bw + ay * bz - az * by,
+            aw * by - ax * bz + ay * bw + az * bx,
+            aw * bz + ax * by - ay * bx + az * bw,
+            aw * bw - ax * bx - ay * by - az * bz,
+        )
+    }
+
+    /// Inverse of a unit quaternion
+    pub fn quat_inverse(&self) -> Self {
+        quat(-self.x.0, -self.y.0, -self.z.0, self.w.0)
+    }
+
+    /// Normalize a quaternion, returns the identity quaternion for zero-length quaternions
+    pub fn quat_normalized(&self) -> Self {
+        let length_squared =
+            self.x.0 * self.x.0 + self.y.0 * self.y.0 + self.z.0 * self.z.0 + self.w.0 * self.w.0;
+        if length_squared < SMALL_NUMBER {
+            return Self::identity_quat();
+        }
+
+        let scale = 1.0 / length_squared.sqrt();
+        quat(
+            self.x.0 * scale,
+            self.y.0 * scale,
+            self.z.0 * scale,
+            self.w.0 * scale,
+        )
+    }
+
+    /// Rotate a vector by this quaternion
+    pub fn quat_rotate_vector(&self, v: &Vector<F64>) -> Vector<F64> {
+        let q = Vector::new(self.x, self.y, self.z);
+        let t = q.cross(v).scale(2.0);
+        v.add(&t.scale(self.w.0)).add(&q.cross(&t))
+    }
+
+    /// Convert a quaternion to a rotator of pitch, yaw and roll in degrees
+    pub fn quat_to_rotator(&self) -> Vector<F64> {
+        let (x, y, z, w) = (self.x.0, self.y.0, self.z.0, self.w.0);
+        let rad_to_deg = 180.0 / PI;
+
+        let singularity_test = z * x - w * y;
+        let yaw_y = 2.0 * (w * z + x * y);
+        let yaw_x = 1.0 - 2.0 * (y * y + z * z);
+        let yaw = yaw_y.atan2(yaw_x) * rad_to_deg;
+
+        if singularity_test < -SINGULARITY_THRESHOLD {
+            let roll = normalize_axis(-yaw - 2.0 * x.atan2(w) * rad_to_deg);
+            vector(-90.0, yaw, roll)
+        } else if singularity_test > SINGULARITY_THRESHOLD {
+            let roll = normalize_axis(yaw - 2.0 * x.atan2(w) * rad_to_deg);
+            vector(90.0, yaw, roll)
+        } else {
+            let pitch = (2.0 * singularity_test).asin() * rad_to_deg;
+            let roll = (-2.0 * (w * x + y * z)).atan2(1.0 - 2.0 * (x * x + y * y)) * rad_to_deg;
+            vector(pitch, yaw, roll)
+        }
+    }
+}
+
+impl Transform<F64> {
+    /// Identity transform
+    pub fn identity() -> Self {
+        Transform::new(
+            Vector4::identity_quat(),
+            vector(0.0, 0.0, 0.0),
+            vector(1.0, 1.0, 1.0),
+        )
+    }
+
+    /// Transform a position by this transform, applying scale, rotation and translation in that order
+    pub fn transform_position(&self, position: &Vector<F64>) -> Vector<F64> {
+        self.rotation
+            .quat_rotate_vector(&position.mul(&self.scale))
+            .add(&self.translation)
+    }
+
+    /// Compose two transforms, the result first applies `self` and then `other`
+    ///
+    /// This matches `FTransform::operator*`, e.g. composing a relative transform
+    /// with its parent's transform results in the world transform.
+    pub fn compose(&self, other: &Self) -> Self {
+        Transform::new(
+            other.rotation.quat_mul(&self.rotation),
+            other.transform_position(&self.translation),
+            self.scale.mul(&other.scale),
+        )
+    }
+
+    /// Inverse of this transform
+    ///
+    /// Like in the engine, this is only exact for uniform scale. Scale components which are zero stay zero.
+    pub fn inverse(&self) -> Self {
+        let reciprocal = |e: F64| match e.0.abs() < SMALL_NUMBER {
+            true => 0.0,
+            false => 1.0 / e.0,
+        };
+        let inverse_scale = vector(
+            reciprocal(self.scale.x),
+            reciprocal(self.scale.y),
+            reciprocal(self.scale.z),
+        );
+        let inverse_rotation = self.rotation.quat_inverse();
+        let inverse_translation = inverse_rotation
+            .quat_rotate_vector(&inverse_scale.mul(&self.translation))
+            .scale(-1.0);
+
+        Transform::new(inverse_rotation, inverse_translation, inverse_scale)
+    }
+
+    /// Convert this transform to a matrix, including scale
+    pub fn to_matrix(&self) -> Matrix<F64> {
+        let (x, y, z, w) = (
+            self.rotation.x.0,
+            self.rotation.y.0,
+            self.rotation.z.0,
+            self.rotation.w.0,
+        );
+        let (sx, sy, sz) = (self.scale.x.0, self.scale.y.0, self.scale.z.0);
+
+        let (x2, y2, z2) = (x + x, y + y, z + z);
+        let (xx2, yy2, zz2) = (x * x2, y * y2, z * z2);
+        let (yz2, wx2) = (y * z2, w * x2);
+        let (xy2, wz2) = (x * y2, w * z2);
+        let (xz2, wy2) = (x * z2, w * y2);
+
+        Matrix::from_f64([
+            [
+                (1.0 - (yy2 + zz2)) * sx,
+                (xy2 + wz2) * sx,
+                (xz2 - wy2) * sx,
+                0.0,
+            ],
+            [
+                (xy2 - wz2) * sy,
+                (1.0 - (xx2 + zz2)) * sy,
+                (yz2 + wx2) * sy,
+                0.0,
+            ],
+            [
+                (xz2 + wy2) * sz,
+                (yz2 - wx2) * sz,
+                (1.0 - (xx2 + yy2)) * sz,
+                0.0,
+            ],
+            [
+                self.translation.x.0,
+                self.translation.y.0,
+                self.translation.z.0,
+                1.0,
+            ],
+        ])
+    }
+}
+
+/// 4x4 row-major matrix
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Matrix<T> {
+    /// Matrix rows
+    pub m: [[T; 4]; 4],
+}
+
+impl<T> Matrix<T> {
+    /// Create a new `Matrix` instance
+    pub fn new(m: [[T; 4]; 4]) -> Self {
+        Matrix { m }
+    }
+}
+
+impl Matrix<F64> {
+    fn from_f64(m: [[f64; 4]; 4]) -> Self {
+        Matrix::new(m.map(|row| row.map(OrderedFloat)))
+    }
+
+    fn to_f64(&self) -> [[f64; 4]; 4] {
+        self.m.map(|row| row.map(|e| e.0))
+    }
+
+    /// Identity matrix
+    pub fn identity() -> Self {
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            row[i] = 1.0;
+        }
+        Matrix::from_f64(m)
+    }
+
+    /// Multiply two matrices, the result first applies `self` and then `other`
+    pub fn mul(&self, other: &Self) -> Self {
+        let (a, b) = (self.to_f64(), other.to_f64());
+
+        let mut m = [[0.0; 4]; 4];
+        for (i, row) in m.iter_mut().enumerate() {
+            for (j, e) in row.iter_mut().enumerate() {
+                *e = (0..4).map(|k| a[i][k] * b[k][j]).sum();
+            }
+        }
+        Matrix::from_f64(m)
+    }
+
+    /// Transform a position by this matrix
+    pub fn transform_position(&self, position: &Vector<F64>) -> Vector<F64> {
+        let m = self.to_f64();
+        let v = [position.x.0, position.y.0, position.z.0, 1.0];
+
+        let component = |j: usize| (0..4).map(|i| v[i] * m[i][j]).sum::<f64>();
+        vector(component(0), component(1), component(2))
+    }
+
+    /// Inverse of this matrix, `None` if the matrix is singular
+    pub fn inverse(&self) -> Option<Self> {
+        let mut a = self.to_f64();
+        let mut inverse = Matrix::identity().to_f64();
+
+        // gauss-jordan elimination with partial pivoting
+        for column in 0..4 {
+            let pivot = (column..4)
+                .max_by(|&i, &j| a[i][column].abs().total_cmp(&a[j][column].abs()))
+                .unwrap_or(column);
+            if a[pivot][column].abs() < SMALL_NUMBER {
+                return None;
+            }
+
+            a.swap(column, pivot);
+            inverse.swap(column, pivot);
+
+            let scale = 1.0 / a[column][column];
+            a[column].iter_mut().for_each(|e| *e *= scale);
+            inverse[column].iter_mut().for_each(|e| *e *= scale);
+
+            let (pivot_row, pivot_inverse) = (a[column], inverse[column]);
+            for row in (0..4).filter(|&row| row != column) {
+                let factor = a[row][column];
+                for (e, pivot) in a[row].iter_mut().zip(pivot_row) {
+                    *e -= factor * pivot;
+                }
+                for (e, pivot) in inverse[row].iter_mut().zip(pivot_inverse) {
+                    *e -= factor * pivot;
+                }
+            }
+        }
+
+        Some(Matrix::from_f64(inverse))
+    }
+}
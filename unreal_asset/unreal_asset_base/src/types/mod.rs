@@ -1,9 +1,16 @@
 //! Unreal types
 
+pub mod field_path;
 pub mod fname;
+pub mod packed_object_ref;
+pub mod strip_data_flags;
 use byteorder::{ReadBytesExt, WriteBytesExt};
+pub use field_path::FieldPath;
 pub use fname::FName;
+pub use packed_object_ref::PackedObjectRef;
+pub use strip_data_flags::StripDataFlags;
 
+pub mod math;
 pub mod movie;
 pub mod vector;
 
@@ -134,7 +141,7 @@ impl std::fmt::Display for PackageIndex {
 // }
 
 /// Asset generation info
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct GenerationInfo {
     /// Export count
     pub export_count: i32,
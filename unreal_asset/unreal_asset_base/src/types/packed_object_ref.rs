@@ -0,0 +1,105 @@
+//! Packed object references
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+
+use crate::error::Error;
+use crate::reader::{ArchiveReader, ArchiveWriter};
+use crate::types::PackageIndexTrait;
+
+const OBJECT_PATH_ID_SHIFT: u32 = 1;
+const OBJECT_PATH_ID_MASK: u64 = 0x00FF_FFFF;
+
+const DATA_CLASS_DESCRIPTOR_ID_SHIFT: u32 = 25;
+const DATA_CLASS_DESCRIPTOR_ID_MASK: u64 = 0xFF;
+
+const PACKAGE_ID_SHIFT: u32 = 33;
+const PACKAGE_ID_MASK: u64 = 0x7FFF_FFFF;
+
+/// Packed object reference, the serialized form of an unresolved `TObjectPtr` in >=UE5
+///
+/// A packed reference is 64 bits wide, the lowest bit is set for packed references,
+/// followed by a 24 bit object path id, an 8 bit data class descriptor id and a 31 bit package id.
+/// An encoded value of 0 is a null reference.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PackedObjectRef {
+    /// Encoded reference
+    pub encoded: u64,
+}
+
+impl PackedObjectRef {
+    /// Null reference
+    pub const NULL: PackedObjectRef = PackedObjectRef { encoded: 0 };
+
+    /// Create a new `PackedObjectRef` from its encoded value
+    pub fn new(encoded: u64) -> Self {
+        PackedObjectRef { encoded }
+    }
+
+    /// Pack an object reference
+    ///
+    /// Fails if the package id doesn't fit in 31 bits or the object path id doesn't fit in 24 bits.
+    pub fn pack(
+        package_id: u32,
+        object_path_id: u32,
+        data_class_descriptor_id: u8,
+    ) -> Result<Self, Error> {
+        if package_id as u64 > PACKAGE_ID_MASK {
+            return Err(Error::invalid_package_index(format!(
+                "Package id {package_id} doesn't fit in a packed object reference"
+            )));
+        }
+        if object_path_id as u64 > OBJECT_PATH_ID_MASK {
+            return Err(Error::invalid_package_index(format!(
+                "Object path id {object_path_id} doesn't fit in a packed object reference"
+            )));
+        }
+
+        Ok(PackedObjectRef {
+            encoded: ((package_id as u64) << PACKAGE_ID_SHIFT)
+                | ((data_class_descriptor_id as u64) << DATA_CLASS_DESCRIPTOR_ID_SHIFT)
+                | ((object_path_id as u64) << OBJECT_PATH_ID_SHIFT)
+                | 1,
+        })
+    }
+
+    /// Check if this is a null reference
+    pub fn is_null(&self) -> bool {
+        self.encoded == 0
+    }
+
+    /// Check if this reference is packed, references which aren't packed are resolved object pointers
+    pub fn is_packed(&self) -> bool {
+        self.encoded & 1 == 1
+    }
+
+    /// Get the id of the package of the referenced object
+    pub fn package_id(&self) -> u32 {
+        ((self.encoded >> PACKAGE_ID_SHIFT) & PACKAGE_ID_MASK) as u32
+    }
+
+    /// Get the id of the referenced object's path inside of its package
+    pub fn object_path_id(&self) -> u32 {
+        ((self.encoded >> OBJECT_PATH_ID_SHIFT) & OBJECT_PATH_ID_MASK) as u32
+    }
+
+    /// Get the id of the class descriptor of the referenced object
+    pub fn data_class_descriptor_id(&self) -> u8 {
+        ((self.encoded >> DATA_CLASS_DESCRIPTOR_ID_SHIFT) & DATA_CLASS_DESCRIPTOR_ID_MASK) as u8
+    }
+
+    /// Read a `PackedObjectRef` from an archive
+    pub fn read<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        asset: &mut Reader,
+    ) -> Result<Self, Error> {
+        Ok(PackedObjectRef::new(asset.read_u64::<LE>()?))
+    }
+
+    /// Write a `PackedObjectRef` to an archive
+    pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        asset: &mut Writer,
+    ) -> Result<(), Error> {
+        asset.write_u64::<LE>(self.encoded)?;
+        Ok(())
+    }
+}
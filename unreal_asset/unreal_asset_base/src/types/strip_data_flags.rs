@@ -0,0 +1,67 @@
+//! Data stripping flags
+
+use byteorder::{ReadBytesExt, WriteBytesExt};
+
+use crate::error::Error;
+use crate::flags::EStripDataFlags;
+use crate::reader::{ArchiveReader, ArchiveWriter};
+use crate::types::PackageIndexTrait;
+
+/// Flags describing which data was stripped when an object was saved
+///
+/// Global flags are shared by all objects, class flags have a meaning specific to the class which serialized them.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct StripDataFlags {
+    /// Global strip flags
+    pub global_strip_flags: EStripDataFlags,
+    /// Class-specific strip flags
+    pub class_strip_flags: u8,
+}
+
+impl StripDataFlags {
+    /// Create a new `StripDataFlags` instance
+    pub fn new(global_strip_flags: EStripDataFlags, class_strip_flags: u8) -> Self {
+        StripDataFlags {
+            global_strip_flags,
+            class_strip_flags,
+        }
+    }
+
+    /// Read `StripDataFlags` from an asset
+    pub fn read<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        asset: &mut Reader,
+    ) -> Result<Self, Error> {
+        let global_strip_flags = EStripDataFlags::from_bits_retain(asset.read_u8()?);
+        let class_strip_flags = asset.read_u8()?;
+
+        Ok(StripDataFlags {
+            global_strip_flags,
+            class_strip_flags,
+        })
+    }
+
+    /// Write `StripDataFlags` to an asset
+    pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        asset: &mut Writer,
+    ) -> Result<(), Error> {
+        asset.write_u8(self.global_strip_flags.bits())?;
+        asset.write_u8(self.class_strip_flags)?;
+        Ok(())
+    }
+
+    /// Check if editor-only data was stripped
+    pub fn is_editor_data_stripped(&self) -> bool {
+        self.global_strip_flags.contains(EStripDataFlags::EDITOR)
+    }
+
+    /// Check if server-only data was stripped
+    pub fn is_server_data_stripped(&self) -> bool {
+        self.global_strip_flags.contains(EStripDataFlags::SERVER)
+    }
+
+    /// Check if class-specific data was stripped
+    pub fn is_class_data_stripped(&self, flags: u8) -> bool {
+        self.class_strip_flags & flags != 0
+    }
+}
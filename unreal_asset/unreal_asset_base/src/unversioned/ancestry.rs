@@ -5,6 +5,21 @@ use crate::FNameContainer;
 use crate::types::FName;
 
 /// Unversioned properties ancestry
+///
+/// Ancestry is the chain of types a property is nested in, it is used for looking up
+/// property types in usmap mappings. The first element is the class of the export the property
+/// belongs to, every struct the property is nested in appends its struct type.
+///
+/// # Examples
+///
+/// Ancestry of a property inside of a `Vector` struct in an export of class `MyActor`
+/// ```
+/// # use unreal_asset_base as unreal_asset;
+/// use unreal_asset::unversioned::Ancestry;
+///
+/// let ancestry = Ancestry::from_parent_chain(["MyActor", "Vector"]);
+/// assert!(ancestry.get_parent().is_some_and(|parent| parent == "Vector"));
+/// ```
 #[derive(FNameContainer, Default, Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Ancestry {
     /// Ancestry array, last element is immediate parent
@@ -24,6 +39,18 @@ impl Ancestry {
         }
     }
 
+    /// Create a new `Ancestry` from a chain of parents, the last element is the immediate parent
+    pub fn from_parent_chain<I, S>(chain: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        chain
+            .into_iter()
+            .map(|parent| FName::from_slice(parent.as_ref()))
+            .collect()
+    }
+
     /// Gets immediate parent if one exists
     pub fn get_parent(&self) -> Option<&FName> {
         self.ancestry.last()
@@ -43,3 +70,11 @@ impl Ancestry {
         new_ancestry
     }
 }
+
+impl FromIterator<FName> for Ancestry {
+    fn from_iter<T: IntoIterator<Item = FName>>(iter: T) -> Self {
+        Ancestry {
+            ancestry: iter.into_iter().collect(),
+        }
+    }
+}
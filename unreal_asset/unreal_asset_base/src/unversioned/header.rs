@@ -1,10 +1,15 @@
 //! Unversioned .usmap header
 
+use std::fmt::{self, Write as _};
+
 use bitvec::prelude::*;
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use unreal_helpers::bitvec_ext::{read_bit_mask, write_bit_mask};
 
+use crate::error::PropertyError;
 use crate::reader::{ArchiveReader, ArchiveWriter};
 use crate::types::PackageIndexTrait;
+use crate::unversioned::Usmap;
 use crate::Error;
 
 /// Unversioned header fragment
@@ -16,7 +21,7 @@ pub struct UnversionedHeaderFragment {
     /// Number of subsequent property values stured
     pub value_num: u8,
     /// First element index of this fragment
-    pub first_num: u8,
+    pub first_num: u32,
     /// Is this the last header fragment?
     pub is_last: bool,
     /// Has zeros
@@ -41,14 +46,22 @@ impl From<u16> for UnversionedHeaderFragment {
 }
 
 impl UnversionedHeaderFragment {
+    /// Maximum amount of properties a fragment can skip or serialize
+    pub const MAX_NUM: u8 = i8::MAX as u8;
+
     const SKIP_NUM_MASK: u16 = 0x007fu16;
     const HAS_ZEROS_MASK: u16 = 0x0080u16;
     const VALUE_NUM_SHIFT: u16 = 9;
     const IS_LAST_MASK: u16 = 0x0100u16;
 
     /// Get last element index of this fragment
-    pub fn get_last_num(&self) -> u8 {
-        self.first_num + self.value_num - 1
+    ///
+    /// Fragments without values don't have a last element, `None` is returned for them.
+    pub fn get_last_num(&self) -> Option<u32> {
+        match self.value_num {
+            0 => None,
+            value_num => Some(self.first_num + value_num as u32 - 1),
+        }
     }
 
     /// Read an `UnversionedHeaderFragment` from an asset
@@ -83,6 +96,22 @@ impl UnversionedHeaderFragment {
     }
 }
 
+impl fmt::Display for UnversionedHeaderFragment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "skip {}, {} values", self.skip_num, self.value_num)?;
+        if let Some(last_num) = self.get_last_num() {
+            write!(f, " ({}..={})", self.first_num, last_num)?;
+        }
+        if self.has_zeros {
+            write!(f, ", has zeros")?;
+        }
+        if self.is_last {
+            write!(f, ", last")?;
+        }
+        Ok(())
+    }
+}
+
 /// List of serialized property indices and which of them are non-zero.
 /// Serialized as a stream of 16-bit skip-x keep-y fragments and a zero bitmask.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -102,29 +131,6 @@ pub struct UnversionedHeader {
 }
 
 impl UnversionedHeader {
-    /// Loads zero mask data from an asset
-    fn load_zero_mask_data<Reader: ArchiveReader<impl PackageIndexTrait>>(
-        asset: &mut Reader,
-        num_bits: u16,
-    ) -> Result<BitVec<u8, Lsb0>, Error> {
-        if num_bits <= 8 {
-            let mut data = [0u8; 1];
-            asset.read_exact(&mut data)?;
-            Ok(BitVec::from_vec(data.to_vec()))
-        } else if num_bits <= 16 {
-            let mut data = [0u8; 2];
-            asset.read_exact(&mut data)?;
-            Ok(BitVec::from_vec(data.to_vec()))
-        } else {
-            let num_bytes = ((num_bits + 31) / 32) * 4;
-            let mut data = Vec::with_capacity(num_bytes as usize);
-            for _ in 0..num_bytes {
-                data.push(asset.read_u8()?);
-            }
-            Ok(BitVec::from_vec(data.to_vec()))
-        }
-    }
-
     /// Read `UnversionedHeader` from an asset
     pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -141,8 +147,8 @@ impl UnversionedHeader {
 
         loop {
             let mut fragment = UnversionedHeaderFragment::read(asset)?;
-            fragment.first_num = first_num + fragment.skip_num;
-            first_num += fragment.skip_num + fragment.value_num;
+            fragment.first_num = first_num + fragment.skip_num as u32;
+            first_num = fragment.first_num + fragment.value_num as u32;
 
             fragments.push(fragment);
 
@@ -158,8 +164,8 @@ impl UnversionedHeader {
 
         let (zero_mask, has_non_zero_values) = match zero_mask_num > 0 {
             true => {
-                let mask = UnversionedHeader::load_zero_mask_data(asset, zero_mask_num)?;
-                let has_non_zero_values = unmasked_num > 0 || mask.iter().all(|e| !*e);
+                let mask = read_bit_mask(asset, zero_mask_num as usize)?;
+                let has_non_zero_values = unmasked_num > 0 || mask.iter().any(|e| !*e);
                 (mask, has_non_zero_values)
             }
             false => {
@@ -180,6 +186,153 @@ impl UnversionedHeader {
         }))
     }
 
+    /// Check if any properties are serialized
+    pub fn has_values(&self) -> bool {
+        self.fragments.iter().any(|e| e.value_num > 0)
+    }
+
+    /// Get the global indices of all serialized properties and whether they are zeroed
+    ///
+    /// Properties are returned in the order they are serialized in.
+    pub fn get_property_indices(&self) -> Vec<(u32, bool)> {
+        let mut indices = Vec::new();
+        let mut zero_mask_index = 0;
+
+        for fragment in &self.fragments {
+            for i in 0..fragment.value_num as u32 {
+                let is_zero = match fragment.has_zeros {
+                    true => {
+                        zero_mask_index += 1;
+                        self.zero_mask
+                            .get(zero_mask_index - 1)
+                            .as_deref()
+                            .copied()
+                            .unwrap_or(false)
+                    }
+                    false => false,
+                };
+                indices.push((fragment.first_num + i, is_zero));
+            }
+        }
+
+        indices
+    }
+
+    /// Validate the fragments of this header
+    ///
+    /// Checks that skip and value counts fit into a fragment, that only the last fragment
+    /// is marked as last and that the zero mask has a bit for every value of fragments with zeros.
+    pub fn validate_fragments(&self) -> Result<(), Error> {
+        if self.fragments.is_empty() {
+            return Err(PropertyError::unversioned_header_mismatch(String::from(
+                "header has no fragments",
+            ))
+            .into());
+        }
+
+        for (i, fragment) in self.fragments.iter().enumerate() {
+            if fragment.skip_num > UnversionedHeaderFragment::MAX_NUM
+                || fragment.value_num > UnversionedHeaderFragment::MAX_NUM
+            {
+                return Err(PropertyError::unversioned_header_mismatch(format!(
+                    "fragment {i} skips {} and serializes {} properties, the maximum is {}",
+                    fragment.skip_num,
+                    fragment.value_num,
+                    UnversionedHeaderFragment::MAX_NUM
+                ))
+                .into());
+            }
+
+            if fragment.is_last != (i == self.fragments.len() - 1) {
+                return Err(PropertyError::unversioned_header_mismatch(format!(
+                    "fragment {i} has an invalid last fragment flag"
+                ))
+                .into());
+            }
+        }
+
+        let masked_num = self
+            .fragments
+            .iter()
+            .filter(|e| e.has_zeros)
+            .map(|e| e.value_num as usize)
+            .sum::<usize>();
+        if self.zero_mask.len() != masked_num {
+            return Err(PropertyError::unversioned_header_mismatch(format!(
+                "zero mask has {} bits, expected {masked_num}",
+                self.zero_mask.len()
+            ))
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Validate this header against the mappings of a class
+    ///
+    /// In addition to [`UnversionedHeader::validate_fragments`], checks that
+    /// every serialized index exists in the schema of `class_name` or its super schemas.
+    pub fn validate(&self, mappings: &Usmap, class_name: &str) -> Result<(), Error> {
+        self.validate_fragments()?;
+
+        if mappings.schemas.get_by_key(class_name).is_none() {
+            return Err(PropertyError::no_schema(class_name.to_string(), 0).into());
+        }
+
+        for (index, _) in self.get_property_indices() {
+            if mappings
+                .get_property_by_global_index(class_name, index)
+                .is_none()
+            {
+                return Err(PropertyError::unversioned_header_mismatch(format!(
+                    "index {index} doesn't exist in the mappings of {class_name}"
+                ))
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create a human readable description of this header
+    ///
+    /// Lists every fragment and the indices it serializes. If mappings are provided,
+    /// indices are resolved to property names using the schema of `class_name`.
+    pub fn describe(&self, mappings: Option<&Usmap>, class_name: &str) -> String {
+        let mut description = String::new();
+
+        let indices = self.get_property_indices();
+        let mut indices = indices.iter();
+        for (i, fragment) in self.fragments.iter().enumerate() {
+            let _ = writeln!(description, "Fragment {i}: {fragment}");
+
+            for (index, is_zero) in indices.by_ref().take(fragment.value_num as usize) {
+                let name = match mappings
+                    .and_then(|e| e.get_property_by_global_index(class_name, *index))
+                {
+                    Some(property) if property.array_size > 1 => {
+                        format!("{}[{}]", property.name, property.array_index)
+                    }
+                    Some(property) => property.name.clone(),
+                    None => String::from("<unknown>"),
+                };
+                let zero = match is_zero {
+                    true => " (zero)",
+                    false => "",
+                };
+                let _ = writeln!(description, "  {index:>4} {name}{zero}");
+            }
+        }
+
+        let _ = writeln!(
+            description,
+            "Zero mask: {} bits, has non-zero values: {}",
+            self.zero_mask.len(),
+            self.has_non_zero_values
+        );
+        description
+    }
+
     /// Write `UnversionedHeader` to an asset
     pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         &self,
@@ -194,7 +347,7 @@ impl UnversionedHeader {
         }
 
         if !self.zero_mask.is_empty() {
-            asset.write_all(self.zero_mask.as_raw_slice())?;
+            write_bit_mask(asset, &self.zero_mask)?;
         }
 
         Ok(())
@@ -86,7 +86,7 @@ pub struct UsmapSchema {
     pub prop_count: u16,
     /// Module path
     pub module_path: Option<String>,
-    /// Properties
+    /// Properties, keyed by name and array index
     pub properties: IndexedMap<UsmapPropertyKey, UsmapProperty>,
 }
 
@@ -112,7 +112,7 @@ impl UsmapSchema {
                 property.schema_index += j as u16;
 
                 properties.insert(
-                    (property.name.clone(), property.schema_index as u32),
+                    (property.name.clone(), property.array_index as u32),
                     property,
                 );
             }
@@ -188,6 +188,25 @@ impl Usmap {
         properties
     }
 
+    /// Gets a usmap property by its "global" index in a schema
+    ///
+    /// Global indices continue into the super schemas, this is the index used by unversioned headers.
+    pub fn get_property_by_global_index(
+        &self,
+        schema_name: &str,
+        global_index: u32,
+    ) -> Option<&UsmapProperty> {
+        let mut schema = self.schemas.get_by_key(schema_name)?;
+        let mut index = global_index as usize;
+
+        while index >= schema.prop_count as usize {
+            index -= schema.prop_count as usize;
+            schema = self.schemas.get_by_key(&schema.super_type)?;
+        }
+
+        schema.properties.get_by_index(index)
+    }
+
     /// Gets usmap property and it's "global" index for a given proeprty name + ancestry with a duplication index
     pub fn get_property_with_duplication_index(
         &self,
@@ -15,6 +15,7 @@ use crate::{
     reader::{
         archive_reader::ArchiveReader,
         archive_trait::{ArchiveTrait, ArchiveType},
+        ParseOptions,
     },
     types::{FName, PackageIndex},
 };
@@ -85,6 +86,10 @@ impl<'parent_reader, 'asset, R: ArchiveReader<PackageIndex>> ArchiveTrait<Packag
         false
     }
 
+    fn get_parse_options(&self) -> ParseOptions {
+        self.parent_reader.get_parse_options()
+    }
+
     fn position(&mut self) -> u64 {
         self.parent_reader.position()
     }
@@ -0,0 +1,25 @@
+//! Warnings about problems which don't stop parsing
+
+use std::fmt::Display;
+
+use crate::Guid;
+
+/// Warning about a problem found while parsing, parsing continues but the result may be incomplete
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AssetWarning {
+    /// Custom version which isn't known, data depending on it may be parsed incorrectly.
+    /// Custom versions can be registered with [`CustomVersion::register`]
+    ///
+    /// [`CustomVersion::register`]: crate::custom_version::CustomVersion::register
+    UnknownCustomVersion(Guid, i32),
+}
+
+impl Display for AssetWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AssetWarning::UnknownCustomVersion(guid, version) => {
+                write!(f, "Unknown custom version {guid} with version {version}")
+            }
+        }
+    }
+}
@@ -98,6 +98,49 @@ impl<Index: PackageIndexTrait> BaseExport<Index> {
         }
         .unwrap_or_default()
     }
+
+    /// Check if this export can be referenced from other packages
+    pub fn is_public(&self) -> bool {
+        self.object_flags.contains(EObjectFlags::RF_PUBLIC)
+    }
+
+    /// Set if this export can be referenced from other packages
+    pub fn set_public(&mut self, public: bool) {
+        self.object_flags.set(EObjectFlags::RF_PUBLIC, public);
+    }
+
+    /// Check if this export is kept loaded even if it's not referenced, this is set for the main object of an asset
+    pub fn is_standalone(&self) -> bool {
+        self.object_flags.contains(EObjectFlags::RF_STANDALONE)
+    }
+
+    /// Set if this export is kept loaded even if it's not referenced
+    pub fn set_standalone(&mut self, standalone: bool) {
+        self.object_flags
+            .set(EObjectFlags::RF_STANDALONE, standalone);
+    }
+
+    /// Check if changes to this export are recorded by the editor's undo system
+    pub fn is_transactional(&self) -> bool {
+        self.object_flags.contains(EObjectFlags::RF_TRANSACTIONAL)
+    }
+
+    /// Set if changes to this export are recorded by the editor's undo system
+    pub fn set_transactional(&mut self, transactional: bool) {
+        self.object_flags
+            .set(EObjectFlags::RF_TRANSACTIONAL, transactional);
+    }
+
+    /// Check if this export is a class default object
+    pub fn is_class_default_object(&self) -> bool {
+        self.object_flags
+            .contains(EObjectFlags::RF_CLASS_DEFAULT_OBJECT)
+    }
+
+    /// Mark this export as the main object of an asset, see [`EObjectFlags::ASSET_FLAGS`]
+    pub fn set_asset_flags(&mut self) {
+        self.object_flags.insert(EObjectFlags::ASSET_FLAGS);
+    }
 }
 
 impl<Index: PackageIndexTrait> ExportNormalTrait<Index> for BaseExport<Index> {
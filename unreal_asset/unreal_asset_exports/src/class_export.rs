@@ -159,6 +159,32 @@ impl<Index: PackageIndexTrait> ClassExport<Index> {
         })
     }
 
+    /// Check if this class implements an interface
+    pub fn has_interface(&self, class: PackageIndex) -> bool {
+        self.interfaces.iter().any(|e| e.class == class)
+    }
+
+    /// Add an implemented interface
+    ///
+    /// Returns `false` if the class already implements the interface.
+    pub fn add_interface(&mut self, interface: SerializedInterfaceReference) -> bool {
+        if self.has_interface(interface.class) {
+            return false;
+        }
+
+        self.interfaces.push(interface);
+        true
+    }
+
+    /// Remove an implemented interface
+    pub fn remove_interface(
+        &mut self,
+        class: PackageIndex,
+    ) -> Option<SerializedInterfaceReference> {
+        let index = self.interfaces.iter().position(|e| e.class == class)?;
+        Some(self.interfaces.remove(index))
+    }
+
     /// Serialize a `ClassExport` interface
     fn serialize_interfaces<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         &self,
@@ -236,7 +262,8 @@ impl<Index: PackageIndexTrait> ExportTrait<Index> for ClassExport<Index> {
             true => 1,
             false => 0,
         })?;
-        asset.write_fname(&asset.get_name_map().get_mut().add_fname("None"))?;
+        let none = asset.get_name_map().get_mut().add_fname("None");
+        asset.write_fname(&none)?;
 
         if asset.get_object_version() >= ObjectVersion::VER_UE4_ADD_COOKED_TO_UCLASS {
             asset.write_i32::<LE>(
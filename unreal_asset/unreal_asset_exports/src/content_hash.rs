@@ -0,0 +1,183 @@
+//! Export content hashing
+
+use std::io::{self, Cursor, Seek, SeekFrom, Write};
+use std::marker::PhantomData;
+
+use byteorder::{WriteBytesExt, LE};
+use naive_cityhash::cityhash64;
+
+use unreal_asset_base::{
+    containers::{IndexedMap, NameMap, SharedResource},
+    custom_version::{CustomVersion, CustomVersionTrait},
+    engine_version::EngineVersion,
+    error::FNameError,
+    object_version::{ObjectVersion, ObjectVersionUE5},
+    reader::{ArchiveTrait, ArchiveType, ArchiveWriter, GameQuirks},
+    types::{FName, PackageIndex, PackageIndexTrait},
+    unversioned::Usmap,
+    Error, Guid,
+};
+use unreal_helpers::UnrealWriteExt;
+
+use crate::{Export, ExportNormalTrait, ExportTrait};
+
+/// Writer serializing an export the same way its asset would,
+/// except that `FName`s are written as their content instead of their name map index
+struct ContentHashWriter<'asset, Index: PackageIndexTrait, A: ArchiveTrait<Index>> {
+    /// Asset the export belongs to
+    asset: &'asset A,
+    /// Serialized export body
+    cursor: Cursor<Vec<u8>>,
+    /// Marker
+    _marker: PhantomData<Index>,
+}
+
+impl<'asset, Index: PackageIndexTrait, A: ArchiveTrait<Index>> ArchiveTrait<Index>
+    for ContentHashWriter<'asset, Index, A>
+{
+    fn get_archive_type(&self) -> ArchiveType {
+        self.asset.get_archive_type()
+    }
+
+    fn get_custom_version<T>(&self) -> CustomVersion
+    where
+        T: CustomVersionTrait + Into<i32>,
+    {
+        self.asset.get_custom_version::<T>()
+    }
+
+    fn has_unversioned_properties(&self) -> bool {
+        self.asset.has_unversioned_properties()
+    }
+
+    fn use_event_driven_loader(&self) -> bool {
+        self.asset.use_event_driven_loader()
+    }
+
+    fn get_quirks(&self) -> GameQuirks {
+        self.asset.get_quirks()
+    }
+
+    fn zero_mask_defaults(&self) -> bool {
+        self.asset.zero_mask_defaults()
+    }
+
+    fn position(&mut self) -> u64 {
+        self.cursor.position()
+    }
+
+    fn get_name_map(&self) -> SharedResource<NameMap> {
+        self.asset.get_name_map()
+    }
+
+    fn get_array_struct_type_override(&self) -> &IndexedMap<String, String> {
+        self.asset.get_array_struct_type_override()
+    }
+
+    fn get_map_key_override(&self) -> &IndexedMap<String, String> {
+        self.asset.get_map_key_override()
+    }
+
+    fn get_map_value_override(&self) -> &IndexedMap<String, String> {
+        self.asset.get_map_value_override()
+    }
+
+    fn get_engine_version(&self) -> EngineVersion {
+        self.asset.get_engine_version()
+    }
+
+    fn get_object_version(&self) -> ObjectVersion {
+        self.asset.get_object_version()
+    }
+
+    fn get_object_version_ue5(&self) -> ObjectVersionUE5 {
+        self.asset.get_object_version_ue5()
+    }
+
+    fn get_mappings(&self) -> Option<&Usmap> {
+        self.asset.get_mappings()
+    }
+
+    fn get_parent_class_export_name(&self) -> Option<FName> {
+        self.asset.get_parent_class_export_name()
+    }
+
+    fn get_object_name(&self, index: Index) -> Option<FName> {
+        self.asset.get_object_name(index)
+    }
+
+    fn get_object_name_packageindex(&self, index: PackageIndex) -> Option<FName> {
+        self.asset.get_object_name_packageindex(index)
+    }
+}
+
+impl<'asset, Index: PackageIndexTrait, A: ArchiveTrait<Index>> ArchiveWriter<Index>
+    for ContentHashWriter<'asset, Index, A>
+{
+    fn write_fname(&mut self, fname: &FName) -> Result<(), Error> {
+        match fname {
+            FName::Backed { number, .. } => {
+                fname.get_content(|content| ArchiveWriter::write_fstring(self, Some(content)))?;
+                self.write_i32::<LE>(*number)?;
+                Ok(())
+            }
+            FName::Dummy { value, number } => {
+                Err(FNameError::dummy_serialize(value, *number).into())
+            }
+        }
+    }
+
+    fn write_fstring(&mut self, value: Option<&str>) -> Result<usize, Error> {
+        Ok(self.cursor.write_fstring(value)?)
+    }
+
+    fn write_guid(&mut self, guid: &Guid) -> io::Result<()> {
+        self.cursor.write_guid(guid)
+    }
+
+    fn write_bool(&mut self, value: bool) -> io::Result<()> {
+        self.cursor.write_bool(value)
+    }
+}
+
+impl<'asset, Index: PackageIndexTrait, A: ArchiveTrait<Index>> Write
+    for ContentHashWriter<'asset, Index, A>
+{
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.cursor.write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.cursor.flush()
+    }
+}
+
+impl<'asset, Index: PackageIndexTrait, A: ArchiveTrait<Index>> Seek
+    for ContentHashWriter<'asset, Index, A>
+{
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.cursor.seek(pos)
+    }
+}
+
+impl<Index: PackageIndexTrait> Export<Index> {
+    /// Compute a hash of the serialized body of this export
+    ///
+    /// `FName`s are hashed by their content, so the hash doesn't depend on the name map
+    /// and can be compared between two versions of an asset to find exports which changed.
+    /// Package indices are hashed as they are, the export map entry is not part of the body.
+    pub fn content_hash<A: ArchiveTrait<Index>>(&self, asset: &A) -> Result<u64, Error> {
+        let mut writer = ContentHashWriter {
+            asset,
+            cursor: Cursor::new(Vec::new()),
+            _marker: PhantomData,
+        };
+
+        self.write(&mut writer)?;
+        if let Some(normal_export) = self.get_normal_export() {
+            writer.write_all(&normal_export.extras)?;
+        }
+
+        Ok(cityhash64(writer.cursor.get_ref()))
+    }
+}
@@ -0,0 +1,328 @@
+//! Curve table export
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+
+use unreal_asset_base::{
+    custom_version::FFortniteMainBranchObjectVersion,
+    reader::{ArchiveReader, ArchiveWriter},
+    types::{FName, PackageIndexTrait},
+    unversioned::Ancestry,
+    Error, FNameContainer,
+};
+use unreal_asset_properties::{
+    int_property::BytePropertyValue, rich_curve_key_property::RichCurveInterpMode,
+    struct_property::StructProperty, Property,
+};
+
+use crate::implement_get;
+use crate::ExportTrait;
+
+use crate::{BaseExport, NormalExport};
+
+/// Curve table mode
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash, IntoPrimitive, TryFromPrimitive)]
+#[repr(u8)]
+pub enum CurveTableMode {
+    /// Table has no curves
+    #[default]
+    Empty = 0,
+    /// Table contains `SimpleCurve`s
+    SimpleCurves = 1,
+    /// Table contains `RichCurve`s
+    RichCurves = 2,
+}
+
+impl CurveTableMode {
+    /// Get the struct type of curves stored in a table with this mode
+    pub fn get_struct_type(&self) -> FName {
+        match self {
+            CurveTableMode::SimpleCurves => FName::from_slice("SimpleCurve"),
+            _ => FName::from_slice("RichCurve"),
+        }
+    }
+}
+
+/// Curve table export
+#[derive(FNameContainer, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CurveTableExport<Index: PackageIndexTrait> {
+    /// Base normal export
+    pub normal_export: NormalExport<Index>,
+    /// Curve table mode
+    #[container_ignore]
+    pub curve_table_mode: CurveTableMode,
+    /// Curves, each row is a `RichCurve` or a `SimpleCurve` struct depending on the table mode
+    pub table: Vec<StructProperty>,
+}
+
+implement_get!(CurveTableExport);
+
+impl<Index: PackageIndexTrait> CurveTableExport<Index> {
+    /// Read a `CurveTableExport` from an asset
+    pub fn from_base<Reader: ArchiveReader<Index>>(
+        base: &BaseExport<Index>,
+        asset: &mut Reader,
+    ) -> Result<Self, Error> {
+        let normal_export = NormalExport::from_base(base, asset)?;
+
+        let num_rows = asset.read_i32::<LE>()?;
+
+        let curve_table_mode = match asset
+            .get_custom_version::<FFortniteMainBranchObjectVersion>()
+            .version
+            < FFortniteMainBranchObjectVersion::ShrinkCurveTableSize as i32
+        {
+            true => match num_rows > 0 {
+                true => CurveTableMode::RichCurves,
+                false => CurveTableMode::Empty,
+            },
+            false => CurveTableMode::try_from(asset.read_u8()?)?,
+        };
+
+        let mut table = Vec::with_capacity(asset.check_array_len(num_rows)?);
+
+        let ancestry = Ancestry::new(base.get_class_type_for_ancestry(asset));
+        let struct_type = curve_table_mode.get_struct_type();
+
+        for _ in 0..num_rows {
+            let row_name = asset.read_fname()?;
+
+            let curve = StructProperty::custom_header(
+                asset,
+                row_name,
+                ancestry.clone(),
+                1,
+                0,
+                Some(struct_type.clone()),
+                None,
+                None,
+            )?;
+
+            table.push(curve);
+        }
+
+        Ok(CurveTableExport {
+            normal_export,
+            curve_table_mode,
+            table,
+        })
+    }
+
+    /// Get a curve by its row name
+    pub fn get_curve(&self, name: &str) -> Option<&StructProperty> {
+        self.table.iter().find(|curve| curve.name == name)
+    }
+
+    /// Get a mutable reference to a curve by its row name
+    pub fn get_curve_mut(&mut self, name: &str) -> Option<&mut StructProperty> {
+        self.table.iter_mut().find(|curve| curve.name == name)
+    }
+
+    /// Add a curve to the table
+    ///
+    /// The curve's name is used as the row name, if a curve with the same name already exists
+    /// it gets replaced and the old curve is returned.
+    pub fn add_curve(&mut self, curve: StructProperty) -> Option<StructProperty> {
+        if self.curve_table_mode == CurveTableMode::Empty {
+            self.curve_table_mode = match curve
+                .struct_type
+                .as_ref()
+                .is_some_and(|struct_type| struct_type == "SimpleCurve")
+            {
+                true => CurveTableMode::SimpleCurves,
+                false => CurveTableMode::RichCurves,
+            };
+        }
+
+        match self
+            .table
+            .iter_mut()
+            .find(|existing| existing.name.eq_content(&curve.name))
+        {
+            Some(existing) => Some(std::mem::replace(existing, curve)),
+            None => {
+                self.table.push(curve);
+                None
+            }
+        }
+    }
+
+    /// Remove a curve from the table by its row name
+    pub fn remove_curve(&mut self, name: &str) -> Option<StructProperty> {
+        let index = self.table.iter().position(|curve| curve.name == name)?;
+        Some(self.table.remove(index))
+    }
+
+    /// Duplicate a curve under a new row name
+    ///
+    /// Returns a mutable reference to the new curve, or `None` if the source curve doesn't exist.
+    pub fn duplicate_curve(&mut self, name: &str, new_name: FName) -> Option<&mut StructProperty> {
+        let mut curve = self.get_curve(name)?.clone();
+        curve.name = new_name.clone();
+
+        self.add_curve(curve);
+        self.table
+            .iter_mut()
+            .find(|curve| curve.name.eq_content(&new_name))
+    }
+
+    /// Evaluate a curve at a given time
+    ///
+    /// Constant, linear and cubic interpolation are supported, tangent weights are ignored.
+    /// Times outside of the key range are clamped to the first or the last key.
+    /// Returns `None` if the curve doesn't exist or if it has no keys and no default value.
+    pub fn evaluate(&self, name: &str, time: f32) -> Option<f32> {
+        let curve = self.get_curve(name)?;
+        let keys = get_curve_keys(curve);
+
+        match keys.is_empty() {
+            true => curve.value.iter().find_map(|property| match property {
+                Property::FloatProperty(default_value) if default_value.name == "DefaultValue" => {
+                    Some(default_value.value.0)
+                }
+                _ => None,
+            }),
+            false => Some(evaluate_keys(&keys, time)),
+        }
+    }
+}
+
+/// Curve key used for evaluation
+struct CurveKey {
+    time: f32,
+    value: f32,
+    interp_mode: RichCurveInterpMode,
+    arrive_tangent: f32,
+    leave_tangent: f32,
+}
+
+/// Get the interpolation mode of a `SimpleCurve`, which applies to all of its keys
+fn get_simple_interp_mode(curve: &StructProperty) -> RichCurveInterpMode {
+    let interp_mode_from_name = |name: &FName| {
+        name.get_content(|name| match name.ends_with("Constant") {
+            true => RichCurveInterpMode::Constant,
+            false => RichCurveInterpMode::Linear,
+        })
+    };
+
+    curve
+        .value
+        .iter()
+        .find_map(|property| match property {
+            Property::ByteProperty(property) if property.name == "InterpMode" => {
+                match &property.value {
+                    BytePropertyValue::Byte(value) => {
+                        RichCurveInterpMode::try_from(*value as i8).ok()
+                    }
+                    BytePropertyValue::FName(name) => Some(interp_mode_from_name(name)),
+                }
+            }
+            Property::EnumProperty(property) if property.name == "InterpMode" => {
+                property.value.as_ref().map(interp_mode_from_name)
+            }
+            _ => None,
+        })
+        .unwrap_or(RichCurveInterpMode::Linear)
+}
+
+/// Get the keys of a `RichCurve` or a `SimpleCurve` sorted by time
+fn get_curve_keys(curve: &StructProperty) -> Vec<CurveKey> {
+    let simple_interp_mode = get_simple_interp_mode(curve);
+
+    let to_key = |property: &Property| match property {
+        Property::RichCurveKeyProperty(key) => Some(CurveKey {
+            time: key.time.0,
+            value: key.value.0,
+            interp_mode: key.interp_mode,
+            arrive_tangent: key.arrive_tangent.0,
+            leave_tangent: key.leave_tangent.0,
+        }),
+        Property::SimpleCurveKeyProperty(key) => Some(CurveKey {
+            time: key.time.0,
+            value: key.value.0,
+            interp_mode: simple_interp_mode,
+            arrive_tangent: 0.0,
+            leave_tangent: 0.0,
+        }),
+        _ => None,
+    };
+
+    let mut keys = curve
+        .value
+        .iter()
+        .filter_map(|property| match property {
+            Property::ArrayProperty(keys) if keys.name == "Keys" => Some(keys),
+            _ => None,
+        })
+        .flat_map(|keys| keys.value.iter())
+        .filter_map(|key| match key {
+            Property::StructProperty(key) => key.value.first().and_then(to_key),
+            key => to_key(key),
+        })
+        .collect::<Vec<_>>();
+
+    keys.sort_by(|a, b| a.time.total_cmp(&b.time));
+    keys
+}
+
+/// Evaluate sorted curve keys at a given time, `keys` must not be empty
+fn evaluate_keys(keys: &[CurveKey], time: f32) -> f32 {
+    let first = &keys[0];
+    let last = &keys[keys.len() - 1];
+
+    if time <= first.time {
+        return first.value;
+    }
+    if time >= last.time {
+        return last.value;
+    }
+
+    let index = keys.partition_point(|key| key.time <= time);
+    let (key1, key2) = (&keys[index - 1], &keys[index]);
+
+    let diff = key2.time - key1.time;
+    if diff <= 0.0 {
+        return key1.value;
+    }
+    let alpha = (time - key1.time) / diff;
+
+    match key1.interp_mode {
+        RichCurveInterpMode::Constant => key1.value,
+        RichCurveInterpMode::Cubic => {
+            let p0 = key1.value;
+            let p1 = key1.value + key1.leave_tangent * diff / 3.0;
+            let p2 = key2.value - key2.arrive_tangent * diff / 3.0;
+            let p3 = key2.value;
+
+            let inv_alpha = 1.0 - alpha;
+            p0 * inv_alpha.powi(3)
+                + 3.0 * p1 * inv_alpha.powi(2) * alpha
+                + 3.0 * p2 * inv_alpha * alpha.powi(2)
+                + p3 * alpha.powi(3)
+        }
+        _ => key1.value + (key2.value - key1.value) * alpha,
+    }
+}
+
+impl<Index: PackageIndexTrait> ExportTrait<Index> for CurveTableExport<Index> {
+    fn write<Writer: ArchiveWriter<Index>>(&self, asset: &mut Writer) -> Result<(), Error> {
+        self.normal_export.write(asset)?;
+
+        asset.write_i32::<LE>(self.table.len() as i32)?;
+        if asset
+            .get_custom_version::<FFortniteMainBranchObjectVersion>()
+            .version
+            >= FFortniteMainBranchObjectVersion::ShrinkCurveTableSize as i32
+        {
+            asset.write_u8(self.curve_table_mode.into())?;
+        }
+
+        let struct_type = self.curve_table_mode.get_struct_type();
+        for curve in &self.table {
+            asset.write_fname(&curve.name)?;
+            curve.write_with_type(asset, false, Some(struct_type.clone()))?;
+        }
+
+        Ok(())
+    }
+}
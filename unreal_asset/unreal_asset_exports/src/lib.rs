@@ -6,11 +6,13 @@
 use std::fmt::Debug;
 
 use unreal_asset_base::{reader::ArchiveWriter, types::PackageIndexTrait, Error, FNameContainer};
+use unreal_asset_properties::Property;
 
 pub mod properties;
 
 pub mod base_export;
 pub mod class_export;
+pub mod curve_table_export;
 pub mod data_table_export;
 pub mod enum_export;
 pub mod function_export;
@@ -23,11 +25,13 @@ pub mod struct_export;
 pub mod user_defined_struct_export;
 pub mod world_export;
 
+mod content_hash;
+
 pub use self::{
-    base_export::BaseExport, class_export::ClassExport, data_table_export::DataTableExport,
-    enum_export::EnumExport, function_export::FunctionExport, level_export::LevelExport,
-    normal_export::NormalExport, property_export::PropertyExport, raw_export::RawExport,
-    string_table_export::StringTableExport, struct_export::StructExport,
+    base_export::BaseExport, class_export::ClassExport, curve_table_export::CurveTableExport,
+    data_table_export::DataTableExport, enum_export::EnumExport, function_export::FunctionExport,
+    level_export::LevelExport, normal_export::NormalExport, property_export::PropertyExport,
+    raw_export::RawExport, string_table_export::StringTableExport, struct_export::StructExport,
     user_defined_struct_export::UserDefinedStructExport, world_export::WorldExport,
 };
 
@@ -86,8 +90,11 @@ pub trait ExportTrait<Index: PackageIndexTrait>: Debug + Clone + PartialEq + Eq
 }
 
 /// Export
+///
+/// New export types can be added in minor releases, matching on an `Export` needs a wildcard arm.
 #[derive(FNameContainer, Debug, Clone, PartialEq, Eq)]
 #[container_nobounds]
+#[non_exhaustive]
 pub enum Export<Index: PackageIndexTrait> {
     /// Base export
     BaseExport(BaseExport<Index>),
@@ -113,6 +120,8 @@ pub enum Export<Index: PackageIndexTrait> {
     FunctionExport(FunctionExport<Index>),
     /// Data table export
     DataTableExport(DataTableExport<Index>),
+    /// Curve table export
+    CurveTableExport(CurveTableExport<Index>),
     /// World export
     WorldExport(WorldExport<Index>),
 }
@@ -189,7 +198,42 @@ manual_dispatch! {
     UserDefinedStructExport,
     FunctionExport,
     DataTableExport,
+    CurveTableExport,
     WorldExport
 }
 
+impl<Index: PackageIndexTrait> Export<Index> {
+    /// Call `visitor` for all properties of this export and their child properties, depth-first
+    ///
+    /// Rows of data tables are visited after the properties of the export.
+    pub fn visit_properties(&self, visitor: &mut impl FnMut(&Property)) {
+        if let Some(normal_export) = self.get_normal_export() {
+            for property in &normal_export.properties {
+                property.visit(visitor);
+            }
+        }
+        if let Export::DataTableExport(data_table) = self {
+            for property in data_table.table.data.iter().flat_map(|e| &e.value) {
+                property.visit(visitor);
+            }
+        }
+    }
+
+    /// Call `visitor` for all properties of this export and their child properties mutably, depth-first
+    ///
+    /// Keys of map properties are not visited, see [`Property::visit_mut`].
+    pub fn visit_properties_mut(&mut self, visitor: &mut impl FnMut(&mut Property)) {
+        if let Some(normal_export) = self.get_normal_export_mut() {
+            for property in &mut normal_export.properties {
+                property.visit_mut(visitor);
+            }
+        }
+        if let Export::DataTableExport(data_table) = self {
+            for property in data_table.table.data.iter_mut().flat_map(|e| &mut e.value) {
+                property.visit_mut(visitor);
+            }
+        }
+    }
+}
+
 // todo: impl hash for export
@@ -20,6 +20,9 @@ pub struct NormalExport<Index: PackageIndexTrait> {
     pub base_export: BaseExport<Index>,
     /// Extra data
     pub extras: Vec<u8>,
+    /// Alignment the end of this export is padded to with zero bytes after the extras
+    #[container_ignore]
+    pub padding_alignment: Option<u64>,
     /// Properties
     pub properties: Vec<Property>,
 }
@@ -45,6 +48,14 @@ impl<Index: PackageIndexTrait> ExportBaseTrait<Index> for NormalExport<Index> {
 }
 
 impl<Index: PackageIndexTrait> NormalExport<Index> {
+    /// Get the amount of padding bytes written after this export if its extras end at `position`
+    pub fn get_padding_len(&self, position: u64) -> u64 {
+        match self.padding_alignment {
+            Some(alignment) if alignment > 1 => (alignment - position % alignment) % alignment,
+            _ => 0,
+        }
+    }
+
     /// Read a `NormalExport` from an asset
     pub fn from_base<Reader: ArchiveReader<Index>>(
         base: &BaseExport<Index>,
@@ -63,6 +74,7 @@ impl<Index: PackageIndexTrait> NormalExport<Index> {
         Ok(NormalExport {
             base_export: base.clone(),
             extras: Vec::new(),
+            padding_alignment: None,
 
             properties,
         })
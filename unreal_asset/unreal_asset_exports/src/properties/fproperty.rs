@@ -170,8 +170,12 @@ pub enum FProperty {
     FMulticastDelegateProperty,
     /// MulticastInlineDelegate
     FMulticastInlineDelegateProperty,
+    /// MulticastSparseDelegate
+    FMulticastSparseDelegateProperty,
     /// Interface
     FInterfaceProperty,
+    /// FieldPath
+    FFieldPathProperty,
     /// Map
     FMapProperty,
     /// Bool
@@ -206,7 +210,11 @@ impl FProperty {
                 "MulticastInlineDelegateProperty" => {
                     FMulticastInlineDelegateProperty::new(asset)?.into()
                 }
+                "MulticastSparseDelegateProperty" => {
+                    FMulticastSparseDelegateProperty::new(asset)?.into()
+                }
                 "InterfaceProperty" => FInterfaceProperty::new(asset)?.into(),
+                "FieldPathProperty" => FFieldPathProperty::new(asset)?.into(),
                 "MapProperty" => FMapProperty::new(asset)?.into(),
                 "BoolProperty" => FBoolProperty::new(asset)?.into(),
                 "ByteProperty" => FByteProperty::new(asset)?.into(),
@@ -224,12 +232,11 @@ impl FProperty {
         asset: &mut Writer,
     ) -> Result<(), Error> {
         let property_serialized_name = property.to_serialized_name();
-        asset.write_fname(
-            &asset
-                .get_name_map()
-                .get_mut()
-                .add_fname(&property_serialized_name),
-        )?;
+        let property_serialized_name = asset
+            .get_name_map()
+            .get_mut()
+            .add_fname(&property_serialized_name);
+        asset.write_fname(&property_serialized_name)?;
         property.write(asset)
     }
 }
@@ -249,7 +256,11 @@ impl ToSerializedName for FProperty {
             FProperty::FMulticastInlineDelegateProperty(_) => {
                 String::from("MulticastInlineDelegateProperty")
             }
+            FProperty::FMulticastSparseDelegateProperty(_) => {
+                String::from("MulticastSparseDelegateProperty")
+            }
             FProperty::FInterfaceProperty(_) => String::from("InterfaceProperty"),
+            FProperty::FFieldPathProperty(_) => String::from("FieldPathProperty"),
             FProperty::FMapProperty(_) => String::from("MapProperty"),
             FProperty::FBoolProperty(_) => String::from("BoolProperty"),
             FProperty::FByteProperty(_) => String::from("ByteProperty"),
@@ -407,6 +418,15 @@ impl FPropertyTrait for FEnumProperty {
     }
 }
 
+/// FieldPath FProperty
+#[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FFieldPathProperty {
+    /// Generic property
+    pub generic_property: FGenericProperty,
+    /// Name of the field class the path points to, e.g. `FloatProperty`
+    pub property_class: FName,
+}
+
 impl FBoolProperty {
     /// Read an `FBoolProperty` from an asset
     pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
@@ -448,6 +468,32 @@ impl FPropertyTrait for FBoolProperty {
     }
 }
 
+impl FFieldPathProperty {
+    /// Read an `FFieldPathProperty` from an asset
+    pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        asset: &mut Reader,
+    ) -> Result<Self, Error> {
+        let generic_property = FGenericProperty::new(asset)?;
+        let property_class = asset.read_fname()?;
+
+        Ok(FFieldPathProperty {
+            generic_property,
+            property_class,
+        })
+    }
+}
+
+impl FPropertyTrait for FFieldPathProperty {
+    fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        asset: &mut Writer,
+    ) -> Result<(), Error> {
+        self.generic_property.write(asset)?;
+        asset.write_fname(&self.property_class)?;
+        Ok(())
+    }
+}
+
 parse_simple_property_prop!(
     FArrayProperty,
     /// Inner property
@@ -505,6 +551,11 @@ parse_simple_property_index!(
     /// Signature function index
     signature_function
 );
+parse_simple_property_index!(
+    FMulticastSparseDelegateProperty,
+    /// Signature function index
+    signature_function
+);
 parse_simple_property_index!(
     FInterfaceProperty,
     /// Interface class index
@@ -124,6 +124,8 @@ pub enum UProperty {
     UMulticastDelegateProperty,
     /// MulticastInlineDelegate
     UMulticastInlineDelegateProperty,
+    /// MulticastSparseDelegate
+    UMulticastSparseDelegateProperty,
     /// Interface
     UInterfaceProperty,
     /// Map
@@ -181,6 +183,9 @@ impl UProperty {
                 "MulticastInlineDelegateProperty" => {
                     UMulticastInlineDelegateProperty::new(asset)?.into()
                 }
+                "MulticastSparseDelegateProperty" => {
+                    UMulticastSparseDelegateProperty::new(asset)?.into()
+                }
                 "InterfaceProperty" => UInterfaceProperty::new(asset)?.into(),
                 "MapProperty" => UMapProperty::new(asset)?.into(),
                 "ByteProperty" => UByteProperty::new(asset)?.into(),
@@ -426,6 +431,11 @@ parse_simple_property!(
     /// Signature function index
     signature_function
 );
+parse_simple_property!(
+    UMulticastSparseDelegateProperty,
+    /// Signature function index
+    signature_function
+);
 parse_simple_property!(
     UInterfaceProperty,
     /// Interface class index
@@ -4,13 +4,15 @@ use std::io::SeekFrom;
 
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 
+#[cfg(feature = "kismet")]
+use unreal_asset_base::engine_version::EngineVersion;
 use unreal_asset_base::{
     custom_version::FCoreObjectVersion,
-    engine_version::EngineVersion,
     reader::{ArchiveReader, ArchiveWriter},
     types::{PackageIndex, PackageIndexTrait},
     Error, FNameContainer,
 };
+#[cfg(feature = "kismet")]
 use unreal_asset_kismet::KismetExpression;
 
 use crate::implement_get;
@@ -35,10 +37,12 @@ pub struct StructExport<Index: PackageIndexTrait> {
     /// Loaded properties
     pub loaded_properties: Vec<FProperty>,
     /// Script bytecode, exists if bytecode deserialized successfully
+    #[cfg(feature = "kismet")]
     pub script_bytecode: Option<Vec<KismetExpression>>,
     /// Script bytecode size
     pub script_bytecode_size: i32,
     /// Script bytecode raw, exists if bytecode couldn't deserialize successfully
+    /// or the `kismet` feature is disabled
     pub script_bytecode_raw: Option<Vec<u8>>,
 }
 
@@ -56,7 +60,7 @@ impl<Index: PackageIndexTrait> StructExport<Index> {
         let super_struct = PackageIndex::new(asset.read_i32::<LE>()?);
 
         let num_index_entries = asset.read_i32::<LE>()?;
-        let mut children = Vec::with_capacity(num_index_entries as usize);
+        let mut children = Vec::with_capacity(asset.check_array_len(num_index_entries)?);
         for _i in 0..num_index_entries as usize {
             children.push(PackageIndex::new(asset.read_i32::<LE>()?));
         }
@@ -66,7 +70,7 @@ impl<Index: PackageIndexTrait> StructExport<Index> {
         {
             true => {
                 let num_props = asset.read_i32::<LE>()?;
-                let mut props = Vec::with_capacity(num_props as usize);
+                let mut props = Vec::with_capacity(asset.check_array_len(num_props)?);
                 for _i in 0..num_props as usize {
                     props.push(FProperty::new(asset)?);
                 }
@@ -79,15 +83,21 @@ impl<Index: PackageIndexTrait> StructExport<Index> {
         let script_storage_size = asset.read_i32::<LE>()?; // number of bytes in total
         let start_offset = asset.position();
 
-        let mut script_bytecode = None;
-        if asset.get_engine_version() >= EngineVersion::VER_UE4_16 {
-            script_bytecode =
-                StructExport::<Index>::read_bytecode(asset, start_offset, script_storage_size).ok();
-        }
-
-        let script_bytecode_raw = match &script_bytecode {
-            Some(_) => None,
-            None => {
+        #[cfg(feature = "kismet")]
+        let script_bytecode = match asset.get_engine_version() >= EngineVersion::VER_UE4_16 {
+            true => {
+                StructExport::<Index>::read_bytecode(asset, start_offset, script_storage_size).ok()
+            }
+            false => None,
+        };
+        #[cfg(feature = "kismet")]
+        let has_bytecode = script_bytecode.is_some();
+        #[cfg(not(feature = "kismet"))]
+        let has_bytecode = false;
+
+        let script_bytecode_raw = match has_bytecode {
+            true => None,
+            false => {
                 asset.seek(SeekFrom::Start(start_offset))?;
                 let mut data = vec![0u8; script_storage_size as usize];
                 asset.read_exact(&mut data)?;
@@ -102,6 +112,7 @@ impl<Index: PackageIndexTrait> StructExport<Index> {
             super_struct,
             children,
             loaded_properties,
+            #[cfg(feature = "kismet")]
             script_bytecode,
             script_bytecode_size,
             script_bytecode_raw,
@@ -109,6 +120,7 @@ impl<Index: PackageIndexTrait> StructExport<Index> {
     }
 
     /// Read kismet bytecode
+    #[cfg(feature = "kismet")]
     fn read_bytecode<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
         start_offset: u64,
@@ -120,6 +132,33 @@ impl<Index: PackageIndexTrait> StructExport<Index> {
         }
         Ok(code)
     }
+
+    /// Write kismet bytecode
+    #[cfg(feature = "kismet")]
+    fn write_bytecode<Writer: ArchiveWriter<Index>>(
+        asset: &mut Writer,
+        bytecode: &[KismetExpression],
+    ) -> Result<(), Error> {
+        let len_offset_1 = asset.position();
+        asset.write_i32::<LE>(0)?; // total iCode offset; will be filled after serialization
+        let len_offset_2 = asset.position();
+        asset.write_i32::<LE>(0)?; // size on disk; will be filled after serialization
+
+        let mut total_offset = 0;
+        let begin = asset.position();
+        for expression in bytecode {
+            total_offset += KismetExpression::write(expression, asset)?;
+        }
+        let end = asset.position();
+
+        let total_len = end - begin;
+        asset.seek(SeekFrom::Start(len_offset_1))?;
+        asset.write_i32::<LE>(total_offset as i32)?;
+        asset.seek(SeekFrom::Start(len_offset_2))?;
+        asset.write_i32::<LE>(total_len as i32)?;
+        asset.seek(SeekFrom::Start(end))?;
+        Ok(())
+    }
 }
 
 impl<Index: PackageIndexTrait> ExportTrait<Index> for StructExport<Index> {
@@ -143,34 +182,18 @@ impl<Index: PackageIndexTrait> ExportTrait<Index> for StructExport<Index> {
             }
         }
 
+        #[cfg(feature = "kismet")]
         if let Some(bytecode) = &self.script_bytecode {
-            let len_offset_1 = asset.position();
-            asset.write_i32::<LE>(0)?; // total iCode offset; will be filled after serialization
-            let len_offset_2 = asset.position();
-            asset.write_i32::<LE>(0)?; // size on disk; will be filled after serialization
-
-            let mut total_offset = 0;
-            let begin = asset.position();
-            for expression in bytecode {
-                total_offset += KismetExpression::write(expression, asset)?;
-            }
-            let end = asset.position();
-
-            let total_len = end - begin;
-            asset.seek(SeekFrom::Start(len_offset_1))?;
-            asset.write_i32::<LE>(total_offset as i32)?;
-            asset.seek(SeekFrom::Start(len_offset_2))?;
-            asset.write_i32::<LE>(total_len as i32)?;
-            asset.seek(SeekFrom::Start(end))?;
-        } else {
-            asset.write_i32::<LE>(self.script_bytecode_size)?;
-            let raw_bytecode = self.script_bytecode_raw.as_ref().ok_or_else(|| {
-                Error::no_data("script_bytecode and raw_bytecode are None".to_string())
-            })?;
-            asset.write_i32::<LE>(raw_bytecode.len() as i32)?;
-            asset.write_all(raw_bytecode)?;
+            return Self::write_bytecode(asset, bytecode);
         }
 
+        asset.write_i32::<LE>(self.script_bytecode_size)?;
+        let raw_bytecode = self.script_bytecode_raw.as_ref().ok_or_else(|| {
+            Error::no_data("script_bytecode and raw_bytecode are None".to_string())
+        })?;
+        asset.write_i32::<LE>(raw_bytecode.len() as i32)?;
+        asset.write_all(raw_bytecode)?;
+
         Ok(())
     }
 }
@@ -26,6 +26,8 @@ use unreal_asset_base::{
     Error,
 };
 
+pub use unreal_asset_base::types::FieldPath;
+
 /// Kismet expression token
 #[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, TryFromPrimitive, IntoPrimitive)]
 #[repr(u8)]
@@ -347,26 +349,6 @@ pub enum EBlueprintTextLiteralType {
     StringTableEntry,
 }
 
-/// Kismet field path
-#[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
-pub struct FieldPath {
-    /// Path
-    pub path: Vec<FName>,
-    /// Path owner
-    #[container_ignore]
-    pub resolved_owner: PackageIndex,
-}
-
-impl FieldPath {
-    /// Create a new `FieldPath` instance
-    pub fn new(path: Vec<FName>, resolved_owner: PackageIndex) -> Self {
-        FieldPath {
-            path,
-            resolved_owner,
-        }
-    }
-}
-
 /// Read a UTF-8 kismet string
 fn read_kismet_string<Reader: ArchiveReader<impl PackageIndexTrait>>(
     asset: &mut Reader,
@@ -761,15 +743,9 @@ impl KismetPropertyPointer {
         if asset.get_object_version()
             >= KismetPropertyPointer::XFER_PROP_POINTER_SWITCH_TO_SERIALIZING_AS_FIELD_PATH_VERSION
         {
-            let num_entries = asset.read_i32::<LE>()?;
-            let mut names = Vec::with_capacity(num_entries as usize);
-            for _i in 0..num_entries as usize {
-                names.push(asset.read_fname()?);
-            }
-            let owner = PackageIndex::new(asset.read_i32::<LE>()?);
-            Ok(KismetPropertyPointer::from_new(FieldPath::new(
-                names, owner,
-            )))
+            Ok(KismetPropertyPointer::from_new(FieldPath::read(
+                asset, true,
+            )?))
         } else {
             Ok(KismetPropertyPointer::from_old(PackageIndex::new(
                 asset.read_i32::<LE>()?,
@@ -790,11 +766,7 @@ impl KismetPropertyPointer {
                     "engine_version >= UE4_ADDED_PACKAGE_OWNER but new is None".to_string(),
                 )
             })?;
-            asset.write_i32::<LE>(new.path.len() as i32)?;
-            for entry in &new.path {
-                asset.write_fname(entry)?;
-            }
-            asset.write_i32::<LE>(new.resolved_owner.index)?;
+            new.write(asset, true)?;
         } else {
             asset.write_i32::<LE>(self.old.map(|e| e.index).ok_or_else(|| {
                 Error::no_data(
@@ -832,6 +804,130 @@ impl KismetSwitchCase {
     }
 }
 
+/// Get the child expressions of an expression for [`KismetExpression::children`] and [`KismetExpression::children_mut`]
+macro_rules! expression_children {
+    ($expr:expr, $as_ref:ident, $iter:ident, $($reference:tt)+) => {
+        match $expr {
+            KismetExpression::ExFieldPathConst(e) => vec![$($reference)+ *e.value],
+            KismetExpression::ExSoftObjectConst(e) => vec![$($reference)+ *e.value],
+            KismetExpression::ExTextConst(e) => {
+                let text = $($reference)+ *e.value;
+                [
+                    text.localized_source.$as_ref(),
+                    text.localized_key.$as_ref(),
+                    text.localized_namespace.$as_ref(),
+                    text.invariant_literal_string.$as_ref(),
+                    text.literal_string.$as_ref(),
+                    text.string_table_id.$as_ref(),
+                    text.string_table_key.$as_ref(),
+                ]
+                .into_iter()
+                .flatten()
+                .collect()
+            }
+            KismetExpression::ExAddMulticastDelegate(e) => {
+                vec![$($reference)+ *e.delegate, $($reference)+ *e.delegate_to_add]
+            }
+            KismetExpression::ExRemoveMulticastDelegate(e) => {
+                vec![$($reference)+ *e.delegate, $($reference)+ *e.delegate_to_add]
+            }
+            KismetExpression::ExClearMulticastDelegate(e) => vec![$($reference)+ *e.delegate_to_clear],
+            KismetExpression::ExBindDelegate(e) => {
+                vec![$($reference)+ *e.delegate, $($reference)+ *e.object_term]
+            }
+            KismetExpression::ExCallMulticastDelegate(e) => {
+                let mut children = vec![$($reference)+ *e.delegate];
+                children.extend(e.parameters.$iter());
+                children
+            }
+            KismetExpression::ExArrayConst(e) => e.elements.$iter().collect(),
+            KismetExpression::ExSetConst(e) => e.elements.$iter().collect(),
+            KismetExpression::ExMapConst(e) => e.elements.$iter().collect(),
+            KismetExpression::ExStructConst(e) => e.value.$iter().collect(),
+            KismetExpression::ExSetArray(e) => {
+                let mut children = e
+                    .assigning_property
+                    .$as_ref()
+                    .map(|e| $($reference)+ **e)
+                    .into_iter()
+                    .collect::<Vec<_>>();
+                children.extend(e.elements.$iter());
+                children
+            }
+            KismetExpression::ExSetSet(e) => {
+                let mut children = vec![$($reference)+ *e.set_property];
+                children.extend(e.elements.$iter());
+                children
+            }
+            KismetExpression::ExSetMap(e) => {
+                let mut children = vec![$($reference)+ *e.map_property];
+                children.extend(e.elements.$iter());
+                children
+            }
+            KismetExpression::ExArrayGetByRef(e) => {
+                vec![$($reference)+ *e.array_variable, $($reference)+ *e.array_index]
+            }
+            KismetExpression::ExAssert(e) => vec![$($reference)+ *e.assert_expression],
+            KismetExpression::ExCallMath(e) => e.parameters.$iter().collect(),
+            KismetExpression::ExFinalFunction(e) => e.parameters.$iter().collect(),
+            KismetExpression::ExLocalFinalFunction(e) => e.parameters.$iter().collect(),
+            KismetExpression::ExVirtualFunction(e) => e.parameters.$iter().collect(),
+            KismetExpression::ExLocalVirtualFunction(e) => e.parameters.$iter().collect(),
+            KismetExpression::ExClassContext(e) => {
+                vec![$($reference)+ *e.object_expression, $($reference)+ *e.context_expression]
+            }
+            KismetExpression::ExContext(e) => {
+                vec![$($reference)+ *e.object_expression, $($reference)+ *e.context_expression]
+            }
+            KismetExpression::ExContextFailSilent(e) => {
+                vec![$($reference)+ *e.object_expression, $($reference)+ *e.context_expression]
+            }
+            KismetExpression::ExInterfaceContext(e) => vec![$($reference)+ *e.interface_value],
+            KismetExpression::ExStructMemberContext(e) => vec![$($reference)+ *e.struct_expression],
+            KismetExpression::ExComputedJump(e) => vec![$($reference)+ *e.code_offset_expression],
+            KismetExpression::ExJumpIfNot(e) => vec![$($reference)+ *e.boolean_expression],
+            KismetExpression::ExPopExecutionFlowIfNot(e) => vec![$($reference)+ *e.boolean_expression],
+            KismetExpression::ExSkip(e) => vec![$($reference)+ *e.skip_expression],
+            KismetExpression::ExReturn(e) => vec![$($reference)+ *e.return_expression],
+            KismetExpression::ExCrossInterfaceCast(e) => vec![$($reference)+ *e.target],
+            KismetExpression::ExInterfaceToObjCast(e) => vec![$($reference)+ *e.target],
+            KismetExpression::ExObjToInterfaceCast(e) => vec![$($reference)+ *e.target],
+            KismetExpression::ExPrimitiveCast(e) => vec![$($reference)+ *e.target],
+            KismetExpression::ExDynamicCast(e) => vec![$($reference)+ *e.target_expression],
+            KismetExpression::ExMetaCast(e) => vec![$($reference)+ *e.target_expression],
+            KismetExpression::ExLet(e) => vec![$($reference)+ *e.variable, $($reference)+ *e.expression],
+            KismetExpression::ExLetBool(e) => {
+                vec![$($reference)+ *e.variable_expression, $($reference)+ *e.assignment_expression]
+            }
+            KismetExpression::ExLetDelegate(e) => {
+                vec![$($reference)+ *e.variable_expression, $($reference)+ *e.assignment_expression]
+            }
+            KismetExpression::ExLetMulticastDelegate(e) => {
+                vec![$($reference)+ *e.variable_expression, $($reference)+ *e.assignment_expression]
+            }
+            KismetExpression::ExLetObj(e) => {
+                vec![$($reference)+ *e.variable_expression, $($reference)+ *e.assignment_expression]
+            }
+            KismetExpression::ExLetWeakObjPtr(e) => {
+                vec![$($reference)+ *e.variable_expression, $($reference)+ *e.assignment_expression]
+            }
+            KismetExpression::ExLetValueOnPersistentFrame(e) => {
+                vec![$($reference)+ *e.assignment_expression]
+            }
+            KismetExpression::ExSwitchValue(e) => {
+                let mut children = vec![$($reference)+ *e.index_term];
+                for case in e.cases.$iter() {
+                    children.push($($reference)+ case.case_index_value_term);
+                    children.push($($reference)+ case.case_term);
+                }
+                children.push($($reference)+ *e.default_term);
+                children
+            }
+            _ => Vec::new(),
+        }
+    };
+}
+
 /// This must be implemented for all KismetExpressions
 #[enum_dispatch]
 pub trait KismetExpressionTrait: Debug + Clone + PartialEq + Eq + Hash {
@@ -1199,6 +1295,21 @@ impl KismetExpression {
         asset.write_u8(expr.get_token().into())?;
         Ok(expr.write(asset)? + size_of::<u8>())
     }
+
+    /// Get the direct child expressions of this expression, in the order they are serialized
+    ///
+    /// The index term of a switch is followed by the value and result term of every case and the default term.
+    /// The children of a text constant are the set expressions of its [`FScriptText`].
+    pub fn children(&self) -> Vec<&KismetExpression> {
+        expression_children!(self, as_ref, iter, &)
+    }
+
+    /// Get the direct child expressions of this expression mutably, in the order they are serialized
+    ///
+    /// See [`KismetExpression::children`].
+    pub fn children_mut(&mut self) -> Vec<&mut KismetExpression> {
+        expression_children!(self, as_mut, iter_mut, &mut)
+    }
 }
 
 declare_expression!(
@@ -3117,7 +3228,7 @@ impl ExSwitchValue {
         let end_goto_offset = asset.read_u32::<LE>()?;
         let index_term = Box::new(KismetExpression::new(asset)?);
 
-        let mut cases = Vec::with_capacity(num_cases as usize);
+        let mut cases = Vec::with_capacity(asset.check_array_len(num_cases)?);
         for _i in 0..num_cases as usize {
             let term_a = KismetExpression::new(asset)?;
             let term_b = asset.read_u32::<LE>()?;
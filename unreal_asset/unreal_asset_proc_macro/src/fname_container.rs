@@ -8,6 +8,8 @@ use syn::{parse_macro_input, DataEnum, DataStruct, DeriveInput, FieldsNamed, Fie
 
 /// Ignore attribute name
 const IGNORE_ATTRIBUTE: &str = "container_ignore";
+/// Cfg attribute name
+const CFG_ATTRIBUTE: &str = "cfg";
 /// No trait bounds attribute name
 /// This is used to prevent trait bounds resolution cycles
 const NO_BOUNDS: &str = "container_nobounds";
@@ -145,20 +147,21 @@ fn add_trait_bounds_for_all_enum_variants(
 ) -> proc_macro2::TokenStream {
     let traits: syn::Type = syn::parse_str(&traits.join(" + ")).unwrap();
 
-    let bounds =
-        proc_macro2::TokenStream::from_iter(data_enum.variants.iter().map(|e| match &e.fields {
-            syn::Fields::Unnamed(unnamed_fields) => {
-                for_each_unnamed_field_type(unnamed_fields, |field_type| {
-                    quote! {
-                        #field_type: #traits,
-                    }
-                })
-            }
-            syn::Fields::Named(_) => {
-                panic!("This macro cannot be used on enums with named fields!")
-            }
-            syn::Fields::Unit => quote! {},
-        }));
+    let variants = data_enum.variants.iter().filter(|e| !has_cfg(&e.attrs));
+
+    let bounds = proc_macro2::TokenStream::from_iter(variants.map(|e| match &e.fields {
+        syn::Fields::Unnamed(unnamed_fields) => {
+            for_each_unnamed_field_type(unnamed_fields, |field_type| {
+                quote! {
+                    #field_type: #traits,
+                }
+            })
+        }
+        syn::Fields::Named(_) => {
+            panic!("This macro cannot be used on enums with named fields!")
+        }
+        syn::Fields::Unit => quote! {},
+    }));
 
     quote! {
         where #bounds
@@ -186,6 +189,7 @@ fn for_each_named_field_type<F: Fn(&syn::Type) -> proc_macro2::TokenStream>(
         .named
         .iter()
         .filter(|e| !e.attrs.iter().any(|e| e.path().is_ident(IGNORE_ATTRIBUTE)))
+        .filter(|e| !has_cfg(&e.attrs))
         .map(|e| executor(&e.ty));
 
     quote! {
@@ -201,6 +205,7 @@ fn for_each_unnamed_field_type<F: Fn(&syn::Type) -> proc_macro2::TokenStream>(
         .unnamed
         .iter()
         .filter(|e| !e.attrs.iter().any(|e| e.path().is_ident(IGNORE_ATTRIBUTE)))
+        .filter(|e| !has_cfg(&e.attrs))
         .map(|e| executor(&e.ty));
 
     quote! {
@@ -215,7 +220,9 @@ fn body_for_struct_named_fields(fields_named: &FieldsNamed) -> proc_macro2::Toke
         .filter(|e| !e.attrs.iter().any(|e| e.path().is_ident(IGNORE_ATTRIBUTE)))
         .map(|e| {
             let name = e.ident.as_ref().unwrap();
+            let cfgs = cfg_attributes(&e.attrs);
             quote! {
+                #(#cfgs)*
                 self.#name.traverse_fnames(traverse)
             }
         });
@@ -250,6 +257,7 @@ fn body_for_enum_variants(data_enum: &DataEnum) -> proc_macro2::TokenStream {
 
     let variant_streams = data_enum.variants.iter().map(|e| {
         let name = &e.ident;
+        let cfgs = cfg_attributes(&e.attrs);
 
         let fields = e
             .fields
@@ -261,6 +269,7 @@ fn body_for_enum_variants(data_enum: &DataEnum) -> proc_macro2::TokenStream {
         let fields_ = fields.clone();
 
         quote! {
+            #(#cfgs)*
             Self::#name #((#fields,))* => {
                 #(#fields_.traverse_fnames(traverse);)*
             }
@@ -271,3 +280,15 @@ fn body_for_enum_variants(data_enum: &DataEnum) -> proc_macro2::TokenStream {
         #(#variant_streams,)*
     }
 }
+
+/// Check if an item is conditionally compiled
+///
+/// Trait bounds can't be conditionally compiled, so no bounds are generated for such items
+fn has_cfg(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|e| e.path().is_ident(CFG_ATTRIBUTE))
+}
+
+/// Get the cfg attributes of an item, these are forwarded to the generated code
+fn cfg_attributes(attrs: &[syn::Attribute]) -> impl Iterator<Item = &syn::Attribute> {
+    attrs.iter().filter(|e| e.path().is_ident(CFG_ATTRIBUTE))
+}
@@ -0,0 +1,60 @@
+//! `Arbitrary` implementations for fuzzing and property based testing, enabled with the `arbitrary` feature
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::int_property::{
+    BoolProperty, DoubleProperty, FloatProperty, Int16Property, Int64Property, Int8Property,
+    IntProperty, UInt16Property, UInt32Property, UInt64Property,
+};
+use crate::property_prelude::*;
+use crate::str_property::{NameProperty, StrProperty};
+use crate::Property;
+
+/// Implement `Arbitrary` for simple properties
+///
+/// Values are generated as `$value_type` and converted into the value type of the property.
+/// Generated properties have an empty ancestry, the ancestry of properties read from an asset
+/// is derived from where they are located.
+macro_rules! impl_arbitrary_simple_property {
+    ($($property_type:ident: $value_type:ty),*) => {
+        $(
+            impl<'a> Arbitrary<'a> for $property_type {
+                #[allow(clippy::useless_conversion)]
+                fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                    Ok($property_type {
+                        name: u.arbitrary()?,
+                        ancestry: Ancestry::default(),
+                        property_guid: u.arbitrary::<Option<[u8; 16]>>()?.map(Guid),
+                        duplication_index: u.arbitrary()?,
+                        value: u.arbitrary::<$value_type>()?.into(),
+                    })
+                }
+            }
+        )*
+
+        impl<'a> Arbitrary<'a> for Property {
+            /// Only simple property types are generated
+            fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+                let generators: &[fn(&mut Unstructured<'a>) -> Result<Property>] = &[
+                    $(|u| Ok($property_type::arbitrary(u)?.into())),*
+                ];
+                u.choose(generators)?(u)
+            }
+        }
+    };
+}
+
+impl_arbitrary_simple_property!(
+    BoolProperty: bool,
+    Int8Property: i8,
+    Int16Property: i16,
+    IntProperty: i32,
+    Int64Property: i64,
+    UInt16Property: u16,
+    UInt32Property: u32,
+    UInt64Property: u64,
+    FloatProperty: f32,
+    DoubleProperty: f64,
+    StrProperty: Option<String>,
+    NameProperty: FName
+);
@@ -1,6 +1,9 @@
 //! Array property
 
+use unreal_asset_base::reader::StructFallbackBehavior;
+
 use crate::property_prelude::*;
+use crate::raw_struct_property::RawStructProperty;
 
 /// Array property
 #[derive(FNameContainer, Debug, Default, Clone, Hash, PartialEq, Eq)]
@@ -51,21 +54,41 @@ impl ArrayProperty {
     }
 
     /// Create an `ArrayProperty` from an array of properties
+    ///
+    /// Ancestry of the array elements is derived from `ancestry`.
     pub fn from_arr(
         name: FName,
         ancestry: Ancestry,
         array_type: Option<FName>,
         value: Vec<Property>,
     ) -> Self {
-        ArrayProperty {
+        let mut property = ArrayProperty {
             name,
-            ancestry,
+            ancestry: Ancestry::default(),
             property_guid: None,
             array_type,
             value,
             duplication_index: 0,
             dummy_property: None,
+        };
+        property.set_ancestry(ancestry);
+        property
+    }
+
+    /// Set this property's ancestry and derive the ancestry of array elements from it
+    pub fn set_ancestry(&mut self, ancestry: Ancestry) {
+        let struct_ancestry = ancestry.with_parent(self.name.clone());
+
+        for property in &mut self.value {
+            match property {
+                Property::StructProperty(_) => property.set_ancestry(struct_ancestry.clone()),
+                _ => property.set_ancestry(ancestry.clone()),
+            }
         }
+        if let Some(dummy_property) = &mut self.dummy_property {
+            dummy_property.set_ancestry(struct_ancestry);
+        }
+        self.ancestry = ancestry;
     }
 
     /// Read an `ArrayProperty` from an asset without reading the property header
@@ -90,25 +113,28 @@ impl ArrayProperty {
 
         let mut dummy_struct = None;
 
+        // the mappings also know the struct type of struct arrays which don't serialize it
         let mut array_struct_type = None;
-        if array_type.is_none() {
-            if let Some(struct_data) = asset
-                .get_mappings()
-                .and_then(|e| e.get_property(&name, &ancestry))
-                .and_then(|e| cast!(UsmapPropertyData, UsmapArrayPropertyData, &e.property_data))
-            {
+        if let Some(inner_type) = asset
+            .get_mappings()
+            .and_then(|e| e.get_property(&name, &ancestry))
+            .and_then(|e| match &e.property_data {
+                UsmapPropertyData::UsmapArrayPropertyData(data) => Some(data.inner_type.as_ref()),
+                UsmapPropertyData::UsmapSetPropertyData(data) => Some(data.inner_type.as_ref()),
+                _ => None,
+            })
+        {
+            if array_type.is_none() {
                 array_type = Some(FName::new_dummy(
-                    struct_data.inner_type.get_property_type().to_string(),
+                    inner_type.get_property_type().to_string(),
                     0,
                 ));
-                if let Some(inner_struct_data) = cast!(
-                    UsmapPropertyData,
-                    UsmapStructPropertyData,
-                    struct_data.inner_type.as_ref()
-                ) {
-                    array_struct_type =
-                        Some(FName::new_dummy(inner_struct_data.struct_type.clone(), 0));
-                }
+            }
+            if let Some(inner_struct_data) =
+                cast!(UsmapPropertyData, UsmapStructPropertyData, inner_type)
+            {
+                array_struct_type =
+                    Some(FName::new_dummy(inner_struct_data.struct_type.clone(), 0));
             }
         }
 
@@ -156,6 +182,13 @@ impl ArrayProperty {
                 .cloned()
             {
                 full_type = asset.add_fname(&type_override);
+            } else if let Some(ref array_struct_type) = array_struct_type {
+                full_type = array_struct_type.clone();
+            } else if asset.get_parse_options().struct_fallback != StructFallbackBehavior::Guess {
+                // the size of the structs is not known
+                return name.get_content(|name| {
+                    Err(PropertyError::unknown_struct_type(name, &ancestry).into())
+                });
             }
 
             if num_entries == 0 {
@@ -185,8 +218,38 @@ impl ArrayProperty {
             let array_type = array_type
                 .as_ref()
                 .ok_or_else(|| Error::invalid_file("Unknown array type".to_string()))?;
+            let struct_fallback = asset.get_parse_options().struct_fallback;
+            let raw_struct_size = match array_type == "StructProperty"
+                && array_struct_type.is_none()
+                && struct_fallback != StructFallbackBehavior::Guess
+            {
+                true => match struct_fallback {
+                    StructFallbackBehavior::RawPreserve
+                        if size_est_2 > 0 && (length - 4) % num_entries as i64 == 0 =>
+                    {
+                        Some(size_est_2)
+                    }
+                    _ => {
+                        return name.get_content(|name| {
+                            Err(PropertyError::unknown_struct_type(name, &ancestry).into())
+                        })
+                    }
+                },
+                false => None,
+            };
+
             for i in 0..num_entries {
-                let entry: Property = if array_type == "StructProperty" {
+                let entry: Property = if let Some(raw_struct_size) = raw_struct_size {
+                    RawStructProperty::new(
+                        asset,
+                        FName::new_dummy(i.to_string(), i32::MIN),
+                        new_ancestry.clone(),
+                        false,
+                        0,
+                        raw_struct_size,
+                    )?
+                    .into()
+                } else if array_type == "StructProperty" {
                     let struct_type = match array_struct_type {
                         Some(ref e) => Some(e.clone()),
                         None => Some(FName::from_slice("Generic")),
@@ -240,7 +303,7 @@ impl ArrayProperty {
     ) -> Result<usize, Error> {
         let array_type = match !self.value.is_empty() {
             true => {
-                let value = self.value[0].to_serialized_name();
+                let value = self.value[0].to_array_entry_type();
                 Some(asset.get_name_map().get_mut().add_fname(&value))
             }
             false => self.array_type.clone(),
@@ -276,7 +339,8 @@ impl ArrayProperty {
             let mut length_loc = None;
             if asset.get_object_version() >= ObjectVersion::VER_UE4_INNER_ARRAY_TAG_INFO {
                 asset.write_fname(&property.name)?;
-                asset.write_fname(&asset.get_name_map().get_mut().add_fname("StructProperty"))?;
+                let struct_property = asset.get_name_map().get_mut().add_fname("StructProperty");
+                asset.write_fname(&struct_property)?;
                 length_loc = Some(asset.position());
                 asset.write_i64::<LE>(0)?;
                 asset.write_fname(
@@ -288,11 +352,7 @@ impl ArrayProperty {
                 {
                     asset.write_guid(&property.property_guid.unwrap_or_default())?;
                 }
-                if asset.get_object_version()
-                    >= ObjectVersion::VER_UE4_PROPERTY_GUID_IN_PROPERTY_TAG
-                {
-                    asset.write_u8(0)?;
-                }
+                asset.write_property_guid(None)?;
             }
 
             for property in &self.value {
@@ -17,6 +17,10 @@ pub struct MeshToMeshVertData {
     /// Weight
     pub weight: OrderedFloat<f32>,
 }
+impl_float_value!(MeshToMeshVertData {
+    weight,
+    set_weight: f32
+});
 
 impl MeshToMeshVertData {
     /// Read `MeshToMeshVertData` from an asset
@@ -133,14 +137,15 @@ impl ClothLodDataProperty {
         )?;
 
         let transition_up_skin_data_len = asset.read_i32::<LE>()?;
-        let mut transition_up_skin_data = Vec::with_capacity(transition_up_skin_data_len as usize);
+        let mut transition_up_skin_data =
+            Vec::with_capacity(asset.check_array_len(transition_up_skin_data_len)?);
         for _ in 0..transition_up_skin_data_len {
             transition_up_skin_data.push(MeshToMeshVertData::new(asset)?);
         }
 
         let transition_down_skin_data_len = asset.read_i32::<LE>()?;
         let mut transition_down_skin_data =
-            Vec::with_capacity(transition_down_skin_data_len as usize);
+            Vec::with_capacity(asset.check_array_len(transition_down_skin_data_len)?);
         for _ in 0..transition_down_skin_data_len {
             transition_down_skin_data.push(MeshToMeshVertData::new(asset)?);
         }
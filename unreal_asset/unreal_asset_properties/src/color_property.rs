@@ -37,6 +37,7 @@ pub struct LinearColorProperty {
     pub color: Color<OrderedFloat<f32>>,
 }
 impl_property_data_trait!(LinearColorProperty);
+impl_float_value!(LinearColorProperty { color, set_color: Color<f32> });
 
 impl ColorProperty {
     /// Read a `ColorProperty` from an asset
@@ -11,15 +11,18 @@ pub struct EmptyProperty {
     pub name: FName,
     /// Property ancestry
     pub ancestry: Ancestry,
+    /// Property duplication index
+    pub duplication_index: i32,
 }
 
 impl EmptyProperty {
     /// Create a new `EmptyProperty` instance
-    pub fn new(type_name: FName, name: FName, ancestry: Ancestry) -> Self {
+    pub fn new(type_name: FName, name: FName, ancestry: Ancestry, duplication_index: i32) -> Self {
         EmptyProperty {
             type_name,
             name,
             ancestry,
+            duplication_index,
         }
     }
 }
@@ -34,7 +37,7 @@ impl PropertyDataTrait for EmptyProperty {
     }
 
     fn get_duplication_index(&self) -> i32 {
-        0
+        self.duplication_index
     }
 
     fn get_property_guid(&self) -> Option<Guid> {
@@ -0,0 +1,54 @@
+//! Field path property
+
+use crate::property_prelude::*;
+
+/// Field path property
+#[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct FieldPathProperty {
+    /// Name
+    pub name: FName,
+    /// Property ancestry
+    pub ancestry: Ancestry,
+    /// Property guid
+    pub property_guid: Option<Guid>,
+    /// Property duplication index
+    pub duplication_index: i32,
+    /// Value
+    pub value: FieldPath,
+}
+impl_property_data_trait!(FieldPathProperty);
+
+impl FieldPathProperty {
+    /// Read a `FieldPathProperty` from an asset
+    pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        asset: &mut Reader,
+        name: FName,
+        ancestry: Ancestry,
+        include_header: bool,
+        duplication_index: i32,
+    ) -> Result<Self, Error> {
+        let property_guid = optional_guid!(asset, include_header);
+        let with_owner = FieldPath::has_serialized_owner(&*asset);
+        let value = FieldPath::read(asset, with_owner)?;
+
+        Ok(FieldPathProperty {
+            name,
+            ancestry,
+            property_guid,
+            duplication_index,
+            value,
+        })
+    }
+}
+
+impl PropertyTrait for FieldPathProperty {
+    fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        asset: &mut Writer,
+        include_header: bool,
+    ) -> Result<usize, Error> {
+        optional_guid_write!(self, asset, include_header);
+        let with_owner = FieldPath::has_serialized_owner(&*asset);
+        self.value.write(asset, with_owner)
+    }
+}
@@ -19,6 +19,12 @@ pub struct FloatRangeProperty {
     pub upper_bound: OrderedFloat<f32>,
 }
 impl_property_data_trait!(FloatRangeProperty);
+impl_float_value!(FloatRangeProperty {
+    lower_bound,
+    set_lower_bound: f32,
+    upper_bound,
+    set_upper_bound: f32,
+});
 
 impl FloatRangeProperty {
     /// Read a `FloatRangeProperty` from an asset
@@ -207,6 +207,7 @@ pub struct FloatProperty {
     pub value: OrderedFloat<f32>,
 }
 impl_property_data_trait!(FloatProperty);
+impl_float_value!(FloatProperty, f32);
 
 /// Double property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -223,6 +224,7 @@ pub struct DoubleProperty {
     pub value: OrderedFloat<f64>,
 }
 impl_property_data_trait!(DoubleProperty);
+impl_float_value!(DoubleProperty, f64);
 
 impl BoolProperty {
     /// Read a `BoolProperty` from an asset
@@ -3,7 +3,6 @@
 
 //! Unreal asset properties
 
-use std::collections::HashSet;
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::io::SeekFrom;
@@ -19,14 +18,16 @@ pub use unreal_asset_base::unversioned::Ancestry;
 pub use unreal_asset_base::Guid;
 
 use unreal_asset_base::error::{Error, PropertyError};
-use unreal_asset_base::reader::{ArchiveReader, ArchiveWriter};
+use unreal_asset_base::reader::{ArchiveReader, ArchiveTrait, ArchiveWriter};
 use unreal_asset_base::types::fname::ToSerializedName;
 use unreal_asset_base::unversioned::header::UnversionedHeaderFragment;
 use unreal_asset_base::unversioned::{
-    header::UnversionedHeader, properties::UsmapPropertyDataTrait,
+    header::UnversionedHeader, properties::UsmapPropertyDataTrait, Usmap,
 };
 use unreal_asset_base::FNameContainer;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
 pub mod array_property;
 pub mod cloth_lod_property;
 pub mod color_property;
@@ -34,6 +35,7 @@ pub mod date_property;
 pub mod delegate_property;
 pub mod empty_property;
 pub mod enum_property;
+pub mod field_path_property;
 pub mod float_range_property;
 pub mod font_character_property;
 pub mod game_framework;
@@ -42,7 +44,9 @@ pub mod guid_property;
 pub mod int_property;
 pub mod map_property;
 pub mod material_input_property;
+#[cfg(feature = "movies")]
 pub mod movies;
+#[cfg(feature = "niagara")]
 pub mod niagara;
 pub mod object_property;
 pub mod per_platform_property;
@@ -50,6 +54,7 @@ pub mod raw_struct_property;
 pub mod rich_curve_key_property;
 pub mod sampler_property;
 pub mod set_property;
+pub mod simple_curve_key_property;
 pub mod slate_core;
 pub mod smart_name_property;
 pub mod soft_path_property;
@@ -70,6 +75,7 @@ use delegate_property::{
 };
 use empty_property::EmptyProperty;
 use enum_property::EnumProperty;
+use field_path_property::FieldPathProperty;
 use float_range_property::FloatRangeProperty;
 use font_character_property::FontCharacterProperty;
 use game_framework::unique_net_id_property::UniqueNetIdProperty;
@@ -85,23 +91,45 @@ use material_input_property::{
     ScalarMaterialInputProperty, ShadingModelMaterialInputProperty, Vector2MaterialInputProperty,
     VectorMaterialInputProperty,
 };
+#[cfg(feature = "movies")]
+use movies::frame_number_property::FrameNumberProperty;
+#[cfg(feature = "movies")]
+use movies::movie_scene_double_channel_property::MovieSceneDoubleChannelProperty;
+#[cfg(feature = "movies")]
+use movies::movie_scene_double_value_property::MovieSceneDoubleValueProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_eval_template_ptr_property::MovieSceneEvalTemplatePtrProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_evaluation_field_entity_tree_property::MovieSceneEvaluationFieldEntityTreeProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_evaluation_key_property::MovieSceneEvaluationKeyProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_event_parameters_property::MovieSceneEventParametersProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_float_channel_property::MovieSceneFloatChannelProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_float_value_property::MovieSceneFloatValueProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_frame_range_property::MovieSceneFrameRangeProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_segment_property::{
     MovieSceneSegmentIdentifierProperty, MovieSceneSegmentProperty,
 };
+#[cfg(feature = "movies")]
 use movies::movie_scene_sequence_id_property::MovieSceneSequenceIdProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_sequence_instance_data_ptr_property::MovieSceneSequenceInstanceDataPtrProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_sub_sequence_tree_property::MovieSceneSubSequenceTreeProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_track_field_data_property::MovieSceneTrackFieldDataProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_track_identifier_property::MovieSceneTrackIdentifierProperty;
+#[cfg(feature = "movies")]
 use movies::movie_scene_track_implementation_ptr_property::MovieSceneTrackImplementationPtrProperty;
+#[cfg(feature = "movies")]
 use movies::section_evaluation_data_tree_property::SectionEvaluationDataTreeProperty;
+#[cfg(feature = "niagara")]
 use niagara::niagara_variable_property::{
     NiagaraVariableProperty, NiagaraVariableWithOffsetProperty,
 };
@@ -116,6 +144,7 @@ use sampler_property::{
     WeightedRandomSamplerProperty,
 };
 use set_property::SetProperty;
+use simple_curve_key_property::SimpleCurveKeyProperty;
 use slate_core::font_data_property::FontDataProperty;
 use smart_name_property::SmartNameProperty;
 use soft_path_property::{
@@ -151,7 +180,7 @@ mod property_prelude {
     pub use unreal_asset_base::reader::{ArchiveReader, ArchiveWriter};
     pub use unreal_asset_base::types::{
         fname::{FName, ToSerializedName},
-        PackageIndex, PackageIndexTrait,
+        FieldPath, PackageIndex, PackageIndexTrait,
     };
     pub use unreal_asset_base::unversioned::{
         header::UnversionedHeader,
@@ -162,8 +191,9 @@ mod property_prelude {
     pub use unreal_asset_base::FNameContainer;
 
     pub use super::generate_unversioned_header;
+    pub(crate) use super::impl_float_value;
     pub use super::impl_property_data_trait;
-    pub use super::object_property::SoftObjectPath;
+    pub use super::object_property::{SoftObjectPath, TopLevelAssetPath};
     pub use super::optional_guid;
     pub use super::optional_guid_write;
     pub use super::simple_property_write;
@@ -245,7 +275,108 @@ macro_rules! impl_property_data_trait {
     };
 }
 
-const CUSTOM_SERIALIZATION: [&str; 57] = [
+/// Implement plain float accessors for `OrderedFloat` fields of a property
+///
+/// Every field gets a getter with the same name as the field and a setter,
+/// vectors, colors and lists of floats are converted component-wise.
+macro_rules! impl_float_value {
+    ($name:ident, $value_type:ty) => {
+        impl_float_value!($name { value, set_value: $value_type });
+    };
+    ($name:ident { $($field:ident, $setter:ident: $value_type:ty),+ $(,)? }) => {
+        impl $name {
+            $(
+                #[doc = concat!("Get `", stringify!($field), "` as plain floats")]
+                pub fn $field(&self) -> $value_type {
+                    $crate::FloatValue::to_float(&self.$field)
+                }
+
+                #[doc = concat!("Set `", stringify!($field), "` from plain floats")]
+                pub fn $setter(&mut self, value: $value_type) {
+                    self.$field = $crate::FloatValue::from_float(value);
+                }
+            )+
+        }
+    };
+}
+pub(crate) use impl_float_value;
+
+/// Conversion between values containing `OrderedFloat`s and their plain float counterparts
+pub(crate) trait FloatValue {
+    /// Plain float type
+    type Float;
+
+    /// Convert to plain floats
+    fn to_float(&self) -> Self::Float;
+
+    /// Convert from plain floats
+    fn from_float(value: Self::Float) -> Self;
+}
+
+impl<T: ordered_float::Float> FloatValue for ordered_float::OrderedFloat<T> {
+    type Float = T;
+
+    fn to_float(&self) -> T {
+        self.0
+    }
+
+    fn from_float(value: T) -> Self {
+        ordered_float::OrderedFloat(value)
+    }
+}
+
+impl<T: FloatValue> FloatValue for Vec<T> {
+    type Float = Vec<T::Float>;
+
+    fn to_float(&self) -> Self::Float {
+        self.iter().map(T::to_float).collect()
+    }
+
+    fn from_float(value: Self::Float) -> Self {
+        value.into_iter().map(T::from_float).collect()
+    }
+}
+
+impl<T: FloatValue> FloatValue for (FName, T) {
+    type Float = (FName, T::Float);
+
+    fn to_float(&self) -> Self::Float {
+        (self.0.clone(), self.1.to_float())
+    }
+
+    fn from_float((name, value): Self::Float) -> Self {
+        (name, T::from_float(value))
+    }
+}
+
+/// Implement `FloatValue` for a vector-like type by converting each component
+macro_rules! impl_float_value_components {
+    ($name:ident, $($component:ident),+) => {
+        impl<T: FloatValue> FloatValue for unreal_asset_base::types::vector::$name<T> {
+            type Float = unreal_asset_base::types::vector::$name<T::Float>;
+
+            fn to_float(&self) -> Self::Float {
+                unreal_asset_base::types::vector::$name {
+                    $($component: self.$component.to_float()),+
+                }
+            }
+
+            fn from_float(value: Self::Float) -> Self {
+                unreal_asset_base::types::vector::$name {
+                    $($component: T::from_float(value.$component)),+
+                }
+            }
+        }
+    };
+}
+
+impl_float_value_components!(Vector, x, y, z);
+impl_float_value_components!(Vector2, x, y);
+impl_float_value_components!(Vector4, x, y, z, w);
+impl_float_value_components!(Plane, x, y, z, w);
+impl_float_value_components!(Color, r, g, b, a);
+
+const CUSTOM_SERIALIZATION: [&str; 61] = [
     "SkeletalMeshSamplingLODBuiltData",
     "SkeletalMeshAreaWeightedTriangleSampler",
     "SmartName",
@@ -265,6 +396,7 @@ const CUSTOM_SERIALIZATION: [&str; 57] = [
     "PerPlatformBool",
     "PerPlatformInt",
     "RichCurveKey",
+    "SimpleCurveKey",
     "SoftAssetPath",
     "Timespan",
     "DateTime",
@@ -298,7 +430,10 @@ const CUSTOM_SERIALIZATION: [&str; 57] = [
     "MovieSceneEventParameters",
     "MovieSceneFloatChannel",
     "MovieSceneFloatValue",
+    "MovieSceneDoubleChannel",
+    "MovieSceneDoubleValue",
     "MovieSceneFrameRange",
+    "FrameNumber",
     "MovieSceneSegment",
     "MovieSceneSegmentIdentifier",
     "MovieSceneTrackIdentifier",
@@ -335,10 +470,14 @@ pub trait PropertyTrait: PropertyDataTrait + Debug + Hash + Clone + PartialEq +
 }
 
 /// Property
+///
+/// New property types can be added in minor releases, matching on a `Property` needs a wildcard arm.
+/// Unknown property types are read as [`Property::UnknownProperty`].
 #[allow(clippy::large_enum_variant)]
 #[enum_dispatch(PropertyTrait, PropertyDataTrait)]
 #[derive(FNameContainer, Debug, Clone, PartialEq, Eq, Hash)]
 #[container_nobounds]
+#[non_exhaustive]
 pub enum Property {
     /// Bool property
     BoolProperty,
@@ -450,8 +589,12 @@ pub enum Property {
     MulticastSparseDelegateProperty,
     /// Multicast inline delegate property
     MulticastInlineDelegateProperty,
+    /// Field path property
+    FieldPathProperty,
     /// Rich curve key property
     RichCurveKeyProperty,
+    /// Simple curve key property
+    SimpleCurveKeyProperty,
     /// View target blend params property
     ViewTargetBlendParamsProperty,
     /// Gameplay tag container property
@@ -469,8 +612,10 @@ pub enum Property {
     /// Unique net identifier property
     UniqueNetIdProperty,
     /// Niagara variable property
+    #[cfg(feature = "niagara")]
     NiagaraVariableProperty,
     /// Niagara variable with offset property
+    #[cfg(feature = "niagara")]
     NiagaraVariableWithOffsetProperty,
     /// Font data property
     FontDataProperty,
@@ -479,36 +624,61 @@ pub enum Property {
     /// Raw struct property
     RawStructProperty,
     /// Movie scene eval template pointer property
+    #[cfg(feature = "movies")]
     MovieSceneEvalTemplatePtrProperty,
     /// Movie scene track implementation pointer property
+    #[cfg(feature = "movies")]
     MovieSceneTrackImplementationPtrProperty,
     /// Movie scene evaluation field entity tree property
+    #[cfg(feature = "movies")]
     MovieSceneEvaluationFieldEntityTreeProperty,
     /// Movie scene sub sequence tree property
+    #[cfg(feature = "movies")]
     MovieSceneSubSequenceTreeProperty,
     /// Movie scene sequence instance data ptr property
+    #[cfg(feature = "movies")]
     MovieSceneSequenceInstanceDataPtrProperty,
     /// Section evaluation data tree property
+    #[cfg(feature = "movies")]
     SectionEvaluationDataTreeProperty,
     /// Movie scene track field data property
+    #[cfg(feature = "movies")]
     MovieSceneTrackFieldDataProperty,
     /// Movie scene event parameters property
+    #[cfg(feature = "movies")]
     MovieSceneEventParametersProperty,
     /// Movie scene float channel property
+    #[cfg(feature = "movies")]
     MovieSceneFloatChannelProperty,
     /// Movie scene float value property
+    #[cfg(feature = "movies")]
     MovieSceneFloatValueProperty,
+    /// Movie scene double channel property
+    #[cfg(feature = "movies")]
+    MovieSceneDoubleChannelProperty,
+    /// Movie scene double value property
+    #[cfg(feature = "movies")]
+    MovieSceneDoubleValueProperty,
+    /// Frame number property
+    #[cfg(feature = "movies")]
+    FrameNumberProperty,
     /// Movie scene frame range property
+    #[cfg(feature = "movies")]
     MovieSceneFrameRangeProperty,
     /// Movie scene segment property
+    #[cfg(feature = "movies")]
     MovieSceneSegmentProperty,
     /// Movie scene segment identifier property
+    #[cfg(feature = "movies")]
     MovieSceneSegmentIdentifierProperty,
     /// Movie scene track identifier property
+    #[cfg(feature = "movies")]
     MovieSceneTrackIdentifierProperty,
     /// Movie scene sequence id property
+    #[cfg(feature = "movies")]
     MovieSceneSequenceIdProperty,
     /// Movie scene evaluation key property
+    #[cfg(feature = "movies")]
     MovieSceneEvaluationKeyProperty,
 
     /// Empty unversioned property
@@ -518,6 +688,98 @@ pub enum Property {
 }
 
 impl Property {
+    /// Check if this property is zeroed
+    ///
+    /// Zeroed properties only exist in assets with unversioned properties,
+    /// they are marked in the zero mask of the unversioned header and have no serialized data.
+    pub fn is_zeroed(&self) -> bool {
+        matches!(self, Property::EmptyProperty(_))
+    }
+
+    /// Mark this property as zeroed, discarding its value
+    ///
+    /// When loading, the engine initializes zeroed properties to the zero value of their type.
+    /// This only has an effect on assets with unversioned properties.
+    pub fn set_zeroed(&mut self) {
+        if self.is_zeroed() {
+            return;
+        }
+
+        *self = EmptyProperty::new(
+            FName::new_dummy(self.to_serialized_name(), 0),
+            self.get_name(),
+            self.get_ancestry().clone(),
+            self.get_duplication_index(),
+        )
+        .into();
+    }
+
+    /// Check if the value of this property is the zero value of its type
+    ///
+    /// These are the values the engine initializes zeroed properties to, so writing them zero-masked doesn't change the asset.
+    /// Enum values are compared to the first value of their enum in `mappings`.
+    /// Structs and other complex properties are never considered zero.
+    pub fn is_zero_value(&self, mappings: &Usmap) -> bool {
+        match self {
+            Property::BoolProperty(property) => !property.value,
+            Property::Int8Property(property) => property.value == 0,
+            Property::ByteProperty(property) => {
+                matches!(property.value, int_property::BytePropertyValue::Byte(0))
+            }
+            Property::Int16Property(property) => property.value == 0,
+            Property::IntProperty(property) => property.value == 0,
+            Property::Int64Property(property) => property.value == 0,
+            Property::UInt16Property(property) => property.value == 0,
+            Property::UInt32Property(property) => property.value == 0,
+            Property::UInt64Property(property) => property.value == 0,
+            // -0.0 is not zeroed memory
+            Property::FloatProperty(property) => property.value.0.to_bits() == 0,
+            Property::DoubleProperty(property) => property.value.0.to_bits() == 0,
+            Property::StrProperty(property) => property
+                .value
+                .as_ref()
+                .map(|e| e.is_empty())
+                .unwrap_or(true),
+            Property::NameProperty(property) => {
+                property.value == "None" && property.value.get_number() == 0
+            }
+            Property::ObjectProperty(property) => property.value.index == 0,
+            Property::ArrayProperty(property) => property.value.is_empty(),
+            Property::SetProperty(property) => {
+                property.value.value.is_empty() && property.removed_items.value.is_empty()
+            }
+            Property::MapProperty(property) => {
+                property.value.is_empty()
+                    && property
+                        .keys_to_remove
+                        .as_ref()
+                        .map(|e| e.is_empty())
+                        .unwrap_or(true)
+            }
+            Property::EnumProperty(property) => {
+                let (Some(enum_type), Some(value)) = (&property.enum_type, &property.value) else {
+                    return false;
+                };
+                let is_byte_enum = property
+                    .inner_type
+                    .as_ref()
+                    .map(|e| e == "ByteProperty")
+                    .unwrap_or(false);
+
+                is_byte_enum
+                    && enum_type.get_content(|enum_type| {
+                        mappings
+                            .enum_map
+                            .get_by_key(enum_type)
+                            .and_then(|e| e.first())
+                            .map(|first| value == first.as_str())
+                            .unwrap_or(false)
+                    })
+            }
+            _ => false,
+        }
+    }
+
     /// Tries to read a property from an ArchiveReader
     pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -538,12 +800,24 @@ impl Property {
                 .ok_or_else(PropertyError::no_mappings)?;
             let parent_name = ancestry.get_parent().ok_or_else(PropertyError::no_parent)?;
 
+            if !header.has_values() {
+                return Ok(None);
+            }
+
+            // move to the fragment containing the next serialized property
             loop {
                 let current_fragment = header.fragments[header.current_fragment_index];
-                if header.unversioned_property_index > current_fragment.get_last_num() as usize {
+                if current_fragment
+                    .get_last_num()
+                    .is_some_and(|e| header.unversioned_property_index <= e as usize)
+                {
                     break;
                 }
 
+                if current_fragment.is_last {
+                    return Ok(None);
+                }
+
                 header.current_fragment_index += 1;
                 header.unversioned_property_index =
                     header.fragments[header.current_fragment_index].first_num as usize;
@@ -635,8 +909,20 @@ impl Property {
         duplication_index: i32,
         is_zero: bool,
     ) -> Result<Self, Error> {
+        let max_property_depth = asset.get_parse_options().max_property_depth;
+        if ancestry.ancestry.len() > max_property_depth {
+            return Err(PropertyError::depth_limit_exceeded(
+                &name.get_owned_content(),
+                &ancestry,
+                max_property_depth,
+            )
+            .into());
+        }
+
         if is_zero {
-            return Ok(EmptyProperty::new(type_name.clone(), name, ancestry).into());
+            return Ok(
+                EmptyProperty::new(type_name.clone(), name, ancestry, duplication_index).into(),
+            );
         }
 
         type_name.get_content(|ty| {
@@ -1050,6 +1336,14 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                "FieldPathProperty" => FieldPathProperty::new(
+                    asset,
+                    name,
+                    ancestry,
+                    include_header,
+                    duplication_index,
+                )?
+                .into(),
                 "RichCurveKey" => RichCurveKeyProperty::new(
                     asset,
                     name,
@@ -1059,6 +1353,15 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                "SimpleCurveKey" => SimpleCurveKeyProperty::new(
+                    asset,
+                    name,
+                    ancestry,
+                    include_header,
+                    length,
+                    duplication_index,
+                )?
+                .into(),
                 "ViewTargetBlendParams" => ViewTargetBlendParamsProperty::new(
                     asset,
                     name,
@@ -1133,6 +1436,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "niagara")]
                 "NiagaraVariable" => NiagaraVariableProperty::new(
                     asset,
                     name,
@@ -1142,6 +1446,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "niagara")]
                 "NiagaraVariableWithOffset" => NiagaraVariableWithOffsetProperty::new(
                     asset,
                     name,
@@ -1178,6 +1483,7 @@ impl Property {
                 )?
                 .into(),
 
+                #[cfg(feature = "movies")]
                 "MovieSceneEvalTemplatePtr" => MovieSceneEvalTemplatePtrProperty::new(
                     asset,
                     name,
@@ -1186,6 +1492,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneTrackImplementationPtr" => {
                     MovieSceneTrackImplementationPtrProperty::new(
                         asset,
@@ -1196,6 +1503,7 @@ impl Property {
                     )?
                     .into()
                 }
+                #[cfg(feature = "movies")]
                 "MovieSceneEvaluationFieldEntityTree" => {
                     MovieSceneEvaluationFieldEntityTreeProperty::new(
                         asset,
@@ -1206,6 +1514,7 @@ impl Property {
                     )?
                     .into()
                 }
+                #[cfg(feature = "movies")]
                 "MovieSceneSubSequenceTree" => MovieSceneSubSequenceTreeProperty::new(
                     asset,
                     name,
@@ -1214,6 +1523,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneSequenceInstanceDataPtr" => {
                     MovieSceneSequenceInstanceDataPtrProperty::new(
                         asset,
@@ -1224,6 +1534,7 @@ impl Property {
                     )?
                     .into()
                 }
+                #[cfg(feature = "movies")]
                 "SectionEvaluationDataTree" => SectionEvaluationDataTreeProperty::new(
                     asset,
                     name,
@@ -1232,6 +1543,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneTrackFieldData" => MovieSceneTrackFieldDataProperty::new(
                     asset,
                     name,
@@ -1240,6 +1552,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneEventParameters" => MovieSceneEventParametersProperty::new(
                     asset,
                     name,
@@ -1248,6 +1561,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneFloatChannel" => MovieSceneFloatChannelProperty::new(
                     asset,
                     name,
@@ -1256,6 +1570,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneFloatValue" => MovieSceneFloatValueProperty::new(
                     asset,
                     name,
@@ -1264,6 +1579,34 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
+                "MovieSceneDoubleChannel" => MovieSceneDoubleChannelProperty::new(
+                    asset,
+                    name,
+                    ancestry,
+                    include_header,
+                    duplication_index,
+                )?
+                .into(),
+                #[cfg(feature = "movies")]
+                "MovieSceneDoubleValue" => MovieSceneDoubleValueProperty::new(
+                    asset,
+                    name,
+                    ancestry,
+                    include_header,
+                    duplication_index,
+                )?
+                .into(),
+                #[cfg(feature = "movies")]
+                "FrameNumber" => FrameNumberProperty::new(
+                    asset,
+                    name,
+                    ancestry,
+                    include_header,
+                    duplication_index,
+                )?
+                .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneFrameRange" => MovieSceneFrameRangeProperty::new(
                     asset,
                     name,
@@ -1272,6 +1615,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneSegment" => MovieSceneSegmentProperty::new(
                     asset,
                     name,
@@ -1280,6 +1624,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneSegmentIdentifier" => MovieSceneSegmentIdentifierProperty::new(
                     asset,
                     name,
@@ -1288,6 +1633,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneTrackIdentifier" => MovieSceneTrackIdentifierProperty::new(
                     asset,
                     name,
@@ -1296,6 +1642,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneSequenceId" => MovieSceneSequenceIdProperty::new(
                     asset,
                     name,
@@ -1304,6 +1651,7 @@ impl Property {
                     duplication_index,
                 )?
                 .into(),
+                #[cfg(feature = "movies")]
                 "MovieSceneEvaluationKey" => MovieSceneEvaluationKeyProperty::new(
                     asset,
                     name,
@@ -1328,20 +1676,28 @@ impl Property {
     }
 
     /// Writes a property to an ArchiveWriter
+    ///
+    /// Unversioned properties are written without a property tag,
+    /// they are identified by the unversioned header instead.
     pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         property: &Property,
         asset: &mut Writer,
         include_header: bool,
     ) -> Result<usize, Error> {
+        if asset.has_unversioned_properties() {
+            let begin = asset.position();
+            property.write(asset, include_header)?;
+            return Ok(begin as usize);
+        }
+
         asset.write_fname(&property.get_name())?;
 
         let property_serialized_name = property.to_serialized_name();
-        asset.write_fname(
-            &asset
-                .get_name_map()
-                .get_mut()
-                .add_fname(&property_serialized_name),
-        )?;
+        let property_serialized_name = asset
+            .get_name_map()
+            .get_mut()
+            .add_fname(&property_serialized_name);
+        asset.write_fname(&property_serialized_name)?;
 
         let begin = asset.position();
         asset.write_i32::<LE>(0)?; // initial length
@@ -1355,19 +1711,110 @@ impl Property {
         Ok(begin as usize)
     }
 
+    /// Get the type name this property is serialized with as an array, set or map entry
+    ///
+    /// Raw struct properties are serialized as `StructProperty` entries.
+    pub fn to_array_entry_type(&self) -> String {
+        match self {
+            Property::RawStructProperty(_) => String::from("StructProperty"),
+            property => property.to_serialized_name(),
+        }
+    }
+
     /// Check if a property type has custom serialization
     pub fn has_custom_serialization(name: &str) -> bool {
         CUSTOM_SERIALIZATION.contains(&name)
     }
+
+    /// Set this property's ancestry and derive the ancestry of all child properties from it
+    ///
+    /// Child ancestries are derived the same way as when reading an asset,
+    /// so manually constructed properties can be found in usmap mappings.
+    pub fn set_ancestry(&mut self, ancestry: Ancestry) {
+        match self {
+            Property::StructProperty(property) => property.set_ancestry(ancestry),
+            Property::ArrayProperty(property) => property.set_ancestry(ancestry),
+            Property::SetProperty(property) => property.set_ancestry(ancestry),
+            Property::MapProperty(property) => property.set_ancestry(ancestry),
+            property => *property.get_ancestry_mut() = ancestry,
+        }
+    }
+
+    /// Call `visitor` for this property and all of its child properties, depth-first
+    ///
+    /// Children are the values of struct, array and set properties and the keys and values of map properties.
+    pub fn visit(&self, visitor: &mut impl FnMut(&Property)) {
+        visitor(self);
+        match self {
+            Property::StructProperty(property) => {
+                for child in &property.value {
+                    child.visit(visitor);
+                }
+            }
+            Property::ArrayProperty(property) => {
+                for child in &property.value {
+                    child.visit(visitor);
+                }
+            }
+            Property::SetProperty(property) => {
+                let removed_items = &property.removed_items.value;
+                for child in property.value.value.iter().chain(removed_items) {
+                    child.visit(visitor);
+                }
+            }
+            Property::MapProperty(property) => {
+                for (_, key, value) in property.value.iter() {
+                    key.visit(visitor);
+                    value.visit(visitor);
+                }
+                for key in property.keys_to_remove.iter().flatten() {
+                    key.visit(visitor);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Call `visitor` for this property and all of its child properties mutably, depth-first
+    ///
+    /// Keys of map properties are not visited, changing them would invalidate the map.
+    pub fn visit_mut(&mut self, visitor: &mut impl FnMut(&mut Property)) {
+        visitor(self);
+        match self {
+            Property::StructProperty(property) => {
+                for child in &mut property.value {
+                    child.visit_mut(visitor);
+                }
+            }
+            Property::ArrayProperty(property) => {
+                for child in &mut property.value {
+                    child.visit_mut(visitor);
+                }
+            }
+            Property::SetProperty(property) => {
+                let removed_items = &mut property.removed_items.value;
+                for child in property.value.value.iter_mut().chain(removed_items) {
+                    child.visit_mut(visitor);
+                }
+            }
+            Property::MapProperty(property) => {
+                for value in property.value.values_mut() {
+                    value.visit_mut(visitor);
+                }
+            }
+            _ => {}
+        }
+    }
 }
 
 /// Implements `ToSerializedName` trait for properties
 macro_rules! property_inner_serialized_name {
-    ($($inner:ident : $name:expr),*) => {
+    ($($(#[$meta:meta])* $inner:ident : $name:expr),*) => {
         impl ToSerializedName for Property {
             fn to_serialized_name(&self) -> String {
                 match self {
                     $(
+                        $(#[$meta])*
                         Self::$inner(_) => String::from($name),
                     )*
                     Self::UnknownProperty(unk) => unk
@@ -1399,6 +1846,7 @@ property_inner_serialized_name! {
     PerPlatformBoolProperty: "PerPlatformBool",
     PerPlatformIntProperty: "PerPlatformInt",
     RichCurveKeyProperty: "RichCurveKey",
+    SimpleCurveKeyProperty: "SimpleCurveKey",
     SoftAssetPathProperty: "SoftAssetPath",
     TimeSpanProperty: "Timespan",
     DateTimeProperty: "DateTime",
@@ -1433,6 +1881,7 @@ property_inner_serialized_name! {
     MulticastSparseDelegateProperty: "MulticastSparseDelegateProperty",
     MulticastInlineDelegateProperty: "MulticastInlineDelegateProperty",
     DelegateProperty: "DelegateProperty",
+    FieldPathProperty: "FieldPathProperty",
     NameProperty: "NameProperty",
     ObjectProperty: "ObjectProperty",
     AssetObjectProperty: "AssetObjectProperty",
@@ -1445,31 +1894,77 @@ property_inner_serialized_name! {
 
     FontCharacterProperty: "FontCharacter",
     UniqueNetIdProperty: "UniqueNetIdRepl",
+    #[cfg(feature = "niagara")]
     NiagaraVariableProperty: "NiagaraVariable",
+    #[cfg(feature = "niagara")]
     NiagaraVariableWithOffsetProperty: "NiagaraVariableWithOffset",
     FontDataProperty: "FontData",
     FloatRangeProperty: "FloatRange",
     RawStructProperty: "RawStructProperty",
 
+    #[cfg(feature = "movies")]
     MovieSceneEvalTemplatePtrProperty: "MovieSceneEvalTemplatePtr",
+    #[cfg(feature = "movies")]
     MovieSceneTrackImplementationPtrProperty: "MovieSceneTrackImplementationPtr",
+    #[cfg(feature = "movies")]
     MovieSceneEvaluationFieldEntityTreeProperty: "MovieSceneEvaluationFieldEntityTree",
+    #[cfg(feature = "movies")]
     MovieSceneSubSequenceTreeProperty: "MovieSceneSubSequenceTree",
+    #[cfg(feature = "movies")]
     MovieSceneSequenceInstanceDataPtrProperty: "MovieSceneSequenceInstanceDataPtr",
+    #[cfg(feature = "movies")]
     SectionEvaluationDataTreeProperty: "SectionEvaluationDataTree",
+    #[cfg(feature = "movies")]
     MovieSceneTrackFieldDataProperty: "MovieSceneTrackFieldData",
+    #[cfg(feature = "movies")]
     MovieSceneEventParametersProperty: "MovieSceneEventParameters",
+    #[cfg(feature = "movies")]
     MovieSceneFloatChannelProperty: "MovieSceneFloatChannel",
+    #[cfg(feature = "movies")]
     MovieSceneFloatValueProperty: "MovieSceneFloatValue",
+    #[cfg(feature = "movies")]
+    MovieSceneDoubleChannelProperty: "MovieSceneDoubleChannel",
+    #[cfg(feature = "movies")]
+    MovieSceneDoubleValueProperty: "MovieSceneDoubleValue",
+    #[cfg(feature = "movies")]
+    FrameNumberProperty: "FrameNumber",
+    #[cfg(feature = "movies")]
     MovieSceneFrameRangeProperty: "MovieSceneFrameRange",
+    #[cfg(feature = "movies")]
     MovieSceneSegmentProperty: "MovieSceneSegment",
+    #[cfg(feature = "movies")]
     MovieSceneSegmentIdentifierProperty: "MovieSceneSegmentIdentifier",
+    #[cfg(feature = "movies")]
     MovieSceneTrackIdentifierProperty: "MovieSceneTrackIdentifier",
+    #[cfg(feature = "movies")]
     MovieSceneSequenceIdProperty: "MovieSceneSequenceId",
+    #[cfg(feature = "movies")]
     MovieSceneEvaluationKeyProperty: "MovieSceneEvaluationKey"
 }
 
+/// Maximum amount of properties skipped or serialized by a single unversioned header fragment
+const MAX_FRAGMENT_NUM: u32 = UnversionedHeaderFragment::MAX_NUM as u32;
+
+/// Get the global index of a property in usmap mappings
+fn get_global_index(mappings: &Usmap, property: &Property) -> Result<u32, Error> {
+    match mappings.get_property_with_duplication_index(
+        &property.get_name(),
+        property.get_ancestry(),
+        property.get_duplication_index() as u32,
+    ) {
+        Some((_, global_index)) => Ok(global_index),
+        None => property.get_name().get_content(|name| {
+            Err(PropertyError::no_mapping(name, property.get_ancestry()).into())
+        }),
+    }
+}
+
 /// Generate property unversioned header
+///
+/// Properties are sorted by their index in the mappings, every [`EmptyProperty`] is marked as zeroed.
+/// If the archive [zero-masks defaults](ArchiveTrait::zero_mask_defaults), properties
+/// with the [zero value](Property::is_zero_value) of their type are zeroed as well.
+/// Returns the header and the properties in the order they need to be serialized in.
 pub fn generate_unversioned_header<W: ArchiveWriter<impl PackageIndexTrait>>(
     archive: &W,
     properties: &[Property],
@@ -1483,109 +1978,90 @@ pub fn generate_unversioned_header<W: ArchiveWriter<impl PackageIndexTrait>>(
         return Ok(None);
     };
 
-    let mut first_global_index = u32::MAX;
-    let mut last_global_index = u32::MIN;
-
-    let mut properties_to_process = HashSet::new();
-    let mut zero_properties: HashSet<u32> = HashSet::new();
-
-    for property in properties {
-        let Some((_, global_index)) = mappings.get_property_with_duplication_index(
-            &property.get_name(),
-            property.get_ancestry(),
-            property.get_duplication_index() as u32,
-        ) else {
-            return property.get_name().get_content(|name| {
-                Err(PropertyError::no_mapping(name, property.get_ancestry()).into())
-            });
-        };
-
-        if matches!(property, Property::EmptyProperty(_)) {
-            zero_properties.insert(global_index);
-        }
-
-        first_global_index = first_global_index.min(global_index);
-        last_global_index = last_global_index.max(global_index);
-        properties_to_process.insert(global_index);
+    let zero_mask_defaults = archive.zero_mask_defaults();
+    let mut indexed_properties = properties
+        .iter()
+        .map(|property| {
+            let mut property = property.clone();
+            if zero_mask_defaults && property.is_zero_value(mappings) {
+                property.set_zeroed();
+            }
+            get_global_index(mappings, &property).map(|global_index| (global_index, property))
+        })
+        .collect::<Result<Vec<_>, Error>>()?;
+    indexed_properties.sort_by_key(|(global_index, _)| *global_index);
+
+    if let Some(duplicate) = indexed_properties.windows(2).find(|e| e[0].0 == e[1].0) {
+        let (global_index, ref property) = duplicate[1];
+        return property.get_name().get_content(|name| {
+            Err(PropertyError::duplicate_unversioned_property(name, global_index).into())
+        });
     }
 
-    // Sort properties and generate header fragments
-    let mut sorted_properties = Vec::new();
-
     let mut fragments: Vec<UnversionedHeaderFragment> = Vec::new();
-    let mut last_num_before_fragment = 0;
-
-    if !properties_to_process.is_empty() {
-        loop {
-            let mut has_zeros = false;
-
-            // Find next contiguous properties chunk
-            let mut start_index = last_num_before_fragment;
-            while !properties_to_process.contains(&start_index) && start_index <= last_global_index
-            {
-                start_index += 1;
-            }
-
-            if start_index > last_global_index {
-                break;
-            }
-
-            // Process contiguous properties chunk
-            let mut end_index = start_index;
-            while properties_to_process.contains(&end_index) {
-                if zero_properties.contains(&end_index) {
-                    has_zeros = true;
-                }
+    let mut zero_mask = BitVec::<u8, Lsb0>::new();
+    let mut next_index = 0u32;
+
+    let mut chunk_start = 0;
+    while chunk_start < indexed_properties.len() {
+        // Find next contiguous properties chunk
+        let mut chunk_end = chunk_start + 1;
+        while chunk_end < indexed_properties.len()
+            && indexed_properties[chunk_end].0 == indexed_properties[chunk_end - 1].0 + 1
+        {
+            chunk_end += 1;
+        }
 
-                // todo: clone might not be needed
-                sorted_properties.push(properties[end_index as usize].clone());
-                end_index += 1;
-            }
+        // Create extra fragments for skips which don't fit into one fragment
+        let mut skip_num = indexed_properties[chunk_start].0 - next_index;
+        while skip_num > MAX_FRAGMENT_NUM {
+            next_index += MAX_FRAGMENT_NUM;
+            fragments.push(UnversionedHeaderFragment {
+                skip_num: MAX_FRAGMENT_NUM as u8,
+                value_num: 0,
+                first_num: next_index,
+                is_last: false,
+                has_zeros: false,
+            });
+            skip_num -= MAX_FRAGMENT_NUM;
+        }
 
-            // Create extra fragments for this chunk
-            let mut skip_num = start_index - last_num_before_fragment - 1;
-            let mut value_num = (end_index - 1) - start_index + 1;
-
-            while skip_num > i8::MAX as u32 {
-                fragments.push(UnversionedHeaderFragment {
-                    skip_num: i8::MAX as u8,
-                    value_num: 0,
-                    first_num: 0,
-                    is_last: false,
-                    has_zeros: false,
-                });
-                skip_num -= i8::MAX as u32;
-            }
-            while value_num > i8::MAX as u32 {
-                fragments.push(UnversionedHeaderFragment {
-                    skip_num: 0,
-                    value_num: i8::MAX as u8,
-                    first_num: 0,
-                    is_last: false,
-                    has_zeros: false,
-                });
-                value_num -= i8::MAX as u32;
+        // Create fragments for this chunk, splitting it if it has too many values
+        for values in indexed_properties[chunk_start..chunk_end].chunks(MAX_FRAGMENT_NUM as usize) {
+            let has_zeros = values.iter().any(|(_, property)| property.is_zeroed());
+            if has_zeros {
+                zero_mask.extend(values.iter().map(|(_, property)| property.is_zeroed()));
             }
 
-            // Create the main fragment for this chunk
-            let fragment = UnversionedHeaderFragment {
+            let first_num = values[0].0;
+            fragments.push(UnversionedHeaderFragment {
                 skip_num: skip_num as u8,
-                value_num: value_num as u8,
-                first_num: start_index as u8,
+                value_num: values.len() as u8,
+                first_num,
                 is_last: false,
                 has_zeros,
-            };
+            });
 
-            fragments.push(fragment);
-            last_num_before_fragment = end_index - 1;
+            skip_num = 0;
+            next_index = first_num + values.len() as u32;
         }
-    } else {
-        fragments.push(parent_name.get_content(|name| UnversionedHeaderFragment {
-            skip_num: usize::min(mappings.get_all_properties(name).len(), i8::MAX as usize) as u8,
-            value_num: 0,
-            first_num: 0,
-            is_last: true,
-            has_zeros: false,
+
+        chunk_start = chunk_end;
+    }
+
+    if fragments.is_empty() {
+        fragments.push(parent_name.get_content(|name| {
+            let skip_num = usize::min(
+                mappings.get_all_properties(name).len(),
+                MAX_FRAGMENT_NUM as usize,
+            ) as u32;
+            UnversionedHeaderFragment {
+                skip_num: skip_num as u8,
+                value_num: 0,
+                first_num: skip_num,
+                is_last: true,
+                has_zeros: false,
+            }
         }));
     }
 
@@ -1593,19 +2069,7 @@ pub fn generate_unversioned_header<W: ArchiveWriter<impl PackageIndexTrait>>(
         fragment.is_last = true;
     }
 
-    let mut has_non_zero_values = false;
-    let mut zero_mask = BitVec::<u8, Lsb0>::new();
-
-    for fragment in fragments.iter().filter(|e| e.has_zeros) {
-        for i in 0..fragment.value_num {
-            let is_zero = zero_properties.contains(&((fragment.first_num + i) as u32));
-            if !is_zero {
-                has_non_zero_values = true;
-            }
-            zero_mask.push(is_zero);
-        }
-    }
-
+    let has_non_zero_values = indexed_properties.iter().any(|(_, e)| !e.is_zeroed());
     let unversioned_property_index =
         fragments.first().map(|e| e.first_num).unwrap_or_default() as usize;
 
@@ -1618,5 +2082,72 @@ pub fn generate_unversioned_header<W: ArchiveWriter<impl PackageIndexTrait>>(
         zero_mask_index: 0,
     };
 
+    let sorted_properties = indexed_properties
+        .into_iter()
+        .map(|(_, property)| property)
+        .collect();
+
     Ok(Some((header, sorted_properties)))
 }
+
+/// Validate an unversioned header against the properties it describes
+///
+/// `properties` need to be in serialization order, like the ones returned by [`generate_unversioned_header`].
+/// Checks that every property is serialized at the index of its mapping and that
+/// the zero mask marks exactly the [`EmptyProperty`] instances as zeroed.
+/// The engine reads values at the wrong offsets if these don't match, which usually crashes the game.
+pub fn validate_unversioned_header<A: ArchiveTrait<impl PackageIndexTrait>>(
+    archive: &A,
+    header: &UnversionedHeader,
+    properties: &[Property],
+) -> Result<(), Error> {
+    let mappings = archive
+        .get_mappings()
+        .ok_or_else(PropertyError::no_mappings)?;
+
+    header.validate_fragments()?;
+
+    let indices = header.get_property_indices();
+    if indices.len() != properties.len() {
+        return Err(PropertyError::unversioned_header_mismatch(format!(
+            "header describes {} properties, got {}",
+            indices.len(),
+            properties.len()
+        ))
+        .into());
+    }
+
+    for (property, (index, is_zero)) in properties.iter().zip(indices) {
+        let global_index = get_global_index(mappings, property)?;
+        let name = property.get_name().get_owned_content();
+
+        if global_index != index {
+            return Err(PropertyError::unversioned_header_mismatch(format!(
+                "property {name} is mapped to index {global_index}, but serialized at index {index}"
+            ))
+            .into());
+        }
+
+        if property.is_zeroed() != is_zero {
+            return Err(PropertyError::unversioned_header_mismatch(format!(
+                "property {name} is {}zeroed, but the zero mask doesn't match",
+                match property.is_zeroed() {
+                    true => "",
+                    false => "not ",
+                }
+            ))
+            .into());
+        }
+    }
+
+    let has_non_zero_values = properties.iter().any(|e| !e.is_zeroed());
+    if header.has_non_zero_values != has_non_zero_values {
+        return Err(PropertyError::unversioned_header_mismatch(format!(
+            "header has_non_zero_values is {}, expected {has_non_zero_values}",
+            header.has_non_zero_values
+        ))
+        .into());
+    }
+
+    Ok(())
+}
@@ -3,6 +3,7 @@
 use std::hash::Hash;
 
 use unreal_asset_base::containers::indexed_map::IndexedMap;
+use unreal_asset_base::reader::StructFallbackBehavior;
 
 use crate::property_prelude::*;
 
@@ -40,6 +41,26 @@ impl Hash for MapProperty {
 }
 
 impl MapProperty {
+    /// Set this property's ancestry and derive the ancestry of map keys and values from it
+    pub fn set_ancestry(&mut self, ancestry: Ancestry) {
+        let child_ancestry = ancestry.with_parent(self.name.clone());
+
+        self.value = std::mem::take(&mut self.value)
+            .into_iter()
+            .map(|(_, mut key, mut value)| {
+                key.set_ancestry(child_ancestry.clone());
+                value.set_ancestry(child_ancestry.clone());
+                (key, value)
+            })
+            .collect();
+        if let Some(keys_to_remove) = &mut self.keys_to_remove {
+            for key in keys_to_remove {
+                key.set_ancestry(child_ancestry.clone());
+            }
+        }
+        self.ancestry = ancestry;
+    }
+
     /// Map type_name to a `Property` and read it from an asset
     fn map_type_to_class<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -75,20 +96,31 @@ impl MapProperty {
                     }
                 }
 
-                let struct_type = struct_type
-                    .or_else(|| {
-                        name.get_content(|name| match is_key {
-                            true => asset
-                                .get_map_key_override()
-                                .get_by_key(name)
-                                .map(|s| FName::new_dummy(s.to_owned(), 0)),
-                            false => asset
-                                .get_map_value_override()
-                                .get_by_key(name)
-                                .map(|s| FName::new_dummy(s.to_owned(), 0)),
-                        })
+                let struct_type = struct_type.or_else(|| {
+                    name.get_content(|name| match is_key {
+                        true => asset
+                            .get_map_key_override()
+                            .get_by_key(name)
+                            .map(|s| FName::new_dummy(s.to_owned(), 0)),
+                        false => asset
+                            .get_map_value_override()
+                            .get_by_key(name)
+                            .map(|s| FName::new_dummy(s.to_owned(), 0)),
                     })
-                    .unwrap_or_else(|| FName::from_slice("Generic"));
+                });
+
+                let struct_type = match struct_type {
+                    Some(struct_type) => struct_type,
+                    // map entries have no known size, raw structs can't be preserved
+                    None if asset.get_parse_options().struct_fallback
+                        != StructFallbackBehavior::Guess =>
+                    {
+                        return name.get_content(|name| {
+                            Err(PropertyError::unknown_struct_type(name, ancestry).into())
+                        })
+                    }
+                    None => FName::from_slice("Generic"),
+                };
 
                 Ok(StructProperty::custom_header(
                     asset,
@@ -156,7 +188,7 @@ impl MapProperty {
         let type_2 = type_2.ok_or_else(|| Error::invalid_file("No type2".to_string()))?;
 
         for _ in 0..num_keys_to_remove as usize {
-            let mut vec = Vec::with_capacity(num_keys_to_remove as usize);
+            let mut vec = Vec::with_capacity(asset.check_array_len(num_keys_to_remove)?);
             vec.push(MapProperty::map_type_to_class(
                 asset,
                 type_1.clone(),
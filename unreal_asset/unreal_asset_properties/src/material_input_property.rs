@@ -54,6 +54,7 @@ pub struct ScalarMaterialInputProperty {
     pub value: OrderedFloat<f32>,
 }
 impl_property_data_trait!(ScalarMaterialInputProperty);
+impl_float_value!(ScalarMaterialInputProperty, f32);
 
 /// Shading model material input property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -1,9 +1,29 @@
 //! Unreal movies
+//!
+//! Only the movie scene structs with native serialization that are listed here are supported,
+//! all other movie scene structs are read as regular tagged structs:
+//!
+//! * channels and values: `MovieSceneFloatChannel`, `MovieSceneDoubleChannel`,
+//!   `MovieSceneFloatValue`, `MovieSceneDoubleValue`
+//! * frames: `FrameNumber`, `MovieSceneFrameRange`
+//! * evaluation: `MovieSceneEvalTemplatePtr`, `MovieSceneTrackImplementationPtr`,
+//!   `MovieSceneEvaluationFieldEntityTree`, `MovieSceneSubSequenceTree`,
+//!   `MovieSceneSequenceInstanceDataPtr`, `SectionEvaluationDataTree`, `MovieSceneTrackFieldData`,
+//!   `MovieSceneEvaluationKey`
+//! * identifiers: `MovieSceneSegment`, `MovieSceneSegmentIdentifier`, `MovieSceneTrackIdentifier`,
+//!   `MovieSceneSequenceId`
+//! * events: `MovieSceneEventParameters`
+//!
+//! Other natively serialized structs, including the ones added in UE5 like time warp variants,
+//! are not implemented. Exports containing them fail to parse and are kept as `RawExport`s.
 
 use crate::property_prelude::*;
 use crate::rich_curve_key_property::RichCurveTangentWeightMode;
 
 pub mod enums;
+pub mod frame_number_property;
+pub mod movie_scene_double_channel_property;
+pub mod movie_scene_double_value_property;
 pub mod movie_scene_eval_template_ptr_property;
 pub mod movie_scene_evaluation;
 pub mod movie_scene_evaluation_field_entity_tree_property;
@@ -37,6 +57,16 @@ pub struct MovieSceneTangentData {
     /// Is compiled with clang win64
     clang_win64: bool,
 }
+impl_float_value!(MovieSceneTangentData {
+    arrive_tangent,
+    set_arrive_tangent: f32,
+    leave_tangent,
+    set_leave_tangent: f32,
+    arrive_tangent_weight,
+    set_arrive_tangent_weight: f32,
+    leave_tangent_weight,
+    set_leave_tangent_weight: f32,
+});
 
 impl MovieSceneTangentData {
     /// Read `MovieSceneTangentData` from an asset
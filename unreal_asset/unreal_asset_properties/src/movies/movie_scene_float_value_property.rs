@@ -17,6 +17,7 @@ pub struct MovieSceneFloatValue {
     /// Tangent mode
     pub tangent_mode: RichCurveTangentMode,
 }
+impl_float_value!(MovieSceneFloatValue, f32);
 
 impl MovieSceneFloatValue {
     /// Read a `MovieSceneFloatValue` from an asset
@@ -113,7 +113,8 @@ impl PropertyTrait for NiagaraVariableProperty {
         }
 
         if !asset.has_unversioned_properties() {
-            asset.write_fname(&asset.get_name_map().get_mut().add_fname("None"))?;
+            let none = asset.get_name_map().get_mut().add_fname("None");
+            asset.write_fname(&none)?;
         }
         asset.write_i32::<LE>(self.variable_offset)?;
 
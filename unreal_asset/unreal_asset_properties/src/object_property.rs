@@ -1,5 +1,7 @@
 //! Object properties
 
+use unreal_asset_base::containers::{NameMap, SharedResource};
+
 use crate::property_prelude::*;
 
 /// Object property
@@ -36,6 +38,10 @@ pub struct AssetObjectProperty {
 impl_property_data_trait!(AssetObjectProperty);
 
 /// Top level asset path
+///
+/// Before UE 5.1 a top level asset path is a single name containing the full path, e.g. `/Game/Items/Conveyor.Conveyor`,
+/// from 5.1 on it's a pair of the package name and the asset name.
+/// Either form can be written to any version, it gets converted to the form the asset's version uses.
 #[derive(FNameContainer, Debug, Clone, Default, Hash, PartialEq, Eq)]
 pub struct TopLevelAssetPath {
     /// Package name that contains the asset e.g. /Some/Path/Package
@@ -55,6 +61,53 @@ impl TopLevelAssetPath {
         }
     }
 
+    /// Get the package name, e.g. `/Game/Items/Conveyor`
+    pub fn get_package_name(&self) -> String {
+        match self.package_name {
+            Some(ref package_name) => package_name.get_owned_content(),
+            None => self
+                .asset_name
+                .get_content(|path| match path.split_once('.') {
+                    Some((package_name, _)) => package_name.to_string(),
+                    None => path.to_string(),
+                }),
+        }
+    }
+
+    /// Get the asset name within the package, e.g. `Conveyor`
+    pub fn get_asset_name(&self) -> String {
+        match self.package_name {
+            Some(_) if self.asset_name == "None" => String::new(),
+            Some(_) => self.asset_name.get_owned_content(),
+            None => self
+                .asset_name
+                .get_content(|path| match path.split_once('.') {
+                    Some((_, asset_name)) => asset_name.to_string(),
+                    None => String::new(),
+                }),
+        }
+    }
+
+    /// Get the full path, e.g. `/Game/Items/Conveyor.Conveyor`
+    pub fn get_path(&self) -> String {
+        match self.package_name {
+            Some(ref package_name) => {
+                let package_name = package_name.get_owned_content();
+                match self.get_asset_name() {
+                    asset_name if asset_name.is_empty() => package_name,
+                    asset_name => format!("{package_name}.{asset_name}"),
+                }
+            }
+            None => self.asset_name.get_owned_content(),
+        }
+    }
+
+    /// Check if this path doesn't point to anything
+    pub fn is_null(&self) -> bool {
+        let package_name = self.get_package_name();
+        package_name.is_empty() || package_name == "None"
+    }
+
     /// Read a `TopLevelAssetPath` from an asset
     pub fn read<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -73,21 +126,64 @@ impl TopLevelAssetPath {
         })
     }
 
+    /// Check if this path is in the form assets with the given UE5 object version use
+    pub fn is_version_form(&self, object_version_ue5: ObjectVersionUE5) -> bool {
+        let split =
+            object_version_ue5 >= ObjectVersionUE5::FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES;
+        split == self.package_name.is_some()
+    }
+
+    /// Convert this path to the form assets with the given UE5 object version use
+    ///
+    /// The names the converted path is made of are added to the name map.
+    pub fn to_version_form(
+        &self,
+        name_map: &mut SharedResource<NameMap>,
+        object_version_ue5: ObjectVersionUE5,
+    ) -> Self {
+        if self.is_version_form(object_version_ue5) {
+            return self.clone();
+        }
+
+        match self.package_name {
+            Some(_) => {
+                let path = self.get_path();
+                TopLevelAssetPath::new(None, name_map.get_mut().add_fname(&path))
+            }
+            None => {
+                let package_name = self.get_package_name();
+                let asset_name = match self.get_asset_name() {
+                    asset_name if asset_name.is_empty() => "None".to_string(),
+                    asset_name => asset_name,
+                };
+
+                let mut name_map = name_map.get_mut();
+                TopLevelAssetPath::new(
+                    Some(name_map.add_fname(&package_name)),
+                    name_map.add_fname(&asset_name),
+                )
+            }
+        }
+    }
+
     /// Write a `TopLevelAssetPath` to an asset
+    ///
+    /// The path has to be in the form the asset's version uses, see [`TopLevelAssetPath::to_version_form`].
     pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         &self,
         asset: &mut Writer,
     ) -> Result<(), Error> {
-        if asset.get_object_version_ue5()
-            >= ObjectVersionUE5::FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES
-        {
-            let Some(package_name) = self.package_name.as_ref() else {
-                return Err(Error::no_data("ObjectVersionUE5 is >= FSOFTOBJECTPATH_REMOVE_ASSET_PATH_FNAMES, but package_name is None".to_string()));
-            };
+        if !self.is_version_form(asset.get_object_version_ue5()) {
+            return Err(PropertyError::other(format!(
+                "TopLevelAssetPath {} is not in the form of the asset's version",
+                self.get_path()
+            ))
+            .into());
+        }
 
+        if let Some(ref package_name) = self.package_name {
             asset.write_fname(package_name)?;
         }
-
         asset.write_fname(&self.asset_name)?;
 
         Ok(())
@@ -95,6 +191,8 @@ impl TopLevelAssetPath {
 }
 
 /// Soft object path
+///
+/// Points to an asset, or to a subobject of an asset with a sub path, e.g. `/Game/Maps/Level.Level:PersistentLevel.Actor`.
 #[derive(FNameContainer, Debug, Clone, Default, Hash, PartialEq, Eq)]
 pub struct SoftObjectPath {
     /// Asset path
@@ -117,7 +215,64 @@ impl SoftObjectPath {
         })
     }
 
+    /// Create a `SoftObjectPath` from a package name, an asset name and an optional sub path
+    ///
+    /// The path is created in the UE 5.1 form, it's converted when written to an asset with an older version.
+    pub fn from_parts(package_name: FName, asset_name: FName, sub_path: Option<String>) -> Self {
+        SoftObjectPath {
+            asset_path: TopLevelAssetPath::new(Some(package_name), asset_name),
+            sub_path_string: sub_path,
+        }
+    }
+
+    /// Get the package name, e.g. `/Game/Maps/Level`
+    pub fn get_package_name(&self) -> String {
+        self.asset_path.get_package_name()
+    }
+
+    /// Get the asset name within the package, e.g. `Level`
+    pub fn get_asset_name(&self) -> String {
+        self.asset_path.get_asset_name()
+    }
+
+    /// Get the sub path, e.g. `PersistentLevel.Actor`, returns `None` if the path points to an asset
+    pub fn get_sub_path(&self) -> Option<&str> {
+        self.sub_path_string.as_deref().filter(|e| !e.is_empty())
+    }
+
+    /// Get the full path, e.g. `/Game/Maps/Level.Level:PersistentLevel.Actor`
+    pub fn get_path(&self) -> String {
+        let asset_path = self.asset_path.get_path();
+        match self.get_sub_path() {
+            Some(sub_path) => format!("{asset_path}:{sub_path}"),
+            None => asset_path,
+        }
+    }
+
+    /// Check if this path doesn't point to anything
+    pub fn is_null(&self) -> bool {
+        self.asset_path.is_null()
+    }
+
+    /// Convert this path to the form assets with the given UE5 object version use
+    ///
+    /// The names the converted path is made of are added to the name map.
+    pub fn to_version_form(
+        &self,
+        name_map: &mut SharedResource<NameMap>,
+        object_version_ue5: ObjectVersionUE5,
+    ) -> Self {
+        SoftObjectPath {
+            asset_path: self
+                .asset_path
+                .to_version_form(name_map, object_version_ue5),
+            sub_path_string: self.sub_path_string.clone(),
+        }
+    }
+
     /// Write a `SoftObjectPath` to an asset
+    ///
+    /// The path has to be in the form the asset's version uses, see [`SoftObjectPath::to_version_form`].
     pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         &self,
         asset: &mut Writer,
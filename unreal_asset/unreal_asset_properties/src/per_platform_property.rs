@@ -1,7 +1,98 @@
 //! Per platform properties
+//!
+//! Per platform properties are serialized as a cooked flag followed by the default value,
+//! uncooked assets additionally contain a map of per platform overrides.
 
 use crate::property_prelude::*;
 
+/// Implement a per platform property
+macro_rules! impl_per_platform_property {
+    (
+        $property_type:ident,
+        $value_type:ty,
+        $serialized_size:expr,
+        |$reader:ident| $read:expr,
+        |$writer:ident, $value:ident| $write:expr
+    ) => {
+        impl $property_type {
+            #[doc = concat!("Read a `", stringify!($property_type), "` from an asset")]
+            pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
+                $reader: &mut Reader,
+                name: FName,
+                ancestry: Ancestry,
+                include_header: bool,
+                _length: i64,
+                duplication_index: i32,
+            ) -> Result<Self, Error> {
+                let property_guid = optional_guid!($reader, include_header);
+
+                let cooked = $reader.read_i32::<LE>()? != 0;
+                let default_value = $read;
+
+                let mut per_platform = Vec::new();
+                if !cooked {
+                    let num_entries = $reader.read_i32::<LE>()?;
+                    per_platform.reserve($reader.check_array_len(num_entries)?);
+
+                    for _ in 0..num_entries {
+                        let platform = $reader.read_fname()?;
+                        per_platform.push((platform, $read));
+                    }
+                }
+
+                Ok($property_type {
+                    name,
+                    ancestry,
+                    property_guid,
+                    duplication_index,
+                    cooked,
+                    default_value,
+                    per_platform,
+                })
+            }
+
+            /// Get the value used on a platform
+            ///
+            /// Falls back to the default value if there is no override for the platform.
+            pub fn get_platform_value(&self, platform: &str) -> &$value_type {
+                self.per_platform
+                    .iter()
+                    .find(|(name, _)| name == platform)
+                    .map(|(_, value)| value)
+                    .unwrap_or(&self.default_value)
+            }
+        }
+
+        impl PropertyTrait for $property_type {
+            fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+                &self,
+                $writer: &mut Writer,
+                include_header: bool,
+            ) -> Result<usize, Error> {
+                optional_guid_write!(self, $writer, include_header);
+
+                $writer.write_i32::<LE>(i32::from(self.cooked))?;
+                let $value = &self.default_value;
+                $write;
+
+                let mut size = size_of::<i32>() + $serialized_size;
+                if !self.cooked {
+                    $writer.write_i32::<LE>(self.per_platform.len() as i32)?;
+                    size += size_of::<i32>();
+
+                    for (platform, $value) in &self.per_platform {
+                        $writer.write_fname(platform)?;
+                        $write;
+                        size += size_of::<i32>() * 2 + $serialized_size;
+                    }
+                }
+
+                Ok(size)
+            }
+        }
+    };
+}
+
 /// Per platform bool property
 #[derive(FNameContainer, Debug, Hash, Clone, Default, PartialEq, Eq)]
 pub struct PerPlatformBoolProperty {
@@ -13,8 +104,12 @@ pub struct PerPlatformBoolProperty {
     pub property_guid: Option<Guid>,
     /// Property duplication index
     pub duplication_index: i32,
-    /// Values for each platform
-    pub value: Vec<bool>,
+    /// Was this property cooked, cooked properties don't have per platform overrides
+    pub cooked: bool,
+    /// Default value
+    pub default_value: bool,
+    /// Per platform overrides
+    pub per_platform: Vec<(FName, bool)>,
 }
 impl_property_data_trait!(PerPlatformBoolProperty);
 
@@ -29,8 +124,12 @@ pub struct PerPlatformIntProperty {
     pub property_guid: Option<Guid>,
     /// Property duplication index
     pub duplication_index: i32,
-    /// Values for each platform
-    pub value: Vec<i32>,
+    /// Was this property cooked, cooked properties don't have per platform overrides
+    pub cooked: bool,
+    /// Default value
+    pub default_value: i32,
+    /// Per platform overrides
+    pub per_platform: Vec<(FName, i32)>,
 }
 impl_property_data_trait!(PerPlatformIntProperty);
 
@@ -45,139 +144,38 @@ pub struct PerPlatformFloatProperty {
     pub property_guid: Option<Guid>,
     /// Property duplication index
     pub duplication_index: i32,
-    /// Values for each platform
-    pub value: Vec<OrderedFloat<f32>>,
+    /// Was this property cooked, cooked properties don't have per platform overrides
+    pub cooked: bool,
+    /// Default value
+    pub default_value: OrderedFloat<f32>,
+    /// Per platform overrides
+    pub per_platform: Vec<(FName, OrderedFloat<f32>)>,
 }
 impl_property_data_trait!(PerPlatformFloatProperty);
-
-impl PerPlatformBoolProperty {
-    /// Read a `PerPlatformBoolProperty` from an asset
-    pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
-        asset: &mut Reader,
-        name: FName,
-        ancestry: Ancestry,
-        include_header: bool,
-        _length: i64,
-        duplication_index: i32,
-    ) -> Result<Self, Error> {
-        let property_guid = optional_guid!(asset, include_header);
-
-        let num_entries = asset.read_i32::<LE>()?;
-        let mut value = Vec::with_capacity(num_entries as usize);
-
-        for _i in 0..num_entries as usize {
-            value.push(asset.read_bool()?);
-        }
-
-        Ok(PerPlatformBoolProperty {
-            name,
-            ancestry,
-            property_guid,
-            duplication_index,
-            value,
-        })
-    }
-}
-
-impl PropertyTrait for PerPlatformBoolProperty {
-    fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
-        &self,
-        asset: &mut Writer,
-        include_header: bool,
-    ) -> Result<usize, Error> {
-        optional_guid_write!(self, asset, include_header);
-        asset.write_i32::<LE>(self.value.len() as i32)?;
-        for entry in &self.value {
-            asset.write_bool(*entry)?;
-        }
-        Ok(size_of::<i32>() + size_of::<bool>() * self.value.len())
-    }
-}
-
-impl PerPlatformIntProperty {
-    /// Read a `PerPlatformIntProperty` from an asset
-    pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
-        asset: &mut Reader,
-        name: FName,
-        ancestry: Ancestry,
-        include_header: bool,
-        _length: i64,
-        duplication_index: i32,
-    ) -> Result<Self, Error> {
-        let property_guid = optional_guid!(asset, include_header);
-
-        let num_entries = asset.read_i32::<LE>()?;
-        let mut value = Vec::with_capacity(num_entries as usize);
-
-        for _i in 0..num_entries as usize {
-            value.push(asset.read_i32::<LE>()?);
-        }
-
-        Ok(PerPlatformIntProperty {
-            name,
-            ancestry,
-            property_guid,
-            duplication_index,
-            value,
-        })
-    }
-}
-
-impl PropertyTrait for PerPlatformIntProperty {
-    fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
-        &self,
-        asset: &mut Writer,
-        include_header: bool,
-    ) -> Result<usize, Error> {
-        optional_guid_write!(self, asset, include_header);
-        asset.write_i32::<LE>(self.value.len() as i32)?;
-        for entry in &self.value {
-            asset.write_i32::<LE>(*entry)?;
-        }
-        Ok(size_of::<i32>() + size_of::<i32>() * self.value.len())
-    }
-}
-
-impl PerPlatformFloatProperty {
-    /// Read a `PerPlatformFloatProperty` from an asset
-    pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
-        asset: &mut Reader,
-        name: FName,
-        ancestry: Ancestry,
-        include_header: bool,
-        _length: i64,
-        duplication_index: i32,
-    ) -> Result<Self, Error> {
-        let property_guid = optional_guid!(asset, include_header);
-
-        let num_entries = asset.read_i32::<LE>()?;
-        let mut value = Vec::with_capacity(num_entries as usize);
-
-        for _i in 0..num_entries as usize {
-            value.push(OrderedFloat(asset.read_f32::<LE>()?));
-        }
-
-        Ok(PerPlatformFloatProperty {
-            name,
-            ancestry,
-            property_guid,
-            duplication_index,
-            value,
-        })
-    }
-}
-
-impl PropertyTrait for PerPlatformFloatProperty {
-    fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
-        &self,
-        asset: &mut Writer,
-        include_header: bool,
-    ) -> Result<usize, Error> {
-        optional_guid_write!(self, asset, include_header);
-        asset.write_i32::<LE>(self.value.len() as i32)?;
-        for entry in &self.value {
-            asset.write_f32::<LE>(entry.0)?;
-        }
-        Ok(size_of::<i32>() + size_of::<f32>() * self.value.len())
-    }
-}
+impl_float_value!(PerPlatformFloatProperty {
+    default_value, set_default_value: f32,
+    per_platform, set_per_platform: Vec<(FName, f32)>,
+});
+
+// bools are serialized as 32-bit integers here
+impl_per_platform_property!(
+    PerPlatformBoolProperty,
+    bool,
+    size_of::<i32>(),
+    |asset| asset.read_i32::<LE>()? != 0,
+    |asset, value| asset.write_i32::<LE>(i32::from(*value))?
+);
+impl_per_platform_property!(
+    PerPlatformIntProperty,
+    i32,
+    size_of::<i32>(),
+    |asset| asset.read_i32::<LE>()?,
+    |asset, value| asset.write_i32::<LE>(*value)?
+);
+impl_per_platform_property!(
+    PerPlatformFloatProperty,
+    OrderedFloat<f32>,
+    size_of::<f32>(),
+    |asset| OrderedFloat(asset.read_f32::<LE>()?),
+    |asset, value| asset.write_f32::<LE>(value.0)?
+);
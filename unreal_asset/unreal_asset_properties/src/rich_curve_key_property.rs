@@ -102,6 +102,20 @@ pub struct RichCurveKeyProperty {
     pub leave_tangent_weight: OrderedFloat<f32>,
 }
 impl_property_data_trait!(RichCurveKeyProperty);
+impl_float_value!(RichCurveKeyProperty {
+    time,
+    set_time: f32,
+    value,
+    set_value: f32,
+    arrive_tangent,
+    set_arrive_tangent: f32,
+    arrive_tangent_weight,
+    set_arrive_tangent_weight: f32,
+    leave_tangent,
+    set_leave_tangent: f32,
+    leave_tangent_weight,
+    set_leave_tangent_weight: f32,
+});
 
 impl RichCurveKeyProperty {
     /// Read a `RichCurveKeyProperty` from an asset
@@ -21,6 +21,10 @@ pub struct WeightedRandomSamplerProperty {
     pub total_weight: OrderedFloat<f32>,
 }
 impl_property_data_trait!(WeightedRandomSamplerProperty);
+impl_float_value!(WeightedRandomSamplerProperty {
+    prob, set_prob: Vec<f32>,
+    total_weight, set_total_weight: f32,
+});
 
 /// Skeletal mesh area weighted triangle sampler
 #[derive(FNameContainer, Debug, Hash, Clone, Default, PartialEq, Eq)]
@@ -41,6 +45,10 @@ pub struct SkeletalMeshAreaWeightedTriangleSampler {
     pub total_weight: OrderedFloat<f32>,
 }
 impl_property_data_trait!(SkeletalMeshAreaWeightedTriangleSampler);
+impl_float_value!(SkeletalMeshAreaWeightedTriangleSampler {
+    prob, set_prob: Vec<f32>,
+    total_weight, set_total_weight: f32,
+});
 
 /// Skeleetal mesh sampling lod built data property
 #[derive(FNameContainer, Debug, Hash, Clone, Default, PartialEq, Eq)]
@@ -71,13 +79,13 @@ impl WeightedRandomSamplerProperty {
         let property_guid = optional_guid!(asset, include_header);
 
         let size = asset.read_i32::<LE>()?;
-        let mut prob = Vec::with_capacity(size as usize);
+        let mut prob = Vec::with_capacity(asset.check_array_len(size)?);
         for _i in 0..size as usize {
             prob.push(OrderedFloat(asset.read_f32::<LE>()?));
         }
 
         let size = asset.read_i32::<LE>()?;
-        let mut alias = Vec::with_capacity(size as usize);
+        let mut alias = Vec::with_capacity(asset.check_array_len(size)?);
         for _i in 0..size as usize {
             alias.push(asset.read_i32::<LE>()?);
         }
@@ -135,13 +143,13 @@ impl SkeletalMeshAreaWeightedTriangleSampler {
         let property_guid = optional_guid!(asset, include_header);
 
         let size = asset.read_i32::<LE>()?;
-        let mut prob = Vec::with_capacity(size as usize);
+        let mut prob = Vec::with_capacity(asset.check_array_len(size)?);
         for _i in 0..size as usize {
             prob.push(OrderedFloat(asset.read_f32::<LE>()?));
         }
 
         let size = asset.read_i32::<LE>()?;
-        let mut alias = Vec::with_capacity(size as usize);
+        let mut alias = Vec::with_capacity(asset.check_array_len(size)?);
         for _i in 0..size as usize {
             alias.push(asset.read_i32::<LE>()?);
         }
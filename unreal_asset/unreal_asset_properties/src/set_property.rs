@@ -24,6 +24,14 @@ pub struct SetProperty {
 impl_property_data_trait!(SetProperty);
 
 impl SetProperty {
+    /// Set this property's ancestry and derive the ancestry of set elements from it
+    pub fn set_ancestry(&mut self, ancestry: Ancestry) {
+        self.value.set_ancestry(ancestry.clone());
+        self.removed_items
+            .set_ancestry(ancestry.with_parent(self.name.clone()));
+        self.ancestry = ancestry;
+    }
+
     /// Read a `SetProperty` from an asset
     pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -82,7 +90,7 @@ impl PropertyTrait for SetProperty {
     ) -> Result<usize, Error> {
         let array_type = match !self.value.value.is_empty() {
             true => {
-                let value = self.value.value[0].to_serialized_name();
+                let value = self.value.value[0].to_array_entry_type();
                 Some(asset.get_name_map().get_mut().add_fname(&value))
             }
             false => self.array_type.clone(),
@@ -0,0 +1,66 @@
+//! Simple curve key property
+
+use crate::property_prelude::*;
+
+/// Simple curve key property
+#[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SimpleCurveKeyProperty {
+    /// Name
+    pub name: FName,
+    /// Property ancestry
+    pub ancestry: Ancestry,
+    /// Property guid
+    pub property_guid: Option<Guid>,
+    /// Property duplication index
+    pub duplication_index: i32,
+    /// Time
+    pub time: OrderedFloat<f32>,
+    /// Curve key value
+    pub value: OrderedFloat<f32>,
+}
+impl_property_data_trait!(SimpleCurveKeyProperty);
+impl_float_value!(SimpleCurveKeyProperty {
+    time,
+    set_time: f32,
+    value,
+    set_value: f32,
+});
+
+impl SimpleCurveKeyProperty {
+    /// Read a `SimpleCurveKeyProperty` from an asset
+    pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
+        asset: &mut Reader,
+        name: FName,
+        ancestry: Ancestry,
+        include_header: bool,
+        _length: i64,
+        duplication_index: i32,
+    ) -> Result<Self, Error> {
+        let property_guid = optional_guid!(asset, include_header);
+
+        let time = OrderedFloat(asset.read_f32::<LE>()?);
+        let value = OrderedFloat(asset.read_f32::<LE>()?);
+
+        Ok(SimpleCurveKeyProperty {
+            name,
+            ancestry,
+            property_guid,
+            duplication_index,
+            time,
+            value,
+        })
+    }
+}
+
+impl PropertyTrait for SimpleCurveKeyProperty {
+    fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
+        &self,
+        asset: &mut Writer,
+        include_header: bool,
+    ) -> Result<usize, Error> {
+        optional_guid_write!(self, asset, include_header);
+        asset.write_f32::<LE>(self.time.0)?;
+        asset.write_f32::<LE>(self.value.0)?;
+        Ok(size_of::<f32>() * 2)
+    }
+}
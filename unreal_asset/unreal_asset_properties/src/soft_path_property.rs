@@ -1,5 +1,7 @@
 //! Soft path properties
 
+use unreal_asset_base::containers::{NameMap, SharedResource};
+
 use crate::property_prelude::*;
 
 /// Soft path property value
@@ -28,18 +30,82 @@ impl SoftObjectPathPropertyValue {
         }
     }
 
+    /// Get the full path this value points to, e.g. `/Game/Maps/Level.Level:PersistentLevel.Actor`
+    pub fn get_path(&self) -> Option<String> {
+        match self {
+            Self::Old(e) => e.clone(),
+            Self::New(e) => match e.is_null() {
+                true => None,
+                false => Some(e.get_path()),
+            },
+        }
+    }
+
+    /// Convert this value to the form assets with the given object versions use
+    ///
+    /// The names the converted value is made of are added to the name map.
+    pub fn to_version_form(
+        &self,
+        name_map: &mut SharedResource<NameMap>,
+        object_version: ObjectVersion,
+        object_version_ue5: ObjectVersionUE5,
+    ) -> Self {
+        let old_format = object_version < ObjectVersion::VER_UE4_ADDED_SOFT_OBJECT_PATH;
+
+        match (self, old_format) {
+            (Self::Old(e), true) => Self::Old(e.clone()),
+            (Self::New(_), true) => Self::Old(self.get_path()),
+            (Self::New(e), false) => Self::New(e.to_version_form(name_map, object_version_ue5)),
+            (Self::Old(e), false) => {
+                let path = e.as_deref().unwrap_or_default();
+                let (asset_path, sub_path) = match path.split_once(':') {
+                    Some((asset_path, sub_path)) => (asset_path, Some(sub_path.to_string())),
+                    None => (path, None),
+                };
+                let asset_path = match asset_path.is_empty() {
+                    true => "None",
+                    false => asset_path,
+                };
+
+                let path = SoftObjectPath {
+                    asset_path: TopLevelAssetPath::new(
+                        None,
+                        name_map.get_mut().add_fname(asset_path),
+                    ),
+                    sub_path_string: sub_path,
+                };
+                Self::New(path.to_version_form(name_map, object_version_ue5))
+            }
+        }
+    }
+
     /// Write `SoftObjectPathPropertyValue` to an asset
+    ///
+    /// The value has to be in the form the asset's version uses, see [`SoftObjectPathPropertyValue::to_version_form`].
     pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         &self,
         asset: &mut Writer,
     ) -> Result<(), Error> {
-        match self {
-            Self::Old(e) => {
+        let old_format = asset.get_object_version() < ObjectVersion::VER_UE4_ADDED_SOFT_OBJECT_PATH;
+
+        match (self, old_format) {
+            (Self::Old(e), true) => {
                 asset.write_fstring(e.as_deref())?;
             }
-            Self::New(e) => {
+            (Self::New(e), false) => {
                 e.write(asset)?;
             }
+            // writing a path as a string doesn't need any names
+            (Self::New(_), true) => {
+                asset.write_fstring(self.get_path().as_deref())?;
+            }
+            (Self::Old(e), false) => {
+                return Err(PropertyError::other(format!(
+                    "Soft object path {} is not in the form of the asset's version",
+                    e.as_deref().unwrap_or_default()
+                ))
+                .into());
+            }
         };
 
         Ok(())
@@ -44,6 +44,48 @@ impl StructProperty {
         }
     }
 
+    /// Create a `StructProperty` from its struct variables
+    ///
+    /// Ancestry of the struct variables is derived from `ancestry`.
+    pub fn from_values(
+        name: FName,
+        ancestry: Ancestry,
+        struct_type: FName,
+        struct_guid: Option<Guid>,
+        value: Vec<Property>,
+    ) -> Self {
+        let mut property = StructProperty {
+            name,
+            ancestry: Ancestry::default(),
+            struct_type: Some(struct_type),
+            struct_guid,
+            property_guid: None,
+            duplication_index: 0,
+            serialize_none: true,
+            value,
+        };
+        property.set_ancestry(ancestry);
+        property
+    }
+
+    /// Set this property's ancestry and derive the ancestry of struct variables from it
+    pub fn set_ancestry(&mut self, ancestry: Ancestry) {
+        let custom_serialization = self
+            .struct_type
+            .as_ref()
+            .is_some_and(|ty| ty.get_content(Property::has_custom_serialization));
+
+        let child_ancestry = match (custom_serialization, &self.struct_type) {
+            (false, Some(struct_type)) => ancestry.with_parent(struct_type.clone()),
+            _ => ancestry.with_parent(self.name.clone()),
+        };
+
+        for property in &mut self.value {
+            property.set_ancestry(child_ancestry.clone());
+        }
+        self.ancestry = ancestry;
+    }
+
     /// Read a `StructProperty` from an asset
     pub fn new<Reader: ArchiveReader<impl PackageIndexTrait>>(
         asset: &mut Reader,
@@ -319,7 +361,8 @@ impl StructProperty {
             }
 
             if !asset.has_unversioned_properties() {
-                asset.write_fname(&asset.get_name_map().get_mut().add_fname("None"))?;
+                let none = asset.get_name_map().get_mut().add_fname("None");
+                asset.write_fname(&none)?;
             }
             Ok((asset.position() - begin) as usize)
         }
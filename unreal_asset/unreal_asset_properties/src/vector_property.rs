@@ -20,6 +20,7 @@ pub struct VectorProperty {
     pub value: Vector<OrderedFloat<f64>>,
 }
 impl_property_data_trait!(VectorProperty);
+impl_float_value!(VectorProperty, Vector<f64>);
 
 /// Int point property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -54,6 +55,7 @@ pub struct Vector4Property {
     pub value: Vector4<OrderedFloat<f64>>,
 }
 impl_property_data_trait!(Vector4Property);
+impl_float_value!(Vector4Property, Vector4<f64>);
 
 /// Vector2D property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -71,6 +73,7 @@ pub struct Vector2DProperty {
     pub value: Vector2<OrderedFloat<f64>>,
 }
 impl_property_data_trait!(Vector2DProperty);
+impl_float_value!(Vector2DProperty, Vector2<f64>);
 
 /// Quaternion property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -88,6 +91,7 @@ pub struct QuatProperty {
     pub value: Vector4<OrderedFloat<f64>>,
 }
 impl_property_data_trait!(QuatProperty);
+impl_float_value!(QuatProperty, Vector4<f64>);
 
 /// Rotator property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -105,6 +109,7 @@ pub struct RotatorProperty {
     pub value: Vector<OrderedFloat<f64>>,
 }
 impl_property_data_trait!(RotatorProperty);
+impl_float_value!(RotatorProperty, Vector<f64>);
 
 /// Box property
 #[derive(FNameContainer, Debug, Clone, Default, PartialEq, Eq, Hash)]
@@ -162,6 +167,7 @@ pub struct PlaneProperty {
     pub value: Plane<OrderedFloat<f64>>,
 }
 impl_property_data_trait!(PlaneProperty);
+impl_float_value!(PlaneProperty, Plane<f64>);
 
 impl VectorProperty {
     /// Read a `VectorProperty` from an asset
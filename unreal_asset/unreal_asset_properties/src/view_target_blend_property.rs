@@ -43,6 +43,12 @@ pub struct ViewTargetBlendParamsProperty {
     pub lock_outgoing: bool,
 }
 impl_property_data_trait!(ViewTargetBlendParamsProperty);
+impl_float_value!(ViewTargetBlendParamsProperty {
+    blend_time,
+    set_blend_time: f32,
+    blend_exp,
+    set_blend_exp: f32,
+});
 
 impl ViewTargetBlendParamsProperty {
     /// Read a `ViewTargetBlendParamsProperty` from an asset
@@ -103,6 +103,12 @@ pub struct FWorldTileLODInfo {
     /// Reserved
     pub reserved_3: i32,
 }
+impl_float_value!(FWorldTileLODInfo {
+    reserved_0,
+    set_reserved_0: f32,
+    reserved_1,
+    set_reserved_1: f32,
+});
 
 impl FWorldTileLODInfo {
     /// Read `FWorldTileLODInfo` from an asset
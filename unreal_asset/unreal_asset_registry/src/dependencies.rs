@@ -0,0 +1,126 @@
+//! Dependency queries
+//!
+//! Mirrors the editor's reference viewer, dependencies and referencers of a package
+//! are looked up through the depends nodes of an [`AssetRegistryState`].
+
+use crate::objects::depends_node::{AssetIdentifier, DependsNode};
+use crate::AssetRegistryState;
+
+/// Dependency edge type
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DependencyType {
+    /// Hard package dependency, the dependency is loaded together with the referencer
+    Hard,
+    /// Soft package dependency, e.g. a soft object path
+    Soft,
+    /// Searchable name dependency, e.g. a data table row handle
+    SearchableName,
+    /// Hard management dependency, used by the asset manager for chunking and cooking
+    HardManage,
+    /// Soft management dependency
+    SoftManage,
+}
+
+impl DependencyType {
+    /// All dependency types
+    pub const ALL: [DependencyType; 5] = [
+        DependencyType::Hard,
+        DependencyType::Soft,
+        DependencyType::SearchableName,
+        DependencyType::HardManage,
+        DependencyType::SoftManage,
+    ];
+
+    /// Package dependency types, these are the ones shown by the reference viewer by default
+    pub const PACKAGE: [DependencyType; 2] = [DependencyType::Hard, DependencyType::Soft];
+}
+
+/// Dependency or referencer found by a query
+#[derive(Debug, Clone)]
+pub struct Dependency<'registry> {
+    /// Identifier of the dependency or referencer
+    pub identifier: &'registry AssetIdentifier,
+    /// Type of the edge between the queried node and this node
+    pub dependency_type: DependencyType,
+}
+
+impl AssetRegistryState {
+    /// Find the depends node of a package
+    ///
+    /// Package names are compared case-insensitively, like `FName`s.
+    pub fn find_package_node(&self, package_name: &str) -> Option<&DependsNode> {
+        self.depends_nodes
+            .iter()
+            .find(|e| e.identifier.is_package(package_name))
+    }
+
+    /// Get a depends node by its index
+    pub fn get_depends_node(&self, index: i32) -> Option<&DependsNode> {
+        match index < 0 {
+            true => None,
+            false => self.depends_nodes.get(index as usize),
+        }
+    }
+
+    /// Get the dependencies of a package
+    ///
+    /// Only dependencies with one of `dependency_types` are returned,
+    /// [`DependencyType::ALL`] returns every dependency.
+    /// Returns an empty list if the package doesn't exist in this registry.
+    pub fn dependencies_of(
+        &self,
+        package_name: &str,
+        dependency_types: &[DependencyType],
+    ) -> Vec<Dependency<'_>> {
+        let Some(node) = self.find_package_node(package_name) else {
+            return Vec::new();
+        };
+
+        let mut dependencies = Vec::new();
+        for dependency_type in dependency_types {
+            for dependency in node.get_dependencies(*dependency_type) {
+                if let Some(dependency_node) = self.get_depends_node(dependency.get_index()) {
+                    dependencies.push(Dependency {
+                        identifier: &dependency_node.identifier,
+                        dependency_type: *dependency_type,
+                    });
+                }
+            }
+        }
+        dependencies
+    }
+
+    /// Get the referencers of a package
+    ///
+    /// Only referencers which depend on the package with one of `dependency_types` are returned,
+    /// [`DependencyType::ALL`] returns every referencer.
+    /// Returns an empty list if the package doesn't exist in this registry.
+    pub fn referencers_of(
+        &self,
+        package_name: &str,
+        dependency_types: &[DependencyType],
+    ) -> Vec<Dependency<'_>> {
+        let Some(node) = self.find_package_node(package_name) else {
+            return Vec::new();
+        };
+        let index = node.get_index();
+
+        // referencer lists don't store the edge type, search the dependencies of every node instead
+        let mut referencers = Vec::new();
+        for referencer in &self.depends_nodes {
+            for dependency_type in dependency_types {
+                if referencer
+                    .get_dependencies(*dependency_type)
+                    .iter()
+                    .any(|e| e.get_index() == index)
+                {
+                    referencers.push(Dependency {
+                        identifier: &referencer.identifier,
+                        dependency_type: *dependency_type,
+                    });
+                }
+            }
+        }
+        referencers
+    }
+}
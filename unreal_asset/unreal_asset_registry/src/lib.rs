@@ -12,16 +12,18 @@ use std::io::{Cursor, Seek, SeekFrom};
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 
 use unreal_asset_base::{
-    containers::{NameMap, SharedResource},
+    containers::{IndexedMap, NameMap, SharedResource},
     crc,
     custom_version::FAssetRegistryVersionType,
     error::RegistryError,
+    flags::EPackageFlags,
     object_version::{ObjectVersion, ObjectVersionUE5},
     reader::{ArchiveReader, ArchiveTrait, ArchiveWriter, RawWriter},
-    types::{PackageIndex, PackageIndexTrait},
+    types::{FName, PackageIndex, PackageIndexTrait},
     Error,
 };
 
+pub mod dependencies;
 pub(crate) mod name_table_reader;
 pub(crate) mod name_table_writer;
 pub mod objects;
@@ -29,7 +31,10 @@ pub mod objects;
 use name_table_reader::NameTableReader;
 use name_table_writer::NameTableWriter;
 use objects::{
-    asset_data::AssetData, asset_package_data::AssetPackageData, depends_node::DependsNode,
+    asset_bundle_data::AssetBundleData,
+    asset_data::{AssetData, TopLevelAssetPath},
+    asset_package_data::AssetPackageData,
+    depends_node::DependsNode,
 };
 
 // reexports for tests
@@ -347,6 +352,99 @@ impl AssetRegistryState {
         self.add_name_reference(string, false)
     }
 
+    /// Create an `FName` for this registry
+    ///
+    /// If this registry has a name table, the name is added to it.
+    pub fn create_fname(&mut self, name: &str) -> FName {
+        match self.name_map {
+            Some(ref mut name_map) => name_map.get_mut().add_fname(name),
+            None => FName::from_slice(name),
+        }
+    }
+
+    /// Create `AssetData` for a new asset
+    ///
+    /// * `object_path` - object path of the asset, e.g. `/Game/Mods/MyMod/BP_Item.BP_Item_C`
+    /// * `class_path` - class path of the asset, e.g. `/Script/Engine.BlueprintGeneratedClass`
+    ///
+    /// Registries before [`FAssetRegistryVersionType::ClassPaths`] only store the class name.
+    /// The created asset has no tags, they can be added with [`AssetData::set_tag`].
+    /// Add it to the registry with [`AssetRegistryState::add_asset`].
+    pub fn create_asset_data(&mut self, object_path: &str, class_path: &str) -> AssetData {
+        let (package_name, asset_name) = object_path.split_once('.').unwrap_or((
+            object_path,
+            object_path.rsplit('/').next().unwrap_or(object_path),
+        ));
+        let package_path = package_name
+            .rsplit_once('/')
+            .map(|(path, _)| path)
+            .unwrap_or(package_name);
+        let (class_package, class_name) = class_path.split_once('.').unwrap_or(("", class_path));
+
+        let (asset_class, asset_path) = match self.version >= FAssetRegistryVersionType::ClassPaths
+        {
+            true => (
+                None,
+                Some(TopLevelAssetPath {
+                    package_name: self.create_fname(class_package),
+                    asset_name: self.create_fname(class_name),
+                }),
+            ),
+            false => (Some(self.create_fname(class_name)), None),
+        };
+
+        AssetData::from_data(
+            self.create_fname(object_path),
+            self.create_fname(package_name),
+            self.create_fname(package_path),
+            self.create_fname(asset_name),
+            asset_class,
+            asset_path,
+            IndexedMap::new(),
+            AssetBundleData::default(),
+            Vec::new(),
+            EPackageFlags::COOKED_FLAGS,
+            self.version,
+        )
+    }
+
+    /// Find an asset by its object path, object paths are compared case-insensitively
+    pub fn find_asset(&self, object_path: &str) -> Option<&AssetData> {
+        self.assets_data.iter().find(|e| {
+            e.object_path
+                .get_content(|e| e.eq_ignore_ascii_case(object_path))
+        })
+    }
+
+    /// Find an asset by its object path and get a mutable reference to it
+    pub fn find_asset_mut(&mut self, object_path: &str) -> Option<&mut AssetData> {
+        self.assets_data.iter_mut().find(|e| {
+            e.object_path
+                .get_content(|e| e.eq_ignore_ascii_case(object_path))
+        })
+    }
+
+    /// Add an asset, replacing an existing asset with the same object path
+    ///
+    /// Some games only discover assets which are listed in the asset registry,
+    /// adding modded assets here makes them visible to the game.
+    pub fn add_asset(&mut self, asset_data: AssetData) {
+        let object_path = asset_data.object_path.get_owned_content();
+        match self.find_asset_mut(&object_path) {
+            Some(existing) => *existing = asset_data,
+            None => self.assets_data.push(asset_data),
+        }
+    }
+
+    /// Remove an asset by its object path, returns the removed asset
+    pub fn remove_asset(&mut self, object_path: &str) -> Option<AssetData> {
+        let index = self.assets_data.iter().position(|e| {
+            e.object_path
+                .get_content(|e| e.eq_ignore_ascii_case(object_path))
+        })?;
+        Some(self.assets_data.remove(index))
+    }
+
     /// Gets current AssetRegistry version
     pub fn get_version(&self) -> FAssetRegistryVersionType {
         self.version
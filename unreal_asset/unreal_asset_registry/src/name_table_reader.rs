@@ -9,7 +9,7 @@ use unreal_asset_base::{
     engine_version::EngineVersion,
     object_version::{ObjectVersion, ObjectVersionUE5},
     passthrough_archive_reader,
-    reader::{ArchiveReader, ArchiveTrait, ArchiveType},
+    reader::{ArchiveReader, ArchiveTrait, ArchiveType, ParseOptions},
     types::{FName, PackageIndex},
     unversioned::Usmap,
     Error,
@@ -81,6 +81,10 @@ impl<'reader, Reader: ArchiveReader<PackageIndex>> ArchiveTrait<PackageIndex>
         self.reader.use_event_driven_loader()
     }
 
+    fn get_parse_options(&self) -> ParseOptions {
+        self.reader.get_parse_options()
+    }
+
     fn position(&mut self) -> u64 {
         self.reader.position()
     }
@@ -172,6 +172,47 @@ impl AssetData {
         }
     }
 
+    /// Get the asset registry version this `AssetData` is serialized with
+    pub fn get_version(&self) -> FAssetRegistryVersionType {
+        self.version
+    }
+
+    /// Get the value of a tag, tag names are compared case-insensitively like `FName`s
+    pub fn get_tag(&self, name: &str) -> Option<&str> {
+        self.tags_and_values
+            .iter()
+            .find(|(_, key, _)| key.get_content(|e| e.eq_ignore_ascii_case(name)))
+            .and_then(|(_, _, value)| value.as_deref())
+    }
+
+    /// Set the value of a tag, replacing the value of an existing tag with the same name
+    pub fn set_tag(&mut self, name: FName, value: Option<String>) {
+        let content = name.get_owned_content();
+        if let Some((_, _, existing)) = self
+            .tags_and_values
+            .iter_mut()
+            .find(|(_, key, _)| key.get_content(|e| e.eq_ignore_ascii_case(&content)))
+        {
+            *existing = value;
+            return;
+        }
+
+        self.tags_and_values.insert(name, value);
+    }
+
+    /// Remove a tag, returns the value of the removed tag
+    pub fn remove_tag(&mut self, name: &str) -> Option<Option<String>> {
+        let index = self
+            .tags_and_values
+            .iter()
+            .find(|(_, key, _)| key.get_content(|e| e.eq_ignore_ascii_case(name)))
+            .map(|(index, _, _)| index)?;
+
+        self.tags_and_values
+            .remove_by_index(index)
+            .map(|(_, _, value)| value)
+    }
+
     /// Write `AssetData` to an asset
     pub fn write<Writer: ArchiveWriter<impl PackageIndexTrait>>(
         &self,
@@ -1,5 +1,7 @@
 //! Asset bundle depends node
 
+use std::fmt;
+
 use bitvec::{order::Lsb0, prelude::BitVec};
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 use lazy_static::lazy_static;
@@ -15,6 +17,8 @@ use unreal_asset_base::{
     Error,
 };
 
+use crate::dependencies::DependencyType;
+
 /// Asset identifier
 #[derive(Debug, Clone, Default)]
 pub struct AssetIdentifier {
@@ -90,6 +94,47 @@ impl AssetIdentifier {
         }
         Ok(())
     }
+
+    /// Check if this identifier refers to a package, package names are compared case-insensitively
+    pub fn is_package(&self, package_name: &str) -> bool {
+        self.primary_asset_type.is_none()
+            && self.object_name.is_none()
+            && self.value_name.is_none()
+            && self
+                .package_name
+                .as_ref()
+                .is_some_and(|e| e.get_content(|e| e.eq_ignore_ascii_case(package_name)))
+    }
+}
+
+impl fmt::Display for AssetIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let package_name = self
+            .package_name
+            .as_ref()
+            .map(|e| e.get_owned_content())
+            .unwrap_or_default();
+
+        match &self.primary_asset_type {
+            Some(primary_asset_type) => write!(
+                f,
+                "{}:{}",
+                primary_asset_type.get_owned_content(),
+                package_name
+            )?,
+            None => {
+                write!(f, "{package_name}")?;
+                if let Some(object_name) = &self.object_name {
+                    write!(f, ".{}", object_name.get_owned_content())?;
+                }
+            }
+        }
+
+        if let Some(value_name) = &self.value_name {
+            write!(f, "::{}", value_name.get_owned_content())?;
+        }
+        Ok(())
+    }
 }
 
 /// Depends node
@@ -367,6 +412,27 @@ impl DependsNode {
         }
     }
 
+    /// Get the index of this node in the registry's depends nodes
+    ///
+    /// Dependencies only store the index of the node they point at,
+    /// use [`AssetRegistryState::get_depends_node`] to get the node itself.
+    ///
+    /// [`AssetRegistryState::get_depends_node`]: crate::AssetRegistryState::get_depends_node
+    pub fn get_index(&self) -> i32 {
+        self.index
+    }
+
+    /// Get the dependencies of a type
+    pub fn get_dependencies(&self, dependency_type: DependencyType) -> &[DependsNode] {
+        match dependency_type {
+            DependencyType::Hard => &self.hard_dependencies,
+            DependencyType::Soft => &self.soft_dependencies,
+            DependencyType::SearchableName => &self.name_dependencies,
+            DependencyType::HardManage => &self.hard_manage_dependencies,
+            DependencyType::SoftManage => &self.soft_manage_dependencies,
+        }
+    }
+
     /// Load `DependsNode` dependencies
     pub fn load_dependencies<Reader: ArchiveReader<impl PackageIndexTrait>>(
         &mut self,
@@ -460,7 +526,7 @@ impl DependsNode {
     ) -> Result<(), Error> {
         for _ in 0..num {
             let index = asset.read_i32::<LE>()?;
-            if index < 0 || nodes.len() <= index as usize {
+            if index < 0 || preallocated_depends_node_buffer.len() <= index as usize {
                 return Err(RegistryError::InvalidIndex(index).into());
             }
 
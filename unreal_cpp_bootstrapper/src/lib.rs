@@ -41,7 +41,7 @@ pub fn bootstrap(
         let mut pak = PakReader::new(&file);
         pak.load_index()?;
 
-        let Ok(metadata_entry) = pak.read_entry(&String::from("metadata.json")) else {
+        let Ok(metadata_entry) = pak.read_entry("metadata.json") else {
             continue;
         };
 
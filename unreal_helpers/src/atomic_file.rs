@@ -0,0 +1,114 @@
+//! File writer which only replaces its destination once writing succeeded
+
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// A file which is written to a temporary location next to its destination
+/// and only moved into place once [`AtomicFile::commit`] is called.
+///
+/// If the `AtomicFile` is dropped or [`AtomicFile::abort`] is called before committing,
+/// the temporary file gets removed and the destination is left untouched.
+/// This prevents half written files, like paks in a game directory or saved assets,
+/// from being left behind when writing fails partway.
+#[derive(Debug)]
+#[must_use = "the file is removed when dropped without calling `commit`"]
+pub struct AtomicFile {
+    path: PathBuf,
+    temp_path: PathBuf,
+    writer: Option<BufWriter<File>>,
+}
+
+impl AtomicFile {
+    /// Create a new `AtomicFile` which will be written to `path` once committed
+    pub fn create<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+
+        let mut temp_name = path.file_name().unwrap_or_default().to_os_string();
+        temp_name.push(".tmp");
+        let temp_path = path.with_file_name(temp_name);
+
+        let file = File::create(&temp_path)?;
+
+        Ok(AtomicFile {
+            path,
+            temp_path,
+            writer: Some(BufWriter::new(file)),
+        })
+    }
+
+    /// Get the destination path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the path of the temporary file that is currently being written to
+    pub fn temp_path(&self) -> &Path {
+        &self.temp_path
+    }
+
+    fn writer(&mut self) -> &mut BufWriter<File> {
+        // the writer is only taken out when consuming self
+        self.writer.as_mut().expect("AtomicFile writer is gone")
+    }
+
+    /// Flush and sync the written data to disk and move the file to its destination,
+    /// replacing any existing file
+    pub fn commit(mut self) -> io::Result<()> {
+        let writer = self.writer.take().expect("AtomicFile writer is gone");
+        let file = writer.into_inner().map_err(|e| e.into_error())?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&self.temp_path, &self.path)?;
+
+        // make sure the rename itself is persisted
+        #[cfg(unix)]
+        {
+            let parent = match self.path.parent() {
+                Some(parent) if !parent.as_os_str().is_empty() => parent,
+                _ => Path::new("."),
+            };
+            File::open(parent)?.sync_all()?;
+        }
+
+        Ok(())
+    }
+
+    /// Stop writing and remove the temporary file
+    pub fn abort(mut self) -> io::Result<()> {
+        self.remove_temp_file()
+    }
+
+    fn remove_temp_file(&mut self) -> io::Result<()> {
+        match self.writer.take() {
+            Some(writer) => {
+                drop(writer);
+                fs::remove_file(&self.temp_path)
+            }
+            None => Ok(()),
+        }
+    }
+}
+
+impl Write for AtomicFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.writer().write(buf)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.writer().flush()
+    }
+}
+
+impl Seek for AtomicFile {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        self.writer().seek(pos)
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        let _ = self.remove_temp_file();
+    }
+}
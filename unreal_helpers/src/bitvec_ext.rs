@@ -1,6 +1,12 @@
 //! BitVec extensions for easier deserialization
 
-use bitvec::{order::BitOrder, prelude::BitVec, store::BitStore};
+use std::io::{self, Read, Write};
+
+use bitvec::{
+    order::{BitOrder, Lsb0},
+    prelude::BitVec,
+    store::BitStore,
+};
 
 /// BitVec extension for setting ranges.
 pub trait BitVecExt<T: BitStore, O: BitOrder> {
@@ -28,3 +34,39 @@ impl<T: BitStore, O: BitOrder> BitVecExt<T, O> for BitVec<T, O> {
         }
     }
 }
+
+/// Get the amount of bytes a bit mask with `num_bits` bits is serialized as.
+///
+/// Like the engine, masks of up to 8 bits are stored in a byte, masks of up to 16 bits in a word
+/// and everything bigger is rounded up to whole dwords.
+pub fn get_bit_mask_size(num_bits: usize) -> usize {
+    if num_bits <= 8 {
+        1
+    } else if num_bits <= 16 {
+        2
+    } else {
+        num_bits.div_ceil(32) * 4
+    }
+}
+
+/// Read a bit mask with `num_bits` bits.
+///
+/// Padding bits are read and discarded.
+pub fn read_bit_mask<R: Read>(reader: &mut R, num_bits: usize) -> io::Result<BitVec<u8, Lsb0>> {
+    let mut data = vec![0u8; get_bit_mask_size(num_bits)];
+    reader.read_exact(&mut data)?;
+
+    let mut mask = BitVec::from_vec(data);
+    mask.truncate(num_bits);
+    Ok(mask)
+}
+
+/// Write a bit mask, padding it with zero bits to the size expected by the engine.
+pub fn write_bit_mask<W: Write>(writer: &mut W, mask: &BitVec<u8, Lsb0>) -> io::Result<()> {
+    let mut data = vec![0u8; get_bit_mask_size(mask.len())];
+    for (i, bit) in mask.iter().by_vals().enumerate() {
+        data[i / 8] |= (bit as u8) << (i % 8);
+    }
+
+    writer.write_all(&data)
+}
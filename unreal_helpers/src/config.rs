@@ -0,0 +1,338 @@
+//! Unreal config (.ini) files
+//!
+//! Unreal config files are ini files with a few additions, lines in config fragments
+//! can be prefixed with an operator which decides how they are combined with existing values:
+//!
+//! - `Key=Value`: Replaces the existing value of `Key`
+//! - `+Key=Value`: Adds `Value` to the `Key` array if it isn't already present
+//! - `.Key=Value`: Adds `Value` to the `Key` array even if it's already present
+//! - `-Key=Value`: Removes `Value` from the `Key` array
+//! - `!Key`: Removes all values of `Key`
+//!
+//! Section and key names are case-insensitive.
+
+use std::fmt::{self, Display, Formatter};
+
+use crate::error::ConfigError;
+
+/// UTF-8 byte order mark
+const UTF8_BOM: [u8; 3] = [0xef, 0xbb, 0xbf];
+/// UTF-16 little endian byte order mark
+const UTF16_LE_BOM: [u8; 2] = [0xff, 0xfe];
+
+/// Operation applied to a config value when combining config files
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub enum ConfigOperation {
+    /// `Key=Value`, replace the value
+    #[default]
+    Set,
+    /// `+Key=Value`, add the value if it's not present yet
+    AddUnique,
+    /// `.Key=Value`, add the value
+    Add,
+    /// `-Key=Value`, remove the value
+    Remove,
+    /// `!Key`, remove all values
+    Clear,
+}
+
+impl ConfigOperation {
+    /// Get the prefix character of this operation
+    pub fn prefix(&self) -> Option<char> {
+        match self {
+            ConfigOperation::Set => None,
+            ConfigOperation::AddUnique => Some('+'),
+            ConfigOperation::Add => Some('.'),
+            ConfigOperation::Remove => Some('-'),
+            ConfigOperation::Clear => Some('!'),
+        }
+    }
+
+    /// Get the operation of a config line prefix character
+    pub fn from_prefix(prefix: char) -> Option<Self> {
+        match prefix {
+            '+' => Some(ConfigOperation::AddUnique),
+            '.' => Some(ConfigOperation::Add),
+            '-' => Some(ConfigOperation::Remove),
+            '!' => Some(ConfigOperation::Clear),
+            _ => None,
+        }
+    }
+}
+
+/// Config entry
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ConfigEntry {
+    /// Operation
+    pub operation: ConfigOperation,
+    /// Key
+    pub key: String,
+    /// Value
+    pub value: String,
+}
+
+impl ConfigEntry {
+    /// Create a new `ConfigEntry` instance
+    pub fn new(operation: ConfigOperation, key: String, value: String) -> Self {
+        ConfigEntry {
+            operation,
+            key,
+            value,
+        }
+    }
+
+    /// Parse a `ConfigEntry` from a config line
+    ///
+    /// Returns `None` if the line is not a valid entry.
+    pub fn parse(line: &str) -> Option<Self> {
+        let line = line.trim();
+        let mut chars = line.chars();
+        let operation = chars.next().and_then(ConfigOperation::from_prefix);
+        let line = match operation {
+            Some(_) => chars.as_str(),
+            None => line,
+        };
+
+        let (key, value) = match line.split_once('=') {
+            Some((key, value)) => (key.trim(), value.trim()),
+            // values of cleared keys are ignored, so they can be omitted
+            None if operation == Some(ConfigOperation::Clear) => (line.trim(), ""),
+            None => return None,
+        };
+        if key.is_empty() {
+            return None;
+        }
+
+        Some(ConfigEntry {
+            operation: operation.unwrap_or_default(),
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Check if this entry's key matches a key
+    pub fn is_key(&self, key: &str) -> bool {
+        self.key.eq_ignore_ascii_case(key)
+    }
+}
+
+impl Display for ConfigEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(prefix) = self.operation.prefix() {
+            write!(f, "{prefix}")?;
+        }
+        match self.operation {
+            ConfigOperation::Clear if self.value.is_empty() => write!(f, "{}", self.key),
+            _ => write!(f, "{}={}", self.key, self.value),
+        }
+    }
+}
+
+/// Config section
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ConfigSection {
+    /// Section name
+    pub name: String,
+    /// Section entries, in file order
+    pub entries: Vec<ConfigEntry>,
+}
+
+impl ConfigSection {
+    /// Create a new empty `ConfigSection` instance
+    pub fn new(name: String) -> Self {
+        ConfigSection {
+            name,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Check if this section's name matches a name
+    pub fn is_name(&self, name: &str) -> bool {
+        self.name.eq_ignore_ascii_case(name)
+    }
+
+    /// Get the first value of a key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .find(|e| e.is_key(key))
+            .map(|e| e.value.as_str())
+    }
+
+    /// Get all values of a key
+    pub fn get_all<'a>(&'a self, key: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.entries
+            .iter()
+            .filter(move |e| e.is_key(key))
+            .map(|e| e.value.as_str())
+    }
+
+    /// Set the value of a key, replacing the first existing value
+    pub fn set(&mut self, key: &str, value: &str) {
+        match self.entries.iter_mut().find(|e| e.is_key(key)) {
+            Some(entry) => entry.value = value.to_string(),
+            None => self.add(key, value),
+        }
+    }
+
+    /// Add a value to a key
+    pub fn add(&mut self, key: &str, value: &str) {
+        self.entries.push(ConfigEntry::new(
+            ConfigOperation::Set,
+            key.to_string(),
+            value.to_string(),
+        ));
+    }
+
+    /// Remove all values of a key
+    pub fn remove(&mut self, key: &str) {
+        self.entries.retain(|e| !e.is_key(key));
+    }
+
+    /// Apply an entry to this section following the operator rules used when combining config files
+    pub fn apply(&mut self, entry: &ConfigEntry) {
+        let has_value = |e: &ConfigEntry| e.is_key(&entry.key) && e.value == entry.value;
+
+        match entry.operation {
+            ConfigOperation::Set => self.set(&entry.key, &entry.value),
+            ConfigOperation::AddUnique => {
+                if !self.entries.iter().any(has_value) {
+                    self.add(&entry.key, &entry.value);
+                }
+            }
+            ConfigOperation::Add => self.add(&entry.key, &entry.value),
+            ConfigOperation::Remove => {
+                if let Some(index) = self.entries.iter().position(has_value) {
+                    self.entries.remove(index);
+                }
+            }
+            ConfigOperation::Clear => self.remove(&entry.key),
+        }
+    }
+}
+
+/// Config file
+///
+/// Entries are stored as they appear in the file, including their operators,
+/// use [`ConfigFile::combine`] to apply them to another config file.
+/// Comments and lines outside of sections are not preserved.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ConfigFile {
+    /// Sections, in file order
+    pub sections: Vec<ConfigSection>,
+}
+
+impl ConfigFile {
+    /// Create a new empty `ConfigFile` instance
+    pub fn new() -> Self {
+        ConfigFile::default()
+    }
+
+    /// Parse a `ConfigFile` from a string
+    ///
+    /// Sections which appear multiple times are merged into the first one.
+    pub fn parse(data: &str) -> Self {
+        let mut config = ConfigFile::new();
+        let mut current_section = None;
+
+        for line in data.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                current_section = Some(config.get_or_add_section_index(name));
+                continue;
+            }
+
+            let Some(section) = current_section else {
+                continue;
+            };
+            if let Some(entry) = ConfigEntry::parse(line) {
+                config.sections[section].entries.push(entry);
+            }
+        }
+
+        config
+    }
+
+    /// Parse a `ConfigFile` from bytes
+    ///
+    /// UTF-8 and UTF-16 LE with a byte order mark are supported, data without a byte order mark is read as UTF-8.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, ConfigError> {
+        if let Some(data) = data.strip_prefix(&UTF16_LE_BOM) {
+            let data = data
+                .chunks_exact(2)
+                .map(|e| u16::from_le_bytes([e[0], e[1]]))
+                .collect::<Vec<_>>();
+            return Ok(ConfigFile::parse(&String::from_utf16(&data)?));
+        }
+
+        let data = data.strip_prefix(&UTF8_BOM).unwrap_or(data);
+        Ok(ConfigFile::parse(std::str::from_utf8(data)?))
+    }
+
+    /// Get a section by name
+    pub fn get_section(&self, name: &str) -> Option<&ConfigSection> {
+        self.sections.iter().find(|e| e.is_name(name))
+    }
+
+    /// Get a mutable reference to a section by name
+    pub fn get_section_mut(&mut self, name: &str) -> Option<&mut ConfigSection> {
+        self.sections.iter_mut().find(|e| e.is_name(name))
+    }
+
+    /// Get a section by name, adding it to the end of the file if it doesn't exist
+    pub fn get_or_add_section(&mut self, name: &str) -> &mut ConfigSection {
+        let index = self.get_or_add_section_index(name);
+        &mut self.sections[index]
+    }
+
+    /// Get the first value of a key in a section
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.get_section(section)?.get(key)
+    }
+
+    /// Combine another config file into this one
+    ///
+    /// Entries of `other` are applied in order using their operators,
+    /// sections which don't exist yet are added after existing sections.
+    pub fn combine(&mut self, other: &ConfigFile) {
+        for other_section in &other.sections {
+            let section = self.get_or_add_section(&other_section.name);
+            for entry in &other_section.entries {
+                section.apply(entry);
+            }
+        }
+    }
+
+    /// Get the index of a section, adding it if it doesn't exist
+    fn get_or_add_section_index(&mut self, name: &str) -> usize {
+        match self.sections.iter().position(|e| e.is_name(name)) {
+            Some(index) => index,
+            None => {
+                self.sections.push(ConfigSection::new(name.to_string()));
+                self.sections.len() - 1
+            }
+        }
+    }
+}
+
+impl Display for ConfigFile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, section) in self.sections.iter().enumerate() {
+            if i != 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "[{}]", section.name)?;
+            for entry in &section.entries {
+                writeln!(f, "{entry}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -6,7 +6,10 @@ use std::{
     string::{FromUtf16Error, FromUtf8Error},
 };
 
-#[cfg(feature = "read_write")]
+#[cfg(feature = "config")]
+use std::str::Utf8Error;
+
+#[cfg(any(feature = "config", feature = "read_write"))]
 use thiserror::Error;
 
 /// Gets thrown when there is an error reading/writing an FString.
@@ -19,6 +22,9 @@ pub enum FStringError {
     /// String has invalid terminator
     #[error("Invalid string terminator {0} at position {1}")]
     InvalidStringTerminator(u16, u64),
+    /// String is too long to be written
+    #[error("String of length {0} is too long")]
+    StringTooLong(usize),
     /// String is not in the expected UTF-8 format
     #[error("Utf8 Error {0}")]
     Utf8(#[from] FromUtf8Error),
@@ -29,3 +35,15 @@ pub enum FStringError {
     #[error("Io Error {0}")]
     Io(#[from] io::Error),
 }
+
+/// Gets thrown when there is an error reading a config file.
+#[cfg(feature = "config")]
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    /// Config is not in the expected UTF-8 format
+    #[error("Utf8 Error {0}")]
+    Utf8(#[from] Utf8Error),
+    /// Config is not in the expected UTF-16 format
+    #[error("Utf16 Error {0}")]
+    Utf16(#[from] std::string::FromUtf16Error),
+}
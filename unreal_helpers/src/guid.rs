@@ -6,6 +6,8 @@ use std::{
     str::FromStr,
 };
 
+use sha1::{Digest, Sha1};
+
 /// Stores a 128-bit guid (globally unique identifier)
 #[derive(Copy, Clone, Default, PartialEq, Eq, Hash)]
 pub struct Guid(pub [u8; 16]);
@@ -40,6 +42,31 @@ impl Guid {
         ])
     }
 
+    /// Create a new random guid, laid out like a version 4 UUID
+    pub fn new_v4() -> Self {
+        let mut guid: [u8; 16] = rand::random();
+        guid[6] = (guid[6] & 0x0f) | 0x40;
+        guid[8] = (guid[8] & 0x3f) | 0x80;
+        Self(guid)
+    }
+
+    /// Create a guid derived from a namespace guid and a name, laid out like a version 5 UUID
+    ///
+    /// The same namespace and name always result in the same guid,
+    /// which keeps generated packages reproducible.
+    pub fn new_v5(namespace: &Guid, name: &str) -> Self {
+        let mut hasher = Sha1::new();
+        hasher.update(namespace.0);
+        hasher.update(name.as_bytes());
+        let hash = hasher.finalize();
+
+        let mut guid = [0u8; 16];
+        guid.copy_from_slice(&hash[..16]);
+        guid[6] = (guid[6] & 0x0f) | 0x50;
+        guid[8] = (guid[8] & 0x3f) | 0x80;
+        Self(guid)
+    }
+
     /// Returns true if the guid is zero.
     #[inline]
     pub fn is_zero(&self) -> bool {
@@ -0,0 +1,200 @@
+//! Reader wrapper collecting I/O statistics
+//!
+//! Parsing assets and paks issues many small reads and seeks. Wrapping the input in an
+//! [`InstrumentedReader`] shows how a reader is accessed, e.g. to check whether wrapping
+//! a `File` in a `BufReader` is worth it.
+
+use std::io::{Read, Result, Seek, SeekFrom};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+/// Default size of the simulated read-ahead buffer, same as the default `BufReader` capacity
+pub const DEFAULT_BUFFER_SIZE: u64 = 8 * 1024;
+
+/// I/O statistics collected by an [`InstrumentedReader`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct ReaderStats {
+    /// Amount of `read` calls
+    pub reads: u64,
+    /// Amount of bytes read
+    pub bytes_read: u64,
+    /// Amount of `seek` calls
+    pub seeks: u64,
+    /// Amount of seeks which didn't change the position
+    pub redundant_seeks: u64,
+    /// Amount of reads which would have been served by a read-ahead buffer
+    pub buffer_hits: u64,
+}
+
+impl ReaderStats {
+    /// Fraction of reads which would have been served by a read-ahead buffer
+    pub fn buffer_hit_rate(&self) -> f64 {
+        match self.reads {
+            0 => 0.0,
+            reads => self.buffer_hits as f64 / reads as f64,
+        }
+    }
+
+    /// Average amount of bytes per read
+    pub fn average_read_size(&self) -> f64 {
+        match self.reads {
+            0 => 0.0,
+            reads => self.bytes_read as f64 / reads as f64,
+        }
+    }
+}
+
+/// Counters shared between a reader and its handles
+#[derive(Debug, Default)]
+struct StatsCounters {
+    reads: AtomicU64,
+    bytes_read: AtomicU64,
+    seeks: AtomicU64,
+    redundant_seeks: AtomicU64,
+    buffer_hits: AtomicU64,
+}
+
+impl StatsCounters {
+    fn get(&self) -> ReaderStats {
+        ReaderStats {
+            reads: self.reads.load(Ordering::Relaxed),
+            bytes_read: self.bytes_read.load(Ordering::Relaxed),
+            seeks: self.seeks.load(Ordering::Relaxed),
+            redundant_seeks: self.redundant_seeks.load(Ordering::Relaxed),
+            buffer_hits: self.buffer_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    fn reset(&self) {
+        self.reads.store(0, Ordering::Relaxed);
+        self.bytes_read.store(0, Ordering::Relaxed);
+        self.seeks.store(0, Ordering::Relaxed);
+        self.redundant_seeks.store(0, Ordering::Relaxed);
+        self.buffer_hits.store(0, Ordering::Relaxed);
+    }
+}
+
+/// Handle for reading the statistics of an [`InstrumentedReader`]
+///
+/// Stays valid after the reader was moved, e.g. into an asset.
+#[derive(Debug, Clone)]
+pub struct ReaderStatsHandle {
+    counters: Arc<StatsCounters>,
+}
+
+impl ReaderStatsHandle {
+    /// Get the current statistics
+    pub fn get(&self) -> ReaderStats {
+        self.counters.get()
+    }
+
+    /// Reset all statistics to 0
+    pub fn reset(&self) {
+        self.counters.reset()
+    }
+}
+
+/// Reader wrapper collecting I/O statistics
+///
+/// Buffer hits are counted by simulating a read-ahead buffer of `buffer_size` bytes,
+/// which is filled from the position of every read that isn't fully contained in it.
+/// The simulated buffer is kept across seeks.
+#[derive(Debug)]
+pub struct InstrumentedReader<R> {
+    inner: R,
+    counters: Arc<StatsCounters>,
+    /// Size of the simulated read-ahead buffer
+    buffer_size: u64,
+    /// Range of the simulated read-ahead buffer
+    buffer: Option<(u64, u64)>,
+    /// Position of the inner reader, unknown until the first seek
+    position: Option<u64>,
+}
+
+impl<R> InstrumentedReader<R> {
+    /// Create a new `InstrumentedReader` instance simulating a buffer of [`DEFAULT_BUFFER_SIZE`] bytes
+    pub fn new(inner: R) -> Self {
+        Self::with_buffer_size(inner, DEFAULT_BUFFER_SIZE)
+    }
+
+    /// Create a new `InstrumentedReader` instance simulating a buffer of `buffer_size` bytes
+    pub fn with_buffer_size(inner: R, buffer_size: u64) -> Self {
+        InstrumentedReader {
+            inner,
+            counters: Arc::new(StatsCounters::default()),
+            buffer_size,
+            buffer: None,
+            position: None,
+        }
+    }
+
+    /// Get the current statistics
+    pub fn stats(&self) -> ReaderStats {
+        self.counters.get()
+    }
+
+    /// Get a handle for reading the statistics after the reader was moved
+    pub fn stats_handle(&self) -> ReaderStatsHandle {
+        ReaderStatsHandle {
+            counters: self.counters.clone(),
+        }
+    }
+
+    /// Reset all statistics to 0
+    pub fn reset_stats(&mut self) {
+        self.counters.reset();
+        self.buffer = None;
+    }
+
+    /// Get a reference to the wrapped reader
+    pub fn get_ref(&self) -> &R {
+        &self.inner
+    }
+
+    /// Consumes the `InstrumentedReader`, returning the wrapped reader
+    pub fn into_inner(self) -> R {
+        self.inner
+    }
+}
+
+impl<R: Read> Read for InstrumentedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let read = self.inner.read(buf)?;
+
+        self.counters.reads.fetch_add(1, Ordering::Relaxed);
+        self.counters
+            .bytes_read
+            .fetch_add(read as u64, Ordering::Relaxed);
+
+        if let Some(position) = self.position {
+            let end = position + read as u64;
+            match self.buffer {
+                Some((start, buffer_end)) if position >= start && end <= buffer_end => {
+                    self.counters.buffer_hits.fetch_add(1, Ordering::Relaxed);
+                }
+                _ => {
+                    self.buffer = Some((position, end.max(position + self.buffer_size)));
+                }
+            }
+            self.position = Some(end);
+        }
+
+        Ok(read)
+    }
+}
+
+impl<R: Seek> Seek for InstrumentedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> Result<u64> {
+        let new_position = self.inner.seek(pos)?;
+
+        self.counters.seeks.fetch_add(1, Ordering::Relaxed);
+        if self.position == Some(new_position) {
+            self.counters
+                .redundant_seeks
+                .fetch_add(1, Ordering::Relaxed);
+        }
+        self.position = Some(new_position);
+
+        Ok(new_position)
+    }
+}
@@ -7,17 +7,32 @@
 //! All content in this crate is hidden behind feature flags.
 //!
 //! - `read_write`: Enables extension Traits [`UnrealReadExt`] and [`UnrealWriteExt`]
-//!                 which help with parsing Unreal data formats.
+//!   which help with parsing Unreal data formats,
+//!   and [`InstrumentedReader`] for collecting I/O statistics.
 //! - `path`: Enables [`game_to_absolute`] function.
 //! - `guid`: Enables [`Guid`] type.
 //! - `serde`: Enables `serde` support for [`Guid`] type.
 //! - `bitvec`: Enables extension Trait [`BitVecExt`].
+//! - `config`: Enables [`ConfigFile`] type for reading, writing and combining config (.ini) files.
+//! - `atomic_file`: Enables [`AtomicFile`] type for replacing files only once writing succeeded.
+//!
+//! The crate requires `std`, the read and write extensions are implemented for the `std::io` traits.
+
+#[cfg(feature = "atomic_file")]
+pub mod atomic_file;
+#[cfg(feature = "atomic_file")]
+pub use atomic_file::AtomicFile;
 
 #[cfg(feature = "bitvec")]
 pub mod bitvec_ext;
 #[cfg(feature = "bitvec")]
 pub use bitvec_ext::BitVecExt;
 
+#[cfg(feature = "config")]
+pub mod config;
+#[cfg(feature = "config")]
+pub use config::ConfigFile;
+
 pub mod error;
 
 #[cfg(feature = "guid")]
@@ -30,6 +45,11 @@ pub mod path;
 #[cfg(feature = "path")]
 pub use path::game_to_absolute;
 
+#[cfg(feature = "read_write")]
+pub mod instrumented;
+#[cfg(feature = "read_write")]
+pub use instrumented::InstrumentedReader;
+
 #[cfg(feature = "read_write")]
 pub mod read_ext;
 #[cfg(feature = "read_write")]
@@ -7,6 +7,14 @@ use byteorder::{ReadBytesExt, LE};
 
 use crate::error::FStringError;
 
+/// Maximum length of an FString in characters, including the null terminator.
+///
+/// Longer strings are rejected when reading to avoid huge allocations on corrupted data.
+pub const MAX_FSTRING_LEN: i32 = 131072;
+
+/// Maximum amount of elements preallocated by [`UnrealReadExt::read_array`].
+const MAX_ARRAY_PREALLOCATION: usize = 1024;
+
 /// Extension for anything that implements `Read` to more easily read Unreal data formats.
 pub trait UnrealReadExt {
     /// Read u8 as bool.
@@ -18,6 +26,9 @@ pub trait UnrealReadExt {
     /// Read an array of type `T` by consuming bytes of the reader `n` times and
     /// parsing them into `T` using the provided function where the value of `n`
     /// is determined by first `i32` aread from the reader.
+    ///
+    /// Counts are not trusted for preallocation, so a corrupted count fails
+    /// when running out of data instead of allocating a huge buffer.
     fn read_array<T>(&mut self, f: impl FnMut(&mut Self) -> io::Result<T>) -> io::Result<Vec<T>>;
 
     /// Read a guid.
@@ -25,6 +36,8 @@ pub trait UnrealReadExt {
     fn read_guid(&mut self) -> io::Result<crate::Guid>;
 
     /// Read string of format \<length i32\>\<string\>\<null\>.
+    ///
+    /// A negative length denotes a UTF-16 string.
     fn read_fstring(&mut self) -> Result<Option<String>, FStringError>;
 }
 
@@ -43,8 +56,9 @@ impl<R: Read + Seek> UnrealReadExt for R {
         &mut self,
         mut f: impl FnMut(&mut Self) -> io::Result<T>,
     ) -> io::Result<Vec<T>> {
-        let mut buf = Vec::with_capacity(self.read_u32::<LE>()? as usize);
-        for _ in 0..buf.capacity() {
+        let len = self.read_u32::<LE>()? as usize;
+        let mut buf = Vec::with_capacity(len.min(MAX_ARRAY_PREALLOCATION));
+        for _ in 0..len {
             buf.push(f(self)?);
         }
         Ok(buf)
@@ -54,7 +68,15 @@ impl<R: Read + Seek> UnrealReadExt for R {
         let len = self.read_i32::<LE>()?;
 
         let (len, is_wide) = match len < 0 {
-            true => (-len, true),
+            true => match len.checked_neg() {
+                Some(len) => (len, true),
+                None => {
+                    return Err(FStringError::InvalidStringSize(
+                        len,
+                        self.stream_position()?,
+                    ))
+                }
+            },
             false => (len, false),
         };
         read_fstring_len(self, len, is_wide)
@@ -69,6 +91,8 @@ impl<R: Read + Seek> UnrealReadExt for R {
 }
 
 /// Read string of format \<string\>\<null\> when length and encoding is already known.
+///
+/// `len` is the amount of characters including the null terminator and must not be negative.
 #[inline(always)]
 pub fn read_fstring_len<R: Read + Seek>(
     reader: &mut R,
@@ -109,29 +133,37 @@ pub fn read_fstring_len_noterm<R: Read + Seek>(
     len: i32,
     is_wide: bool,
 ) -> Result<Option<String>, FStringError> {
-    if !(-131072..=131072).contains(&len) {
+    if !(0..=MAX_FSTRING_LEN).contains(&len) {
         return Err(FStringError::InvalidStringSize(
             len,
             reader.stream_position()?,
         ));
     }
 
-    if is_wide {
-        let len = len * size_of::<u16>() as i32;
-        let mut buf = vec![0u8; len as usize];
-        reader.read_exact(&mut buf)?;
+    let len = match is_wide {
+        true => len as usize * size_of::<u16>(),
+        false => len as usize,
+    };
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf)?;
 
-        String::from_utf16(
-            &buf.chunks(2)
-                .map(|e| u16::from_le_bytes([e[0], e[1]]))
-                .collect::<Vec<_>>(),
-        )
-        .map(Some)
-        .map_err(|e| e.into())
-    } else {
-        let mut buf = vec![0u8; len as usize];
-        reader.read_exact(&mut buf)?;
+    decode_fstring(buf, is_wide).map(Some)
+}
 
-        String::from_utf8(buf).map(Some).map_err(|e| e.into())
+/// Decode string data without a length prefix or a null terminator.
+///
+/// Wide strings are decoded from UTF-16LE in a single pass, other strings are decoded as UTF-8.
+/// Taking ownership of narrow string data allows it to be reused without copying.
+pub fn decode_fstring(data: impl Into<Vec<u8>>, is_wide: bool) -> Result<String, FStringError> {
+    let data = data.into();
+    match is_wide {
+        true => {
+            let wide = data
+                .chunks_exact(2)
+                .map(|e| u16::from_le_bytes([e[0], e[1]]))
+                .collect::<Vec<_>>();
+            Ok(String::from_utf16(&wide)?)
+        }
+        false => Ok(String::from_utf8(data)?),
     }
 }
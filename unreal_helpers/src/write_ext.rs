@@ -6,6 +6,7 @@ use std::mem::size_of;
 use byteorder::{WriteBytesExt, LE};
 
 use crate::error::FStringError;
+use crate::read_ext::MAX_FSTRING_LEN;
 
 /// Extension for anything that implements `Write` to more easily write Unreal data formats.
 pub trait UnrealWriteExt {
@@ -60,6 +61,7 @@ impl<W: Write> UnrealWriteExt for W {
 
             if is_unicode {
                 let utf16 = string.encode_utf16().collect::<Vec<_>>();
+                check_fstring_len(utf16.len())?;
 
                 // this is safe because we know that string is utf16 and therefore can easily be aligned to u8
                 // this is also faster than alternatives without unsafe block
@@ -71,6 +73,7 @@ impl<W: Write> UnrealWriteExt for W {
                 self.write_all(&[0u8; 2])?;
                 Ok(size_of::<i32>() + aligned.len())
             } else {
+                check_fstring_len(string.len())?;
                 self.write_i32::<LE>(string.len() as i32 + 1)?;
                 let bytes = string.as_bytes();
                 self.write_all(bytes)?;
@@ -84,3 +87,11 @@ impl<W: Write> UnrealWriteExt for W {
         }
     }
 }
+
+/// Check that a string of `len` characters can be read back by [`crate::UnrealReadExt::read_fstring`].
+fn check_fstring_len(len: usize) -> Result<(), FStringError> {
+    match len < MAX_FSTRING_LEN as usize {
+        true => Ok(()),
+        false => Err(FStringError::StringTooLong(len)),
+    }
+}
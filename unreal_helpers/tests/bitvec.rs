@@ -1,7 +1,12 @@
 #![cfg(feature = "bitvec")]
 
+use std::io::Cursor;
+
 use bitvec::{bitvec, order::Lsb0};
-use unreal_helpers::BitVecExt;
+use unreal_helpers::{
+    bitvec_ext::{get_bit_mask_size, read_bit_mask, write_bit_mask},
+    BitVecExt,
+};
 
 #[test]
 fn test_set_range_from_range() {
@@ -12,3 +17,28 @@ fn test_set_range_from_range() {
 
     assert_eq!(vec, bitvec![1, 1, 1, 1, 1, 0, 0]);
 }
+
+#[test]
+fn test_bit_mask_size() {
+    assert_eq!(get_bit_mask_size(1), 1);
+    assert_eq!(get_bit_mask_size(8), 1);
+    assert_eq!(get_bit_mask_size(9), 2);
+    assert_eq!(get_bit_mask_size(16), 2);
+    assert_eq!(get_bit_mask_size(17), 4);
+    assert_eq!(get_bit_mask_size(32), 4);
+    assert_eq!(get_bit_mask_size(33), 8);
+}
+
+#[test]
+fn test_bit_mask_roundtrip() -> std::io::Result<()> {
+    let mask = bitvec![u8, Lsb0; 1, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1];
+
+    let mut cursor = Cursor::new(Vec::new());
+    write_bit_mask(&mut cursor, &mask)?;
+    assert_eq!(cursor.get_ref(), &[0b0000_1101, 0, 0b0000_0011, 0]);
+
+    cursor.set_position(0);
+    assert_eq!(read_bit_mask(&mut cursor, mask.len())?, mask);
+
+    Ok(())
+}
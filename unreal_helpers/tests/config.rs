@@ -0,0 +1,104 @@
+#![cfg(feature = "config")]
+
+use unreal_helpers::{
+    config::{ConfigEntry, ConfigOperation},
+    error::ConfigError,
+    ConfigFile,
+};
+
+const BASE: &str = "\
+; Engine config
+[/Script/Engine.Engine]
+GameViewportClientClassName=/Script/Engine.GameViewportClient
++ActiveGameNameRedirects=(OldGameName=\"A\",NewGameName=\"B\")
+
+[Core.Log]
+LogTemp=Warning
+";
+
+const FRAGMENT: &str = "\
+[core.log]
+LogTemp=Verbose
++LogNet=Log
++LogNet=Log
+.LogNet=Log
+
+[/Script/Engine.Engine]
+-ActiveGameNameRedirects=(OldGameName=\"A\",NewGameName=\"B\")
+!GameViewportClientClassName
+
+[/Script/ModdedGame.Settings]
+bEnabled=True
+";
+
+#[test]
+fn test_parse_config() {
+    let config = ConfigFile::parse(BASE);
+    assert_eq!(config.sections.len(), 2);
+    assert_eq!(config.sections[0].name, "/Script/Engine.Engine");
+    assert_eq!(config.get("core.log", "logtemp"), Some("Warning"));
+    assert_eq!(
+        config.sections[0].entries[1],
+        ConfigEntry::new(
+            ConfigOperation::AddUnique,
+            "ActiveGameNameRedirects".to_string(),
+            "(OldGameName=\"A\",NewGameName=\"B\")".to_string()
+        )
+    );
+
+    // round trip keeps operators and section order
+    assert_eq!(ConfigFile::parse(&config.to_string()), config);
+    assert_eq!(
+        ConfigEntry::parse("!Key").map(|e| e.to_string()),
+        Some("!Key".to_string())
+    );
+    assert_eq!(ConfigEntry::parse("NotAnEntry"), None);
+}
+
+#[test]
+fn test_combine_config() {
+    let mut config = ConfigFile::parse(BASE);
+    config.combine(&ConfigFile::parse(FRAGMENT));
+
+    assert_eq!(
+        config
+            .sections
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>(),
+        vec![
+            "/Script/Engine.Engine",
+            "Core.Log",
+            "/Script/ModdedGame.Settings"
+        ]
+    );
+
+    let engine = config.get_section("/Script/Engine.Engine").unwrap();
+    assert!(engine.entries.is_empty());
+
+    let log = config.get_section("Core.Log").unwrap();
+    assert_eq!(log.get("LogTemp"), Some("Verbose"));
+    assert_eq!(log.get_all("LogNet").count(), 2);
+
+    assert_eq!(
+        config.get("/Script/ModdedGame.Settings", "bEnabled"),
+        Some("True")
+    );
+}
+
+#[test]
+fn test_config_from_bytes() -> Result<(), ConfigError> {
+    let utf8 = [&[0xef, 0xbb, 0xbf][..], BASE.as_bytes()].concat();
+    assert_eq!(ConfigFile::from_bytes(&utf8)?, ConfigFile::parse(BASE));
+
+    let utf16 = [0xff, 0xfe]
+        .into_iter()
+        .chain(BASE.encode_utf16().flat_map(|e| e.to_le_bytes()))
+        .collect::<Vec<_>>();
+    assert_eq!(ConfigFile::from_bytes(&utf16)?, ConfigFile::parse(BASE));
+
+    let err = ConfigFile::from_bytes(&[0xc3]).expect_err("Expected err");
+    assert!(matches!(err, ConfigError::Utf8(_)));
+
+    Ok(())
+}
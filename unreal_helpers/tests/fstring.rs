@@ -2,7 +2,11 @@
 
 use std::io::Cursor;
 
-use unreal_helpers::{error::FStringError, UnrealReadExt, UnrealWriteExt};
+use unreal_helpers::{
+    error::FStringError,
+    read_ext::{decode_fstring, MAX_FSTRING_LEN},
+    UnrealReadExt, UnrealWriteExt,
+};
 
 #[test]
 fn test_read_fstring() -> Result<(), FStringError> {
@@ -62,3 +66,46 @@ fn test_write_fstring() -> Result<(), FStringError> {
 
     Ok(())
 }
+
+#[test]
+fn test_decode_fstring() -> Result<(), FStringError> {
+    assert_eq!(
+        decode_fstring(b"Default__Object".as_slice(), false)?,
+        "Default__Object"
+    );
+
+    let wide = "Wide\u{00e9}"
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .collect::<Vec<_>>();
+    assert_eq!(decode_fstring(wide, true)?, "Wide\u{00e9}");
+
+    assert!(decode_fstring([0x00, 0xd8], true).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_fstring_len_guards() {
+    // i32::MIN can't be negated
+    let mut cursor = Cursor::new(vec![0u8, 0u8, 0u8, 0x80u8]);
+    let err = cursor.read_fstring().expect_err("Expected err");
+    assert!(matches!(err, FStringError::InvalidStringSize(i32::MIN, 4)));
+
+    // Over the maximum length
+    let mut cursor = Cursor::new(vec![0u8, 0u8, 0x10u8, 0u8]);
+    let err = cursor.read_fstring().expect_err("Expected err");
+    assert!(matches!(err, FStringError::InvalidStringSize(1048575, 4)));
+
+    // Over the maximum length, UTF-16
+    let mut cursor = Cursor::new(vec![0u8, 0u8, 0xf0u8, 0xffu8]);
+    let err = cursor.read_fstring().expect_err("Expected err");
+    assert!(matches!(err, FStringError::InvalidStringSize(1048575, 4)));
+
+    let string = "a".repeat(MAX_FSTRING_LEN as usize);
+    let mut cursor = Cursor::new(Vec::new());
+    let err = cursor
+        .write_fstring(Some(&string))
+        .expect_err("Expected err");
+    assert!(matches!(err, FStringError::StringTooLong(131072)));
+    assert!(cursor.get_ref().is_empty());
+}
@@ -0,0 +1,25 @@
+#![cfg(feature = "guid")]
+
+use unreal_helpers::Guid;
+
+#[test]
+fn test_new_v4() {
+    let guid = Guid::new_v4();
+    assert!(!guid.is_zero());
+    assert_ne!(guid, Guid::new_v4());
+
+    let guid = guid.to_string();
+    assert_eq!(&guid[14..15], "4");
+}
+
+#[test]
+fn test_new_v5() {
+    let namespace = Guid::from_ints(1, 2, 3, 4);
+    let guid = Guid::new_v5(&namespace, "/Game/Maps/Level");
+    assert_eq!(guid, Guid::new_v5(&namespace, "/Game/Maps/Level"));
+    assert_ne!(guid, Guid::new_v5(&namespace, "/Game/Maps/Other"));
+    assert_ne!(guid, Guid::new_v5(&Guid::default(), "/Game/Maps/Level"));
+
+    let guid = guid.to_string();
+    assert_eq!(&guid[14..15], "5");
+}
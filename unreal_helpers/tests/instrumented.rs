@@ -0,0 +1,44 @@
+#![cfg(feature = "read_write")]
+
+use std::io::{self, Cursor, Read, Seek, SeekFrom};
+
+use unreal_helpers::{instrumented::ReaderStats, InstrumentedReader};
+
+#[test]
+fn test_instrumented_reader() -> io::Result<()> {
+    let mut reader = InstrumentedReader::with_buffer_size(Cursor::new(vec![0u8; 64]), 16);
+    let handle = reader.stats_handle();
+
+    let mut buf = [0u8; 4];
+    reader.rewind()?;
+    reader.read_exact(&mut buf)?;
+    reader.read_exact(&mut buf)?;
+    reader.stream_position()?;
+    reader.seek(SeekFrom::Start(32))?;
+    reader.read_exact(&mut buf)?;
+    reader.seek(SeekFrom::Start(0))?;
+    reader.read_exact(&mut buf)?;
+
+    let stats = reader.stats();
+    assert_eq!(
+        stats,
+        ReaderStats {
+            reads: 4,
+            bytes_read: 16,
+            seeks: 4,
+            redundant_seeks: 1,
+            buffer_hits: 1,
+        }
+    );
+    assert_eq!(stats.buffer_hit_rate(), 0.25);
+    assert_eq!(stats.average_read_size(), 4.0);
+
+    let inner = reader.into_inner();
+    assert_eq!(inner.position(), 4);
+    assert_eq!(handle.get(), stats);
+
+    handle.reset();
+    assert_eq!(handle.get(), ReaderStats::default());
+
+    Ok(())
+}
@@ -0,0 +1,394 @@
+//! Plugin (.uplugin) and project (.uproject) descriptors
+
+use serde::{Deserialize, Serialize};
+use serde_json::{ser::PrettyFormatter, Map, Serializer, Value};
+
+use crate::Error;
+
+/// UTF-8 byte order mark, descriptors saved by some tools start with it
+const UTF8_BOM: [u8; 3] = [0xef, 0xbb, 0xbf];
+
+/// Descriptor file version written by current engine versions
+pub const LATEST_DESCRIPTOR_FILE_VERSION: i32 = 3;
+
+/// Environments a module can be loaded in
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum ModuleHostType {
+    #[default]
+    Runtime,
+    RuntimeNoCommandlet,
+    RuntimeAndProgram,
+    CookedOnly,
+    UncookedOnly,
+    Developer,
+    DeveloperTool,
+    Editor,
+    EditorNoCommandlet,
+    EditorAndProgram,
+    Program,
+    ServerOnly,
+    ClientOnly,
+    ClientOnlyNoCommandlet,
+}
+
+/// Phase of engine startup a module is loaded in
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum LoadingPhase {
+    EarliestPossible,
+    PostConfigInit,
+    PostSplashScreen,
+    PreEarlyLoadingScreen,
+    PreLoadingScreen,
+    PreDefault,
+    #[default]
+    Default,
+    PostDefault,
+    PostEngineInit,
+    None,
+}
+
+/// Module of a plugin or project
+///
+/// Platform lists are kept under the key they were read from, UE5 renamed
+/// `WhitelistPlatforms` and `BlacklistPlatforms` to `PlatformAllowList` and `PlatformDenyList`.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ModuleDescriptor {
+    /// Name of the module
+    pub name: String,
+    /// Environments the module is loaded in
+    #[serde(rename = "Type")]
+    pub host_type: ModuleHostType,
+    /// Phase of engine startup the module is loaded in
+    #[serde(default)]
+    pub loading_phase: LoadingPhase,
+    /// Platforms this module is allowed on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platform_allow_list: Vec<String>,
+    /// Platforms this module is not allowed on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platform_deny_list: Vec<String>,
+    /// Platforms this module is allowed on, written by engine versions before UE5
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub whitelist_platforms: Vec<String>,
+    /// Platforms this module is not allowed on, written by engine versions before UE5
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blacklist_platforms: Vec<String>,
+    /// Fields which are not modeled, kept to write them back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ModuleDescriptor {
+    /// Create a new `ModuleDescriptor` instance
+    pub fn new(name: String, host_type: ModuleHostType, loading_phase: LoadingPhase) -> Self {
+        ModuleDescriptor {
+            name,
+            host_type,
+            loading_phase,
+            ..Default::default()
+        }
+    }
+
+    /// Get the platforms this module is allowed on, from either the UE5 or the older key
+    pub fn get_allowed_platforms(&self) -> impl Iterator<Item = &str> {
+        self.platform_allow_list
+            .iter()
+            .chain(&self.whitelist_platforms)
+            .map(String::as_str)
+    }
+
+    /// Get the platforms this module is not allowed on, from either the UE5 or the older key
+    pub fn get_denied_platforms(&self) -> impl Iterator<Item = &str> {
+        self.platform_deny_list
+            .iter()
+            .chain(&self.blacklist_platforms)
+            .map(String::as_str)
+    }
+}
+
+/// Reference to a plugin from a plugin or project
+///
+/// Platform lists are kept under the key they were read from, like in [`ModuleDescriptor`].
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PluginReferenceDescriptor {
+    /// Name of the plugin
+    pub name: String,
+    /// Whether the plugin is enabled
+    pub enabled: bool,
+    /// Whether the plugin may be missing
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Platforms this plugin is enabled on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platform_allow_list: Vec<String>,
+    /// Platforms this plugin is disabled on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub platform_deny_list: Vec<String>,
+    /// Platforms this plugin is enabled on, written by engine versions before UE5
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub whitelist_platforms: Vec<String>,
+    /// Platforms this plugin is disabled on, written by engine versions before UE5
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blacklist_platforms: Vec<String>,
+    /// Fields which are not modeled, kept to write them back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl PluginReferenceDescriptor {
+    /// Create a new `PluginReferenceDescriptor` instance
+    pub fn new(name: String, enabled: bool) -> Self {
+        PluginReferenceDescriptor {
+            name,
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Get the platforms this plugin is enabled on, from either the UE5 or the older key
+    pub fn get_allowed_platforms(&self) -> impl Iterator<Item = &str> {
+        self.platform_allow_list
+            .iter()
+            .chain(&self.whitelist_platforms)
+            .map(String::as_str)
+    }
+
+    /// Get the platforms this plugin is disabled on, from either the UE5 or the older key
+    pub fn get_denied_platforms(&self) -> impl Iterator<Item = &str> {
+        self.platform_deny_list
+            .iter()
+            .chain(&self.blacklist_platforms)
+            .map(String::as_str)
+    }
+}
+
+/// .uplugin file
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct PluginDescriptor {
+    /// Version of the descriptor format
+    pub file_version: i32,
+    /// Version number of the plugin, used to compare versions
+    #[serde(default)]
+    pub version: i32,
+    /// Version of the plugin shown to users
+    #[serde(default)]
+    pub version_name: String,
+    /// Name of the plugin shown to users
+    #[serde(default)]
+    pub friendly_name: String,
+    /// Description of the plugin
+    #[serde(default)]
+    pub description: String,
+    /// Category the plugin is listed in
+    #[serde(default)]
+    pub category: String,
+    /// Author of the plugin
+    #[serde(default)]
+    pub created_by: String,
+    /// Website of the author
+    #[serde(default, rename = "CreatedByURL")]
+    pub created_by_url: String,
+    /// Engine version the plugin was built for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub engine_version: Option<String>,
+    /// Whether the plugin has a content directory
+    #[serde(default)]
+    pub can_contain_content: bool,
+    /// Whether the plugin is marked as beta
+    #[serde(default)]
+    pub is_beta_version: bool,
+    /// Whether the plugin is installed with the engine or from the marketplace
+    #[serde(default)]
+    pub installed: bool,
+    /// Platforms the plugin supports, all platforms if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub supported_target_platforms: Vec<String>,
+    /// Modules of the plugin
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<ModuleDescriptor>,
+    /// Plugins this plugin depends on
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginReferenceDescriptor>,
+    /// Fields which are not modeled, kept to write them back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl PluginDescriptor {
+    /// Create a new `PluginDescriptor` instance with the latest file version
+    pub fn new(friendly_name: String, version_name: String) -> Self {
+        PluginDescriptor {
+            file_version: LATEST_DESCRIPTOR_FILE_VERSION,
+            version: 1,
+            version_name,
+            friendly_name,
+            ..Default::default()
+        }
+    }
+
+    /// Read a `PluginDescriptor` from the contents of a .uplugin file
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        from_slice(data)
+    }
+
+    /// Write this `PluginDescriptor` as the contents of a .uplugin file
+    pub fn to_vec(&self) -> Result<Vec<u8>, Error> {
+        to_vec(self)
+    }
+}
+
+/// .uproject file
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProjectDescriptor {
+    /// Version of the descriptor format
+    pub file_version: i32,
+    /// Engine version or identifier of the engine build the project uses
+    #[serde(default)]
+    pub engine_association: String,
+    /// Category the project is listed in
+    #[serde(default)]
+    pub category: String,
+    /// Description of the project
+    #[serde(default)]
+    pub description: String,
+    /// Modules of the project
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modules: Vec<ModuleDescriptor>,
+    /// Plugins enabled or disabled for the project
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub plugins: Vec<PluginReferenceDescriptor>,
+    /// Platforms the project targets, all platforms if empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub target_platforms: Vec<String>,
+    /// Fields which are not modeled, kept to write them back unchanged
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ProjectDescriptor {
+    /// Create a new `ProjectDescriptor` instance with the latest file version
+    pub fn new(engine_association: String) -> Self {
+        ProjectDescriptor {
+            file_version: LATEST_DESCRIPTOR_FILE_VERSION,
+            engine_association,
+            ..Default::default()
+        }
+    }
+
+    /// Read a `ProjectDescriptor` from the contents of a .uproject file
+    pub fn from_slice(data: &[u8]) -> Result<Self, Error> {
+        from_slice(data)
+    }
+
+    /// Write this `ProjectDescriptor` as the contents of a .uproject file
+    pub fn to_vec(&self) -> Result<Vec<u8>, Error> {
+        to_vec(self)
+    }
+
+    /// Get a plugin reference by name
+    pub fn get_plugin(&self, name: &str) -> Option<&PluginReferenceDescriptor> {
+        self.plugins.iter().find(|e| e.name == name)
+    }
+
+    /// Enable or disable a plugin, adding a reference to it if needed
+    pub fn set_plugin_enabled(&mut self, name: &str, enabled: bool) {
+        match self.plugins.iter_mut().find(|e| e.name == name) {
+            Some(plugin) => plugin.enabled = enabled,
+            None => self
+                .plugins
+                .push(PluginReferenceDescriptor::new(name.to_string(), enabled)),
+        }
+    }
+}
+
+fn from_slice<T: for<'de> Deserialize<'de>>(data: &[u8]) -> Result<T, Error> {
+    let data = data.strip_prefix(&UTF8_BOM).unwrap_or(data);
+    Ok(serde_json::from_slice(data)?)
+}
+
+/// Serialize a descriptor the way the engine does, indented with tabs
+fn to_vec<T: Serialize>(descriptor: &T) -> Result<Vec<u8>, Error> {
+    let mut data = Vec::new();
+    let mut serializer = Serializer::with_formatter(&mut data, PrettyFormatter::with_indent(b"\t"));
+    descriptor.serialize(&mut serializer)?;
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::{ModuleHostType, PluginDescriptor, ProjectDescriptor};
+
+    #[test]
+    fn plugin_round_trip() {
+        let src = "\u{feff}{
+            \"FileVersion\": 3,
+            \"Version\": 2,
+            \"VersionName\": \"1.1\",
+            \"FriendlyName\": \"Test\",
+            \"CanContainContent\": true,
+            \"EnabledByDefault\": true,
+            \"Modules\": [
+                {
+                    \"Name\": \"TestModule\",
+                    \"Type\": \"Runtime\",
+                    \"LoadingPhase\": \"PostConfigInit\",
+                    \"WhitelistPlatforms\": [\"Win64\"]
+                }
+            ],
+            \"Plugins\": [
+                { \"Name\": \"Paper2D\", \"Enabled\": true, \"PlatformDenyList\": [\"Linux\"] }
+            ]
+        }";
+
+        let plugin = PluginDescriptor::from_slice(src.as_bytes()).unwrap();
+        assert_eq!(plugin.friendly_name, "Test");
+        assert_eq!(plugin.modules[0].host_type, ModuleHostType::Runtime);
+        assert_eq!(
+            plugin.modules[0]
+                .get_allowed_platforms()
+                .collect::<Vec<_>>(),
+            ["Win64"]
+        );
+        assert_eq!(
+            plugin.plugins[0].get_denied_platforms().collect::<Vec<_>>(),
+            ["Linux"]
+        );
+        assert_eq!(plugin.extra["EnabledByDefault"], Value::Bool(true));
+
+        // platform lists are written back under the key they were read from
+        let written = plugin.to_vec().unwrap();
+        let written_json: Value = serde_json::from_slice(&written).unwrap();
+        assert_eq!(
+            written_json["Modules"][0]["WhitelistPlatforms"],
+            serde_json::json!(["Win64"])
+        );
+        assert!(written_json["Modules"][0]
+            .get("PlatformAllowList")
+            .is_none());
+        assert_eq!(
+            written_json["Plugins"][0]["PlatformDenyList"],
+            serde_json::json!(["Linux"])
+        );
+
+        assert_eq!(PluginDescriptor::from_slice(&written).unwrap(), plugin);
+    }
+
+    #[test]
+    fn project_plugins() {
+        let mut project = ProjectDescriptor::new("4.27".to_string());
+        project.set_plugin_enabled("Paper2D", false);
+        project.set_plugin_enabled("Paper2D", true);
+        assert_eq!(project.plugins.len(), 1);
+        assert!(project.get_plugin("Paper2D").unwrap().enabled);
+
+        let written = project.to_vec().unwrap();
+        assert!(written.starts_with(b"{\n\t\""));
+        assert_eq!(ProjectDescriptor::from_slice(&written).unwrap(), project);
+    }
+}
@@ -5,6 +5,9 @@ pub enum IntegrationError {
     GameNotFound,
     AssetNotFound(String),
     CorruptedStarterPak,
+    TextPatchFailed(String),
+    TemplateGenerationFailed(String),
+    InvalidSplitPakName(String),
 }
 
 impl IntegrationError {
@@ -19,6 +22,18 @@ impl IntegrationError {
     pub fn corrupted_starter_pak() -> Self {
         Self::CorruptedStarterPak
     }
+
+    pub fn text_patch_failed(reason: String) -> Self {
+        Self::TextPatchFailed(reason)
+    }
+
+    pub fn template_generation_failed(reason: String) -> Self {
+        Self::TemplateGenerationFailed(reason)
+    }
+
+    pub fn invalid_split_pak_name(reason: String) -> Self {
+        Self::InvalidSplitPakName(reason)
+    }
 }
 
 impl Display for IntegrationError {
@@ -27,6 +42,13 @@ impl Display for IntegrationError {
             Self::GameNotFound => write!(f, "Game not found"),
             Self::AssetNotFound(ref name) => write!(f, "Asset {name:?} not found"),
             Self::CorruptedStarterPak => write!(f, "Corrupted starter pak"),
+            Self::TextPatchFailed(ref reason) => write!(f, "Text patch failed: {reason}"),
+            Self::TemplateGenerationFailed(ref reason) => {
+                write!(f, "Template generation failed: {reason}")
+            }
+            Self::InvalidSplitPakName(ref reason) => {
+                write!(f, "Invalid split pak name: {reason}")
+            }
         }
     }
 }
@@ -38,7 +60,10 @@ pub enum ErrorCode {
     Pak(unreal_pak::error::PakError),
     UnrealModMetaData(unreal_mod_metadata::error::Error),
     Json(serde_json::Error),
+    Config(unreal_helpers::error::ConfigError),
     Integration(IntegrationError),
+    #[cfg(feature = "watcher")]
+    Watcher(notify::Error),
     Other(Box<dyn std::error::Error + Send>),
 }
 
@@ -49,7 +74,10 @@ impl Display for ErrorCode {
             ErrorCode::Uasset(ref err) => Display::fmt(err, f),
             ErrorCode::Pak(ref err) => Display::fmt(err, f),
             ErrorCode::Json(ref err) => Display::fmt(err, f),
+            ErrorCode::Config(ref err) => Display::fmt(err, f),
             ErrorCode::Integration(ref err) => Display::fmt(err, f),
+            #[cfg(feature = "watcher")]
+            ErrorCode::Watcher(ref err) => Display::fmt(err, f),
             ErrorCode::Other(ref err) => Display::fmt(err, f),
             ErrorCode::UnrealModMetaData(ref err) => Display::fmt(err, f),
         }
@@ -117,6 +145,23 @@ impl From<serde_json::Error> for Error {
     }
 }
 
+impl From<unreal_helpers::error::ConfigError> for Error {
+    fn from(e: unreal_helpers::error::ConfigError) -> Self {
+        Error {
+            code: ErrorCode::Config(e),
+        }
+    }
+}
+
+#[cfg(feature = "watcher")]
+impl From<notify::Error> for Error {
+    fn from(e: notify::Error) -> Self {
+        Error {
+            code: ErrorCode::Watcher(e),
+        }
+    }
+}
+
 impl Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         Display::fmt(&self.code, f)
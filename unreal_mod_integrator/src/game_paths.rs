@@ -0,0 +1,488 @@
+//! Game path discovery
+//!
+//! Helpers to find where a game is installed and where its paks are, for the platforms most
+//! games are installed from: Steam (including Steam libraries on other drives), Proton prefixes
+//! of Windows games running on Linux through Steam and the Epic Games Launcher.
+//!
+//! All helpers only read launcher files and never start a launcher,
+//! missing launchers and games are reported as `None`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+/// Platform a game is installed from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamePlatform {
+    /// Steam
+    Steam,
+    /// Steam on Linux running the game through Proton
+    Proton,
+    /// Epic Games Launcher
+    Epic,
+}
+
+/// Installed game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameInstall {
+    /// Platform the game was found on
+    pub platform: GamePlatform,
+    /// Install directory, the engine root of the game
+    pub install_path: PathBuf,
+    /// Wine prefix of the game for [`GamePlatform::Proton`]
+    pub prefix_path: Option<PathBuf>,
+}
+
+impl GameInstall {
+    /// Get the directory with the game paks, `<install>/<GameName>/Content/Paks`
+    pub fn game_paks_path(&self, game_name: &str) -> PathBuf {
+        game_paks_path(&self.install_path, game_name)
+    }
+
+    /// Get the directory mods are usually installed to, `%LocalAppData%/<GameName>/Saved/Paks`
+    ///
+    /// For [`GamePlatform::Proton`] this is inside the wine prefix, on other platforms
+    /// `None` is returned if the local app data directory can't be determined.
+    pub fn mods_paks_path(&self, game_name: &str) -> Option<PathBuf> {
+        let local_app_data = match self.prefix_path {
+            Some(ref prefix_path) => prefix_local_app_data(prefix_path),
+            None => PathBuf::from(env::var_os("LOCALAPPDATA")?),
+        };
+        Some(local_app_data.join(game_name).join("Saved").join("Paks"))
+    }
+}
+
+/// Get the directory with the game paks of an install directory, `<install>/<GameName>/Content/Paks`
+pub fn game_paks_path(install_path: &Path, game_name: &str) -> PathBuf {
+    install_path.join(game_name).join("Content").join("Paks")
+}
+
+/// Find a game on all supported platforms
+///
+/// Steam is searched first if `steam_app_id` is set, the Epic Games Launcher if `epic_app_name` is set.
+/// A Steam game is reported as [`GamePlatform::Proton`] if a Proton prefix exists for it.
+pub fn find_game(steam_app_id: Option<u32>, epic_app_name: Option<&str>) -> Option<GameInstall> {
+    if let Some(app_id) = steam_app_id {
+        if let Some(install_path) = find_steam_app(app_id) {
+            let prefix_path = find_proton_prefix(app_id);
+            return Some(GameInstall {
+                platform: match prefix_path {
+                    Some(_) => GamePlatform::Proton,
+                    None => GamePlatform::Steam,
+                },
+                install_path,
+                prefix_path,
+            });
+        }
+    }
+
+    if let Some(app_name) = epic_app_name {
+        if let Some(install_path) = find_epic_app(app_name) {
+            return Some(GameInstall {
+                platform: GamePlatform::Epic,
+                install_path,
+                prefix_path: None,
+            });
+        }
+    }
+
+    None
+}
+
+/// Get the user's home directory
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+/// Get all existing Steam installation directories
+///
+/// The `STEAM_DIR` environment variable is checked first, then the default locations
+/// of Windows, macOS and Linux, including the Flatpak and Snap versions of Steam.
+pub fn steam_roots() -> Vec<PathBuf> {
+    let mut candidates = Vec::new();
+
+    if let Some(steam_dir) = env::var_os("STEAM_DIR") {
+        candidates.push(PathBuf::from(steam_dir));
+    }
+
+    for program_files in ["ProgramFiles(x86)", "ProgramFiles"] {
+        if let Some(program_files) = env::var_os(program_files) {
+            candidates.push(PathBuf::from(program_files).join("Steam"));
+        }
+    }
+
+    if let Some(home) = home_dir() {
+        candidates.push(home.join(".steam").join("steam"));
+        candidates.push(home.join(".local").join("share").join("Steam"));
+        candidates.push(
+            home.join(".var")
+                .join("app")
+                .join("com.valvesoftware.Steam")
+                .join(".local")
+                .join("share")
+                .join("Steam"),
+        );
+        candidates.push(
+            home.join("snap")
+                .join("steam")
+                .join("common")
+                .join(".local")
+                .join("share")
+                .join("Steam"),
+        );
+        candidates.push(
+            home.join("Library")
+                .join("Application Support")
+                .join("Steam"),
+        );
+    }
+
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for candidate in candidates {
+        if !candidate.join("steamapps").is_dir() {
+            continue;
+        }
+        // ~/.steam/steam is usually a symlink to one of the other directories
+        let candidate = fs::canonicalize(&candidate).unwrap_or(candidate);
+        if !roots.contains(&candidate) {
+            roots.push(candidate);
+        }
+    }
+    roots
+}
+
+/// Read all key value pairs of a Valve KeyValues (VDF/ACF) text, ignoring the nesting
+///
+/// Keys are returned in the order they appear in, which is enough to look up
+/// the few values needed from launcher files.
+pub fn parse_vdf_values(text: &str) -> Vec<(String, String)> {
+    let mut values = Vec::new();
+    let mut key: Option<String> = None;
+    let mut chars = text.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        match char {
+            '"' => {
+                let mut token = String::new();
+                while let Some(char) = chars.next() {
+                    match char {
+                        '"' => break,
+                        '\\' => match chars.next() {
+                            Some('n') => token.push('\n'),
+                            Some('t') => token.push('\t'),
+                            Some(char) => token.push(char),
+                            None => break,
+                        },
+                        char => token.push(char),
+                    }
+                }
+                match key.take() {
+                    Some(key) => values.push((key, token)),
+                    None => key = Some(token),
+                }
+            }
+            '{' | '}' => key = None,
+            '/' if chars.peek() == Some(&'/') => {
+                for char in chars.by_ref() {
+                    if char == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    values
+}
+
+/// Get all library folders of a Steam installation, starting with the installation itself
+pub fn steam_library_folders(steam_root: &Path) -> Vec<PathBuf> {
+    let mut folders = vec![steam_root.to_path_buf()];
+
+    let Ok(library_folders) =
+        fs::read_to_string(steam_root.join("steamapps").join("libraryfolders.vdf"))
+    else {
+        return folders;
+    };
+
+    for (key, value) in parse_vdf_values(&library_folders) {
+        // old files store paths directly under numbered keys
+        if key == "path" || key.parse::<u32>().is_ok() {
+            let folder = PathBuf::from(value);
+            if folder.join("steamapps").is_dir() && !folders.contains(&folder) {
+                folders.push(folder);
+            }
+        }
+    }
+
+    folders
+}
+
+/// Find the Steam library folder a Steam app is installed in
+fn find_steam_library(app_id: u32) -> Option<(PathBuf, String)> {
+    let manifest_name = format!("appmanifest_{app_id}.acf");
+
+    steam_roots()
+        .into_iter()
+        .flat_map(|e| steam_library_folders(&e))
+        .find_map(|library| {
+            let manifest =
+                fs::read_to_string(library.join("steamapps").join(&manifest_name)).ok()?;
+            let (_, install_dir) = parse_vdf_values(&manifest)
+                .into_iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case("installdir"))?;
+            Some((library, install_dir))
+        })
+}
+
+/// Find the install directory of a Steam app
+pub fn find_steam_app(app_id: u32) -> Option<PathBuf> {
+    let (library, install_dir) = find_steam_library(app_id)?;
+    let install_path = library.join("steamapps").join("common").join(install_dir);
+    install_path.is_dir().then_some(install_path)
+}
+
+/// Find the Proton wine prefix of a Steam app
+///
+/// Prefixes are stored in the library the app is installed in, `<library>/steamapps/compatdata/<app id>/pfx`.
+pub fn find_proton_prefix(app_id: u32) -> Option<PathBuf> {
+    let (library, _) = find_steam_library(app_id)?;
+    let prefix_path = library
+        .join("steamapps")
+        .join("compatdata")
+        .join(app_id.to_string())
+        .join("pfx");
+    prefix_path.is_dir().then_some(prefix_path)
+}
+
+/// Get the local app data directory of the Steam user inside a wine prefix
+pub fn prefix_local_app_data(prefix_path: &Path) -> PathBuf {
+    prefix_path
+        .join("drive_c")
+        .join("users")
+        .join("steamuser")
+        .join("AppData")
+        .join("Local")
+}
+
+/// Epic Games Launcher install manifest, stored as a `.item` file
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct EpicManifest {
+    /// Name of the app, e.g. `Fortnite`
+    pub app_name: String,
+    /// Display name of the app
+    #[serde(default)]
+    pub display_name: String,
+    /// Install directory
+    pub install_location: PathBuf,
+}
+
+/// Get the directory the Epic Games Launcher stores install manifests in
+pub fn epic_manifests_dir() -> Option<PathBuf> {
+    let program_data = env::var_os("ProgramData")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("C:\\ProgramData"));
+    let manifests_dir = program_data
+        .join("Epic")
+        .join("EpicGamesLauncher")
+        .join("Data")
+        .join("Manifests");
+    manifests_dir.is_dir().then_some(manifests_dir)
+}
+
+/// Read all Epic Games Launcher install manifests in a directory, invalid manifests are skipped
+pub fn read_epic_manifests(manifests_dir: &Path) -> Vec<EpicManifest> {
+    let Ok(entries) = fs::read_dir(manifests_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|e| e.extension().map(|e| e == "item").unwrap_or(false))
+        .filter_map(|e| fs::read(e).ok())
+        .filter_map(|e| serde_json::from_slice(&e).ok())
+        .collect()
+}
+
+/// Find the install directory of an Epic Games Launcher app by its app name or display name
+pub fn find_epic_app(app_name: &str) -> Option<PathBuf> {
+    read_epic_manifests(&epic_manifests_dir()?)
+        .into_iter()
+        .find(|e| e.app_name == app_name || e.display_name.eq_ignore_ascii_case(app_name))
+        .map(|e| e.install_location)
+        .filter(|e| e.is_dir())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs;
+    use std::path::Path;
+
+    use serde_json::json;
+
+    use super::{
+        find_game, parse_vdf_values, read_epic_manifests, steam_library_folders, GameInstall,
+        GamePlatform,
+    };
+
+    const PROTON_APP_ID: u32 = 999999999;
+    const STEAM_APP_ID: u32 = 999999998;
+
+    fn write(path: &Path, contents: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, contents).unwrap();
+    }
+
+    fn app_manifest(app_id: u32, install_dir: &str) -> String {
+        format!(
+            "\"AppState\"\n{{\n\t\"appid\"\t\t\"{app_id}\"\n\t\"InstallDir\"\t\t\"{install_dir}\"\n}}\n"
+        )
+    }
+
+    #[test]
+    fn parse_vdf() {
+        let text = r#"
+            // comment with "quotes"
+            "libraryfolders"
+            {
+                "0" { "path" "C:\\Program Files (x86)\\Steam" }
+                "name" "tab\tquote\""
+            }
+        "#;
+        assert_eq!(
+            parse_vdf_values(text),
+            [
+                ("path", "C:\\Program Files (x86)\\Steam"),
+                ("name", "tab\tquote\""),
+            ]
+            .map(|(key, value)| (key.to_string(), value.to_string()))
+        );
+    }
+
+    #[test]
+    fn find_installed_games() {
+        let directory = tempfile::tempdir().unwrap();
+        let directory = fs::canonicalize(directory.path()).unwrap();
+
+        // the proton app is installed in a second library on another drive
+        let steam_root = directory.join("Steam");
+        let library = directory.join("Library");
+        let escape = |path: &Path| path.to_string_lossy().replace('\\', "\\\\");
+        write(
+            &steam_root.join("steamapps").join("libraryfolders.vdf"),
+            &format!(
+                "\"libraryfolders\"\n{{\n\t\"0\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"1\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n\t\"2\"\n\t{{\n\t\t\"path\"\t\t\"{}\"\n\t}}\n}}\n",
+                escape(&steam_root),
+                escape(&library),
+                escape(&directory.join("Missing")),
+            ),
+        );
+
+        let steamapps = steam_root.join("steamapps");
+        write(
+            &steamapps.join(format!("appmanifest_{STEAM_APP_ID}.acf")),
+            &app_manifest(STEAM_APP_ID, "SteamGame"),
+        );
+        fs::create_dir_all(steamapps.join("common").join("SteamGame")).unwrap();
+
+        let steamapps = library.join("steamapps");
+        write(
+            &steamapps.join(format!("appmanifest_{PROTON_APP_ID}.acf")),
+            &app_manifest(PROTON_APP_ID, "ProtonGame"),
+        );
+        fs::create_dir_all(steamapps.join("common").join("ProtonGame")).unwrap();
+        let prefix_path = steamapps
+            .join("compatdata")
+            .join(PROTON_APP_ID.to_string())
+            .join("pfx");
+        fs::create_dir_all(&prefix_path).unwrap();
+
+        // missing libraries are skipped
+        assert_eq!(
+            steam_library_folders(&steam_root),
+            [steam_root.clone(), library.clone()]
+        );
+
+        // invalid manifests and other files are skipped
+        let program_data = directory.join("ProgramData");
+        let manifests_dir = program_data
+            .join("Epic")
+            .join("EpicGamesLauncher")
+            .join("Data")
+            .join("Manifests");
+        let epic_install = directory.join("Epic").join("EpicGame");
+        fs::create_dir_all(&epic_install).unwrap();
+        let manifest = json!({
+            "AppName": "EpicApp",
+            "DisplayName": "Epic Game",
+            "InstallLocation": epic_install,
+        });
+        write(&manifests_dir.join("EpicApp.item"), &manifest.to_string());
+        write(&manifests_dir.join("Invalid.item"), "{");
+        write(&manifests_dir.join("Other.txt"), &manifest.to_string());
+        assert_eq!(read_epic_manifests(&manifests_dir).len(), 1);
+
+        env::set_var("STEAM_DIR", &steam_root);
+        env::set_var("ProgramData", &program_data);
+
+        let game = find_game(Some(PROTON_APP_ID), Some("EpicApp")).unwrap();
+        assert_eq!(
+            game,
+            GameInstall {
+                platform: GamePlatform::Proton,
+                install_path: steamapps.join("common").join("ProtonGame"),
+                prefix_path: Some(prefix_path.clone()),
+            }
+        );
+        assert_eq!(
+            game.game_paks_path("Game"),
+            steamapps
+                .join("common")
+                .join("ProtonGame")
+                .join("Game")
+                .join("Content")
+                .join("Paks")
+        );
+        assert_eq!(
+            game.mods_paks_path("Game"),
+            Some(
+                prefix_path
+                    .join("drive_c")
+                    .join("users")
+                    .join("steamuser")
+                    .join("AppData")
+                    .join("Local")
+                    .join("Game")
+                    .join("Saved")
+                    .join("Paks")
+            )
+        );
+
+        let game = find_game(Some(STEAM_APP_ID), None).unwrap();
+        assert_eq!(game.platform, GamePlatform::Steam);
+        assert_eq!(
+            game.install_path,
+            steam_root
+                .join("steamapps")
+                .join("common")
+                .join("SteamGame")
+        );
+
+        // games missing on steam are searched on epic, by app name or display name
+        let game = find_game(Some(1), Some("epic game")).unwrap();
+        assert_eq!(game.platform, GamePlatform::Epic);
+        assert_eq!(game.install_path, epic_install);
+        assert_eq!(find_game(None, Some("Missing")), None);
+
+        env::remove_var("STEAM_DIR");
+        env::remove_var("ProgramData");
+    }
+}
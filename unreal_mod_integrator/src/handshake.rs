@@ -0,0 +1,153 @@
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use unreal_mod_metadata::{Metadata, SyncMode};
+use unreal_pak::{PakMemory, PakReader};
+
+use crate::{Error, INTEGRATOR_PAK_FILE_NAME};
+
+/// Path of the handshake file inside of the integrator pak, relative to the game directory
+pub const HANDSHAKE_FILE_PATH: &str = "Content/Integrator/Handshake.json";
+
+/// Current handshake format version
+pub const HANDSHAKE_SCHEMA_VERSION: usize = 1;
+
+/// Information about the integrated mods shared with in-game mod frameworks
+///
+/// This gets written as JSON into the integrator pak so that the game
+/// and the launcher agree on which mods are installed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Handshake {
+    /// Handshake format version, see [`HANDSHAKE_SCHEMA_VERSION`]
+    pub schema_version: usize,
+    /// Version of the integrator which wrote the handshake
+    pub integrator_version: String,
+    /// Should connections with a different set of synced mods be refused
+    pub refuse_mismatched_connections: bool,
+    /// Integrated mods
+    pub mods: Vec<HandshakeMod>,
+}
+
+/// A single integrated mod
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HandshakeMod {
+    /// Mod id
+    pub mod_id: String,
+    /// Display name
+    pub name: String,
+    /// Mod version
+    pub version: String,
+    /// Sync mode, mods without one are synced between server and client
+    pub sync: SyncMode,
+    /// Full metadata of the mod
+    pub metadata: Metadata,
+}
+
+impl Handshake {
+    /// Create a new `Handshake` instance for the given integrated mods
+    pub fn new(
+        integrator_version: String,
+        refuse_mismatched_connections: bool,
+        mods: &[Metadata],
+    ) -> Self {
+        Handshake {
+            schema_version: HANDSHAKE_SCHEMA_VERSION,
+            integrator_version,
+            refuse_mismatched_connections,
+            mods: mods
+                .iter()
+                .map(|metadata| HandshakeMod {
+                    mod_id: metadata.mod_id.clone(),
+                    name: metadata.name.clone(),
+                    version: metadata.mod_version.clone(),
+                    sync: metadata.sync.unwrap_or(SyncMode::ServerAndClient),
+                    metadata: metadata.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Get all mods which need to be installed on both server and client
+    pub fn synced_mods(&self) -> impl Iterator<Item = &HandshakeMod> {
+        self.mods
+            .iter()
+            .filter(|e| e.sync == SyncMode::ServerAndClient)
+    }
+}
+
+fn get_handshake_path(game_name: &str) -> String {
+    format!("{game_name}/{HANDSHAKE_FILE_PATH}")
+}
+
+/// Write a handshake into a pak
+pub fn write_handshake(
+    pak: &mut PakMemory,
+    game_name: &str,
+    handshake: &Handshake,
+) -> Result<(), Error> {
+    pak.set_entry(
+        get_handshake_path(game_name),
+        serde_json::to_vec_pretty(handshake)?,
+    );
+    Ok(())
+}
+
+/// Read a handshake from a pak
+pub fn read_handshake<R: Read + Seek>(
+    pak: &mut PakReader<R>,
+    game_name: &str,
+) -> Result<Handshake, Error> {
+    let data = pak.read_entry(&get_handshake_path(game_name))?;
+    Ok(serde_json::from_slice(&data)?)
+}
+
+/// Read the handshake from the integrator pak in a paks directory
+pub fn read_installed_handshake(paks_path: &Path, game_name: &str) -> Result<Handshake, Error> {
+    let file = File::open(paks_path.join(INTEGRATOR_PAK_FILE_NAME))?;
+
+    let mut pak = PakReader::new(BufReader::new(file));
+    pak.load_index()?;
+
+    read_handshake(&mut pak, game_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+
+    use unreal_mod_metadata::SyncMode;
+    use unreal_pak::{pakversion::PakVersion, PakMemory};
+
+    use super::{read_installed_handshake, write_handshake, Handshake};
+
+    #[test]
+    fn handshake_round_trip() {
+        let mods = [("First", "serverclient"), ("Second", "client")].map(|(mod_id, sync)| {
+            let src = format!(
+                r#"{{"name": "{mod_id}", "mod_id": "{mod_id}", "version": "1.0.0", "sync": "{sync}"}}"#
+            );
+            unreal_mod_metadata::from_slice(src.as_bytes()).unwrap()
+        });
+        let handshake = Handshake::new(String::from("0.1.0"), true, &mods);
+
+        assert_eq!(handshake.mods[1].sync, SyncMode::ClientOnly);
+        let synced = handshake
+            .synced_mods()
+            .map(|e| e.mod_id.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(synced, ["First"]);
+
+        let directory = tempfile::tempdir().unwrap();
+        let mut pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+        write_handshake(&mut pak, "Game", &handshake).unwrap();
+        pak.write(
+            &mut File::create(directory.path().join(crate::INTEGRATOR_PAK_FILE_NAME)).unwrap(),
+        )
+        .unwrap();
+
+        let installed = read_installed_handshake(directory.path(), "Game").unwrap();
+        assert_eq!(installed, handshake);
+    }
+}
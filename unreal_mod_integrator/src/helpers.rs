@@ -3,6 +3,7 @@ use std::io::{BufReader, Cursor};
 use std::path::Path;
 
 use unreal_asset::{engine_version::EngineVersion, reader::ArchiveTrait, Asset};
+use unreal_helpers::ConfigFile;
 use unreal_pak::{PakMemory, PakReader};
 
 use crate::{error::IntegrationError, Error};
@@ -122,3 +123,20 @@ pub fn write_asset<C: std::io::Read + std::io::Seek>(
     }
     Ok(())
 }
+
+/// Merge config (.ini) fragments into a config file
+///
+/// Fragments are combined in order using Unreal's config operators, so later fragments win.
+/// The merged config is returned as UTF-8.
+pub fn merge_config(base: Option<&[u8]>, fragments: &[&[u8]]) -> Result<Vec<u8>, Error> {
+    let mut config = match base {
+        Some(base) => ConfigFile::from_bytes(base)?,
+        None => ConfigFile::new(),
+    };
+
+    for fragment in fragments {
+        config.combine(&ConfigFile::from_bytes(fragment)?);
+    }
+
+    Ok(config.to_string().into_bytes())
+}
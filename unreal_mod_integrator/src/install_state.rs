@@ -0,0 +1,371 @@
+//! Install state
+//!
+//! Records what an integration was based on, the installed mods, the game paks and the paks
+//! generated by the integrator, so launchers can tell when integration needs to run again.
+//! The state is stored as JSON.
+
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{BufReader, Cursor, ErrorKind, Read, Seek, Write};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+
+use unreal_pak::{manifest::HashAlgorithm, AtomicFile, PakReader};
+
+use crate::{is_integrator_pak, Error, IntegratorMod, IntegratorModInfo};
+
+/// File name state files are usually stored with
+pub const INSTALL_STATE_FILE_NAME: &str = "integrator_state.json";
+
+/// State of a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileState {
+    /// File name
+    pub file_name: String,
+    /// File size
+    pub size: u64,
+    /// Last modification time in seconds since the unix epoch
+    pub modified: u64,
+    /// Hex encoded SHA-1 hash of the file, not recorded for game paks
+    pub hash: Option<String>,
+}
+
+impl FileState {
+    /// Get the state of a file, hashing the file if `hash` is set
+    pub fn from_path(path: &Path, hash: bool) -> Result<Self, Error> {
+        let metadata = fs::metadata(path)?;
+        let modified = metadata
+            .modified()
+            .ok()
+            .and_then(|e| e.duration_since(UNIX_EPOCH).ok())
+            .map(|e| e.as_secs())
+            .unwrap_or_default();
+
+        let hash = match hash {
+            true => Some(hash_reader(&mut BufReader::new(File::open(path)?))?),
+            false => None,
+        };
+
+        Ok(FileState {
+            file_name: path
+                .file_name()
+                .map(|e| e.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            size: metadata.len(),
+            modified,
+            hash,
+        })
+    }
+
+    /// Check if the file changed, files with a hash are only compared by their hash
+    pub fn changed(&self, current: &FileState) -> bool {
+        match (&self.hash, &current.hash) {
+            (Some(hash), Some(current_hash)) => hash != current_hash,
+            _ => self.size != current.size || self.modified != current.modified,
+        }
+    }
+}
+
+/// Installed mod
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstalledMod {
+    /// Mod id
+    pub mod_id: String,
+    /// Mod version, `None` for dynamic mods
+    pub mod_version: Option<String>,
+    /// Pak of the mod, `None` for baked and dynamic mods
+    pub file: Option<FileState>,
+}
+
+/// Difference between a recorded and the current install state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Drift {
+    /// The integrator version changed
+    IntegratorUpdated(String),
+    /// A game pak was added, removed or changed, e.g. by a game update
+    GameFileChanged(String),
+    /// A mod was installed
+    ModAdded(String),
+    /// A mod was uninstalled
+    ModRemoved(String),
+    /// A mod was updated or its pak was modified
+    ModChanged(String),
+    /// A pak generated by the integrator was modified or removed
+    GeneratedPakChanged(String),
+}
+
+/// State of an installation
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InstallState {
+    /// Integrator version
+    pub integrator_version: String,
+    /// Installed mods
+    pub mods: Vec<InstalledMod>,
+    /// Game paks
+    pub game_paks: Vec<FileState>,
+    /// Paks generated by the integrator
+    pub generated_paks: Vec<FileState>,
+}
+
+/// Hex encoded SHA-1 hash of a reader
+fn hash_reader<R: Read>(reader: &mut R) -> Result<String, Error> {
+    let hash = HashAlgorithm::Sha1.hash_reader(reader)?;
+    Ok(hash.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    }))
+}
+
+/// Read the version of a mod from its pak
+fn read_mod_version<R: Read + Seek>(reader: R) -> Result<String, Error> {
+    let mut pak = PakReader::new(reader);
+    pak.load_index()?;
+    let metadata = unreal_mod_metadata::from_slice(&pak.read_entry("metadata.json")?)?;
+    Ok(metadata.mod_version)
+}
+
+/// Get the states of all .pak files in a directory, sorted by file name
+fn read_paks(
+    path: &Path,
+    filter: impl Fn(&str) -> bool,
+    hash: bool,
+) -> Result<Vec<FileState>, Error> {
+    let mut paks = Vec::new();
+    for entry in fs::read_dir(path)?.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if file_name.ends_with(".pak") && filter(&file_name) {
+            paks.push(FileState::from_path(&entry.path(), hash)?);
+        }
+    }
+    paks.sort_by(|a, b| a.file_name.cmp(&b.file_name));
+    Ok(paks)
+}
+
+impl InstallState {
+    /// Capture the current state of an installation
+    ///
+    /// `paks_path` and `game_path` are the same paths passed to [`crate::integrate_mods`].
+    /// Mod paks and generated paks are hashed, game paks are only compared by size and modification time.
+    pub fn capture<E: std::error::Error>(
+        integrator_version: &str,
+        mods: &[IntegratorMod<E>],
+        paks_path: &Path,
+        game_path: &Path,
+    ) -> Result<Self, Error> {
+        let mut installed_mods = Vec::with_capacity(mods.len());
+        for integrator_mod in mods {
+            let (mod_version, file) = match integrator_mod {
+                IntegratorMod::File(file_mod) => (
+                    Some(read_mod_version(BufReader::new(File::open(
+                        &file_mod.path,
+                    )?))?),
+                    Some(FileState::from_path(&file_mod.path, true)?),
+                ),
+                IntegratorMod::Baked(baked_mod) => {
+                    (Some(read_mod_version(Cursor::new(baked_mod.data))?), None)
+                }
+                IntegratorMod::Dynamic(_) => (None, None),
+            };
+
+            installed_mods.push(InstalledMod {
+                mod_id: integrator_mod.get_mod_id(),
+                mod_version,
+                file,
+            });
+        }
+
+        Ok(InstallState {
+            integrator_version: integrator_version.to_string(),
+            mods: installed_mods,
+            game_paks: read_paks(game_path, |_| true, false)?,
+            generated_paks: read_paks(paks_path, is_integrator_pak, true)?,
+        })
+    }
+
+    /// Load a state file, returns `None` if it doesn't exist
+    pub fn load(path: &Path) -> Result<Option<Self>, Error> {
+        match fs::read(path) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Save this state, the state file is only replaced once writing succeeded
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let mut file = AtomicFile::create(path)?;
+        file.write_all(&serde_json::to_vec_pretty(self)?)?;
+        file.commit()?;
+        Ok(())
+    }
+
+    /// Find everything that changed since this state was recorded
+    pub fn detect_drift(&self, current: &InstallState) -> Vec<Drift> {
+        let mut drift = Vec::new();
+
+        if self.integrator_version != current.integrator_version {
+            drift.push(Drift::IntegratorUpdated(current.integrator_version.clone()));
+        }
+
+        let changed_files = |recorded: &[FileState], current: &[FileState]| {
+            let mut changed = Vec::new();
+            for file in recorded {
+                match current.iter().find(|e| e.file_name == file.file_name) {
+                    Some(current_file) if !file.changed(current_file) => {}
+                    _ => changed.push(file.file_name.clone()),
+                }
+            }
+            for file in current {
+                if !recorded.iter().any(|e| e.file_name == file.file_name) {
+                    changed.push(file.file_name.clone());
+                }
+            }
+            changed
+        };
+
+        drift.extend(
+            changed_files(&self.game_paks, &current.game_paks)
+                .into_iter()
+                .map(Drift::GameFileChanged),
+        );
+
+        for installed_mod in &self.mods {
+            match current
+                .mods
+                .iter()
+                .find(|e| e.mod_id == installed_mod.mod_id)
+            {
+                Some(current_mod) => {
+                    let file_changed = match (&installed_mod.file, &current_mod.file) {
+                        (Some(file), Some(current_file)) => file.changed(current_file),
+                        (file, current_file) => file.is_some() != current_file.is_some(),
+                    };
+                    if file_changed || installed_mod.mod_version != current_mod.mod_version {
+                        drift.push(Drift::ModChanged(installed_mod.mod_id.clone()));
+                    }
+                }
+                None => drift.push(Drift::ModRemoved(installed_mod.mod_id.clone())),
+            }
+        }
+        for current_mod in &current.mods {
+            if !self.mods.iter().any(|e| e.mod_id == current_mod.mod_id) {
+                drift.push(Drift::ModAdded(current_mod.mod_id.clone()));
+            }
+        }
+
+        drift.extend(
+            changed_files(&self.generated_paks, &current.generated_paks)
+                .into_iter()
+                .map(Drift::GeneratedPakChanged),
+        );
+
+        drift
+    }
+
+    /// Check if integration needs to run again to match the current state
+    pub fn needs_reintegration(&self, current: &InstallState) -> bool {
+        !self.detect_drift(current).is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::{self, File};
+    use std::path::Path;
+
+    use unreal_pak::{pakversion::PakVersion, PakWriter};
+
+    use super::{Drift, InstallState};
+    use crate::{Error, FileMod, IntegratorMod, INTEGRATOR_PAK_FILE_NAME};
+
+    const MOD_FILE_NAME: &str = "000-TestMod-1.0.0_P.pak";
+    const GAME_FILE_NAME: &str = "Game-WindowsNoEditor.pak";
+
+    fn write_mod_pak(path: &Path, extra_entry: bool) {
+        let metadata = r#"{"name": "TestMod", "mod_id": "TestMod", "version": "1.0.0"}"#;
+        let mut pak = PakWriter::new(File::create(path).unwrap(), PakVersion::FrozenIndex);
+        pak.write_entry(&String::from("metadata.json"), metadata.as_bytes(), false)
+            .unwrap();
+        if extra_entry {
+            pak.write_entry(&String::from("Extra.bin"), &[1u8; 0x10], false)
+                .unwrap();
+        }
+        pak.finish_write().unwrap();
+    }
+
+    fn capture(paks_path: &Path, game_path: &Path, installed: bool) -> InstallState {
+        let mods = match installed {
+            true => vec![IntegratorMod::<Error>::File(FileMod {
+                path: paks_path.join(MOD_FILE_NAME),
+                mod_id: String::from("TestMod"),
+                priority: 0,
+            })],
+            false => Vec::new(),
+        };
+        InstallState::capture("0.1.0", &mods, paks_path, game_path).unwrap()
+    }
+
+    #[test]
+    fn detect_drift() {
+        let directory = tempfile::tempdir().unwrap();
+        let paks_path = directory.path().join("paks");
+        let game_path = directory.path().join("game");
+        fs::create_dir_all(&paks_path).unwrap();
+        fs::create_dir_all(&game_path).unwrap();
+
+        write_mod_pak(&paks_path.join(MOD_FILE_NAME), false);
+        fs::write(paks_path.join(INTEGRATOR_PAK_FILE_NAME), [1u8; 0x100]).unwrap();
+        fs::write(game_path.join(GAME_FILE_NAME), [2u8; 0x100]).unwrap();
+
+        let state = capture(&paks_path, &game_path, true);
+        assert_eq!(state.mods[0].mod_version.as_deref(), Some("1.0.0"));
+        assert_eq!(state.game_paks[0].hash, None);
+        assert_eq!(state.generated_paks[0].file_name, INTEGRATOR_PAK_FILE_NAME);
+
+        let state_path = directory.path().join(super::INSTALL_STATE_FILE_NAME);
+        assert_eq!(InstallState::load(&state_path).unwrap(), None);
+        state.save(&state_path).unwrap();
+        let state = InstallState::load(&state_path).unwrap().unwrap();
+        assert!(!state.needs_reintegration(&capture(&paks_path, &game_path, true)));
+
+        // modified files are found by their hash even if their size didn't change
+        write_mod_pak(&paks_path.join(MOD_FILE_NAME), true);
+        fs::write(paks_path.join(INTEGRATOR_PAK_FILE_NAME), [3u8; 0x100]).unwrap();
+        assert_eq!(
+            state.detect_drift(&capture(&paks_path, &game_path, true)),
+            [
+                Drift::ModChanged(String::from("TestMod")),
+                Drift::GeneratedPakChanged(String::from(INTEGRATOR_PAK_FILE_NAME)),
+            ]
+        );
+
+        // missing files
+        fs::remove_file(paks_path.join(INTEGRATOR_PAK_FILE_NAME)).unwrap();
+        fs::remove_file(game_path.join(GAME_FILE_NAME)).unwrap();
+        assert_eq!(
+            state.detect_drift(&capture(&paks_path, &game_path, false)),
+            [
+                Drift::GameFileChanged(String::from(GAME_FILE_NAME)),
+                Drift::ModRemoved(String::from("TestMod")),
+                Drift::GeneratedPakChanged(String::from(INTEGRATOR_PAK_FILE_NAME)),
+            ]
+        );
+
+        // new files
+        let empty = InstallState {
+            integrator_version: String::from("0.0.1"),
+            ..Default::default()
+        };
+        fs::write(game_path.join(GAME_FILE_NAME), [2u8; 0x100]).unwrap();
+        assert_eq!(
+            empty.detect_drift(&capture(&paks_path, &game_path, true)),
+            [
+                Drift::IntegratorUpdated(String::from("0.1.0")),
+                Drift::GameFileChanged(String::from(GAME_FILE_NAME)),
+                Drift::ModAdded(String::from("TestMod")),
+            ]
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use std::collections::HashMap;
-use std::fs::{self, File, OpenOptions};
-use std::io::{BufReader, BufWriter, Cursor, Write};
+use std::fs::{self, File};
+use std::io::{BufReader, Cursor, Read, Seek, Write};
 use std::path::{Path, PathBuf};
 
 use error::IntegrationError;
@@ -22,25 +22,91 @@ use unreal_asset::{
     Asset,
 };
 use unreal_mod_metadata::{Metadata, SyncMode};
-use unreal_pak::{pakversion::PakVersion, PakMemory, PakReader};
+use unreal_pak::{pakversion::PakVersion, AtomicFile, PakMemory, PakReader};
 
 mod assets;
+pub mod descriptors;
 pub mod error;
+pub mod game_paths;
 mod handlers;
+pub mod handshake;
 pub mod helpers;
+pub mod install_state;
 pub mod macros;
+pub mod report;
+pub mod templates;
+pub mod text_patches;
+#[cfg(feature = "watcher")]
+pub mod watcher;
 
-use assets::{COPY_OVER, INTEGRATOR_STATICS_ASSET, LIST_OF_MODS_ASSET, METADATA_JSON};
-#[cfg(not(feature = "no_bulk_data"))]
-use assets::{INTEGRATOR_STATICS_BULK, LIST_OF_MODS_BULK};
+use assets::METADATA_JSON;
 
 pub use crate::error::Error;
 use crate::handlers::handle_persistent_actors;
+use crate::handshake::{write_handshake, Handshake};
 use crate::helpers::write_asset;
+use crate::report::IntegrationReport;
+use crate::templates::{
+    get_template, TemplateAsset, TemplateGenerator, COPIED_TEMPLATES, INTEGRATOR_STATICS,
+    LIST_OF_MODS,
+};
+use crate::text_patches::{handle_text_patches, AssetTextFormat, TEXT_PATCHES_KEY};
 
 pub trait IntegratorInfo {}
 
 pub const INTEGRATOR_PAK_FILE_NAME: &str = "900-ModIntegrator_P.pak";
+/// Priority of the paks generated by the integrator, unless set by [`DynamicMod::split_output_priority`]
+pub const INTEGRATOR_PAK_PRIORITY: u32 = 900;
+/// Part of the file name of paks containing the integration output of a single mod after the priority
+const INTEGRATOR_SPLIT_PAK_INFIX: &str = "-ModIntegrator-";
+
+/// Get the file name of the pak containing the integration output of a single mod
+///
+/// The file name is `<priority>-ModIntegrator-<mod_id>_P.pak`, with the priority written as 3 digits.
+/// Returns an error if the priority doesn't fit in 3 digits, or if the mod id is empty or contains characters
+/// other than ASCII letters, digits, `.`, `_` and `-`, so the file name can't point outside of the paks directory.
+pub fn get_split_pak_file_name(mod_id: &str, priority: u32) -> Result<String, Error> {
+    if priority > 999 {
+        return Err(IntegrationError::invalid_split_pak_name(format!(
+            "priority {priority} has more than 3 digits"
+        ))
+        .into());
+    }
+    if !is_valid_split_mod_id(mod_id) {
+        return Err(IntegrationError::invalid_split_pak_name(format!(
+            "mod id {mod_id:?} can't be used in a file name"
+        ))
+        .into());
+    }
+
+    Ok(format!(
+        "{priority:03}{INTEGRATOR_SPLIT_PAK_INFIX}{mod_id}_P.pak"
+    ))
+}
+
+/// Check if a mod id can be used in the file name of a split pak
+fn is_valid_split_mod_id(mod_id: &str) -> bool {
+    !mod_id.is_empty()
+        && mod_id
+            .chars()
+            .all(|e| e.is_ascii_alphanumeric() || matches!(e, '.' | '_' | '-'))
+}
+
+/// Check if a pak was generated by the integrator
+pub fn is_integrator_pak(file_name: &str) -> bool {
+    if file_name == INTEGRATOR_PAK_FILE_NAME {
+        return true;
+    }
+
+    let (Some(priority), Some(name)) = (file_name.get(..3), file_name.get(3..)) else {
+        return false;
+    };
+    priority.bytes().all(|e| e.is_ascii_digit())
+        && name
+            .strip_prefix(INTEGRATOR_SPLIT_PAK_INFIX)
+            .and_then(|e| e.strip_suffix("_P.pak"))
+            .is_some_and(is_valid_split_mod_id)
+}
 
 pub enum IntegratorMod<E: std::error::Error> {
     File(FileMod),
@@ -152,6 +218,27 @@ pub trait DynamicMod<E: std::error::Error>: IntegratorModInfo {
         game_paks: &mut Vec<PakReader<BufReader<File>>>,
         mod_paks: &mut Vec<PakReader<BufReader<File>>>,
     ) -> Result<(), E>;
+
+    /// If true, the output of this mod is written to its own pak
+    /// named by [`get_split_pak_file_name`] instead of the core integrator pak
+    ///
+    /// The `integrated_pak` passed to [`DynamicMod::integrate`] then only contains this mod's output.
+    fn split_output(&self) -> bool {
+        false
+    }
+
+    /// Priority of the pak the output of this mod is written to if [`DynamicMod::split_output`] is true,
+    /// has to fit in 3 digits
+    fn split_output_priority(&self) -> u32 {
+        INTEGRATOR_PAK_PRIORITY
+    }
+}
+
+fn write_pak(paks_path: &Path, file_name: &str, pak: &PakMemory) -> Result<(), Error> {
+    let mut file = AtomicFile::create(paks_path.join(file_name))?;
+    pak.write(&mut file)?;
+    file.commit()?;
+    Ok(())
 }
 
 pub type HandlerFn<D, E> = dyn FnMut(
@@ -168,12 +255,24 @@ pub trait IntegratorConfig<'data, D, E: std::error::Error + 'static> {
 
     fn get_baked_mods(&self) -> Vec<IntegratorMod<E>>;
 
+    /// Generator used to create the integrator template assets at runtime,
+    /// if `None` the templates cooked at build time are used
+    fn get_template_generator(&self) -> Option<&dyn TemplateGenerator> {
+        None
+    }
+
+    /// Text form assets are converted to for applying text patches,
+    /// if `None` only entries which are not assets can be patched
+    fn get_asset_text_format(&self) -> Option<&dyn AssetTextFormat> {
+        None
+    }
+
     const GAME_NAME: &'static str;
     const INTEGRATOR_VERSION: &'static str;
     const ENGINE_VERSION: EngineVersion;
 }
 
-fn bake_mod_data(asset: &mut Asset<Cursor<&[u8]>>, mods: &Vec<Metadata>) -> Result<(), Error> {
+fn bake_mod_data<C: Read + Seek>(asset: &mut Asset<C>, mods: &Vec<Metadata>) -> Result<(), Error> {
     let data_table_export = asset
         .asset_data
         .exports
@@ -313,8 +412,8 @@ fn bake_mod_data(asset: &mut Asset<Cursor<&[u8]>>, mods: &Vec<Metadata>) -> Resu
     Ok(())
 }
 
-fn bake_integrator_data(
-    asset: &mut Asset<Cursor<&[u8]>>,
+fn bake_integrator_data<C: Read + Seek>(
+    asset: &mut Asset<C>,
     integrator_version: String,
     refuse_mismatched_connections: bool,
 ) -> Result<(), Error> {
@@ -372,12 +471,74 @@ pub fn integrate_mods<
     game_path: &Path,
     refuse_mismatched_connections: bool,
 ) -> Result<(), Error> {
+    integrate(
+        integrator_config,
+        mods,
+        paks_path,
+        game_path,
+        refuse_mismatched_connections,
+        false,
+    )?;
+    Ok(())
+}
+
+/// Run all integration steps in memory without writing or removing any paks
+///
+/// Baked mods are written to a temporary directory instead of `paks_path`, which is removed afterwards.
+/// The returned report describes what [`integrate_mods`] would do with the same arguments.
+pub fn dry_run_integration<
+    'data,
+    T: 'data,
+    E: 'static + std::error::Error + Send,
+    C: IntegratorConfig<'data, T, E>,
+>(
+    integrator_config: &C,
+    mods: &[IntegratorMod<E>],
+    paks_path: &Path,
+    game_path: &Path,
+    refuse_mismatched_connections: bool,
+) -> Result<IntegrationReport, Error> {
+    integrate(
+        integrator_config,
+        mods,
+        paks_path,
+        game_path,
+        refuse_mismatched_connections,
+        true,
+    )
+}
+
+fn integrate<
+    'data,
+    T: 'data,
+    E: 'static + std::error::Error + Send,
+    C: IntegratorConfig<'data, T, E>,
+>(
+    integrator_config: &C,
+    mods: &[IntegratorMod<E>],
+    paks_path: &Path,
+    game_path: &Path,
+    refuse_mismatched_connections: bool,
+    dry_run: bool,
+) -> Result<IntegrationReport, Error> {
     debug!(
-        "Integrating {} mods, refuse_mismatched_connections: {}",
+        "Integrating {} mods, refuse_mismatched_connections: {}, dry_run: {}",
         mods.len(),
-        refuse_mismatched_connections
+        refuse_mismatched_connections,
+        dry_run
     );
 
+    let mut report = IntegrationReport::default();
+    // removed once the integration is done, declared before the mod paks so it outlives their open files
+    let dry_run_dir = match dry_run {
+        true => Some(tempfile::tempdir()?),
+        false => None,
+    };
+    let baked_mods_path = match dry_run_dir {
+        Some(ref dir) => dir.path().to_path_buf(),
+        None => paks_path.to_path_buf(),
+    };
+
     let baked_mods = integrator_config.get_baked_mods();
     let core_mods = baked_mods.iter().filter(|e| e.is_core());
 
@@ -402,7 +563,7 @@ pub fn integrate_mods<
         .chain(enabled_baked_mods)
         .filter_map(|e| match e {
             IntegratorMod::File(file_mod) => File::open(&file_mod.path).ok(),
-            IntegratorMod::Baked(baked_mod) => baked_mod.write(paks_path).ok(),
+            IntegratorMod::Baked(baked_mod) => baked_mod.write(&baked_mods_path).ok(),
             _ => None,
         })
         .collect::<Vec<_>>();
@@ -415,7 +576,7 @@ pub fn integrate_mods<
         let mut pak = PakReader::new(BufReader::new(mod_file));
         pak.load_index()?;
 
-        let record = pak.read_entry(&String::from("metadata.json"))?;
+        let record = pak.read_entry("metadata.json")?;
         let metadata = unreal_mod_metadata::from_slice(&record)?;
         read_mods.push(metadata.clone());
 
@@ -434,17 +595,18 @@ pub fn integrate_mods<
         mod_paks.push(pak);
     }
 
+    report.add_mods(&read_mods);
+    report.add_conflicts(&read_mods, &mod_paks);
+
+    let mut split_paks = Vec::new();
     if !mods.is_empty() {
         let mut generated_pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+        let template_generator = integrator_config.get_template_generator();
 
-        #[cfg(not(feature = "no_bulk_data"))]
-        let list_of_mods_bulk = Some(LIST_OF_MODS_BULK);
-        #[cfg(feature = "no_bulk_data")]
-        let list_of_mods_bulk = None;
-
+        let template = get_template(template_generator, LIST_OF_MODS, C::ENGINE_VERSION)?;
         let mut list_of_mods = Asset::new(
-            Cursor::new(LIST_OF_MODS_ASSET),
-            list_of_mods_bulk.map(Cursor::new),
+            Cursor::new(template.asset),
+            template.bulk.map(Cursor::new),
             C::ENGINE_VERSION,
             None,
         )?;
@@ -455,14 +617,10 @@ pub fn integrate_mods<
             &(C::GAME_NAME.to_owned() + "/Content/Integrator/ListOfMods.uasset"),
         )?;
 
-        #[cfg(not(feature = "no_bulk_data"))]
-        let integrator_statics_bulk = Some(INTEGRATOR_STATICS_BULK);
-        #[cfg(feature = "no_bulk_data")]
-        let integrator_statics_bulk = None;
-
+        let template = get_template(template_generator, INTEGRATOR_STATICS, C::ENGINE_VERSION)?;
         let mut integrator_statics = Asset::new(
-            Cursor::new(INTEGRATOR_STATICS_ASSET),
-            integrator_statics_bulk.map(Cursor::new),
+            Cursor::new(template.asset),
+            template.bulk.map(Cursor::new),
             C::ENGINE_VERSION,
             None,
         )?;
@@ -478,13 +636,27 @@ pub fn integrate_mods<
             &(C::GAME_NAME.to_owned() + "/Content/Integrator/IntegratorStatics_BP.uasset"),
         )?;
 
+        write_handshake(
+            &mut generated_pak,
+            C::GAME_NAME,
+            &Handshake::new(
+                C::INTEGRATOR_VERSION.to_owned(),
+                refuse_mismatched_connections,
+                &read_mods,
+            ),
+        )?;
+
         generated_pak.set_entry(String::from("metadata.json"), METADATA_JSON.to_vec());
 
-        for entry in &COPY_OVER {
-            generated_pak.set_entry(
-                C::GAME_NAME.to_owned() + "/Content/Integrator/" + entry.1,
-                entry.0.to_vec(),
-            );
+        for name in COPIED_TEMPLATES {
+            let TemplateAsset { asset, bulk } =
+                get_template(template_generator, name, C::ENGINE_VERSION)?;
+            let path = format!("{}/Content/Integrator/{}", C::GAME_NAME, name);
+
+            generated_pak.set_entry(path.clone() + ".uasset", asset);
+            if let Some(bulk) = bulk {
+                generated_pak.set_entry(path + ".uexp", bulk);
+            }
         }
 
         let mut game_paks = Vec::new();
@@ -516,12 +688,46 @@ pub fn integrate_mods<
             &mut mod_paks,
             persistent_actors,
         )?;
+        if !persistent_actors.is_empty() {
+            report.add_hook(String::from("persistent_actors"), persistent_actors.len());
+        }
+
+        let text_patches = optional_mods_data
+            .get(TEXT_PATCHES_KEY)
+            .unwrap_or(&empty_vec);
+        let patch_count = handle_text_patches(
+            C::GAME_NAME,
+            C::ENGINE_VERSION,
+            integrator_config.get_asset_text_format(),
+            &mut generated_pak,
+            &mut game_paks,
+            &mut mod_paks,
+            text_patches,
+        )?;
+        if patch_count > 0 {
+            report.add_hook(String::from(TEXT_PATCHES_KEY), patch_count);
+        }
 
         for dynamic_mod in mods.iter() {
             if let IntegratorMod::Dynamic(dynamic_mod) = dynamic_mod {
-                dynamic_mod
-                    .integrate(&mut generated_pak, &mut game_paks, &mut mod_paks)
-                    .map_err(|e| Error::other(Box::new(e)))?;
+                report.add_hook(dynamic_mod.get_mod_id(), 1);
+                if dynamic_mod.split_output() {
+                    let mut split_pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+                    dynamic_mod
+                        .integrate(&mut split_pak, &mut game_paks, &mut mod_paks)
+                        .map_err(|e| Error::other(Box::new(e)))?;
+                    split_paks.push((
+                        get_split_pak_file_name(
+                            &dynamic_mod.get_mod_id(),
+                            dynamic_mod.split_output_priority(),
+                        )?,
+                        split_pak,
+                    ));
+                } else {
+                    dynamic_mod
+                        .integrate(&mut generated_pak, &mut game_paks, &mut mod_paks)
+                        .map_err(|e| Error::other(Box::new(e)))?;
+                }
             }
         }
 
@@ -536,18 +742,93 @@ pub fn integrate_mods<
                 all_mods,
             )
             .map_err(|e| Error::other(Box::new(e)))?;
+            if !all_mods.is_empty() {
+                report.add_hook(name, all_mods.len());
+            }
         }
 
-        let path = Path::new(paks_path).join(INTEGRATOR_PAK_FILE_NAME);
-        let file = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(path)?;
+        report.add_files(
+            INTEGRATOR_PAK_FILE_NAME,
+            &generated_pak,
+            &game_paks,
+            &mod_paks,
+        );
+        for (file_name, split_pak) in &split_paks {
+            report.add_files(file_name, split_pak, &game_paks, &mod_paks);
+        }
 
-        let mut writer = BufWriter::new(file);
-        generated_pak.write(&mut writer)?;
+        if !dry_run {
+            write_pak(paks_path, INTEGRATOR_PAK_FILE_NAME, &generated_pak)?;
+            for (file_name, split_pak) in &split_paks {
+                write_pak(paks_path, file_name, split_pak)?;
+            }
+        }
     }
 
-    Ok(())
+    // remove split paks of mods which are no longer installed or split,
+    // this also has to happen when the last mod was uninstalled
+    for entry in fs::read_dir(paks_path)?.filter_map(|e| e.ok()) {
+        let file_name = entry.file_name().to_string_lossy().into_owned();
+        if file_name != INTEGRATOR_PAK_FILE_NAME
+            && is_integrator_pak(&file_name)
+            && !split_paks.iter().any(|(name, _)| *name == file_name)
+        {
+            report.removed_paks.push(file_name);
+            if !dry_run {
+                fs::remove_file(entry.path())?;
+            }
+        }
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{get_split_pak_file_name, is_integrator_pak, INTEGRATOR_PAK_FILE_NAME};
+
+    #[test]
+    fn split_pak_file_names() {
+        assert_eq!(
+            get_split_pak_file_name("TestMod", 900).unwrap(),
+            "900-ModIntegrator-TestMod_P.pak"
+        );
+        assert_eq!(
+            get_split_pak_file_name("Author.Test_Mod-2", 5).unwrap(),
+            "005-ModIntegrator-Author.Test_Mod-2_P.pak"
+        );
+
+        assert!(get_split_pak_file_name("TestMod", 1000).is_err());
+        for mod_id in [
+            "",
+            "../TestMod",
+            "Test/Mod",
+            "Test\\Mod",
+            "C:TestMod",
+            "Test Mod",
+        ] {
+            assert!(get_split_pak_file_name(mod_id, 900).is_err(), "{mod_id:?}");
+        }
+    }
+
+    #[test]
+    fn integrator_pak_round_trip() {
+        assert!(is_integrator_pak(INTEGRATOR_PAK_FILE_NAME));
+        for (mod_id, priority) in [("TestMod", 900), ("Author.TestMod", 0), ("Test-Mod", 999)] {
+            assert!(is_integrator_pak(
+                &get_split_pak_file_name(mod_id, priority).unwrap()
+            ));
+        }
+
+        for file_name in [
+            "000-TestMod-1.0.0_P.pak",
+            "900-ModIntegrator-_P.pak",
+            "900-ModIntegrator-TestMod.pak",
+            "9x0-ModIntegrator-TestMod_P.pak",
+            "900-ModIntegrator-../TestMod_P.pak",
+            "ModIntegrator-TestMod_P.pak",
+        ] {
+            assert!(!is_integrator_pak(file_name), "{file_name}");
+        }
+    }
 }
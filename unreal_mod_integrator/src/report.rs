@@ -0,0 +1,245 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+
+use serde::{Deserialize, Serialize};
+use unreal_mod_metadata::Metadata;
+use unreal_pak::{PakMemory, PakReader};
+
+/// Summary of an integration, produced by [`crate::dry_run_integration`]
+///
+/// Serializes to JSON so mod sets can be validated in CI.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntegrationReport {
+    /// Integrated mods
+    pub mods: Vec<ReportedMod>,
+    /// Files the integration writes
+    pub files: Vec<ReportedFile>,
+    /// Hooks applied to the output
+    pub hooks: Vec<ReportedHook>,
+    /// Files provided by more than one mod
+    pub conflicts: Vec<ReportedConflict>,
+    /// Integrator paks from a previous integration which get removed
+    pub removed_paks: Vec<String>,
+}
+
+/// Mod read from the mods directory
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedMod {
+    /// Mod id from the mod's metadata
+    pub mod_id: String,
+    /// Mod version from the mod's metadata
+    pub mod_version: String,
+}
+
+/// How a file written by the integration relates to the game and mod paks
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FileChange {
+    /// File doesn't exist in any game or mod pak
+    Added,
+    /// File replaces a file from a game or mod pak
+    Overridden,
+}
+
+/// File written to an integrator pak
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedFile {
+    /// File name of the pak the file is written to
+    pub pak_file_name: String,
+    /// Path of the file in the pak
+    pub path: String,
+    /// Whether the file is new or replaces a file from a game or mod pak
+    pub change: FileChange,
+}
+
+/// Handler or dynamic mod which was run during the integration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedHook {
+    /// Handler name, or mod id for dynamic mods
+    pub name: String,
+    /// Amount of mods which provided data for this hook
+    pub mod_count: usize,
+}
+
+/// File provided by more than one mod pak
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReportedConflict {
+    /// Path of the file in the mod paks
+    pub path: String,
+    /// Mods providing this file, in load order
+    pub mod_ids: Vec<String>,
+}
+
+impl IntegrationReport {
+    pub(crate) fn add_mods(&mut self, mods: &[Metadata]) {
+        self.mods.extend(mods.iter().map(|e| ReportedMod {
+            mod_id: e.mod_id.clone(),
+            mod_version: e.mod_version.clone(),
+        }));
+    }
+
+    pub(crate) fn add_conflicts(
+        &mut self,
+        mods: &[Metadata],
+        mod_paks: &[PakReader<BufReader<File>>],
+    ) {
+        let mut providers: BTreeMap<&String, Vec<String>> = BTreeMap::new();
+        for (metadata, pak) in mods.iter().zip(mod_paks) {
+            for name in pak.get_entry_names() {
+                if name != "metadata.json" {
+                    providers
+                        .entry(name)
+                        .or_default()
+                        .push(metadata.mod_id.clone());
+                }
+            }
+        }
+
+        self.conflicts
+            .extend(
+                providers
+                    .into_iter()
+                    .filter(|(_, e)| e.len() > 1)
+                    .map(|(path, mod_ids)| ReportedConflict {
+                        path: path.clone(),
+                        mod_ids,
+                    }),
+            );
+    }
+
+    pub(crate) fn add_hook(&mut self, name: String, mod_count: usize) {
+        self.hooks.push(ReportedHook { name, mod_count });
+    }
+
+    pub(crate) fn add_files(
+        &mut self,
+        pak_file_name: &str,
+        pak: &PakMemory,
+        game_paks: &[PakReader<BufReader<File>>],
+        mod_paks: &[PakReader<BufReader<File>>],
+    ) {
+        let mut names = pak.get_entry_names();
+        names.sort();
+
+        self.files.extend(names.into_iter().map(|name| {
+            let change = match game_paks
+                .iter()
+                .chain(mod_paks)
+                .any(|e| e.contains_entry(name))
+            {
+                true => FileChange::Overridden,
+                false => FileChange::Added,
+            };
+
+            ReportedFile {
+                pak_file_name: pak_file_name.to_string(),
+                path: name.clone(),
+                change,
+            }
+        }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs::File;
+    use std::io::BufReader;
+    use std::path::Path;
+
+    use unreal_pak::{pakversion::PakVersion, PakMemory, PakReader};
+
+    use super::{
+        FileChange, IntegrationReport, ReportedConflict, ReportedFile, ReportedHook, ReportedMod,
+    };
+
+    fn write_pak(path: &Path, entries: &[&str]) -> PakReader<BufReader<File>> {
+        let mut pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+        for entry in entries {
+            pak.set_entry(entry.to_string(), vec![0; 4]);
+        }
+        pak.write(&mut File::create(path).unwrap()).unwrap();
+
+        let mut reader = PakReader::new(BufReader::new(File::open(path).unwrap()));
+        reader.load_index().unwrap();
+        reader
+    }
+
+    #[test]
+    fn reported_plan() {
+        let directory = tempfile::tempdir().unwrap();
+        let game_paks = vec![write_pak(
+            &directory.path().join("Game.pak"),
+            &["Game/A.uasset"],
+        )];
+        let mod_paks = vec![
+            write_pak(
+                &directory.path().join("000-First.pak"),
+                &["metadata.json", "Game/B.uasset", "Game/C.uasset"],
+            ),
+            write_pak(
+                &directory.path().join("000-Second.pak"),
+                &["metadata.json", "Game/C.uasset"],
+            ),
+        ];
+        let mods = ["First", "Second"].map(|mod_id| {
+            let src =
+                format!(r#"{{"name": "{mod_id}", "mod_id": "{mod_id}", "version": "1.0.0"}}"#);
+            unreal_mod_metadata::from_slice(src.as_bytes()).unwrap()
+        });
+
+        let mut generated_pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+        generated_pak.set_entry(String::from("Game/D.uasset"), Vec::new());
+        generated_pak.set_entry(String::from("Game/A.uasset"), Vec::new());
+        generated_pak.set_entry(String::from("Game/B.uasset"), Vec::new());
+
+        let mut report = IntegrationReport::default();
+        report.add_mods(&mods);
+        report.add_conflicts(&mods, &mod_paks);
+        report.add_hook(String::from("persistent_actors"), 2);
+        report.add_files(
+            crate::INTEGRATOR_PAK_FILE_NAME,
+            &generated_pak,
+            &game_paks,
+            &mod_paks,
+        );
+
+        let file = |path: &str, change| ReportedFile {
+            pak_file_name: crate::INTEGRATOR_PAK_FILE_NAME.to_string(),
+            path: path.to_string(),
+            change,
+        };
+        let expected = IntegrationReport {
+            mods: vec![
+                ReportedMod {
+                    mod_id: String::from("First"),
+                    mod_version: String::from("1.0.0"),
+                },
+                ReportedMod {
+                    mod_id: String::from("Second"),
+                    mod_version: String::from("1.0.0"),
+                },
+            ],
+            files: vec![
+                file("Game/A.uasset", FileChange::Overridden),
+                file("Game/B.uasset", FileChange::Overridden),
+                file("Game/D.uasset", FileChange::Added),
+            ],
+            hooks: vec![ReportedHook {
+                name: String::from("persistent_actors"),
+                mod_count: 2,
+            }],
+            conflicts: vec![ReportedConflict {
+                path: String::from("Game/C.uasset"),
+                mod_ids: vec![String::from("First"), String::from("Second")],
+            }],
+            removed_paks: Vec::new(),
+        };
+        assert_eq!(report, expected);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert_eq!(
+            serde_json::from_str::<IntegrationReport>(&json).unwrap(),
+            expected
+        );
+    }
+}
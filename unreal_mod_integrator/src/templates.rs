@@ -0,0 +1,217 @@
+//! Integrator template assets
+//!
+//! The integrator pak is built from template assets which are cooked with Unreal Engine
+//! for the engine version selected through the `ue4_*` feature and embedded at build time,
+//! see [`COOKED_ENGINE_VERSION`]. The templates are always embedded, a [`TemplateGenerator`]
+//! can only replace them for the engine version being integrated.
+//!
+//! [`VersionedTemplateGenerator`] doesn't build the ListOfMods DataTable or the IntegratorStatics
+//! Blueprint from scratch, it re-serializes the cooked templates with the versions of another engine.
+//! This only works for engine versions whose export data has the same layout as the cooked one.
+
+use std::io::Cursor;
+
+use unreal_asset::{engine_version::EngineVersion, exports::Export, reader::ArchiveTrait, Asset};
+
+use crate::assets::{COPY_OVER, INTEGRATOR_STATICS_ASSET, LIST_OF_MODS_ASSET};
+#[cfg(not(feature = "no_bulk_data"))]
+use crate::assets::{INTEGRATOR_STATICS_BULK, LIST_OF_MODS_BULK};
+use crate::error::IntegrationError;
+use crate::Error;
+
+/// Engine version the templates are cooked with at build time
+#[cfg(feature = "ue4_23")]
+pub const COOKED_ENGINE_VERSION: EngineVersion = EngineVersion::VER_UE4_23;
+
+/// DataTable template which the list of installed mods is baked into
+pub const LIST_OF_MODS: &str = "ListOfMods";
+/// Blueprint template which the integrator settings are baked into
+pub const INTEGRATOR_STATICS: &str = "IntegratorStatics_BP";
+
+/// Templates which are copied into the integrator pak as is
+pub const COPIED_TEMPLATES: [&str; 5] = [
+    "Mod",
+    "ModIntegrator",
+    "ModIntegratorComponent",
+    "ModMismatchWidget",
+    "SyncMode",
+];
+
+/// Serialized template asset
+pub struct TemplateAsset {
+    /// .uasset file
+    pub asset: Vec<u8>,
+    /// .uexp file, if the asset uses separate bulk data
+    pub bulk: Option<Vec<u8>>,
+}
+
+/// Generates integrator template assets at runtime
+///
+/// This allows using the integrator with engine versions the templates weren't cooked for,
+/// as long as the generator can produce templates for them.
+/// Templates which are not generated fall back to the ones cooked at build time.
+pub trait TemplateGenerator {
+    /// Generate a template asset
+    ///
+    /// `name` is the asset name without extension, one of [`LIST_OF_MODS`], [`INTEGRATOR_STATICS`]
+    /// or an entry of [`COPIED_TEMPLATES`].
+    ///
+    /// Returning `Ok(None)` uses the cooked template.
+    fn generate(
+        &self,
+        name: &str,
+        engine_version: EngineVersion,
+    ) -> Result<Option<TemplateAsset>, Error>;
+}
+
+/// Generates templates for other engine versions by re-serializing the cooked templates
+///
+/// Each cooked template is parsed and written again with the object version and custom versions
+/// of the requested engine version, so the engine loads it without a version mismatch.
+/// Nothing is generated for [`COOKED_ENGINE_VERSION`].
+///
+/// The export data itself isn't converted, so this only works for engine versions which serialize
+/// the exports of the templates the same way as the cooked engine version.
+/// Templates with exports that can't be parsed fail to generate,
+/// since their data would be written in the layout of the cooked engine version.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct VersionedTemplateGenerator;
+
+impl TemplateGenerator for VersionedTemplateGenerator {
+    fn generate(
+        &self,
+        name: &str,
+        engine_version: EngineVersion,
+    ) -> Result<Option<TemplateAsset>, Error> {
+        if engine_version == COOKED_ENGINE_VERSION {
+            return Ok(None);
+        }
+
+        let template = get_cooked_template(name)
+            .ok_or_else(|| IntegrationError::asset_not_found(name.to_string()))?;
+        let mut asset = Asset::new(
+            Cursor::new(template.asset),
+            template.bulk.map(Cursor::new),
+            COOKED_ENGINE_VERSION,
+            None,
+        )?;
+
+        if asset
+            .asset_data
+            .exports
+            .iter()
+            .any(|e| matches!(e, Export::RawExport(_)))
+        {
+            return Err(IntegrationError::template_generation_failed(format!(
+                "{name} contains exports which can't be parsed"
+            ))
+            .into());
+        }
+
+        asset.asset_data.set_engine_version(engine_version);
+
+        let mut asset_cursor = Cursor::new(Vec::new());
+        let mut bulk_cursor = match asset.use_event_driven_loader() {
+            true => Some(Cursor::new(Vec::new())),
+            false => None,
+        };
+        asset.write_data(&mut asset_cursor, bulk_cursor.as_mut())?;
+
+        Ok(Some(TemplateAsset {
+            asset: asset_cursor.into_inner(),
+            bulk: bulk_cursor.map(Cursor::into_inner),
+        }))
+    }
+}
+
+fn get_cooked_template(name: &str) -> Option<TemplateAsset> {
+    match name {
+        #[cfg(not(feature = "no_bulk_data"))]
+        LIST_OF_MODS => Some(TemplateAsset {
+            asset: LIST_OF_MODS_ASSET.to_vec(),
+            bulk: Some(LIST_OF_MODS_BULK.to_vec()),
+        }),
+        #[cfg(feature = "no_bulk_data")]
+        LIST_OF_MODS => Some(TemplateAsset {
+            asset: LIST_OF_MODS_ASSET.to_vec(),
+            bulk: None,
+        }),
+        #[cfg(not(feature = "no_bulk_data"))]
+        INTEGRATOR_STATICS => Some(TemplateAsset {
+            asset: INTEGRATOR_STATICS_ASSET.to_vec(),
+            bulk: Some(INTEGRATOR_STATICS_BULK.to_vec()),
+        }),
+        #[cfg(feature = "no_bulk_data")]
+        INTEGRATOR_STATICS => Some(TemplateAsset {
+            asset: INTEGRATOR_STATICS_ASSET.to_vec(),
+            bulk: None,
+        }),
+        _ => {
+            let find = |file_name: String| {
+                COPY_OVER
+                    .iter()
+                    .find(|(_, e)| *e == file_name)
+                    .map(|(data, _)| data.to_vec())
+            };
+
+            Some(TemplateAsset {
+                asset: find(format!("{name}.uasset"))?,
+                bulk: find(format!("{name}.uexp")),
+            })
+        }
+    }
+}
+
+pub(crate) fn get_template(
+    generator: Option<&dyn TemplateGenerator>,
+    name: &str,
+    engine_version: EngineVersion,
+) -> Result<TemplateAsset, Error> {
+    if let Some(generator) = generator {
+        if let Some(template) = generator.generate(name, engine_version)? {
+            return Ok(template);
+        }
+    }
+
+    get_cooked_template(name).ok_or_else(|| IntegrationError::corrupted_starter_pak().into())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use unreal_asset::{engine_version::EngineVersion, exports::Export, Asset};
+
+    use super::{
+        TemplateGenerator, VersionedTemplateGenerator, COOKED_ENGINE_VERSION, LIST_OF_MODS,
+    };
+
+    #[test]
+    fn versioned_templates() {
+        let generator = VersionedTemplateGenerator;
+        assert!(generator
+            .generate(LIST_OF_MODS, COOKED_ENGINE_VERSION)
+            .unwrap()
+            .is_none());
+        assert!(generator
+            .generate("Missing", EngineVersion::VER_UE4_25)
+            .is_err());
+
+        let template = generator
+            .generate(LIST_OF_MODS, EngineVersion::VER_UE4_25)
+            .unwrap()
+            .unwrap();
+        let asset = Asset::new(
+            Cursor::new(template.asset),
+            template.bulk.map(Cursor::new),
+            EngineVersion::VER_UE4_25,
+            None,
+        )
+        .unwrap();
+        assert!(asset
+            .asset_data
+            .exports
+            .iter()
+            .any(|e| matches!(e, Export::DataTableExport(_))));
+    }
+}
@@ -0,0 +1,405 @@
+//! Declarative text patches
+//!
+//! Mods can describe simple edits of game files in their metadata instead of shipping a dynamic mod.
+//! Each patch replaces text in an entry, `find` and `replace` are templates which can use
+//! `{{variable}}` placeholders.
+//!
+//! ```json
+//! "integrator": {
+//!     "text_patches": [
+//!         {
+//!             "path": "GameName/Content/Items/Drill.uasset",
+//!             "variables": { "cost": "10" },
+//!             "edits": [
+//!                 { "find": "\"Cost\": 5", "replace": "\"Cost\": {{cost}}" }
+//!             ]
+//!         }
+//!     ]
+//! }
+//! ```
+//!
+//! Assets are patched in a text form created by the [`AssetTextFormat`] of the integrator config,
+//! e.g. JSON. All other entries, like config files, are patched as UTF-8 text.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use unreal_asset::engine_version::EngineVersion;
+use unreal_pak::{error::PakErrorKind, PakMemory, PakReader};
+
+use crate::error::IntegrationError;
+use crate::templates::TemplateAsset;
+use crate::Error;
+
+/// Metadata key text patches are read from
+pub const TEXT_PATCHES_KEY: &str = "text_patches";
+
+/// Converts assets to and from a text form which text patches are applied to
+pub trait AssetTextFormat {
+    /// Convert an asset to text
+    fn to_text(
+        &self,
+        asset: &TemplateAsset,
+        engine_version: EngineVersion,
+    ) -> Result<String, Error>;
+
+    /// Convert patched text back to an asset
+    fn parse_text(&self, text: &str, engine_version: EngineVersion)
+        -> Result<TemplateAsset, Error>;
+}
+
+/// Text patch of an entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextPatch {
+    /// Entry path, e.g. `GameName/Content/Items/Drill.uasset`
+    pub path: String,
+    /// Variables usable in the templates of the edits
+    #[serde(default)]
+    pub variables: HashMap<String, String>,
+    /// Edits applied in order
+    pub edits: Vec<TextEdit>,
+}
+
+/// Replacement of text
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TextEdit {
+    /// Template of the text to find, every occurrence is replaced
+    pub find: String,
+    /// Template of the replacement
+    pub replace: String,
+}
+
+/// Render a template, replacing `{{variable}}` placeholders
+///
+/// Whitespace around variable names is ignored, unknown variables and unclosed placeholders are errors.
+pub fn render_template(
+    template: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String, Error> {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        rendered.push_str(&rest[..start]);
+
+        let end = rest[start..].find("}}").ok_or_else(|| {
+            IntegrationError::text_patch_failed(format!("Unclosed placeholder in {template:?}"))
+        })?;
+        let name = rest[start + 2..start + end].trim();
+        let value = variables.get(name).ok_or_else(|| {
+            IntegrationError::text_patch_failed(format!("Unknown variable {name:?}"))
+        })?;
+        rendered.push_str(value);
+
+        rest = &rest[start + end + 2..];
+    }
+    rendered.push_str(rest);
+
+    Ok(rendered)
+}
+
+impl TextPatch {
+    /// Apply this patch to text
+    ///
+    /// `game_name` is available to templates as the `game_name` variable.
+    /// Fails if the text to find of an edit doesn't occur in the text.
+    pub fn apply(&self, text: &str, game_name: &str) -> Result<String, Error> {
+        let mut variables = self.variables.clone();
+        variables
+            .entry(String::from("game_name"))
+            .or_insert_with(|| game_name.to_string());
+
+        let mut text = text.to_string();
+        for edit in &self.edits {
+            let find = render_template(&edit.find, &variables)?;
+            if find.is_empty() || !text.contains(&find) {
+                return Err(IntegrationError::text_patch_failed(format!(
+                    "{find:?} not found in {}",
+                    self.path
+                ))
+                .into());
+            }
+            text = text.replace(&find, &render_template(&edit.replace, &variables)?);
+        }
+
+        Ok(text)
+    }
+}
+
+/// Read an entry from the integrated pak, the mod paks or the game paks, in that order
+fn read_entry(
+    integrated_pak: &PakMemory,
+    game_paks: &mut [PakReader<BufReader<File>>],
+    mod_paks: &mut [PakReader<BufReader<File>>],
+    name: &String,
+) -> Result<Option<Vec<u8>>, Error> {
+    if let Some(data) = integrated_pak.get_entry(name) {
+        return Ok(Some(data.clone()));
+    }
+
+    for pak in mod_paks.iter_mut().chain(game_paks.iter_mut()) {
+        match pak.read_entry(name) {
+            Ok(data) => return Ok(Some(data)),
+            Err(err) if matches!(err.kind, PakErrorKind::EntryNotFound(_)) => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Apply the text patches of all mods, patched entries are written to the integrated pak
+///
+/// `text_patches` are the values of the [`TEXT_PATCHES_KEY`] metadata key of every mod.
+/// Returns the amount of applied patches.
+pub fn handle_text_patches(
+    game_name: &str,
+    engine_version: EngineVersion,
+    text_format: Option<&dyn AssetTextFormat>,
+    integrated_pak: &mut PakMemory,
+    game_paks: &mut [PakReader<BufReader<File>>],
+    mod_paks: &mut [PakReader<BufReader<File>>],
+    text_patches: &[Value],
+) -> Result<usize, Error> {
+    let mut count = 0;
+
+    for patches in text_patches {
+        let patches: Vec<TextPatch> = serde_json::from_value(patches.clone())?;
+
+        for patch in patches {
+            let path = Path::new(&patch.path);
+            let is_asset = path
+                .extension()
+                .map(|e| e == "uasset" || e == "umap")
+                .unwrap_or(false);
+
+            let data = read_entry(integrated_pak, game_paks, mod_paks, &patch.path)?
+                .ok_or_else(|| IntegrationError::asset_not_found(patch.path.clone()))?;
+
+            if !is_asset {
+                let text = String::from_utf8(data).map_err(|_| {
+                    IntegrationError::text_patch_failed(format!("{} is not UTF-8", patch.path))
+                })?;
+                let patched = patch.apply(&text, game_name)?;
+                integrated_pak.set_entry(patch.path.clone(), patched.into_bytes());
+                count += 1;
+                continue;
+            }
+
+            let text_format = text_format.ok_or_else(|| {
+                IntegrationError::text_patch_failed(format!(
+                    "No asset text format to patch {}",
+                    patch.path
+                ))
+            })?;
+
+            let bulk_path = path.with_extension("uexp").to_string_lossy().into_owned();
+            let asset = TemplateAsset {
+                asset: data,
+                bulk: read_entry(integrated_pak, game_paks, mod_paks, &bulk_path)?,
+            };
+
+            let text = text_format.to_text(&asset, engine_version)?;
+            let patched = patch.apply(&text, game_name)?;
+            let TemplateAsset { asset, bulk } = text_format.parse_text(&patched, engine_version)?;
+
+            integrated_pak.set_entry(patch.path.clone(), asset);
+            if let Some(bulk) = bulk {
+                integrated_pak.set_entry(bulk_path, bulk);
+            }
+            count += 1;
+        }
+    }
+
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::fs::File;
+    use std::io::BufReader;
+
+    use serde_json::{json, Value};
+    use unreal_asset::engine_version::EngineVersion;
+    use unreal_pak::{pakversion::PakVersion, PakMemory, PakReader, PakWriter};
+
+    use super::{handle_text_patches, render_template, AssetTextFormat, TextEdit, TextPatch};
+    use crate::templates::TemplateAsset;
+    use crate::Error;
+
+    /// Stores the asset and its bulk data as plain text, separated by a line
+    struct PlainTextFormat;
+
+    impl AssetTextFormat for PlainTextFormat {
+        fn to_text(
+            &self,
+            asset: &TemplateAsset,
+            _engine_version: EngineVersion,
+        ) -> Result<String, Error> {
+            let mut text = String::from_utf8(asset.asset.clone()).unwrap();
+            if let Some(bulk) = &asset.bulk {
+                text.push_str("\n---\n");
+                text.push_str(std::str::from_utf8(bulk).unwrap());
+            }
+            Ok(text)
+        }
+
+        fn parse_text(
+            &self,
+            text: &str,
+            _engine_version: EngineVersion,
+        ) -> Result<TemplateAsset, Error> {
+            let (asset, bulk) = match text.split_once("\n---\n") {
+                Some((asset, bulk)) => (asset, Some(bulk)),
+                None => (text, None),
+            };
+            Ok(TemplateAsset {
+                asset: asset.as_bytes().to_vec(),
+                bulk: bulk.map(|e| e.as_bytes().to_vec()),
+            })
+        }
+    }
+
+    fn edit(find: &str, replace: &str) -> TextEdit {
+        TextEdit {
+            find: find.to_string(),
+            replace: replace.to_string(),
+        }
+    }
+
+    #[test]
+    fn render_templates() {
+        let variables = HashMap::from([(String::from("cost"), String::from("10"))]);
+
+        assert_eq!(
+            render_template("Cost: {{cost}}, {{ cost }}", &variables).unwrap(),
+            "Cost: 10, 10"
+        );
+        assert_eq!(render_template("Cost", &variables).unwrap(), "Cost");
+        assert!(render_template("{{missing}}", &variables).is_err());
+        assert!(render_template("{{cost", &variables).is_err());
+    }
+
+    #[test]
+    fn apply_patch() {
+        let mut patch = TextPatch {
+            path: String::from("Game/Config/DefaultGame.ini"),
+            variables: HashMap::from([(String::from("speed"), String::from("2.0"))]),
+            edits: vec![
+                edit("Speed=1.0", "Speed={{speed}}"),
+                edit("Map=Default", "Map={{game_name}}Map"),
+            ],
+        };
+
+        // every occurrence is replaced
+        let text = "Speed=1.0\nMap=Default\nSpeed=1.0\n";
+        assert_eq!(
+            patch.apply(text, "Game").unwrap(),
+            "Speed=2.0\nMap=GameMap\nSpeed=2.0\n"
+        );
+
+        // edits see the text changed by the edits before them
+        patch.edits.push(edit("Speed=1.0", "Speed=3.0"));
+        assert!(patch.apply(text, "Game").is_err());
+
+        patch.edits = vec![edit("{{missing}}", "")];
+        assert!(patch.apply(text, "Game").is_err());
+        patch.edits = vec![edit("", "Speed=3.0")];
+        assert!(patch.apply(text, "Game").is_err());
+    }
+
+    #[test]
+    fn handle_patches() {
+        let directory = tempfile::tempdir().unwrap();
+        let game_pak_path = directory.path().join("Game-WindowsNoEditor.pak");
+        let mut pak_writer = PakWriter::new(
+            File::create(&game_pak_path).unwrap(),
+            PakVersion::FnameBasedCompressionMethod,
+        );
+        for (name, data) in [
+            ("Game/Config/DefaultGame.ini", "Speed=1.0\n"),
+            ("Game/Content/Items/Drill.uasset", "Name=Drill"),
+            ("Game/Content/Items/Drill.uexp", "Cost=5"),
+        ] {
+            pak_writer
+                .write_entry(&name.to_string(), data.as_bytes(), false)
+                .unwrap();
+        }
+        pak_writer.finish_write().unwrap();
+
+        let mut game_pak = PakReader::new(BufReader::new(File::open(&game_pak_path).unwrap()));
+        game_pak.load_index().unwrap();
+        let mut game_paks = vec![game_pak];
+
+        // entries already in the integrated pak are patched instead of the game's
+        let mut integrated_pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+        integrated_pak.set_entry(
+            String::from("Game/Config/DefaultGame.ini"),
+            b"Speed=1.5\nFps=30\n".to_vec(),
+        );
+
+        let drill_patch = json!([{
+            "path": "Game/Content/Items/Drill.uasset",
+            "variables": { "cost": "10" },
+            "edits": [{ "find": "Cost=5", "replace": "Cost={{cost}}" }]
+        }]);
+        let text_patches = [
+            json!([{
+                "path": "Game/Config/DefaultGame.ini",
+                "edits": [{ "find": "Fps=30", "replace": "Fps=60" }]
+            }]),
+            drill_patch.clone(),
+        ];
+
+        let mut patch = |text_format: Option<&dyn AssetTextFormat>,
+                         integrated_pak: &mut PakMemory,
+                         text_patches: &[Value]| {
+            handle_text_patches(
+                "Game",
+                EngineVersion::VER_UE4_23,
+                text_format,
+                integrated_pak,
+                &mut game_paks,
+                &mut [],
+                text_patches,
+            )
+        };
+
+        let count = patch(Some(&PlainTextFormat), &mut integrated_pak, &text_patches).unwrap();
+        assert_eq!(count, 2);
+
+        let entry = |name: &str| {
+            integrated_pak
+                .get_entry(&name.to_string())
+                .map(|e| String::from_utf8(e.clone()).unwrap())
+        };
+        assert_eq!(
+            entry("Game/Config/DefaultGame.ini").as_deref(),
+            Some("Speed=1.5\nFps=60\n")
+        );
+        assert_eq!(
+            entry("Game/Content/Items/Drill.uasset").as_deref(),
+            Some("Name=Drill")
+        );
+        assert_eq!(
+            entry("Game/Content/Items/Drill.uexp").as_deref(),
+            Some("Cost=10")
+        );
+
+        // assets can't be patched without a text format, missing entries can't be patched at all
+        let mut integrated_pak = PakMemory::new(PakVersion::FnameBasedCompressionMethod);
+        assert!(patch(None, &mut integrated_pak, &[drill_patch]).is_err());
+        let missing_patch = json!([{
+            "path": "Game/Config/Missing.ini",
+            "edits": [{ "find": "Fps=30", "replace": "Fps=60" }]
+        }]);
+        assert!(patch(None, &mut integrated_pak, &[missing_patch]).is_err());
+        assert!(integrated_pak.get_entry_names().is_empty());
+    }
+}
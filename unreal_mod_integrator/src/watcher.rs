@@ -0,0 +1,160 @@
+//! Mods directory watcher
+//!
+//! Watches the mods directory and calls a callback once mod paks were added, changed or removed,
+//! so mod managers can reintegrate without a restart.
+//! Events are debounced, copying a large pak or installing several mods at once only triggers
+//! a single callback once the directory stopped changing.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use log::{trace, warn};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::{is_integrator_pak, Error};
+
+/// Default time the mods directory needs to stay unchanged before the callback is called
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// Check if a path is a mod pak, paks written by the integrator itself are ignored
+fn is_mod_pak(path: &Path) -> bool {
+    let Some(file_name) = path.file_name().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    file_name.ends_with(".pak") && !is_integrator_pak(file_name)
+}
+
+/// Mods directory watcher
+///
+/// Watching stops when the watcher is dropped.
+pub struct ModsWatcher {
+    /// Filesystem watcher, taken on drop to stop the debounce thread
+    watcher: Option<RecommendedWatcher>,
+    /// Debounce thread
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ModsWatcher {
+    /// Start watching a mods directory
+    ///
+    /// `callback` is called from a background thread with the sorted paths of all mod paks
+    /// which changed since the last call, after no change happened for `debounce`.
+    pub fn new<F>(mods_path: &Path, debounce: Duration, mut callback: F) -> Result<Self, Error>
+    where
+        F: FnMut(Vec<PathBuf>) + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel::<Event>();
+
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            match event {
+                Ok(event) => {
+                    // the receiver is only gone while the watcher is being dropped
+                    let _ = sender.send(event);
+                }
+                Err(err) => warn!("Error while watching mods directory: {}", err),
+            }
+        })?;
+        watcher.watch(mods_path, RecursiveMode::NonRecursive)?;
+
+        let thread = thread::spawn(move || {
+            let mut changed = BTreeSet::new();
+
+            loop {
+                let event = match changed.is_empty() {
+                    true => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                    false => receiver.recv_timeout(debounce),
+                };
+
+                match event {
+                    Ok(event) => {
+                        if matches!(event.kind, EventKind::Access(_)) {
+                            continue;
+                        }
+                        changed.extend(event.paths.into_iter().filter(|e| is_mod_pak(e)));
+                    }
+                    Err(RecvTimeoutError::Timeout) => {
+                        let changed = std::mem::take(&mut changed);
+                        trace!("Mods changed: {:?}", changed);
+                        callback(changed.into_iter().collect());
+                    }
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Ok(ModsWatcher {
+            watcher: Some(watcher),
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for ModsWatcher {
+    fn drop(&mut self) {
+        // dropping the watcher drops the event sender, which stops the debounce thread
+        drop(self.watcher.take());
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    use super::{is_mod_pak, ModsWatcher};
+    use crate::{get_split_pak_file_name, INTEGRATOR_PAK_FILE_NAME};
+
+    const DEBOUNCE: Duration = Duration::from_millis(200);
+    const TIMEOUT: Duration = Duration::from_secs(10);
+
+    #[test]
+    fn mod_paks() {
+        assert!(is_mod_pak("mods/000-TestMod-0.1.0_P.pak".as_ref()));
+        assert!(!is_mod_pak("mods/readme.txt".as_ref()));
+        assert!(!is_mod_pak(INTEGRATOR_PAK_FILE_NAME.as_ref()));
+        assert!(!is_mod_pak(
+            get_split_pak_file_name("TestMod", 900).unwrap().as_ref()
+        ));
+    }
+
+    #[test]
+    fn debounced_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let mods_path = dir.path().canonicalize().unwrap();
+
+        let (sender, receiver) = mpsc::channel();
+        let watcher = ModsWatcher::new(&mods_path, DEBOUNCE, move |changed| {
+            let _ = sender.send(changed);
+        })
+        .unwrap();
+
+        // several mods installed at once only trigger a single callback
+        let first = mods_path.join("001-First-0.1.0_P.pak");
+        let second = mods_path.join("000-Second-0.1.0_P.pak");
+        for path in [&first, &second] {
+            fs::write(path, [0u8; 0x100]).unwrap();
+        }
+        fs::write(mods_path.join("readme.txt"), "").unwrap();
+        fs::write(mods_path.join(INTEGRATOR_PAK_FILE_NAME), "").unwrap();
+
+        let changed = receiver.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(changed, [second.clone(), first.clone()]);
+        assert!(receiver.recv_timeout(DEBOUNCE * 2).is_err());
+
+        fs::remove_file(&first).unwrap();
+        let changed = receiver.recv_timeout(TIMEOUT).unwrap();
+        assert_eq!(changed, [first]);
+
+        // no callbacks after the watcher was dropped
+        drop(watcher);
+        fs::write(&second, [1u8; 0x100]).unwrap();
+        assert!(receiver.recv_timeout(DEBOUNCE * 2).is_err());
+    }
+}
@@ -18,7 +18,7 @@ use semver::Version;
 use sha2::{Digest, Sha256};
 
 use unreal_mod_integrator::{
-    integrate_mods, FileMod, IntegratorConfig, IntegratorModInfo, INTEGRATOR_PAK_FILE_NAME,
+    integrate_mods, is_integrator_pak, FileMod, IntegratorConfig, IntegratorModInfo,
 };
 use unreal_mod_metadata::Metadata;
 use unreal_pak::PakReader;
@@ -85,7 +85,7 @@ fn download_mod(
     let mut pak = PakReader::new(&file);
     pak.load_index()?;
 
-    let metadata = pak.read_entry(&String::from("metadata.json"))?;
+    let metadata = pak.read_entry("metadata.json")?;
     let metadata = unreal_mod_metadata::from_slice(&metadata)
         .map_err(|_| ModLoaderWarning::invalid_metadata(mod_version.file_name.clone()))?;
 
@@ -154,7 +154,7 @@ where
         let mod_files = mods_dir
             .filter_map(|e| e.ok())
             .filter(|e| match e.file_name().into_string() {
-                Ok(s) => s.ends_with("_P.pak") && s != INTEGRATOR_PAK_FILE_NAME,
+                Ok(s) => s.ends_with("_P.pak") && !is_integrator_pak(&s),
                 Err(_) => false,
             })
             .map(|e| FileToProcess::new(e.path(), false))
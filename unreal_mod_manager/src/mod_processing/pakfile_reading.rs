@@ -34,15 +34,13 @@ pub(crate) fn read_pak_files(
             pak.load_index()
                 .map_err(|err| ModLoaderWarning::from(err).with_mod_id(file_name.clone()))?;
 
-            let record = pak
-                .read_entry(&String::from("metadata.json"))
-                .map_err(|err| {
-                    if matches!(err.kind, PakErrorKind::EntryNotFound(_)) {
-                        ModLoaderWarning::missing_metadata(file_name.clone())
-                    } else {
-                        ModLoaderWarning::from(err).with_mod_id(file_name.clone())
-                    }
-                })?;
+            let record = pak.read_entry("metadata.json").map_err(|err| {
+                if matches!(err.kind, PakErrorKind::EntryNotFound(_)) {
+                    ModLoaderWarning::missing_metadata(file_name.clone())
+                } else {
+                    ModLoaderWarning::from(err).with_mod_id(file_name.clone())
+                }
+            })?;
 
             let metadata: Metadata = unreal_mod_metadata::from_slice(&record).map_err(|err| {
                 warn!("json error: {}", err);
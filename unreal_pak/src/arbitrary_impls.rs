@@ -0,0 +1,94 @@
+//! `Arbitrary` implementations for fuzzing and property based testing, enabled with the `arbitrary` feature
+
+use arbitrary::{Arbitrary, Result, Unstructured};
+
+use crate::entry::FLAG_ENCRYPTED;
+use crate::game_profile::MAX_WRITABLE_PAK_VERSION;
+use crate::pakversion::PakVersion;
+use crate::{Compression, EntryMetadata, PakMemory};
+
+/// All valid pak versions
+const PAK_VERSIONS: [PakVersion; 12] = [
+    PakVersion::Initial,
+    PakVersion::NoTimestamps,
+    PakVersion::CompressionEncryption,
+    PakVersion::IndexEncryption,
+    PakVersion::RelativeChunkOffsets,
+    PakVersion::DeleteRecords,
+    PakVersion::EncryptionKeyGuid,
+    PakVersion::FnameBasedCompressionMethodInitial,
+    PakVersion::FnameBasedCompressionMethod,
+    PakVersion::FrozenIndex,
+    PakVersion::PathHashIndex,
+    PakVersion::Fnv64BugFix,
+];
+
+/// Characters entry path segments are made of
+const PATH_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789_-";
+
+impl<'a> Arbitrary<'a> for PakVersion {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u.choose(&PAK_VERSIONS).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for Compression {
+    /// Only compression methods which can be written are generated
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        #[cfg(feature = "zstd")]
+        let methods = [Compression::None, Compression::zlib(), Compression::zstd()];
+        #[cfg(not(feature = "zstd"))]
+        let methods = [Compression::None, Compression::zlib()];
+
+        u.choose(&methods).copied()
+    }
+}
+
+impl<'a> Arbitrary<'a> for EntryMetadata {
+    /// The encrypted flag is never set
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(EntryMetadata {
+            compress: u.arbitrary()?,
+            flags: u.arbitrary::<u8>()? & !FLAG_ENCRYPTED,
+            timestamp: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PakMemory {
+    /// Generates writable paks, with a pak version up to [`MAX_WRITABLE_PAK_VERSION`]
+    /// and entries with relative paths made of ASCII letters, digits, `_` and `-`
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let writable = PAK_VERSIONS
+            .iter()
+            .position(|e| *e == MAX_WRITABLE_PAK_VERSION)
+            .expect("Max writable pak version is valid");
+        let pak_version = *u.choose(&PAK_VERSIONS[..=writable])?;
+
+        let mut pak = PakMemory::new(pak_version);
+        pak.block_size = u.int_in_range(0x1000..=0x40000)?;
+
+        for _ in 0..u.arbitrary_len::<(u8, Vec<u8>)>()? {
+            let path = arbitrary_path(u)?;
+            pak.set_entry(path, u.arbitrary()?);
+        }
+
+        Ok(pak)
+    }
+}
+
+/// Generate a relative entry path, e.g. `Content/a_1/B-2`
+fn arbitrary_path(u: &mut Unstructured) -> Result<String> {
+    let segment_count = u.int_in_range(1..=4)?;
+
+    let mut segments = Vec::with_capacity(segment_count);
+    for _ in 0..segment_count {
+        let len = u.int_in_range(1..=12)?;
+        let segment = (0..len)
+            .map(|_| u.choose(PATH_CHARS).map(|e| *e as char))
+            .collect::<Result<String>>()?;
+        segments.push(segment);
+    }
+
+    Ok(segments.join("/"))
+}
@@ -0,0 +1,32 @@
+//! Streaming install chunks
+//!
+//! Games using streaming installs split their content into chunks, each chunk is stored in its own
+//! pak named `pakchunk<id>-<platform>.pak`. Patch paks, which most mods are, have a `_P` suffix
+//! and are mounted with a higher priority than the paks they patch.
+
+use std::path::Path;
+
+/// Get the file name of the pak of a chunk
+///
+/// E.g. `pakchunk3-WindowsNoEditor.pak`, or `pakchunk3-WindowsNoEditor_P.pak` for a patch pak.
+pub fn chunk_pak_name(chunk_id: i32, platform: &str, patch: bool) -> String {
+    match patch {
+        true => format!("pakchunk{chunk_id}-{platform}_P.pak"),
+        false => format!("pakchunk{chunk_id}-{platform}.pak"),
+    }
+}
+
+/// Get the chunk id of a pak from its file name or path
+///
+/// Returns `None` if the file name doesn't follow the `pakchunk<id>` convention.
+pub fn parse_chunk_id(pak_path: &str) -> Option<i32> {
+    let file_name = Path::new(pak_path).file_name()?.to_str()?;
+    let prefix = file_name.get(.."pakchunk".len())?;
+    if !prefix.eq_ignore_ascii_case("pakchunk") {
+        return None;
+    }
+
+    let id = &file_name[prefix.len()..];
+    let end = id.find(|e: char| !e.is_ascii_digit()).unwrap_or(id.len());
+    id[..end].parse().ok()
+}
@@ -1,10 +1,12 @@
 //! Compression abstraction
 //! Currently supportted compressions (in addition to no compression):
 //! - Zlib
+//! - Zstd, with the `zstd` feature
 
 //* Note: when adding more compressions you should only have to update stuff in this file, but in a few places.
 
 use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::thread;
 
 use flate2::{read::ZlibDecoder, write::ZlibEncoder};
 
@@ -29,6 +31,14 @@ impl Compression {
         Self::Known("Zlib")
     }
 
+    /// Create Zstd Compression configuration
+    ///
+    /// Zstd is not supported by the engine by default, only use it for games which ship a zstd compression plugin.
+    /// Compressing and decompressing requires the `zstd` feature.
+    pub fn zstd() -> Self {
+        Self::Known("Zstd")
+    }
+
     pub(crate) fn from_reader<R: Read + Seek>(reader: &mut R) -> io::Result<Self> {
         let mut buf = [0; 0x20];
         reader.read_exact(&mut buf)?;
@@ -37,6 +47,8 @@ impl Compression {
             Self::None
         } else if buf == pad_zeroes("Zlib".as_bytes()) {
             Self::zlib()
+        } else if buf == pad_zeroes("Zstd".as_bytes()) {
+            Self::zstd()
         } else {
             Self::Unknown(buf)
         })
@@ -112,25 +124,128 @@ impl Compression {
                     decoder.read_to_end(buf)?;
                     Ok(())
                 }
+                #[cfg(feature = "zstd")]
+                "Zstd" => {
+                    let mut decoder = zstd::Decoder::new(data)?;
+                    decoder.read_to_end(buf)?;
+                    Ok(())
+                }
+                #[cfg(not(feature = "zstd"))]
+                "Zstd" => Err(zstd_unsupported()),
                 _ => panic!("Found Compression::Known with unknown compression."),
             },
             _ => panic!("Attempted to decompress with Compression type that can't decompress."),
         }
     }
 
-    pub(crate) fn compress(&self, data: &[u8]) -> io::Result<Vec<u8>> {
+    pub(crate) fn compress(
+        &self,
+        data: &[u8],
+        options: &CompressionOptions,
+    ) -> io::Result<Vec<u8>> {
         match self {
             Self::Known(method) => match *method {
                 "Zlib" => {
-                    let mut encoder = ZlibEncoder::new(Vec::new(), flate2::Compression::default());
+                    let level = match options.level {
+                        Some(level) => flate2::Compression::new(level.clamp(0, 9) as u32),
+                        None => flate2::Compression::default(),
+                    };
+                    let mut encoder = ZlibEncoder::new(Vec::new(), level);
                     encoder.write_all(data)?;
                     Ok(encoder.finish()?)
                 }
+                #[cfg(feature = "zstd")]
+                "Zstd" => {
+                    let level = options.level.unwrap_or(zstd::DEFAULT_COMPRESSION_LEVEL);
+                    let mut compressor = match options.dictionary {
+                        Some(ref dictionary) => {
+                            zstd::bulk::Compressor::with_dictionary(level, dictionary)?
+                        }
+                        None => zstd::bulk::Compressor::new(level)?,
+                    };
+                    compressor.compress(data)
+                }
+                #[cfg(not(feature = "zstd"))]
+                "Zstd" => Err(zstd_unsupported()),
                 _ => panic!("Found Compression::Known with unknown compression."),
             },
             _ => panic!("Attempted to compress with Compression type that can't compress."),
         }
     }
+
+    /// Compress blocks of data, using multiple threads if enabled in the options
+    pub(crate) fn compress_blocks(
+        &self,
+        data: &[u8],
+        block_size: usize,
+        options: &CompressionOptions,
+    ) -> io::Result<Vec<Vec<u8>>> {
+        let blocks = data.chunks(block_size).collect::<Vec<_>>();
+        if options.threads <= 1 || blocks.len() <= 1 {
+            return blocks
+                .into_iter()
+                .map(|block| self.compress(block, options))
+                .collect();
+        }
+
+        let blocks_per_thread = blocks.len().div_ceil(options.threads);
+        thread::scope(|scope| {
+            let handles = blocks
+                .chunks(blocks_per_thread)
+                .map(|blocks| {
+                    scope.spawn(move || {
+                        blocks
+                            .iter()
+                            .map(|block| self.compress(block, options))
+                            .collect::<io::Result<Vec<_>>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            let mut compressed = Vec::with_capacity(blocks.len());
+            for handle in handles {
+                let blocks = handle
+                    .join()
+                    .map_err(|_| io::Error::other("Compression thread panicked"))?;
+                compressed.extend(blocks?);
+            }
+            Ok(compressed)
+        })
+    }
+}
+
+/// Options for compressing pak entries
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompressionOptions {
+    /// Compression level, `None` uses the default level of the compression method
+    ///
+    /// Zlib supports levels from 0 to 9, zstd from 1 to 22.
+    pub level: Option<i32>,
+    /// Zstd dictionary
+    ///
+    /// The game needs to use the same dictionary for decompression,
+    /// entries compressed with a dictionary can't be read by [`crate::PakReader`].
+    pub dictionary: Option<Vec<u8>>,
+    /// Amount of threads used to compress the blocks of an entry
+    pub threads: usize,
+}
+
+impl Default for CompressionOptions {
+    fn default() -> Self {
+        CompressionOptions {
+            level: None,
+            dictionary: None,
+            threads: 1,
+        }
+    }
+}
+
+#[cfg(not(feature = "zstd"))]
+fn zstd_unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "Zstd compression requires the zstd feature",
+    )
 }
 
 fn pad_zeroes(slice: &[u8]) -> [u8; 0x20] {
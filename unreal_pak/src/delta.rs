@@ -0,0 +1,348 @@
+//! Binary deltas between two pak files
+//!
+//! A [`PakDelta`] only contains the entries which changed between two versions of a pak,
+//! which allows updating a pak without transferring the whole archive.
+//! With the `bsdiff` feature enabled, changed entries can be stored as binary patches
+//! against their previous version instead of their full content.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::io::{Read, Seek, Write};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, BE, LE};
+
+use unreal_helpers::{UnrealReadExt, UnrealWriteExt};
+
+use crate::entry::EntryMetadata;
+use crate::error::PakError;
+use crate::hash;
+use crate::pakreader::PakReader;
+use crate::pakwriter::PakWriter;
+
+const DELTA_MAGIC: u32 = u32::from_be_bytes(*b"UPDL");
+const DELTA_VERSION: u32 = 2;
+
+/// A change made to a single pak entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DeltaEntry {
+    /// The entry was removed
+    Removed,
+    /// The entry was added or changed, contains the full new content
+    Full {
+        /// Compression, flags and timestamp of the new entry
+        metadata: EntryMetadata,
+        /// New content
+        data: Vec<u8>,
+    },
+    /// The entry was changed, contains a bsdiff patch against the old content
+    Patch {
+        /// Compression, flags and timestamp of the new entry
+        metadata: EntryMetadata,
+        /// SHA1 hash of the old content the patch applies to
+        old_hash: [u8; 20],
+        /// bsdiff patch
+        patch: Vec<u8>,
+    },
+}
+
+/// Delta between two pak files
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PakDelta {
+    /// Mount point of the new pak
+    pub mount_point: String,
+    /// Changed entries, entries which are not listed are unchanged
+    pub entries: Vec<(String, DeltaEntry)>,
+}
+
+#[cfg(feature = "bsdiff")]
+fn create_patch(old_data: &[u8], new_data: &[u8]) -> Result<Vec<u8>, PakError> {
+    let mut patch = Vec::new();
+    bsdiff::diff(old_data, new_data, &mut patch)?;
+    Ok(patch)
+}
+
+#[cfg(feature = "bsdiff")]
+fn apply_patch(old_data: &[u8], mut patch: &[u8]) -> Result<Vec<u8>, PakError> {
+    let mut new_data = Vec::new();
+    bsdiff::patch(old_data, &mut patch, &mut new_data)?;
+    Ok(new_data)
+}
+
+#[cfg(not(feature = "bsdiff"))]
+fn apply_patch(_old_data: &[u8], _patch: &[u8]) -> Result<Vec<u8>, PakError> {
+    Err(PakError::configuration_invalid())
+}
+
+impl PakDelta {
+    /// Create a delta which turns `old` into `new`
+    ///
+    /// Both readers need to have their index loaded already,
+    /// indices loaded with [`PakReader::load_index_lazy`] are fully decoded.
+    /// If `use_patches` is set and the `bsdiff` feature is enabled,
+    /// changed entries are stored as patches whenever that is smaller than their full content.
+    pub fn create<R1, R2>(
+        old: &mut PakReader<R1>,
+        new: &mut PakReader<R2>,
+        use_patches: bool,
+    ) -> Result<Self, PakError>
+    where
+        R1: Read + Seek,
+        R2: Read + Seek,
+    {
+        #[cfg(not(feature = "bsdiff"))]
+        let _ = use_patches;
+
+        old.preload_full_index()?;
+        new.preload_full_index()?;
+
+        let names = old
+            .get_entry_names()
+            .into_iter()
+            .chain(new.get_entry_names())
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        let mut entries = Vec::new();
+        for name in names {
+            if !new.contains_entry(&name) {
+                entries.push((name, DeltaEntry::Removed));
+                continue;
+            }
+
+            let metadata = new.get_entry_metadata(&name).unwrap_or_default();
+            let new_data = new.read_entry(&name)?;
+
+            if !old.contains_entry(&name) {
+                entries.push((
+                    name,
+                    DeltaEntry::Full {
+                        metadata,
+                        data: new_data,
+                    },
+                ));
+                continue;
+            }
+
+            let old_data = old.read_entry(&name)?;
+            if old_data == new_data && old.get_entry_metadata(&name) == Some(metadata) {
+                continue;
+            }
+
+            #[cfg(feature = "bsdiff")]
+            {
+                if use_patches {
+                    let patch = create_patch(&old_data, &new_data)?;
+                    if patch.len() < new_data.len() {
+                        entries.push((
+                            name,
+                            DeltaEntry::Patch {
+                                metadata,
+                                old_hash: hash(&old_data),
+                                patch,
+                            },
+                        ));
+                        continue;
+                    }
+                }
+            }
+
+            entries.push((
+                name,
+                DeltaEntry::Full {
+                    metadata,
+                    data: new_data,
+                },
+            ));
+        }
+
+        Ok(PakDelta {
+            mount_point: new.mount_point.clone(),
+            entries,
+        })
+    }
+
+    /// Apply this delta to `old`, writing the resulting pak into `new`
+    ///
+    /// The reader needs to have its index loaded already, an index loaded with [`PakReader::load_index_lazy`]
+    /// is fully decoded. The writer is not finished.
+    pub fn apply<R, W>(
+        &self,
+        old: &mut PakReader<R>,
+        new: &mut PakWriter<W>,
+    ) -> Result<(), PakError>
+    where
+        R: Read + Seek,
+        W: Write + Seek,
+    {
+        new.mount_point = self.mount_point.clone();
+        old.preload_full_index()?;
+
+        let changed = self
+            .entries
+            .iter()
+            .map(|(name, entry)| (name, entry))
+            .collect::<BTreeMap<_, _>>();
+
+        let names = old
+            .get_entry_names()
+            .into_iter()
+            .chain(changed.keys().copied())
+            .cloned()
+            .collect::<BTreeSet<_>>();
+
+        for name in names {
+            match changed.get(&name) {
+                None => {
+                    let metadata = old.get_entry_metadata(&name).unwrap_or_default();
+                    let data = old.read_entry(&name)?;
+                    new.write_entry_with_metadata(&name, &data, &metadata)?;
+                }
+                Some(DeltaEntry::Removed) => {}
+                Some(DeltaEntry::Full { metadata, data }) => {
+                    new.write_entry_with_metadata(&name, data, metadata)?;
+                }
+                Some(DeltaEntry::Patch {
+                    metadata,
+                    old_hash,
+                    patch,
+                }) => {
+                    let old_data = old.read_entry(&name)?;
+                    if hash(&old_data) != *old_hash {
+                        return Err(PakError::entry_invalid());
+                    }
+
+                    let data = apply_patch(&old_data, patch)?;
+                    new.write_entry_with_metadata(&name, &data, metadata)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Read a delta
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, PakError> {
+        if reader.read_u32::<BE>()? != DELTA_MAGIC {
+            return Err(PakError::pak_invalid());
+        }
+        if reader.read_u32::<LE>()? != DELTA_VERSION {
+            return Err(PakError::pak_invalid());
+        }
+
+        let mount_point = reader.read_fstring()?.unwrap_or_default();
+
+        let entry_count = reader.read_u32::<LE>()?;
+        let mut entries = Vec::new();
+        for _ in 0..entry_count {
+            let name = reader.read_fstring()?.unwrap_or_default();
+
+            let entry = match reader.read_u8()? {
+                0 => DeltaEntry::Removed,
+                1 => {
+                    let metadata = read_metadata(reader)?;
+                    let data = read_data(reader)?;
+                    DeltaEntry::Full { metadata, data }
+                }
+                2 => {
+                    let metadata = read_metadata(reader)?;
+                    let mut old_hash = [0u8; 20];
+                    reader.read_exact(&mut old_hash)?;
+                    let patch = read_data(reader)?;
+                    DeltaEntry::Patch {
+                        metadata,
+                        old_hash,
+                        patch,
+                    }
+                }
+                _ => return Err(PakError::entry_invalid()),
+            };
+
+            entries.push((name, entry));
+        }
+
+        Ok(PakDelta {
+            mount_point,
+            entries,
+        })
+    }
+
+    /// Write this delta
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), PakError> {
+        writer.write_u32::<BE>(DELTA_MAGIC)?;
+        writer.write_u32::<LE>(DELTA_VERSION)?;
+
+        writer.write_fstring(Some(&self.mount_point))?;
+
+        writer.write_u32::<LE>(self.entries.len() as u32)?;
+        for (name, entry) in &self.entries {
+            writer.write_fstring(Some(name.as_str()))?;
+
+            match entry {
+                DeltaEntry::Removed => writer.write_u8(0)?,
+                DeltaEntry::Full { metadata, data } => {
+                    writer.write_u8(1)?;
+                    write_metadata(writer, metadata)?;
+                    write_data(writer, data)?;
+                }
+                DeltaEntry::Patch {
+                    metadata,
+                    old_hash,
+                    patch,
+                } => {
+                    writer.write_u8(2)?;
+                    write_metadata(writer, metadata)?;
+                    writer.write_all(old_hash)?;
+                    write_data(writer, patch)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn read_data<R: Read>(reader: &mut R) -> Result<Vec<u8>, PakError> {
+    let len = reader.read_u64::<LE>()?;
+
+    // don't trust the length for preallocation
+    let mut data = Vec::new();
+    reader.by_ref().take(len).read_to_end(&mut data)?;
+    if data.len() as u64 != len {
+        return Err(PakError::entry_invalid());
+    }
+
+    Ok(data)
+}
+
+fn write_data<W: Write>(writer: &mut W, data: &[u8]) -> Result<(), PakError> {
+    writer.write_u64::<LE>(data.len() as u64)?;
+    writer.write_all(data)?;
+    Ok(())
+}
+
+fn read_metadata<R: Read>(reader: &mut R) -> Result<EntryMetadata, PakError> {
+    let compress = reader.read_u8()? != 0;
+    let flags = reader.read_u8()?;
+    let timestamp = match reader.read_u8()? {
+        0 => None,
+        _ => Some(reader.read_u64::<LE>()?),
+    };
+
+    Ok(EntryMetadata {
+        compress,
+        flags,
+        timestamp,
+    })
+}
+
+fn write_metadata<W: Write>(writer: &mut W, metadata: &EntryMetadata) -> Result<(), PakError> {
+    writer.write_u8(u8::from(metadata.compress))?;
+    writer.write_u8(metadata.flags)?;
+    match metadata.timestamp {
+        Some(timestamp) => {
+            writer.write_u8(1)?;
+            writer.write_u64::<LE>(timestamp)?;
+        }
+        None => writer.write_u8(0)?,
+    }
+    Ok(())
+}
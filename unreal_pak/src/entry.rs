@@ -1,12 +1,45 @@
+use std::borrow::Cow;
 use std::io::{Read, Seek, SeekFrom, Write};
 
-use crate::compression::CompressionMethods;
+use crate::compression::{CompressionMethods, CompressionOptions};
 use crate::error::PakError;
 use crate::hash;
 use crate::header::{Block, Header};
 use crate::pakversion::PakVersion;
 use crate::Compression;
 
+/// Flag of encrypted entries
+pub(crate) const FLAG_ENCRYPTED: u8 = 0x01;
+
+/// Metadata stored in the header of an entry
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct EntryMetadata {
+    /// Whether the entry is compressed, entries under 32 bytes are never compressed
+    pub compress: bool,
+    /// Entry flags, the encrypted flag is never written because encryption is unsupported
+    pub flags: u8,
+    /// Timestamp, only stored by the initial pak version
+    pub timestamp: Option<u64>,
+}
+
+impl EntryMetadata {
+    /// Create new `EntryMetadata` with no flags or timestamp
+    pub fn new(compress: bool) -> Self {
+        EntryMetadata {
+            compress,
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn from_header(header: &Header) -> Self {
+        EntryMetadata {
+            compress: !matches!(header.compression_method, Compression::None),
+            flags: header.flags.unwrap_or_default(),
+            timestamp: header.timestamp,
+        }
+    }
+}
+
 /// Read a pak entry at the given offset in the reader
 ///
 /// # Arguments
@@ -28,13 +61,9 @@ where
     let header = Header::read(reader, pak_version, compression)?;
 
     match header.compression_method {
-        Compression::None => {
-            let mut data = vec![0u8; header.decompressed_size as usize];
-            reader.read_exact(data.as_mut_slice())?;
-            Ok(data)
-        }
+        Compression::None => read_data(reader, header.decompressed_size),
         Compression::Known(_) => {
-            let mut data = Vec::with_capacity(header.decompressed_size as usize);
+            let mut data = allocate(header.decompressed_size)?;
 
             let compression_blocks = header
                 .compression_blocks
@@ -42,8 +71,7 @@ where
                 .ok_or_else(PakError::entry_invalid)?;
             for block in compression_blocks {
                 // we do not need to seek here because the reader is at the end of the header and compression blocks are continuous
-                let mut compressed_data = vec![0u8; block.size as usize];
-                reader.read_exact(&mut compressed_data)?;
+                let compressed_data = read_data(reader, block.size)?;
                 header
                     .compression_method
                     .decompress(&mut data, compressed_data.as_slice())?;
@@ -55,43 +83,60 @@ where
     }
 }
 
-/// Write an entry with Header at the position the write is at
+/// Allocate a buffer for `size` bytes, sizes of entries in damaged paks may be far too large
+fn allocate(size: u64) -> Result<Vec<u8>, PakError> {
+    let mut data = Vec::new();
+    data.try_reserve_exact(size as usize)
+        .map_err(|_| PakError::entry_invalid())?;
+    Ok(data)
+}
+
+/// Read `size` bytes, only filling the buffer as far as the data goes
+fn read_data<R: Read>(reader: &mut R, size: u64) -> Result<Vec<u8>, PakError> {
+    let mut data = allocate(size)?;
+    reader.by_ref().take(size).read_to_end(&mut data)?;
+    if data.len() as u64 != size {
+        return Err(PakError::entry_invalid());
+    }
+    Ok(data)
+}
+
+/// Entry which was compressed and hashed but not written yet
+#[derive(Debug, Clone)]
+pub(crate) struct EncodedEntry<'data> {
+    /// Header of the entry, the offset is set once the entry is written
+    pub header: Header,
+    /// Possibly compressed data of the entry
+    pub data: Cow<'data, [u8]>,
+}
+
+/// Compress and hash an entry so it can be written later
 ///
 /// # Arguments
 ///
-/// * `writer` - Anything that implements Write + Seek
 /// * `pak_version` - Version of the pak format to be used
 /// * `data` - Uncompressed data to be written
-/// * `compression_method` - What compression to use
+/// * `metadata` - Compression, flags and timestamp of the entry
+/// * `compression` - Compression methods of the pak, the first one is used
 /// * `block_size` - size of the used compression blocks
-pub(crate) fn write_entry<W>(
-    writer: &mut W,
+/// * `options` - Compression level and threading options
+pub(crate) fn encode_entry<'data>(
     pak_version: PakVersion,
-    data: &Vec<u8>,
-    compress: bool,
+    data: Cow<'data, [u8]>,
+    metadata: &EntryMetadata,
     compression: &CompressionMethods,
     block_size: u32,
-) -> Result<Header, PakError>
-where
-    W: Write + Seek,
-{
-    let offset = writer.stream_position()?;
+    options: &CompressionOptions,
+) -> Result<EncodedEntry<'data>, PakError> {
     let decompressed_size = data.len() as u64;
 
-    let compress = compress && decompressed_size >= 32;
+    let compress = metadata.compress && decompressed_size >= 32;
     let compression_method = if compress {
         compression.0[0]
     } else {
         Compression::None
     };
 
-    // compress data in memory
-    let mut compressed_data = if compress {
-        Vec::with_capacity(data.len())
-    } else {
-        // this will actually never be used
-        Vec::new()
-    };
     let mut compression_blocks = None;
     let data = match compression_method {
         Compression::Known(_) => {
@@ -99,14 +144,18 @@ where
                 return Err(PakError::configuration_invalid());
             }
 
+            // compress data in memory
+            let mut compressed_data = Vec::with_capacity(data.len());
+
             let block_count = (data.len() as f64 / block_size as f64).ceil() as usize;
             let mut compression_blocks_inner = Vec::with_capacity(block_count);
             let header_len = Header::calculate_header_len(pak_version, Some(block_count as u32));
 
-            for chunk in data.chunks(block_size as usize) {
+            for block_compressed_data in
+                compression_method.compress_blocks(&data, block_size as usize, options)?
+            {
                 let begin = compressed_data.len() as u64;
 
-                let block_compressed_data = compression_method.compress(chunk)?;
                 compressed_data.extend_from_slice(&block_compressed_data);
 
                 compression_blocks_inner.push(Block {
@@ -116,7 +165,7 @@ where
             }
 
             compression_blocks = Some(compression_blocks_inner);
-            &compressed_data
+            Cow::Owned(compressed_data)
         }
         Compression::None => data,
         _ => return Err(PakError::compression_unsupported(compression_method)),
@@ -134,22 +183,80 @@ where
         None
     };
 
-    let mut header = Header {
+    let header = Header {
         offset: 0x00,
         compressed_size: data.len() as u64,
         decompressed_size,
         compression_method,
-        hash: hash(data),
+        timestamp: metadata.timestamp,
+        hash: hash(&data),
         compression_blocks,
         compression_block_size,
-        flags: Some(0x00),
+        flags: Some(metadata.flags & !FLAG_ENCRYPTED),
     };
 
+    Ok(EncodedEntry { header, data })
+}
+
+/// Write an encoded entry with Header at the position the write is at
+///
+/// # Arguments
+///
+/// * `writer` - Anything that implements Write + Seek
+/// * `pak_version` - Version of the pak format to be used
+/// * `compression` - Compression methods of the pak
+/// * `entry` - Entry created by [`encode_entry`]
+pub(crate) fn write_encoded_entry<W>(
+    writer: &mut W,
+    pak_version: PakVersion,
+    compression: &CompressionMethods,
+    entry: EncodedEntry<'_>,
+) -> Result<Header, PakError>
+where
+    W: Write + Seek,
+{
+    let offset = writer.stream_position()?;
+    let EncodedEntry { mut header, data } = entry;
+
     Header::write(writer, pak_version, compression, &header)?;
-    writer.write_all(data)?;
+    writer.write_all(&data)?;
 
     // the offset in the header right before the data is always 0x00, so only set here
     header.offset = offset;
 
     Ok(header)
 }
+
+/// Write an entry with Header at the position the write is at
+///
+/// # Arguments
+///
+/// * `writer` - Anything that implements Write + Seek
+/// * `pak_version` - Version of the pak format to be used
+/// * `data` - Uncompressed data to be written
+/// * `metadata` - Compression, flags and timestamp of the entry
+/// * `compression` - Compression methods of the pak, the first one is used
+/// * `block_size` - size of the used compression blocks
+/// * `options` - Compression level and threading options
+pub(crate) fn write_entry<W>(
+    writer: &mut W,
+    pak_version: PakVersion,
+    data: &[u8],
+    metadata: &EntryMetadata,
+    compression: &CompressionMethods,
+    block_size: u32,
+    options: &CompressionOptions,
+) -> Result<Header, PakError>
+where
+    W: Write + Seek,
+{
+    let entry = encode_entry(
+        pak_version,
+        Cow::Borrowed(data),
+        metadata,
+        compression,
+        block_size,
+        options,
+    )?;
+    write_encoded_entry(writer, pak_version, compression, entry)
+}
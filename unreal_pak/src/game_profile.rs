@@ -0,0 +1,134 @@
+//! Game profiles
+//!
+//! A [`GameProfile`] pins everything a game expects of the paks it loads: the pak version,
+//! the compression method, whether paks have to be encrypted and where paks are mounted.
+//! Profiles for every engine version and a few games ship with the crate,
+//! profiles of other games can be created like any other struct.
+//!
+//! ```no_run
+//! use std::fs::File;
+//! use std::io::BufWriter;
+//!
+//! use unreal_pak::{GameProfile, PakWriter};
+//!
+//! let profile = GameProfile::find("Astroneer").unwrap();
+//! let file = BufWriter::new(File::create("Mod_P.pak").unwrap());
+//! let mut pak = PakWriter::for_game(file, &profile).unwrap();
+//! ```
+
+use crate::error::PakError;
+use crate::mount_point::DEFAULT_MOUNT_POINT;
+use crate::pakversion::PakVersion;
+use crate::Compression;
+
+/// Newest pak version which can be written
+///
+/// Paks for games using a newer version are written with this version,
+/// the engine can load paks of all older versions.
+pub const MAX_WRITABLE_PAK_VERSION: PakVersion = PakVersion::Fnv64BugFix;
+
+/// Description of the paks a game loads
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameProfile {
+    /// Name of the game or engine version, e.g. `Astroneer` or `UE4.27`
+    pub name: String,
+    /// Version of the paks shipped with the game
+    pub pak_version: PakVersion,
+    /// Compression method used for compressed entries
+    pub compression: Compression,
+    /// Compression block size
+    pub block_size: u32,
+    /// Whether the game only loads encrypted paks
+    pub requires_encryption: bool,
+    /// Mount point of paks, typically `../../../`
+    pub mount_point: String,
+}
+
+impl GameProfile {
+    /// Create a new `GameProfile` with zlib compression, 64 KiB compression blocks,
+    /// no encryption and paks mounted at the engine root
+    pub fn new(name: impl Into<String>, pak_version: PakVersion) -> Self {
+        GameProfile {
+            name: name.into(),
+            pak_version,
+            compression: Compression::zlib(),
+            block_size: 0x010000,
+            requires_encryption: false,
+            mount_point: DEFAULT_MOUNT_POINT.to_owned(),
+        }
+    }
+
+    /// Create the profile of an unmodified engine version, e.g. `engine(4, 27)`
+    ///
+    /// Returns `None` for engine versions before 4.0.
+    pub fn engine(major: u32, minor: u32) -> Option<Self> {
+        let pak_version = match (major, minor) {
+            (4, 0..=2) => PakVersion::NoTimestamps,
+            (4, 3..=15) => PakVersion::CompressionEncryption,
+            (4, 16..=19) => PakVersion::IndexEncryption,
+            (4, 20) => PakVersion::RelativeChunkOffsets,
+            (4, 21) => PakVersion::EncryptionKeyGuid,
+            (4, 22) => PakVersion::FnameBasedCompressionMethodInitial,
+            (4, 23..=24) => PakVersion::FnameBasedCompressionMethod,
+            (4, 25) => PakVersion::FrozenIndex,
+            (4, _) | (5, _) => PakVersion::Fnv64BugFix,
+            _ => return None,
+        };
+        Some(Self::new(format!("UE{major}.{minor}"), pak_version))
+    }
+
+    /// Get all profiles shipped with the crate
+    ///
+    /// Contains a profile for every engine version from 4.0 to 5.4 named like `UE4.27`
+    /// and profiles for some games.
+    pub fn builtin() -> Vec<Self> {
+        let mut profiles = (0..=27)
+            .map(|minor| (4, minor))
+            .chain((0..=4).map(|minor| (5, minor)))
+            .filter_map(|(major, minor)| Self::engine(major, minor))
+            .collect::<Vec<_>>();
+
+        profiles.push(Self::new(
+            "Astroneer",
+            PakVersion::FnameBasedCompressionMethod,
+        ));
+
+        profiles
+    }
+
+    /// Find a profile shipped with the crate by its name, ignoring case
+    pub fn find(name: &str) -> Option<Self> {
+        Self::builtin()
+            .into_iter()
+            .find(|e| e.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Negotiate the pak version paks for this game are written with
+    ///
+    /// This is the version of the game, or [`MAX_WRITABLE_PAK_VERSION`] for games using a newer version.
+    /// Fails if the game requires encryption or the compression method can't be stored in the negotiated version.
+    pub fn negotiate_pak_version(&self) -> Result<PakVersion, PakError> {
+        if self.requires_encryption {
+            return Err(PakError::enrcryption_unsupported());
+        }
+        if self.pak_version == PakVersion::Invalid {
+            return Err(PakError::pak_version_unsupported(self.pak_version));
+        }
+
+        let pak_version = self.pak_version.min(MAX_WRITABLE_PAK_VERSION);
+
+        let compression_supported = match self.compression {
+            // before compression methods were stored by name only zlib could be used
+            Compression::Known(_) if pak_version < PakVersion::FnameBasedCompressionMethod => {
+                self.compression == Compression::zlib()
+            }
+            Compression::Known(_) => true,
+            _ => false,
+        };
+        if !compression_supported {
+            return Err(PakError::compression_unsupported(self.compression));
+        }
+
+        Ok(pak_version)
+    }
+}
@@ -4,6 +4,7 @@
     - u64 size
     - u64 size decompressed
     - u32 compression method
+    - u64 timestamp (only version 1)
     - 20 bytes sha1 hash
     - compression block data (only when compression method is not 0)
         - u32 number of blocks
@@ -20,16 +21,18 @@ use bitvec::prelude::*;
 use byteorder::{ReadBytesExt, WriteBytesExt, LE};
 
 use crate::compression::{Compression, CompressionMethods};
+use crate::entry::FLAG_ENCRYPTED;
 use crate::error::PakError;
 use crate::pakversion::PakVersion;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) struct Header {
     /// This may incorrectly be 0x00
     pub offset: u64,
     pub compressed_size: u64,
     pub decompressed_size: u64,
     pub compression_method: Compression,
+    pub timestamp: Option<u64>,
     pub hash: [u8; 20],
     pub compression_blocks: Option<Vec<Block>>,
     pub flags: Option<u8>,
@@ -61,9 +64,10 @@ impl Header {
         let compression_method =
             Compression::from_u32(reader.read_u32::<LE>()?, pak_version, compression);
 
-        if pak_version <= PakVersion::Initial {
-            let _timestamp = reader.read_u64::<LE>()?;
-        }
+        let timestamp = match pak_version <= PakVersion::Initial {
+            true => Some(reader.read_u64::<LE>()?),
+            false => None,
+        };
 
         let mut hash = [0u8; 20];
         reader.read_exact(&mut hash)?;
@@ -74,21 +78,26 @@ impl Header {
 
         if pak_version >= PakVersion::CompressionEncryption {
             if !matches!(compression_method, Compression::None) {
-                let block_count = reader.read_u32::<LE>()? as usize;
-                let mut compression_blocks_inner = Vec::with_capacity(block_count);
+                let block_count = reader.read_u32::<LE>()?;
+                // don't trust the block count for preallocation
+                let mut compression_blocks_inner = Vec::new();
 
                 for _ in 0..block_count {
                     // convert old absolute to relative offsets
-                    let start_offset = reader.read_u64::<LE>()?
-                        - if pak_version < PakVersion::RelativeChunkOffsets {
+                    let start_offset = reader
+                        .read_u64::<LE>()?
+                        .checked_sub(if pak_version < PakVersion::RelativeChunkOffsets {
                             offset
                         } else {
                             0
-                        };
+                        })
+                        .ok_or_else(PakError::pak_invalid)?;
                     let end_offset = reader.read_u64::<LE>()?;
                     compression_blocks_inner.push(Block {
                         start: start_offset,
-                        size: end_offset - start_offset,
+                        size: end_offset
+                            .checked_sub(start_offset)
+                            .ok_or_else(PakError::pak_invalid)?,
                     });
                 }
                 compression_blocks = Some(compression_blocks_inner);
@@ -103,6 +112,7 @@ impl Header {
             compressed_size,
             decompressed_size,
             compression_method,
+            timestamp,
             hash,
             compression_blocks,
             compression_block_size,
@@ -110,6 +120,105 @@ impl Header {
         })
     }
 
+    /// Read the header in front of the data of an entry while scanning for entries,
+    /// `position` is the offset the reader is at.
+    ///
+    /// Returns `None` if the data doesn't look like an unencrypted entry header.
+    /// Unlike [`Header::read`] this doesn't trust any counts or offsets, so it can be used on arbitrary data.
+    pub(crate) fn scan<R: Read>(
+        reader: &mut R,
+        pak_version: PakVersion,
+        compression: &CompressionMethods,
+        position: u64,
+    ) -> Result<Option<Self>, PakError> {
+        // the offset in the header right before the data is always 0x00
+        if reader.read_u64::<LE>()? != 0 {
+            return Ok(None);
+        }
+
+        let compressed_size = reader.read_u64::<LE>()?;
+        let decompressed_size = reader.read_u64::<LE>()?;
+
+        let compression_method =
+            Compression::from_u32(reader.read_u32::<LE>()?, pak_version, compression);
+        match compression_method {
+            Compression::None if compressed_size != decompressed_size => return Ok(None),
+            Compression::Unknown(_) => return Ok(None),
+            _ => {}
+        }
+
+        let timestamp = match pak_version <= PakVersion::Initial {
+            true => Some(reader.read_u64::<LE>()?),
+            false => None,
+        };
+
+        let mut hash = [0u8; 20];
+        reader.read_exact(&mut hash)?;
+
+        let mut compression_blocks = None;
+        let mut flags = None;
+        let mut compression_block_size = None;
+
+        if pak_version >= PakVersion::CompressionEncryption {
+            if !matches!(compression_method, Compression::None) {
+                let block_count = reader.read_u32::<LE>()?;
+                if block_count == 0 || block_count as u64 > compressed_size {
+                    return Ok(None);
+                }
+
+                // blocks follow the header back to back, old versions may store absolute offsets
+                let header_len = Self::calculate_header_len(pak_version, Some(block_count));
+                let mut base = None;
+
+                let mut next_start = header_len;
+                let mut compression_blocks_inner = Vec::new();
+                for _ in 0..block_count {
+                    let start = reader.read_u64::<LE>()?;
+                    let end = reader.read_u64::<LE>()?;
+                    let base = *base.get_or_insert(match start == header_len {
+                        true => 0,
+                        false => position,
+                    });
+
+                    match (start.checked_sub(base), end.checked_sub(base)) {
+                        (Some(start), Some(end)) if start == next_start && end >= start => {
+                            compression_blocks_inner.push(Block {
+                                start,
+                                size: end - start,
+                            });
+                            next_start = end;
+                        }
+                        _ => return Ok(None),
+                    }
+                }
+
+                if next_start - header_len != compressed_size {
+                    return Ok(None);
+                }
+                compression_blocks = Some(compression_blocks_inner);
+            }
+
+            let entry_flags = reader.read_u8()?;
+            if entry_flags & FLAG_ENCRYPTED != 0 {
+                return Ok(None);
+            }
+            flags = Some(entry_flags);
+            compression_block_size = Some(reader.read_u32::<LE>()?);
+        }
+
+        Ok(Some(Header {
+            offset: 0,
+            compressed_size,
+            decompressed_size,
+            compression_method,
+            timestamp,
+            hash,
+            compression_blocks,
+            compression_block_size,
+            flags,
+        }))
+    }
+
     /// Read (bit)encoded header
     pub(crate) fn read_encoded<R: Read + Seek>(
         reader: &mut R,
@@ -161,6 +270,7 @@ impl Header {
             compressed_size,
             decompressed_size,
             compression_method,
+            timestamp: None,
             hash: [0; 20],
             compression_blocks: None,
             compression_block_size: Some(block_size),
@@ -168,6 +278,72 @@ impl Header {
         })
     }
 
+    /// Write (bit)encoded header, returns `false` without writing anything if the header can't be encoded
+    ///
+    /// Headers with more than 0xffff compression blocks, a compression method index above 0x3f
+    /// or flags other than encryption have to be stored unencoded.
+    pub(crate) fn write_encoded<W: Write>(
+        writer: &mut W,
+        pak_version: PakVersion,
+        compression: &CompressionMethods,
+        header: &Self,
+    ) -> Result<bool, PakError> {
+        let compression_method = header.compression_method.as_u32(pak_version, compression)?;
+        let compression_blocks = header.compression_blocks.as_deref().unwrap_or_default();
+        let flags = header.flags.unwrap_or(0);
+
+        if compression_method > 0x3f
+            || compression_blocks.len() > 0xffff
+            || flags & !FLAG_ENCRYPTED != 0
+        {
+            return Ok(false);
+        }
+
+        let block_size = header.compression_block_size.unwrap_or(0);
+        let encoded_block_size = match block_size & 0x7ff == 0 && block_size >> 11 < 0x3f {
+            true => block_size >> 11,
+            false => 0x3f,
+        };
+        let is_encrypted = flags & FLAG_ENCRYPTED != 0;
+
+        let mut header_bits = [0u8; 4];
+        let bits = header_bits.view_bits_mut::<Lsb0>();
+        bits[0..=5].store_le(encoded_block_size);
+        bits[6..=21].store_le(compression_blocks.len() as u32);
+        bits.set(22, is_encrypted);
+        bits[23..=28].store_le(compression_method);
+        bits.set(29, header.compressed_size <= u32::MAX as u64);
+        bits.set(30, header.decompressed_size <= u32::MAX as u64);
+        bits.set(31, header.offset <= u32::MAX as u64);
+        writer.write_all(&header_bits)?;
+
+        if encoded_block_size == 0x3f {
+            writer.write_u32::<LE>(block_size)?;
+        }
+
+        let mut write_size = |size: u64| -> io::Result<()> {
+            match u32::try_from(size) {
+                Ok(size) => writer.write_u32::<LE>(size),
+                Err(_) => writer.write_u64::<LE>(size),
+            }
+        };
+
+        write_size(header.offset)?;
+        write_size(header.decompressed_size)?;
+        if !matches!(header.compression_method, Compression::None) {
+            write_size(header.compressed_size)?;
+
+            // a single unencrypted block spans the whole entry so its size isn't needed
+            if is_encrypted || compression_blocks.len() != 1 {
+                for block in compression_blocks {
+                    writer.write_u32::<LE>(block.size as u32)?;
+                }
+            }
+        }
+
+        Ok(true)
+    }
+
     /// Write data from a Header into the writer, writer needs to be set where the header is supposed to be written
     pub(crate) fn write<W: Write>(
         writer: &mut W,
@@ -180,6 +356,10 @@ impl Header {
         writer.write_u64::<LE>(header.decompressed_size)?;
         writer.write_u32::<LE>(header.compression_method.as_u32(pak_version, compression)?)?;
 
+        if pak_version <= PakVersion::Initial {
+            writer.write_u64::<LE>(header.timestamp.unwrap_or_default())?;
+        }
+
         writer.write_all(&header.hash)?;
 
         if pak_version >= PakVersion::CompressionEncryption {
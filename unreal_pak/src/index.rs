@@ -1,38 +1,56 @@
+use std::collections::BTreeMap;
 use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
 use byteorder::{ReadBytesExt, WriteBytesExt, BE, LE};
 
-use unreal_helpers::{UnrealReadExt, UnrealWriteExt};
-
 use crate::compression::CompressionMethods;
 use crate::error::PakError;
 use crate::header::Header;
 use crate::pakversion::PakVersion;
+use crate::path_encoding::{read_path, write_path, PathEncoding};
 use crate::{hash, PAK_MAGIC};
 
 #[derive(Debug)]
 pub(crate) struct Index {
     pub mount_point: String,
+    pub path_encoding: PathEncoding,
     pub path_hash_seed: Option<u64>,
     pub entries: Vec<(String, Header)>,
     pub footer: Footer,
 }
 
 impl Index {
-    pub(crate) fn read<R: Read + Seek>(mut reader: &mut R) -> Result<Self, PakError> {
-        let footer = Footer::read(&mut reader)?;
+    pub(crate) fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, PakError> {
+        let (mut index, encoded_index) = Self::read_lazy(reader)?;
+        if let Some(encoded_index) = encoded_index {
+            index.entries = encoded_index.read_full_directory(
+                reader,
+                index.footer.pak_version,
+                &index.footer.compression_methods,
+            )?;
+        }
+        Ok(index)
+    }
+
+    /// Read the index without decoding the entries of v10+ paks, these are returned as an [`EncodedIndex`]
+    pub(crate) fn read_lazy<R: Read + Seek>(
+        reader: &mut R,
+    ) -> Result<(Self, Option<EncodedIndex>), PakError> {
+        let footer = Footer::read(reader)?;
 
         reader.seek(SeekFrom::Start(footer.index_offset))?;
 
-        let mount_point = reader.read_fstring()?.unwrap_or_default();
+        let mount_point = read_path(reader)?;
         let mut path_hash_seed = None;
+        let mut encoded_index = None;
 
         let entry_count = reader.read_u32::<LE>()?;
-        let mut entries = Vec::with_capacity(entry_count as usize);
+        // don't trust the counts in the index for preallocation
+        let mut entries = Vec::new();
 
         if footer.pak_version < PakVersion::PathHashIndex {
             for _ in 0..entry_count {
-                let file_name = reader.read_fstring()?.unwrap_or_default();
+                let file_name = read_path(reader)?;
 
                 entries.push((
                     file_name,
@@ -40,70 +58,21 @@ impl Index {
                 ));
             }
         } else {
-            path_hash_seed = Some(reader.read_u64::<LE>()?);
-
-            // path hash index
-            if reader.read_u32::<LE>()? != 0 {
-                let _path_hash_index_offset = reader.read_u64::<LE>()?;
-                let _path_hash_index_size = reader.read_u64::<LE>()?;
-                // skip hash
-                reader.seek(SeekFrom::Current(20))?;
-            }
-
-            let full_directory_index = if reader.read_u32::<LE>()? != 0 {
-                let full_directory_index_offset = reader.read_u64::<LE>()?;
-                let _full_directory_index_size = reader.read_u64::<LE>()?;
-                // skip hash
-                reader.seek(SeekFrom::Current(20))?;
-
-                let previous_pos = reader.stream_position()?;
-                reader.seek(SeekFrom::Start(full_directory_index_offset))?;
-
-                let directory_count = reader.read_u32::<LE>()? as usize;
-                let mut directories = Vec::new();
-                for _ in 0..directory_count {
-                    let directory_name = reader.read_fstring()?.unwrap_or_default();
-                    let file_count = reader.read_u32::<LE>()? as usize;
-                    let mut files = Vec::new();
-                    for _ in 0..file_count {
-                        let file_name = reader.read_fstring()?.unwrap_or_default();
-                        files.push((file_name, reader.read_u32::<LE>()?));
-                    }
-                    directories.push((directory_name, files));
-                }
-
-                reader.seek(SeekFrom::Start(previous_pos))?;
-                directories
-            } else {
-                return Err(PakError::pak_invalid());
-            };
-
-            let _encoded_size = reader.read_u32::<LE>()? as usize;
-            let position = reader.stream_position()?;
-
-            for (dir_name, dir) in &full_directory_index {
-                for (file_name, encoded_offset) in dir {
-                    let mut path = dir_name.strip_prefix('/').unwrap_or(dir_name).to_owned();
-                    path.push_str(file_name);
-
-                    reader.seek(SeekFrom::Start(position + *encoded_offset as u64))?;
-                    let entry = Header::read_encoded(
-                        &mut reader,
-                        footer.pak_version,
-                        &footer.compression_methods,
-                    )?;
-
-                    entries.push((path, entry));
-                }
-            }
+            let index = EncodedIndex::read(reader, &footer)?;
+            path_hash_seed = Some(index.path_hash_seed);
+            encoded_index = Some(index);
         }
 
-        Ok(Index {
-            mount_point,
-            path_hash_seed,
-            entries,
-            footer,
-        })
+        Ok((
+            Index {
+                mount_point,
+                path_encoding: PathEncoding::default(),
+                path_hash_seed,
+                entries,
+                footer,
+            },
+            encoded_index,
+        ))
     }
 
     pub(crate) fn write<W: Write + Seek>(writer: &mut W, mut index: Self) -> Result<(), PakError> {
@@ -111,13 +80,14 @@ impl Index {
 
         let mut index_writer = Cursor::new(Vec::new());
 
-        index_writer.write_fstring(Some(&index.mount_point))?;
+        write_path(&mut index_writer, &index.mount_point, index.path_encoding)?;
 
         index_writer.write_u32::<LE>(index.entries.len() as u32)?;
 
+        let mut secondary_indices = Vec::new();
         if index.footer.pak_version < PakVersion::PathHashIndex {
             for (name, header) in index.entries {
-                index_writer.write_fstring(Some(name.as_str()))?;
+                write_path(&mut index_writer, &name, index.path_encoding)?;
                 Header::write(
                     &mut index_writer,
                     index.footer.pak_version,
@@ -126,8 +96,7 @@ impl Index {
                 )?;
             }
         } else {
-            dbg!(index.path_hash_seed);
-            return Err(PakError::pak_version_unsupported(index.footer.pak_version));
+            secondary_indices = Self::write_encoded(&mut index_writer, &index, index_offset)?;
         }
 
         let index_data = index_writer.into_inner();
@@ -137,11 +106,284 @@ impl Index {
         index.footer.index_hash = hash(&index_data);
 
         writer.write_all(&index_data)?;
+        writer.write_all(&secondary_indices)?;
 
         Footer::write(writer, index.footer)?;
 
         Ok(())
     }
+
+    /// Write the encoded index of a v10+ pak after the entry count of the primary index
+    ///
+    /// Returns the path hash index and the full directory index,
+    /// which have to be written right after the primary index.
+    fn write_encoded(
+        index_writer: &mut Cursor<Vec<u8>>,
+        index: &Self,
+        index_offset: u64,
+    ) -> Result<Vec<u8>, PakError> {
+        let pak_version = index.footer.pak_version;
+        let compression = &index.footer.compression_methods;
+        let path_hash_seed = index.path_hash_seed.unwrap_or_default();
+
+        let mut encoded_entries = Vec::new();
+        let mut non_encoded_entries = Vec::new();
+        let mut non_encoded_count = 0u32;
+        let mut path_hashes = Vec::with_capacity(index.entries.len());
+        let mut directories = BTreeMap::<&str, Vec<(&str, i32)>>::new();
+
+        for (name, header) in &index.entries {
+            let offset = encoded_entries.len() as i32;
+            let offset = match Header::write_encoded(
+                &mut encoded_entries,
+                pak_version,
+                compression,
+                header,
+            )? {
+                true => offset,
+                false => {
+                    Header::write(&mut non_encoded_entries, pak_version, compression, header)?;
+                    non_encoded_count += 1;
+                    -(non_encoded_count as i32)
+                }
+            };
+
+            path_hashes.push((hash_path(name, path_hash_seed, pak_version), offset));
+
+            let (directory, file_name) = match name.rfind('/') {
+                Some(i) => name.split_at(i + 1),
+                None => ("/", name.as_str()),
+            };
+            directories
+                .entry(directory)
+                .or_default()
+                .push((file_name, offset));
+        }
+
+        let mut path_hash_index = Cursor::new(Vec::new());
+        path_hash_index.write_u32::<LE>(path_hashes.len() as u32)?;
+        for (path_hash, offset) in path_hashes {
+            path_hash_index.write_u64::<LE>(path_hash)?;
+            path_hash_index.write_i32::<LE>(offset)?;
+        }
+        // empty pruned directory index
+        path_hash_index.write_u32::<LE>(0)?;
+        let path_hash_index = path_hash_index.into_inner();
+
+        let mut full_directory_index = Cursor::new(Vec::new());
+        full_directory_index.write_u32::<LE>(directories.len() as u32)?;
+        for (directory, files) in directories {
+            write_path(&mut full_directory_index, directory, index.path_encoding)?;
+            full_directory_index.write_u32::<LE>(files.len() as u32)?;
+            for (file_name, offset) in files {
+                write_path(&mut full_directory_index, file_name, index.path_encoding)?;
+                full_directory_index.write_i32::<LE>(offset)?;
+            }
+        }
+        let full_directory_index = full_directory_index.into_inner();
+
+        // seed, two index locations, encoded entries and non encoded entries
+        let primary_index_size = index_writer.get_ref().len()
+            + 8
+            + 2 * (4 + 8 + 8 + 20)
+            + 4
+            + encoded_entries.len()
+            + 4
+            + non_encoded_entries.len();
+        let path_hash_index_offset = index_offset + primary_index_size as u64;
+        let full_directory_index_offset = path_hash_index_offset + path_hash_index.len() as u64;
+
+        index_writer.write_u64::<LE>(path_hash_seed)?;
+        for (offset, data) in [
+            (path_hash_index_offset, &path_hash_index),
+            (full_directory_index_offset, &full_directory_index),
+        ] {
+            index_writer.write_u32::<LE>(1)?;
+            index_writer.write_u64::<LE>(offset)?;
+            index_writer.write_u64::<LE>(data.len() as u64)?;
+            index_writer.write_all(&hash(data))?;
+        }
+
+        index_writer.write_u32::<LE>(encoded_entries.len() as u32)?;
+        index_writer.write_all(&encoded_entries)?;
+        index_writer.write_u32::<LE>(non_encoded_count)?;
+        index_writer.write_all(&non_encoded_entries)?;
+
+        let mut secondary_indices = path_hash_index;
+        secondary_indices.extend(full_directory_index);
+        Ok(secondary_indices)
+    }
+}
+
+/// Entries of a v10+ pak which have not been decoded yet
+///
+/// Entries can be looked up by name through the path hash index
+/// without reading the full directory index, which contains the names of all entries.
+#[derive(Debug)]
+pub(crate) struct EncodedIndex {
+    pub path_hash_seed: u64,
+    /// Offset and size of the path hash index
+    pub path_hash_index: Option<(u64, u64)>,
+    /// Offset and size of the full directory index
+    pub full_directory_index: Option<(u64, u64)>,
+    /// Path hashes and encoded offsets, sorted by hash, empty until read with [`EncodedIndex::read_path_hashes`]
+    pub path_hashes: Vec<(u64, i32)>,
+    pub encoded_entries: Vec<u8>,
+    pub non_encoded_entries: Vec<Header>,
+}
+
+impl EncodedIndex {
+    /// Read the encoded index, the reader has to be positioned after the entry count of the index
+    fn read<R: Read + Seek>(reader: &mut R, footer: &Footer) -> Result<Self, PakError> {
+        let path_hash_seed = reader.read_u64::<LE>()?;
+
+        let read_location = |reader: &mut R| -> Result<_, PakError> {
+            if reader.read_u32::<LE>()? == 0 {
+                return Ok(None);
+            }
+            let offset = reader.read_u64::<LE>()?;
+            let size = reader.read_u64::<LE>()?;
+            // skip hash
+            reader.seek(SeekFrom::Current(20))?;
+            Ok(Some((offset, size)))
+        };
+
+        let path_hash_index = read_location(reader)?;
+        let full_directory_index = read_location(reader)?;
+
+        // don't trust the sizes and counts for preallocation
+        let encoded_size = reader.read_u32::<LE>()? as u64;
+        let mut encoded_entries = Vec::new();
+        reader
+            .by_ref()
+            .take(encoded_size)
+            .read_to_end(&mut encoded_entries)?;
+        if encoded_entries.len() as u64 != encoded_size {
+            return Err(PakError::pak_invalid());
+        }
+
+        let non_encoded_count = reader.read_u32::<LE>()?;
+        let mut non_encoded_entries = Vec::new();
+        for _ in 0..non_encoded_count {
+            non_encoded_entries.push(Header::read(
+                reader,
+                footer.pak_version,
+                &footer.compression_methods,
+            )?);
+        }
+
+        Ok(EncodedIndex {
+            path_hash_seed,
+            path_hash_index,
+            full_directory_index,
+            path_hashes: Vec::new(),
+            encoded_entries,
+            non_encoded_entries,
+        })
+    }
+
+    /// Read the path hash index, returns `false` if the pak doesn't have one
+    pub(crate) fn read_path_hashes<R: Read + Seek>(
+        &mut self,
+        reader: &mut R,
+    ) -> Result<bool, PakError> {
+        let Some((offset, _)) = self.path_hash_index else {
+            return Ok(false);
+        };
+
+        reader.seek(SeekFrom::Start(offset))?;
+        let count = reader.read_u32::<LE>()?;
+        let mut path_hashes = Vec::new();
+        for _ in 0..count {
+            path_hashes.push((reader.read_u64::<LE>()?, reader.read_i32::<LE>()?));
+        }
+        // the pruned directory index which follows is not needed
+
+        path_hashes.sort_unstable_by_key(|(hash, _)| *hash);
+        self.path_hashes = path_hashes;
+        Ok(true)
+    }
+
+    /// Find the encoded offset of an entry through the path hash index
+    pub(crate) fn find_offset(&self, name: &str, pak_version: PakVersion) -> Option<i32> {
+        let hash = hash_path(name, self.path_hash_seed, pak_version);
+        self.path_hashes
+            .binary_search_by_key(&hash, |(hash, _)| *hash)
+            .ok()
+            .map(|i| self.path_hashes[i].1)
+    }
+
+    /// Decode an entry, negative offsets refer to entries which could not be encoded
+    pub(crate) fn get_entry(
+        &self,
+        offset: i32,
+        pak_version: PakVersion,
+        compression: &CompressionMethods,
+    ) -> Result<Header, PakError> {
+        if offset < 0 {
+            return self
+                .non_encoded_entries
+                .get((-(offset as i64) - 1) as usize)
+                .cloned()
+                .ok_or_else(PakError::pak_invalid);
+        }
+
+        let data = self
+            .encoded_entries
+            .get(offset as usize..)
+            .ok_or_else(PakError::pak_invalid)?;
+        Header::read_encoded(&mut Cursor::new(data), pak_version, compression)
+    }
+
+    /// Read the full directory index and decode all entries
+    pub(crate) fn read_full_directory<R: Read + Seek>(
+        &self,
+        reader: &mut R,
+        pak_version: PakVersion,
+        compression: &CompressionMethods,
+    ) -> Result<Vec<(String, Header)>, PakError> {
+        let (offset, _) = self
+            .full_directory_index
+            .ok_or_else(PakError::pak_invalid)?;
+        reader.seek(SeekFrom::Start(offset))?;
+
+        let mut entries = Vec::new();
+        let directory_count = reader.read_u32::<LE>()? as usize;
+        for _ in 0..directory_count {
+            let directory_name = read_path(reader)?;
+            let directory_name = directory_name.strip_prefix('/').unwrap_or(&directory_name);
+
+            let file_count = reader.read_u32::<LE>()? as usize;
+            for _ in 0..file_count {
+                let file_name = read_path(reader)?;
+                let encoded_offset = reader.read_i32::<LE>()?;
+
+                let path = format!("{directory_name}{file_name}");
+                entries.push((
+                    path,
+                    self.get_entry(encoded_offset, pak_version, compression)?,
+                ));
+            }
+        }
+
+        Ok(entries)
+    }
+}
+
+/// Hash of an entry path in the path hash index, paths are relative to the mount point
+pub(crate) fn hash_path(path: &str, seed: u64, pak_version: PakVersion) -> u64 {
+    // the offset basis and prime of FNV-1a were swapped before the bug fix version
+    let (offset_basis, prime) = match pak_version >= PakVersion::Fnv64BugFix {
+        true => (0xcbf29ce484222325u64, 0x00000100000001b3u64),
+        false => (0x00000100000001b3, 0xcbf29ce484222325),
+    };
+
+    path.to_lowercase()
+        .encode_utf16()
+        .flat_map(u16::to_le_bytes)
+        .fold(offset_basis.wrapping_add(seed), |hash, byte| {
+            (hash ^ byte as u64).wrapping_mul(prime)
+        })
 }
 
 #[derive(Debug)]
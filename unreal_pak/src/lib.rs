@@ -18,21 +18,40 @@ File parts:
 //! Supports both reading and writing and aims to support all pak versions.
 //! Encrytion is currently unsupported
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary_impls;
+pub mod chunk;
 pub mod compression;
+pub mod delta;
 mod entry;
 pub mod error;
+pub mod game_profile;
 mod header;
 mod index;
+pub mod manifest;
+pub mod mount_point;
 pub mod pakmemory;
 pub mod pakreader;
 pub mod pakversion;
 pub mod pakwriter;
+pub mod parallel;
+pub mod path_encoding;
+pub mod repack;
 
+pub use delta::PakDelta;
+pub use entry::EntryMetadata;
+pub use game_profile::GameProfile;
+pub use manifest::{HashManifest, PakSignature};
 pub use pakmemory::PakMemory;
-pub use pakreader::PakReader;
+pub use pakreader::{PakDiff, PakReader, SalvagedEntry};
 pub use pakwriter::PakWriter;
+pub use parallel::ParallelPakWriter;
+pub use path_encoding::PathEncoding;
+pub use repack::repack;
+pub use unreal_helpers::atomic_file;
+pub use unreal_helpers::AtomicFile;
 
-pub use compression::Compression;
+pub use compression::{Compression, CompressionOptions};
 pub use error::PakError;
 
 pub(crate) const PAK_MAGIC: u32 = u32::from_be_bytes([0xE1, 0x12, 0x6F, 0x5A]);
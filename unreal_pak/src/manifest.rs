@@ -0,0 +1,313 @@
+//! Hash manifests and signature files for paks
+//!
+//! Some games verify their paks before mounting them, either against a manifest listing
+//! the expected hash of every pak or against a `.sig` file stored next to each pak.
+//! After rebuilding paks these need to be regenerated so that the game accepts them.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use crate::error::PakError;
+
+/// Magic of pak signature files
+const SIGNATURE_MAGIC: u32 = 0x73832daa;
+/// Pak signature file version written by the engine
+const SIGNATURE_VERSION: u32 = 1;
+/// Size of the chunks hashed in pak signature files
+pub const SIGNATURE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Hash algorithm used in a manifest
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum HashAlgorithm {
+    /// SHA-1, 20 byte hashes
+    Sha1,
+    /// SHA-256, 32 byte hashes
+    Sha256,
+}
+
+impl HashAlgorithm {
+    /// Size of hashes created by this algorithm in bytes
+    pub fn hash_size(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha1 => 20,
+            HashAlgorithm::Sha256 => 32,
+        }
+    }
+
+    /// Hash all data of a reader
+    pub fn hash_reader<R: Read>(&self, reader: &mut R) -> Result<Vec<u8>, PakError> {
+        match self {
+            HashAlgorithm::Sha1 => hash_reader::<Sha1, R>(reader),
+            HashAlgorithm::Sha256 => hash_reader::<Sha256, R>(reader),
+        }
+    }
+}
+
+fn hash_reader<D: Digest, R: Read>(reader: &mut R) -> Result<Vec<u8>, PakError> {
+    let mut hasher = D::new();
+    let mut buf = vec![0u8; SIGNATURE_CHUNK_SIZE];
+    loop {
+        let read = reader.read(&mut buf)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buf[..read]);
+    }
+    Ok(hasher.finalize().to_vec())
+}
+
+/// Manifest entry
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ManifestEntry {
+    /// File name, relative to the manifest
+    pub file_name: String,
+    /// Hash of the file
+    pub hash: Vec<u8>,
+}
+
+/// Manifest of pak hashes
+///
+/// Manifests are written in the format used by `sha1sum`/`sha256sum`,
+/// one `<hex hash>  <file name>` line per file.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct HashManifest {
+    /// Hash algorithm
+    pub algorithm: HashAlgorithm,
+    /// Entries
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl HashManifest {
+    /// Create a new empty manifest
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        HashManifest {
+            algorithm,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Hash a file and add it to the manifest, replacing an existing entry of the same name
+    pub fn add_file(&mut self, file_name: String, path: &Path) -> Result<(), PakError> {
+        let hash = self
+            .algorithm
+            .hash_reader(&mut BufReader::new(File::open(path)?))?;
+        self.set_hash(file_name, hash);
+        Ok(())
+    }
+
+    /// Set the hash of a file, replacing an existing entry of the same name
+    pub fn set_hash(&mut self, file_name: String, hash: Vec<u8>) {
+        match self.entries.iter_mut().find(|e| e.file_name == file_name) {
+            Some(entry) => entry.hash = hash,
+            None => self.entries.push(ManifestEntry { file_name, hash }),
+        }
+    }
+
+    /// Get the hash of a file
+    pub fn get_hash(&self, file_name: &str) -> Option<&[u8]> {
+        self.entries
+            .iter()
+            .find(|e| e.file_name == file_name)
+            .map(|e| e.hash.as_slice())
+    }
+
+    /// Create a manifest of all paks in a directory
+    pub fn from_directory(algorithm: HashAlgorithm, path: &Path) -> Result<Self, PakError> {
+        let mut paks = std::fs::read_dir(path)?
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().extension().map(|e| e == "pak").unwrap_or(false))
+            .map(|e| e.file_name().to_string_lossy().into_owned())
+            .collect::<Vec<_>>();
+        paks.sort();
+
+        let mut manifest = HashManifest::new(algorithm);
+        for pak in paks {
+            let pak_path = path.join(&pak);
+            manifest.add_file(pak, &pak_path)?;
+        }
+        Ok(manifest)
+    }
+
+    /// Read a manifest
+    pub fn read<R: Read>(algorithm: HashAlgorithm, reader: &mut R) -> Result<Self, PakError> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+
+        let mut manifest = HashManifest::new(algorithm);
+        for line in data.lines().map(|e| e.trim()).filter(|e| !e.is_empty()) {
+            let (hash, file_name) = line.split_once(' ').ok_or_else(PakError::pak_invalid)?;
+            // binary mode marker written by sha*sum
+            let file_name = file_name.trim_start();
+            let file_name = file_name.strip_prefix('*').unwrap_or(file_name);
+
+            let hash = decode_hex(hash).ok_or_else(PakError::pak_invalid)?;
+            if hash.len() != algorithm.hash_size() {
+                return Err(PakError::pak_invalid());
+            }
+
+            manifest.entries.push(ManifestEntry {
+                file_name: file_name.to_string(),
+                hash,
+            });
+        }
+        Ok(manifest)
+    }
+
+    /// Write this manifest
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), PakError> {
+        for entry in &self.entries {
+            writeln!(writer, "{}  {}", encode_hex(&entry.hash), entry.file_name)?;
+        }
+        Ok(())
+    }
+}
+
+fn encode_hex(data: &[u8]) -> String {
+    data.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// Decode a hex string, returns `None` if it isn't valid hex
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+/// Pak signature file (`.sig`)
+///
+/// Contains a CRC32 for every 64 KiB chunk of the pak and an encrypted SHA-1 hash of those CRCs.
+/// The encryption depends on the game's signing key, so it is done by a caller provided signer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct PakSignature {
+    /// Encrypted principal hash
+    pub encrypted_hash: Vec<u8>,
+    /// CRC32 of every chunk
+    pub chunk_hashes: Vec<u32>,
+}
+
+impl PakSignature {
+    /// Compute the chunk hashes of a pak, the encrypted hash is left empty
+    pub fn from_pak<R: Read + Seek>(reader: &mut R) -> Result<Self, PakError> {
+        reader.seek(SeekFrom::Start(0))?;
+
+        let mut chunk_hashes = Vec::new();
+        let mut buf = vec![0u8; SIGNATURE_CHUNK_SIZE];
+        loop {
+            let read = read_chunk(reader, &mut buf)?;
+            if read == 0 {
+                break;
+            }
+            chunk_hashes.push(crc32fast::hash(&buf[..read]));
+        }
+
+        Ok(PakSignature {
+            encrypted_hash: Vec::new(),
+            chunk_hashes,
+        })
+    }
+
+    /// SHA-1 hash of the chunk hashes, this is what gets encrypted
+    pub fn principal_hash(&self) -> [u8; 20] {
+        let mut hasher = Sha1::new();
+        for chunk_hash in &self.chunk_hashes {
+            hasher.update(chunk_hash.to_le_bytes());
+        }
+        hasher.finalize().into()
+    }
+
+    /// Set the encrypted hash using a signer which encrypts the principal hash
+    pub fn sign<F>(&mut self, signer: F) -> Result<(), PakError>
+    where
+        F: FnOnce(&[u8; 20]) -> Result<Vec<u8>, PakError>,
+    {
+        self.encrypted_hash = signer(&self.principal_hash())?;
+        Ok(())
+    }
+
+    /// Read a signature file
+    ///
+    /// The lengths stored in the file are checked against the size of the stream before allocating.
+    pub fn read<R: Read + Seek>(reader: &mut R) -> Result<Self, PakError> {
+        let start = reader.stream_position()?;
+        let end = reader.seek(SeekFrom::End(0))?;
+        reader.seek(SeekFrom::Start(start))?;
+        let mut remaining = end.saturating_sub(start);
+
+        // magic, version and both lengths
+        remaining = remaining
+            .checked_sub(16)
+            .ok_or_else(PakError::pak_invalid)?;
+
+        if reader.read_u32::<LE>()? != SIGNATURE_MAGIC {
+            return Err(PakError::pak_invalid());
+        }
+        let _version = reader.read_u32::<LE>()?;
+
+        let encrypted_hash_len = read_len(reader, &mut remaining, 1)?;
+        let mut encrypted_hash = vec![0u8; encrypted_hash_len];
+        reader.read_exact(&mut encrypted_hash)?;
+
+        let chunk_count = read_len(reader, &mut remaining, 4)?;
+        let mut chunk_hashes = Vec::with_capacity(chunk_count);
+        for _ in 0..chunk_count {
+            chunk_hashes.push(reader.read_u32::<LE>()?);
+        }
+
+        Ok(PakSignature {
+            encrypted_hash,
+            chunk_hashes,
+        })
+    }
+
+    /// Write a signature file
+    pub fn write<W: Write>(&self, writer: &mut W) -> Result<(), PakError> {
+        writer.write_u32::<LE>(SIGNATURE_MAGIC)?;
+        writer.write_u32::<LE>(SIGNATURE_VERSION)?;
+
+        writer.write_i32::<LE>(self.encrypted_hash.len() as i32)?;
+        writer.write_all(&self.encrypted_hash)?;
+
+        writer.write_i32::<LE>(self.chunk_hashes.len() as i32)?;
+        for chunk_hash in &self.chunk_hashes {
+            writer.write_u32::<LE>(*chunk_hash)?;
+        }
+        Ok(())
+    }
+}
+
+/// Read the element count of an array in a signature file
+///
+/// Fails if the count is negative or the array wouldn't fit into the `remaining` bytes of the stream.
+fn read_len<R: Read>(
+    reader: &mut R,
+    remaining: &mut u64,
+    element_size: u64,
+) -> Result<usize, PakError> {
+    let len = u64::try_from(reader.read_i32::<LE>()?).map_err(|_| PakError::pak_invalid())?;
+    *remaining = remaining
+        .checked_sub(len * element_size)
+        .ok_or_else(PakError::pak_invalid)?;
+    Ok(len as usize)
+}
+
+/// Fill a buffer as far as possible, returns the amount of bytes read
+fn read_chunk<R: Read>(reader: &mut R, buf: &mut [u8]) -> io::Result<usize> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..])? {
+            0 => break,
+            n => read += n,
+        }
+    }
+    Ok(read)
+}
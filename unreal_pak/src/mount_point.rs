@@ -0,0 +1,116 @@
+//! Mount point and path helpers
+//!
+//! Entry names in a pak are relative to its mount point, which is usually relative to the
+//! game's `Binaries/<Platform>` directory, e.g. `../../../GameName/Content/`.
+//! These helpers turn entry names into paths relative to the engine root
+//! (`GameName/Content/Maps/Planet.umap`) and engine object paths (`/Game/Maps/Planet`) and back.
+
+/// Mount point used by most paks, the engine root
+pub const DEFAULT_MOUNT_POINT: &str = "../../../";
+
+/// Canonicalize a path
+///
+/// Backslashes are turned into slashes, `.` and empty components are removed and `..` components are resolved.
+/// `..` components which would leave the root are dropped, so `../../../GameName/` becomes `GameName`.
+pub fn canonicalize_path(path: &str) -> String {
+    let mut components = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    components.join("/")
+}
+
+/// Normalize a mount point to a path relative to the engine root
+///
+/// The result is empty for the engine root and ends with `/` otherwise,
+/// so entry names can be appended to it.
+pub fn normalize_mount_point(mount_point: &str) -> String {
+    let mut mount_point = canonicalize_path(mount_point);
+    if !mount_point.is_empty() {
+        mount_point.push('/');
+    }
+    mount_point
+}
+
+/// Get the path of an entry relative to the engine root
+///
+/// E.g. `Maps/Planet.umap` mounted at `../../../GameName/Content/` is `GameName/Content/Maps/Planet.umap`.
+pub fn entry_to_engine_path(mount_point: &str, entry_name: &str) -> String {
+    canonicalize_path(&(normalize_mount_point(mount_point) + entry_name))
+}
+
+/// Get the entry name of a path relative to the engine root
+///
+/// Returns `None` if the path is not below the mount point.
+pub fn engine_path_to_entry(mount_point: &str, engine_path: &str) -> Option<String> {
+    let engine_path = canonicalize_path(engine_path);
+    strip_prefix_ignore_case(&engine_path, &normalize_mount_point(mount_point))
+        .map(|e| e.to_string())
+}
+
+/// Get the engine object path of an entry
+///
+/// E.g. `Maps/Planet.umap` mounted at `../../../GameName/Content/` is `/Game/Maps/Planet`.
+/// Content of the game and of the engine is supported, `None` is returned for all other entries.
+/// The extension is removed, so the `.uasset`, `.uexp` and `.ubulk` of an asset have the same object path.
+pub fn entry_to_object_path(
+    game_name: &str,
+    mount_point: &str,
+    entry_name: &str,
+) -> Option<String> {
+    let engine_path = entry_to_engine_path(mount_point, entry_name);
+
+    let (root, path) = [(game_name, "Game"), ("Engine", "Engine")]
+        .into_iter()
+        .find_map(|(directory, root)| {
+            let path = strip_prefix_ignore_case(&engine_path, directory)?;
+            let path = strip_prefix_ignore_case(path, "/Content/")?;
+            Some((root, path))
+        })?;
+
+    Some(format!("/{root}/{}", strip_extension(path)))
+}
+
+/// Get the entry name of an engine object path
+///
+/// E.g. `/Game/Maps/Planet` with the extension `umap` mounted at `../../../GameName/Content/` is `Maps/Planet.umap`.
+/// An object name after the package path, like in `/Game/Maps/Planet.Planet`, is ignored.
+/// Returns `None` if the object path is not in `/Game/` or `/Engine/` or is not below the mount point.
+pub fn object_path_to_entry(
+    game_name: &str,
+    mount_point: &str,
+    object_path: &str,
+    extension: &str,
+) -> Option<String> {
+    let (directory, path) = match strip_prefix_ignore_case(object_path, "/Game/") {
+        Some(path) => (game_name, path),
+        None => ("Engine", strip_prefix_ignore_case(object_path, "/Engine/")?),
+    };
+
+    engine_path_to_entry(
+        mount_point,
+        &format!("{directory}/Content/{}.{extension}", strip_extension(path)),
+    )
+}
+
+/// Strip everything after the last `.` of the last path component
+fn strip_extension(path: &str) -> &str {
+    match path.rfind(['.', '/']) {
+        Some(index) if path[index..].starts_with('.') => &path[..index],
+        _ => path,
+    }
+}
+
+/// Strip a prefix from a string, ignoring ASCII case like the engine does for paths
+fn strip_prefix_ignore_case<'a>(string: &'a str, prefix: &str) -> Option<&'a str> {
+    match string.get(..prefix.len()) {
+        Some(start) if start.eq_ignore_ascii_case(prefix) => Some(&string[prefix.len()..]),
+        _ => None,
+    }
+}
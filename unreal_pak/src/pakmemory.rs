@@ -3,11 +3,13 @@
 use std::collections::BTreeMap;
 use std::io::{Read, Seek, Write};
 
-use crate::compression::CompressionMethods;
-use crate::entry::{read_entry, write_entry};
+use crate::compression::{CompressionMethods, CompressionOptions};
+use crate::entry::{read_entry, write_entry, EntryMetadata};
 use crate::error::PakError;
 use crate::index::{random_path_hash_seed, Footer, Index};
+use crate::mount_point::DEFAULT_MOUNT_POINT;
 use crate::pakversion::PakVersion;
+use crate::path_encoding::PathEncoding;
 
 /// A Unreal Pak file which keeps all of it's data in memory.
 /// It allows reading and writing of the same entries before comitting the file to disk.
@@ -17,10 +19,14 @@ pub struct PakMemory {
     pub pak_version: PakVersion,
     /// mount point (Unreal stuff)
     pub mount_point: String,
+    /// encoding the mount point and entry names are written with
+    pub path_encoding: PathEncoding,
     /// the compression method preferred for this file
     compression: CompressionMethods,
     /// the compression block size
     pub block_size: u32,
+    /// guid of the key the pak is encrypted with, all zeros for unencrypted paks
+    pub encryption_key_guid: [u8; 0x10],
     entries: BTreeMap<String, Vec<u8>>,
 }
 
@@ -29,9 +35,11 @@ impl PakMemory {
     pub fn new(pak_version: PakVersion) -> Self {
         Self {
             pak_version,
-            mount_point: "../../../".to_owned(),
+            mount_point: DEFAULT_MOUNT_POINT.to_owned(),
+            path_encoding: PathEncoding::default(),
             compression: CompressionMethods::default(),
             block_size: 0x010000,
+            encryption_key_guid: [0u8; 0x10],
             entries: BTreeMap::new(),
         }
     }
@@ -43,6 +51,7 @@ impl PakMemory {
         self.pak_version = index.footer.pak_version;
         self.mount_point = index.mount_point.clone();
         self.compression = index.footer.compression_methods;
+        self.encryption_key_guid = index.footer.encryption_key_guid.unwrap_or_default();
 
         for (name, header) in index.entries {
             self.entries.insert(
@@ -95,9 +104,10 @@ impl PakMemory {
                 writer,
                 self.pak_version,
                 data,
-                true,
+                &EntryMetadata::new(true),
                 &self.compression,
                 self.block_size,
+                &CompressionOptions::default(),
             )?;
             written_entries.push((name.clone(), header));
         }
@@ -110,11 +120,12 @@ impl PakMemory {
             index_hash: [0u8; 20],
             compression_methods: self.compression,
             index_encrypted: Some(false),
-            encryption_key_guid: Some([0u8; 0x10]),
+            encryption_key_guid: Some(self.encryption_key_guid),
         };
 
         let index = Index {
             mount_point: self.mount_point.clone(),
+            path_encoding: self.path_encoding,
             path_hash_seed: Some(random_path_hash_seed()),
             entries: written_entries,
             footer,
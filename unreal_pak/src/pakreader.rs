@@ -1,15 +1,21 @@
 //! PakFile data structure for reading large pak files
 
 use std::collections::BTreeMap;
-use std::io::{Read, Seek};
+use std::io::{self, Read, Seek, SeekFrom};
+
+use sha1::{Digest, Sha1};
 
 use crate::compression::CompressionMethods;
-use crate::entry::read_entry;
+use crate::entry::{read_entry, EntryMetadata};
 use crate::error::PakError;
 use crate::header::Header;
-use crate::index::Index;
+use crate::index::{EncodedIndex, Footer, Index};
+use crate::mount_point;
 use crate::pakversion::PakVersion;
 
+/// Largest entry which is checked against its hash while scanning a pak anywhere but right after the previous entry
+const SALVAGE_SCAN_HASH_LIMIT: u64 = 0x1000000;
+
 /// An Unreal pak file reader with it's data kept on disk and only read on demand.
 #[derive(Debug)]
 pub struct PakReader<R>
@@ -21,7 +27,10 @@ where
     /// mount point (Unreal stuff)
     pub mount_point: String,
     compression: CompressionMethods,
+    encryption_key_guid: Option<[u8; 0x10]>,
     entries: BTreeMap<String, Header>,
+    /// entries of a v10+ pak which are decoded on demand, set by `load_index_lazy`
+    encoded_index: Option<EncodedIndex>,
     reader: R,
 }
 
@@ -37,7 +46,9 @@ where
             pak_version: PakVersion::Invalid,
             mount_point: "".to_owned(),
             compression: Default::default(),
+            encryption_key_guid: None,
             entries: BTreeMap::new(),
+            encoded_index: None,
             reader,
         }
     }
@@ -45,10 +56,12 @@ where
     /// Load the entry info contained in the footer into memory to start reading individual entries.
     pub fn load_index(&mut self) -> Result<(), PakError> {
         let index = Index::read(&mut self.reader)?;
+        self.encoded_index = None;
 
         self.pak_version = index.footer.pak_version;
         self.mount_point = index.mount_point.clone();
         self.compression = index.footer.compression_methods;
+        self.encryption_key_guid = index.footer.encryption_key_guid;
 
         for (name, header) in index.entries {
             self.entries.insert(name, header);
@@ -57,7 +70,227 @@ where
         Ok(())
     }
 
+    /// Load the entry info without decoding the directory of v10+ paks.
+    ///
+    /// Entries are looked up through the path hash index when they are accessed,
+    /// which is much faster and uses less memory for paks with many entries.
+    /// Entry names are not known in this mode, so [`PakReader::get_entry_names`] and [`PakReader::iter`]
+    /// only see entries after calling [`PakReader::preload_full_index`].
+    ///
+    /// Older paks don't have a path hash index, their index is loaded fully like with [`PakReader::load_index`].
+    pub fn load_index_lazy(&mut self) -> Result<(), PakError> {
+        let (index, encoded_index) = Index::read_lazy(&mut self.reader)?;
+        self.encoded_index = None;
+
+        self.pak_version = index.footer.pak_version;
+        self.mount_point = index.mount_point.clone();
+        self.compression = index.footer.compression_methods;
+        self.encryption_key_guid = index.footer.encryption_key_guid;
+
+        for (name, header) in index.entries {
+            self.entries.insert(name, header);
+        }
+
+        if let Some(mut encoded_index) = encoded_index {
+            let has_path_hashes = encoded_index.read_path_hashes(&mut self.reader)?;
+            self.encoded_index = Some(encoded_index);
+            if !has_path_hashes {
+                self.preload_full_index()?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load as many entries as possible from a pak with a damaged or truncated index,
+    /// e.g. from an interrupted download.
+    ///
+    /// If the index can be read, its entries are loaded and checked against the hashes in front of their data.
+    /// Otherwise the pak is scanned for entry headers, which is slow for large paks.
+    /// Entry names and the mount point are only stored in the index, so entries found by scanning
+    /// are named `salvaged/<offset>` with the offset as 16 hex digits.
+    /// `pak_version` is only used if the footer can't be read.
+    ///
+    /// Entries which don't match their hash or which are cut off are returned with [`SalvagedEntry::verified`] unset,
+    /// they can still be read, but their data may be corrupted.
+    /// The first entry after damaged data is only found if it is at most 16 MiB large.
+    pub fn load_index_salvage(
+        &mut self,
+        pak_version: PakVersion,
+    ) -> Result<Vec<SalvagedEntry>, PakError> {
+        let file_len = self.reader.seek(SeekFrom::End(0))?;
+        self.entries.clear();
+        self.encoded_index = None;
+
+        let footer = Footer::read(&mut self.reader)
+            .ok()
+            .filter(|e| e.index_offset <= file_len);
+        let data_end = match &footer {
+            Some(footer) => {
+                self.pak_version = footer.pak_version;
+                self.compression = footer.compression_methods;
+                self.encryption_key_guid = footer.encryption_key_guid;
+                footer.index_offset
+            }
+            None => {
+                self.pak_version = pak_version;
+                self.compression = CompressionMethods::zlib();
+                self.encryption_key_guid = None;
+                file_len
+            }
+        };
+
+        let mut salvaged = Vec::new();
+
+        let index = match footer {
+            Some(_) => Index::read(&mut self.reader).ok(),
+            None => None,
+        };
+        if let Some(index) = index {
+            self.mount_point = index.mount_point;
+            for (name, header) in index.entries {
+                let verified = match self.scan_entry(header.offset, data_end, u64::MAX)? {
+                    Some(entry) => {
+                        entry.verified == Some(true)
+                            && entry.header.compressed_size == header.compressed_size
+                    }
+                    None => false,
+                };
+                salvaged.push(SalvagedEntry {
+                    name: name.clone(),
+                    offset: header.offset,
+                    in_index: true,
+                    verified,
+                });
+                self.entries.insert(name, header);
+            }
+            return Ok(salvaged);
+        }
+
+        // entries are stored back to back, a header right after the previous entry is kept
+        // even if its data is damaged, anywhere else only headers of intact entries are accepted
+        let mut next_entry = 0;
+        let mut position = 0;
+        while position < data_end {
+            // headers anywhere else are mostly random data, hashing them all would take forever
+            let max_hash_size = match position == next_entry {
+                true => u64::MAX,
+                false => SALVAGE_SCAN_HASH_LIMIT,
+            };
+            let entry = match self.scan_entry(position, data_end, max_hash_size)? {
+                Some(entry) if position == next_entry || entry.verified == Some(true) => entry,
+                _ => {
+                    position += 1;
+                    continue;
+                }
+            };
+
+            let name = format!("salvaged/{position:016x}");
+            salvaged.push(SalvagedEntry {
+                name: name.clone(),
+                offset: position,
+                in_index: false,
+                verified: entry.verified == Some(true),
+            });
+            self.entries.insert(name, entry.header);
+
+            // nothing can follow an entry which is cut off
+            if entry.verified.is_none() {
+                break;
+            }
+            position = entry.end;
+            next_entry = entry.end;
+        }
+
+        Ok(salvaged)
+    }
+
+    /// Read the header in front of the data of an entry and check the data against its hash
+    ///
+    /// Returns `None` if there is no entry header at `position`
+    /// or if the data of the entry is larger than `max_hash_size`.
+    fn scan_entry(
+        &mut self,
+        position: u64,
+        data_end: u64,
+        max_hash_size: u64,
+    ) -> Result<Option<ScannedEntry>, PakError> {
+        self.reader.seek(SeekFrom::Start(position))?;
+        // data which isn't an entry header may run past the end of the pak
+        let mut header = match Header::scan(
+            &mut self.reader,
+            self.pak_version,
+            &self.compression,
+            position,
+        ) {
+            Ok(Some(header)) => header,
+            _ => return Ok(None),
+        };
+        header.offset = position;
+        let data_start = self.reader.stream_position()?;
+
+        if header.compressed_size > data_end.saturating_sub(data_start) {
+            return Ok(Some(ScannedEntry {
+                header,
+                end: data_end,
+                verified: None,
+            }));
+        }
+
+        if header.compressed_size > max_hash_size {
+            return Ok(None);
+        }
+
+        let mut hasher = Sha1::new();
+        io::copy(
+            &mut (&mut self.reader).take(header.compressed_size),
+            &mut hasher,
+        )?;
+        let hash: [u8; 20] = hasher.finalize().into();
+
+        Ok(Some(ScannedEntry {
+            end: data_start + header.compressed_size,
+            verified: Some(hash == header.hash),
+            header,
+        }))
+    }
+
+    /// Decode all entries of a pak loaded with [`PakReader::load_index_lazy`].
+    ///
+    /// Does nothing if the full index is already loaded.
+    pub fn preload_full_index(&mut self) -> Result<(), PakError> {
+        let Some(encoded_index) = self.encoded_index.take() else {
+            return Ok(());
+        };
+
+        let entries = encoded_index.read_full_directory(
+            &mut self.reader,
+            self.pak_version,
+            &self.compression,
+        )?;
+        for (name, header) in entries {
+            self.entries.insert(name, header);
+        }
+
+        Ok(())
+    }
+
+    /// Returns `true` if all entries have been decoded and their names are known.
+    pub fn is_full_index_loaded(&self) -> bool {
+        self.encoded_index.is_none()
+    }
+
+    /// Returns the guid of the key the pak is encrypted with, all zeros for unencrypted paks.
+    ///
+    /// Only paks since [`PakVersion::EncryptionKeyGuid`] store this.
+    pub fn get_encryption_key_guid(&self) -> Option<[u8; 0x10]> {
+        self.encryption_key_guid
+    }
+
     /// Returns the names of all entries which have been found.
+    ///
+    /// Entries of an index loaded with [`PakReader::load_index_lazy`] are only returned
+    /// after calling [`PakReader::preload_full_index`].
     pub fn get_entry_names(&self) -> Vec<&String> {
         self.entries.keys().collect()
     }
@@ -65,15 +298,84 @@ where
     /// Checks if the pak file contains an entry with the given name
     pub fn contains_entry(&self, name: &String) -> bool {
         self.entries.contains_key(name)
+            || self
+                .encoded_index
+                .as_ref()
+                .and_then(|e| e.find_offset(name, self.pak_version))
+                .is_some()
     }
 
-    /// Reads an entry from the pak on disk into memory and returns it's data.
-    pub fn read_entry(&mut self, name: &String) -> Result<Vec<u8>, PakError> {
-        let header = self
+    /// Get the engine object path of an entry, e.g. `/Game/Maps/Planet` for `Maps/Planet.umap`
+    /// in a pak mounted at `../../../GameName/Content/`.
+    ///
+    /// See [`mount_point::entry_to_object_path`].
+    pub fn get_object_path(&self, game_name: &str, name: &str) -> Option<String> {
+        mount_point::entry_to_object_path(game_name, &self.mount_point, name)
+    }
+
+    /// Returns the object paths of all .uasset and .umap entries.
+    ///
+    /// An index loaded with [`PakReader::load_index_lazy`] is fully decoded.
+    pub fn get_object_paths(&mut self, game_name: &str) -> Result<Vec<String>, PakError> {
+        self.preload_full_index()?;
+
+        Ok(self
             .entries
-            .get(name)
-            .ok_or_else(|| PakError::entry_not_found(name.clone()))?;
-        self.read_entry_at_offset(header.offset)
+            .keys()
+            .filter(|e| e.ends_with(".uasset") || e.ends_with(".umap"))
+            .filter_map(|e| self.get_object_path(game_name, e))
+            .collect())
+    }
+
+    /// Find the name of the entry with an extension for an engine object path.
+    ///
+    /// Returns `None` if the pak doesn't contain the entry.
+    pub fn find_object_entry(
+        &self,
+        game_name: &str,
+        object_path: &str,
+        extension: &str,
+    ) -> Option<String> {
+        mount_point::object_path_to_entry(game_name, &self.mount_point, object_path, extension)
+            .filter(|e| self.contains_entry(e))
+    }
+
+    /// Reads an entry from the pak on disk into memory and returns it's data.
+    pub fn read_entry(&mut self, name: &str) -> Result<Vec<u8>, PakError> {
+        let offset = self
+            .find_entry_header(name)?
+            .ok_or_else(|| PakError::entry_not_found(name.to_string()))?
+            .offset;
+        self.read_entry_at_offset(offset)
+    }
+
+    /// Get the compression, flags and timestamp of an entry
+    pub fn get_entry_metadata(&self, name: &str) -> Option<EntryMetadata> {
+        self.get_entry_header(name)
+            .map(|e| EntryMetadata::from_header(&e))
+    }
+
+    pub(crate) fn get_entry_header(&self, name: &str) -> Option<Header> {
+        self.find_entry_header(name).ok().flatten()
+    }
+
+    /// Find the header of an entry, decoding it if the index was loaded lazily
+    fn find_entry_header(&self, name: &str) -> Result<Option<Header>, PakError> {
+        if let Some(header) = self.entries.get(name) {
+            return Ok(Some(header.clone()));
+        }
+
+        let Some((encoded_index, offset)) = self
+            .encoded_index
+            .as_ref()
+            .and_then(|e| Some((e, e.find_offset(name, self.pak_version)?)))
+        else {
+            return Ok(None);
+        };
+
+        encoded_index
+            .get_entry(offset, self.pak_version, &self.compression)
+            .map(Some)
     }
 
     fn read_entry_at_offset(&mut self, offset: u64) -> Result<Vec<u8>, PakError> {
@@ -85,6 +387,44 @@ where
         )
     }
 
+    /// Compare the entries of this pak against the entries of a baseline pak
+    ///
+    /// Entries are compared by the hashes and sizes stored in the indices, so no entry is read.
+    /// Because the hashes cover the stored data, entries with the same content but a different compression
+    /// are reported as changed. Both readers need to have their index loaded already,
+    /// indices loaded with [`PakReader::load_index_lazy`] are fully decoded.
+    pub fn diff_against<R2>(&mut self, baseline: &mut PakReader<R2>) -> Result<PakDiff, PakError>
+    where
+        R2: Read + Seek,
+    {
+        self.preload_full_index()?;
+        baseline.preload_full_index()?;
+
+        let mut diff = PakDiff::default();
+
+        for (name, header) in &self.entries {
+            match baseline.entries.get(name) {
+                None => diff.added.push(name.clone()),
+                Some(baseline_header)
+                    if header.hash != baseline_header.hash
+                        || header.decompressed_size != baseline_header.decompressed_size =>
+                {
+                    diff.changed.push(name.clone())
+                }
+                Some(_) => {}
+            }
+        }
+
+        diff.removed = baseline
+            .entries
+            .keys()
+            .filter(|e| !self.entries.contains_key(*e))
+            .cloned()
+            .collect();
+
+        Ok(diff)
+    }
+
     /// Iterate over the entries in the PakReader
     pub fn iter(&mut self) -> PakReaderIter<R> {
         PakReaderIter {
@@ -102,6 +442,55 @@ where
     }
 }
 
+/// Entry found by [`PakReader::load_index_salvage`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SalvagedEntry {
+    /// Name of the entry, `salvaged/<offset>` for entries which were found by scanning
+    pub name: String,
+    /// Offset of the header in front of the data of the entry
+    pub offset: u64,
+    /// Was the entry listed in the index
+    pub in_index: bool,
+    /// Does the data of the entry match its hash
+    pub verified: bool,
+}
+
+/// Entry header found while scanning a pak
+struct ScannedEntry {
+    header: Header,
+    /// End of the data of the entry
+    end: u64,
+    /// Does the data match the hash in the header, `None` if the data is cut off
+    verified: Option<bool>,
+}
+
+/// Difference between the entries of two paks, see [`PakReader::diff_against`]
+///
+/// All names are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PakDiff {
+    /// Entries which are not in the baseline
+    pub added: Vec<String>,
+    /// Entries which are only in the baseline
+    pub removed: Vec<String>,
+    /// Entries which are in both paks with different data
+    pub changed: Vec<String>,
+}
+
+impl PakDiff {
+    /// Returns `true` if both paks have the same entries with the same data
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+
+    /// Get the names of all added and changed entries, the entries which override the baseline
+    ///
+    /// Read them with [`PakReader::read_entry`] to only extract the modified entries.
+    pub fn modified(&self) -> impl Iterator<Item = &String> {
+        self.added.iter().chain(self.changed.iter())
+    }
+}
+
 /// An iterator over the entries of a PakReader
 pub struct PakReaderIter<'a, R>
 where
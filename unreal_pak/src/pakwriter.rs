@@ -1,14 +1,23 @@
 //! PakFile data structure for writing large pak files
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io::{Seek, Write};
+use std::path::Path;
 
-use crate::compression::CompressionMethods;
-use crate::entry::write_entry;
+use crate::atomic_file::AtomicFile;
+use crate::chunk::chunk_pak_name;
+use crate::compression::{CompressionMethods, CompressionOptions};
+use crate::entry::{write_encoded_entry, write_entry, EncodedEntry, EntryMetadata};
 use crate::error::PakError;
+use crate::game_profile::GameProfile;
+use crate::hash;
 use crate::header::Header;
 use crate::index::{random_path_hash_seed, Footer, Index};
+use crate::mount_point::DEFAULT_MOUNT_POINT;
 use crate::pakversion::PakVersion;
+use crate::parallel::ParallelPakWriterBuilder;
+use crate::path_encoding::PathEncoding;
+use crate::Compression;
 
 /// An Unreal pak file writer which allows incrementally writing data.
 /// Good for working with very large files, but it has restrictions when it
@@ -24,11 +33,21 @@ where
     pub pak_version: PakVersion,
     /// Mount point. Typically `../../../`.
     pub mount_point: String,
+    /// Encoding the mount point and entry names are written with
+    pub path_encoding: PathEncoding,
     /// Compression method preferred for this file
-    compression: CompressionMethods,
+    pub(crate) compression: CompressionMethods,
     /// Compression block size
     pub block_size: u32,
+    /// Compression level, dictionary and threading options
+    pub compression_options: CompressionOptions,
+    /// Guid of the key the pak is encrypted with, all zeros for unencrypted paks
+    pub encryption_key_guid: [u8; 0x10],
+    /// Write entries with identical content only once and let all of them reference the same data
+    pub deduplicate: bool,
     entries: BTreeMap<String, Header>,
+    /// Headers of already written data by hash of the uncompressed data and its metadata
+    written_data: HashMap<([u8; 20], EntryMetadata), Header>,
     writer: W,
 }
 
@@ -42,14 +61,48 @@ where
     pub fn new(writer: W, pak_version: PakVersion) -> Self {
         Self {
             pak_version,
-            mount_point: "../../../".to_owned(),
+            mount_point: DEFAULT_MOUNT_POINT.to_owned(),
+            path_encoding: PathEncoding::default(),
             compression: CompressionMethods::zlib(),
             block_size: 0x010000,
+            compression_options: CompressionOptions::default(),
+            encryption_key_guid: [0u8; 0x10],
+            deduplicate: false,
             entries: BTreeMap::new(),
+            written_data: HashMap::new(),
             writer,
         }
     }
 
+    /// Creates a new `PakWriter` which writes paks loadable by the game of `profile`
+    ///
+    /// The pak version is negotiated with [`GameProfile::negotiate_pak_version`],
+    /// the compression method, block size and mount point are taken from the profile.
+    pub fn for_game(writer: W, profile: &GameProfile) -> Result<Self, PakError> {
+        let mut pak_writer = Self::new(writer, profile.negotiate_pak_version()?);
+        pak_writer.set_compression(profile.compression)?;
+        pak_writer.block_size = profile.block_size;
+        pak_writer.mount_point = profile.mount_point.clone();
+        Ok(pak_writer)
+    }
+
+    /// Set the compression method used for compressed entries, defaults to [`Compression::zlib`]
+    ///
+    /// Has to be set before writing the first entry.
+    /// Unknown compression methods can't be written and are rejected.
+    pub fn set_compression(&mut self, compression: Compression) -> Result<(), PakError> {
+        if !self.entries.is_empty() || matches!(compression, Compression::None) {
+            return Err(PakError::configuration_invalid());
+        }
+        if matches!(compression, Compression::Unknown(_)) {
+            return Err(PakError::compression_unsupported(compression));
+        }
+
+        self.compression = CompressionMethods::default();
+        self.compression.0[0] = compression;
+        Ok(())
+    }
+
     /// Returns the names of all entries which have been found.
     pub fn get_entry_names(&self) -> Vec<&String> {
         self.entries.keys().collect()
@@ -58,31 +111,117 @@ where
     /// Writes the given data into the pak file on disk.
     /// Writes should happen in an aplphabetical order.
     /// Entries under 32 bytes are never compressed.
+    /// If [`PakWriter::deduplicate`] is set and the same data was already written,
+    /// the entry references the existing data instead.
     pub fn write_entry(
         &mut self,
         name: &String,
-        data: &Vec<u8>,
+        data: &[u8],
         compress: bool,
     ) -> Result<(), PakError> {
-        if self.entries.contains_key(name) {
-            return Err(PakError::double_write(name.clone()));
+        self.write_entry_with_metadata(name, data, &EntryMetadata::new(compress))
+    }
+
+    /// Writes the given data into the pak file on disk with the given entry flags and timestamp.
+    /// Use [`PakReader::get_entry_metadata`] to keep the metadata of entries from another pak.
+    ///
+    /// [`PakReader::get_entry_metadata`]: crate::pakreader::PakReader::get_entry_metadata
+    pub fn write_entry_with_metadata(
+        &mut self,
+        name: &String,
+        data: &[u8],
+        metadata: &EntryMetadata,
+    ) -> Result<(), PakError> {
+        let data_hash = match self.deduplicate {
+            true => Some((hash(data), *metadata)),
+            false => None,
+        };
+        if self.reuse_written_data(name, data_hash)? {
+            return Ok(());
         }
 
         let header = write_entry(
             &mut self.writer,
             self.pak_version,
             data,
-            compress,
+            metadata,
             &self.compression,
             self.block_size,
+            &self.compression_options,
         )?;
-        self.entries.insert(name.clone(), header);
+        self.add_written_entry(name.clone(), header, data_hash);
+
+        Ok(())
+    }
+
+    /// Write an entry which was already compressed, e.g. by a [`ParallelPakWriter`]
+    ///
+    /// `data_hash` is the hash of the uncompressed data and the metadata of the entry,
+    /// it is only used if [`PakWriter::deduplicate`] is set.
+    ///
+    /// [`ParallelPakWriter`]: crate::parallel::ParallelPakWriter
+    pub(crate) fn write_encoded_entry(
+        &mut self,
+        name: String,
+        entry: EncodedEntry<'_>,
+        data_hash: Option<([u8; 20], EntryMetadata)>,
+    ) -> Result<(), PakError> {
+        if self.reuse_written_data(&name, data_hash)? {
+            return Ok(());
+        }
+
+        let header =
+            write_encoded_entry(&mut self.writer, self.pak_version, &self.compression, entry)?;
+        self.add_written_entry(name, header, data_hash);
 
         Ok(())
     }
 
-    /// Finish writing the pak file by writing index and footer
-    pub fn finish_write(mut self) -> Result<(), PakError> {
+    /// Check that an entry wasn't written yet and let it reference already written identical data
+    ///
+    /// Returns `true` if written data was reused and the entry doesn't need to be written.
+    fn reuse_written_data(
+        &mut self,
+        name: &String,
+        data_hash: Option<([u8; 20], EntryMetadata)>,
+    ) -> Result<bool, PakError> {
+        if self.entries.contains_key(name) {
+            return Err(PakError::double_write(name.clone()));
+        }
+
+        match data_hash.and_then(|key| self.written_data.get(&key)) {
+            Some(header) => {
+                self.entries.insert(name.clone(), header.clone());
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    /// Record a written entry
+    fn add_written_entry(
+        &mut self,
+        name: String,
+        header: Header,
+        data_hash: Option<([u8; 20], EntryMetadata)>,
+    ) {
+        if let Some(key) = data_hash {
+            self.written_data.insert(key, header.clone());
+        }
+        self.entries.insert(name, header);
+    }
+
+    /// Start building a pipeline which compresses entries from multiple threads
+    /// and writes them to this pak, see [`ParallelPakWriterBuilder`]
+    pub fn parallel(self) -> ParallelPakWriterBuilder<W> {
+        ParallelPakWriterBuilder::new(self)
+    }
+
+    /// Finish writing the pak file by writing index and footer and return the underlying writer
+    ///
+    /// A writer created with [`PakWriter::create`] returns the [`AtomicFile`] which still has to be committed,
+    /// use [`PakWriter::commit`] to finish and commit in one step.
+    pub fn finish_write(mut self) -> Result<W, PakError> {
         let footer = Footer {
             pak_version: self.pak_version,
             // these are set in write_index
@@ -91,16 +230,59 @@ where
             index_hash: [0u8; 20],
             compression_methods: self.compression,
             index_encrypted: Some(false),
-            encryption_key_guid: Some([0u8; 0x10]),
+            encryption_key_guid: Some(self.encryption_key_guid),
         };
 
         let index = Index {
             mount_point: self.mount_point,
+            path_encoding: self.path_encoding,
             path_hash_seed: Some(random_path_hash_seed()),
             entries: self.entries.into_iter().collect::<Vec<_>>(),
             footer,
         };
 
-        Index::write(&mut self.writer, index)
+        Index::write(&mut self.writer, index)?;
+        Ok(self.writer)
+    }
+}
+
+impl PakWriter<AtomicFile> {
+    /// Creates a new `PakWriter` that writes to a temporary file next to `path`.
+    ///
+    /// The pak file only replaces `path` once [`PakWriter::commit`] succeeded,
+    /// if writing fails or [`PakWriter::abort`] is called the temporary file is removed
+    /// and whatever was at `path` before is left untouched.
+    pub fn create<P: AsRef<Path>>(path: P, pak_version: PakVersion) -> Result<Self, PakError> {
+        Ok(Self::new(AtomicFile::create(path)?, pak_version))
+    }
+
+    /// Creates a new `PakWriter` for the pak of a streaming install chunk in `directory`.
+    ///
+    /// The pak is named following the `pakchunk<id>-<platform>.pak` convention, see [`chunk_pak_name`].
+    pub fn create_chunk<P: AsRef<Path>>(
+        directory: P,
+        chunk_id: i32,
+        platform: &str,
+        patch: bool,
+        pak_version: PakVersion,
+    ) -> Result<Self, PakError> {
+        let path = directory
+            .as_ref()
+            .join(chunk_pak_name(chunk_id, platform, patch));
+        Self::create(path, pak_version)
+    }
+
+    /// Finish writing the pak file and move it to its destination
+    ///
+    /// The index and footer are written and synced to disk before the pak replaces its destination.
+    pub fn commit(self) -> Result<(), PakError> {
+        self.finish_write()?.commit()?;
+        Ok(())
+    }
+
+    /// Stop writing the pak file and remove everything written so far
+    pub fn abort(self) -> Result<(), PakError> {
+        self.writer.abort()?;
+        Ok(())
     }
 }
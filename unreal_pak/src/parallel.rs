@@ -0,0 +1,314 @@
+//! Multi-threaded pak writing
+//!
+//! Packing many small entries is mostly spent compressing them. A [`ParallelPakWriter`] accepts entries
+//! from any amount of producer threads through [`EntrySender`]s, compresses them in a pool of worker threads
+//! and writes them to the underlying [`PakWriter`] from a single thread.
+//!
+//! Entries are always written ordered by their name, so the written pak doesn't depend
+//! on the order entries were sent in or on how threads were scheduled.
+
+use std::borrow::Cow;
+use std::collections::btree_map::{self, BTreeMap};
+use std::io::{self, Seek, Write};
+use std::num::NonZeroUsize;
+use std::sync::mpsc::{self, Receiver, Sender, SyncSender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+
+use crate::compression::{CompressionMethods, CompressionOptions};
+use crate::entry::{encode_entry, EncodedEntry, EntryMetadata};
+use crate::error::PakError;
+use crate::hash;
+use crate::pakversion::PakVersion;
+use crate::pakwriter::PakWriter;
+
+/// Entry waiting to be compressed
+struct Job {
+    name: String,
+    data: Vec<u8>,
+    metadata: EntryMetadata,
+}
+
+/// Compressed entry with the hash used for deduplication
+type EncodedJob = (
+    String,
+    EncodedEntry<'static>,
+    Option<([u8; 20], EntryMetadata)>,
+);
+
+/// Settings of the pak needed to compress entries
+struct EncodeSettings {
+    pak_version: PakVersion,
+    compression: CompressionMethods,
+    block_size: u32,
+    options: CompressionOptions,
+    deduplicate: bool,
+}
+
+/// Builder for a [`ParallelPakWriter`]
+#[derive(Debug)]
+pub struct ParallelPakWriterBuilder<W>
+where
+    W: Write + Seek,
+{
+    pak_writer: PakWriter<W>,
+    threads: usize,
+    queue_size: Option<usize>,
+}
+
+impl<W> ParallelPakWriterBuilder<W>
+where
+    W: Write + Seek,
+{
+    /// Creates a new `ParallelPakWriterBuilder` writing to `pak_writer`
+    ///
+    /// The compression, block size, compression options and deduplication of the `PakWriter`
+    /// are used for all entries.
+    pub fn new(pak_writer: PakWriter<W>) -> Self {
+        ParallelPakWriterBuilder {
+            pak_writer,
+            threads: thread::available_parallelism()
+                .map(NonZeroUsize::get)
+                .unwrap_or(1),
+            queue_size: None,
+        }
+    }
+
+    /// Set the amount of worker threads compressing entries, defaults to the available parallelism
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Set how many entries can wait to be compressed before sending an entry blocks,
+    /// defaults to 4 entries per worker thread
+    pub fn queue_size(mut self, queue_size: usize) -> Self {
+        self.queue_size = Some(queue_size);
+        self
+    }
+
+    /// Start the worker threads
+    pub fn build(self) -> ParallelPakWriter<W> {
+        let (sender, jobs) = mpsc::sync_channel(self.queue_size.unwrap_or(self.threads * 4));
+        let (results_sender, results) = mpsc::channel();
+
+        let jobs = Arc::new(Mutex::new(jobs));
+        let settings = Arc::new(EncodeSettings {
+            pak_version: self.pak_writer.pak_version,
+            compression: self.pak_writer.compression,
+            block_size: self.pak_writer.block_size,
+            options: self.pak_writer.compression_options.clone(),
+            deduplicate: self.pak_writer.deduplicate,
+        });
+
+        let workers = (0..self.threads)
+            .map(|_| {
+                let jobs = Arc::clone(&jobs);
+                let results = results_sender.clone();
+                let settings = Arc::clone(&settings);
+                thread::spawn(move || worker(&jobs, &results, &settings))
+            })
+            .collect();
+
+        ParallelPakWriter {
+            pak_writer: self.pak_writer,
+            sender: EntrySender { sender },
+            results,
+            workers,
+        }
+    }
+}
+
+/// Compress jobs until all senders were dropped
+fn worker(
+    jobs: &Mutex<Receiver<Job>>,
+    results: &Sender<Result<EncodedJob, PakError>>,
+    settings: &EncodeSettings,
+) {
+    loop {
+        // the lock is only held while waiting for the next job
+        let job = match jobs.lock() {
+            Ok(jobs) => jobs.recv(),
+            Err(_) => return,
+        };
+        let Ok(Job {
+            name,
+            data,
+            metadata,
+        }) = job
+        else {
+            return;
+        };
+
+        let data_hash = match settings.deduplicate {
+            true => Some((hash(&data), metadata)),
+            false => None,
+        };
+        let result = encode_entry(
+            settings.pak_version,
+            Cow::Owned(data),
+            &metadata,
+            &settings.compression,
+            settings.block_size,
+            &settings.options,
+        )
+        .map(|entry| (name, entry, data_hash));
+
+        if results.send(result).is_err() {
+            return;
+        }
+    }
+}
+
+/// Sends entries to a [`ParallelPakWriter`], can be cloned and moved to other threads
+#[derive(Debug, Clone)]
+pub struct EntrySender {
+    sender: SyncSender<Job>,
+}
+
+impl EntrySender {
+    /// Send an entry to be compressed and written
+    ///
+    /// Blocks while the queue of the workers is full.
+    /// Entries under 32 bytes are never compressed.
+    pub fn write_entry(&self, name: String, data: Vec<u8>, compress: bool) -> Result<(), PakError> {
+        self.write_entry_with_metadata(name, data, EntryMetadata::new(compress))
+    }
+
+    /// Send an entry to be compressed and written with the given entry flags and timestamp
+    ///
+    /// Blocks while the queue of the workers is full.
+    pub fn write_entry_with_metadata(
+        &self,
+        name: String,
+        data: Vec<u8>,
+        metadata: EntryMetadata,
+    ) -> Result<(), PakError> {
+        self.sender
+            .send(Job {
+                name,
+                data,
+                metadata,
+            })
+            .map_err(|_| io::Error::other("Pak compression workers stopped").into())
+    }
+}
+
+/// Pak writer which compresses entries sent from multiple threads in a pool of worker threads
+///
+/// Created with [`PakWriter::parallel`]. Compressed entries are kept in memory until
+/// [`ParallelPakWriter::finish`] writes them ordered by their name.
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::BufWriter;
+/// use std::thread;
+///
+/// use unreal_pak::{pakversion::PakVersion, PakWriter};
+///
+/// let file = BufWriter::new(File::create("out.pak").unwrap());
+/// let pipeline = PakWriter::new(file, PakVersion::FnameBasedCompressionMethod)
+///     .parallel()
+///     .threads(8)
+///     .build();
+///
+/// let producers = (0..4)
+///     .map(|i| {
+///         let sender = pipeline.sender();
+///         thread::spawn(move || {
+///             sender.write_entry(format!("Game/Content/{i}.uasset"), vec![0u8; 1024], true)
+///         })
+///     })
+///     .collect::<Vec<_>>();
+/// for producer in producers {
+///     producer.join().unwrap().unwrap();
+/// }
+///
+/// pipeline.finish().unwrap().finish_write().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct ParallelPakWriter<W>
+where
+    W: Write + Seek,
+{
+    pak_writer: PakWriter<W>,
+    sender: EntrySender,
+    results: Receiver<Result<EncodedJob, PakError>>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl<W> ParallelPakWriter<W>
+where
+    W: Write + Seek,
+{
+    /// Get a new sender to send entries from another thread
+    ///
+    /// All senders have to be dropped before [`ParallelPakWriter::finish`] can return.
+    pub fn sender(&self) -> EntrySender {
+        self.sender.clone()
+    }
+
+    /// Send an entry to be compressed and written, see [`EntrySender::write_entry`]
+    pub fn write_entry(&self, name: String, data: Vec<u8>, compress: bool) -> Result<(), PakError> {
+        self.sender.write_entry(name, data, compress)
+    }
+
+    /// Send an entry to be compressed and written with the given entry flags and timestamp,
+    /// see [`EntrySender::write_entry_with_metadata`]
+    pub fn write_entry_with_metadata(
+        &self,
+        name: String,
+        data: Vec<u8>,
+        metadata: EntryMetadata,
+    ) -> Result<(), PakError> {
+        self.sender.write_entry_with_metadata(name, data, metadata)
+    }
+
+    /// Wait for all entries to be compressed and write them ordered by their name
+    ///
+    /// Blocks until all [`EntrySender`]s were dropped.
+    /// Returns the `PakWriter` to write more entries or the index with.
+    pub fn finish(self) -> Result<PakWriter<W>, PakError> {
+        let ParallelPakWriter {
+            mut pak_writer,
+            sender,
+            results,
+            workers,
+        } = self;
+        drop(sender);
+
+        let mut entries = BTreeMap::new();
+        let mut error = None;
+        // workers stop once all senders were dropped and all entries were compressed
+        for result in results {
+            match result {
+                Ok((name, entry, data_hash)) => match entries.entry(name) {
+                    btree_map::Entry::Vacant(vacant) => {
+                        vacant.insert((entry, data_hash));
+                    }
+                    btree_map::Entry::Occupied(occupied) => {
+                        error.get_or_insert_with(|| PakError::double_write(occupied.key().clone()));
+                    }
+                },
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+
+        for worker in workers {
+            worker
+                .join()
+                .map_err(|_| io::Error::other("Pak compression thread panicked"))?;
+        }
+        if let Some(err) = error {
+            return Err(err);
+        }
+
+        for (name, (entry, data_hash)) in entries {
+            pak_writer.write_encoded_entry(name, entry, data_hash)?;
+        }
+
+        Ok(pak_writer)
+    }
+}
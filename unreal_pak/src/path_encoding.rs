@@ -0,0 +1,93 @@
+//! Encoding of entry paths and the mount point in the pak index
+//!
+//! Paths are stored as FStrings, a negative length denotes a UTF-16 string.
+//! The engine writes ASCII paths as narrow strings and all other paths as UTF-16 strings,
+//! narrow paths written by other tools may contain UTF-8 or Latin-1 text instead.
+
+use std::io::{Read, Seek, Write};
+
+use byteorder::{ReadBytesExt, WriteBytesExt, LE};
+use unreal_helpers::read_ext::{decode_fstring, MAX_FSTRING_LEN};
+use unreal_helpers::UnrealWriteExt;
+
+use crate::error::PakError;
+
+/// Encoding paths are written with
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum PathEncoding {
+    /// ASCII paths are written as narrow strings, all other paths as UTF-16 strings, like the engine does
+    #[default]
+    Auto,
+    /// All paths are written as UTF-16 strings
+    Utf16,
+    /// All paths are written as narrow UTF-8 strings
+    Utf8,
+}
+
+/// Read a path, empty and null strings are both read as an empty path
+///
+/// Narrow strings which aren't valid UTF-8 are decoded as Latin-1.
+pub(crate) fn read_path<R: Read + Seek>(reader: &mut R) -> Result<String, PakError> {
+    let len = reader.read_i32::<LE>()?;
+    let (len, is_wide) = match len < 0 {
+        true => (len.checked_neg().ok_or_else(PakError::pak_invalid)?, true),
+        false => (len, false),
+    };
+    if len == 0 {
+        return Ok(String::new());
+    }
+    if len > MAX_FSTRING_LEN {
+        return Err(PakError::pak_invalid());
+    }
+
+    let char_size = match is_wide {
+        true => 2,
+        false => 1,
+    };
+    let mut data = vec![0u8; len as usize * char_size];
+    reader.read_exact(&mut data)?;
+
+    let terminator = data.split_off(data.len() - char_size);
+    if terminator.iter().any(|e| *e != 0) {
+        return Err(PakError::pak_invalid());
+    }
+
+    match is_wide {
+        true => Ok(decode_fstring(data, true)?),
+        false => Ok(String::from_utf8(data)
+            .unwrap_or_else(|e| e.into_bytes().into_iter().map(char::from).collect())),
+    }
+}
+
+/// Write a path with the given encoding
+pub(crate) fn write_path<W: Write>(
+    writer: &mut W,
+    path: &str,
+    encoding: PathEncoding,
+) -> Result<(), PakError> {
+    match encoding {
+        PathEncoding::Auto => {
+            writer.write_fstring(Some(path))?;
+        }
+        PathEncoding::Utf16 => {
+            let utf16 = path.encode_utf16().collect::<Vec<_>>();
+            if utf16.len() >= MAX_FSTRING_LEN as usize {
+                return Err(PakError::configuration_invalid());
+            }
+            writer.write_i32::<LE>(-(utf16.len() as i32) - 1)?;
+            for unit in utf16 {
+                writer.write_u16::<LE>(unit)?;
+            }
+            writer.write_u16::<LE>(0)?;
+        }
+        PathEncoding::Utf8 => {
+            if path.len() >= MAX_FSTRING_LEN as usize {
+                return Err(PakError::configuration_invalid());
+            }
+            writer.write_i32::<LE>(path.len() as i32 + 1)?;
+            writer.write_all(path.as_bytes())?;
+            writer.write_u8(0)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! Utility for streaming entries from one pak file into another
+
+use std::io::{Read, Seek, Write};
+
+use crate::error::PakError;
+use crate::pakreader::PakReader;
+use crate::pakwriter::PakWriter;
+
+/// Stream all entries of a [`PakReader`] into a [`PakWriter`].
+///
+/// Entries are read and written one at a time so the whole archive never has to be kept in memory.
+/// The `filter_map` closure is called for every entry with its name and decompressed data and
+/// decides what ends up in the new pak:
+/// - returning `Some((name, data))` writes the (optionally renamed or transformed) entry
+/// - returning `None` drops the entry
+///
+/// Entries which were compressed in the source pak are compressed again when written
+/// and their flags and timestamps are kept.
+/// The mount point of the reader is copied over to the writer.
+///
+/// The reader needs to have its index loaded already, an index loaded with [`PakReader::load_index_lazy`]
+/// is fully decoded. The writer is not finished
+/// so that additional entries can still be written before calling [`PakWriter::finish_write`].
+///
+/// # Example
+///
+/// ```no_run
+/// use std::fs::File;
+/// use std::io::{BufReader, BufWriter};
+///
+/// use unreal_pak::{pakversion::PakVersion, repack, PakReader, PakWriter};
+///
+/// let mut reader = PakReader::new(BufReader::new(File::open("in.pak").unwrap()));
+/// reader.load_index().unwrap();
+///
+/// let mut writer = PakWriter::new(
+///     BufWriter::new(File::create("out.pak").unwrap()),
+///     PakVersion::FnameBasedCompressionMethod,
+/// );
+///
+/// // drop all .uexp files and move everything else into a new folder
+/// repack(&mut reader, &mut writer, |name, data| {
+///     if name.ends_with(".uexp") {
+///         None
+///     } else {
+///         Some((format!("Repacked/{name}"), data))
+///     }
+/// })
+/// .unwrap();
+///
+/// writer.finish_write().unwrap();
+/// ```
+pub fn repack<R, W, F>(
+    reader: &mut PakReader<R>,
+    writer: &mut PakWriter<W>,
+    mut filter_map: F,
+) -> Result<(), PakError>
+where
+    R: Read + Seek,
+    W: Write + Seek,
+    F: FnMut(&String, Vec<u8>) -> Option<(String, Vec<u8>)>,
+{
+    writer.mount_point = reader.mount_point.clone();
+    writer.encryption_key_guid = reader.get_encryption_key_guid().unwrap_or_default();
+
+    reader.preload_full_index()?;
+
+    // only the names are collected here, entry data is read lazily below
+    let names = reader
+        .get_entry_names()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for name in names {
+        let metadata = reader.get_entry_metadata(&name).unwrap_or_default();
+
+        let data = reader.read_entry(&name)?;
+
+        if let Some((new_name, new_data)) = filter_map(&name, data) {
+            writer.write_entry_with_metadata(&new_name, &new_data, &metadata)?;
+        }
+    }
+
+    Ok(())
+}
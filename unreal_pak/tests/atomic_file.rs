@@ -0,0 +1,41 @@
+use std::fs;
+
+use unreal_pak::{pakversion::PakVersion, PakError, PakReader, PakWriter};
+
+#[test]
+fn finish_and_commit() -> Result<(), PakError> {
+    let directory =
+        std::env::temp_dir().join(format!("unreal_pak_atomic_test_{}", std::process::id()));
+    fs::create_dir_all(&directory)?;
+
+    let data = vec![3u8; 0x100];
+    let write_pak = |name: &str| -> Result<PakWriter<_>, PakError> {
+        let mut pak_writer = PakWriter::create(
+            directory.join(name),
+            PakVersion::FnameBasedCompressionMethod,
+        )?;
+        pak_writer.write_entry(&"Test.bin".to_string(), &data, false)?;
+        Ok(pak_writer)
+    };
+
+    // nothing is at the destination until the file is committed
+    let file = write_pak("Finished.pak")?.finish_write()?;
+    assert!(file.temp_path().exists());
+    assert!(!directory.join("Finished.pak").exists());
+    file.commit()?;
+    assert!(directory.join("Finished.pak").exists());
+
+    write_pak("Committed.pak")?.commit()?;
+    let mut pak_reader = PakReader::new(fs::File::open(directory.join("Committed.pak"))?);
+    pak_reader.load_index()?;
+    assert_eq!(pak_reader.read_entry("Test.bin")?, data);
+
+    // aborting leaves neither the destination nor the temporary file behind
+    let pak_writer = write_pak("Aborted.pak")?;
+    pak_writer.abort()?;
+    assert!(!directory.join("Aborted.pak").exists());
+    assert!(!directory.join("Aborted.pak.tmp").exists());
+
+    fs::remove_dir_all(&directory)?;
+    Ok(())
+}
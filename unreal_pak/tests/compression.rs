@@ -0,0 +1,44 @@
+use std::io::Cursor;
+
+use unreal_pak::{
+    error::PakErrorKind, pakversion::PakVersion, Compression, PakError, PakReader, PakWriter,
+};
+
+#[test]
+fn set_compression() -> Result<(), PakError> {
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+
+    let error = pak_writer
+        .set_compression(Compression::Unknown([1; 0x20]))
+        .unwrap_err();
+    assert!(matches!(
+        error.kind,
+        PakErrorKind::CompressionUnsupported(Compression::Unknown(_))
+    ));
+    assert!(matches!(
+        pak_writer
+            .set_compression(Compression::None)
+            .unwrap_err()
+            .kind,
+        PakErrorKind::ConfigurationInvalid
+    ));
+
+    // the default compression is kept after rejecting a method
+    let data = vec![7u8; 0x1000];
+    pak_writer.write_entry(&"Test.bin".to_string(), &data, true)?;
+    assert!(pak_writer.set_compression(Compression::zlib()).is_err());
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut pak_writer = PakWriter::new(&mut cursor, PakVersion::FnameBasedCompressionMethod);
+    pak_writer.write_entry(&"Test.bin".to_string(), &data, true)?;
+    pak_writer.finish_write()?;
+
+    let mut pak_reader = PakReader::new(Cursor::new(cursor.into_inner()));
+    pak_reader.load_index()?;
+    assert_eq!(pak_reader.read_entry("Test.bin")?, data);
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use std::io::Cursor;
+
+use unreal_pak::{pakversion::PakVersion, PakError, PakReader, PakWriter};
+
+fn write_pak(deduplicate: bool, entries: &[(&str, &[u8], bool)]) -> Result<Vec<u8>, PakError> {
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    pak_writer.deduplicate = deduplicate;
+
+    for (name, data, compress) in entries {
+        pak_writer.write_entry(&name.to_string(), data, *compress)?;
+    }
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+#[test]
+fn deduplicate() -> Result<(), PakError> {
+    let data = vec![5u8; 0x1000];
+    let other_data = vec![6u8; 0x1000];
+    let entries: [(&str, &[u8], bool); 5] = [
+        ("A/Data.bin", &data, false),
+        ("B/Data.bin", &data, false),
+        ("C/Data.bin", &data, true),
+        ("D/Data.bin", &data, true),
+        ("E/Data.bin", &other_data, false),
+    ];
+
+    let duplicated = write_pak(false, &entries)?;
+    let deduplicated = write_pak(true, &entries)?;
+    // one uncompressed and one compressed copy of `data` are dropped
+    assert!(deduplicated.len() + data.len() < duplicated.len());
+
+    let mut pak_reader = PakReader::new(Cursor::new(deduplicated));
+    pak_reader.load_index()?;
+    assert_eq!(pak_reader.get_entry_names().len(), entries.len());
+    for (name, data, _) in entries {
+        assert_eq!(pak_reader.read_entry(name)?, data);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,160 @@
+use std::io::Cursor;
+
+use unreal_pak::{
+    delta::DeltaEntry, error::PakErrorKind, pakversion::PakVersion, EntryMetadata, PakDelta,
+    PakError, PakReader, PakWriter,
+};
+
+fn write_pak(entries: &[(&str, &[u8])]) -> Result<PakReader<Cursor<Vec<u8>>>, PakError> {
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    for (name, data) in entries {
+        pak_writer.write_entry(&name.to_string(), data, false)?;
+    }
+
+    let mut pak_reader = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+    pak_reader.load_index()?;
+    Ok(pak_reader)
+}
+
+#[test]
+fn delta_round_trip() -> Result<(), PakError> {
+    let mut old = write_pak(&[
+        ("Unchanged.bin", &[1; 0x100]),
+        ("Changed.bin", &[2; 0x100]),
+        ("Removed.bin", &[3; 0x100]),
+    ])?;
+    let new_entries: [(&str, &[u8]); 3] = [
+        ("Added.bin", &[4; 0x100]),
+        ("Changed.bin", &[5; 0x100]),
+        ("Unchanged.bin", &[1; 0x100]),
+    ];
+    let mut new = write_pak(&new_entries)?;
+
+    let delta = PakDelta::create(&mut old, &mut new, false)?;
+    let names = delta
+        .entries
+        .iter()
+        .map(|(name, entry)| (name.as_str(), matches!(entry, DeltaEntry::Removed)))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        names,
+        [
+            ("Added.bin", false),
+            ("Changed.bin", false),
+            ("Removed.bin", true)
+        ]
+    );
+
+    let mut data = Vec::new();
+    delta.write(&mut data)?;
+    let delta = PakDelta::read(&mut Cursor::new(data))?;
+
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    delta.apply(&mut old, &mut pak_writer)?;
+
+    let mut applied = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+    applied.load_index()?;
+    assert_eq!(applied.get_entry_names().len(), new_entries.len());
+    for (name, data) in new_entries {
+        assert_eq!(applied.read_entry(name)?, data);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn delta_patch_hash_mismatch() -> Result<(), PakError> {
+    let mut old = write_pak(&[("Changed.bin", &[2; 0x100])])?;
+    let delta = PakDelta {
+        mount_point: old.mount_point.clone(),
+        entries: vec![(
+            "Changed.bin".to_string(),
+            DeltaEntry::Patch {
+                metadata: EntryMetadata::default(),
+                old_hash: [0; 20],
+                patch: Vec::new(),
+            },
+        )],
+    };
+
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    let error = delta.apply(&mut old, &mut pak_writer).unwrap_err();
+    assert!(matches!(error.kind, PakErrorKind::EntryInvalid));
+
+    Ok(())
+}
+
+fn write_pak_with_metadata(
+    pak_version: PakVersion,
+    entries: &[(&str, &[u8])],
+    metadata: &EntryMetadata,
+) -> Result<Vec<u8>, PakError> {
+    let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), pak_version);
+    for (name, data) in entries {
+        pak_writer.write_entry_with_metadata(&name.to_string(), data, metadata)?;
+    }
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+#[test]
+fn delta_keeps_metadata() -> Result<(), PakError> {
+    // flags are only stored since v3, timestamps only in v1
+    for (pak_version, metadata) in [
+        (
+            PakVersion::FnameBasedCompressionMethod,
+            EntryMetadata {
+                compress: true,
+                flags: 0x02,
+                timestamp: None,
+            },
+        ),
+        (
+            PakVersion::Initial,
+            EntryMetadata {
+                compress: false,
+                flags: 0,
+                timestamp: Some(0x01d9_8c5e_2b4a_6000),
+            },
+        ),
+    ] {
+        let old_entries: [(&str, &[u8]); 2] =
+            [("Changed.bin", &[2; 0x100]), ("Unchanged.bin", &[1; 0x100])];
+        let new_entries: [(&str, &[u8]); 3] = [
+            ("Added.bin", &[4; 0x100]),
+            ("Changed.bin", &[5; 0x100]),
+            ("Unchanged.bin", &[1; 0x100]),
+        ];
+        let old = write_pak_with_metadata(pak_version, &old_entries, &metadata)?;
+        let new = write_pak_with_metadata(pak_version, &new_entries, &metadata)?;
+
+        let mut old = PakReader::new(Cursor::new(old));
+        old.load_index()?;
+        let mut new = PakReader::new(Cursor::new(new));
+        new.load_index()?;
+
+        let mut data = Vec::new();
+        PakDelta::create(&mut old, &mut new, false)?.write(&mut data)?;
+        let delta = PakDelta::read(&mut Cursor::new(data))?;
+
+        let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), pak_version);
+        delta.apply(&mut old, &mut pak_writer)?;
+
+        let mut applied = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+        applied.load_index()?;
+        for (name, data) in new_entries {
+            assert_eq!(applied.read_entry(name)?, data);
+            assert_eq!(applied.get_entry_metadata(name), Some(metadata));
+        }
+    }
+
+    Ok(())
+}
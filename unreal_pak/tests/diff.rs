@@ -0,0 +1,54 @@
+use std::io::Cursor;
+
+use unreal_pak::{pakversion::PakVersion, PakDiff, PakError, PakReader, PakWriter};
+
+const BASELINE: [(&str, &[u8], bool); 4] = [
+    ("Changed.bin", &[1; 0x100], false),
+    ("Recompressed.bin", &[2; 0x100], false),
+    ("Removed.bin", &[3; 0x100], false),
+    ("Unchanged.bin", &[4; 0x100], false),
+];
+
+fn write_pak(entries: &[(&str, &[u8], bool)]) -> Result<PakReader<Cursor<Vec<u8>>>, PakError> {
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    for (name, data, compress) in entries {
+        pak_writer.write_entry(&name.to_string(), data, *compress)?;
+    }
+
+    let mut pak_reader = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+    pak_reader.load_index()?;
+    Ok(pak_reader)
+}
+
+#[test]
+fn diff_against() -> Result<(), PakError> {
+    let mut baseline = write_pak(&BASELINE)?;
+    let mut modified = write_pak(&[
+        ("Added.bin", &[5; 0x100], false),
+        ("Changed.bin", &[6; 0x100], false),
+        ("Recompressed.bin", &[2; 0x100], true),
+        ("Unchanged.bin", &[4; 0x100], false),
+    ])?;
+
+    let diff = modified.diff_against(&mut baseline)?;
+    assert_eq!(
+        diff,
+        PakDiff {
+            added: vec!["Added.bin".to_string()],
+            removed: vec!["Removed.bin".to_string()],
+            changed: vec!["Changed.bin".to_string(), "Recompressed.bin".to_string()],
+        }
+    );
+    assert_eq!(
+        diff.modified().collect::<Vec<_>>(),
+        ["Added.bin", "Changed.bin", "Recompressed.bin"]
+    );
+
+    let diff = write_pak(&BASELINE)?.diff_against(&mut baseline)?;
+    assert!(diff.is_empty());
+
+    Ok(())
+}
@@ -0,0 +1,77 @@
+use std::collections::HashSet;
+use std::io::Cursor;
+
+use unreal_pak::{
+    error::PakErrorKind, game_profile::MAX_WRITABLE_PAK_VERSION, pakversion::PakVersion,
+    Compression, GameProfile, PakError, PakReader, PakWriter,
+};
+
+#[test]
+fn builtin_profiles() {
+    let profiles = GameProfile::builtin();
+    let names = profiles
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<HashSet<_>>();
+    assert_eq!(names.len(), profiles.len());
+
+    assert_eq!(GameProfile::find("ue4.27"), GameProfile::engine(4, 27));
+    assert_eq!(
+        GameProfile::find("astroneer").map(|e| e.pak_version),
+        Some(PakVersion::FnameBasedCompressionMethod)
+    );
+    assert_eq!(GameProfile::find("Unknown"), None);
+    assert_eq!(GameProfile::engine(3, 0), None);
+}
+
+#[test]
+fn negotiate_pak_version() {
+    let negotiate = |profile: &GameProfile| profile.negotiate_pak_version().map_err(|e| e.kind);
+
+    // newer games get the newest version which can be written
+    assert!(matches!(
+        negotiate(&GameProfile::engine(5, 1).unwrap()),
+        Ok(MAX_WRITABLE_PAK_VERSION)
+    ));
+    assert!(matches!(
+        negotiate(&GameProfile::engine(4, 20).unwrap()),
+        Ok(PakVersion::RelativeChunkOffsets)
+    ));
+
+    let mut profile = GameProfile::engine(4, 20).unwrap();
+    profile.compression = Compression::zstd();
+    assert!(matches!(
+        negotiate(&profile),
+        Err(PakErrorKind::CompressionUnsupported(_))
+    ));
+    profile.pak_version = PakVersion::FnameBasedCompressionMethod;
+    assert!(matches!(
+        negotiate(&profile),
+        Ok(PakVersion::FnameBasedCompressionMethod)
+    ));
+
+    profile.requires_encryption = true;
+    assert!(matches!(
+        negotiate(&profile),
+        Err(PakErrorKind::EncryptionUnsupported)
+    ));
+}
+
+#[test]
+fn for_game() -> Result<(), PakError> {
+    let mut profile = GameProfile::new("Game", PakVersion::FnameBasedCompressionMethod);
+    profile.mount_point = "../../../Game/Content/".to_string();
+    profile.block_size = 0x1000;
+
+    let data = vec![3u8; 0x4000];
+    let mut pak_writer = PakWriter::for_game(Cursor::new(Vec::new()), &profile)?;
+    assert_eq!(pak_writer.block_size, profile.block_size);
+    pak_writer.write_entry(&"Test.bin".to_string(), &data, true)?;
+
+    let mut pak_reader = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+    pak_reader.load_index()?;
+    assert_eq!(pak_reader.mount_point, profile.mount_point);
+    assert_eq!(pak_reader.read_entry("Test.bin")?, data);
+
+    Ok(())
+}
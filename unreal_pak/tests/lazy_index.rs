@@ -0,0 +1,186 @@
+use std::io::Cursor;
+
+use unreal_pak::{
+    pakversion::PakVersion, repack, EntryMetadata, PakDelta, PakError, PakReader, PakWriter,
+};
+
+fn entries() -> Vec<(String, Vec<u8>)> {
+    vec![
+        ("Root.bin".to_string(), vec![1u8; 0x10]),
+        ("Maps/Planet.umap".to_string(), vec![2u8; 0x30000]),
+        (
+            "Maps/Planet.uexp".to_string(),
+            (0..0x800).map(|e| e as u8).collect(),
+        ),
+        ("Maps/Sub/Moon.umap".to_string(), vec![3u8; 0x100]),
+        ("Localization/Größe.locres".to_string(), vec![4u8; 0x40]),
+        ("Deleted.bin".to_string(), vec![5u8; 0x20]),
+    ]
+}
+
+/// Writes a v11 pak with an encoded index, the flagged entry can't be encoded
+fn write_pak(entries: &[(String, Vec<u8>)]) -> Result<Vec<u8>, PakError> {
+    let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), PakVersion::Fnv64BugFix);
+    for (name, data) in entries {
+        let metadata = EntryMetadata {
+            compress: name.ends_with(".umap"),
+            flags: match name.as_str() {
+                "Deleted.bin" => 0x02,
+                _ => 0,
+            },
+            timestamp: None,
+        };
+        pak_writer.write_entry_with_metadata(name, data, &metadata)?;
+    }
+
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+#[test]
+fn lazy_and_full_index() -> Result<(), PakError> {
+    let entries = entries();
+    let data = write_pak(&entries)?;
+
+    let mut full_reader = PakReader::new(Cursor::new(data.clone()));
+    full_reader.load_index()?;
+    let mut lazy_reader = PakReader::new(Cursor::new(data));
+    lazy_reader.load_index_lazy()?;
+
+    assert!(full_reader.is_full_index_loaded());
+    assert!(!lazy_reader.is_full_index_loaded());
+    assert!(lazy_reader.get_entry_names().is_empty());
+
+    let mut names = entries.iter().map(|(name, _)| name).collect::<Vec<_>>();
+    names.sort();
+    assert_eq!(full_reader.get_entry_names(), names);
+
+    for (name, data) in &entries {
+        assert!(lazy_reader.contains_entry(name));
+        assert_eq!(
+            lazy_reader.get_entry_metadata(name),
+            full_reader.get_entry_metadata(name)
+        );
+        assert_eq!(&lazy_reader.read_entry(name)?, data);
+        assert_eq!(&full_reader.read_entry(name)?, data);
+    }
+    assert_eq!(
+        full_reader
+            .get_entry_metadata("Deleted.bin")
+            .map(|e| e.flags),
+        Some(0x02)
+    );
+
+    let missing = "Maps/Missing.umap".to_string();
+    assert!(!lazy_reader.contains_entry(&missing));
+    assert!(lazy_reader.get_entry_metadata(&missing).is_none());
+
+    lazy_reader.preload_full_index()?;
+    assert!(lazy_reader.is_full_index_loaded());
+    assert_eq!(lazy_reader.get_entry_names(), full_reader.get_entry_names());
+
+    Ok(())
+}
+
+fn lazy_reader(data: &[u8]) -> Result<PakReader<Cursor<Vec<u8>>>, PakError> {
+    let mut pak_reader = PakReader::new(Cursor::new(data.to_vec()));
+    pak_reader.load_index_lazy()?;
+    Ok(pak_reader)
+}
+
+fn pak_writer() -> PakWriter<Cursor<Vec<u8>>> {
+    PakWriter::new(Cursor::new(Vec::new()), PakVersion::Fnv64BugFix)
+}
+
+fn read_all(pak_writer: PakWriter<Cursor<Vec<u8>>>) -> Result<Vec<(String, Vec<u8>)>, PakError> {
+    let mut pak_reader = PakReader::new(pak_writer.finish_write()?);
+    pak_reader.load_index()?;
+    let names = pak_reader
+        .get_entry_names()
+        .into_iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    names
+        .into_iter()
+        .map(|name| Ok((name.clone(), pak_reader.read_entry(&name)?)))
+        .collect()
+}
+
+#[test]
+fn repack_and_delta_from_lazy_index() -> Result<(), PakError> {
+    let mut old_entries = entries();
+    let old = write_pak(&old_entries)?;
+    old_entries.sort();
+
+    let mut repacked = pak_writer();
+    repack(&mut lazy_reader(&old)?, &mut repacked, |name, data| {
+        Some((name.clone(), data))
+    })?;
+    assert_eq!(read_all(repacked)?, old_entries);
+
+    let mut new_entries = old_entries.clone();
+    new_entries.retain(|(name, _)| name != "Root.bin");
+    new_entries[0].1 = vec![6u8; 0x40];
+    new_entries.push(("Added.bin".to_string(), vec![7u8; 0x20]));
+    let new = write_pak(&new_entries)?;
+    new_entries.sort();
+
+    let delta = PakDelta::create(&mut lazy_reader(&old)?, &mut lazy_reader(&new)?, false)?;
+    assert_eq!(delta.entries.len(), 3);
+
+    let mut applied = pak_writer();
+    delta.apply(&mut lazy_reader(&old)?, &mut applied)?;
+    assert_eq!(read_all(applied)?, new_entries);
+
+    Ok(())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+}
+
+/// Offsets of the encoded entries size, the non encoded entry count and the path hash count
+fn index_counts(data: &[u8]) -> [usize; 3] {
+    // v11 footer: magic, version, index offset
+    let index_offset =
+        u64::from_le_bytes(data[data.len() - 0xcc + 8..][..8].try_into().unwrap()) as usize;
+    // mount point, entry count, seed
+    let mut offset = index_offset + 4 + read_u32(data, index_offset) + 4 + 8;
+
+    let path_hash_index = u64::from_le_bytes(data[offset + 4..][..8].try_into().unwrap()) as usize;
+    // path hash and full directory index locations
+    offset += 2 * (4 + 8 + 8 + 20);
+
+    let encoded_size = offset;
+    let non_encoded_count = offset + 4 + read_u32(data, offset);
+    [encoded_size, non_encoded_count, path_hash_index]
+}
+
+#[test]
+fn corrupted_index_counts() -> Result<(), PakError> {
+    let data = write_pak(&entries())?;
+
+    let [encoded_size, non_encoded_count, path_hash_count] = index_counts(&data);
+    let corrupt = |offset: usize| {
+        let mut data = data.clone();
+        data[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+        data
+    };
+
+    for offset in [encoded_size, non_encoded_count] {
+        assert!(PakReader::new(Cursor::new(corrupt(offset)))
+            .load_index()
+            .is_err());
+    }
+    // the path hash index is only read when loading lazily
+    let mut pak_reader = PakReader::new(Cursor::new(corrupt(path_hash_count)));
+    pak_reader.load_index()?;
+    assert_eq!(pak_reader.get_entry_names().len(), 6);
+
+    for offset in [encoded_size, non_encoded_count, path_hash_count] {
+        assert!(PakReader::new(Cursor::new(corrupt(offset)))
+            .load_index_lazy()
+            .is_err());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::io::Cursor;
+
+use unreal_pak::{
+    mount_point::{
+        canonicalize_path, engine_path_to_entry, entry_to_engine_path, entry_to_object_path,
+        normalize_mount_point, object_path_to_entry,
+    },
+    pakversion::PakVersion,
+    PakError, PakReader, PakWriter,
+};
+
+#[test]
+fn paths() {
+    assert_eq!(
+        canonicalize_path("../../../Game\\.//Content/"),
+        "Game/Content"
+    );
+    assert_eq!(canonicalize_path("A/B/../C"), "A/C");
+    assert_eq!(normalize_mount_point("../../../"), "");
+    assert_eq!(
+        normalize_mount_point("../../../GameName/Content"),
+        "GameName/Content/"
+    );
+
+    let mount_point = "../../../GameName/Content/";
+    assert_eq!(
+        entry_to_engine_path(mount_point, "Maps/Planet.umap"),
+        "GameName/Content/Maps/Planet.umap"
+    );
+    assert_eq!(
+        engine_path_to_entry(mount_point, "gamename/content/Maps/Planet.umap").as_deref(),
+        Some("Maps/Planet.umap")
+    );
+    assert_eq!(
+        engine_path_to_entry(mount_point, "Engine/Content/A.uasset"),
+        None
+    );
+}
+
+#[test]
+fn object_paths() {
+    for (mount_point, entry_name) in [
+        ("../../../", "GameName/Content/Maps/Planet.umap"),
+        ("../../../GameName/", "Content/Maps/Planet.umap"),
+        ("../../../GameName/Content/", "Maps/Planet.umap"),
+    ] {
+        assert_eq!(
+            entry_to_object_path("GameName", mount_point, entry_name).as_deref(),
+            Some("/Game/Maps/Planet")
+        );
+        assert_eq!(
+            object_path_to_entry("GameName", mount_point, "/Game/Maps/Planet.Planet", "umap")
+                .as_deref(),
+            Some(entry_name)
+        );
+    }
+
+    assert_eq!(
+        entry_to_object_path("GameName", "../../../", "Engine/Content/Font.uasset").as_deref(),
+        Some("/Engine/Font")
+    );
+    assert_eq!(
+        entry_to_object_path("GameName", "../../../", "GameName/Config/Game.ini"),
+        None
+    );
+    assert_eq!(
+        object_path_to_entry(
+            "GameName",
+            "../../../GameName/Content/",
+            "/Engine/Font",
+            "uasset"
+        ),
+        None
+    );
+}
+
+#[test]
+fn pak_object_paths() -> Result<(), PakError> {
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    pak_writer.mount_point = "../../../GameName/Content/".to_string();
+    for name in ["Maps/Planet.umap", "Maps/Planet.uexp", "Items/Rock.uasset"] {
+        pak_writer.write_entry(&name.to_string(), &[0; 4], false)?;
+    }
+
+    let mut pak_reader = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+    pak_reader.load_index()?;
+
+    let mut object_paths = pak_reader.get_object_paths("GameName")?;
+    object_paths.sort();
+    assert_eq!(object_paths, ["/Game/Items/Rock", "/Game/Maps/Planet"]);
+    assert_eq!(
+        pak_reader
+            .find_object_entry("GameName", "/Game/Maps/Planet", "uexp")
+            .as_deref(),
+        Some("Maps/Planet.uexp")
+    );
+    assert_eq!(
+        pak_reader.find_object_entry("GameName", "/Game/Items/Rock", "uexp"),
+        None
+    );
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use std::io::Cursor;
+use std::thread;
+
+use unreal_pak::{error::PakErrorKind, pakversion::PakVersion, PakError, PakReader, PakWriter};
+
+/// Entries with compressible, incompressible, tiny and duplicated data
+fn entries() -> Vec<(String, Vec<u8>)> {
+    let mut entries = (0..32)
+        .map(|i| {
+            let data = match i % 4 {
+                0 => vec![i as u8; 0x20000],
+                1 => (0..0x3000u32).map(|e| (e * 7919 % 251) as u8).collect(),
+                2 => vec![i as u8; 0x10],
+                _ => vec![0xaa; 0x1000],
+            };
+            (format!("Game/Content/{i:02}.uasset"), data)
+        })
+        .collect::<Vec<_>>();
+    // sent out of order, the parallel writer has to sort them
+    entries.reverse();
+    entries
+}
+
+fn pak_writer(pak_version: PakVersion) -> PakWriter<Cursor<Vec<u8>>> {
+    let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), pak_version);
+    pak_writer.deduplicate = true;
+    pak_writer
+}
+
+fn write_sequential(pak_version: PakVersion) -> Result<Vec<u8>, PakError> {
+    let mut entries = entries();
+    entries.sort();
+
+    let mut pak_writer = pak_writer(pak_version);
+    for (name, data) in &entries {
+        pak_writer.write_entry(name, data, true)?;
+    }
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+fn write_parallel(pak_version: PakVersion) -> Result<Vec<u8>, PakError> {
+    let pipeline = pak_writer(pak_version).parallel().threads(4).build();
+
+    let producers = entries()
+        .chunks(8)
+        .map(|chunk| {
+            let sender = pipeline.sender();
+            let chunk = chunk.to_vec();
+            thread::spawn(move || -> Result<(), PakError> {
+                for (name, data) in chunk {
+                    sender.write_entry(name, data, true)?;
+                }
+                Ok(())
+            })
+        })
+        .collect::<Vec<_>>();
+    for producer in producers {
+        producer.join().unwrap()?;
+    }
+
+    Ok(pipeline.finish()?.finish_write()?.into_inner())
+}
+
+#[test]
+fn same_output_as_sequential() -> Result<(), PakError> {
+    let pak_version = PakVersion::FnameBasedCompressionMethod;
+    assert_eq!(write_parallel(pak_version)?, write_sequential(pak_version)?);
+    Ok(())
+}
+
+#[test]
+fn readable_like_sequential() -> Result<(), PakError> {
+    // v10+ paks use a random path hash seed, so they can only be compared by reading them
+    let mut parallel = PakReader::new(Cursor::new(write_parallel(PakVersion::Fnv64BugFix)?));
+    parallel.load_index()?;
+    let mut sequential = PakReader::new(Cursor::new(write_sequential(PakVersion::Fnv64BugFix)?));
+    sequential.load_index()?;
+
+    assert_eq!(parallel.get_entry_names(), sequential.get_entry_names());
+    for (name, data) in entries() {
+        assert_eq!(parallel.read_entry(&name)?, data);
+        assert_eq!(
+            parallel.get_entry_metadata(&name),
+            sequential.get_entry_metadata(&name)
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn double_write() {
+    let pipeline = pak_writer(PakVersion::FnameBasedCompressionMethod)
+        .parallel()
+        .build();
+    pipeline
+        .write_entry("Test.bin".to_string(), vec![1u8; 0x100], true)
+        .unwrap();
+    pipeline
+        .write_entry("Test.bin".to_string(), vec![2u8; 0x100], true)
+        .unwrap();
+
+    assert!(matches!(
+        pipeline.finish().map_err(|e| e.kind),
+        Err(PakErrorKind::DoubleWrite(_))
+    ));
+}
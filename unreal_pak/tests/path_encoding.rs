@@ -0,0 +1,67 @@
+use std::io::Cursor;
+
+use unreal_pak::{pakversion::PakVersion, PakError, PakReader, PakWriter, PathEncoding};
+
+const ASCII_NAME: &str = "Maps/Planet.umap";
+const WIDE_NAME: &str = "Maps/Étoile_星.umap";
+
+fn write_pak(path_encoding: PathEncoding, names: &[&str]) -> Result<Vec<u8>, PakError> {
+    let mut pak_writer = PakWriter::new(
+        Cursor::new(Vec::new()),
+        PakVersion::FnameBasedCompressionMethod,
+    );
+    pak_writer.path_encoding = path_encoding;
+    for name in names {
+        pak_writer.write_entry(&name.to_string(), name.as_bytes(), false)?;
+    }
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+fn read_names(data: Vec<u8>) -> Result<Vec<String>, PakError> {
+    let mut pak_reader = PakReader::new(Cursor::new(data));
+    pak_reader.load_index()?;
+    Ok(pak_reader.get_entry_names().into_iter().cloned().collect())
+}
+
+fn contains(data: &[u8], needle: &[u8]) -> bool {
+    data.windows(needle.len()).any(|e| e == needle)
+}
+
+fn utf16(path: &str) -> Vec<u8> {
+    path.encode_utf16().flat_map(u16::to_le_bytes).collect()
+}
+
+#[test]
+fn path_encodings() -> Result<(), PakError> {
+    for path_encoding in [PathEncoding::Auto, PathEncoding::Utf16, PathEncoding::Utf8] {
+        let data = write_pak(path_encoding, &[ASCII_NAME, WIDE_NAME])?;
+
+        // entry data is the narrow name, so UTF-16 names can only be found in the index
+        let (ascii_wide, wide_wide) = match path_encoding {
+            PathEncoding::Auto => (false, true),
+            PathEncoding::Utf16 => (true, true),
+            PathEncoding::Utf8 => (false, false),
+        };
+        assert_eq!(contains(&data, &utf16(ASCII_NAME)), ascii_wide);
+        assert_eq!(contains(&data, &utf16(WIDE_NAME)), wide_wide);
+
+        assert_eq!(read_names(data)?, [ASCII_NAME, WIDE_NAME]);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn latin1_paths() -> Result<(), PakError> {
+    let mut data = write_pak(PathEncoding::Utf8, &["Cafe.bin"])?;
+
+    // replace the `e` in the index with a Latin-1 `é`, which is not valid UTF-8
+    let position = data
+        .windows(9)
+        .rposition(|e| e == b"Cafe.bin\0")
+        .expect("path not found in the index");
+    data[position + 3] = 0xe9;
+
+    assert_eq!(read_names(data)?, ["Café.bin"]);
+    Ok(())
+}
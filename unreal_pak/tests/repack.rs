@@ -0,0 +1,65 @@
+use std::io::Cursor;
+
+use unreal_pak::{pakversion::PakVersion, repack, PakError, PakReader, PakWriter};
+
+const PAK_VERSION: PakVersion = PakVersion::FnameBasedCompressionMethod;
+const MOUNT_POINT: &str = "../../../GameName/Content/";
+
+fn write_pak() -> Result<Vec<u8>, PakError> {
+    let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), PAK_VERSION);
+    pak_writer.mount_point = MOUNT_POINT.to_string();
+    for (name, data, compress) in [
+        ("Maps/Planet.umap", vec![1u8; 0x1000], true),
+        ("Maps/Planet.uexp", vec![2u8; 0x100], false),
+        ("Items/Rock.uasset", vec![3u8; 0x40], false),
+    ] {
+        pak_writer.write_entry(&name.to_string(), &data, compress)?;
+    }
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+#[test]
+fn repack_entries() -> Result<(), PakError> {
+    let mut pak_reader = PakReader::new(Cursor::new(write_pak()?));
+    pak_reader.load_index()?;
+
+    let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), PAK_VERSION);
+    let mut seen = Vec::new();
+    repack(&mut pak_reader, &mut pak_writer, |name, data| {
+        seen.push(name.clone());
+        match name.as_str() {
+            "Maps/Planet.uexp" => None,
+            "Items/Rock.uasset" => Some((
+                "Items/Stone.uasset".to_string(),
+                data.into_iter().map(|e| e + 1).collect(),
+            )),
+            _ => Some((name.clone(), data)),
+        }
+    })?;
+    // the writer isn't finished, so more entries can be added
+    pak_writer.write_entry(&"Extra.bin".to_string(), &[4u8; 0x10], false)?;
+
+    assert_eq!(
+        seen,
+        ["Items/Rock.uasset", "Maps/Planet.uexp", "Maps/Planet.umap"]
+    );
+
+    let mut repacked = PakReader::new(Cursor::new(pak_writer.finish_write()?.into_inner()));
+    repacked.load_index()?;
+
+    assert_eq!(repacked.mount_point, MOUNT_POINT);
+    assert_eq!(
+        repacked.get_entry_names(),
+        ["Extra.bin", "Items/Stone.uasset", "Maps/Planet.umap"]
+    );
+    assert_eq!(repacked.read_entry("Items/Stone.uasset")?, [4u8; 0x40]);
+    assert_eq!(repacked.read_entry("Maps/Planet.umap")?, [1u8; 0x1000]);
+    assert!(repacked
+        .get_entry_metadata("Maps/Planet.umap")
+        .is_some_and(|e| e.compress));
+    assert!(repacked
+        .get_entry_metadata("Items/Stone.uasset")
+        .is_some_and(|e| !e.compress));
+
+    Ok(())
+}
@@ -0,0 +1,161 @@
+use std::io::Cursor;
+
+use unreal_pak::{pakversion::PakVersion, PakError, PakReader, PakWriter, SalvagedEntry};
+
+const PAK_VERSION: PakVersion = PakVersion::FnameBasedCompressionMethod;
+
+type MemoryPakReader = PakReader<Cursor<Vec<u8>>>;
+
+fn entries() -> Vec<(String, Vec<u8>)> {
+    vec![
+        ("First.bin".to_string(), vec![1u8; 0x1000]),
+        ("Second.umap".to_string(), vec![2u8; 0x30000]),
+        (
+            "Third.bin".to_string(),
+            (0..0x2000).map(|e| e as u8).collect(),
+        ),
+    ]
+}
+
+fn write_pak() -> Result<Vec<u8>, PakError> {
+    write_pak_version(PAK_VERSION)
+}
+
+fn write_pak_version(pak_version: PakVersion) -> Result<Vec<u8>, PakError> {
+    let mut pak_writer = PakWriter::new(Cursor::new(Vec::new()), pak_version);
+    for (name, data) in entries() {
+        pak_writer.write_entry(&name, &data, name.ends_with(".umap"))?;
+    }
+    Ok(pak_writer.finish_write()?.into_inner())
+}
+
+fn salvage(data: Vec<u8>) -> Result<(MemoryPakReader, Vec<SalvagedEntry>), PakError> {
+    let mut pak_reader = PakReader::new(Cursor::new(data));
+    let salvaged = pak_reader.load_index_salvage(PAK_VERSION)?;
+    Ok((pak_reader, salvaged))
+}
+
+#[test]
+fn intact_index() -> Result<(), PakError> {
+    let (mut pak_reader, salvaged) = salvage(write_pak()?)?;
+
+    assert_eq!(salvaged.len(), 3);
+    assert!(salvaged.iter().all(|e| e.in_index && e.verified));
+    for (name, data) in entries() {
+        assert_eq!(pak_reader.read_entry(&name)?, data);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn truncated_pak() -> Result<(), PakError> {
+    let data = write_pak()?;
+    let (_, intact) = salvage(data.clone())?;
+    let offsets = intact.iter().map(|e| e.offset).collect::<Vec<_>>();
+
+    // cut off in the middle of the data of the last entry, which also removes the index
+    let (mut pak_reader, salvaged) = salvage(data[..offsets[2] as usize + 0x1000].to_vec())?;
+
+    let names = offsets
+        .iter()
+        .map(|e| format!("salvaged/{e:016x}"))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        salvaged.iter().map(|e| &e.name).collect::<Vec<_>>(),
+        names.iter().collect::<Vec<_>>()
+    );
+    assert!(salvaged.iter().all(|e| !e.in_index));
+    assert_eq!(
+        salvaged.iter().map(|e| e.verified).collect::<Vec<_>>(),
+        [true, true, false]
+    );
+
+    let entries = entries();
+    assert_eq!(pak_reader.read_entry(&names[0])?, entries[0].1);
+    assert_eq!(pak_reader.read_entry(&names[1])?, entries[1].1);
+
+    Ok(())
+}
+
+#[test]
+fn damaged_entries() -> Result<(), PakError> {
+    let data = write_pak()?;
+    let (_, intact) = salvage(data.clone())?;
+    let offsets = intact.iter().map(|e| e.offset).collect::<Vec<_>>();
+    let index_offset = offsets[2] as usize + 0x1000;
+
+    // damaged data of an entry is kept, the entry after it is still found
+    let mut damaged_data = data[..index_offset].to_vec();
+    damaged_data[offsets[1] as usize + 0x100] ^= 0xff;
+    let (_, salvaged) = salvage(damaged_data)?;
+    assert_eq!(
+        salvaged
+            .iter()
+            .map(|e| (e.offset, e.verified))
+            .collect::<Vec<_>>(),
+        [(offsets[0], true), (offsets[1], false), (offsets[2], false)]
+    );
+
+    // after a damaged header scanning continues with the next intact entry
+    let mut damaged_header = data[..index_offset].to_vec();
+    damaged_header[0] = 0xff;
+    let (mut pak_reader, salvaged) = salvage(damaged_header)?;
+    assert_eq!(
+        salvaged
+            .iter()
+            .map(|e| (e.offset, e.verified))
+            .collect::<Vec<_>>(),
+        [(offsets[1], true), (offsets[2], false)]
+    );
+    assert_eq!(pak_reader.read_entry(&salvaged[0].name)?, entries()[1].1);
+
+    Ok(())
+}
+
+fn read_u32(data: &[u8], offset: usize) -> usize {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()) as usize
+}
+
+fn read_u64(data: &[u8], offset: usize) -> usize {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap()) as usize
+}
+
+/// Offset of the entry count of the index, or of the encoded entries size for v10+ paks
+fn index_count_offset(data: &[u8], pak_version: PakVersion) -> usize {
+    let footer = [0x2c, 0xac, 0xcc, 0xcd]
+        .into_iter()
+        .map(|e| data.len() - e)
+        .find(|e| data[*e..*e + 4] == [0xe1, 0x12, 0x6f, 0x5a])
+        .unwrap();
+    let index_offset = read_u64(data, footer + 8);
+
+    let entry_count = index_offset + 4 + read_u32(data, index_offset);
+    match pak_version >= PakVersion::PathHashIndex {
+        // seed and path hash and full directory index locations
+        true => entry_count + 4 + 8 + 2 * (4 + 8 + 8 + 20),
+        false => entry_count,
+    }
+}
+
+#[test]
+fn corrupted_index_count() -> Result<(), PakError> {
+    for pak_version in [
+        PakVersion::FnameBasedCompressionMethod,
+        PakVersion::Fnv64BugFix,
+    ] {
+        let mut data = write_pak_version(pak_version)?;
+        let offset = index_count_offset(&data, pak_version);
+        data[offset..offset + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        // the index can't be read, all entries are found by scanning
+        let (mut pak_reader, salvaged) = salvage(data)?;
+        assert_eq!(salvaged.len(), 3);
+        assert!(salvaged.iter().all(|e| !e.in_index && e.verified));
+        for (entry, (_, data)) in salvaged.iter().zip(entries()) {
+            assert_eq!(pak_reader.read_entry(&entry.name)?, data);
+        }
+    }
+
+    Ok(())
+}
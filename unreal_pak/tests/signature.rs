@@ -0,0 +1,64 @@
+use std::io::Cursor;
+
+use byteorder::{WriteBytesExt, LE};
+use unreal_pak::{error::PakErrorKind, manifest::PakSignature, PakError};
+
+/// Signature file with a 4 byte encrypted hash and no chunk hashes, but the given lengths
+fn signature_data(encrypted_hash_len: i32, chunk_count: i32) -> Vec<u8> {
+    let mut data = Vec::new();
+    data.write_u32::<LE>(0x73832daa).unwrap();
+    data.write_u32::<LE>(1).unwrap();
+    data.write_i32::<LE>(encrypted_hash_len).unwrap();
+    data.extend([1u8; 4]);
+    data.write_i32::<LE>(chunk_count).unwrap();
+    data
+}
+
+fn is_invalid(result: Result<PakSignature, PakError>) -> bool {
+    matches!(
+        result,
+        Err(PakError {
+            kind: PakErrorKind::PakInvalid
+        })
+    )
+}
+
+#[test]
+fn round_trip() -> Result<(), PakError> {
+    let mut signature = PakSignature::from_pak(&mut Cursor::new(vec![5u8; 0x18000]))?;
+    assert_eq!(signature.chunk_hashes.len(), 2);
+    signature.sign(|hash| Ok(hash.to_vec()))?;
+
+    let mut data = Vec::new();
+    signature.write(&mut data)?;
+    assert_eq!(PakSignature::read(&mut Cursor::new(data))?, signature);
+    Ok(())
+}
+
+#[test]
+fn untrusted_lengths() {
+    // lengths larger than the remaining stream are rejected before allocating
+    let data = signature_data(i32::MAX, 0);
+    assert!(is_invalid(PakSignature::read(&mut Cursor::new(data))));
+
+    let data = signature_data(4, i32::MAX);
+    assert!(is_invalid(PakSignature::read(&mut Cursor::new(data))));
+
+    let mut data = signature_data(4, 3);
+    data.extend([0u8; 8]);
+    assert!(is_invalid(PakSignature::read(&mut Cursor::new(data))));
+
+    let data = signature_data(4, 0);
+    assert!(PakSignature::read(&mut Cursor::new(data)).is_ok());
+
+    let data = signature_data(-1, 0);
+    assert!(is_invalid(PakSignature::read(&mut Cursor::new(data))));
+
+    let data = signature_data(4, -1);
+    assert!(is_invalid(PakSignature::read(&mut Cursor::new(data))));
+
+    let data = signature_data(0, 0);
+    assert!(is_invalid(PakSignature::read(&mut Cursor::new(
+        data[..10].to_vec()
+    ))));
+}